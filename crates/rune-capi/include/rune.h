@@ -0,0 +1,61 @@
+#ifndef RUNE_H
+#define RUNE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A context with the default modules installed. */
+typedef struct rune_context rune_context;
+/* A collection of sources to compile. */
+typedef struct rune_sources rune_sources;
+/* A compiled unit. */
+typedef struct rune_unit rune_unit;
+/* A virtual machine which can call functions in a unit. */
+typedef struct rune_vm rune_vm;
+/* A value passed to or returned from a virtual machine. */
+typedef struct rune_value rune_value;
+/* An error raised while compiling or calling a function. */
+typedef struct rune_error rune_error;
+
+rune_context *rune_context_new(void);
+void rune_context_free(rune_context *context);
+
+rune_sources *rune_sources_new(void);
+bool rune_sources_insert(rune_sources *sources, const char *name, const char *source);
+void rune_sources_free(rune_sources *sources);
+
+rune_unit *rune_unit_build(const rune_context *context, rune_sources *sources, rune_error **error);
+void rune_unit_free(rune_unit *unit);
+
+rune_vm *rune_vm_new(const rune_context *context, const rune_unit *unit);
+rune_value *rune_vm_call(rune_vm *vm, const char *path, const rune_value *const *args, size_t len, rune_error **error);
+void rune_vm_free(rune_vm *vm);
+
+const char *rune_error_message(const rune_error *error);
+void rune_error_free(rune_error *error);
+
+rune_value *rune_value_unit(void);
+rune_value *rune_value_bool(bool value);
+rune_value *rune_value_integer(int64_t value);
+rune_value *rune_value_float(double value);
+rune_value *rune_value_string(const char *value);
+bool rune_value_is_unit(const rune_value *value);
+bool rune_value_as_bool(const rune_value *value, bool *out);
+bool rune_value_as_integer(const rune_value *value, int64_t *out);
+bool rune_value_as_float(const rune_value *value, double *out);
+char *rune_value_as_string(const rune_value *value);
+char *rune_value_debug(const rune_value *value);
+void rune_value_free(rune_value *value);
+
+void rune_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RUNE_H */
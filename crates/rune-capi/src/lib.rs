@@ -0,0 +1,472 @@
+//! <img alt="rune logo" src="https://raw.githubusercontent.com/rune-rs/rune/main/assets/icon.png" />
+//! <br>
+//! <a href="https://rune-rs.github.io"><b>Visit the site 🌐</b></a>
+//! &mdash;
+//! <a href="https://rune-rs.github.io/book/"><b>Read the book 📖</b></a>
+//! <br>
+//! <br>
+//!
+//! A C API for embedding the Rune Language, an embeddable dynamic programming
+//! language for Rust.
+//!
+//! <br>
+//!
+//! ## Usage
+//!
+//! This is part of the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! The crate builds a static and a dynamic library, with the declarations in
+//! `include/rune.h`. Every object is an opaque handle which is allocated by
+//! a `rune_*_new` or similar function and has to be released with the
+//! matching `rune_*_free` function. Strings passed in are NUL-terminated
+//! UTF-8.
+//!
+//! ```c
+//! #include <stdio.h>
+//! #include "rune.h"
+//!
+//! int main() {
+//!     rune_context *context = rune_context_new();
+//!     rune_sources *sources = rune_sources_new();
+//!     rune_sources_insert(sources, "entry", "pub fn add(a, b) { a + b }");
+//!
+//!     rune_error *error = NULL;
+//!     rune_unit *unit = rune_unit_build(context, sources, &error);
+//!
+//!     if (unit == NULL) {
+//!         printf("%s\n", rune_error_message(error));
+//!         rune_error_free(error);
+//!         return 1;
+//!     }
+//!
+//!     rune_vm *vm = rune_vm_new(context, unit);
+//!     const rune_value *args[] = {rune_value_integer(1), rune_value_integer(2)};
+//!     rune_value *output = rune_vm_call(vm, "add", args, 2, &error);
+//!
+//!     int64_t n = 0;
+//!
+//!     if (output != NULL && rune_value_as_integer(output, &n)) {
+//!         printf("%lld\n", (long long)n);
+//!     }
+//!
+//!     rune_value_free(output);
+//!     rune_value_free((rune_value *)args[0]);
+//!     rune_value_free((rune_value *)args[1]);
+//!     rune_vm_free(vm);
+//!     rune_unit_free(unit);
+//!     rune_sources_free(sources);
+//!     rune_context_free(context);
+//!     return 0;
+//! }
+//! ```
+
+#![allow(non_camel_case_types)]
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+use std::sync::Arc;
+
+use rune::runtime::{RuntimeContext, Value};
+use rune::termcolor::Buffer;
+use rune::{Context, Diagnostics, FromValue, Source, Sources, Unit, Vm};
+
+/// A context with the default modules installed.
+pub struct rune_context {
+    context: Context,
+    runtime: Arc<RuntimeContext>,
+}
+
+/// A collection of sources to compile.
+pub struct rune_sources {
+    sources: Sources,
+}
+
+/// A compiled unit.
+pub struct rune_unit {
+    unit: Arc<Unit>,
+}
+
+/// A virtual machine which can call functions in a unit.
+pub struct rune_vm {
+    vm: Vm,
+}
+
+/// A value passed to or returned from a virtual machine.
+pub struct rune_value {
+    value: Value,
+}
+
+/// An error raised while compiling or calling a function.
+pub struct rune_error {
+    message: CString,
+}
+
+impl rune_error {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: to_c_string(message.into()),
+        }
+    }
+}
+
+/// Convert a string into a C string, escaping any interior NUL bytes.
+fn to_c_string(string: String) -> CString {
+    match CString::new(string) {
+        Ok(string) => string,
+        Err(e) => {
+            let string = String::from_utf8_lossy(&e.into_vec()).replace('\0', "\\0");
+            CString::new(string).unwrap_or_default()
+        }
+    }
+}
+
+/// Store the given error in `out` if it's non-null.
+unsafe fn set_error(out: *mut *mut rune_error, message: impl Into<String>) {
+    if !out.is_null() {
+        *out = Box::into_raw(Box::new(rune_error::new(message)));
+    }
+}
+
+/// Convert a NUL-terminated string into a `&str`.
+unsafe fn to_str<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+
+    CStr::from_ptr(string).to_str().ok()
+}
+
+/// Release an object allocated through `Box::into_raw`.
+unsafe fn free<T>(value: *mut T) {
+    if !value.is_null() {
+        drop(Box::from_raw(value));
+    }
+}
+
+/// Construct a context with the default modules installed, including the
+/// modules from `rune-modules` which are enabled.
+///
+/// Returns NULL if the context couldn't be constructed.
+#[no_mangle]
+pub extern "C" fn rune_context_new() -> *mut rune_context {
+    let context = match rune_modules::default_context() {
+        Ok(context) => context,
+        Err(..) => return ptr::null_mut(),
+    };
+
+    let runtime = Arc::new(context.runtime());
+    Box::into_raw(Box::new(rune_context { context, runtime }))
+}
+
+/// Free a context.
+#[no_mangle]
+pub unsafe extern "C" fn rune_context_free(context: *mut rune_context) {
+    free(context);
+}
+
+/// Construct an empty collection of sources.
+#[no_mangle]
+pub extern "C" fn rune_sources_new() -> *mut rune_sources {
+    Box::into_raw(Box::new(rune_sources {
+        sources: Sources::new(),
+    }))
+}
+
+/// Insert a source with the given name.
+///
+/// Returns false if any argument is NULL or isn't valid UTF-8.
+#[no_mangle]
+pub unsafe extern "C" fn rune_sources_insert(
+    sources: *mut rune_sources,
+    name: *const c_char,
+    source: *const c_char,
+) -> bool {
+    match (sources.as_mut(), to_str(name), to_str(source)) {
+        (Some(sources), Some(name), Some(source)) => {
+            sources.sources.insert(Source::new(name, source));
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Free a collection of sources.
+#[no_mangle]
+pub unsafe extern "C" fn rune_sources_free(sources: *mut rune_sources) {
+    free(sources);
+}
+
+/// Compile the given sources into a unit.
+///
+/// Returns NULL on failure, in which case the diagnostics are stored in
+/// `error` unless it is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn rune_unit_build(
+    context: *const rune_context,
+    sources: *mut rune_sources,
+    error: *mut *mut rune_error,
+) -> *mut rune_unit {
+    let (context, sources) = match (context.as_ref(), sources.as_mut()) {
+        (Some(context), Some(sources)) => (context, sources),
+        _ => {
+            set_error(error, "context and sources must not be NULL");
+            return ptr::null_mut();
+        }
+    };
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = rune::prepare(&mut sources.sources)
+        .with_context(&context.context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    match result {
+        Ok(unit) => Box::into_raw(Box::new(rune_unit {
+            unit: Arc::new(unit),
+        })),
+        Err(e) => {
+            let mut out = Buffer::no_color();
+
+            let message = match diagnostics.emit(&mut out, &sources.sources) {
+                Ok(()) => String::from_utf8_lossy(out.as_slice()).into_owned(),
+                Err(..) => e.to_string(),
+            };
+
+            set_error(error, message);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Free a unit.
+#[no_mangle]
+pub unsafe extern "C" fn rune_unit_free(unit: *mut rune_unit) {
+    free(unit);
+}
+
+/// Construct a virtual machine for the given unit.
+///
+/// Returns NULL if any argument is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn rune_vm_new(
+    context: *const rune_context,
+    unit: *const rune_unit,
+) -> *mut rune_vm {
+    match (context.as_ref(), unit.as_ref()) {
+        (Some(context), Some(unit)) => {
+            let vm = Vm::new(context.runtime.clone(), unit.unit.clone());
+            Box::into_raw(Box::new(rune_vm { vm }))
+        }
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Free a virtual machine.
+#[no_mangle]
+pub unsafe extern "C" fn rune_vm_free(vm: *mut rune_vm) {
+    free(vm);
+}
+
+/// Call the function at the given path, like `main` or `foo::bar`, with
+/// `len` arguments.
+///
+/// The arguments are not consumed. Returns the value returned by the
+/// function, or NULL on failure in which case the error is stored in `error`
+/// unless it is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn rune_vm_call(
+    vm: *mut rune_vm,
+    path: *const c_char,
+    args: *const *const rune_value,
+    len: usize,
+    error: *mut *mut rune_error,
+) -> *mut rune_value {
+    let vm = match vm.as_mut() {
+        Some(vm) => vm,
+        None => {
+            set_error(error, "vm must not be NULL");
+            return ptr::null_mut();
+        }
+    };
+
+    let path = match to_str(path) {
+        Some(path) => path,
+        None => {
+            set_error(error, "path must be a valid UTF-8 string");
+            return ptr::null_mut();
+        }
+    };
+
+    let mut arguments = Vec::with_capacity(len);
+
+    for n in 0..len {
+        match (*args.add(n)).as_ref() {
+            Some(arg) => arguments.push(arg.value.clone()),
+            None => {
+                set_error(error, format!("argument #{} is NULL", n));
+                return ptr::null_mut();
+            }
+        }
+    }
+
+    let path = path.split("::").collect::<Vec<_>>();
+
+    let result = match vm.vm.execute(&path[..], arguments) {
+        Ok(mut execution) => execution.complete(),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(value) => rune_value::into_raw(value),
+        Err(e) => {
+            set_error(error, e.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Get the message of an error. The string is owned by the error.
+#[no_mangle]
+pub unsafe extern "C" fn rune_error_message(error: *const rune_error) -> *const c_char {
+    match error.as_ref() {
+        Some(error) => error.message.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Free an error.
+#[no_mangle]
+pub unsafe extern "C" fn rune_error_free(error: *mut rune_error) {
+    free(error);
+}
+
+impl rune_value {
+    fn into_raw(value: Value) -> *mut rune_value {
+        Box::into_raw(Box::new(rune_value { value }))
+    }
+}
+
+/// Construct a unit value.
+#[no_mangle]
+pub extern "C" fn rune_value_unit() -> *mut rune_value {
+    rune_value::into_raw(Value::Unit)
+}
+
+/// Construct a boolean value.
+#[no_mangle]
+pub extern "C" fn rune_value_bool(value: bool) -> *mut rune_value {
+    rune_value::into_raw(Value::from(value))
+}
+
+/// Construct an integer value.
+#[no_mangle]
+pub extern "C" fn rune_value_integer(value: i64) -> *mut rune_value {
+    rune_value::into_raw(Value::from(value))
+}
+
+/// Construct a float value.
+#[no_mangle]
+pub extern "C" fn rune_value_float(value: f64) -> *mut rune_value {
+    rune_value::into_raw(Value::from(value))
+}
+
+/// Construct a string value by copying the given string.
+///
+/// Returns NULL if the string is NULL or isn't valid UTF-8.
+#[no_mangle]
+pub unsafe extern "C" fn rune_value_string(value: *const c_char) -> *mut rune_value {
+    match to_str(value) {
+        Some(value) => rune_value::into_raw(Value::from(String::from(value))),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Test if the value is a unit.
+#[no_mangle]
+pub unsafe extern "C" fn rune_value_is_unit(value: *const rune_value) -> bool {
+    matches!(value.as_ref(), Some(rune_value { value: Value::Unit }))
+}
+
+/// Read a value of type `T` out of `value` into `out`.
+unsafe fn read<T>(value: *const rune_value, out: *mut T) -> bool
+where
+    T: FromValue,
+{
+    let value = match value.as_ref() {
+        Some(value) if !out.is_null() => value,
+        _ => return false,
+    };
+
+    match T::from_value(value.value.clone()) {
+        Ok(value) => {
+            *out = value;
+            true
+        }
+        Err(..) => false,
+    }
+}
+
+/// Read a boolean into `out`, returning false if the value isn't a boolean.
+#[no_mangle]
+pub unsafe extern "C" fn rune_value_as_bool(value: *const rune_value, out: *mut bool) -> bool {
+    read(value, out)
+}
+
+/// Read an integer into `out`, returning false if the value isn't an
+/// integer.
+#[no_mangle]
+pub unsafe extern "C" fn rune_value_as_integer(value: *const rune_value, out: *mut i64) -> bool {
+    read(value, out)
+}
+
+/// Read a float into `out`, returning false if the value isn't a float.
+#[no_mangle]
+pub unsafe extern "C" fn rune_value_as_float(value: *const rune_value, out: *mut f64) -> bool {
+    read(value, out)
+}
+
+/// Copy a string value into a newly allocated string, which has to be freed
+/// with `rune_string_free`.
+///
+/// Returns NULL if the value isn't a string.
+#[no_mangle]
+pub unsafe extern "C" fn rune_value_as_string(value: *const rune_value) -> *mut c_char {
+    let value = match value.as_ref() {
+        Some(value) => value,
+        None => return ptr::null_mut(),
+    };
+
+    match String::from_value(value.value.clone()) {
+        Ok(string) => to_c_string(string).into_raw(),
+        Err(..) => ptr::null_mut(),
+    }
+}
+
+/// Format the value using its debug representation into a newly allocated
+/// string, which has to be freed with `rune_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn rune_value_debug(value: *const rune_value) -> *mut c_char {
+    match value.as_ref() {
+        Some(value) => to_c_string(format!("{:?}", value.value)).into_raw(),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Free a value.
+#[no_mangle]
+pub unsafe extern "C" fn rune_value_free(value: *mut rune_value) {
+    free(value);
+}
+
+/// Free a string allocated by this library.
+#[no_mangle]
+pub unsafe extern "C" fn rune_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
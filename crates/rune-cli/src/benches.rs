@@ -4,12 +4,12 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use clap::Parser;
-use rune::compile::{Item, ItemBuf};
+use rune::compile::Item;
 use rune::runtime::{Function, Unit, Value};
-use rune::{Any, Context, ContextError, Hash, Module, Sources};
+use rune::{Any, Context, ContextError, Module, Sources};
 use rune_modules::capture_io::CaptureIo;
 
-use crate::{ExitCode, Io, SharedFlags};
+use crate::{visitor, ExitCode, Io, SharedFlags};
 
 #[derive(Parser, Debug, Clone)]
 pub(crate) struct Flags {
@@ -21,6 +21,11 @@ pub(crate) struct Flags {
     #[arg(long, default_value = "100")]
     iterations: u32,
 
+    /// Only run benchmarks whose name contains the given string. Can be
+    /// specified multiple times to run benchmarks matching any of them.
+    #[arg(long = "filter", short = 'f')]
+    filters: Vec<String>,
+
     #[command(flatten)]
     pub(crate) shared: SharedFlags,
 }
@@ -52,7 +57,7 @@ pub(crate) async fn run(
     capture_io: Option<&CaptureIo>,
     unit: Arc<Unit>,
     sources: &Sources,
-    fns: &[(Hash, ItemBuf)],
+    fns: &[visitor::Function],
 ) -> anyhow::Result<ExitCode> {
     let runtime = Arc::new(context.runtime());
     let mut vm = rune::Vm::new(runtime, unit);
@@ -65,7 +70,30 @@ pub(crate) async fn run(
 
     let mut any_error = false;
 
-    for (hash, item) in fns {
+    for visitor::Function {
+        hash,
+        item,
+        is_ignored,
+        ..
+    } in fns
+    {
+        if !args.filters.is_empty() {
+            let name = item.to_string();
+
+            if !args
+                .filters
+                .iter()
+                .any(|filter| name.contains(filter.as_str()))
+            {
+                continue;
+            }
+        }
+
+        if *is_ignored {
+            writeln!(io.stdout, "bench {}: ignored", item)?;
+            continue;
+        }
+
         let mut bencher = Bencher::default();
 
         if let Err(error) = vm.call(*hash, (&mut bencher,)) {
@@ -132,6 +160,12 @@ fn bench_fn(
 
     collected.sort_unstable();
 
+    let median = match collected.len() {
+        0 => 0.0,
+        n if n % 2 == 0 => (collected[n / 2 - 1] + collected[n / 2]) as f64 / 2.0,
+        n => collected[n / 2] as f64,
+    };
+
     let len = collected.len() as f64;
     let average = collected.iter().copied().sum::<i128>() as f64 / len;
     let variance = collected
@@ -144,6 +178,7 @@ fn bench_fn(
 
     let format = Format {
         average: average as u128,
+        median: median as u128,
         stddev: stddev as u128,
         iterations,
     };
@@ -159,6 +194,7 @@ fn bench_fn(
 
 struct Format {
     average: u128,
+    median: u128,
     stddev: u128,
     iterations: usize,
 }
@@ -167,8 +203,9 @@ impl fmt::Display for Format {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "mean={:.2}, stddev={:.2}, iterations={}",
+            "mean={:.2}, median={:.2}, stddev={:.2}, iterations={}",
             Time(self.average),
+            Time(self.median),
             Time(self.stddev),
             self.iterations
         )
@@ -179,6 +216,16 @@ struct Time(u128);
 
 impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}ns", self.0)
+        let precision = f.precision().unwrap_or(2);
+
+        // Pick the largest unit which keeps the value at or above one.
+        let (value, unit) = match self.0 {
+            n if n >= 1_000_000_000 => (n as f64 / 1_000_000_000.0, "s"),
+            n if n >= 1_000_000 => (n as f64 / 1_000_000.0, "ms"),
+            n if n >= 1_000 => (n as f64 / 1_000.0, "µs"),
+            n => return write!(f, "{}ns", n),
+        };
+
+        write!(f, "{:.*}{}", precision, value, unit)
     }
 }
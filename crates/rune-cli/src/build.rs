@@ -0,0 +1,372 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context as _, Result};
+use clap::{Parser, ValueEnum};
+use rune::termcolor::NoColor;
+use rune::{Sources, Unit};
+
+use crate::{Config, ExitCode, Io, SharedFlags};
+
+/// The version of rune which generated executables depend on.
+const RUNE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The name of the embedded unit inside of the generated project.
+const UNIT_FILE: &str = "unit.rnc";
+
+#[derive(Parser, Debug, Clone)]
+pub(crate) struct Flags {
+    /// The path to write the resulting executable, or emitted output, to.
+    ///
+    /// Defaults to the name of the script being built without its extension.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Directory used to generate and build the intermediate cargo project.
+    ///
+    /// Defaults to `target/rune-build/<name>`.
+    #[arg(long)]
+    build_dir: Option<PathBuf>,
+    /// Path to a local checkout of rune to build the executable against,
+    /// instead of the published crates.
+    #[arg(long)]
+    rune_path: Option<PathBuf>,
+    /// Build the executable in debug mode.
+    #[arg(long)]
+    debug: bool,
+    /// Emit the compiled unit instead of building an executable.
+    ///
+    /// The output is written to the path specified with `--output`. It
+    /// defaults to the name of the script with an `.rnc` extension for
+    /// `unit`, and to stdout for `asm`.
+    #[arg(long, value_enum)]
+    emit: Option<Emit>,
+    #[command(flatten)]
+    pub(crate) shared: SharedFlags,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum Emit {
+    /// The serialized unit, as used by the bytecode cache.
+    Unit,
+    /// A human-readable disassembly of the unit, with instructions annotated
+    /// with the source lines they were compiled from.
+    Asm,
+}
+
+/// Bundle the given unit together with the runtime into a standalone
+/// executable.
+pub(crate) fn run(
+    io: &mut Io<'_>,
+    c: &Config,
+    flags: &Flags,
+    path: &Path,
+    unit: &Unit,
+    sources: &Sources,
+) -> Result<ExitCode> {
+    if let Some(emit) = flags.emit {
+        return emit_unit(io, flags, emit, path, unit, sources);
+    }
+
+    let name = match path.file_stem().and_then(|s| s.to_str()) {
+        Some(name) => name,
+        None => bail!(
+            "cannot determine name of executable from: {}",
+            path.display()
+        ),
+    };
+
+    let build_dir = match &flags.build_dir {
+        Some(build_dir) => build_dir.to_owned(),
+        None => {
+            let mut target = match &c.manifest_root {
+                Some(root) => root.join("target"),
+                None => match std::env::var_os("CARGO_TARGET_DIR") {
+                    Some(target) => PathBuf::from(target),
+                    None => PathBuf::from("target"),
+                },
+            };
+
+            target.push("rune-build");
+            target.push(name);
+            target
+        }
+    };
+
+    let output = match &flags.output {
+        Some(output) => output.to_owned(),
+        None => PathBuf::from(format!("{}{}", name, std::env::consts::EXE_SUFFIX)),
+    };
+
+    writeln!(
+        io.stdout,
+        "Building: {} -> {}",
+        path.display(),
+        output.display()
+    )?;
+
+    generate(&build_dir, name, flags, unit)?;
+
+    let mut command = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.arg("build").current_dir(&build_dir);
+
+    if !flags.debug {
+        command.arg("--release");
+    }
+
+    let status = command
+        .status()
+        .with_context(|| format!("running cargo in: {}", build_dir.display()))?;
+
+    if !status.success() {
+        writeln!(io.stderr, "cargo build failed: {}", status)?;
+        return Ok(ExitCode::Failure);
+    }
+
+    let built = build_dir
+        .join("target")
+        .join(if flags.debug { "debug" } else { "release" })
+        .join(format!("{}{}", name, std::env::consts::EXE_SUFFIX));
+
+    fs::copy(&built, &output)
+        .with_context(|| format!("copying {} to {}", built.display(), output.display()))?;
+
+    Ok(ExitCode::Success)
+}
+
+/// Generate the intermediate cargo project in the given directory.
+fn generate(build_dir: &Path, name: &str, flags: &Flags, unit: &Unit) -> Result<()> {
+    let src = build_dir.join("src");
+
+    fs::create_dir_all(&src).with_context(|| format!("creating directory: {}", src.display()))?;
+
+    let unit_path = src.join(UNIT_FILE);
+    let f = fs::File::create(&unit_path)
+        .with_context(|| format!("creating file: {}", unit_path.display()))?;
+    bincode::serialize_into(f, unit)?;
+
+    write_if_changed(
+        &build_dir.join("Cargo.toml"),
+        &manifest(name, flags.rune_path.as_deref(), flags.shared.experimental)?,
+    )?;
+    write_if_changed(
+        &src.join("main.rs"),
+        &main_source(flags.shared.experimental),
+    )?;
+
+    Ok(())
+}
+
+/// Write the compiled unit instead of building an executable.
+fn emit_unit(
+    io: &mut Io<'_>,
+    flags: &Flags,
+    emit: Emit,
+    path: &Path,
+    unit: &Unit,
+    sources: &Sources,
+) -> Result<ExitCode> {
+    match emit {
+        Emit::Unit => {
+            let output = match &flags.output {
+                Some(output) => output.to_owned(),
+                None => path.with_extension("rnc"),
+            };
+
+            writeln!(
+                io.stdout,
+                "Emitting: {} -> {}",
+                path.display(),
+                output.display()
+            )?;
+
+            let f = fs::File::create(&output)
+                .with_context(|| format!("creating file: {}", output.display()))?;
+            bincode::serialize_into(f, unit)?;
+        }
+        Emit::Asm => match &flags.output {
+            Some(output) => {
+                let f = fs::File::create(output)
+                    .with_context(|| format!("creating file: {}", output.display()))?;
+                unit.emit_instructions(&mut NoColor::new(f), sources, true)?;
+            }
+            None => {
+                unit.emit_instructions(io.stdout, sources, true)?;
+            }
+        },
+    }
+
+    Ok(ExitCode::Success)
+}
+
+/// Only write the given file if its contents differ, so that we don't force
+/// cargo to rebuild unnecessarily.
+fn write_if_changed(path: &Path, contents: &str) -> Result<()> {
+    if let Ok(existing) = fs::read_to_string(path) {
+        if existing == contents {
+            return Ok(());
+        }
+    }
+
+    fs::write(path, contents).with_context(|| format!("writing file: {}", path.display()))?;
+    Ok(())
+}
+
+/// Generate the manifest of the intermediate project.
+fn manifest(name: &str, rune_path: Option<&Path>, experimental: bool) -> Result<String> {
+    let features = if experimental {
+        r#"["full", "experiments"]"#
+    } else {
+        r#"["full"]"#
+    };
+
+    let (rune, rune_modules) = match rune_path {
+        Some(path) => {
+            let path = path
+                .canonicalize()
+                .with_context(|| format!("resolving rune path: {}", path.display()))?;
+            let crates = path.join("crates");
+
+            (
+                format!(
+                    "{{ path = {:?} }}",
+                    crates.join("rune").display().to_string()
+                ),
+                format!(
+                    "{{ path = {:?}, features = {} }}",
+                    crates.join("rune-modules").display().to_string(),
+                    features
+                ),
+            )
+        }
+        None => (
+            format!("\"={}\"", RUNE_VERSION),
+            format!(
+                "{{ version = \"={}\", features = {} }}",
+                RUNE_VERSION, features
+            ),
+        ),
+    };
+
+    Ok(format!(
+        r#"[package]
+name = "{name}"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[dependencies]
+rune = {rune}
+rune-modules = {rune_modules}
+bincode = "1.3.3"
+tokio = {{ version = "1.26.0", features = ["rt-multi-thread", "macros"] }}
+
+[workspace]
+"#
+    ))
+}
+
+/// Generate the entrypoint of the intermediate project, which embeds the
+/// serialized unit and runs its `main` function.
+///
+/// The context is set up in the same way as through
+/// [SharedFlags::run_context], with the arguments the executable is started
+/// with being passed on to the script.
+fn main_source(experimental: bool) -> String {
+    let experiments = if experimental {
+        "    context.install(rune_modules::experiments::module(true)?)?;\n"
+    } else {
+        ""
+    };
+
+    format!(
+        r#"use std::sync::Arc;
+
+static UNIT: &[u8] = include_bytes!("{UNIT_FILE}");
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {{
+    let unit: rune::Unit = bincode::deserialize(UNIT)?;
+
+    let mut context = rune_modules::default_context()?;
+{experiments}
+    let env = rune_modules::env::Config::new()
+        .args(std::env::args().skip(1))
+        .allow_all_vars();
+    context.install(rune_modules::env::module(env)?)?;
+
+    let runtime = Arc::new(context.runtime());
+    let mut vm = rune::Vm::new(runtime, Arc::new(unit));
+    vm.async_call(["main"], ()).await?;
+    Ok(())
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate, Flags, UNIT_FILE};
+    use crate::Config;
+    use anyhow::Result;
+    use clap::Parser;
+    use rune::{Hash, Unit};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_generate_project() -> Result<()> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("..")
+            .canonicalize()?;
+
+        let flags = Flags::try_parse_from([
+            "build".as_ref(),
+            "--experimental".as_ref(),
+            "--rune-path".as_ref(),
+            root.as_os_str(),
+        ])?;
+
+        let context = flags
+            .shared
+            .run_context(&Config::default(), std::iter::empty::<String>())?;
+
+        let mut sources = rune::sources! {
+            entry => {
+                pub fn main() {
+                    std::env::args()
+                }
+            }
+        };
+
+        let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+
+        let build_dir =
+            std::env::temp_dir().join(format!("rune-build-test-{}", std::process::id()));
+        generate(&build_dir, "script", &flags, &unit)?;
+
+        let manifest = fs::read_to_string(build_dir.join("Cargo.toml"));
+        let main = fs::read_to_string(build_dir.join("src").join("main.rs"));
+        let unit = fs::read(build_dir.join("src").join(UNIT_FILE));
+        fs::remove_dir_all(&build_dir)?;
+
+        let manifest = manifest?;
+        assert!(manifest.contains("name = \"script\""));
+        assert!(manifest.contains(&format!(
+            "rune = {{ path = {:?} }}",
+            root.join("crates").join("rune").display().to_string()
+        )));
+        assert!(manifest.contains("features = [\"full\", \"experiments\"]"));
+
+        let main = main?;
+        assert!(main.contains("context.install(rune_modules::experiments::module(true)?)?;"));
+        assert!(main.contains(".args(std::env::args().skip(1))"));
+        assert!(main.contains("context.install(rune_modules::env::module(env)?)?;"));
+
+        let unit: Unit = bincode::deserialize(&unit?)?;
+        assert!(unit.function(Hash::type_hash(["main"])).is_some());
+        Ok(())
+    }
+}
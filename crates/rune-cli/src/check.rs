@@ -36,11 +36,9 @@ pub(crate) fn run(
 
     sources.insert(source);
 
-    let mut diagnostics = if flags.shared.warnings || flags.warnings_are_errors {
-        Diagnostics::new()
-    } else {
-        Diagnostics::without_warnings()
-    };
+    // Warnings are always reported when checking, since reporting problems
+    // without executing anything is the whole point.
+    let mut diagnostics = Diagnostics::new();
 
     let mut test_finder = visitor::FunctionVisitor::new(visitor::Attribute::None);
     let mut source_loader = FileSourceLoader::new();
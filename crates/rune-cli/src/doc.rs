@@ -2,7 +2,7 @@ use std::io::Write;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use rune::compile::FileSourceLoader;
 use rune::{Diagnostics, Options, Source, Sources};
 
@@ -19,10 +19,21 @@ pub(crate) struct Flags {
     /// Open the generated documentation in a browser.
     #[arg(long)]
     open: bool,
+    /// The format to write documentation in.
+    #[arg(long, value_enum, default_value = "html")]
+    format: Format,
     #[command(flatten)]
     pub(crate) shared: SharedFlags,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum Format {
+    /// A set of html pages.
+    Html,
+    /// A set of markdown files, one per module.
+    Markdown,
+}
+
 pub(crate) fn run<I>(
     io: &mut Io<'_>,
     c: &Config,
@@ -94,10 +105,19 @@ where
         visitors.push(visitor);
     }
 
-    rune::doc::write_html("root", &root, &context, &visitors)?;
+    let index = match flags.format {
+        Format::Html => {
+            rune::doc::write_html("root", &root, &context, &visitors)?;
+            "index.html"
+        }
+        Format::Markdown => {
+            rune::doc::write_markdown("root", &root, &context, &visitors)?;
+            "index.md"
+        }
+    };
 
     if flags.open {
-        let path = root.join("index.html");
+        let path = root.join(index);
         let _ = webbrowser::open(&path.display().to_string());
     }
 
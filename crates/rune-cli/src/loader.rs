@@ -6,9 +6,9 @@ use std::path::PathBuf;
 use std::{path::Path, sync::Arc};
 
 use anyhow::{anyhow, Context as _, Result};
-use rune::compile::{FileSourceLoader, ItemBuf};
+use rune::compile::FileSourceLoader;
 use rune::Diagnostics;
-use rune::{Context, Hash, Options, Source, Sources, Unit};
+use rune::{Context, Options, Source, Sources, Unit};
 use tracing::{error, trace};
 
 use crate::{visitor, Args, Io};
@@ -16,7 +16,7 @@ use crate::{visitor, Args, Io};
 pub(crate) struct Load {
     pub(crate) unit: Arc<Unit>,
     pub(crate) sources: Sources,
-    pub(crate) functions: Vec<(Hash, ItemBuf)>,
+    pub(crate) functions: Vec<visitor::Function>,
 }
 
 /// Load context and code for a given path
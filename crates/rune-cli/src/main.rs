@@ -30,12 +30,15 @@
 //! [rune]: https://github.com/rune-rs/rune
 
 mod benches;
+mod build;
 mod check;
 mod doc;
 mod loader;
+mod repl;
 mod run;
 mod tests;
 mod visitor;
+mod watch;
 
 use anyhow::{bail, Result};
 use clap::{Parser, Subcommand};
@@ -63,7 +66,8 @@ struct Io<'a> {
 
 #[derive(Subcommand, Debug, Clone)]
 enum Command {
-    /// Run checks but do not execute
+    /// Parse and compile sources, reporting any diagnostics, but do not
+    /// execute them
     Check(check::Flags),
     /// Build documentation.
     Doc(doc::Flags),
@@ -73,6 +77,10 @@ enum Command {
     Bench(benches::Flags),
     /// Run the designated script
     Run(run::Flags),
+    /// Bundle the designated script into a standalone executable
+    Build(build::Flags),
+    /// Start an interactive session, evaluating any given scripts first
+    Repl(repl::Flags),
 }
 
 impl Command {
@@ -89,6 +97,8 @@ impl Command {
             Command::Run(args) => {
                 args.propagate_related_flags();
             }
+            Command::Build(..) => {}
+            Command::Repl(..) => {}
         }
     }
 
@@ -99,6 +109,8 @@ impl Command {
             Command::Test(..) => "Testing",
             Command::Bench(..) => "Benchmarking",
             Command::Run(..) => "Running",
+            Command::Build(..) => "Building",
+            Command::Repl(..) => "Evaluating",
         }
     }
 
@@ -109,13 +121,15 @@ impl Command {
             Command::Test(args) => &args.shared,
             Command::Bench(args) => &args.shared,
             Command::Run(args) => &args.shared,
+            Command::Build(args) => &args.shared,
+            Command::Repl(args) => &args.shared,
         }
     }
 
     fn bins_test(&self) -> Option<WorkspaceFilter<'_>> {
         if !matches!(
             self,
-            Command::Run(..) | Command::Check(..) | Command::Doc(..)
+            Command::Run(..) | Command::Build(..) | Command::Check(..) | Command::Doc(..)
         ) {
             return None;
         }
@@ -149,7 +163,7 @@ impl Command {
     fn examples_test(&self) -> Option<WorkspaceFilter<'_>> {
         if !matches!(
             self,
-            Command::Run(..) | Command::Check(..) | Command::Doc(..)
+            Command::Run(..) | Command::Build(..) | Command::Check(..) | Command::Doc(..)
         ) {
             return None;
         }
@@ -276,6 +290,23 @@ impl SharedFlags {
         Ok(context)
     }
 
+    /// Construct the context scripts are run in, which in addition to
+    /// [SharedFlags::context] gives them access to the given arguments and to
+    /// environment variables.
+    ///
+    /// Executables generated through `rune build` construct the same context
+    /// when they start.
+    fn run_context<I>(&self, c: &Config, args: I) -> Result<Context, ContextError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut context = self.context(c)?;
+        let env = rune_modules::env::Config::new().args(args).allow_all_vars();
+        context.install(rune_modules::env::module(env)?)?;
+        Ok(context)
+    }
+
     /// Setup a context that captures output.
     fn context_with_capture(&self, c: &Config, io: &CaptureIo) -> Result<Context, ContextError> {
         let mut context = rune_modules::with_config(false)?;
@@ -325,7 +356,11 @@ impl Args {
                 options.test(true);
                 options.bytecode(false);
             }
-            Command::Bench(_) | Command::Doc(..) | Command::Run(_) => (),
+            Command::Bench(_)
+            | Command::Doc(..)
+            | Command::Run(_)
+            | Command::Build(_)
+            | Command::Repl(_) => (),
         }
 
         for option in &self.cmd.shared().compiler_options {
@@ -548,6 +583,13 @@ fn populate_config(io: &mut Io<'_>, c: &mut Config, args: &Args) -> Result<()> {
 async fn main_with_out(io: &mut Io<'_>, mut args: Args) -> Result<ExitCode> {
     let mut c = Config::default();
     args.cmd.propagate_related_flags(&mut c);
+
+    // The repl doesn't operate on entries, so there is nothing to discover.
+    if let Command::Repl(flags) = &args.cmd {
+        let options = args.options()?;
+        return repl::run(io, &c, flags, &options).await;
+    }
+
     populate_config(io, &mut c, &args)?;
 
     let entries = std::mem::take(&mut c.entries);
@@ -555,7 +597,8 @@ async fn main_with_out(io: &mut Io<'_>, mut args: Args) -> Result<ExitCode> {
 
     let what = args.cmd.describe();
     let verbose = c.verbose;
-    let recursive = args.cmd.shared().recursive;
+    // Tests are discovered in every file of the directories they're run on.
+    let recursive = args.cmd.shared().recursive || matches!(args.cmd, Command::Test(..));
 
     let mut entrys = Vec::new();
 
@@ -608,26 +651,35 @@ where
 {
     match &args.cmd {
         Command::Check(flags) => {
+            let mut exit_code = ExitCode::Success;
+
+            // Check every path so that all diagnostics are reported at once.
             for e in entrys {
                 for path in &e.paths {
                     match check::run(io, c, flags, options, path)? {
                         ExitCode::Success => (),
-                        other => return Ok(other),
+                        other => exit_code = other,
                     }
                 }
             }
+
+            return Ok(exit_code);
         }
         Command::Doc(flags) => return doc::run(io, c, flags, options, entrys),
         Command::Test(flags) => {
+            let paths = entrys.into_iter().flat_map(|e| e.paths).collect::<Vec<_>>();
+            return tests::run(io, c, flags, options, &paths).await;
+        }
+        Command::Bench(flags) => {
             for e in entrys {
                 for path in &e.paths {
                     let capture_io = rune_modules::capture_io::CaptureIo::new();
                     let context = flags.shared.context_with_capture(c, &capture_io)?;
 
                     let load =
-                        loader::load(io, &context, args, options, path, visitor::Attribute::Test)?;
+                        loader::load(io, &context, args, options, path, visitor::Attribute::Bench)?;
 
-                    match tests::run(
+                    match benches::run(
                         io,
                         flags,
                         &context,
@@ -644,47 +696,42 @@ where
                 }
             }
         }
-        Command::Bench(flags) => {
+        Command::Run(flags) => {
+            let context = flags.shared.run_context(c, flags.args.iter().cloned())?;
+
+            if flags.watch {
+                let paths = entrys.into_iter().flat_map(|e| e.paths).collect::<Vec<_>>();
+                return watch::run(io, c, args, flags, options, &context, &paths).await;
+            }
+
             for e in entrys {
                 for path in &e.paths {
-                    let capture_io = rune_modules::capture_io::CaptureIo::new();
-                    let context = flags.shared.context_with_capture(c, &capture_io)?;
-
                     let load =
-                        loader::load(io, &context, args, options, path, visitor::Attribute::Bench)?;
+                        loader::load(io, &context, args, options, path, visitor::Attribute::None)?;
 
-                    match benches::run(
-                        io,
-                        flags,
-                        &context,
-                        Some(&capture_io),
-                        load.unit,
-                        &load.sources,
-                        &load.functions,
-                    )
-                    .await?
-                    {
+                    match run::run(io, c, flags, &context, load.unit, &load.sources).await? {
                         ExitCode::Success => (),
                         other => return Ok(other),
                     }
                 }
             }
         }
-        Command::Run(flags) => {
-            let context = flags.shared.context(c)?;
+        Command::Build(flags) => {
+            let context = flags.shared.run_context(c, std::iter::empty::<String>())?;
 
             for e in entrys {
                 for path in &e.paths {
                     let load =
                         loader::load(io, &context, args, options, path, visitor::Attribute::None)?;
 
-                    match run::run(io, c, flags, &context, load.unit, &load.sources).await? {
+                    match build::run(io, c, flags, path, &load.unit, &load.sources)? {
                         ExitCode::Success => (),
                         other => return Ok(other),
                     }
                 }
             }
         }
+        Command::Repl(flags) => return repl::run(io, c, flags, options).await,
     }
 
     Ok(ExitCode::Success)
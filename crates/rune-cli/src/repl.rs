@@ -0,0 +1,415 @@
+use std::fmt::Write as _;
+use std::io::Write;
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use rune::ast::{self, Spanned};
+use rune::compile::ItemBuf;
+use rune::parse::ParseError;
+use rune::runtime::RuntimeContext;
+use rune::{Context, Diagnostics, Options, Source, SourceId, Sources, Value, Vm};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use crate::{Config, ExitCode, Io, SharedFlags};
+
+/// The name of the function input is compiled into.
+const ENTRY: &str = "repl_main";
+
+/// The name of the variable the value of the input is bound to.
+const VALUE: &str = "repl_value";
+
+#[derive(Parser, Debug, Clone)]
+pub(crate) struct Flags {
+    #[command(flatten)]
+    pub(crate) shared: SharedFlags,
+}
+
+/// What to do with the result of an evaluation.
+#[derive(Clone, Copy)]
+enum Mode {
+    /// Print the value of the evaluated input.
+    Value,
+    /// Print the type of the evaluated input.
+    Type,
+}
+
+/// A single item defined in the session.
+struct Definition {
+    /// The name the item is defined under, redefining an item with the same
+    /// name replaces it.
+    name: String,
+    /// The source of the item.
+    source: String,
+}
+
+/// Input which has been split up into definitions and statements.
+struct Input {
+    definitions: Vec<Definition>,
+    /// The source of statements to execute.
+    statements: String,
+    /// The source of the trailing expression, if any.
+    value: Option<String>,
+    /// Names of variables bound by the statements.
+    bindings: Vec<String>,
+}
+
+struct Session<'a> {
+    context: &'a Context,
+    runtime: Arc<RuntimeContext>,
+    options: &'a Options,
+    vm: Option<Vm>,
+    /// Items defined in the session, in definition order.
+    definitions: Vec<Definition>,
+    /// Variables bound in the session, passed as arguments to every
+    /// evaluation.
+    variables: Vec<(String, Value)>,
+}
+
+impl<'a> Session<'a> {
+    fn new(context: &'a Context, options: &'a Options) -> Self {
+        Self {
+            context,
+            runtime: Arc::new(context.runtime()),
+            options,
+            vm: None,
+            definitions: Vec::new(),
+            variables: Vec::new(),
+        }
+    }
+
+    /// Evaluate the given input and report its outcome.
+    async fn eval(&mut self, io: &mut Io<'_>, input: &str, mode: Mode) -> Result<()> {
+        let input = match split(input) {
+            Ok(input) => input,
+            Err(error) => {
+                let mut sources = Sources::new();
+                let source_id = sources.insert(Source::new("<repl>", input));
+                let mut diagnostics = Diagnostics::new();
+                diagnostics.error(source_id, error);
+                diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+                return Ok(());
+            }
+        };
+
+        let mut definitions = self
+            .definitions
+            .iter()
+            .map(|d| (d.name.as_str(), d.source.as_str()))
+            .collect::<Vec<_>>();
+
+        for d in &input.definitions {
+            match definitions.iter_mut().find(|(name, _)| *name == d.name) {
+                Some(existing) => existing.1 = d.source.as_str(),
+                None => definitions.push((d.name.as_str(), d.source.as_str())),
+            }
+        }
+
+        let mut names = self
+            .variables
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+
+        for name in &input.bindings {
+            if !names.contains(&name.as_str()) {
+                names.push(name.as_str());
+            }
+        }
+
+        let mut source = String::new();
+
+        for (_, definition) in &definitions {
+            writeln!(source, "{}", definition)?;
+        }
+
+        let arguments = self
+            .variables
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        writeln!(source, "pub fn {}({}) {{", ENTRY, arguments)?;
+        writeln!(source, "{}", input.statements)?;
+        writeln!(
+            source,
+            "let {} = {};",
+            VALUE,
+            input.value.as_deref().unwrap_or("()")
+        )?;
+        writeln!(
+            source,
+            "({0}, format!(\"{{:?}}\", {0}), [{1}])",
+            VALUE,
+            names.join(", ")
+        )?;
+        writeln!(source, "}}")?;
+
+        let mut sources = Sources::new();
+        sources.insert(Source::new("<repl>", source));
+
+        // Items which are defined but not yet used would otherwise be warned
+        // about on every evaluation.
+        let mut diagnostics = Diagnostics::without_warnings();
+
+        let result = rune::prepare(&mut sources)
+            .with_context(self.context)
+            .with_diagnostics(&mut diagnostics)
+            .with_options(self.options)
+            .build();
+
+        diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+
+        let unit = match result {
+            Ok(unit) => Arc::new(unit),
+            Err(..) => return Ok(()),
+        };
+
+        let vm = match &mut self.vm {
+            Some(vm) => {
+                // The previous evaluation might have errored in the middle of
+                // execution, so make sure the virtual machine is reset.
+                vm.clear();
+                vm.swap_unit(unit)?;
+                vm
+            }
+            None => self.vm.insert(Vm::new(self.runtime.clone(), unit)),
+        };
+
+        let args = self
+            .variables
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect::<Vec<_>>();
+
+        let result = match vm.execute([ENTRY], args) {
+            Ok(mut execution) => execution.async_complete().await,
+            Err(error) => Err(error),
+        };
+
+        let output = match result {
+            Ok(output) => output,
+            Err(error) => {
+                error.emit(io.stdout, &sources)?;
+                return Ok(());
+            }
+        };
+
+        // The input was successfully evaluated, so its definitions are now
+        // part of the session.
+        for d in input.definitions {
+            match self.definitions.iter_mut().find(|e| e.name == d.name) {
+                Some(existing) => *existing = d,
+                None => self.definitions.push(d),
+            }
+        }
+
+        let (value, debug, values) = match output {
+            Value::Tuple(tuple) => {
+                let tuple = tuple.take()?;
+
+                match &tuple[..] {
+                    [value, debug, values] => (
+                        value.clone(),
+                        debug.clone().into_string()?.take()?,
+                        values.clone().into_vec()?.take()?,
+                    ),
+                    _ => return Ok(()),
+                }
+            }
+            // The input returned early, so there are no bindings to update.
+            output => {
+                writeln!(io.stdout, "{:?}", output)?;
+                return Ok(());
+            }
+        };
+
+        self.variables = names.into_iter().map(str::to_owned).zip(values).collect();
+
+        match mode {
+            Mode::Value => {
+                if !matches!(value, Value::Unit) {
+                    writeln!(io.stdout, "{}", debug)?;
+                }
+            }
+            Mode::Type => {
+                writeln!(io.stdout, "{}", value.type_info()?)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Print the documentation of the given item.
+    fn doc(&self, io: &mut Io<'_>, path: &str) -> Result<()> {
+        if path.is_empty() {
+            writeln!(io.stdout, "expected a path, like `std::io::println`")?;
+            return Ok(());
+        }
+
+        let mut components = path.trim_start_matches("::").split("::").map(str::trim);
+
+        let item = match components.next() {
+            Some(name) => ItemBuf::with_crate_item(name, components),
+            None => ItemBuf::new(),
+        };
+
+        match self.context.lookup_docs(&item) {
+            Some(docs) if !docs.is_empty() => {
+                for line in docs.lines() {
+                    writeln!(io.stdout, "{}", line.strip_prefix(' ').unwrap_or(line))?;
+                }
+            }
+            Some(..) => writeln!(io.stdout, "`{}` is not documented", item)?,
+            None => writeln!(io.stdout, "no item named `{}`", item)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Run an interactive session.
+pub(crate) async fn run(
+    io: &mut Io<'_>,
+    c: &Config,
+    flags: &Flags,
+    options: &Options,
+) -> Result<ExitCode> {
+    let context = flags.shared.context(c)?;
+    let mut session = Session::new(&context, options);
+
+    // Files passed on the command line are evaluated up front, so that their
+    // items are available in the session.
+    for path in &flags.shared.paths {
+        let input = std::fs::read_to_string(path)?;
+        session.eval(io, &input, Mode::Value).await?;
+    }
+
+    let mut editor = DefaultEditor::new()?;
+    let mut input = String::new();
+
+    writeln!(
+        io.stdout,
+        "Rune {}, type `:help` for a list of commands",
+        crate::VERSION.trim()
+    )?;
+
+    loop {
+        let prompt = if input.is_empty() { "> " } else { ". " };
+
+        let line = match editor.readline(prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                input.clear();
+                continue;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(error) => return Err(error.into()),
+        };
+
+        if input.is_empty() {
+            if let Some(command) = line.trim().strip_prefix(':') {
+                editor.add_history_entry(line.as_str())?;
+
+                let (command, rest) = command.split_once(' ').unwrap_or((command, ""));
+
+                match command {
+                    "q" | "quit" => break,
+                    "h" | "help" => {
+                        writeln!(io.stdout, ":type <expr> - print the type of an expression")?;
+                        writeln!(
+                            io.stdout,
+                            ":doc <path>  - print the documentation of an item"
+                        )?;
+                        writeln!(io.stdout, ":quit        - exit the session")?;
+                    }
+                    "t" | "type" => session.eval(io, rest, Mode::Type).await?,
+                    "d" | "doc" => session.doc(io, rest.trim())?,
+                    _ => writeln!(io.stdout, "unknown command `:{}`", command)?,
+                }
+
+                continue;
+            }
+        }
+
+        input.push_str(&line);
+        input.push('\n');
+
+        if let Err(error) = split(&input) {
+            if error.is_incomplete() {
+                continue;
+            }
+        }
+
+        editor.add_history_entry(input.trim_end())?;
+        session.eval(io, &input, Mode::Value).await?;
+        input.clear();
+    }
+
+    Ok(ExitCode::Success)
+}
+
+/// Split the given input into item definitions and statements.
+fn split(input: &str) -> Result<Input, ParseError> {
+    let mut parser = rune::parse::Parser::new(input, SourceId::empty(), false);
+
+    let mut definitions = Vec::new();
+    let mut statements = Vec::new();
+
+    while !parser.is_eof()? {
+        match parser.parse::<ast::Stmt>()? {
+            ast::Stmt::Item(item, _) => {
+                let name = match &item {
+                    ast::Item::Fn(item) => item.name.span(),
+                    ast::Item::Enum(item) => item.name.span(),
+                    ast::Item::Struct(item) => item.ident.span(),
+                    ast::Item::Mod(item) => item.name.span(),
+                    ast::Item::Const(item) => item.name.span(),
+                    item => item.span(),
+                };
+
+                definitions.push(Definition {
+                    name: input[name.range()].to_owned(),
+                    source: input[item.span().range()].to_owned(),
+                });
+            }
+            stmt => statements.push(stmt),
+        }
+    }
+
+    let value = match statements.last() {
+        Some(ast::Stmt::Expr(..)) => statements.pop().map(|stmt| stmt.span()),
+        _ => None,
+    };
+
+    let mut bindings = Vec::new();
+
+    for stmt in &statements {
+        if let ast::Stmt::Local(local) = stmt {
+            if let ast::Pat::PatPath(pat) = &local.pat {
+                let path = &pat.path;
+
+                if let (None, true, ast::PathSegment::Ident(ident)) =
+                    (&path.global, path.rest.is_empty(), &path.first)
+                {
+                    bindings.push(input[ident.span().range()].to_owned());
+                }
+            }
+        }
+    }
+
+    let statements = statements
+        .iter()
+        .map(|stmt| &input[stmt.span().range()])
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(Input {
+        definitions,
+        statements,
+        value: value.map(|span| input[span.range()].to_owned()),
+        bindings,
+    })
+}
@@ -1,10 +1,12 @@
+use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
 use anyhow::Result;
 use clap::Parser;
-use rune::runtime::{VmError, VmExecution};
+use rune::runtime::{Profiler, VmError, VmExecution};
 use rune::{Context, Sources, Unit, Value, Vm};
 
 use crate::{Config, ExitCode, Io, SharedFlags};
@@ -43,11 +45,26 @@ pub(crate) struct Flags {
     /// Dump native types.
     #[arg(long)]
     dump_native_types: bool,
+    /// Dump a report of per-function instruction counts and static data sizes.
+    #[arg(long)]
+    dump_report: bool,
     /// Include source code references where appropriate (only available if -O debug-info=true).
     #[arg(long)]
     with_source: bool,
+    /// Profile the execution, writing the sampled call stacks in the
+    /// collapsed stack format used by flamegraph tools to the given path.
+    #[arg(long)]
+    profile: Option<PathBuf>,
+    /// Watch the script and the modules it loads, re-running it whenever any
+    /// of them change.
+    #[arg(long, short = 'w')]
+    pub(crate) watch: bool,
     #[command(flatten)]
     pub(crate) shared: SharedFlags,
+    /// Arguments to pass to the script, available through `std::env::args`.
+    /// These must follow a `--`.
+    #[arg(last = true)]
+    pub(crate) args: Vec<String>,
 }
 
 impl Flags {
@@ -60,6 +77,7 @@ impl Flags {
             self.dump_types = true;
             self.dump_native_functions = true;
             self.dump_native_types = true;
+            self.dump_report = true;
         }
     }
 
@@ -75,6 +93,7 @@ impl Flags {
             || self.dump_types
             || self.dump_constants
             || self.emit_instructions
+            || self.dump_report
     }
 }
 
@@ -120,6 +139,11 @@ pub(crate) async fn run(
             unit.emit_instructions(&mut o, sources, args.with_source)?;
         }
 
+        if args.dump_report {
+            writeln!(io.stdout, "# report")?;
+            write!(io.stdout, "{}", unit.report())?;
+        }
+
         let mut functions = unit.iter_functions().peekable();
         let mut strings = unit.iter_static_strings().peekable();
         let mut keys = unit.iter_static_object_keys().peekable();
@@ -167,6 +191,13 @@ pub(crate) async fn run(
     let last = Instant::now();
 
     let mut vm = Vm::new(runtime, unit);
+
+    let profiler = args.profile.as_ref().map(|_| Arc::new(Profiler::new()));
+
+    if let Some(profiler) = &profiler {
+        vm.set_profiler(profiler.clone());
+    }
+
     let mut execution: VmExecution<_> = vm.execute(["main"], ())?;
     let result = if args.trace {
         match do_trace(
@@ -186,6 +217,12 @@ pub(crate) async fn run(
         execution.async_complete().await
     };
 
+    if let (Some(profiler), Some(path)) = (&profiler, &args.profile) {
+        let mut out = Vec::new();
+        profiler.profile().write_collapsed(&mut out)?;
+        fs::write(path, out)?;
+    }
+
     let errored = match result {
         Ok(result) => {
             let duration = Instant::now().duration_since(last);
@@ -1,15 +1,17 @@
 use std::io::Write;
-use std::sync::Arc;
-use std::time::Instant;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, Context as _, Result};
 use clap::Parser;
-use rune::compile::ItemBuf;
-use rune::runtime::{Unit, Value, Vm, VmError};
-use rune::{Context, Hash, Sources};
-use rune_modules::capture_io::CaptureIo;
+use rune::runtime::{Executor, SpawnFuture, SpawnTask};
+use rune::testing::{self, TestFailure, TestOptions, TestOutcome, TestReport, TestResult};
+use rune::{Options, Source, Sources};
+use tokio::runtime::Handle;
 
-use crate::{ExitCode, Io, SharedFlags};
+use crate::{Config, ExitCode, Io, SharedFlags};
 
 #[derive(Parser, Debug, Clone)]
 pub(crate) struct Flags {
@@ -21,172 +23,168 @@ pub(crate) struct Flags {
     #[arg(long)]
     no_fail_fast: bool,
 
+    /// Only run tests whose name contains the given string. Can be specified
+    /// multiple times to run tests matching any of them.
+    #[arg(long = "filter", short = 'f')]
+    filters: Vec<String>,
+
+    /// Only run tests marked with `#[ignore]`
+    #[arg(long)]
+    ignored: bool,
+
+    /// Run tests marked with `#[ignore]` along with all other tests
+    #[arg(long)]
+    include_ignored: bool,
+
+    /// The number of tests to run in parallel, defaults to the number of
+    /// available cores
+    #[arg(long, short = 'j')]
+    jobs: Option<NonZeroUsize>,
+
     #[command(flatten)]
     pub(crate) shared: SharedFlags,
 }
 
-#[derive(Debug)]
-enum FailureReason {
-    Crash(VmError),
-    ReturnedNone,
-    ReturnedErr { output: Box<[u8]>, error: Value },
-}
+/// Runs the tasks spawned by tests on the runtime of the command line, so that
+/// tests running in parallel can use modules which depend on it.
+struct Tokio(Handle);
 
-#[derive(Debug)]
-struct TestCase<'a> {
-    hash: Hash,
-    item: &'a ItemBuf,
-    outcome: Option<FailureReason>,
-    buf: Vec<u8>,
-}
+impl Executor for Tokio {
+    fn spawn(&self, future: SpawnFuture) {
+        self.0.spawn(future);
+    }
 
-impl<'a> TestCase<'a> {
-    fn from_parts(hash: Hash, item: &'a ItemBuf) -> Self {
-        Self {
-            hash,
-            item,
-            outcome: None,
-            buf: Vec::new(),
-        }
+    fn spawn_blocking(&self, task: SpawnTask) {
+        self.0.spawn_blocking(task);
     }
 
-    async fn execute(
-        &mut self,
-        io: &mut Io<'_>,
-        vm: &mut Vm,
-        quiet: bool,
-        capture_io: Option<&CaptureIo>,
-    ) -> Result<bool> {
-        if !quiet {
-            write!(io.stdout, "Test {:30} ", self.item)?;
-        }
+    fn sleep(&self, duration: Duration) -> SpawnFuture {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
 
-        let result = match vm.execute(self.hash, ()) {
-            Err(err) => Err(err),
-            Ok(mut execution) => execution.async_complete().await,
+fn report(io: &mut Io<'_>, test: &TestResult, quiet: bool) -> Result<()> {
+    if quiet {
+        let c = match &test.outcome {
+            TestOutcome::Failed(TestFailure::Crash { .. }) => "F",
+            TestOutcome::Failed(TestFailure::ReturnedErr { .. }) => "f",
+            TestOutcome::Failed(TestFailure::ReturnedNone) => "n",
+            TestOutcome::Failed(TestFailure::DidNotPanic) => "p",
+            TestOutcome::Ignored => "i",
+            TestOutcome::Skipped => "s",
+            _ => ".",
         };
 
-        if let Some(capture_io) = capture_io {
-            let _ = capture_io.drain_into(&mut self.buf);
-        }
-
-        self.outcome = match result {
-            Err(e) => Some(FailureReason::Crash(e)),
-            Ok(v) => match v {
-                Value::Result(result) => match result.take()? {
-                    Ok(..) => None,
-                    Err(error) => Some(FailureReason::ReturnedErr {
-                        output: self.buf.as_slice().into(),
-                        error,
-                    }),
-                },
-                Value::Option(option) => match *option.borrow_ref()? {
-                    Some(..) => None,
-                    None => Some(FailureReason::ReturnedNone),
-                },
-                _ => None,
-            },
+        write!(io.stdout, "{}", c)?;
+    } else {
+        let status = match &test.outcome {
+            TestOutcome::Failed(TestFailure::Crash { .. }) => "failed",
+            TestOutcome::Failed(TestFailure::ReturnedErr { .. }) => "returned error",
+            TestOutcome::Failed(TestFailure::ReturnedNone) => "returned none",
+            TestOutcome::Failed(TestFailure::DidNotPanic) => "did not panic",
+            TestOutcome::Ignored => "ignored",
+            TestOutcome::Skipped => "skipped",
+            _ => "passed",
         };
 
-        if quiet {
-            match &self.outcome {
-                Some(FailureReason::Crash { .. }) => {
-                    write!(io.stdout, "F")?;
-                }
-                Some(FailureReason::ReturnedErr { .. }) => {
-                    write!(io.stdout, "f")?;
-                }
-                Some(FailureReason::ReturnedNone { .. }) => {
-                    write!(io.stdout, "n")?;
-                }
-                None => {
-                    write!(io.stdout, ".")?;
-                }
-            }
-        } else {
-            match &self.outcome {
-                Some(FailureReason::Crash { .. }) => {
-                    writeln!(io.stdout, "failed")?;
-                }
-                Some(FailureReason::ReturnedErr { .. }) => {
-                    writeln!(io.stdout, "returned error")?;
-                }
-                Some(FailureReason::ReturnedNone { .. }) => {
-                    writeln!(io.stdout, "returned none")?;
-                }
-                None => {
-                    writeln!(io.stdout, "passed")?;
-                }
-            }
-        }
-
-        self.buf.clear();
-        Ok(self.outcome.is_none())
+        writeln!(io.stdout, "Test {:30} {}", test.item, status)?;
     }
 
-    fn emit(&self, io: &mut Io<'_>, sources: &Sources) -> Result<()> {
-        if let Some(outcome) = &self.outcome {
-            match outcome {
-                FailureReason::Crash(err) => {
-                    writeln!(io.stdout, "----------------------------------------")?;
-                    writeln!(io.stdout, "Test: {}\n", self.item)?;
-                    err.emit(io.stdout, sources)?;
-                }
-                FailureReason::ReturnedNone { .. } => {}
-                FailureReason::ReturnedErr { output, error, .. } => {
-                    writeln!(io.stdout, "----------------------------------------")?;
-                    writeln!(io.stdout, "Test: {}\n", self.item)?;
-                    writeln!(io.stdout, "Error: {:?}\n", error)?;
-                    writeln!(io.stdout, "-- output --")?;
-                    io.stdout.write_all(output)?;
-                    writeln!(io.stdout, "-- end of output --")?;
-                }
-            }
+    Ok(())
+}
+
+fn emit(io: &mut Io<'_>, test: &TestResult, sources: &Sources) -> Result<()> {
+    let failure = match &test.outcome {
+        TestOutcome::Failed(failure) => failure,
+        _ => return Ok(()),
+    };
+
+    writeln!(io.stdout, "----------------------------------------")?;
+    writeln!(io.stdout, "Test: {}\n", test.item)?;
+
+    match failure {
+        TestFailure::Crash(err) => {
+            err.emit(io.stdout, sources)?;
         }
+        failure => {
+            writeln!(io.stdout, "{}\n", failure)?;
+        }
+    }
 
-        Ok(())
+    if !test.output.is_empty() {
+        writeln!(io.stdout, "-- output --")?;
+        io.stdout.write_all(&test.output)?;
+        writeln!(io.stdout, "-- end of output --")?;
     }
+
+    Ok(())
 }
 
 pub(crate) async fn run(
     io: &mut Io<'_>,
+    c: &Config,
     flags: &Flags,
-    context: &Context,
-    capture_io: Option<&CaptureIo>,
-    unit: Arc<Unit>,
-    sources: &Sources,
-    fns: &[(Hash, ItemBuf)],
-) -> anyhow::Result<ExitCode> {
-    let runtime = Arc::new(context.runtime());
-
-    let mut cases = fns
-        .iter()
-        .map(|v| TestCase::from_parts(v.0, &v.1))
-        .collect::<Vec<_>>();
+    options: &Options,
+    paths: &[PathBuf],
+) -> Result<ExitCode> {
+    let mut context = flags.shared.context(c)?;
+    context.set_executor(Tokio(Handle::current()));
+
+    let mut test_options = TestOptions::default();
+    test_options.compile = options.clone();
+    test_options.filters = flags.filters.clone();
+    test_options.ignored = flags.ignored;
+    test_options.include_ignored = flags.include_ignored;
+    test_options.fail_fast = !flags.no_fail_fast;
+    test_options.capture = true;
+    test_options.jobs = match flags.jobs {
+        Some(jobs) => jobs,
+        None => thread::available_parallelism().unwrap_or(test_options.jobs),
+    };
+
+    // Tests are collected across all paths, so that they are reported on in a
+    // single summary.
+    let mut reports = Vec::new();
+
+    for path in paths {
+        let source = Source::from_path(path)
+            .with_context(|| anyhow!("cannot read file: {}", path.display()))?;
+
+        let mut sources = Sources::new();
+        sources.insert(source);
+
+        let report = testing::run_tests(&context, &mut sources, &test_options).await;
+
+        if report.diagnostics.has_error() || flags.shared.warnings {
+            report.diagnostics.emit(io.stdout, &sources)?;
+        }
 
-    if cases.is_empty() {
-        return Ok(ExitCode::Success);
-    }
+        if report.diagnostics.has_error() {
+            return Ok(ExitCode::Failure);
+        }
 
-    writeln!(io.stdout, "Found {} tests...", cases.len())?;
+        let failed = report.failures().next().is_some();
+        reports.push((report, sources));
 
-    let start = Instant::now();
-    let mut failure_count = 0;
-    let mut executed_count = 0;
-
-    let mut vm = Vm::new(runtime.clone(), unit.clone());
+        if failed && test_options.fail_fast {
+            break;
+        }
+    }
 
-    for test in &mut cases {
-        executed_count += 1;
+    let count = reports
+        .iter()
+        .map(|(report, _)| report.tests.len())
+        .sum::<usize>();
 
-        let success = test.execute(io, &mut vm, flags.quiet, capture_io).await?;
+    if count == 0 {
+        return Ok(ExitCode::Success);
+    }
 
-        if !success {
-            failure_count += 1;
+    writeln!(io.stdout, "Found {} tests...", count)?;
 
-            if !flags.no_fail_fast {
-                break;
-            }
+    for (test_report, _) in &reports {
+        for test in &test_report.tests {
+            report(io, test, flags.quiet)?;
         }
     }
 
@@ -194,23 +192,59 @@ pub(crate) async fn run(
         writeln!(io.stdout)?;
     }
 
-    let elapsed = start.elapsed();
+    let mut executed_count = 0;
+    let mut failures = Vec::new();
+    let mut ignored_count = 0;
+    let mut filtered_count = 0;
+    let mut elapsed = Duration::default();
+
+    for (report, sources) in &reports {
+        let TestReport {
+            tests,
+            filtered,
+            elapsed: report_elapsed,
+            ..
+        } = report;
+
+        filtered_count += filtered;
+        elapsed += *report_elapsed;
+
+        for test in tests {
+            emit(io, test, sources)?;
+
+            match &test.outcome {
+                TestOutcome::Passed => executed_count += 1,
+                TestOutcome::Failed(..) => {
+                    executed_count += 1;
+                    failures.push(&test.item);
+                }
+                TestOutcome::Ignored => ignored_count += 1,
+                _ => (),
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        writeln!(io.stdout, "Failures:")?;
 
-    for case in &cases {
-        case.emit(io, sources)?;
+        for item in &failures {
+            writeln!(io.stdout, "    {}", item)?;
+        }
     }
 
     writeln!(io.stdout, "====")?;
     writeln!(
         io.stdout,
-        "Executed {} tests with {} failures ({} skipped) in {:.3} seconds",
+        "Executed {} tests with {} failures ({} skipped, {} ignored, {} filtered out) in {:.3} seconds",
         executed_count,
-        failure_count,
-        cases.len() - executed_count,
+        failures.len(),
+        count - executed_count - ignored_count,
+        ignored_count,
+        filtered_count,
         elapsed.as_secs_f64()
     )?;
 
-    if failure_count == 0 {
+    if failures.is_empty() {
         Ok(ExitCode::Success)
     } else {
         Ok(ExitCode::Failure)
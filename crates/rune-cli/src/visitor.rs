@@ -6,16 +6,25 @@ use rune::Hash;
 pub(crate) enum Attribute {
     /// Do not collect any functions.
     None,
-    /// Collect `#[test]` functions.
-    Test,
     /// Collect `#[bench]` functions.
     Bench,
 }
 
+/// A function collected by [FunctionVisitor].
+#[derive(Debug, Clone)]
+pub(crate) struct Function {
+    /// The hash of the function.
+    pub(crate) hash: Hash,
+    /// The item of the function.
+    pub(crate) item: ItemBuf,
+    /// If the function has an `#[ignore]` attribute.
+    pub(crate) is_ignored: bool,
+}
+
 /// A compile visitor that collects functions with a specific attribute.
 pub struct FunctionVisitor {
     attribute: Attribute,
-    functions: Vec<(Hash, ItemBuf)>,
+    functions: Vec<Function>,
 }
 
 impl FunctionVisitor {
@@ -27,19 +36,29 @@ impl FunctionVisitor {
     }
 
     /// Convert visitor into test functions.
-    pub(crate) fn into_functions(self) -> Vec<(Hash, ItemBuf)> {
+    pub(crate) fn into_functions(self) -> Vec<Function> {
         self.functions
     }
 }
 
 impl CompileVisitor for FunctionVisitor {
     fn register_meta(&mut self, meta: MetaRef<'_>) {
-        let type_hash = match (self.attribute, &meta.kind) {
-            (Attribute::Test, MetaKind::Function { is_test, .. }) if *is_test => meta.hash,
-            (Attribute::Bench, MetaKind::Function { is_bench, .. }) if *is_bench => meta.hash,
+        let is_ignored = match (self.attribute, &meta.kind) {
+            (
+                Attribute::Bench,
+                MetaKind::Function {
+                    is_bench,
+                    is_ignored,
+                    ..
+                },
+            ) if *is_bench => *is_ignored,
             _ => return,
         };
 
-        self.functions.push((type_hash, meta.item.to_owned()));
+        self.functions.push(Function {
+            hash: meta.hash,
+            item: meta.item.to_owned(),
+            is_ignored,
+        });
     }
 }
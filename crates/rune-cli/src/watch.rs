@@ -0,0 +1,91 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use rune::{Context, Options};
+
+use crate::{loader, run, visitor, Args, Config, ExitCode, Io};
+
+/// How often watched files are checked for modifications.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Run the given paths, and run them again whenever they or any of the
+/// modules they load are modified.
+pub(crate) async fn run(
+    io: &mut Io<'_>,
+    c: &Config,
+    args: &Args,
+    flags: &run::Flags,
+    options: &Options,
+    context: &Context,
+    paths: &[PathBuf],
+) -> Result<ExitCode> {
+    // The bytecode cache is only invalidated when the entry file changes, so
+    // it can't be trusted once other modules are modified.
+    let mut options = *options;
+    options.bytecode(false);
+
+    let mut watched = paths.to_vec();
+
+    loop {
+        let mut files = paths.to_vec();
+        let mut failed = false;
+
+        for path in paths {
+            match loader::load(io, context, args, &options, path, visitor::Attribute::None) {
+                Ok(load) => {
+                    files.extend(
+                        load.sources
+                            .iter()
+                            .filter_map(|s| s.path().map(Path::to_owned)),
+                    );
+                    // Errors raised while running are reported by the runner,
+                    // after which we keep watching.
+                    run::run(io, c, flags, context, load.unit, &load.sources).await?;
+                }
+                Err(error) => {
+                    // Diagnostics have already been emitted while loading.
+                    writeln!(io.stdout, "Error: {}", error)?;
+                    failed = true;
+                }
+            }
+        }
+
+        // Modules which failed to load can't be discovered, so keep watching
+        // everything that was loaded before.
+        if failed {
+            files.extend(watched);
+        }
+
+        files.sort();
+        files.dedup();
+        watched = files;
+
+        writeln!(
+            io.stderr,
+            "Watching {} file(s) for changes, press Ctrl-C to exit",
+            watched.len()
+        )?;
+
+        wait_for_change(&watched).await;
+    }
+}
+
+/// Wait until any of the given files are modified, created or removed.
+async fn wait_for_change(paths: &[PathBuf]) {
+    let initial = paths.iter().map(|p| modified(p)).collect::<Vec<_>>();
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        if paths.iter().zip(&initial).any(|(p, m)| modified(p) != *m) {
+            return;
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).ok()?.modified().ok()
+}
@@ -0,0 +1,330 @@
+//! Fixes for diagnostics which can be applied automatically, offered as code
+//! actions.
+
+use rune::ast::{self, Span, Spanned};
+use rune::compile::{CompileErrorKind, ComponentRef, Item, MetaKind};
+use rune::diagnostics::{Diagnostic, FatalDiagnosticKind, WarningDiagnosticKind};
+use rune::parse::{Expectation, ParseErrorKind};
+use rune::{Context, SourceId};
+
+use crate::state::{contains, span_to_lsp_range, Index};
+
+/// A fix for a diagnostic.
+pub(crate) struct Fix {
+    /// The range of the diagnostic being fixed.
+    pub(crate) range: lsp::Range,
+    /// A description of what the fix does.
+    pub(crate) title: String,
+    /// Edits to make to the source the diagnostic was reported in.
+    pub(crate) edits: Vec<lsp::TextEdit>,
+    /// Whether the fix is the one which is most likely wanted.
+    pub(crate) preferred: bool,
+}
+
+/// Suggest fixes for the given diagnostic, which was reported in `source`.
+///
+/// Whether something is used is only known if the build is `complete`, so
+/// nothing is removed unless it is.
+pub(crate) fn fixes(
+    context: &Context,
+    index: &Index,
+    source: &rune::Source,
+    diagnostic: &Diagnostic,
+    complete: bool,
+) -> Vec<Fix> {
+    let mut fixes = Vec::new();
+
+    match diagnostic {
+        Diagnostic::Fatal(fatal) => match fatal.kind() {
+            FatalDiagnosticKind::ParseError(error) => {
+                if let ParseErrorKind::Expected {
+                    expected: Expectation::Punctuation(";"),
+                    ..
+                } = error.kind()
+                {
+                    fixes.extend(missing_semicolon(source, error.span()));
+                }
+            }
+            FatalDiagnosticKind::CompileError(error) => {
+                if let CompileErrorKind::MissingItem { .. }
+                | CompileErrorKind::MissingLocal { .. } = error.kind()
+                {
+                    fixes.extend(import(context, index, source, error.span()));
+                }
+            }
+            _ => (),
+        },
+        Diagnostic::Warning(warning) => {
+            if let (WarningDiagnosticKind::NotUsed { span, .. }, true) = (warning.kind(), complete)
+            {
+                fixes.extend(remove_import(source, *span));
+            }
+        }
+    }
+
+    fixes
+}
+
+/// Prefix the name of an unused variable declared at the given span with an
+/// underscore.
+pub(crate) fn prefix_binding(source: &rune::Source, span: Span) -> Option<Fix> {
+    let name = source.get(span.range())?;
+    let at = span_to_lsp_range(source, Span::point(span.start.into_usize()))?;
+
+    Some(Fix {
+        range: span_to_lsp_range(source, span)?,
+        title: format!("Prefix `{}` with an underscore", name),
+        edits: vec![lsp::TextEdit::new(at, String::from("_"))],
+        preferred: true,
+    })
+}
+
+/// Add the semicolon which was expected in front of the given span.
+fn missing_semicolon(source: &rune::Source, span: Span) -> Option<Fix> {
+    let end = source.get(..span.start.into_usize())?.trim_end().len();
+
+    if end == 0 {
+        return None;
+    }
+
+    let at = span_to_lsp_range(source, Span::point(end))?;
+
+    Some(Fix {
+        range: span_to_lsp_range(source, span)?,
+        title: String::from("Add missing semicolon"),
+        edits: vec![lsp::TextEdit::new(at, String::from(";"))],
+        preferred: true,
+    })
+}
+
+/// Import items with the name of the first component of the unresolved path
+/// at the given span.
+fn import(context: &Context, index: &Index, source: &rune::Source, span: Span) -> Vec<Fix> {
+    let name = match source.get(span.range()) {
+        Some(path) => path.split("::").next().unwrap_or_default().trim(),
+        None => return Vec::new(),
+    };
+
+    if !is_importable(name) {
+        return Vec::new();
+    }
+
+    let mut candidates = Vec::new();
+
+    for (item, _) in context.iter_meta() {
+        if is_named(item, name) {
+            candidates.push(item.to_string().trim_start_matches("::").to_owned());
+        }
+    }
+
+    // Only items declared directly in modules can be imported.
+    for item in index.items.values() {
+        let in_module = match item.parent() {
+            Some(parent) => {
+                parent.is_empty() || matches!(index.metas.get(parent), Some(MetaKind::Module))
+            }
+            None => false,
+        };
+
+        if in_module && is_named(item, name) {
+            candidates.push(format!("crate::{}", item));
+        }
+    }
+
+    candidates.sort();
+    candidates.dedup();
+
+    let (range, at) = match (
+        span_to_lsp_range(source, span),
+        use_position(source, span).and_then(|at| span_to_lsp_range(source, Span::point(at))),
+    ) {
+        (Some(range), Some(at)) => (range, at),
+        _ => return Vec::new(),
+    };
+
+    let preferred = candidates.len() == 1;
+
+    candidates
+        .into_iter()
+        .map(|path| Fix {
+            range,
+            title: format!("Import `{}`", path),
+            edits: vec![lsp::TextEdit::new(at, format!("use {};\n", path))],
+            preferred,
+        })
+        .collect()
+}
+
+/// Remove the import at the given span, unless what's at the span is something
+/// else which isn't used.
+fn remove_import(source: &rune::Source, span: Span) -> Option<Fix> {
+    let file =
+        rune::parse::parse_all::<ast::File>(source.as_str(), SourceId::empty(), true).ok()?;
+    let removed = find_import(&file, span)?;
+
+    // Remove the rest of the line as well if nothing else is on it.
+    let text = source.as_str();
+    let rest = &text[removed.end.into_usize()..];
+    let line = rest.find('\n').map_or(rest.len(), |n| n + 1);
+
+    let removed = if rest[..line].trim().is_empty() && starts_line(text, removed.start.into_usize())
+    {
+        Span::new(removed.start.into_usize(), removed.end.into_usize() + line)
+    } else {
+        removed
+    };
+
+    Some(Fix {
+        range: span_to_lsp_range(source, span)?,
+        title: String::from("Remove unused import"),
+        edits: vec![lsp::TextEdit::new(
+            span_to_lsp_range(source, removed)?,
+            String::new(),
+        )],
+        preferred: true,
+    })
+}
+
+/// What to remove to get rid of an import.
+enum Removal {
+    /// The whole path being searched.
+    Path,
+    /// The given span.
+    Span(Span),
+}
+
+/// Find the span to remove to get rid of the import at the given span in
+/// `file`.
+fn find_import(file: &ast::File, span: Span) -> Option<Span> {
+    for (item, semi) in &file.items {
+        if !contains(item.span(), span) {
+            continue;
+        }
+
+        match item {
+            ast::Item::Use(item) => {
+                return match find_import_path(&item.path, span)? {
+                    Removal::Path => {
+                        let end = semi.map_or(item.span().end, |semi| semi.span().end);
+                        Some(Span::new(item.span().start, end))
+                    }
+                    Removal::Span(span) => Some(span),
+                };
+            }
+            ast::Item::Mod(item) => {
+                if let ast::ItemModBody::InlineBody(body) = &item.body {
+                    return find_import(&body.file, span);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+fn find_import_path(path: &ast::ItemUsePath, span: Span) -> Option<Removal> {
+    if path.span() == span {
+        return Some(Removal::Path);
+    }
+
+    let segments =
+        std::iter::once(&path.first).chain(path.segments.iter().map(|(_, segment)| segment));
+
+    for segment in segments {
+        let group = match segment {
+            ast::ItemUseSegment::Group(group) => group,
+            _ => continue,
+        };
+
+        for (n, (path, comma)) in group.iter().enumerate() {
+            match find_import_path(path, span) {
+                Some(Removal::Path) if group.len() == 1 => return Some(Removal::Path),
+                Some(Removal::Path) => {
+                    // Remove the separating comma along with the path, which
+                    // is the one in front of it for the last path.
+                    let next = group.as_slice().get(n + 1);
+                    let prev = n.checked_sub(1).and_then(|n| group.as_slice().get(n));
+
+                    let removed = match (next, comma, prev) {
+                        (Some((next, _)), _, _) => Span::new(path.span().start, next.span().start),
+                        (None, Some(comma), _) => path.span().join(comma.span()),
+                        (None, None, Some((prev, _))) => {
+                            Span::new(prev.span().end, path.span().end)
+                        }
+                        (None, None, None) => path.span(),
+                    };
+
+                    return Some(Removal::Span(removed));
+                }
+                Some(removal) => return Some(removal),
+                None => (),
+            }
+        }
+    }
+
+    None
+}
+
+/// Find where to insert a `use` item which should be in scope at the given
+/// span.
+fn use_position(source: &rune::Source, span: Span) -> Option<usize> {
+    let file =
+        rune::parse::parse_all::<ast::File>(source.as_str(), SourceId::empty(), true).ok()?;
+    let (file, start) = innermost_file(&file, span, 0);
+
+    // After the last import, or in front of the first item.
+    let last_use = file
+        .items
+        .iter()
+        .rev()
+        .find(|(item, _)| matches!(item, ast::Item::Use(..)));
+
+    if let Some((item, semi)) = last_use {
+        let end = semi.map_or(item.span().end, |semi| semi.span().end);
+        let text = source.as_str();
+        let rest = &text[end.into_usize()..];
+        return Some(end.into_usize() + rest.find('\n').map_or(rest.len(), |n| n + 1));
+    }
+
+    match file.items.first() {
+        Some((item, _)) => Some(item.span().start.into_usize()),
+        None => Some(start),
+    }
+}
+
+/// Find the innermost file or inline module containing the given span, and
+/// the offset at which its body starts.
+fn innermost_file(file: &ast::File, span: Span, start: usize) -> (&ast::File, usize) {
+    for (item, _) in &file.items {
+        if let ast::Item::Mod(item) = item {
+            if let ast::ItemModBody::InlineBody(body) = &item.body {
+                if contains(body.span(), span) {
+                    return innermost_file(&body.file, span, body.open.span().end.into_usize());
+                }
+            }
+        }
+    }
+
+    (file, start)
+}
+
+/// Test if the last component of the given item is `name`.
+fn is_named(item: &Item, name: &str) -> bool {
+    matches!(item.last(), Some(ComponentRef::Str(last)) if last == name)
+}
+
+/// Test if the given name could refer to an item which can be imported.
+fn is_importable(name: &str) -> bool {
+    !matches!(name, "" | "self" | "super" | "crate" | "Self")
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c == '_' || c.is_alphanumeric())
+}
+
+/// Test if the given offset is preceded only by whitespace on its line.
+fn starts_line(text: &str, offset: usize) -> bool {
+    let line = &text[..offset];
+    line[line.rfind('\n').map_or(0, |n| n + 1)..]
+        .trim()
+        .is_empty()
+}
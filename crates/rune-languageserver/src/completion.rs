@@ -0,0 +1,397 @@
+//! Completion of paths, fields, methods and variables.
+
+use hashbrown::HashSet;
+use rune::ast::{self, Span};
+use rune::compile::{ComponentRef, ContextSignature, Item, ItemBuf, MetaKind};
+use rune::runtime::{StaticType, TypeInfo, CHAR_TYPE, FLOAT_TYPE, INTEGER_TYPE, STRING_TYPE};
+use rune::{Context, InstFnKind, SourceId};
+
+use crate::state::{contains, Build, DefinitionSource};
+
+/// Complete what is being typed at the given span, where `line` is the text
+/// on the same line in front of it.
+pub(crate) fn complete(
+    context: &Context,
+    build: &Build,
+    source_id: SourceId,
+    span: Span,
+    line: &str,
+) -> Vec<lsp::CompletionItem> {
+    let before = line.trim_end_matches(is_ident_char);
+    let mut completions = Completions::new(&line[before.len()..]);
+
+    if let Some(path) = before.strip_suffix("::") {
+        let start = path
+            .trim_end_matches(|c| is_ident_char(c) || c == ':')
+            .len();
+        let module = module_at(build, source_id, span);
+        complete_path(&mut completions, context, build, &module, &path[start..]);
+    } else if let Some(receiver) = before.strip_suffix('.') {
+        // Two dots is a range, not a method call.
+        if !receiver.ends_with('.') {
+            complete_method(&mut completions, context, build, receiver);
+        }
+    } else {
+        complete_scope(&mut completions, build, source_id, span);
+    }
+
+    completions.items
+}
+
+/// Collected completions, matching what's being typed.
+struct Completions<'a> {
+    /// The partially typed name.
+    partial: &'a str,
+    /// Labels which have already been completed.
+    seen: HashSet<String>,
+    items: Vec<lsp::CompletionItem>,
+}
+
+impl<'a> Completions<'a> {
+    fn new(partial: &'a str) -> Self {
+        Self {
+            partial,
+            seen: HashSet::new(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Add a completion, unless it doesn't match or has already been added.
+    fn push(&mut self, label: &str, kind: lsp::CompletionItemKind, detail: Option<String>) {
+        if !label.starts_with(self.partial) || !self.seen.insert(label.to_owned()) {
+            return;
+        }
+
+        self.items.push(lsp::CompletionItem {
+            label: label.to_owned(),
+            kind: Some(kind),
+            detail,
+            ..Default::default()
+        });
+    }
+}
+
+/// Complete items in the module referred to by `path`.
+fn complete_path(
+    completions: &mut Completions<'_>,
+    context: &Context,
+    build: &Build,
+    module: &Item,
+    path: &str,
+) {
+    let global = path.starts_with("::");
+    let names = path
+        .trim_start_matches("::")
+        .split("::")
+        .collect::<Vec<_>>();
+
+    // Paths into native modules always start with the name of a crate, like
+    // `std::io`.
+    if let Some((first, rest)) = names.split_first() {
+        let prefix = ItemBuf::with_crate_item(first, rest.iter().copied());
+        let constants = context
+            .iter_constants()
+            .map(|(item, _)| item)
+            .collect::<HashSet<_>>();
+
+        for (item, meta) in context.iter_meta() {
+            if let Some((name, true)) = child(item, &prefix) {
+                let (kind, detail) = match context.lookup_signature(meta.hash) {
+                    Some(signature @ ContextSignature::Instance { .. }) => {
+                        (lsp::CompletionItemKind::METHOD, Some(signature.to_string()))
+                    }
+                    Some(signature) => (
+                        lsp::CompletionItemKind::FUNCTION,
+                        Some(format!("fn {}", signature)),
+                    ),
+                    None if constants.contains(item) => (lsp::CompletionItemKind::CONSTANT, None),
+                    None => (lsp::CompletionItemKind::STRUCT, None),
+                };
+
+                completions.push(name, kind, detail);
+            }
+        }
+
+        for (item, _) in context.iter_meta() {
+            if let Some((name, false)) = child(item, &prefix) {
+                completions.push(name, lsp::CompletionItemKind::MODULE, None);
+            }
+        }
+    }
+
+    // Paths to script items are relative to the current module, unless they
+    // are global.
+    let mut prefix = if global {
+        ItemBuf::new()
+    } else {
+        module.to_owned()
+    };
+
+    for name in names {
+        match name {
+            "crate" => prefix.clear(),
+            "self" => (),
+            "super" => {
+                prefix.pop();
+            }
+            name => prefix.push(name),
+        }
+    }
+
+    for item in build.index.items.values() {
+        if let Some((name, direct)) = child(item, &prefix) {
+            let kind = match build.index.metas.get(item) {
+                Some(kind) if direct => script_kind(*kind),
+                _ => lsp::CompletionItemKind::MODULE,
+            };
+
+            completions.push(name, kind, Some(item.to_string()));
+        }
+    }
+}
+
+/// Complete fields and methods which can be called on `receiver`.
+fn complete_method(
+    completions: &mut Completions<'_>,
+    context: &Context,
+    build: &Build,
+    receiver: &str,
+) {
+    let receiver_type = literal_type(receiver);
+
+    // Script types can't be the type of a literal.
+    if receiver_type.is_none() {
+        complete_script_method(completions, build);
+    }
+
+    for (_, signature) in context.iter_functions() {
+        if let ContextSignature::Instance {
+            name: InstFnKind::Instance(name),
+            self_type_info,
+            ..
+        } = signature
+        {
+            if let Some(expected) = receiver_type {
+                if !matches!(self_type_info, TypeInfo::StaticType(ty) if *ty == expected) {
+                    continue;
+                }
+            }
+
+            completions.push(
+                name,
+                lsp::CompletionItemKind::METHOD,
+                Some(signature.to_string()),
+            );
+        }
+    }
+}
+
+/// Complete fields and methods of types declared in scripts.
+fn complete_script_method(completions: &mut Completions<'_>, build: &Build) {
+    for ((source_id, span), item) in &build.index.items {
+        let name = match item.last() {
+            Some(ComponentRef::Str(name)) => name,
+            _ => continue,
+        };
+
+        match build.index.metas.get(item) {
+            Some(MetaKind::Function { .. }) => {
+                let is_associated = matches!(
+                    item.parent()
+                        .and_then(|parent| build.index.metas.get(parent)),
+                    Some(MetaKind::Struct | MetaKind::TupleStruct | MetaKind::Enum)
+                );
+
+                if is_associated {
+                    completions.push(
+                        name,
+                        lsp::CompletionItemKind::METHOD,
+                        Some(item.to_string()),
+                    );
+                }
+            }
+            Some(MetaKind::Struct) => {
+                for field in struct_fields(build, *source_id, *span) {
+                    completions.push(
+                        &field,
+                        lsp::CompletionItemKind::FIELD,
+                        Some(item.to_string()),
+                    );
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Complete variables and items which are in scope at the given span.
+fn complete_scope(
+    completions: &mut Completions<'_>,
+    build: &Build,
+    source_id: SourceId,
+    span: Span,
+) {
+    let function = enclosing_function(build, source_id, span);
+
+    if let (Some((scope, _)), Some(source), Some(definitions)) = (
+        function,
+        build.sources.get(source_id),
+        build.index.definitions.get(&source_id),
+    ) {
+        for definition in definitions.values() {
+            if let DefinitionSource::Location(location) = &definition.source {
+                // Only variables which have been declared in the current
+                // function before what's being typed are in scope.
+                if !contains(scope, location.span) || location.span.end > span.start {
+                    continue;
+                }
+
+                if let Some(name) = source.get(location.span.range()) {
+                    completions.push(name, lsp::CompletionItemKind::VARIABLE, None);
+                }
+            }
+        }
+    }
+
+    let module = module_at(build, source_id, span);
+
+    for item in build.index.items.values() {
+        if let (Some((name, true)), Some(kind)) =
+            (child(item, &module), build.index.metas.get(item))
+        {
+            completions.push(name, script_kind(*kind), Some(item.to_string()));
+        }
+    }
+}
+
+/// Get the name of the component of `item` which is right below `prefix`, and
+/// whether that component is the last one in `item`.
+fn child<'a>(item: &'a Item, prefix: &Item) -> Option<(&'a str, bool)> {
+    if !item.starts_with(prefix) {
+        return None;
+    }
+
+    let mut it = item.iter().skip(prefix.iter().count());
+
+    let name = match it.next()? {
+        ComponentRef::Str(name) => name,
+        _ => return None,
+    };
+
+    Some((name, it.next().is_none()))
+}
+
+/// Find the innermost function declared around the given span.
+fn enclosing_function(build: &Build, source_id: SourceId, span: Span) -> Option<(Span, &Item)> {
+    build
+        .index
+        .items
+        .iter()
+        .filter(|((declared_in, declared), item)| {
+            *declared_in == source_id
+                && contains(*declared, span)
+                && matches!(
+                    build.index.metas.get(*item),
+                    Some(MetaKind::Function { .. } | MetaKind::ConstFn)
+                )
+        })
+        .map(|((_, declared), item)| (*declared, &**item))
+        .min_by_key(|(declared, _)| declared.range().len())
+}
+
+/// Get the module the given span is in.
+fn module_at(build: &Build, source_id: SourceId, span: Span) -> ItemBuf {
+    if let Some((_, item)) = enclosing_function(build, source_id, span) {
+        return item
+            .parent()
+            .map(|item| item.to_owned())
+            .unwrap_or_default();
+    }
+
+    // Outside of functions, the outermost item declared in the source is in
+    // the module of the source.
+    build
+        .index
+        .items
+        .iter()
+        .filter(|((declared_in, _), _)| *declared_in == source_id)
+        .map(|(_, item)| item)
+        .min_by_key(|item| item.iter().count())
+        .and_then(|item| item.parent())
+        .map(|item| item.to_owned())
+        .unwrap_or_default()
+}
+
+/// Get the names of the fields of the struct declared at the given span.
+fn struct_fields(build: &Build, source_id: SourceId, span: Span) -> Vec<String> {
+    let text = match build
+        .sources
+        .get(source_id)
+        .and_then(|source| source.get(span.range()))
+    {
+        Some(text) => text,
+        None => return Vec::new(),
+    };
+
+    let item = match rune::parse::parse_all::<ast::Item>(text, SourceId::empty(), false) {
+        Ok(ast::Item::Struct(item)) => item,
+        _ => return Vec::new(),
+    };
+
+    match &item.body {
+        ast::ItemStructBody::StructBody(fields) => fields
+            .iter()
+            .filter_map(|(field, _)| text.get(ast::Spanned::span(&field.name).range()))
+            .map(str::to_owned)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Get the type of the given receiver if it's a literal.
+fn literal_type(receiver: &str) -> Option<&'static StaticType> {
+    let receiver = receiver.trim_end();
+
+    if receiver.ends_with('"') {
+        return Some(STRING_TYPE);
+    }
+
+    if receiver.ends_with('\'') {
+        return Some(CHAR_TYPE);
+    }
+
+    let number = &receiver[receiver
+        .trim_end_matches(|c| is_ident_char(c) || c == '.')
+        .len()..];
+
+    if number.starts_with(|c: char| c.is_ascii_digit()) {
+        if number.contains('.') {
+            return Some(FLOAT_TYPE);
+        }
+
+        return Some(INTEGER_TYPE);
+    }
+
+    None
+}
+
+/// The kind of completion for a script item of the given kind.
+fn script_kind(kind: MetaKind) -> lsp::CompletionItemKind {
+    match kind {
+        MetaKind::UnitStruct | MetaKind::TupleStruct | MetaKind::Struct => {
+            lsp::CompletionItemKind::STRUCT
+        }
+        MetaKind::UnitVariant | MetaKind::TupleVariant | MetaKind::StructVariant => {
+            lsp::CompletionItemKind::ENUM_MEMBER
+        }
+        MetaKind::Enum => lsp::CompletionItemKind::ENUM,
+        MetaKind::Function { .. } | MetaKind::ConstFn => lsp::CompletionItemKind::FUNCTION,
+        MetaKind::Const => lsp::CompletionItemKind::CONSTANT,
+        MetaKind::Module => lsp::CompletionItemKind::MODULE,
+        _ => lsp::CompletionItemKind::VALUE,
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
@@ -1,6 +1,7 @@
 //! Types to deserialize.
 
 use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -33,7 +34,8 @@ pub struct ResponseMessage<T, D> {
     pub error: Option<ResponseError<D>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize_repr, Deserialize_repr)]
+#[repr(i32)]
 pub enum Code {
     ParseError = -32700,
     InvalidRequest = -32600,
@@ -45,6 +47,7 @@ pub enum Code {
     ServerNotInitialized = -32002,
     UnknownErrorCode = -32001,
     RequestCancelled = -32800,
+    RequestFailed = -32803,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,246 @@
+//! Formatting of sources.
+//!
+//! This only normalizes whitespace: lines are indented by how deeply nested
+//! they are, trailing whitespace is removed, runs of blank lines are collapsed
+//! and the source ends with a single newline. Nothing inside of strings is
+//! touched, and the code itself is kept as it was written.
+
+/// A line of a source.
+struct Line<'a> {
+    /// The content of the line, without its ending.
+    content: &'a str,
+    /// How the line ends, which is empty for the last line if the source
+    /// doesn't end with a newline.
+    ending: &'a str,
+    /// The formatted content, unless the line should be kept as is.
+    formatted: Option<String>,
+}
+
+/// Format a whole source, returning the formatted text.
+pub(crate) fn document(text: &str, options: &lsp::FormattingOptions) -> String {
+    let lines = lines(text, &indent(options));
+
+    let newline = lines
+        .iter()
+        .map(|line| line.ending)
+        .find(|ending| !ending.is_empty())
+        .unwrap_or("\n");
+
+    let mut output = String::with_capacity(text.len());
+    let mut blank = None;
+
+    for line in &lines {
+        let content = line.formatted.as_deref().unwrap_or(line.content);
+
+        if line.formatted.is_some() && content.is_empty() {
+            if !output.is_empty() {
+                blank = Some(line.ending);
+            }
+
+            continue;
+        }
+
+        if let Some(ending) = blank.take() {
+            output.push_str(if ending.is_empty() { newline } else { ending });
+        }
+
+        output.push_str(content);
+        output.push_str(if line.ending.is_empty() {
+            newline
+        } else {
+            line.ending
+        });
+    }
+
+    output
+}
+
+/// Format the lines `start` to `end` inclusive of a source, returning the
+/// number and formatted content of every line which changed.
+///
+/// Unlike when formatting a whole source lines are never removed, so that the
+/// rest of the source is kept as is.
+pub(crate) fn range<'a>(
+    text: &'a str,
+    options: &lsp::FormattingOptions,
+    start: usize,
+    end: usize,
+) -> Vec<(usize, &'a str, String)> {
+    lines(text, &indent(options))
+        .into_iter()
+        .enumerate()
+        .skip(start)
+        .take(end.saturating_sub(start) + 1)
+        .filter_map(|(n, line)| {
+            let formatted = line.formatted?;
+            (formatted != line.content).then_some((n, line.content, formatted))
+        })
+        .collect()
+}
+
+/// The string to indent lines with for every level of nesting.
+fn indent(options: &lsp::FormattingOptions) -> String {
+    if options.insert_spaces {
+        " ".repeat(options.tab_size as usize)
+    } else {
+        String::from("\t")
+    }
+}
+
+/// Split a source into lines and format them.
+fn lines<'a>(text: &'a str, indent: &str) -> Vec<Line<'a>> {
+    let mut scanner = Scanner::default();
+    let mut continues = false;
+    let mut lines = Vec::new();
+
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\r', '\n']);
+        let ending = &line[content.len()..];
+
+        let in_string = scanner.string.is_some();
+        let in_comment = scanner.comment;
+        let trimmed = content.trim_start();
+
+        let closers = trimmed
+            .chars()
+            .take_while(|c| matches!(c, ')' | ']' | '}'))
+            .count();
+
+        let mut level = match scanner.open.len().checked_sub(closers) {
+            Some(n) if closers > 0 => scanner.open[n],
+            _ => scanner.open.last().map_or(0, |level| level + 1),
+        };
+
+        if !in_string && !in_comment && (continues || is_continuation(trimmed)) {
+            level += 1;
+        }
+
+        let end = scanner.scan(content, level);
+
+        if end > 0 {
+            let code = content[..end].trim_end();
+            continues = ["=", "=>", "&&", "||"]
+                .iter()
+                .any(|operator| code.ends_with(operator));
+        }
+
+        // Whitespace at the end of a line which is inside of a string is part
+        // of it.
+        let formatted = if in_string {
+            None
+        } else if scanner.string.is_some() {
+            (!in_comment).then(|| format!("{}{}", indent.repeat(level), trimmed))
+        } else if in_comment {
+            Some(content.trim_end().to_owned())
+        } else if trimmed.trim_end().is_empty() {
+            Some(String::new())
+        } else {
+            Some(format!("{}{}", indent.repeat(level), trimmed.trim_end()))
+        };
+
+        lines.push(Line {
+            content,
+            ending,
+            formatted,
+        });
+    }
+
+    lines
+}
+
+/// Test if a line continues the expression on the line before it.
+fn is_continuation(line: &str) -> bool {
+    (line.starts_with('.') && !line.starts_with(".."))
+        || line.starts_with('?')
+        || line.starts_with("&&")
+        || line.starts_with("||")
+}
+
+/// Keeps track of what the end of the lines scanned so far is inside of.
+#[derive(Default)]
+struct Scanner {
+    /// The indentation levels of the lines the open delimiters are on.
+    open: Vec<usize>,
+    /// The quote of the string the last line ended inside of.
+    string: Option<char>,
+    /// Whether the last line ended inside of a block comment.
+    comment: bool,
+}
+
+impl Scanner {
+    /// Scan a line indented to the given level, returning where the code on
+    /// it ends, which excludes comments.
+    fn scan(&mut self, line: &str, level: usize) -> usize {
+        let mut chars = line.char_indices().peekable();
+        let mut end = 0;
+
+        while let Some((n, c)) = chars.next() {
+            if self.comment {
+                if c == '*' && chars.next_if(|(_, c)| *c == '/').is_some() {
+                    self.comment = false;
+                }
+
+                continue;
+            }
+
+            if !c.is_whitespace() {
+                end = n + c.len_utf8();
+            }
+
+            if let Some(quote) = self.string {
+                match c {
+                    '\\' => {
+                        if let Some((n, c)) = chars.next() {
+                            end = n + c.len_utf8();
+                        }
+                    }
+                    c if c == quote => self.string = None,
+                    _ => (),
+                }
+
+                continue;
+            }
+
+            match c {
+                '(' | '[' | '{' => self.open.push(level),
+                ')' | ']' | '}' => {
+                    self.open.pop();
+                }
+                '"' | '`' => self.string = Some(c),
+                // Either a character literal or a label.
+                '\'' => {
+                    let mut rest = line[n + 1..].chars();
+
+                    let len = match (rest.next(), rest.next()) {
+                        (Some('\\'), Some(e)) => {
+                            let escaped = n + 2 + e.len_utf8();
+                            line[escaped..].find('\'').map(|m| escaped + m - n)
+                        }
+                        (Some(c), Some('\'')) => Some(c.len_utf8() + 1),
+                        _ => None,
+                    };
+
+                    if let Some(len) = len {
+                        while chars.next_if(|(m, _)| *m <= n + len).is_some() {}
+                        end = n + len + 1;
+                    }
+                }
+                '/' => match chars.peek() {
+                    Some((_, '/')) => {
+                        end = n;
+                        break;
+                    }
+                    Some((_, '*')) => {
+                        chars.next();
+                        end = n;
+                        self.comment = true;
+                    }
+                    _ => (),
+                },
+                _ => (),
+            }
+        }
+
+        end
+    }
+}
@@ -0,0 +1,149 @@
+//! Descriptions of items and variables shown when hovering over them.
+
+use std::fmt::Write as _;
+
+use rune::ast::{self, Spanned};
+use rune::compile::{Item, MetaKind};
+use rune::runtime::ConstValue;
+use rune::{Context, Hash, SourceId};
+
+use crate::state::{Build, Target};
+
+/// Describe the given target as markdown.
+pub(crate) fn describe(context: &Context, build: &Build, target: &Target) -> Option<String> {
+    match target {
+        Target::Native(item, hash) => Some(native(context, item, *hash)),
+        Target::Declared(source_id, span) => {
+            let text = build.sources.get(*source_id)?.get(span.range())?;
+
+            match build.index.items.get(&(*source_id, *span)) {
+                Some(item) => Some(script(build, item, text)),
+                // Anything else that is declared is a variable.
+                None => Some(markdown(&format!("let {}", text), &[], None)),
+            }
+        }
+    }
+}
+
+/// Describe an item in a native module.
+fn native(context: &Context, item: &Item, hash: Hash) -> String {
+    let signature = if let Some(signature) = context.lookup_signature(hash) {
+        format!("fn {}", signature)
+    } else if let Some((_, value)) = context.iter_constants().find(|(i, _)| *i == item) {
+        format!("const {} = {}", item, const_value(value))
+    } else {
+        format!("type {}", item)
+    };
+
+    let docs = context
+        .lookup_docs(item)
+        .map(|docs| docs.lines())
+        .unwrap_or_default();
+
+    markdown(&signature, docs, Some((item, hash)))
+}
+
+/// Describe an item declared in a script, where `text` is its declaration.
+fn script(build: &Build, item: &Item, text: &str) -> String {
+    let hash = Hash::type_hash(item);
+
+    let signature = match rune::parse::parse_all::<ast::Item>(text, SourceId::empty(), false) {
+        Ok(ast::Item::Fn(f)) => {
+            let mut signature = String::new();
+
+            if f.const_token.is_some() {
+                signature.push_str("const ");
+            }
+
+            if f.async_token.is_some() {
+                signature.push_str("async ");
+            }
+
+            let _ = write!(signature, "fn {}{}", item, &text[f.args.span().range()]);
+            signature
+        }
+        Ok(ast::Item::Struct(s)) => {
+            let body = text[s.ident.span().end.into_usize()..]
+                .trim()
+                .trim_end_matches(';');
+
+            if body.is_empty() {
+                format!("struct {}", item)
+            } else {
+                format!("struct {} {}", item, body)
+            }
+        }
+        Ok(ast::Item::Enum(..)) => format!("enum {}", item),
+        Ok(ast::Item::Const(..)) => {
+            match build.unit.as_ref().and_then(|unit| unit.constant(hash)) {
+                Some(value) => format!("const {} = {}", item, const_value(value)),
+                None => format!("const {}", item),
+            }
+        }
+        _ => match build.index.metas.get(item) {
+            Some(MetaKind::Module) => format!("mod {}", item),
+            _ => item.to_string(),
+        },
+    };
+
+    let docs = build
+        .index
+        .docs
+        .get(item)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    markdown(&signature, docs, Some((item, hash)))
+}
+
+/// Format a description out of a signature, documentation, and the item
+/// being described with its hash.
+fn markdown(signature: &str, docs: &[String], item: Option<(&Item, Hash)>) -> String {
+    let mut out = String::new();
+    out.push_str("```rune\n");
+    out.push_str(signature);
+    out.push_str("\n```\n");
+
+    if !docs.is_empty() {
+        out.push('\n');
+
+        for line in docs {
+            let line = line.trim_end();
+            out.push_str(line.strip_prefix(' ').unwrap_or(line));
+            out.push('\n');
+        }
+    }
+
+    if let Some((item, hash)) = item {
+        let _ = write!(out, "\n---\n\n`{}` `{}`\n", item, hash);
+    }
+
+    out
+}
+
+/// Format a constant value like it would be written in a script.
+fn const_value(value: &ConstValue) -> String {
+    fn join<'a>(values: impl IntoIterator<Item = &'a ConstValue>) -> String {
+        values
+            .into_iter()
+            .map(const_value)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    match value {
+        ConstValue::Unit => String::from("()"),
+        ConstValue::Byte(b) => format!("b{:?}", char::from(*b)),
+        ConstValue::Char(c) => format!("{:?}", c),
+        ConstValue::Bool(b) => b.to_string(),
+        ConstValue::Integer(n) => n.to_string(),
+        ConstValue::Float(n) => format!("{:?}", n),
+        ConstValue::String(s) => format!("{:?}", s),
+        ConstValue::StaticString(s) => format!("{:?}", s.as_str()),
+        ConstValue::Vec(values) => format!("[{}]", join(values)),
+        ConstValue::Tuple(values) => format!("({})", join(values.iter())),
+        ConstValue::Option(Some(value)) => format!("Some({})", const_value(value)),
+        ConstValue::Option(None) => String::from("None"),
+        value => format!("{:?}", value),
+    }
+}
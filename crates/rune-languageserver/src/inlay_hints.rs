@@ -0,0 +1,177 @@
+//! Hints shown in between the code of a source, which are inferred from what
+//! was resolved while building it.
+
+use rune::ast::{self, Span, Spanned};
+use rune::parse::Parser;
+use rune::SourceId;
+
+use crate::settings::InlayHintSettings;
+use crate::state::{span_to_lsp_range, Build, DefinitionKind, DefinitionSource};
+
+/// Collect the hints to show in the given source of a build.
+pub(crate) fn hints(
+    build: &Build,
+    source_id: SourceId,
+    settings: &InlayHintSettings,
+) -> Vec<lsp::InlayHint> {
+    let source = match build.sources.get(source_id) {
+        Some(source) => source,
+        None => return Vec::new(),
+    };
+
+    let mut hints = Vec::new();
+
+    if settings.parameter_names {
+        parameter_names(build, source_id, source, &mut hints);
+    }
+
+    if settings.closure_parameter_counts {
+        closure_parameter_counts(build, source_id, source, &mut hints);
+    }
+
+    hints.sort_by_key(|hint| hint.position);
+    hints
+}
+
+/// Name the parameters arguments are passed as in calls to functions declared
+/// in scripts.
+///
+/// Which function a method call refers to is only known at runtime, so only
+/// calls through paths get hints.
+fn parameter_names(
+    build: &Build,
+    source_id: SourceId,
+    source: &rune::Source,
+    hints: &mut Vec<lsp::InlayHint>,
+) {
+    let definitions = match build.index.definitions.get(&source_id) {
+        Some(definitions) => definitions,
+        None => return,
+    };
+
+    for (span, definition) in definitions {
+        let location = match (definition.kind, &definition.source) {
+            (DefinitionKind::Function, DefinitionSource::SourceMeta(meta)) => meta.location,
+            _ => continue,
+        };
+
+        let parameters = build
+            .sources
+            .get(location.source_id)
+            .and_then(|source| source.get(location.span.range()))
+            .and_then(crate::signature_help::parameters);
+
+        let (parameters, _) = match parameters {
+            Some(parameters) => parameters,
+            None => continue,
+        };
+
+        let arguments = arguments(source.as_str(), span.end.into_usize());
+
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            let text = source.get(argument.range()).unwrap_or_default();
+
+            if !is_hinted(parameter, text) {
+                continue;
+            }
+
+            hints.extend(hint(
+                source,
+                argument.start.into_usize(),
+                format!("{}:", parameter),
+                lsp::InlayHintKind::PARAMETER,
+            ));
+        }
+    }
+}
+
+/// Show how many parameters the closures declared in a source take.
+fn closure_parameter_counts(
+    build: &Build,
+    source_id: SourceId,
+    source: &rune::Source,
+    hints: &mut Vec<lsp::InlayHint>,
+) {
+    for (declared_in, span) in build.index.items.keys() {
+        if *declared_in != source_id {
+            continue;
+        }
+
+        let closure = match source
+            .get(span.range())
+            .map(|text| rune::parse::parse_all::<ast::Expr>(text, SourceId::empty(), false))
+        {
+            Some(Ok(ast::Expr::Closure(closure))) => closure,
+            _ => continue,
+        };
+
+        let count = match &closure.args {
+            ast::ExprClosureArgs::List { args, .. } => args.len(),
+            _ => 0,
+        };
+
+        hints.extend(hint(
+            source,
+            span.start.into_usize(),
+            format!("fn({})", count),
+            lsp::InlayHintKind::TYPE,
+        ));
+    }
+}
+
+/// Get the spans of the arguments of the call whose callee ends at `end`,
+/// unless there is no call there.
+fn arguments(text: &str, end: usize) -> Vec<Span> {
+    let rest = &text[end..];
+    let open = end + (rest.len() - rest.trim_start().len());
+
+    if !text[open..].starts_with('(') {
+        return Vec::new();
+    }
+
+    let mut parser = Parser::new(&text[open..], SourceId::empty(), false);
+
+    let arguments = match parser.parse::<ast::Parenthesized<ast::Expr, rune::T![,]>>() {
+        Ok(arguments) => arguments,
+        Err(..) => return Vec::new(),
+    };
+
+    arguments
+        .iter()
+        .map(|(argument, _)| {
+            let span = argument.span();
+            Span::new(open + span.start.into_usize(), open + span.end.into_usize())
+        })
+        .collect()
+}
+
+/// Test if an argument should be hinted with the name of its parameter, which
+/// isn't the case if the argument already is named like it or the parameter
+/// isn't named at all.
+fn is_hinted(parameter: &str, argument: &str) -> bool {
+    let named = !parameter.starts_with('_')
+        && !parameter.starts_with(|c: char| c.is_ascii_digit())
+        && parameter.chars().all(|c| c == '_' || c.is_alphanumeric());
+
+    named && argument != parameter && !argument.ends_with(&format!(".{}", parameter))
+}
+
+fn hint(
+    source: &rune::Source,
+    offset: usize,
+    label: String,
+    kind: lsp::InlayHintKind,
+) -> Option<lsp::InlayHint> {
+    let position = span_to_lsp_range(source, Span::point(offset))?.start;
+
+    Some(lsp::InlayHint {
+        position,
+        label: lsp::InlayHintLabel::String(label),
+        kind: Some(kind),
+        text_edits: None,
+        tooltip: None,
+        padding_left: None,
+        padding_right: Some(true),
+        data: None,
+    })
+}
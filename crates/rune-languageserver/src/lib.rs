@@ -22,17 +22,25 @@
 //!
 //! This is part of the [Rune language](https://rune-rs.github.io).
 
+mod code_action;
+mod completion;
 mod connection;
 pub mod envelope;
+mod format;
+mod hover;
+mod inlay_hints;
 mod server;
+mod settings;
+mod signature_help;
 mod state;
+mod symbols;
 
 pub const VERSION: &str = include_str!(concat!(env!("OUT_DIR"), "/version.txt"));
 
 pub use crate::connection::stdio;
 pub use crate::connection::{Input, Output};
-pub use crate::server::Server;
-pub use crate::state::State;
+pub use crate::server::{RequestError, Server};
+pub use crate::state::{GotoDefinition, State};
 use anyhow::Result;
 use rune::{Context, Options};
 use tokio::sync::mpsc;
@@ -47,6 +55,17 @@ pub fn run(context: Context, options: Options) -> Result<()> {
     server.request_handler::<lsp::request::Initialize, _, _>(initialize);
 
     server.request_handler::<lsp::request::GotoDefinition, _, _>(goto_definition);
+    server.request_handler::<lsp::request::References, _, _>(references);
+    server.request_handler::<lsp::request::Rename, _, _>(rename);
+    server.request_handler::<lsp::request::Completion, _, _>(completion);
+    server.request_handler::<lsp::request::HoverRequest, _, _>(hover);
+    server.request_handler::<lsp::request::SignatureHelpRequest, _, _>(signature_help);
+    server.request_handler::<lsp::request::InlayHintRequest, _, _>(inlay_hint);
+    server.request_handler::<lsp::request::DocumentSymbolRequest, _, _>(document_symbol);
+    server.request_handler::<lsp::request::Formatting, _, _>(formatting);
+    server.request_handler::<lsp::request::RangeFormatting, _, _>(range_formatting);
+    server.request_handler::<lsp::request::CodeActionRequest, _, _>(code_action);
+    server.request_handler::<lsp::request::WorkspaceSymbolRequest, _, _>(workspace_symbol);
 
     server.notification_handler::<lsp::notification::DidOpenTextDocument, _, _>(
         did_open_text_document,
@@ -61,6 +80,9 @@ pub fn run(context: Context, options: Options) -> Result<()> {
         did_save_text_document,
     );
     server.notification_handler::<lsp::notification::Initialized, _, _>(initialized);
+    server.notification_handler::<lsp::notification::DidChangeConfiguration, _, _>(
+        did_change_configuration,
+    );
 
     tracing::info!("Starting server");
 
@@ -89,10 +111,14 @@ pub fn run(context: Context, options: Options) -> Result<()> {
 async fn initialize(
     state: State,
     output: Output,
-    _: lsp::InitializeParams,
+    params: lsp::InitializeParams,
 ) -> Result<lsp::InitializeResult> {
     state.initialize();
 
+    if let Some(options) = &params.initialization_options {
+        state.update_settings(options).await;
+    }
+
     output
         .log(lsp::MessageType::INFO, "Starting language server")
         .await?;
@@ -102,6 +128,23 @@ async fn initialize(
             lsp::TextDocumentSyncKind::INCREMENTAL,
         )),
         definition_provider: Some(lsp::OneOf::Left(true)),
+        references_provider: Some(lsp::OneOf::Left(true)),
+        rename_provider: Some(lsp::OneOf::Left(true)),
+        hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+        signature_help_provider: Some(lsp::SignatureHelpOptions {
+            trigger_characters: Some(vec![String::from("("), String::from(",")]),
+            ..Default::default()
+        }),
+        document_symbol_provider: Some(lsp::OneOf::Left(true)),
+        document_formatting_provider: Some(lsp::OneOf::Left(true)),
+        document_range_formatting_provider: Some(lsp::OneOf::Left(true)),
+        workspace_symbol_provider: Some(lsp::OneOf::Left(true)),
+        code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
+        inlay_hint_provider: Some(lsp::OneOf::Left(true)),
+        completion_provider: Some(lsp::CompletionOptions {
+            trigger_characters: Some(vec![String::from("."), String::from(":")]),
+            ..Default::default()
+        }),
         ..Default::default()
     };
 
@@ -122,20 +165,195 @@ async fn initialized(_: State, _: Output, _: lsp::InitializedParams) -> Result<(
     Ok(())
 }
 
-/// Handle initialized notification.
-async fn goto_definition(
+/// Handle changes to the settings of the client.
+async fn did_change_configuration(
     state: State,
     _: Output,
+    params: lsp::DidChangeConfigurationParams,
+) -> Result<()> {
+    state.update_settings(&params.settings).await;
+    Ok(())
+}
+
+/// Find the definition of the item at the given position.
+async fn goto_definition(
+    state: State,
+    output: Output,
     params: lsp::GotoDefinitionParams,
 ) -> Result<Option<lsp::GotoDefinitionResponse>> {
-    let position = state
+    let definition = state
         .goto_definition(
             &params.text_document_position_params.text_document.uri,
             params.text_document_position_params.position,
         )
         .await;
 
-    Ok(position.map(lsp::GotoDefinitionResponse::Scalar))
+    match definition {
+        Some(GotoDefinition::Location(location)) => {
+            Ok(Some(lsp::GotoDefinitionResponse::Scalar(location)))
+        }
+        // Native items have no source to go to, so describe them instead.
+        Some(GotoDefinition::Native(description)) => {
+            output
+                .notification::<lsp::notification::ShowMessage>(lsp::ShowMessageParams {
+                    typ: lsp::MessageType::INFO,
+                    message: description,
+                })
+                .await?;
+
+            Ok(None)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Find all references to the item at the given position.
+async fn references(
+    state: State,
+    _: Output,
+    params: lsp::ReferenceParams,
+) -> Result<Option<Vec<lsp::Location>>> {
+    let locations = state
+        .references(
+            &params.text_document_position.text_document.uri,
+            params.text_document_position.position,
+            params.context.include_declaration,
+        )
+        .await;
+
+    Ok(locations)
+}
+
+/// Rename the item at the given position.
+async fn rename(
+    state: State,
+    _: Output,
+    params: lsp::RenameParams,
+) -> Result<Option<lsp::WorkspaceEdit>> {
+    state
+        .rename(
+            &params.text_document_position.text_document.uri,
+            params.text_document_position.position,
+            &params.new_name,
+        )
+        .await
+}
+
+/// Complete what is being typed at the given position.
+async fn completion(
+    state: State,
+    _: Output,
+    params: lsp::CompletionParams,
+) -> Result<Option<lsp::CompletionResponse>> {
+    let items = state
+        .complete(
+            &params.text_document_position.text_document.uri,
+            params.text_document_position.position,
+        )
+        .await;
+
+    Ok(items.map(lsp::CompletionResponse::Array))
+}
+
+/// Describe the item at the given position.
+async fn hover(state: State, _: Output, params: lsp::HoverParams) -> Result<Option<lsp::Hover>> {
+    let hover = state
+        .hover(
+            &params.text_document_position_params.text_document.uri,
+            params.text_document_position_params.position,
+        )
+        .await;
+
+    Ok(hover)
+}
+
+/// Describe the signature of the function being called at the given position.
+async fn signature_help(
+    state: State,
+    _: Output,
+    params: lsp::SignatureHelpParams,
+) -> Result<Option<lsp::SignatureHelp>> {
+    let help = state
+        .signature_help(
+            &params.text_document_position_params.text_document.uri,
+            params.text_document_position_params.position,
+        )
+        .await;
+
+    Ok(help)
+}
+
+/// Get the inlay hints to show in a range of a document.
+async fn inlay_hint(
+    state: State,
+    _: Output,
+    params: lsp::InlayHintParams,
+) -> Result<Option<Vec<lsp::InlayHint>>> {
+    let hints = state
+        .inlay_hints(&params.text_document.uri, params.range)
+        .await;
+    Ok(hints)
+}
+
+/// Format a document.
+async fn formatting(
+    state: State,
+    _: Output,
+    params: lsp::DocumentFormattingParams,
+) -> Result<Option<Vec<lsp::TextEdit>>> {
+    let edits = state
+        .formatting(&params.text_document.uri, &params.options)
+        .await;
+    Ok(edits)
+}
+
+/// Format a range of a document.
+async fn range_formatting(
+    state: State,
+    _: Output,
+    params: lsp::DocumentRangeFormattingParams,
+) -> Result<Option<Vec<lsp::TextEdit>>> {
+    let edits = state
+        .range_formatting(&params.text_document.uri, params.range, &params.options)
+        .await;
+    Ok(edits)
+}
+
+/// Get the outline of a document.
+async fn document_symbol(
+    state: State,
+    _: Output,
+    params: lsp::DocumentSymbolParams,
+) -> Result<Option<lsp::DocumentSymbolResponse>> {
+    let symbols = state.document_symbols(&params.text_document.uri).await;
+    Ok(symbols.map(lsp::DocumentSymbolResponse::Nested))
+}
+
+/// Search for symbols across the workspace.
+async fn workspace_symbol(
+    state: State,
+    _: Output,
+    params: lsp::WorkspaceSymbolParams,
+) -> Result<Option<lsp::WorkspaceSymbolResponse>> {
+    let symbols = state.workspace_symbols(&params.query).await;
+    Ok(Some(lsp::WorkspaceSymbolResponse::Nested(symbols)))
+}
+
+/// Get the fixes for diagnostics in the given range.
+async fn code_action(
+    state: State,
+    _: Output,
+    params: lsp::CodeActionParams,
+) -> Result<Option<lsp::CodeActionResponse>> {
+    let actions = state
+        .code_actions(
+            &params.text_document.uri,
+            params.range,
+            &params.context.diagnostics,
+        )
+        .await;
+
+    Ok(Some(actions))
 }
 
 /// Handle open text document.
@@ -4,6 +4,7 @@ use crate::State;
 use anyhow::Result;
 use hashbrown::HashMap;
 use rune::{Context, Options};
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use tokio::sync::mpsc;
@@ -14,6 +15,35 @@ pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + 'static>>;
 /// A raw message handler.
 type Handler = dyn Fn(State, Output, IncomingMessage) -> BoxFuture<Result<()>> + 'static;
 
+/// An error which is sent as the response to the request that caused it,
+/// instead of stopping the server.
+#[derive(Debug)]
+pub struct RequestError {
+    code: Code,
+    message: String,
+}
+
+impl RequestError {
+    /// Construct a new request error with the given code and message.
+    pub fn new<M>(code: Code, message: M) -> Self
+    where
+        M: fmt::Display,
+    {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl std::error::Error for RequestError {}
+
 /// An lsp server implementation.
 pub struct Server {
     /// Shared server state.
@@ -99,7 +129,18 @@ impl Server {
             Box::pin(async move {
                 use serde::de::Deserialize as _;
                 let params = <T::Params>::deserialize(incoming.params)?;
-                let result = handler(state, output.clone(), params).await?;
+
+                let result = match handler(state, output.clone(), params).await {
+                    Ok(result) => result,
+                    Err(error) => {
+                        let error = error.downcast::<RequestError>()?;
+                        output
+                            .error(incoming.id, error.code, error.message, None::<()>)
+                            .await?;
+                        return Ok(());
+                    }
+                };
+
                 output.response(incoming.id, result).await?;
                 Ok(())
             })
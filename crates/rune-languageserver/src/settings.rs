@@ -0,0 +1,40 @@
+//! Settings which are provided by the client.
+
+use serde::Deserialize;
+
+/// Settings of the language server, which clients provide under the `rune`
+/// section of their configuration.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct Settings {
+    /// Which inlay hints to show.
+    pub(crate) inlay_hints: InlayHintSettings,
+}
+
+impl Settings {
+    /// Read settings out of a configuration value, which is either the `rune`
+    /// section or contains it.
+    pub(crate) fn from_value(value: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        let value = value.get("rune").unwrap_or(value);
+        serde_json::from_value(value.clone())
+    }
+}
+
+/// Which inlay hints to show.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct InlayHintSettings {
+    /// Show the names of parameters in front of the arguments they are passed.
+    pub(crate) parameter_names: bool,
+    /// Show how many parameters closures take.
+    pub(crate) closure_parameter_counts: bool,
+}
+
+impl Default for InlayHintSettings {
+    fn default() -> Self {
+        Self {
+            parameter_names: true,
+            closure_parameter_counts: true,
+        }
+    }
+}
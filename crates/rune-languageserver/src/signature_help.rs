@@ -0,0 +1,429 @@
+//! Signatures of the function being called, shown while typing its arguments.
+
+use rune::ast::{self, Spanned};
+use rune::compile::{ComponentRef, ContextSignature, Item, MetaKind};
+use rune::{Context, Hash, InstFnKind, SourceId};
+
+use crate::state::Build;
+
+/// Keywords which can be followed by a parenthesized expression without it
+/// being a call.
+const KEYWORDS: &[&str] = &[
+    "if", "else", "while", "for", "in", "loop", "match", "return", "break", "yield", "await",
+    "let", "fn", "not", "select",
+];
+
+/// Describe the signatures of the function being called at the end of `text`,
+/// which is the source up until the cursor.
+///
+/// Since the build is typically stale while arguments are being typed, the
+/// function is looked up by name rather than resolved.
+pub(crate) fn help(
+    context: &Context,
+    build: Option<&Build>,
+    text: &str,
+) -> Option<lsp::SignatureHelp> {
+    let call = find_call(text)?;
+
+    let mut signatures = Vec::new();
+
+    if let Some(build) = build {
+        signatures.extend(script(build, &call));
+    }
+
+    let mut natives = native(context, &call);
+    natives.sort_by(|a, b| a.label.cmp(&b.label));
+    signatures.extend(natives);
+    signatures.dedup_by(|a, b| a.label == b.label);
+
+    if signatures.is_empty() {
+        return None;
+    }
+
+    let signatures = signatures
+        .into_iter()
+        .map(|signature| signature.into_lsp(&call))
+        .collect();
+
+    Some(lsp::SignatureHelp {
+        signatures,
+        active_signature: Some(0),
+        active_parameter: None,
+    })
+}
+
+/// A call whose arguments are being typed.
+struct Call<'a> {
+    /// The path to the function being called, or the name of the method.
+    path: Vec<&'a str>,
+    /// Whether this is a method call on a value.
+    method: bool,
+    /// The argument being typed, counting from zero.
+    argument: usize,
+}
+
+/// A signature which might be the one of the function being called.
+struct Signature {
+    /// The label of the signature, like `fn foo(a, b)`.
+    label: String,
+    /// Byte offsets of the parameters in the label.
+    parameters: Vec<(usize, usize)>,
+    /// Whether the first parameter is `self`.
+    receiver: bool,
+    /// Whether the last parameter stands for any number of arguments.
+    variadic: bool,
+    /// Documentation for the function.
+    docs: Vec<String>,
+}
+
+impl Signature {
+    fn new(name: &str, parameters: &[String], receiver: bool, variadic: bool) -> Self {
+        let mut label = format!("fn {}(", name);
+        let mut offsets = Vec::new();
+
+        for (n, parameter) in parameters.iter().enumerate() {
+            if n > 0 {
+                label.push_str(", ");
+            }
+
+            offsets.push((label.len(), label.len() + parameter.len()));
+            label.push_str(parameter);
+        }
+
+        label.push(')');
+
+        Self {
+            label,
+            parameters: offsets,
+            receiver,
+            variadic,
+            docs: Vec::new(),
+        }
+    }
+
+    fn into_lsp(self, call: &Call<'_>) -> lsp::SignatureInformation {
+        // The value a method is called on is passed as `self`.
+        let mut active = call.argument + usize::from(call.method && self.receiver);
+
+        if self.variadic {
+            active = active.min(self.parameters.len().saturating_sub(1));
+        }
+
+        let utf16 = |offset: usize| self.label[..offset].encode_utf16().count() as u32;
+
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|&(start, end)| lsp::ParameterInformation {
+                label: lsp::ParameterLabel::LabelOffsets([utf16(start), utf16(end)]),
+                documentation: None,
+            })
+            .collect();
+
+        let documentation = (!self.docs.is_empty()).then(|| {
+            let value = self
+                .docs
+                .iter()
+                .map(|line| line.trim_end())
+                .map(|line| line.strip_prefix(' ').unwrap_or(line))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            lsp::Documentation::MarkupContent(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value,
+            })
+        });
+
+        lsp::SignatureInformation {
+            label: self.label,
+            documentation,
+            parameters: Some(parameters),
+            active_parameter: Some(active as u32),
+        }
+    }
+}
+
+/// Signatures of functions declared in the script which match the call.
+fn script(build: &Build, call: &Call<'_>) -> Vec<Signature> {
+    let mut signatures = Vec::new();
+
+    for ((source_id, span), item) in &build.index.items {
+        let args = match build.index.metas.get(item) {
+            Some(MetaKind::Function { args, .. }) => *args,
+            Some(MetaKind::ConstFn) => None,
+            _ => continue,
+        };
+
+        if !ends_with(item, &call.path) {
+            continue;
+        }
+
+        let declaration = build
+            .sources
+            .get(*source_id)
+            .and_then(|source| source.get(span.range()))
+            .and_then(parameters);
+
+        let (parameters, receiver) = match declaration {
+            Some(declaration) => declaration,
+            None => match args {
+                Some(args) => ((0..args).map(|n| format!("#{}", n)).collect(), false),
+                None => continue,
+            },
+        };
+
+        if call.method && !receiver {
+            continue;
+        }
+
+        let mut signature = Signature::new(&item.to_string(), &parameters, receiver, false);
+
+        if let Some(docs) = build.index.docs.get(item) {
+            signature.docs = docs.clone();
+        }
+
+        signatures.push(signature);
+    }
+
+    signatures
+}
+
+/// Get the parameters of the function declared by `text`, and whether the
+/// first one is `self`.
+pub(crate) fn parameters(text: &str) -> Option<(Vec<String>, bool)> {
+    let f = match rune::parse::parse_all::<ast::Item>(text, SourceId::empty(), false) {
+        Ok(ast::Item::Fn(f)) => f,
+        _ => return None,
+    };
+
+    let receiver = matches!(f.args.first(), Some((ast::FnArg::SelfValue(..), _)));
+
+    let parameters = f
+        .args
+        .iter()
+        .map(|(arg, _)| text[arg.span().range()].to_owned())
+        .collect();
+
+    Some((parameters, receiver))
+}
+
+/// Signatures of native functions which match the call.
+fn native(context: &Context, call: &Call<'_>) -> Vec<Signature> {
+    let mut signatures = Vec::new();
+
+    for (hash, signature) in context.iter_functions() {
+        let signature = match signature {
+            ContextSignature::Function { item, args, .. } => {
+                if call.method || !ends_with(item, &call.path) {
+                    continue;
+                }
+
+                let (parameters, variadic) = match args {
+                    Some(args) => ((0..*args).map(|n| format!("#{}", n)).collect(), false),
+                    None => (vec![String::from("...")], true),
+                };
+
+                let mut signature = Signature::new(&item.to_string(), &parameters, false, variadic);
+                signature.docs = docs(context, item);
+                signature
+            }
+            ContextSignature::Instance {
+                type_hash,
+                item,
+                name: InstFnKind::Instance(name),
+                args,
+                ..
+            } => {
+                // Only named instance functions can be called as methods,
+                // which excludes field functions.
+                if hash != Hash::instance_function(*type_hash, name.as_ref()) {
+                    continue;
+                }
+
+                let (last, path) = match call.path.split_last() {
+                    Some(split) => split,
+                    None => continue,
+                };
+
+                // Either called as a method, or through the path of its type.
+                if *last != name.as_ref() || !call.method && !ends_with(item, path) {
+                    continue;
+                }
+
+                // The arguments include the instance.
+                let mut parameters = vec![String::from("self")];
+
+                let variadic = match args {
+                    Some(args) => {
+                        parameters.extend((1..*args).map(|n| format!("#{}", n - 1)));
+                        false
+                    }
+                    None => {
+                        parameters.push(String::from("..."));
+                        true
+                    }
+                };
+
+                let function = item.extended(name.as_ref());
+                let mut signature =
+                    Signature::new(&function.to_string(), &parameters, true, variadic);
+                signature.docs = docs(context, &function);
+                signature
+            }
+            _ => continue,
+        };
+
+        signatures.push(signature);
+    }
+
+    signatures
+}
+
+fn docs(context: &Context, item: &Item) -> Vec<String> {
+    context
+        .lookup_docs(item)
+        .map(|docs| docs.lines().to_vec())
+        .unwrap_or_default()
+}
+
+/// Find the innermost call whose argument list is still open at the end of
+/// `text`.
+fn find_call(text: &str) -> Option<Call<'_>> {
+    // Open delimiters, where they are, and how many commas have been seen
+    // directly inside of them.
+    let mut open = Vec::<(char, usize, usize)>::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((n, c)) = chars.next() {
+        match c {
+            '(' | '[' | '{' => open.push((c, n, 0)),
+            ')' | ']' | '}' => {
+                open.pop();
+            }
+            ',' => {
+                if let Some((_, _, commas)) = open.last_mut() {
+                    *commas += 1;
+                }
+            }
+            '"' | '`' => {
+                while let Some((_, s)) = chars.next() {
+                    match s {
+                        '\\' => {
+                            chars.next();
+                        }
+                        s if s == c => break,
+                        _ => (),
+                    }
+                }
+            }
+            // Either a character literal or a label.
+            '\'' => {
+                let mut rest = text[n + 1..].chars();
+
+                let len = match (rest.next(), rest.next()) {
+                    (Some('\\'), Some(e)) => {
+                        let escaped = n + 2 + e.len_utf8();
+                        text[escaped..].find('\'').map(|m| escaped + m - n)
+                    }
+                    (Some(c), Some('\'')) => Some(c.len_utf8() + 1),
+                    _ => None,
+                };
+
+                if let Some(len) = len {
+                    while chars.next_if(|(m, _)| *m <= n + len).is_some() {}
+                }
+            }
+            '/' => match chars.peek() {
+                Some((_, '/')) => while chars.next_if(|(_, c)| *c != '\n').is_some() {},
+                Some((_, '*')) => {
+                    chars.next();
+                    let mut star = false;
+
+                    for (_, c) in chars.by_ref() {
+                        if star && c == '/' {
+                            break;
+                        }
+
+                        star = c == '*';
+                    }
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+    }
+
+    match open.pop()? {
+        ('(', n, argument) => callee(&text[..n], argument),
+        _ => None,
+    }
+}
+
+/// Parse what's being called out of the text in front of an opening
+/// parenthesis.
+fn callee(text: &str, argument: usize) -> Option<Call<'_>> {
+    let text = text.trim_end();
+
+    let start = text
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_alphanumeric() || *c == '_' || *c == ':'))
+        .map_or(0, |(n, c)| n + c.len_utf8());
+
+    let mut path = text[start..].split("::").collect::<Vec<_>>();
+
+    let is_ident = |name: &str| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c == '_' || c.is_alphanumeric())
+    };
+
+    if !path.iter().all(|name| is_ident(name)) {
+        return None;
+    }
+
+    if let [name] = path[..] {
+        if KEYWORDS.contains(&name) {
+            return None;
+        }
+    }
+
+    let prefix = text[..start].trim_end();
+
+    // The function being declared rather than called.
+    if prefix
+        .rsplit(|c: char| !(c == '_' || c.is_alphanumeric()))
+        .next()
+        == Some("fn")
+    {
+        return None;
+    }
+
+    let method = prefix.ends_with('.');
+
+    if method && path.len() != 1 {
+        return None;
+    }
+
+    // Paths relative to the current item are looked up by name.
+    while path.len() > 1 && matches!(path[0], "crate" | "self" | "super" | "Self") {
+        path.remove(0);
+    }
+
+    Some(Call {
+        path,
+        method,
+        argument,
+    })
+}
+
+/// Test if the last components of `item` are the given names.
+fn ends_with(item: &Item, path: &[&str]) -> bool {
+    let mut components = item.iter().rev();
+
+    path.iter().rev().all(|name| match components.next() {
+        Some(ComponentRef::Crate(c) | ComponentRef::Str(c)) => c == *name,
+        _ => false,
+    })
+}
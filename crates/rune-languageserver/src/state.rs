@@ -1,24 +1,28 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use lsp::Url;
 use ropey::Rope;
-use rune::ast::{Span, Spanned};
+use rune::ast::{self, Span, Spanned};
 use rune::compile::{
-    CompileError, CompileVisitor, ComponentRef, FileSourceLoader, Item, LinkerError, Location,
-    MetaKind, MetaRef, SourceMeta,
+    CompileError, CompileVisitor, ComponentRef, FileSourceLoader, Item, ItemBuf, LinkerError,
+    Location, MetaKind, MetaRef, SourceMeta,
 };
 use rune::diagnostics::{Diagnostic, FatalDiagnosticKind};
-use rune::{Context, Options, SourceId};
+use rune::{Context, Hash, Options, SourceId};
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::RwLockWriteGuard;
 use tokio::sync::{mpsc, RwLock};
 
-use crate::Output;
+use crate::code_action::Fix;
+use crate::envelope::Code;
+use crate::settings::Settings;
+use crate::{Output, RequestError};
 
 /// Shared server state.
 #[derive(Clone)]
@@ -35,6 +39,7 @@ impl State {
                 context,
                 options,
                 initialized: Default::default(),
+                settings: Default::default(),
                 sources: Default::default(),
             }),
         }
@@ -50,6 +55,15 @@ impl State {
         self.inner.initialized.load(Ordering::Acquire)
     }
 
+    /// Update the settings provided by the client, out of the given
+    /// configuration value.
+    pub async fn update_settings(&self, value: &serde_json::Value) {
+        match Settings::from_value(value) {
+            Ok(settings) => *self.inner.settings.write().await = settings,
+            Err(error) => tracing::warn!("ignoring invalid settings: {}", error),
+        }
+    }
+
     /// Access sources in the current state.
     pub async fn sources_mut(&self) -> RwLockWriteGuard<'_, Sources> {
         self.inner.sources.write().await
@@ -59,12 +73,13 @@ impl State {
     ///
     /// Sources that have been modified will be marked as dirty.
     pub async fn rebuild_interest(&self) -> Result<()> {
-        self.inner
-            .rebuild_tx
-            .clone()
-            .send(())
-            .await
-            .map_err(|_| anyhow!("failed to send rebuild interest"))
+        // Waiting for room in the channel would block the loop which is
+        // supposed to receive from it. If it's full, a rebuild is already
+        // pending which will pick up any modifications.
+        match self.inner.rebuild_tx.try_send(()) {
+            Ok(()) | Err(TrySendError::Full(())) => Ok(()),
+            Err(TrySendError::Closed(())) => Err(anyhow!("failed to send rebuild interest")),
+        }
     }
 
     /// Find definition at the given uri and LSP position.
@@ -72,38 +87,320 @@ impl State {
         &self,
         uri: &Url,
         position: lsp::Position,
-    ) -> Option<lsp::Location> {
+    ) -> Option<GotoDefinition> {
         let sources = self.inner.sources.read().await;
 
         let source = sources.get(uri)?;
-        let offset = source.lsp_position_to_offset(position);
-        let def = source.find_definition_at(Span::point(offset))?;
+        let (build, source_id) = source.build.as_ref()?;
+        let offset = source.lsp_position_to_offset(position)?;
+        let def = build.find_definition_at(*source_id, Span::point(offset))?;
+
+        let (source_id, span) = match &def.source {
+            DefinitionSource::Native { item, hash } => {
+                let description = describe_native(&self.inner.context, item, *hash);
+                return Some(GotoDefinition::Native(description));
+            }
+            source => source.location(),
+        };
 
-        let url = match def.source.path() {
-            Some(path) => Url::from_file_path(path).ok()?,
-            None => uri.clone(),
+        let location = build.lsp_location(source_id, span, uri)?;
+        tracing::trace!("go to location: {:?}", location);
+        Some(GotoDefinition::Location(location))
+    }
+
+    /// Find all references to the item or variable at the given uri and LSP
+    /// position.
+    pub async fn references(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        include_declaration: bool,
+    ) -> Option<Vec<lsp::Location>> {
+        let sources = self.inner.sources.read().await;
+
+        let source = sources.get(uri)?;
+        let (build, source_id) = source.build.as_ref()?;
+        let offset = source.lsp_position_to_offset(position)?;
+        let target = build.target_at(*source_id, Span::point(offset))?;
+
+        let mut locations = Vec::new();
+
+        for (source_id, span) in build.references(&target, include_declaration)? {
+            locations.extend(build.lsp_location(source_id, span, uri));
+        }
+
+        Some(locations)
+    }
+
+    /// Complete the path, field, method or variable being typed at the given
+    /// uri and LSP position.
+    pub async fn complete(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Option<Vec<lsp::CompletionItem>> {
+        let sources = self.inner.sources.read().await;
+
+        let source = sources.get(uri)?;
+        let (build, source_id) = source.build.as_ref()?;
+        let line = source.line_before(position)?;
+        let offset = source.lsp_position_to_offset(position)?;
+
+        Some(crate::completion::complete(
+            &self.inner.context,
+            build,
+            *source_id,
+            Span::point(offset),
+            &line,
+        ))
+    }
+
+    /// Describe the item or variable at the given uri and LSP position.
+    pub async fn hover(&self, uri: &Url, position: lsp::Position) -> Option<lsp::Hover> {
+        let sources = self.inner.sources.read().await;
+
+        let source = sources.get(uri)?;
+        let (build, source_id) = source.build.as_ref()?;
+        let offset = source.lsp_position_to_offset(position)?;
+        let target = build.target_at(*source_id, Span::point(offset))?;
+        let value = crate::hover::describe(&self.inner.context, build, &target)?;
+
+        Some(lsp::Hover {
+            contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value,
+            }),
+            range: None,
+        })
+    }
+
+    /// Describe the signature of the function being called at the given uri
+    /// and LSP position.
+    pub async fn signature_help(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Option<lsp::SignatureHelp> {
+        let sources = self.inner.sources.read().await;
+
+        let source = sources.get(uri)?;
+        let text = source.to_string();
+        let offset = source.lsp_position_to_offset(position)?;
+        let build = source.build.as_ref().map(|(build, _)| &**build);
+        crate::signature_help::help(&self.inner.context, build, text.get(..offset)?)
+    }
+
+    /// Get the inlay hints to show in the given range of the source at the
+    /// given uri.
+    pub async fn inlay_hints(&self, uri: &Url, range: lsp::Range) -> Option<Vec<lsp::InlayHint>> {
+        let sources = self.inner.sources.read().await;
+        let settings = self.inner.settings.read().await;
+
+        let source = sources.get(uri)?;
+        let (build, source_id) = source.build.as_ref()?;
+
+        let hints = crate::inlay_hints::hints(build, *source_id, &settings.inlay_hints)
+            .into_iter()
+            .filter(|hint| range.start <= hint.position && hint.position <= range.end)
+            .collect();
+
+        Some(hints)
+    }
+
+    /// Format the source at the given uri, which replaces all of it.
+    pub async fn formatting(
+        &self,
+        uri: &Url,
+        options: &lsp::FormattingOptions,
+    ) -> Option<Vec<lsp::TextEdit>> {
+        let sources = self.inner.sources.read().await;
+
+        let source = sources.get(uri)?;
+        let text = source.to_string();
+        let formatted = crate::format::document(&text, options);
+
+        if formatted == text {
+            return Some(Vec::new());
+        }
+
+        let range = source.span_to_lsp_range(Span::new(0, text.len()));
+        Some(vec![lsp::TextEdit::new(range, formatted)])
+    }
+
+    /// Format the lines in the given range of the source at the given uri.
+    pub async fn range_formatting(
+        &self,
+        uri: &Url,
+        range: lsp::Range,
+        options: &lsp::FormattingOptions,
+    ) -> Option<Vec<lsp::TextEdit>> {
+        let sources = self.inner.sources.read().await;
+
+        let source = sources.get(uri)?;
+        let text = source.to_string();
+
+        // A range ending at the start of a line doesn't include it.
+        let mut end = range.end.line;
+
+        if range.end.character == 0 && end > range.start.line {
+            end -= 1;
+        }
+
+        let lines = crate::format::range(&text, options, range.start.line as usize, end as usize);
+
+        let edits = lines
+            .into_iter()
+            .map(|(line, content, formatted)| {
+                let end = content.encode_utf16().count() as u32;
+                let range = lsp::Range::new(
+                    lsp::Position::new(line as u32, 0),
+                    lsp::Position::new(line as u32, end),
+                );
+                lsp::TextEdit::new(range, formatted)
+            })
+            .collect();
+
+        Some(edits)
+    }
+
+    /// Get the outline of the source at the given uri.
+    pub async fn document_symbols(&self, uri: &Url) -> Option<Vec<lsp::DocumentSymbol>> {
+        let sources = self.inner.sources.read().await;
+
+        let source = sources.get(uri)?;
+        let text = source.to_string();
+        let file = rune::parse::parse_all::<ast::File>(&text, SourceId::empty(), true).ok()?;
+
+        Some(crate::symbols::document(&text, &file, &|span| {
+            source.span_to_lsp_range(span)
+        }))
+    }
+
+    /// Search for items declared anywhere in the workspace whose name matches
+    /// the given query.
+    pub async fn workspace_symbols(&self, query: &str) -> Vec<lsp::WorkspaceSymbol> {
+        let sources = self.inner.sources.read().await;
+
+        // Only consider builds of sources, not the builds of their parents
+        // which they are part of.
+        let builds = sources
+            .sources
+            .iter()
+            .filter_map(|(url, source)| match &source.build {
+                Some((build, source_id)) if *source_id == SourceId::new(0) => Some((url, &**build)),
+                _ => None,
+            });
+
+        crate::symbols::workspace(builds, query)
+    }
+
+    /// Get the fixes for diagnostics in the given range of the source at the
+    /// given uri. The diagnostics which are fixed are taken from the ones the
+    /// client has.
+    pub async fn code_actions(
+        &self,
+        uri: &Url,
+        range: lsp::Range,
+        diagnostics: &[lsp::Diagnostic],
+    ) -> Vec<lsp::CodeActionOrCommand> {
+        let sources = self.inner.sources.read().await;
+
+        let source = match sources.get(uri) {
+            Some(source) => source,
+            None => return Vec::new(),
         };
 
-        let source = source.build_sources.as_ref()?.get(def.source.source_id())?;
+        let mut actions = Vec::new();
 
-        let (l, c) = source.pos_to_utf16cu_linecol(def.source.span().start.into_usize());
-        let start = lsp::Position {
-            line: l as u32,
-            character: c as u32,
+        for fix in &source.fixes {
+            if fix.range.end < range.start || range.end < fix.range.start {
+                continue;
+            }
+
+            let fixed = diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.range == fix.range)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let mut changes = std::collections::HashMap::new();
+            changes.insert(uri.clone(), fix.edits.clone());
+
+            actions.push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                title: fix.title.clone(),
+                kind: Some(lsp::CodeActionKind::QUICKFIX),
+                diagnostics: (!fixed.is_empty()).then_some(fixed),
+                edit: Some(lsp::WorkspaceEdit::new(changes)),
+                is_preferred: Some(fix.preferred),
+                ..Default::default()
+            }));
+        }
+
+        actions
+    }
+
+    /// Rename the item or variable at the given uri and LSP position.
+    ///
+    /// Every reference to it in the build it's part of is renamed, which might
+    /// span multiple files.
+    pub async fn rename(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        new_name: &str,
+    ) -> Result<Option<lsp::WorkspaceEdit>> {
+        let sources = self.inner.sources.read().await;
+
+        let (source, (build, source_id)) = match sources.get(uri) {
+            Some(source) => match &source.build {
+                Some(build) => (source, build),
+                None => return Ok(None),
+            },
+            None => return Ok(None),
+        };
+
+        let offset = match source.lsp_position_to_offset(position) {
+            Some(offset) => offset,
+            None => return Ok(None),
         };
 
-        let (l, c) = source.pos_to_utf16cu_linecol(def.source.span().end.into_usize());
-        let end = lsp::Position {
-            line: l as u32,
-            character: c as u32,
+        let target = match build.target_at(*source_id, Span::point(offset)) {
+            Some(target) => target,
+            None => return Ok(None),
         };
 
-        let range = lsp::Range { start, end };
+        if let Target::Native(item, _) = &target {
+            let message = format!("`{}` is defined in a native module", item);
+            return Err(RequestError::new(Code::RequestFailed, message).into());
+        }
 
-        let location = lsp::Location { uri: url, range };
+        if !is_ident(new_name) {
+            let message = format!("`{}` is not a valid identifier", new_name);
+            return Err(RequestError::new(Code::InvalidParams, message).into());
+        }
 
-        tracing::trace!("go to location: {:?}", location);
-        Some(location)
+        if let Some(existing) = build.conflict(&target, new_name) {
+            let message = format!("`{}` is already defined", existing);
+            return Err(RequestError::new(Code::RequestFailed, message).into());
+        }
+
+        let references = match build.references(&target, true) {
+            Some(references) => references,
+            None => return Ok(None),
+        };
+
+        let mut changes = std::collections::HashMap::<_, Vec<_>>::new();
+
+        for (source_id, span) in references {
+            if let Some(location) = build.lsp_location(source_id, span, uri) {
+                changes
+                    .entry(location.uri)
+                    .or_default()
+                    .push(lsp::TextEdit::new(location.range, new_name.to_owned()));
+            }
+        }
+
+        Ok(Some(lsp::WorkspaceEdit::new(changes)))
     }
 
     /// Rebuild the current project.
@@ -111,6 +408,7 @@ impl State {
         let mut inner = self.inner.sources.write().await;
 
         let mut by_url = HashMap::<Url, Vec<lsp::Diagnostic>>::new();
+        let mut fixes_by_url = HashMap::<Url, Vec<Fix>>::new();
 
         for (url, _) in inner.removed.drain(..) {
             by_url.insert(url.clone(), Vec::new());
@@ -124,6 +422,7 @@ impl State {
             tracing::trace!("build: {}", url);
 
             by_url.insert(url.clone(), Default::default());
+            fixes_by_url.insert(url.clone(), Default::default());
 
             let mut sources = rune::Sources::new();
             let input = rune::Source::with_path(url, source.to_string(), url.to_file_path().ok());
@@ -133,13 +432,14 @@ impl State {
             let mut diagnostics = rune::Diagnostics::new();
             let mut visitor = Visitor::new(Index::default());
 
-            let _ = rune::prepare(&mut sources)
+            let unit = rune::prepare(&mut sources)
                 .with_context(&self.inner.context)
                 .with_diagnostics(&mut diagnostics)
                 .with_options(&self.inner.options)
                 .with_visitor(&mut visitor)
                 .with_source_loader(&mut source_loader)
-                .build();
+                .build()
+                .ok();
 
             for diagnostic in diagnostics.diagnostics() {
                 match diagnostic {
@@ -219,18 +519,97 @@ impl State {
                         );
                     }
                 }
+
+                let source_id = match diagnostic {
+                    Diagnostic::Fatal(fatal) => fatal.source_id(),
+                    Diagnostic::Warning(warning) => warning.source_id(),
+                };
+
+                if let Some((url, source)) = source_url(&sources, source_id) {
+                    let fixes = crate::code_action::fixes(
+                        &self.inner.context,
+                        &visitor.index,
+                        source,
+                        diagnostic,
+                        unit.is_some(),
+                    );
+
+                    fixes_by_url.entry(url).or_default().extend(fixes);
+                }
             }
 
-            builds.push((url.clone(), sources, visitor.into_index()));
+            // Sources are often edited into something which doesn't parse,
+            // like while completing, in which case nothing could be indexed.
+            // Queries are then answered using the previous build until it
+            // parses again.
+            if diagnostics.diagnostics().iter().any(is_parse_error) {
+                continue;
+            }
+
+            let index = visitor.into_index();
+
+            // Whether a variable is used is only known once everything which
+            // could use it has been compiled.
+            if unit.is_some() {
+                for (source_id, span) in index.unused_variables() {
+                    let name = match sources.get(source_id).and_then(|s| s.get(span.range())) {
+                        Some(name) if !name.starts_with('_') => name,
+                        _ => continue,
+                    };
+
+                    report(
+                        &sources,
+                        &mut by_url,
+                        span,
+                        source_id,
+                        format!("unused variable `{}`", name),
+                        display_to_unused,
+                    );
+
+                    if let Some((url, source)) = source_url(&sources, source_id) {
+                        let fix = crate::code_action::prefix_binding(source, span);
+                        fixes_by_url.entry(url).or_default().extend(fix);
+                    }
+                }
+            }
+
+            let build = Build {
+                sources,
+                index,
+                unit,
+            };
+
+            builds.push((url.clone(), Arc::new(build)));
         }
 
-        for (url, build_sources, index) in builds {
-            if let Some(source) = inner.sources.get_mut(&url) {
-                source.index = index;
-                source.build_sources = Some(build_sources);
+        for (url, build) in &builds {
+            if let Some(source) = inner.sources.get_mut(url) {
+                source.build = Some((build.clone(), SourceId::new(0)));
             }
         }
 
+        // A source which is loaded as a module by another open source is
+        // answered using that build instead, since it can only be fully
+        // resolved in the context of its parent.
+        for (url, build) in &builds {
+            let modules = (0..).map(SourceId::new).zip(build.sources.iter()).skip(1);
+
+            for (source_id, module) in modules {
+                let module_url = match module.path().map(Url::from_file_path) {
+                    Some(Ok(module_url)) if module_url != *url => module_url,
+                    _ => continue,
+                };
+
+                if let Some(source) = inner.sources.get_mut(&module_url) {
+                    source.build = Some((build.clone(), source_id));
+                }
+            }
+        }
+
+        for (url, source) in &mut inner.sources {
+            source.fixes = fixes_by_url.remove(url).unwrap_or_default();
+        }
+
         for (url, diagnostics) in by_url {
             let diagnostics = lsp::PublishDiagnosticsParams {
                 uri: url.clone(),
@@ -257,6 +636,8 @@ struct Inner {
     options: Options,
     /// Indicate if the server is initialized.
     initialized: AtomicBool,
+    /// Settings provided by the client.
+    settings: RwLock<Settings>,
     /// Sources used in the project.
     sources: RwLock<Sources>,
 }
@@ -275,8 +656,8 @@ impl Sources {
     pub fn insert_text(&mut self, url: Url, text: String) -> Option<Source> {
         let source = Source {
             content: Rope::from(text),
-            index: Default::default(),
-            build_sources: None,
+            build: None,
+            fixes: Vec::new(),
         };
 
         self.sources.insert(url, source)
@@ -300,30 +681,240 @@ impl Sources {
     }
 }
 
-/// A single open source.
-pub struct Source {
-    /// The content of the current source.
-    content: Rope,
+/// The target of a go-to-definition request.
+pub enum GotoDefinition {
+    /// A location in a source file.
+    Location(lsp::Location),
+    /// An item in a native module, which has no source to go to. Contains a
+    /// description of its signature and documentation instead.
+    Native(String),
+}
+
+/// The outcome of building a single source, along with the modules it loaded.
+pub struct Build {
+    /// Rune sources loaded as part of the build.
+    pub(crate) sources: rune::Sources,
     /// Indexes used to answer queries.
-    index: Index,
-    /// Loaded Rune sources for this source file. Will be present after the
-    /// source file has been built.
-    build_sources: Option<rune::Sources>,
+    pub(crate) index: Index,
+    /// The unit produced by the build, unless it failed.
+    pub(crate) unit: Option<rune::Unit>,
 }
 
-impl Source {
-    /// Find the definition at the given span.
-    pub fn find_definition_at(&self, span: Span) -> Option<&Definition> {
-        let (found_span, definition) = self.index.definitions.range(..=span).rev().next()?;
+impl Build {
+    /// Find the definition at the given span in the given source.
+    pub fn find_definition_at(&self, source_id: SourceId, span: Span) -> Option<&Definition> {
+        let definitions = self.index.definitions.get(&source_id)?;
+
+        // Any span starting at or before the given one might contain it, and
+        // the innermost one starts last.
+        let (_, definition) = definitions
+            .range(..=Span::new(span.start, u32::MAX))
+            .rev()
+            .find(|(found, _)| span.start >= found.start && span.end <= found.end)?;
 
-        if span.start >= found_span.start && span.end <= found_span.end {
-            tracing::trace!("found {:?}", definition);
-            return Some(definition);
+        tracing::trace!("found {:?}", definition);
+        Some(definition)
+    }
+
+    /// Find what is referred to or declared at the given span in the given
+    /// source.
+    pub(crate) fn target_at(&self, source_id: SourceId, span: Span) -> Option<Target> {
+        if let Some(definition) = self.find_definition_at(source_id, span) {
+            return definition.source.target();
+        }
+
+        let items = self.index.items.keys().copied();
+
+        let variables = self
+            .index
+            .definitions
+            .get(&source_id)
+            .into_iter()
+            .flat_map(|definitions| definitions.values())
+            .filter_map(|definition| match &definition.source {
+                DefinitionSource::Location(location) => Some((location.source_id, location.span)),
+                _ => None,
+            });
+
+        for (declared_in, declared) in items.chain(variables) {
+            if declared_in != source_id || !contains(declared, span) {
+                continue;
+            }
+
+            let target = Target::Declared(declared_in, declared);
+
+            if let Some((_, name)) = self.declaration(&target) {
+                if contains(name, span) {
+                    return Some(target);
+                }
+            }
         }
 
         None
     }
 
+    /// Get the name of the given target.
+    fn name<'a>(&'a self, target: &'a Target) -> Option<&'a str> {
+        match target {
+            Target::Declared(source_id, span) => match self.index.items.get(&(*source_id, *span)) {
+                Some(item) => match item.last()? {
+                    ComponentRef::Str(name) => Some(name),
+                    _ => None,
+                },
+                None => {
+                    let name = self.sources.get(*source_id)?.get(span.range())?;
+                    is_ident(name).then_some(name)
+                }
+            },
+            Target::Native(item, _) => match item.last()? {
+                ComponentRef::Str(name) => Some(name),
+                _ => None,
+            },
+        }
+    }
+
+    /// Get the span of the name in the declaration of the given target.
+    fn declaration(&self, target: &Target) -> Option<(SourceId, Span)> {
+        let (source_id, span) = match target {
+            Target::Declared(source_id, span) => (*source_id, *span),
+            Target::Native(..) => return None,
+        };
+
+        let name = self.name(target)?;
+        let source = self.sources.get(source_id)?;
+
+        let span = if self.index.items.contains_key(&(source_id, span)) {
+            declared_name_span(source, span, name)?
+        } else {
+            find_name(source, span, name, false)?
+        };
+
+        Some((source_id, span))
+    }
+
+    /// Find the names of all references to the given target, optionally
+    /// including its declaration.
+    fn references(
+        &self,
+        target: &Target,
+        include_declaration: bool,
+    ) -> Option<Vec<(SourceId, Span)>> {
+        let name = self.name(target)?;
+        let mut references = Vec::new();
+
+        if include_declaration {
+            references.extend(self.declaration(target));
+        }
+
+        for (source_id, definitions) in &self.index.definitions {
+            let source = match self.sources.get(*source_id) {
+                Some(source) => source,
+                None => continue,
+            };
+
+            for (span, definition) in definitions {
+                if definition.source.target().as_ref() != Some(target) {
+                    continue;
+                }
+
+                // Paths refer to the item by their last component.
+                if let Some(span) = find_name(source, *span, name, true) {
+                    references.push((*source_id, span));
+                }
+            }
+        }
+
+        references.sort();
+        references.dedup();
+        Some(references)
+    }
+
+    /// Find an existing declaration which renaming the given target to `name`
+    /// would conflict with.
+    fn conflict(&self, target: &Target, name: &str) -> Option<String> {
+        let (source_id, span) = match target {
+            Target::Declared(source_id, span) => (*source_id, *span),
+            Target::Native(..) => return None,
+        };
+
+        if let Some(item) = self.index.items.get(&(source_id, span)) {
+            let renamed = item.parent()?.join([name]);
+            return self
+                .index
+                .items
+                .values()
+                .any(|item| *item == renamed)
+                .then(|| renamed.to_string());
+        }
+
+        // Variables conflict with other variables in the innermost item, like a
+        // function or closure, they're declared in.
+        let (_, scope) = self
+            .index
+            .items
+            .keys()
+            .filter(|(declared_in, declared)| {
+                *declared_in == source_id && contains(*declared, span)
+            })
+            .min_by_key(|(_, declared)| declared.range().len())?;
+
+        let source = self.sources.get(source_id)?;
+
+        self.index
+            .definitions
+            .get(&source_id)?
+            .values()
+            .filter_map(|definition| match &definition.source {
+                DefinitionSource::Location(location) if contains(*scope, location.span) => {
+                    source.get(location.span.range())
+                }
+                _ => None,
+            })
+            .find(|existing| *existing == name)
+            .map(str::to_owned)
+    }
+
+    /// Convert a span in a source of the build into an lsp location. Sources
+    /// without a path are assumed to be at `uri`.
+    pub(crate) fn lsp_location(
+        &self,
+        source_id: SourceId,
+        span: Span,
+        uri: &Url,
+    ) -> Option<lsp::Location> {
+        let source = self.sources.get(source_id)?;
+
+        let uri = match source.path() {
+            Some(path) => Url::from_file_path(path).ok()?,
+            None => uri.clone(),
+        };
+
+        let range = span_to_lsp_range(source, span)?;
+        Some(lsp::Location { uri, range })
+    }
+}
+
+/// Something which can be referred to by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Target {
+    /// An item or variable declared at the given span in the given source.
+    Declared(SourceId, Span),
+    /// An item defined in a native module, and its hash.
+    Native(ItemBuf, Hash),
+}
+
+/// A single open source.
+pub struct Source {
+    /// The content of the current source.
+    content: Rope,
+    /// The build this source is part of, and the id of the source in it. Will
+    /// be present after the source file has been built.
+    build: Option<(Arc<Build>, SourceId)>,
+    /// Fixes for the diagnostics reported for the source in the last build.
+    fixes: Vec<Fix>,
+}
+
+impl Source {
     /// Modify the given lsp range in the file.
     pub fn modify_lsp_range(&mut self, range: lsp::Range, content: &str) -> Result<()> {
         let start = rope_utf16_position(&self.content, range.start)?;
@@ -360,11 +951,43 @@ impl Source {
         lsp::Position::new(line as u32, col_char as u32)
     }
 
-    /// Offset in the rope to lsp position.
-    fn lsp_position_to_offset(&self, position: lsp::Position) -> usize {
-        let line = self.content.line_to_char(position.line as usize);
-        self.content
-            .utf16_cu_to_char(line + position.character as usize)
+    /// Lsp position to byte offset in the rope, or `None` if the position is
+    /// outside of the source.
+    fn lsp_position_to_offset(&self, position: lsp::Position) -> Option<usize> {
+        let line = position.line as usize;
+
+        if line >= self.content.len_lines() {
+            return None;
+        }
+
+        let character = position.character as usize;
+        let slice = self.content.line(line);
+        let mut end = slice.len_chars();
+
+        // NB: the line ending is not part of the line.
+        while end > 0 && matches!(slice.char(end - 1), '\n' | '\r') {
+            end -= 1;
+        }
+
+        if character > slice.char_to_utf16_cu(end) {
+            return None;
+        }
+
+        let start = self.content.line_to_char(line);
+        let start = self.content.char_to_utf16_cu(start);
+        let char = self.content.utf16_cu_to_char(start + character);
+        Some(self.content.char_to_byte(char))
+    }
+
+    /// Get the text on the line of the given lsp position, in front of it.
+    fn line_before(&self, position: lsp::Position) -> Option<String> {
+        if position.line as usize >= self.content.len_lines() {
+            return None;
+        }
+
+        let start = self.content.line_to_char(position.line as usize);
+        let end = rope_utf16_position(&self.content, position).ok()?;
+        Some(self.content.slice(start..end).to_string())
     }
 
     /// Iterate over the text chunks in the source.
@@ -380,7 +1003,7 @@ impl fmt::Display for Source {
 }
 
 /// Convert the given span into an lsp range.
-fn span_to_lsp_range(source: &rune::Source, span: Span) -> Option<lsp::Range> {
+pub(crate) fn span_to_lsp_range(source: &rune::Source, span: Span) -> Option<lsp::Range> {
     let (line, character) = source.pos_to_utf16cu_linecol(span.start.into_usize());
     let start = lsp::Position::new(line as u32, character as u32);
     let (line, character) = source.pos_to_utf16cu_linecol(span.end.into_usize());
@@ -429,16 +1052,8 @@ fn report<E, R>(
     E: fmt::Display,
     R: Fn(lsp::Range, E) -> lsp::Diagnostic,
 {
-    let source = match sources.get(source_id) {
-        Some(source) => source,
-        None => return,
-    };
-
-    let url = match source.path() {
-        Some(path) => match Url::from_file_path(path) {
-            Ok(url) => url,
-            Err(()) => return,
-        },
+    let (url, source) = match source_url(sources, source_id) {
+        Some(found) => found,
         None => return,
     };
 
@@ -451,6 +1066,85 @@ fn report<E, R>(
     diagnostics.push(report(range, error));
 }
 
+/// Get the url of the source with the given id, along with the source.
+fn source_url(sources: &rune::Sources, source_id: SourceId) -> Option<(Url, &rune::Source)> {
+    let source = sources.get(source_id)?;
+    let url = Url::from_file_path(source.path()?).ok()?;
+    Some((url, source))
+}
+
+/// Test if the given diagnostic is an error to parse a source.
+fn is_parse_error(diagnostic: &Diagnostic) -> bool {
+    matches!(
+        diagnostic,
+        Diagnostic::Fatal(fatal) if matches!(fatal.kind(), FatalDiagnosticKind::ParseError(..))
+    )
+}
+
+/// Test if the given span is contained in another span.
+pub(crate) fn contains(outer: Span, inner: Span) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Test if the given string is a valid identifier.
+fn is_ident(string: &str) -> bool {
+    rune::parse::parse_all::<ast::Ident>(string, SourceId::empty(), false).is_ok()
+}
+
+/// Find the span of a whole word occurrence of `name` in the given span of
+/// the source, either the first or the last one.
+fn find_name(source: &rune::Source, span: Span, name: &str, last: bool) -> Option<Span> {
+    let text = source.get(span.range())?;
+    let is_ident_char = |c: char| c == '_' || c.is_alphanumeric();
+
+    let mut found = text.match_indices(name).map(|(at, _)| at).filter(|&at| {
+        !text[..at].ends_with(is_ident_char) && !text[at + name.len()..].starts_with(is_ident_char)
+    });
+
+    let at = if last { found.last()? } else { found.next()? };
+    let start = span.start.into_usize() + at;
+    Some(Span::new(start, start + name.len()))
+}
+
+/// Find the span of the name declared by the item at the given span.
+fn declared_name_span(source: &rune::Source, span: Span, name: &str) -> Option<Span> {
+    let text = source.get(span.range())?;
+
+    let name_span = match rune::parse::parse_all::<ast::Item>(text, SourceId::empty(), false) {
+        Ok(ast::Item::Fn(item)) => item.name.span(),
+        Ok(ast::Item::Enum(item)) => item.name.span(),
+        Ok(ast::Item::Struct(item)) => item.ident.span(),
+        Ok(ast::Item::Const(item)) => item.name.span(),
+        // Things like variants and modules are declared by their first word.
+        _ => return find_name(source, span, name, false),
+    };
+
+    let start = span.start.into_usize() + name_span.start.into_usize();
+    Some(Span::new(start, start + name_span.range().len()))
+}
+
+/// Describe an item in a native module by its signature and documentation.
+fn describe_native(context: &Context, item: &Item, hash: Hash) -> String {
+    let mut description = match context.lookup_signature(hash) {
+        Some(signature) => format!("fn {}", signature),
+        None => item.to_string(),
+    };
+
+    if let Some(docs) = context.lookup_docs(item) {
+        if !docs.is_empty() {
+            description.push('\n');
+
+            for line in docs.lines() {
+                let line = line.trim_end();
+                description.push('\n');
+                description.push_str(line.strip_prefix(' ').unwrap_or(line));
+            }
+        }
+    }
+
+    description
+}
+
 /// Convert the given span and error into an error diagnostic.
 fn display_to_error<E>(range: lsp::Range, error: E) -> lsp::Diagnostic
 where
@@ -467,6 +1161,18 @@ where
     display_to_diagnostic(range, error, lsp::DiagnosticSeverity::WARNING)
 }
 
+/// Convert a span and something displayeable into a hint about something
+/// which is unused.
+fn display_to_unused<E>(range: lsp::Range, error: E) -> lsp::Diagnostic
+where
+    E: fmt::Display,
+{
+    lsp::Diagnostic {
+        tags: Some(vec![lsp::DiagnosticTag::UNNECESSARY]),
+        ..display_to_diagnostic(range, error, lsp::DiagnosticSeverity::HINT)
+    }
+}
+
 /// Convert a span and something displayeable into diagnostics.
 fn display_to_diagnostic<E>(
     range: lsp::Range,
@@ -491,8 +1197,39 @@ where
 
 #[derive(Default)]
 pub struct Index {
-    /// Spans mapping to their corresponding definitions.
-    definitions: BTreeMap<Span, Definition>,
+    /// Spans mapping to their corresponding definitions, by the source they
+    /// occur in.
+    pub(crate) definitions: HashMap<SourceId, BTreeMap<Span, Definition>>,
+    /// Items declared in the build, by the source and span they're declared
+    /// at.
+    pub(crate) items: HashMap<(SourceId, Span), ItemBuf>,
+    /// The kinds of items which have been resolved during the build.
+    pub(crate) metas: HashMap<ItemBuf, MetaKind>,
+    /// Documentation of items declared in the build.
+    pub(crate) docs: HashMap<ItemBuf, Vec<String>>,
+    /// Spans variables are declared at, by the source they're declared in.
+    pub(crate) declarations: HashMap<SourceId, BTreeSet<Span>>,
+}
+
+impl Index {
+    /// Get the variables which are declared but never used.
+    fn unused_variables(&self) -> Vec<(SourceId, Span)> {
+        let used = self
+            .definitions
+            .values()
+            .flat_map(|definitions| definitions.values())
+            .filter_map(|definition| match &definition.source {
+                DefinitionSource::Location(location) => Some((location.source_id, location.span)),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
+        self.declarations
+            .iter()
+            .flat_map(|(source_id, spans)| spans.iter().map(move |span| (*source_id, *span)))
+            .filter(|declaration| !used.contains(declaration))
+            .collect()
+    }
 }
 
 /// A definition source.
@@ -504,29 +1241,40 @@ pub enum DefinitionSource {
     Location(Location),
     /// A complete compile source.
     SourceMeta(SourceMeta),
+    /// An item defined in a native module.
+    Native {
+        /// The item being defined.
+        item: ItemBuf,
+        /// The hash of the item.
+        hash: Hash,
+    },
 }
 
 impl DefinitionSource {
-    fn span(&self) -> Span {
-        match self {
-            Self::Source(..) => Span::empty(),
-            Self::Location(location) => location.span,
-            Self::SourceMeta(compile_source) => compile_source.location.span,
-        }
-    }
-
-    fn source_id(&self) -> SourceId {
+    /// The target being referred to, unless it can't be referred to by name.
+    fn target(&self) -> Option<Target> {
         match self {
-            Self::Source(source_id) => *source_id,
-            Self::Location(location) => location.source_id,
-            Self::SourceMeta(compile_source) => compile_source.location.source_id,
+            Self::Source(..) => None,
+            Self::Location(location) => Some(Target::Declared(location.source_id, location.span)),
+            Self::SourceMeta(compile_source) => Some(Target::Declared(
+                compile_source.location.source_id,
+                compile_source.location.span,
+            )),
+            Self::Native { item, hash } => Some(Target::Native(item.clone(), *hash)),
         }
     }
 
-    fn path(&self) -> Option<&Path> {
+    /// The source and span of the definition. Native items are not defined in
+    /// any source, so they have an empty one.
+    fn location(&self) -> (SourceId, Span) {
         match self {
-            Self::SourceMeta(compile_source) => compile_source.path.as_deref(),
-            _ => None,
+            Self::Source(source_id) => (*source_id, Span::empty()),
+            Self::Location(location) => (location.source_id, location.span),
+            Self::SourceMeta(compile_source) => (
+                compile_source.location.source_id,
+                compile_source.location.span,
+            ),
+            Self::Native { .. } => (SourceId::empty(), Span::empty()),
         }
     }
 }
@@ -557,10 +1305,14 @@ pub enum DefinitionKind {
     Enum,
     /// A function.
     Function,
+    /// A constant.
+    Const,
     /// A local variable.
     Local,
     /// A module that can be jumped to.
     Module,
+    /// A type defined in a native module.
+    Type,
 }
 
 struct Visitor {
@@ -577,19 +1329,41 @@ impl Visitor {
     pub fn into_index(self) -> Index {
         self.index
     }
+
+    /// Insert a definition used at the given location.
+    fn insert(&mut self, location: Location, definition: Definition) {
+        let definitions = self
+            .index
+            .definitions
+            .entry(location.source_id)
+            .or_default();
+
+        if let Some(d) = definitions.insert(location.span, definition) {
+            tracing::warn!("replaced definition: {:?}", d.kind)
+        }
+    }
 }
 
 impl CompileVisitor for Visitor {
-    fn visit_meta(&mut self, location: Location, meta: MetaRef<'_>) {
-        if location.source_id.into_index() != 0 {
-            return;
-        }
+    fn register_meta(&mut self, meta: MetaRef<'_>) {
+        self.index.metas.insert(meta.item.to_owned(), meta.kind);
+    }
 
-        let source = match meta.source {
-            Some(source) => source,
-            None => return,
-        };
+    fn visit_item(&mut self, location: Location, item: &Item) {
+        self.index
+            .items
+            .insert((location.source_id, location.span), item.to_owned());
+    }
+
+    fn visit_doc_comment(&mut self, _: Location, item: &Item, docstr: &str) {
+        self.index
+            .docs
+            .entry(item.to_owned())
+            .or_default()
+            .push(docstr.to_owned());
+    }
 
+    fn visit_meta(&mut self, location: Location, meta: MetaRef<'_>) {
         let kind = match &meta.kind {
             MetaKind::UnitStruct { .. } => DefinitionKind::UnitStruct,
             MetaKind::TupleStruct { .. } => DefinitionKind::TupleStruct,
@@ -598,48 +1372,47 @@ impl CompileVisitor for Visitor {
             MetaKind::TupleVariant { .. } => DefinitionKind::TupleVariant,
             MetaKind::StructVariant { .. } => DefinitionKind::StructVariant,
             MetaKind::Enum { .. } => DefinitionKind::Enum,
-            MetaKind::Function { .. } => DefinitionKind::Function,
+            MetaKind::Function { .. } | MetaKind::ConstFn => DefinitionKind::Function,
+            MetaKind::Const => DefinitionKind::Const,
+            MetaKind::Unknown if meta.source.is_none() => DefinitionKind::Type,
             _ => return,
         };
 
-        let definition = Definition {
-            kind,
-            source: DefinitionSource::SourceMeta(source.clone()),
+        let source = match meta.source {
+            Some(source) => DefinitionSource::SourceMeta(source.clone()),
+            None => DefinitionSource::Native {
+                item: meta.item.to_owned(),
+                hash: meta.hash,
+            },
         };
 
-        if let Some(d) = self.index.definitions.insert(location.span, definition) {
-            tracing::warn!("replaced definition: {:?}", d.kind)
-        }
+        self.insert(location, Definition { kind, source });
     }
 
-    fn visit_variable_use(&mut self, source_id: SourceId, var_span: Span, span: Span) {
-        if source_id.into_index() != 0 {
-            return;
-        }
+    fn visit_variable_declaration(&mut self, source_id: SourceId, span: Span) {
+        self.index
+            .declarations
+            .entry(source_id)
+            .or_default()
+            .insert(span);
+    }
 
+    fn visit_variable_use(&mut self, source_id: SourceId, var_span: Span, span: Span) {
         let definition = Definition {
             kind: DefinitionKind::Local,
             source: DefinitionSource::Location(Location::new(source_id, var_span)),
         };
 
-        if let Some(d) = self.index.definitions.insert(span, definition) {
-            tracing::warn!("replaced definition: {:?}", d.kind)
-        }
+        self.insert(Location::new(source_id, span), definition);
     }
 
-    fn visit_mod(&mut self, source_id: SourceId, span: Span) {
-        if source_id.into_index() != 0 {
-            return;
-        }
-
+    fn visit_mod_declaration(&mut self, location: Location, source_id: SourceId) {
         let definition = Definition {
             kind: DefinitionKind::Module,
             source: DefinitionSource::Source(source_id),
         };
 
-        if let Some(d) = self.index.definitions.insert(span, definition) {
-            tracing::warn!("replaced definition: {:?}", d.kind)
-        }
+        self.insert(location, definition);
     }
 }
 
@@ -661,6 +1434,10 @@ impl<'a> SourceLoader<'a> {
     fn candidates(root: &Path, item: &Item) -> Option<[Url; 2]> {
         let mut base = root.to_owned();
 
+        if !base.pop() {
+            return None;
+        }
+
         let mut it = item.iter().peekable();
         let mut last = None;
 
@@ -704,7 +1481,11 @@ impl<'a> rune::compile::SourceLoader for SourceLoader<'a> {
         if let Some(candidates) = Self::candidates(root, item) {
             for url in candidates.iter() {
                 if let Some(s) = self.sources.get(url) {
-                    return Ok(rune::Source::new(url, s.to_string()));
+                    return Ok(rune::Source::with_path(
+                        url,
+                        s.to_string(),
+                        url.to_file_path().ok(),
+                    ));
                 }
             }
         }
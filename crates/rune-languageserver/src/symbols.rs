@@ -0,0 +1,271 @@
+//! Outlines of single sources and symbol search across the workspace.
+
+use hashbrown::HashSet;
+use lsp::Url;
+use rune::ast::{self, Span, Spanned};
+use rune::compile::{ComponentRef, MetaKind};
+
+use crate::state::Build;
+
+/// Collect the outline of a parsed file, using `range` to translate spans in
+/// `text` into lsp ranges.
+pub(crate) fn document(
+    text: &str,
+    file: &ast::File,
+    range: &dyn Fn(Span) -> lsp::Range,
+) -> Vec<lsp::DocumentSymbol> {
+    let outline = Outline { text, range };
+    outline.file(file)
+}
+
+/// Search for items declared in the given builds whose name fuzzily matches
+/// `query`. Every build is paired with the uri of the source it was built
+/// from.
+pub(crate) fn workspace<'a>(
+    builds: impl IntoIterator<Item = (&'a Url, &'a Build)>,
+    query: &str,
+) -> Vec<lsp::WorkspaceSymbol> {
+    let mut seen = HashSet::new();
+    let mut matches = Vec::new();
+
+    for (uri, build) in builds {
+        for ((source_id, span), item) in &build.index.items {
+            let name = match item.last() {
+                Some(ComponentRef::Str(name)) => name,
+                _ => continue,
+            };
+
+            let kind = match build.index.metas.get(item) {
+                Some(kind) => match symbol_kind(*kind) {
+                    Some(kind) => kind,
+                    None => continue,
+                },
+                None => continue,
+            };
+
+            let score = match fuzzy_score(query, name) {
+                Some(score) => score,
+                None => continue,
+            };
+
+            let location = match build.lsp_location(*source_id, *span, uri) {
+                Some(location) => location,
+                None => continue,
+            };
+
+            // Modules are part of every build which loads them.
+            if !seen.insert((location.uri.clone(), *span)) {
+                continue;
+            }
+
+            let container_name = item
+                .parent()
+                .filter(|parent| !parent.is_empty())
+                .map(|parent| parent.to_string());
+
+            let symbol = lsp::WorkspaceSymbol {
+                name: name.to_owned(),
+                kind,
+                tags: None,
+                container_name,
+                location: lsp::OneOf::Left(location),
+                data: None,
+            };
+
+            matches.push((score, symbol));
+        }
+    }
+
+    matches.sort_by(|(a, a_symbol), (b, b_symbol)| {
+        a.cmp(b)
+            .then_with(|| a_symbol.name.cmp(&b_symbol.name))
+            .then_with(|| a_symbol.container_name.cmp(&b_symbol.container_name))
+    });
+
+    matches.into_iter().map(|(_, symbol)| symbol).collect()
+}
+
+struct Outline<'a> {
+    text: &'a str,
+    range: &'a dyn Fn(Span) -> lsp::Range,
+}
+
+impl Outline<'_> {
+    fn file(&self, file: &ast::File) -> Vec<lsp::DocumentSymbol> {
+        file.items
+            .iter()
+            .filter_map(|(item, _)| self.item(item))
+            .collect()
+    }
+
+    fn item(&self, item: &ast::Item) -> Option<lsp::DocumentSymbol> {
+        let symbol = match item {
+            ast::Item::Fn(f) => self.function(f, lsp::SymbolKind::FUNCTION),
+            ast::Item::Struct(s) => {
+                let fields = match &s.body {
+                    ast::ItemStructBody::StructBody(fields) => fields
+                        .iter()
+                        .map(|(field, _)| {
+                            self.symbol(
+                                field.span(),
+                                field.name.span(),
+                                self.text(&field.name),
+                                lsp::SymbolKind::FIELD,
+                                Vec::new(),
+                            )
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+
+                self.symbol(
+                    s.span(),
+                    s.ident.span(),
+                    self.text(&s.ident),
+                    lsp::SymbolKind::STRUCT,
+                    fields,
+                )
+            }
+            ast::Item::Enum(e) => {
+                let variants = e
+                    .variants
+                    .iter()
+                    .map(|(variant, _)| {
+                        self.symbol(
+                            variant.span(),
+                            variant.name.span(),
+                            self.text(&variant.name),
+                            lsp::SymbolKind::ENUM_MEMBER,
+                            Vec::new(),
+                        )
+                    })
+                    .collect();
+
+                self.symbol(
+                    e.span(),
+                    e.name.span(),
+                    self.text(&e.name),
+                    lsp::SymbolKind::ENUM,
+                    variants,
+                )
+            }
+            ast::Item::Impl(i) => {
+                let functions = i
+                    .functions
+                    .iter()
+                    .map(|f| self.function(f, lsp::SymbolKind::METHOD))
+                    .collect();
+
+                let name = format!("impl {}", self.text(&i.path));
+
+                self.symbol(
+                    i.span(),
+                    i.path.span(),
+                    &name,
+                    lsp::SymbolKind::OBJECT,
+                    functions,
+                )
+            }
+            ast::Item::Mod(m) => {
+                let items = match &m.body {
+                    ast::ItemModBody::InlineBody(body) => self.file(&body.file),
+                    _ => Vec::new(),
+                };
+
+                self.symbol(
+                    m.span(),
+                    m.name.span(),
+                    self.text(&m.name),
+                    lsp::SymbolKind::MODULE,
+                    items,
+                )
+            }
+            ast::Item::Const(c) => self.symbol(
+                c.span(),
+                c.name.span(),
+                self.text(&c.name),
+                lsp::SymbolKind::CONSTANT,
+                Vec::new(),
+            ),
+            _ => return None,
+        };
+
+        Some(symbol)
+    }
+
+    fn function(&self, f: &ast::ItemFn, kind: lsp::SymbolKind) -> lsp::DocumentSymbol {
+        let mut symbol = self.symbol(
+            f.span(),
+            f.name.span(),
+            self.text(&f.name),
+            kind,
+            Vec::new(),
+        );
+        symbol.detail = Some(self.text(&f.args).to_owned());
+        symbol
+    }
+
+    #[allow(deprecated)]
+    fn symbol(
+        &self,
+        span: Span,
+        name_span: Span,
+        name: &str,
+        kind: lsp::SymbolKind,
+        children: Vec<lsp::DocumentSymbol>,
+    ) -> lsp::DocumentSymbol {
+        lsp::DocumentSymbol {
+            name: name.to_owned(),
+            detail: None,
+            kind,
+            tags: None,
+            deprecated: None,
+            range: (self.range)(span),
+            selection_range: (self.range)(name_span),
+            children: (!children.is_empty()).then_some(children),
+        }
+    }
+
+    fn text(&self, spanned: &dyn Spanned) -> &str {
+        self.text.get(spanned.span().range()).unwrap_or_default()
+    }
+}
+
+/// The kind of symbol for a script item of the given kind, unless it's
+/// something which can't be searched for.
+fn symbol_kind(kind: MetaKind) -> Option<lsp::SymbolKind> {
+    Some(match kind {
+        MetaKind::UnitStruct | MetaKind::TupleStruct | MetaKind::Struct => lsp::SymbolKind::STRUCT,
+        MetaKind::UnitVariant | MetaKind::TupleVariant | MetaKind::StructVariant => {
+            lsp::SymbolKind::ENUM_MEMBER
+        }
+        MetaKind::Enum => lsp::SymbolKind::ENUM,
+        MetaKind::Function { .. } | MetaKind::ConstFn => lsp::SymbolKind::FUNCTION,
+        MetaKind::Const => lsp::SymbolKind::CONSTANT,
+        MetaKind::Module => lsp::SymbolKind::MODULE,
+        _ => return None,
+    })
+}
+
+/// Score how well `name` matches `query`, where a lower score is a better
+/// match.
+///
+/// Every character in the query has to appear in the name in the same order,
+/// ignoring case. Characters of the name which are skipped over before and in
+/// between them count against the match.
+fn fuzzy_score(query: &str, name: &str) -> Option<usize> {
+    let mut chars = name.chars().flat_map(char::to_lowercase);
+    let mut score = 0;
+
+    for q in query.chars().flat_map(char::to_lowercase) {
+        loop {
+            if chars.next()? == q {
+                break;
+            }
+
+            score += 1;
+        }
+    }
+
+    Some(score)
+}
@@ -110,9 +110,17 @@ pub(crate) fn expand_install_with(
 
     match &input.data {
         syn::Data::Struct(st) => {
-            expand_struct_install_with(ctx, &mut installers, st, tokens)?;
+            expand_struct_install_with(ctx, &mut installers, st, tokens, attrs)?;
         }
         syn::Data::Enum(en) => {
+            if attrs.fields {
+                ctx.errors.push(syn::Error::new_spanned(
+                    input,
+                    "#[rune(fields)] is only supported on structs",
+                ));
+                return None;
+            }
+
             expand_enum_install_with(ctx, &mut installers, ident, en, tokens, generics)?;
         }
         syn::Data::Union(..) => {
@@ -124,6 +132,42 @@ pub(crate) fn expand_install_with(
         }
     }
 
+    let protocol = &tokens.protocol;
+
+    if attrs.eq {
+        installers.push(quote_spanned! { input.span() =>
+            module.inst_fn(#protocol::EQ, |a: &Self, b: &Self| ::std::cmp::PartialEq::eq(a, b))?;
+        });
+    }
+
+    if attrs.clone {
+        installers.push(quote_spanned! { input.span() =>
+            module.inst_fn(#protocol::CLONE, |a: &Self| ::std::clone::Clone::clone(a))?;
+        });
+    }
+
+    if attrs.hash {
+        installers.push(quote_spanned! { input.span() =>
+            module.inst_fn(#protocol::HASH, |a: &Self| {
+                let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+                ::std::hash::Hash::hash(a, &mut hasher);
+                ::std::hash::Hasher::finish(&hasher) as i64
+            })?;
+        });
+    }
+
+    if attrs.partial_cmp {
+        installers.push(quote_spanned! { input.span() =>
+            module.inst_fn(#protocol::PARTIAL_CMP, |a: &Self, b: &Self| ::std::cmp::PartialOrd::partial_cmp(a, b))?;
+        });
+    }
+
+    if attrs.cmp {
+        installers.push(quote_spanned! { input.span() =>
+            module.inst_fn(#protocol::CMP, |a: &Self, b: &Self| ::std::cmp::Ord::cmp(a, b))?;
+        });
+    }
+
     if let Some(install_with) = &attrs.install_with {
         installers.push(quote_spanned! { input.span() =>
             #install_with(module)?;
@@ -141,11 +185,17 @@ fn expand_struct_install_with(
     installers: &mut Vec<TokenStream>,
     st: &syn::DataStruct,
     tokens: &Tokens,
+    type_attrs: &TypeAttrs,
 ) -> Option<()> {
     let mut fields = Vec::new();
 
     for (n, field) in st.fields.iter().enumerate() {
-        let attrs = ctx.field_attrs(&field.attrs)?;
+        let mut attrs = ctx.field_attrs(&field.attrs)?;
+
+        if type_attrs.fields && !attrs.skip() {
+            attrs.with_accessors();
+        }
+
         let name;
         let index;
 
@@ -213,11 +263,14 @@ fn expand_enum_install_with(
     let vm_error = &tokens.vm_error;
     let vm_error_kind = &tokens.vm_error_kind;
     let to_value = &tokens.to_value;
+    let from_value = &tokens.from_value;
+    let object = &tokens.object;
     let type_of = &tokens.type_of;
 
     let mut is_variant = Vec::new();
     let mut variants = Vec::new();
     let mut constructors = Vec::new();
+    let mut struct_constructors = Vec::new();
 
     // Protocol::GET implementations per available field. Each implementation
     // needs to match the enum to extract the appropriate field.
@@ -236,6 +289,7 @@ fn expand_enum_install_with(
         match &variant.fields {
             syn::Fields::Named(fields) => {
                 let mut field_names = Vec::new();
+                let mut from_values = Vec::new();
 
                 for f in &fields.named {
                     let attrs = ctx.field_attrs(&f.attrs)?;
@@ -252,7 +306,7 @@ fn expand_enum_install_with(
                     if attrs.field {
                         let f_name = f_ident.to_string();
                         let name = syn::LitStr::new(&f_name, f.span());
-                        field_names.push(name);
+                        field_names.push(name.clone());
 
                         let fields = field_fns.entry(f_name).or_default();
 
@@ -263,10 +317,35 @@ fn expand_enum_install_with(
                         };
 
                         fields.push(quote!(#ident::#variant_ident { #f_ident, .. } => #value));
+
+                        from_values.push(quote! {
+                            #f_ident: match object.get(#name) {
+                                Some(value) => #from_value::from_value(value.clone())?,
+                                None => {
+                                    return Err(#vm_error::from(#vm_error_kind::MissingStructField {
+                                        target: std::any::type_name::<Self>(),
+                                        name: #name,
+                                    }));
+                                }
+                            }
+                        });
                     }
                 }
 
                 variants.push(quote!((#variant_name, #variant_meta::st([#(#field_names),*]))));
+
+                if variant_attrs.constructor {
+                    if from_values.len() != fields.named.len() {
+                        ctx.errors.push(syn::Error::new_spanned(fields, "#[rune(constructor)] can only be used if all fields are marked with #[rune(get)"));
+                        return None;
+                    }
+
+                    struct_constructors.push(quote! {
+                        #variant_index, |object: #object| {
+                            Ok::<_, #vm_error>(#ident #generics :: #variant_ident { #(#from_values),* })
+                        }
+                    });
+                }
             }
             syn::Fields::Unnamed(fields) => {
                 let mut fields_len = 0usize;
@@ -361,6 +440,10 @@ fn expand_enum_install_with(
         installers.push(quote!(module.variant_constructor(#constructor)?;))
     }
 
+    for constructor in struct_constructors {
+        installers.push(quote!(module.struct_variant_constructor(#constructor)?;))
+    }
+
     Some(())
 }
 
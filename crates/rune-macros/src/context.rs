@@ -32,6 +32,8 @@ pub(crate) struct FieldAttrs {
     pub(crate) copy: bool,
     /// Whether this field should be known at compile time or not.
     pub(crate) field: bool,
+    /// Whether a `#[rune(set)]` protocol has been registered for this field.
+    set: bool,
 }
 
 impl FieldAttrs {
@@ -39,6 +41,26 @@ impl FieldAttrs {
     pub(crate) fn skip(&self) -> bool {
         self.skip.is_some() || self.id.is_some()
     }
+
+    /// Add a getter and a setter for the field unless they've already been
+    /// specified, as requested by `#[rune(fields)]`.
+    pub(crate) fn with_accessors(&mut self) {
+        if !self.field {
+            self.field = true;
+            self.protocols.push(FieldProtocol {
+                custom: None,
+                generate: generate_get,
+            });
+        }
+
+        if !self.set {
+            self.set = true;
+            self.protocols.push(FieldProtocol {
+                custom: None,
+                generate: generate_set,
+            });
+        }
+    }
 }
 
 /// The parsing implementations to build.
@@ -65,6 +87,20 @@ pub(crate) struct TypeAttrs {
     pub(crate) module: Option<syn::Path>,
     /// `#[rune(install_with = "...")]`.
     pub(crate) install_with: Option<syn::Path>,
+    /// `#[rune(eq)]` to install the `EQ` protocol using `PartialEq`.
+    pub(crate) eq: bool,
+    /// `#[rune(clone)]` to install the `CLONE` protocol using `Clone`.
+    pub(crate) clone: bool,
+    /// `#[rune(hash)]` to install the `HASH` protocol using `Hash`.
+    pub(crate) hash: bool,
+    /// `#[rune(partial_cmp)]` to install the `PARTIAL_CMP` protocol using
+    /// `PartialOrd`.
+    pub(crate) partial_cmp: bool,
+    /// `#[rune(cmp)]` to install the `CMP` protocol using `Ord`.
+    pub(crate) cmp: bool,
+    /// `#[rune(fields)]` to generate a getter and a setter for every field
+    /// which isn't skipped.
+    pub(crate) fields: bool,
     /// `#[rune(parse = "..")]` type attribute.
     pub(crate) parse: ParseKind,
 }
@@ -102,6 +138,70 @@ pub(crate) struct FieldProtocol {
     custom: Option<syn::Path>,
 }
 
+/// Generate a `Protocol::GET` implementation for a field.
+fn generate_get(g: Generate<'_>) -> TokenStream {
+    let Generate { target, .. } = g;
+
+    match target {
+        GenerateTarget::Named {
+            field_ident,
+            field_name,
+        } => {
+            let access = if g.attrs.copy {
+                quote!(s.#field_ident)
+            } else {
+                quote!(Clone::clone(&s.#field_ident))
+            };
+
+            let protocol = g.tokens.protocol(PROTOCOL_GET);
+
+            quote_spanned! { g.field.span() =>
+                module.field_fn(#protocol, #field_name, |s: &Self| #access)?;
+            }
+        }
+        GenerateTarget::Numbered { field_index } => {
+            let access = if g.attrs.copy {
+                quote!(s.#field_index)
+            } else {
+                quote!(Clone::clone(&s.#field_index))
+            };
+
+            let protocol = g.tokens.protocol(PROTOCOL_GET);
+
+            quote_spanned! { g.field.span() =>
+                module.index_fn(#protocol, #field_index, |s: &Self| #access)?;
+            }
+        }
+    }
+}
+
+/// Generate a `Protocol::SET` implementation for a field.
+fn generate_set(g: Generate<'_>) -> TokenStream {
+    let Generate { ty, target, .. } = g;
+
+    let protocol = g.tokens.protocol(PROTOCOL_SET);
+
+    match target {
+        GenerateTarget::Named {
+            field_ident,
+            field_name,
+        } => {
+            quote_spanned! { g.field.span() =>
+                module.field_fn(#protocol, #field_name, |s: &mut Self, value: #ty| {
+                    s.#field_ident = value;
+                })?;
+            }
+        }
+        GenerateTarget::Numbered { field_index } => {
+            quote_spanned! { g.field.span() =>
+                module.index_fn(#protocol, #field_index, |s: &mut Self, value: #ty| {
+                    s.#field_index = value;
+                })?;
+            }
+        }
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct Context {
     pub(crate) errors: Vec<syn::Error>,
@@ -244,71 +344,13 @@ impl Context {
                     attrs.field = true;
                     attrs.protocols.push(FieldProtocol {
                         custom: self.parse_field_custom(meta.input)?,
-                        generate: |g| {
-                            let Generate {
-                                target,
-                                ..
-                            } = g;
-
-                            match target {
-                                GenerateTarget::Named { field_ident, field_name } => {
-                                    let access = if g.attrs.copy {
-                                        quote!(s.#field_ident)
-                                    } else {
-                                        quote!(Clone::clone(&s.#field_ident))
-                                    };
-
-                                    let protocol = g.tokens.protocol(PROTOCOL_GET);
-
-                                    quote_spanned! { g.field.span() =>
-                                        module.field_fn(#protocol, #field_name, |s: &Self| #access)?;
-                                    }
-                                }
-                                GenerateTarget::Numbered { field_index } => {
-                                    let access = if g.attrs.copy {
-                                        quote!(s.#field_index)
-                                    } else {
-                                        quote!(Clone::clone(&s.#field_index))
-                                    };
-
-                                    let protocol = g.tokens.protocol(PROTOCOL_GET);
-
-                                    quote_spanned! { g.field.span() =>
-                                        module.index_fn(#protocol, #field_index, |s: &Self| #access)?;
-                                    }
-                                }
-                            }
-                        },
+                        generate: generate_get,
                     });
                 } else if meta.path == SET {
+                    attrs.set = true;
                     attrs.protocols.push(FieldProtocol {
                         custom: self.parse_field_custom(meta.input)?,
-                        generate: |g| {
-                            let Generate {
-                                ty,
-                                target,
-                                ..
-                            } = g;
-
-                            let protocol = g.tokens.protocol(PROTOCOL_SET);
-
-                            match target {
-                                GenerateTarget::Named { field_ident, field_name } => {
-                                    quote_spanned! { g.field.span() =>
-                                        module.field_fn(#protocol, #field_name, |s: &mut Self, value: #ty| {
-                                            s.#field_ident = value;
-                                        })?;
-                                    }
-                                }
-                                GenerateTarget::Numbered { field_index } => {
-                                    quote_spanned! { g.field.span() =>
-                                        module.index_fn(#protocol, #field_index, |s: &mut Self, value: #ty| {
-                                            s.#field_index = value;
-                                        })?;
-                                    }
-                                }
-                            }
-                        },
+                        generate: generate_set,
                     });
                 } else if meta.path == ADD_ASSIGN {
                     attrs.protocols.push(FieldProtocol {
@@ -421,6 +463,24 @@ impl Context {
                     let s: syn::LitStr = meta.input.parse()?;
                     let install_with = s.parse_with(syn::Path::parse_mod_style)?;
                     attrs.install_with = Some(install_with);
+                } else if meta.path == EQ {
+                    // Parse `#[rune(eq)]`
+                    attrs.eq = true;
+                } else if meta.path == CLONE {
+                    // Parse `#[rune(clone)]`
+                    attrs.clone = true;
+                } else if meta.path == HASH {
+                    // Parse `#[rune(hash)]`
+                    attrs.hash = true;
+                } else if meta.path == PARTIAL_CMP {
+                    // Parse `#[rune(partial_cmp)]`
+                    attrs.partial_cmp = true;
+                } else if meta.path == CMP {
+                    // Parse `#[rune(cmp)]`
+                    attrs.cmp = true;
+                } else if meta.path == FIELDS {
+                    // Parse `#[rune(fields)]`
+                    attrs.fields = true;
                 } else {
                     return Err(syn::Error::new_spanned(
                         &meta.path,
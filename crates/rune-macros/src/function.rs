@@ -28,6 +28,19 @@ pub(crate) struct FunctionAttrs {
 }
 
 impl FunctionAttrs {
+    /// Attributes for an associated function which is registered under
+    /// `Self::<ident>`, as if it was declared with `path = Self::<ident>`.
+    pub(crate) fn associated(ident: &syn::Ident) -> Self {
+        Self {
+            instance: false,
+            path: Path::Path(
+                ident.span(),
+                Some(syn::token::SelfType(ident.span())),
+                vec![ident.clone()],
+            ),
+        }
+    }
+
     /// Parse the given parse stream.
     pub(crate) fn parse(input: ParseStream) -> Result<Self, Error> {
         let span = input.span();
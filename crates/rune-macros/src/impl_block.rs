@@ -0,0 +1,127 @@
+use crate::function::{Function, FunctionAttrs};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::Parser;
+use syn::Error;
+
+/// An `impl` block annotated with `#[rune::impl_block]`.
+pub(crate) struct ImplBlock {
+    item: syn::ItemImpl,
+}
+
+impl ImplBlock {
+    /// Parse the given parse stream.
+    pub(crate) fn parse(input: syn::parse::ParseStream) -> Result<Self, Error> {
+        Ok(Self {
+            item: input.parse()?,
+        })
+    }
+
+    /// Expand the impl block, turning every method into a rune function and
+    /// adding an `install_functions` function which registers all of them.
+    pub(crate) fn expand(self) -> Result<TokenStream, Error> {
+        let mut item = self.item;
+
+        if let Some((_, path, _)) = &item.trait_ {
+            return Err(Error::new_spanned(
+                path,
+                "#[rune::impl_block] is not supported on trait implementations",
+            ));
+        }
+
+        let mut items = Vec::new();
+        let mut installers = Vec::new();
+
+        for impl_item in std::mem::take(&mut item.items) {
+            let mut method = match impl_item {
+                syn::ImplItem::Fn(method) => method,
+                impl_item => {
+                    items.push(impl_item.into_token_stream());
+                    continue;
+                }
+            };
+
+            if take_skip(&mut method.attrs)? {
+                items.push(method.into_token_stream());
+                continue;
+            }
+
+            let ident = method.sig.ident.clone();
+            installers.push(quote!(module.function_meta(Self::#ident)?;));
+
+            // Functions which are already annotated are expanded on their
+            // own.
+            if method.attrs.iter().any(is_function_attr) {
+                items.push(method.into_token_stream());
+                continue;
+            }
+
+            let attrs = if method.sig.receiver().is_some() {
+                FunctionAttrs::default()
+            } else {
+                FunctionAttrs::associated(&ident)
+            };
+
+            let function = Function::parse.parse2(method.into_token_stream())?;
+            items.push(function.expand(attrs)?);
+        }
+
+        let attrs = &item.attrs;
+        let unsafety = &item.unsafety;
+        let impl_token = &item.impl_token;
+        let self_ty = &item.self_ty;
+        let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+
+        Ok(quote! {
+            #(#attrs)*
+            #unsafety #impl_token #impl_generics #self_ty #where_clause {
+                #(#items)*
+
+                /// Install all functions declared in this impl block into
+                /// the given module.
+                #[automatically_derived]
+                pub fn install_functions(module: &mut rune::Module) -> Result<(), rune::ContextError> {
+                    #(#installers)*
+                    Ok(())
+                }
+            }
+        })
+    }
+}
+
+/// Remove `#[rune(skip)]` from the given attributes, returning `true` if it
+/// was present.
+fn take_skip(attrs: &mut Vec<syn::Attribute>) -> Result<bool, Error> {
+    let mut skip = false;
+    let mut out = Vec::with_capacity(attrs.len());
+
+    for attr in attrs.drain(..) {
+        if !attr.path().is_ident("rune") {
+            out.push(attr);
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute"))
+            }
+        })?;
+    }
+
+    *attrs = out;
+    Ok(skip)
+}
+
+/// Test if the attribute is `#[rune::function]`.
+fn is_function_attr(attr: &syn::Attribute) -> bool {
+    let path = attr.path();
+    let mut segments = path.segments.iter().map(|s| &s.ident);
+
+    matches!(
+        (segments.next(), segments.next(), segments.next()),
+        (Some(a), Some(b), None) if a == "rune" && b == "function"
+    )
+}
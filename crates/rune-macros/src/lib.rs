@@ -28,6 +28,7 @@ mod any;
 mod context;
 mod from_value;
 mod function;
+mod impl_block;
 mod instrument;
 mod internals;
 mod opaque;
@@ -194,6 +195,84 @@ pub fn function(
     output.into()
 }
 
+/// Macro used to annotate an `impl` block whose functions should all be
+/// loaded into rune.
+///
+/// Every function in the block is treated as if it was annotated with
+/// [`#[rune::function]`][macro@function]. Functions which take `self` become
+/// instance functions, and the remaining ones are associated functions
+/// available as `Type::name`. Functions can be excluded with `#[rune(skip)]`.
+///
+/// An associated `install_functions` function is generated, which registers
+/// all the functions in the block with a [`Module`].
+///
+/// [`Module`]: https://docs.rs/rune/latest/rune/struct.Module.html
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Any, Module, ContextError};
+///
+/// #[derive(Any)]
+/// struct Counter {
+///     value: i64,
+/// }
+///
+/// #[rune::impl_block]
+/// impl Counter {
+///     /// Construct a new counter.
+///     fn new() -> Self {
+///         Self { value: 0 }
+///     }
+///
+///     /// Increment the counter.
+///     fn increment(&mut self) {
+///         self.value += 1;
+///     }
+///
+///     /// Get the current value of the counter.
+///     async fn get(&self) -> i64 {
+///         self.value
+///     }
+///
+///     /// Not visible to scripts.
+///     #[rune(skip)]
+///     fn reset(&mut self) {
+///         self.value = 0;
+///     }
+/// }
+///
+/// fn module() -> Result<Module, ContextError> {
+///     let mut m = Module::new();
+///     m.ty::<Counter>()?;
+///     Counter::install_functions(&mut m)?;
+///     Ok(m)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn impl_block(
+    attrs: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !attrs.is_empty() {
+        let error = syn::Error::new_spanned(
+            proc_macro2::TokenStream::from(attrs),
+            "#[rune::impl_block] does not take any arguments",
+        );
+
+        return proc_macro::TokenStream::from(error.to_compile_error());
+    }
+
+    let block = syn::parse_macro_input!(item with crate::impl_block::ImplBlock::parse);
+
+    let output = match block.expand() {
+        Ok(output) => output,
+        Err(e) => return proc_macro::TokenStream::from(e.to_compile_error()),
+    };
+
+    output.into()
+}
+
 /// Helper derive to implement `ToTokens`.
 #[proc_macro_derive(ToTokens, attributes(rune))]
 #[doc(hidden)]
@@ -73,6 +73,18 @@ pub fn module(io: &CaptureIo) -> Result<Module, ContextError> {
 
     let o = io.clone();
 
+    module.function(["eprint"], move |m: &str| {
+        write!(o.inner.lock(), "{}", m).map_err(Panic::custom)
+    })?;
+
+    let o = io.clone();
+
+    module.function(["eprintln"], move |m: &str| {
+        writeln!(o.inner.lock(), "{}", m).map_err(Panic::custom)
+    })?;
+
+    let o = io.clone();
+
     module.raw_fn(["dbg"], move |stack, args| {
         let mut o = o.inner.lock();
         dbg_impl(&mut *o, stack, args)
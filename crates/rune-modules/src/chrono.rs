@@ -0,0 +1,334 @@
+//! The native `chrono` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! Provides calendar date and time support through the [chrono] crate, with
+//! parsing and formatting of RFC 3339 and `strftime`-style timestamps.
+//!
+//! [chrono]: https://docs.rs/chrono
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["chrono"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::chrono::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use chrono::{DateTime, Duration};
+//!
+//! fn main() {
+//!     let start = DateTime::parse_rfc3339("2023-03-01T12:00:00+01:00")?;
+//!     let end = (start + Duration::days(30)?)?;
+//!     println!("{}", end.format("%Y-%m-%d %H:%M"));
+//! }
+//! ```
+//!
+//! Host functions can accept and return these types directly, and convert
+//! to and from the corresponding [chrono] types through [From].
+
+use rune::runtime::Protocol;
+use rune::{Any, ContextError, Module};
+use std::fmt;
+use std::fmt::Write;
+
+/// Construct the `chrono` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("chrono");
+
+    module.ty::<DateTime>()?;
+    module.ty::<Duration>()?;
+
+    module.function(["DateTime", "now"], DateTime::now)?;
+    module.function(["DateTime", "from_timestamp"], DateTime::from_timestamp)?;
+    module.function(
+        ["DateTime", "from_timestamp_millis"],
+        DateTime::from_timestamp_millis,
+    )?;
+    module.function(["DateTime", "parse_rfc3339"], DateTime::parse_rfc3339)?;
+    module.function(["DateTime", "parse_from_str"], DateTime::parse_from_str)?;
+    module.inst_fn("to_rfc3339", DateTime::to_rfc3339)?;
+    module.inst_fn("format", DateTime::format)?;
+    module.inst_fn("timestamp", DateTime::timestamp)?;
+    module.inst_fn("timestamp_millis", DateTime::timestamp_millis)?;
+    module.inst_fn("year", DateTime::year)?;
+    module.inst_fn("month", DateTime::month)?;
+    module.inst_fn("day", DateTime::day)?;
+    module.inst_fn("hour", DateTime::hour)?;
+    module.inst_fn("minute", DateTime::minute)?;
+    module.inst_fn("second", DateTime::second)?;
+    module.inst_fn("offset_seconds", DateTime::offset_seconds)?;
+    module.inst_fn("signed_duration_since", DateTime::signed_duration_since)?;
+    module.inst_fn(Protocol::ADD, DateTime::add)?;
+    module.inst_fn(Protocol::SUB, DateTime::sub)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, DateTime::string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, DateTime::string_debug)?;
+
+    module.function(["Duration", "milliseconds"], Duration::milliseconds)?;
+    module.function(["Duration", "seconds"], Duration::seconds)?;
+    module.function(["Duration", "minutes"], Duration::minutes)?;
+    module.function(["Duration", "hours"], Duration::hours)?;
+    module.function(["Duration", "days"], Duration::days)?;
+    module.inst_fn("num_milliseconds", Duration::num_milliseconds)?;
+    module.inst_fn("num_seconds", Duration::num_seconds)?;
+    module.inst_fn("num_minutes", Duration::num_minutes)?;
+    module.inst_fn("num_hours", Duration::num_hours)?;
+    module.inst_fn("num_days", Duration::num_days)?;
+    module.inst_fn(Protocol::ADD, Duration::add)?;
+    module.inst_fn(Protocol::SUB, Duration::sub)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Duration::string_debug)?;
+    Ok(module)
+}
+
+/// A date and time with a fixed offset from UTC.
+#[derive(Debug, Any, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[rune(eq, partial_cmp, cmp)]
+pub struct DateTime {
+    inner: chrono::DateTime<chrono::FixedOffset>,
+}
+
+impl From<chrono::DateTime<chrono::FixedOffset>> for DateTime {
+    fn from(inner: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<chrono::DateTime<chrono::Utc>> for DateTime {
+    fn from(inner: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            inner: inner.into(),
+        }
+    }
+}
+
+impl From<DateTime> for chrono::DateTime<chrono::FixedOffset> {
+    fn from(value: DateTime) -> Self {
+        value.inner
+    }
+}
+
+impl From<DateTime> for chrono::DateTime<chrono::Utc> {
+    fn from(value: DateTime) -> Self {
+        value.inner.into()
+    }
+}
+
+impl DateTime {
+    /// The current date and time in UTC.
+    fn now() -> Self {
+        Self::from(chrono::Utc::now())
+    }
+
+    /// Construct a UTC date and time from a number of seconds since the unix
+    /// epoch.
+    fn from_timestamp(secs: i64) -> Option<Self> {
+        use chrono::TimeZone as _;
+        chrono::Utc.timestamp_opt(secs, 0).single().map(Self::from)
+    }
+
+    /// Construct a UTC date and time from a number of milliseconds since the
+    /// unix epoch.
+    fn from_timestamp_millis(millis: i64) -> Option<Self> {
+        use chrono::TimeZone as _;
+        chrono::Utc.timestamp_millis_opt(millis).single().map(Self::from)
+    }
+
+    /// Parse an RFC 3339 timestamp, like `1996-12-19T16:39:57-08:00`.
+    fn parse_rfc3339(s: &str) -> rune::Result<Self> {
+        Ok(Self::from(chrono::DateTime::parse_from_rfc3339(s)?))
+    }
+
+    /// Parse a timestamp using a `strftime`-style format string. The format
+    /// must include an offset.
+    fn parse_from_str(s: &str, fmt: &str) -> rune::Result<Self> {
+        Ok(Self::from(chrono::DateTime::parse_from_str(s, fmt)?))
+    }
+
+    /// Format as an RFC 3339 timestamp.
+    fn to_rfc3339(&self) -> String {
+        self.inner.to_rfc3339()
+    }
+
+    /// Format using a `strftime`-style format string.
+    fn format(&self, fmt: &str) -> rune::Result<String> {
+        let mut out = String::new();
+
+        if write!(out, "{}", self.inner.format(fmt)).is_err() {
+            return Err(rune::Error::msg(format!("invalid format string `{fmt}`")));
+        }
+
+        Ok(out)
+    }
+
+    fn timestamp(&self) -> i64 {
+        self.inner.timestamp()
+    }
+
+    fn timestamp_millis(&self) -> i64 {
+        self.inner.timestamp_millis()
+    }
+
+    fn year(&self) -> i64 {
+        use chrono::Datelike as _;
+        self.inner.year() as i64
+    }
+
+    fn month(&self) -> i64 {
+        use chrono::Datelike as _;
+        self.inner.month() as i64
+    }
+
+    fn day(&self) -> i64 {
+        use chrono::Datelike as _;
+        self.inner.day() as i64
+    }
+
+    fn hour(&self) -> i64 {
+        use chrono::Timelike as _;
+        self.inner.hour() as i64
+    }
+
+    fn minute(&self) -> i64 {
+        use chrono::Timelike as _;
+        self.inner.minute() as i64
+    }
+
+    fn second(&self) -> i64 {
+        use chrono::Timelike as _;
+        self.inner.second() as i64
+    }
+
+    /// The offset from UTC in seconds.
+    fn offset_seconds(&self) -> i64 {
+        self.inner.offset().local_minus_utc() as i64
+    }
+
+    /// The duration elapsed since `earlier`, which is negative if `earlier` is
+    /// later than this date and time.
+    fn signed_duration_since(&self, earlier: &DateTime) -> Duration {
+        Duration::from(self.inner.signed_duration_since(earlier.inner))
+    }
+
+    fn add(&self, duration: &Duration) -> rune::Result<Self> {
+        match self.inner.checked_add_signed(duration.inner) {
+            Some(inner) => Ok(Self { inner }),
+            None => Err(rune::Error::msg("date and time out of range")),
+        }
+    }
+
+    fn sub(&self, duration: &Duration) -> rune::Result<Self> {
+        match self.inner.checked_sub_signed(duration.inner) {
+            Some(inner) => Ok(Self { inner }),
+            None => Err(rune::Error::msg("date and time out of range")),
+        }
+    }
+
+    fn string_display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+
+    fn string_debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// A signed duration of time with millisecond or better precision.
+#[derive(Debug, Any, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[rune(eq, partial_cmp, cmp)]
+pub struct Duration {
+    inner: chrono::Duration,
+}
+
+impl From<chrono::Duration> for Duration {
+    fn from(inner: chrono::Duration) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<Duration> for chrono::Duration {
+    fn from(value: Duration) -> Self {
+        value.inner
+    }
+}
+
+impl Duration {
+    fn milliseconds(millis: i64) -> Self {
+        Self::from(chrono::Duration::milliseconds(millis))
+    }
+
+    fn seconds(secs: i64) -> rune::Result<Self> {
+        Self::from_millis_scaled(secs, 1000)
+    }
+
+    fn minutes(minutes: i64) -> rune::Result<Self> {
+        Self::from_millis_scaled(minutes, 60 * 1000)
+    }
+
+    fn hours(hours: i64) -> rune::Result<Self> {
+        Self::from_millis_scaled(hours, 60 * 60 * 1000)
+    }
+
+    fn days(days: i64) -> rune::Result<Self> {
+        Self::from_millis_scaled(days, 24 * 60 * 60 * 1000)
+    }
+
+    /// Construct a duration from `value` units of `scale` milliseconds,
+    /// erroring instead of panicking if it's out of range.
+    fn from_millis_scaled(value: i64, scale: i64) -> rune::Result<Self> {
+        match value.checked_mul(scale) {
+            Some(millis) => Ok(Self::milliseconds(millis)),
+            None => Err(rune::Error::msg("duration out of range")),
+        }
+    }
+
+    fn num_milliseconds(&self) -> i64 {
+        self.inner.num_milliseconds()
+    }
+
+    fn num_seconds(&self) -> i64 {
+        self.inner.num_seconds()
+    }
+
+    fn num_minutes(&self) -> i64 {
+        self.inner.num_minutes()
+    }
+
+    fn num_hours(&self) -> i64 {
+        self.inner.num_hours()
+    }
+
+    fn num_days(&self) -> i64 {
+        self.inner.num_days()
+    }
+
+    fn add(&self, other: &Duration) -> rune::Result<Self> {
+        match self.inner.checked_add(&other.inner) {
+            Some(inner) => Ok(Self { inner }),
+            None => Err(rune::Error::msg("duration out of range")),
+        }
+    }
+
+    fn sub(&self, other: &Duration) -> rune::Result<Self> {
+        match self.inner.checked_sub(&other.inner) {
+            Some(inner) => Ok(Self { inner }),
+            None => Err(rune::Error::msg("duration out of range")),
+        }
+    }
+
+    fn string_debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
@@ -0,0 +1,107 @@
+//! The native `crypto` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! Provides message digests, HMAC and constant-time comparison. Every function
+//! accepts either a string or bytes as input, and digests are returned as
+//! bytes.
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["crypto"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::crypto::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! fn verify(secret, body, signature) {
+//!     let expected = crypto::hmac_sha256(secret, body)?;
+//!     crypto::constant_time_eq(expected, signature)
+//! }
+//!
+//! fn main() {
+//!     let checksum = crypto::sha256(b"hello world")?;
+//!     dbg(checksum);
+//! }
+//! ```
+
+use digest::Digest;
+use hmac::{Mac, SimpleHmac};
+use rune::runtime::{Bytes, Value, VmError};
+use rune::{ContextError, Module};
+
+/// Construct the `crypto` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("crypto");
+    module.function(["md5"], hash::<md5::Md5>)?;
+    module.function(["sha1"], hash::<sha1::Sha1>)?;
+    module.function(["sha256"], hash::<sha2::Sha256>)?;
+    module.function(["sha512"], hash::<sha2::Sha512>)?;
+    module.function(["hmac_sha1"], mac::<sha1::Sha1>)?;
+    module.function(["hmac_sha256"], mac::<sha2::Sha256>)?;
+    module.function(["hmac_sha512"], mac::<sha2::Sha512>)?;
+    module.function(["constant_time_eq"], constant_time_eq)?;
+    Ok(module)
+}
+
+/// Hash the given string or bytes.
+fn hash<D>(data: Value) -> Result<Bytes, VmError>
+where
+    D: Digest,
+{
+    with_bytes(&data, |data| Bytes::from_vec(D::digest(data).to_vec()))
+}
+
+/// Compute the HMAC of the given string or bytes using `key`.
+fn mac<D>(key: Value, data: Value) -> Result<Bytes, VmError>
+where
+    D: Digest + digest::core_api::BlockSizeUser,
+{
+    let mut mac = with_bytes(&key, |key| {
+        <SimpleHmac<D> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length")
+    })?;
+
+    with_bytes(&data, |data| mac.update(data))?;
+    Ok(Bytes::from_vec(mac.finalize().into_bytes().to_vec()))
+}
+
+/// Compare two strings or byte sequences in an amount of time which only
+/// depends on their lengths, so that comparing secrets like signatures
+/// doesn't leak how much of them matched.
+fn constant_time_eq(a: Value, b: Value) -> Result<bool, VmError> {
+    with_bytes(&a, |a| {
+        with_bytes(&b, |b| {
+            if a.len() != b.len() {
+                return false;
+            }
+
+            a.iter().zip(b).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+        })
+    })?
+}
+
+/// Call `f` with the contents of a string or bytes value.
+fn with_bytes<F, T>(value: &Value, f: F) -> Result<T, VmError>
+where
+    F: FnOnce(&[u8]) -> T,
+{
+    match value {
+        Value::String(s) => Ok(f(s.borrow_ref()?.as_bytes())),
+        Value::StaticString(s) => Ok(f(s.as_bytes())),
+        Value::Bytes(b) => Ok(f(&b.borrow_ref()?)),
+        actual => Err(VmError::expected::<Bytes>(actual.type_info()?)),
+    }
+}
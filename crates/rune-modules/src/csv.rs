@@ -0,0 +1,332 @@
+//! The native `csv` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["csv"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::csv::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use csv::{Reader, Writer};
+//!
+//! fn main() {
+//!     // Read everything at once, using the first row as headers.
+//!     let people = csv::from_string("name,age\nAda,36\nAlan,41\n")?;
+//!
+//!     // Or one record at a time.
+//!     let reader = Reader::from_string_with("a;b\n1;2\n", #{ delimiter: ";" })?;
+//!
+//!     while let Some(row) = reader.read_object()? {
+//!         dbg(row);
+//!     }
+//!
+//!     let writer = Writer::new();
+//!     writer.write_record(["name", "age"])?;
+//!     writer.write_record(["Grace", 45])?;
+//!     println!("{}", writer.finish()?);
+//! }
+//! ```
+
+use rune::runtime::{Object, Value, VmError};
+use rune::{Any, ContextError, FromValue, Module};
+use std::io::Cursor;
+
+/// Construct the `csv` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("csv");
+
+    module.function(["from_string"], from_string)?;
+    module.function(["records_from_string"], records_from_string)?;
+    module.function(["to_string"], to_string)?;
+
+    module.ty::<Reader>()?;
+    module.function(["Reader", "from_string"], Reader::from_string)?;
+    module.function(["Reader", "from_string_with"], Reader::from_string_with)?;
+    module.function(["Reader", "from_bytes"], Reader::from_bytes)?;
+    module.function(["Reader", "from_bytes_with"], Reader::from_bytes_with)?;
+    module.inst_fn("headers", Reader::headers)?;
+    module.inst_fn("read_record", Reader::read_record)?;
+    module.inst_fn("read_object", Reader::read_object)?;
+
+    module.ty::<Writer>()?;
+    module.function(["Writer", "new"], Writer::new)?;
+    module.function(["Writer", "with_options"], Writer::with_options)?;
+    module.inst_fn("write_record", Writer::write_record)?;
+    module.inst_fn("finish", Writer::finish)?;
+    Ok(module)
+}
+
+/// Options for reading and writing, parsed from an object like
+/// `#{ delimiter: ";", headers: false }`.
+struct Options {
+    delimiter: u8,
+    headers: bool,
+}
+
+impl Options {
+    const DEFAULT: Self = Self {
+        delimiter: b',',
+        headers: true,
+    };
+
+    fn from_object(object: &Object) -> rune::Result<Self> {
+        let mut options = Self::DEFAULT;
+
+        for (key, value) in object {
+            match key.as_str() {
+                "delimiter" => {
+                    options.delimiter = match String::from_value(value.clone())?.as_bytes() {
+                        &[delimiter] => delimiter,
+                        _ => {
+                            return Err(rune::Error::msg(
+                                "`delimiter` must be a single byte character",
+                            ))
+                        }
+                    };
+                }
+                "headers" => {
+                    options.headers = bool::from_value(value.clone())?;
+                }
+                other => return Err(rune::Error::msg(format!("unsupported option `{other}`"))),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn reader(&self, data: Vec<u8>) -> csv::Reader<Cursor<Vec<u8>>> {
+        csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(self.headers)
+            .from_reader(Cursor::new(data))
+    }
+}
+
+/// Parse every record, using the first row as headers, into objects.
+fn from_string(data: &str) -> rune::Result<Vec<Object>> {
+    let mut reader = Reader::from_string(data);
+    let mut objects = Vec::new();
+
+    while let Some(object) = reader.read_object()? {
+        objects.push(object);
+    }
+
+    Ok(objects)
+}
+
+/// Parse every record, including the first, into vectors of strings.
+fn records_from_string(data: &str) -> rune::Result<Vec<Vec<String>>> {
+    let mut reader = Options {
+        headers: false,
+        ..Options::DEFAULT
+    }
+    .reader(data.as_bytes().to_vec());
+
+    let mut records = Vec::new();
+
+    for record in reader.records() {
+        records.push(record?.iter().map(str::to_owned).collect());
+    }
+
+    Ok(records)
+}
+
+/// Write rows, which are either all vectors or all objects. The headers of
+/// objects are taken from the keys of the first one, in sorted order.
+fn to_string(rows: Vec<Value>) -> rune::Result<String> {
+    let mut writer = Writer::new();
+    let mut headers = None::<Vec<String>>;
+
+    for row in rows {
+        match row {
+            Value::Object(object) => {
+                let object = object.borrow_ref()?;
+
+                if headers.is_none() {
+                    let keys = object.keys().cloned().collect::<Vec<_>>();
+                    writer.inner.write_record(&keys)?;
+                    headers = Some(keys);
+                }
+
+                let mut record = Vec::new();
+
+                for key in headers.iter().flatten() {
+                    record.push(match object.get(key) {
+                        Some(value) => field(value)?,
+                        None => String::new(),
+                    });
+                }
+
+                writer.inner.write_record(&record)?;
+            }
+            row => {
+                writer.write_record(Vec::from_value(row)?)?;
+            }
+        }
+    }
+
+    writer.finish()
+}
+
+/// Format a value as a field.
+fn field(value: &Value) -> Result<String, VmError> {
+    Ok(match value {
+        Value::String(s) => s.borrow_ref()?.clone(),
+        Value::StaticString(s) => (***s).to_owned(),
+        Value::Integer(n) => n.to_string(),
+        Value::Float(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Char(c) => c.to_string(),
+        Value::Unit => String::new(),
+        Value::Option(option) => match &*option.borrow_ref()? {
+            Some(value) => field(value)?,
+            None => String::new(),
+        },
+        actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+    })
+}
+
+/// Reads records one at a time.
+#[derive(Any)]
+struct Reader {
+    inner: csv::Reader<Cursor<Vec<u8>>>,
+    headers: bool,
+}
+
+impl Reader {
+    /// Read from a string, using the first row as headers.
+    fn from_string(data: &str) -> Self {
+        Self::new(data.as_bytes().to_vec(), &Options::DEFAULT)
+    }
+
+    /// Read from a string with the given options.
+    fn from_string_with(data: &str, options: &Object) -> rune::Result<Self> {
+        let options = Options::from_object(options)?;
+        Ok(Self::new(data.as_bytes().to_vec(), &options))
+    }
+
+    /// Read from bytes, using the first row as headers.
+    fn from_bytes(data: &[u8]) -> Self {
+        Self::new(data.to_vec(), &Options::DEFAULT)
+    }
+
+    /// Read from bytes with the given options.
+    fn from_bytes_with(data: &[u8], options: &Object) -> rune::Result<Self> {
+        let options = Options::from_object(options)?;
+        Ok(Self::new(data.to_vec(), &options))
+    }
+
+    fn new(data: Vec<u8>, options: &Options) -> Self {
+        Self {
+            inner: options.reader(data),
+            headers: options.headers,
+        }
+    }
+
+    /// The headers, or an empty vector if the reader was configured without
+    /// them.
+    fn headers(&mut self) -> rune::Result<Vec<String>> {
+        if !self.headers {
+            return Ok(Vec::new());
+        }
+
+        Ok(self.inner.headers()?.iter().map(str::to_owned).collect())
+    }
+
+    /// Read the next record as a vector of strings.
+    fn read_record(&mut self) -> rune::Result<Option<Vec<String>>> {
+        let mut record = csv::StringRecord::new();
+
+        if !self.inner.read_record(&mut record)? {
+            return Ok(None);
+        }
+
+        Ok(Some(record.iter().map(str::to_owned).collect()))
+    }
+
+    /// Read the next record as an object keyed by the headers.
+    fn read_object(&mut self) -> rune::Result<Option<Object>> {
+        if !self.headers {
+            return Err(rune::Error::msg(
+                "reading objects requires a reader with headers",
+            ));
+        }
+
+        let headers = self.inner.headers()?.clone();
+        let mut record = csv::StringRecord::new();
+
+        if !self.inner.read_record(&mut record)? {
+            return Ok(None);
+        }
+
+        let mut object = Object::with_capacity(headers.len());
+
+        for (key, value) in headers.iter().zip(record.iter()) {
+            object.insert(key.to_owned(), Value::from(value.to_owned()));
+        }
+
+        Ok(Some(object))
+    }
+}
+
+/// Writes records one at a time into a string.
+#[derive(Any)]
+struct Writer {
+    inner: csv::Writer<Vec<u8>>,
+}
+
+impl Writer {
+    /// Construct a writer which separates fields with commas.
+    fn new() -> Self {
+        Self {
+            inner: csv::Writer::from_writer(Vec::new()),
+        }
+    }
+
+    /// Construct a writer with the given options. Only `delimiter` is
+    /// supported.
+    fn with_options(options: &Object) -> rune::Result<Self> {
+        let options = Options::from_object(options)?;
+
+        Ok(Self {
+            inner: csv::WriterBuilder::new()
+                .delimiter(options.delimiter)
+                .from_writer(Vec::new()),
+        })
+    }
+
+    /// Write a record of strings, numbers, booleans or characters, where
+    /// units and `None` are written as empty fields.
+    fn write_record(&mut self, record: Vec<Value>) -> rune::Result<()> {
+        let mut fields = Vec::with_capacity(record.len());
+
+        for value in &record {
+            fields.push(field(value)?);
+        }
+
+        self.inner.write_record(&fields)?;
+        Ok(())
+    }
+
+    /// Finish writing, returning everything that has been written.
+    fn finish(self) -> rune::Result<String> {
+        let bytes = self.inner.into_inner().map_err(|e| e.into_error())?;
+        Ok(String::from_utf8(bytes)?)
+    }
+}
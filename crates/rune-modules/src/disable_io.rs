@@ -21,6 +21,10 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.function(["println"], move |_: &str| {})?;
 
+    module.function(["eprint"], move |_: &str| {})?;
+
+    module.function(["eprintln"], move |_: &str| {})?;
+
     module.raw_fn(["dbg"], move |stack: &mut Stack, args: usize| {
         // NB: still need to maintain the stack.
         drop(stack.drain(args)?);
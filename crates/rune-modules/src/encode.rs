@@ -0,0 +1,124 @@
+//! The native `encode` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! Provides base64, hex and percent-encoding. Every encoder accepts either a
+//! string or bytes, and decoders return bytes except for `url_decode`.
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["encode"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::encode::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! fn main() {
+//!     let token = encode::base64_url_encode(b"\xfb\xff");
+//!     let raw = encode::base64_url_decode(token)?;
+//!
+//!     let digest = encode::hex_encode(raw);
+//!     let query = encode::url_encode("a b&c");
+//! }
+//! ```
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+use base64::Engine as _;
+use percent_encoding::{percent_decode_str, percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use rune::runtime::{Bytes, Value, VmError};
+use rune::{ContextError, Module};
+
+/// Characters which are left as-is by `url_encode`, the unreserved
+/// characters of RFC 3986.
+const URL_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Construct the `encode` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("encode");
+    module.function(["base64_encode"], base64_encode)?;
+    module.function(["base64_decode"], base64_decode)?;
+    module.function(["base64_url_encode"], base64_url_encode)?;
+    module.function(["base64_url_decode"], base64_url_decode)?;
+    module.function(["hex_encode"], hex_encode)?;
+    module.function(["hex_decode"], hex_decode)?;
+    module.function(["url_encode"], url_encode)?;
+    module.function(["url_decode"], url_decode)?;
+    module.function(["url_decode_bytes"], url_decode_bytes)?;
+    Ok(module)
+}
+
+/// Encode using the standard base64 alphabet, with padding.
+fn base64_encode(data: Value) -> Result<String, VmError> {
+    with_bytes(&data, |data| STANDARD.encode(data))
+}
+
+/// Decode using the standard base64 alphabet, with padding.
+fn base64_decode(data: &str) -> rune::Result<Bytes> {
+    Ok(Bytes::from_vec(STANDARD.decode(data)?))
+}
+
+/// Encode using the URL and filename safe base64 alphabet, with padding.
+fn base64_url_encode(data: Value) -> Result<String, VmError> {
+    with_bytes(&data, |data| URL_SAFE.encode(data))
+}
+
+/// Decode using the URL and filename safe base64 alphabet, with padding.
+fn base64_url_decode(data: &str) -> rune::Result<Bytes> {
+    Ok(Bytes::from_vec(URL_SAFE.decode(data)?))
+}
+
+/// Encode as lowercase hex.
+fn hex_encode(data: Value) -> Result<String, VmError> {
+    with_bytes(&data, |data| hex::encode(data))
+}
+
+/// Decode hex, in either case.
+fn hex_decode(data: &str) -> rune::Result<Bytes> {
+    Ok(Bytes::from_vec(hex::decode(data)?))
+}
+
+/// Percent-encode every character except for the unreserved characters
+/// `A-Z`, `a-z`, `0-9`, `-`, `.`, `_` and `~`.
+fn url_encode(data: Value) -> Result<String, VmError> {
+    with_bytes(&data, |data| percent_encode(data, URL_ENCODE_SET).to_string())
+}
+
+/// Decode a percent-encoded string, erroring if it's not valid UTF-8.
+fn url_decode(data: &str) -> rune::Result<String> {
+    Ok(percent_decode_str(data).decode_utf8()?.into_owned())
+}
+
+/// Decode a percent-encoded string into bytes.
+fn url_decode_bytes(data: &str) -> Bytes {
+    Bytes::from_vec(percent_decode_str(data).collect())
+}
+
+/// Call `f` with the contents of a string or bytes value.
+fn with_bytes<F, T>(value: &Value, f: F) -> Result<T, VmError>
+where
+    F: FnOnce(&[u8]) -> T,
+{
+    match value {
+        Value::String(s) => Ok(f(s.borrow_ref()?.as_bytes())),
+        Value::StaticString(s) => Ok(f(s.as_bytes())),
+        Value::Bytes(b) => Ok(f(&b.borrow_ref()?)),
+        actual => Err(VmError::expected::<Bytes>(actual.type_info()?)),
+    }
+}
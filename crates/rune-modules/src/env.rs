@@ -0,0 +1,159 @@
+//! The native `std::env` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! Gives scripts access to the arguments they were started with and to
+//! environment variables. Which variables are visible is decided by the
+//! embedder through [Config], and none are by default.
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["env"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! use rune_modules::env::{self, Config};
+//!
+//! # fn main() -> rune::Result<()> {
+//! let config = Config::new()
+//!     .args(["--verbose", "input.txt"])
+//!     .allow_var("HOME");
+//!
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(env::module(config)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! fn main() {
+//!     for arg in std::env::args() {
+//!         println!("{}", arg);
+//!     }
+//!
+//!     if let Some(home) = std::env::var("HOME") {
+//!         println!("home: {}", home);
+//!     }
+//! }
+//! ```
+
+use rune::runtime::Object;
+use rune::{ContextError, Module, Value};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// Construct the `std::env` module with the given configuration.
+pub fn module(config: Config) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["env"]).with_capability("env");
+
+    let config = Arc::new(config);
+
+    let c = config.clone();
+    module.function(["args"], move || c.args.clone())?;
+
+    let c = config.clone();
+    module.function(["var"], move |name: &str| c.var(name))?;
+
+    let c = config;
+    module.function(["vars"], move || c.vars())?;
+    Ok(module)
+}
+
+/// Configuration for the `std::env` module.
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    args: Vec<String>,
+    vars: Vars,
+}
+
+#[derive(Debug, Clone)]
+enum Vars {
+    /// Only the named variables are visible.
+    Only(BTreeSet<String>),
+    /// Every variable is visible.
+    All,
+}
+
+impl Default for Vars {
+    fn default() -> Self {
+        Self::Only(BTreeSet::new())
+    }
+}
+
+impl Config {
+    /// Construct a configuration with no arguments, where no environment
+    /// variables are visible.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the arguments returned by `std::env::args`.
+    pub fn args<I>(mut self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Make the environment variable `name` visible to scripts.
+    pub fn allow_var<N>(mut self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        if let Vars::Only(names) = &mut self.vars {
+            names.insert(name.into());
+        }
+
+        self
+    }
+
+    /// Make every environment variable visible to scripts.
+    pub fn allow_all_vars(mut self) -> Self {
+        self.vars = Vars::All;
+        self
+    }
+
+    fn is_allowed(&self, name: &str) -> bool {
+        match &self.vars {
+            Vars::Only(names) => names.contains(name),
+            Vars::All => true,
+        }
+    }
+
+    /// Read a visible environment variable, which is `None` if it's not set,
+    /// not visible, or not valid unicode.
+    fn var(&self, name: &str) -> Option<String> {
+        if !self.is_allowed(name) {
+            return None;
+        }
+
+        std::env::var(name).ok()
+    }
+
+    /// Every visible environment variable which is set to valid unicode.
+    fn vars(&self) -> Object {
+        let mut object = Object::new();
+
+        for (name, value) in std::env::vars_os() {
+            let (name, value) = match (name.into_string(), value.into_string()) {
+                (Ok(name), Ok(value)) => (name, value),
+                _ => continue,
+            };
+
+            if self.is_allowed(&name) {
+                object.insert(name, Value::from(value));
+            }
+        }
+
+        object
+    }
+}
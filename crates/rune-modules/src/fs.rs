@@ -28,18 +28,279 @@
 //!     println(`{file}`);
 //! }
 //! ```
+//!
+//! ## Sandboxing
+//!
+//! The module constructed through [module] can access any path the process
+//! can. To only grant access to some directories, install the module
+//! constructed through [module_with_roots] instead:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! let scratch = std::env::temp_dir();
+//! context.install(rune_modules::fs::module_with_roots([scratch])?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Every root has to exist when the module is constructed. Relative paths used
+//! by scripts are then resolved against the first root, and any path which
+//! after resolving symbolic links and `..` components ends up outside of every
+//! root is rejected with a permission denied error.
 
+use rune::runtime::{Bytes, Protocol};
+use rune::{Any, ContextError, Module};
+use std::fmt;
+use std::fmt::Write;
 use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::fs;
-use rune::{Module, ContextError};
 
 /// Construct the `fs` module.
+///
+/// Its functions are gated behind the `fs` capability, see
+/// [rune::runtime::Capabilities].
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
-    let mut module = Module::with_crate("fs");
-    module.async_function(["read_to_string"], read_to_string)?;
+    install(Sandbox { roots: None })
+}
+
+/// Construct the `fs` module, only granting access to paths inside of the
+/// given root directories.
+///
+/// Its functions are gated behind the `fs` capability, see
+/// [rune::runtime::Capabilities].
+///
+/// This errors if any of the roots can't be resolved, like if it doesn't
+/// exist.
+pub fn module_with_roots<I>(roots: I) -> rune::Result<Module>
+where
+    I: IntoIterator,
+    I::Item: Into<PathBuf>,
+{
+    let roots = roots
+        .into_iter()
+        .map(|root| {
+            let root = root.into();
+
+            std::fs::canonicalize(&root).map_err(|error| {
+                rune::Error::new(error)
+                    .context(format!("cannot resolve root {}", root.display()))
+            })
+        })
+        .collect::<rune::Result<_>>()?;
+
+    Ok(install(Sandbox { roots: Some(roots) })?)
+}
+
+fn install(sandbox: Sandbox) -> Result<Module, ContextError> {
+    let sandbox = Arc::new(sandbox);
+
+    let mut module = Module::with_crate("fs").with_capability("fs");
+    module.ty::<Metadata>()?;
+
+    let s = sandbox.clone();
+    module.async_function(["read_to_string"], move |path: String| {
+        read_to_string(s.clone(), path)
+    })?;
+
+    let s = sandbox.clone();
+    module.async_function(["read"], move |path: String| read(s.clone(), path))?;
+
+    let s = sandbox.clone();
+    module.async_function(["write"], move |path: String, contents: String| {
+        write(s.clone(), path, contents.into_bytes())
+    })?;
+
+    let s = sandbox.clone();
+    module.async_function(["write_bytes"], move |path: String, contents: Bytes| {
+        write(s.clone(), path, contents.into_vec())
+    })?;
+
+    let s = sandbox.clone();
+    module.async_function(["read_dir"], move |path: String| {
+        read_dir(s.clone(), path)
+    })?;
+
+    let s = sandbox.clone();
+    module.async_function(["metadata"], move |path: String| {
+        metadata(s.clone(), path)
+    })?;
+
+    let s = sandbox.clone();
+    module.async_function(["exists"], move |path: String| exists(s.clone(), path))?;
+
+    let s = sandbox.clone();
+    module.async_function(["create_dir_all"], move |path: String| {
+        create_dir_all(s.clone(), path)
+    })?;
+
+    let s = sandbox;
+    module.async_function(["remove_file"], move |path: String| {
+        remove_file(s.clone(), path)
+    })?;
+
+    module.inst_fn("len", Metadata::len)?;
+    module.inst_fn("is_file", Metadata::is_file)?;
+    module.inst_fn("is_dir", Metadata::is_dir)?;
+    module.inst_fn("is_readonly", Metadata::is_readonly)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Metadata::string_debug)?;
     Ok(module)
 }
 
-async fn read_to_string(path: &str) -> io::Result<String> {
-    fs::read_to_string(path).await
+/// The directories scripts are allowed to access, or `None` if they can
+/// access anything.
+struct Sandbox {
+    roots: Option<Vec<PathBuf>>,
+}
+
+impl Sandbox {
+    /// Resolve a path provided by a script, checking that it's inside of one
+    /// of the roots.
+    async fn resolve(&self, path: &str) -> io::Result<PathBuf> {
+        let roots = match &self.roots {
+            Some(roots) => roots,
+            None => return Ok(PathBuf::from(path)),
+        };
+
+        let path = Path::new(path);
+
+        let path = if path.is_relative() {
+            match roots.first() {
+                Some(root) => root.join(path),
+                None => return Err(denied(path)),
+            }
+        } else {
+            path.to_owned()
+        };
+
+        // NB: paths which are about to be created don't exist yet, so resolve
+        // their closest existing ancestor instead. Components which don't
+        // resolve might still be dangling symbolic links which would be
+        // followed when writing, so those are rejected, as is `..` since it
+        // has no file name.
+        let mut existing = path.as_path();
+        let mut missing = Vec::new();
+
+        let resolved = loop {
+            match fs::canonicalize(existing).await {
+                Ok(resolved) => break resolved,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    if let Ok(metadata) = fs::symlink_metadata(existing).await {
+                        if metadata.file_type().is_symlink() {
+                            return Err(denied(&path));
+                        }
+                    }
+
+                    match (existing.parent(), existing.file_name()) {
+                        (Some(parent), Some(name)) => {
+                            missing.push(name.to_owned());
+                            existing = parent;
+                        }
+                        _ => return Err(e),
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        };
+
+        let resolved = missing
+            .into_iter()
+            .rev()
+            .fold(resolved, |path, name| path.join(name));
+
+        if roots.iter().any(|root| resolved.starts_with(root)) {
+            Ok(resolved)
+        } else {
+            Err(denied(&path))
+        }
+    }
+}
+
+fn denied(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        format!("{} is outside of the allowed roots", path.display()),
+    )
+}
+
+/// Metadata about a file or directory.
+#[derive(Debug, Any)]
+struct Metadata {
+    inner: std::fs::Metadata,
+}
+
+impl Metadata {
+    /// The size of the file in bytes.
+    fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    fn is_file(&self) -> bool {
+        self.inner.is_file()
+    }
+
+    fn is_dir(&self) -> bool {
+        self.inner.is_dir()
+    }
+
+    fn is_readonly(&self) -> bool {
+        self.inner.permissions().readonly()
+    }
+
+    fn string_debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// Read the contents of a file as a string.
+async fn read_to_string(sandbox: Arc<Sandbox>, path: String) -> io::Result<String> {
+    fs::read_to_string(sandbox.resolve(&path).await?).await
+}
+
+/// Read the contents of a file as bytes.
+async fn read(sandbox: Arc<Sandbox>, path: String) -> io::Result<Bytes> {
+    Ok(Bytes::from_vec(fs::read(sandbox.resolve(&path).await?).await?))
+}
+
+/// Write the given contents to a file, replacing it if it exists.
+async fn write(sandbox: Arc<Sandbox>, path: String, contents: Vec<u8>) -> io::Result<()> {
+    fs::write(sandbox.resolve(&path).await?, contents).await
+}
+
+/// List the names of the entries in a directory.
+async fn read_dir(sandbox: Arc<Sandbox>, path: String) -> io::Result<Vec<String>> {
+    let mut dir = fs::read_dir(sandbox.resolve(&path).await?).await?;
+    let mut names = Vec::new();
+
+    while let Some(entry) = dir.next_entry().await? {
+        names.push(entry.file_name().to_string_lossy().into_owned());
+    }
+
+    Ok(names)
+}
+
+/// Get the metadata of a file or directory.
+async fn metadata(sandbox: Arc<Sandbox>, path: String) -> io::Result<Metadata> {
+    let inner = fs::metadata(sandbox.resolve(&path).await?).await?;
+    Ok(Metadata { inner })
+}
+
+/// Test if a path exists. Paths outside of the allowed roots never exist.
+async fn exists(sandbox: Arc<Sandbox>, path: String) -> bool {
+    match sandbox.resolve(&path).await {
+        Ok(path) => fs::metadata(path).await.is_ok(),
+        Err(..) => false,
+    }
+}
+
+/// Create a directory and all of its missing parents.
+async fn create_dir_all(sandbox: Arc<Sandbox>, path: String) -> io::Result<()> {
+    fs::create_dir_all(sandbox.resolve(&path).await?).await
+}
+
+/// Remove a file.
+async fn remove_file(sandbox: Arc<Sandbox>, path: String) -> io::Result<()> {
+    fs::remove_file(sandbox.resolve(&path).await?).await
 }
@@ -25,13 +25,10 @@
 //!
 //! ```rust,ignore
 //! use http;
-//! use json;
 //!
-//! fn main() {
-//!     let client = http::Client::new();
-//!     let response = client.get("http://worldtimeapi.org/api/ip");
-//!     let text = response.text();
-//!     let json = json::from_string(text);
+//! pub async fn main() {
+//!     let response = http::get("http://worldtimeapi.org/api/ip").await?;
+//!     let json = response.json().await?;
 //!
 //!     let timezone = json["timezone"];
 //!
@@ -39,25 +36,31 @@
 //!         dbg(timezone);
 //!     }
 //!
-//!     let body = json::to_bytes(#{"hello": "world"});
+//!     let client = http::Client::new();
 //!
-//!     let response = client.post("https://postman-echo.com/post")
-//!         .body_bytes(body)
-//!         .send();
+//!     let response = client.post("https://postman-echo.com/post").await?
+//!         .header("Accept", "application/json")
+//!         .timeout(std::time::Duration::from_secs(10))
+//!         .body_json(#{"hello": "world"})
+//!         .send()
+//!         .await?;
 //!
-//!     let response = json::from_string(response.text());
-//!     dbg(response);
+//!     dbg(response.header("content-type"), response.json().await?);
 //! }
 //! ```
 
 use rune::{Any, Module, Value, ContextError};
+use rune::modules::time::Duration;
 use rune::runtime::{Bytes, Protocol};
 use std::fmt;
 use std::fmt::Write;
 
 /// Construct the `http` module.
+///
+/// Its functions are gated behind the `net` capability, see
+/// [rune::runtime::Capabilities].
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
-    let mut module = Module::with_crate("http");
+    let mut module = Module::with_crate("http").with_capability("net");
 
     module.ty::<Client>()?;
     module.ty::<Response>()?;
@@ -74,10 +77,13 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     module.async_inst_fn("text", Response::text)?;
     module.async_inst_fn("json", Response::json)?;
     module.inst_fn("status", Response::status)?;
+    module.inst_fn("header", Response::header)?;
 
     module.async_inst_fn("send", RequestBuilder::send)?;
     module.inst_fn("header", RequestBuilder::header)?;
+    module.inst_fn("timeout", RequestBuilder::timeout)?;
     module.async_inst_fn("body_bytes", RequestBuilder::body_bytes)?;
+    module.inst_fn("body_json", RequestBuilder::body_json)?;
 
     module.inst_fn(Protocol::STRING_DISPLAY, Error::display)?;
     module.inst_fn(Protocol::STRING_DISPLAY, StatusCode::display)?;
@@ -139,6 +145,13 @@ impl Response {
 
         StatusCode { inner }
     }
+
+    /// Get the value of a header in the response, if it's present and valid
+    /// UTF-8.
+    fn header(&self, key: &str) -> Option<String> {
+        let value = self.response.headers().get(key)?;
+        Some(value.to_str().ok()?.to_owned())
+    }
 }
 
 #[derive(Debug, Any)]
@@ -160,6 +173,22 @@ impl RequestBuilder {
         }
     }
 
+    /// Set a timeout for the whole request, from when it starts connecting
+    /// until the response body has been read.
+    fn timeout(self, duration: &Duration) -> Self {
+        Self {
+            request: self.request.timeout((*duration).into()),
+        }
+    }
+
+    /// Set the request body to the given value encoded as JSON, and the
+    /// `Content-Type` header to `application/json`.
+    fn body_json(self, value: Value) -> Self {
+        Self {
+            request: self.request.json(&value),
+        }
+    }
+
     /// Set the request body from bytes.
     async fn body_bytes(self, bytes: Bytes) -> Result<Self, Error> {
         let bytes = bytes.into_vec();
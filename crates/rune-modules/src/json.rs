@@ -26,31 +26,129 @@
 //! use json;
 //!
 //! fn main() {
-//!     let data = json::from_string("{\"key\": 42}");
+//!     let data = json::from_string("{\"key\": 42}")?;
 //!     dbg(data);
+//!
+//!     // Decode every number as a float.
+//!     let data = json::from_string_with("[1, 2.5]", #{ numbers: "float" })?;
+//!     println!("{}", json::to_string_pretty(data)?);
 //! }
 //! ```
+//!
+//! Numbers are decoded as integers when they are written without a fraction
+//! or exponent and fit in an `i64`, and as floats otherwise. Floats are always
+//! encoded with a fraction, so `1.0` round-trips as a float.
 
-use rune::{ContextError, Module};
-use rune::runtime::{Bytes, Value};
+use rune::runtime::{Bytes, Object, Shared, Value, Vec};
+use rune::{ContextError, FromValue, Module};
 
 /// Construct the `json` module.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     let mut module = Module::with_crate("json");
     module.function(["from_bytes"], from_bytes)?;
     module.function(["from_string"], from_string)?;
+    module.function(["from_bytes_with"], from_bytes_with)?;
+    module.function(["from_string_with"], from_string_with)?;
     module.function(["to_string"], to_string)?;
+    module.function(["to_string_pretty"], to_string_pretty)?;
     module.function(["to_bytes"], to_bytes)?;
+    module.function(["to_bytes_pretty"], to_bytes_pretty)?;
     Ok(module)
 }
 
+/// How numbers are decoded.
+#[derive(Clone, Copy)]
+enum Numbers {
+    /// Integers which fit in an `i64` are decoded as integers, everything
+    /// else as floats.
+    Auto,
+    /// Every number is decoded as a float.
+    Float,
+}
+
+/// Options for decoding, parsed from an object like `#{ numbers: "float" }`.
+struct Options {
+    numbers: Numbers,
+}
+
+impl Options {
+    const DEFAULT: Self = Self {
+        numbers: Numbers::Auto,
+    };
+
+    fn from_object(object: &Object) -> rune::Result<Self> {
+        let mut options = Self::DEFAULT;
+
+        for (key, value) in object {
+            match key.as_str() {
+                "numbers" => {
+                    options.numbers = match String::from_value(value.clone())?.as_str() {
+                        "auto" => Numbers::Auto,
+                        "float" => Numbers::Float,
+                        other => {
+                            return Err(rune::Error::msg(format!(
+                                "unsupported `numbers` option `{other}`, expected `auto` or `float`"
+                            )))
+                        }
+                    };
+                }
+                other => return Err(rune::Error::msg(format!("unsupported option `{other}`"))),
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Convert a decoded JSON value into a script value.
+    fn decode(&self, value: serde_json::Value) -> Value {
+        match value {
+            serde_json::Value::Null => Value::Unit,
+            serde_json::Value::Bool(b) => Value::from(b),
+            serde_json::Value::Number(n) => match (self.numbers, n.as_i64()) {
+                (Numbers::Auto, Some(n)) => Value::from(n),
+                _ => Value::from(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(s) => Value::from(s),
+            serde_json::Value::Array(values) => {
+                let values = values
+                    .into_iter()
+                    .map(|v| self.decode(v))
+                    .collect::<std::vec::Vec<_>>();
+
+                Value::from(Shared::new(Vec::from(values)))
+            }
+            serde_json::Value::Object(values) => {
+                let object = values
+                    .into_iter()
+                    .map(|(k, v)| (k, self.decode(v)))
+                    .collect::<Object>();
+
+                Value::from(Shared::new(object))
+            }
+        }
+    }
+}
+
+/// Get value from json bytes.
 fn from_bytes(bytes: &[u8]) -> rune::Result<Value> {
-    Ok(serde_json::from_slice(bytes)?)
+    Ok(Options::DEFAULT.decode(serde_json::from_slice(bytes)?))
 }
 
 /// Get value from json string.
 fn from_string(string: &str) -> rune::Result<Value> {
-    Ok(serde_json::from_str(string)?)
+    Ok(Options::DEFAULT.decode(serde_json::from_str(string)?))
+}
+
+/// Get value from json bytes, decoded according to the given options.
+fn from_bytes_with(bytes: &[u8], options: &Object) -> rune::Result<Value> {
+    let options = Options::from_object(options)?;
+    Ok(options.decode(serde_json::from_slice(bytes)?))
+}
+
+/// Get value from json string, decoded according to the given options.
+fn from_string_with(string: &str, options: &Object) -> rune::Result<Value> {
+    let options = Options::from_object(options)?;
+    Ok(options.decode(serde_json::from_str(string)?))
 }
 
 /// Convert any value to a json string.
@@ -58,8 +156,19 @@ fn to_string(value: Value) -> rune::Result<String> {
     Ok(serde_json::to_string(&value)?)
 }
 
+/// Convert any value to a pretty-printed json string.
+fn to_string_pretty(value: Value) -> rune::Result<String> {
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
 /// Convert any value to json bytes.
 fn to_bytes(value: Value) -> rune::Result<Bytes> {
     let bytes = serde_json::to_vec(&value)?;
     Ok(Bytes::from_vec(bytes))
 }
+
+/// Convert any value to pretty-printed json bytes.
+fn to_bytes_pretty(value: Value) -> rune::Result<Bytes> {
+    let bytes = serde_json::to_vec_pretty(&value)?;
+    Ok(Bytes::from_vec(bytes))
+}
@@ -25,7 +25,12 @@
 //! [Rune Language]: https://rune-rs.github.io
 //!
 //! See each module for documentation:
+//! * [chrono]
 //! * [core]
+//! * [crypto]
+//! * [csv]
+//! * [encode]
+//! * [env]
 //! * [experiments]
 //! * [fmt]
 //! * [fs]
@@ -33,18 +38,30 @@
 //! * [io]
 //! * [json]
 //! * [macros]
+//! * [net]
+//! * [path]
 //! * [process]
 //! * [rand]
+//! * [regex]
 //! * [signal]
 //! * [test]
 //! * [time]
 //! * [toml]
+//! * [unicode]
+//! * [url]
+//! * [uuid]
+//! * [yaml]
 //!
 //! <br>
 //!
 //! ## Features
 //!
+//! * `chrono` for the [chrono module][chrono]
 //! * `core` for the [core module][toml]
+//! * `crypto` for the [crypto module][crypto]
+//! * `csv` for the [csv module][csv]
+//! * `encode` for the [encode module][encode]
+//! * `env` for the [env module][env]
 //! * `experiments` for the [experiments module][experiments]
 //! * `fmt` for the [fmt module][fmt]
 //! * `fs` for the [fs module][fs]
@@ -53,14 +70,26 @@
 //! * `io` for the [io module][io]
 //! * `json` for the [json module][json]
 //! * `macros` for the [macros module][macros]
+//! * `net` for the [net module][net]
+//! * `path` for the [path module][path]
 //! * `process` for the [process module][process]
 //! * `rand` for the [rand module][rand]
+//! * `regex` for the [regex module][regex]
 //! * `signal` for the [signal module][signal]
 //! * `test` for the [test module][test]
 //! * `time` for the [time module][time]
 //! * `toml` for the [toml module][toml]
+//! * `unicode` for the [unicode module][unicode]
+//! * `url` for the [url module][url]
+//! * `uuid` for the [uuid module][uuid]
+//! * `yaml` for the [yaml module][yaml]
 //!
+//! [chrono]: https://docs.rs/rune-modules/0/rune_modules/chrono/
 //! [core]: https://docs.rs/rune-modules/0/rune_modules/core/
+//! [crypto]: https://docs.rs/rune-modules/0/rune_modules/crypto/
+//! [csv]: https://docs.rs/rune-modules/0/rune_modules/csv/
+//! [encode]: https://docs.rs/rune-modules/0/rune_modules/encode/
+//! [env]: https://docs.rs/rune-modules/0/rune_modules/env/
 //! [experiments]: https://docs.rs/rune-modules/0/rune_modules/experiments/
 //! [fmt]: https://docs.rs/rune-modules/0/rune_modules/fmt/
 //! [fs]: https://docs.rs/rune-modules/0/rune_modules/fs/
@@ -68,12 +97,19 @@
 //! [io]: https://docs.rs/rune-modules/0/rune_modules/io/
 //! [json]: https://docs.rs/rune-modules/0/rune_modules/json/
 //! [macros]: https://docs.rs/rune-modules/0/rune_modules/macros/
+//! [net]: https://docs.rs/rune-modules/0/rune_modules/net/
+//! [path]: https://docs.rs/rune-modules/0/rune_modules/path/
 //! [process]: https://docs.rs/rune-modules/0/rune_modules/process/
 //! [rand]: https://docs.rs/rune-modules/0/rune_modules/rand/
+//! [regex]: https://docs.rs/rune-modules/0/rune_modules/regex/
 //! [signal]: https://docs.rs/rune-modules/0/rune_modules/signal/
 //! [test]: https://docs.rs/rune-modules/0/rune_modules/test/
 //! [time]: https://docs.rs/rune-modules/0/rune_modules/time/
 //! [toml]: https://docs.rs/rune-modules/0/rune_modules/toml/
+//! [unicode]: https://docs.rs/rune-modules/0/rune_modules/unicode/
+//! [url]: https://docs.rs/rune-modules/0/rune_modules/url/
+//! [uuid]: https://docs.rs/rune-modules/0/rune_modules/uuid/
+//! [yaml]: https://docs.rs/rune-modules/0/rune_modules/yaml/
 
 // Note: The above links to docs.rs are needed because cargo-readme does not
 // support intra-doc links (yet):
@@ -82,6 +118,9 @@
 #[cfg(feature = "experiments")]
 pub mod experiments;
 
+#[cfg(feature = "env")]
+pub mod env;
+
 #[cfg(feature = "capture-io")]
 pub mod capture_io;
 
@@ -121,17 +160,28 @@ macro_rules! modules {
 }
 
 modules! {
+    chrono, "chrono",
     core, "core",
+    crypto, "crypto",
+    csv, "csv",
+    encode, "encode",
     fmt, "fmt",
     fs, "fs",
     http, "http",
     io, "io",
     json, "json",
     macros, "macros",
+    net, "net",
+    path, "path",
     process, "process",
     rand, "rand",
+    regex, "regex",
     signal, "signal",
     test, "test",
     time, "time",
     toml, "toml",
+    unicode, "unicode",
+    url, "url",
+    uuid, "uuid",
+    yaml, "yaml",
 }
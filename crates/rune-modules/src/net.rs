@@ -0,0 +1,228 @@
+//! The native `net` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! Provides asynchronous TCP and UDP sockets. Addresses are given as strings
+//! like `"127.0.0.1:8080"` or `"example.com:80"`, and data is read and written
+//! as bytes.
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["net"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::net::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use net::{TcpListener, TcpStream};
+//!
+//! async fn main() {
+//!     let listener = TcpListener::bind("127.0.0.1:0").await?;
+//!     let addr = listener.local_addr()?;
+//!
+//!     let client = TcpStream::connect(addr).await?;
+//!     client.write_all(b"ping").await?;
+//!
+//!     let (server, peer) = listener.accept().await?;
+//!     dbg(server.read(1024).await?, peer);
+//! }
+//! ```
+
+use rune::{Any, ContextError, Module};
+use rune::runtime::Bytes;
+use std::io;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net;
+
+/// Construct the `net` module.
+///
+/// Its functions are gated behind the `net` capability, see
+/// [rune::runtime::Capabilities].
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("net").with_capability("net");
+    module.ty::<TcpStream>()?;
+    module.ty::<TcpListener>()?;
+    module.ty::<UdpSocket>()?;
+
+    module.async_function(["TcpStream", "connect"], TcpStream::connect)?;
+    module.async_inst_fn("read", TcpStream::read)?;
+    module.async_inst_fn("read_exact", TcpStream::read_exact)?;
+    module.async_inst_fn("write", TcpStream::write)?;
+    module.async_inst_fn("write_all", TcpStream::write_all)?;
+    module.async_inst_fn("flush", TcpStream::flush)?;
+    module.async_inst_fn("shutdown", TcpStream::shutdown)?;
+    module.inst_fn("local_addr", TcpStream::local_addr)?;
+    module.inst_fn("peer_addr", TcpStream::peer_addr)?;
+    module.inst_fn("set_nodelay", TcpStream::set_nodelay)?;
+
+    module.async_function(["TcpListener", "bind"], TcpListener::bind)?;
+    module.async_inst_fn("accept", TcpListener::accept)?;
+    module.inst_fn("local_addr", TcpListener::local_addr)?;
+
+    module.async_function(["UdpSocket", "bind"], UdpSocket::bind)?;
+    module.async_inst_fn("connect", UdpSocket::connect)?;
+    module.async_inst_fn("send", UdpSocket::send)?;
+    module.async_inst_fn("recv", UdpSocket::recv)?;
+    module.async_inst_fn("send_to", UdpSocket::send_to)?;
+    module.async_inst_fn("recv_from", UdpSocket::recv_from)?;
+    module.inst_fn("local_addr", UdpSocket::local_addr)?;
+    module.inst_fn("peer_addr", UdpSocket::peer_addr)?;
+    Ok(module)
+}
+
+/// A TCP connection.
+#[derive(Debug, Any)]
+struct TcpStream {
+    inner: net::TcpStream,
+}
+
+impl TcpStream {
+    /// Open a connection to the given address.
+    async fn connect(addr: String) -> io::Result<Self> {
+        Ok(Self {
+            inner: net::TcpStream::connect(addr).await?,
+        })
+    }
+
+    /// Read at most `max` bytes, returning empty bytes once the connection
+    /// has been closed by the peer.
+    async fn read(&mut self, max: usize) -> io::Result<Bytes> {
+        let mut buf = vec![0; max];
+        let n = self.inner.read(&mut buf).await?;
+        buf.truncate(n);
+        Ok(Bytes::from_vec(buf))
+    }
+
+    /// Read exactly `len` bytes, erroring if the connection is closed first.
+    async fn read_exact(&mut self, len: usize) -> io::Result<Bytes> {
+        let mut buf = vec![0; len];
+        self.inner.read_exact(&mut buf).await?;
+        Ok(Bytes::from_vec(buf))
+    }
+
+    /// Write some of `bytes`, returning how many were written.
+    async fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.inner.write(bytes).await
+    }
+
+    /// Write all of `bytes`.
+    async fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.inner.write_all(bytes).await
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().await
+    }
+
+    /// Shut down the writing half of the connection.
+    async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+
+    fn local_addr(&self) -> io::Result<String> {
+        Ok(self.inner.local_addr()?.to_string())
+    }
+
+    fn peer_addr(&self) -> io::Result<String> {
+        Ok(self.inner.peer_addr()?.to_string())
+    }
+
+    fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.set_nodelay(nodelay)
+    }
+}
+
+/// A TCP socket listening for connections.
+#[derive(Debug, Any)]
+struct TcpListener {
+    inner: net::TcpListener,
+}
+
+impl TcpListener {
+    /// Listen on the given address. Use port `0` to have one assigned.
+    async fn bind(addr: String) -> io::Result<Self> {
+        Ok(Self {
+            inner: net::TcpListener::bind(addr).await?,
+        })
+    }
+
+    /// Wait for a connection, returning it along with the address of the
+    /// peer.
+    async fn accept(&self) -> io::Result<(TcpStream, String)> {
+        let (inner, addr) = self.inner.accept().await?;
+        Ok((TcpStream { inner }, addr.to_string()))
+    }
+
+    fn local_addr(&self) -> io::Result<String> {
+        Ok(self.inner.local_addr()?.to_string())
+    }
+}
+
+/// A UDP socket.
+#[derive(Debug, Any)]
+struct UdpSocket {
+    inner: net::UdpSocket,
+}
+
+impl UdpSocket {
+    /// Bind a socket to the given address. Use port `0` to have one assigned.
+    async fn bind(addr: String) -> io::Result<Self> {
+        Ok(Self {
+            inner: net::UdpSocket::bind(addr).await?,
+        })
+    }
+
+    /// Set the address that [UdpSocket::send] sends to, and the only one
+    /// [UdpSocket::recv] receives from.
+    async fn connect(&self, addr: String) -> io::Result<()> {
+        self.inner.connect(addr).await
+    }
+
+    /// Send a datagram to the connected address.
+    async fn send(&self, bytes: &[u8]) -> io::Result<usize> {
+        self.inner.send(bytes).await
+    }
+
+    /// Receive a datagram of at most `max` bytes from the connected address.
+    async fn recv(&self, max: usize) -> io::Result<Bytes> {
+        let mut buf = vec![0; max];
+        let n = self.inner.recv(&mut buf).await?;
+        buf.truncate(n);
+        Ok(Bytes::from_vec(buf))
+    }
+
+    /// Send a datagram to the given address.
+    async fn send_to(&self, bytes: &[u8], addr: String) -> io::Result<usize> {
+        self.inner.send_to(bytes, addr).await
+    }
+
+    /// Receive a datagram of at most `max` bytes, returning it along with the
+    /// address it came from.
+    async fn recv_from(&self, max: usize) -> io::Result<(Bytes, String)> {
+        let mut buf = vec![0; max];
+        let (n, addr) = self.inner.recv_from(&mut buf).await?;
+        buf.truncate(n);
+        Ok((Bytes::from_vec(buf), addr.to_string()))
+    }
+
+    fn local_addr(&self) -> io::Result<String> {
+        Ok(self.inner.local_addr()?.to_string())
+    }
+
+    fn peer_addr(&self) -> io::Result<String> {
+        Ok(self.inner.peer_addr()?.to_string())
+    }
+}
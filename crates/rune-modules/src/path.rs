@@ -0,0 +1,273 @@
+//! The native `path` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! Provides lexical path manipulation which never touches the filesystem. Both
+//! `/` and `\` are accepted as separators regardless of platform, and a path
+//! is formatted with the first separator it was written with.
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["path"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::path::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use path::Path;
+//!
+//! fn main() {
+//!     let path = Path::new("src/bin").join("../lib.rs").normalize();
+//!     assert_eq!(path.to_string(), "src/lib.rs");
+//!     assert_eq!(path.extension(), Some("rs"));
+//!
+//!     let path = Path::new("C:\\Users\\rune\\notes.txt");
+//!     assert_eq!(path.with_extension("md").to_string(), "C:\\Users\\rune\\notes.md");
+//! }
+//! ```
+
+use rune::runtime::Protocol;
+use rune::{Any, ContextError, Module};
+use std::fmt;
+use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+
+/// Construct the `path` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("path");
+
+    module.ty::<Path>()?;
+    module.function(["Path", "new"], Path::new)?;
+    module.inst_fn("join", Path::join)?;
+    module.inst_fn("parent", Path::parent)?;
+    module.inst_fn("file_name", Path::file_name)?;
+    module.inst_fn("file_stem", Path::file_stem)?;
+    module.inst_fn("extension", Path::extension)?;
+    module.inst_fn("with_file_name", Path::with_file_name)?;
+    module.inst_fn("with_extension", Path::with_extension)?;
+    module.inst_fn("normalize", Path::normalize)?;
+    module.inst_fn("components", Path::components)?;
+    module.inst_fn("is_absolute", Path::is_absolute)?;
+    module.inst_fn("to_string", Path::format)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Path::string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Path::string_debug)?;
+    Ok(module)
+}
+
+/// A path which has been split into its components.
+///
+/// Equality and hashing ignore which separator the path uses.
+#[derive(Debug, Any, Clone)]
+#[rune(eq, hash)]
+pub struct Path {
+    /// A drive prefix like `C:`, or an empty string.
+    prefix: String,
+    /// Whether the path starts at the root, like `/usr` or `C:\Users`.
+    root: bool,
+    /// The non-empty components of the path.
+    parts: Vec<String>,
+    /// The separator used when formatting the path.
+    sep: char,
+}
+
+impl PartialEq for Path {
+    fn eq(&self, other: &Self) -> bool {
+        self.prefix == other.prefix && self.root == other.root && self.parts == other.parts
+    }
+}
+
+impl Eq for Path {}
+
+impl Hash for Path {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.prefix.hash(state);
+        self.root.hash(state);
+        self.parts.hash(state);
+    }
+}
+
+impl Path {
+    /// Parse a path.
+    pub fn new(path: &str) -> Self {
+        let sep = path.chars().find(|&c| is_sep(c)).unwrap_or('/');
+
+        let (prefix, rest) = match path.as_bytes() {
+            [drive, b':', ..] if drive.is_ascii_alphabetic() => path.split_at(2),
+            _ => ("", path),
+        };
+
+        Self {
+            prefix: prefix.to_owned(),
+            root: rest.starts_with(is_sep),
+            parts: rest
+                .split(is_sep)
+                .filter(|part| !part.is_empty())
+                .map(str::to_owned)
+                .collect(),
+            sep,
+        }
+    }
+
+    /// Append `path` to this path. If `path` is absolute or has a drive
+    /// prefix it replaces this path instead.
+    fn join(&self, path: &str) -> Self {
+        let path = Self::new(path);
+
+        if path.root || !path.prefix.is_empty() {
+            return path;
+        }
+
+        let mut joined = self.clone();
+        joined.parts.extend(path.parts);
+        joined
+    }
+
+    /// The path without its final component, or `None` if there is none.
+    fn parent(&self) -> Option<Self> {
+        let mut parent = self.clone();
+        parent.parts.pop()?;
+        Some(parent)
+    }
+
+    /// The final component, unless it's `..`.
+    fn file_name(&self) -> Option<String> {
+        Some(self.name()?.to_owned())
+    }
+
+    /// The final component without its extension.
+    fn file_stem(&self) -> Option<String> {
+        Some(split_extension(self.name()?).0.to_owned())
+    }
+
+    /// The extension of the final component, without the leading `.`.
+    fn extension(&self) -> Option<String> {
+        Some(split_extension(self.name()?).1?.to_owned())
+    }
+
+    /// Replace the final component with `name`.
+    fn with_file_name(&self, name: &str) -> Self {
+        let mut path = self.clone();
+
+        if path.name().is_some() {
+            path.parts.pop();
+        }
+
+        path.parts.push(name.to_owned());
+        path
+    }
+
+    /// Replace the extension of the final component, removing it if
+    /// `extension` is empty.
+    fn with_extension(&self, extension: &str) -> Self {
+        let name = match self.name() {
+            Some(name) => name,
+            None => return self.clone(),
+        };
+
+        let stem = split_extension(name).0;
+
+        let name = if extension.is_empty() {
+            stem.to_owned()
+        } else {
+            format!("{}.{}", stem, extension)
+        };
+
+        self.with_file_name(&name)
+    }
+
+    /// Remove `.` components and resolve `..` components against the ones
+    /// preceding them. Leading `..` components are kept for relative paths
+    /// and dropped for absolute ones.
+    fn normalize(&self) -> Self {
+        let mut parts = Vec::<String>::new();
+
+        for part in &self.parts {
+            match part.as_str() {
+                "." => {}
+                ".." => {
+                    if matches!(parts.last(), Some(last) if last != "..") {
+                        parts.pop();
+                    } else if !self.root {
+                        parts.push(part.clone());
+                    }
+                }
+                _ => parts.push(part.clone()),
+            }
+        }
+
+        Self {
+            parts,
+            ..self.clone()
+        }
+    }
+
+    /// The components of the path, not including its prefix or root.
+    fn components(&self) -> Vec<String> {
+        self.parts.clone()
+    }
+
+    fn is_absolute(&self) -> bool {
+        self.root
+    }
+
+    fn format(&self) -> String {
+        let mut out = self.prefix.clone();
+
+        if self.root {
+            out.push(self.sep);
+        }
+
+        for (n, part) in self.parts.iter().enumerate() {
+            if n > 0 {
+                out.push(self.sep);
+            }
+
+            out.push_str(part);
+        }
+
+        out
+    }
+
+    /// The final component, unless it's `..`.
+    fn name(&self) -> Option<&str> {
+        match self.parts.last()?.as_str() {
+            ".." => None,
+            name => Some(name),
+        }
+    }
+
+    fn string_display(&self, buf: &mut String) -> fmt::Result {
+        buf.push_str(&self.format());
+        Ok(())
+    }
+
+    fn string_debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "Path({:?})", self.format())
+    }
+}
+
+fn is_sep(c: char) -> bool {
+    c == '/' || c == '\\'
+}
+
+/// Split a file name into its stem and extension. A leading `.` doesn't
+/// start an extension, so `.bashrc` has none.
+fn split_extension(name: &str) -> (&str, Option<&str>) {
+    match name.rfind('.') {
+        Some(0) | None => (name, None),
+        Some(n) => (&name[..n], Some(&name[n + 1..])),
+    }
+}
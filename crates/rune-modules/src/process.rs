@@ -23,11 +23,24 @@
 //! Use it in Rune:
 //!
 //! ```rust,ignore
-//! use process::Command;
+//! use process::{Command, Stdio};
 //!
-//! fn main() {
+//! async fn main() {
 //!     let command = Command::new("ls");
-//!     command.run().await;
+//!     command.args(["-l", "src"]);
+//!     command.current_dir("/tmp");
+//!     command.env("LC_ALL", "C");
+//!
+//!     let output = command.output().await?;
+//!
+//!     if output.status.success() {
+//!         dbg(output.stdout);
+//!     }
+//!
+//!     let command = Command::new("cat");
+//!     command.stdout(Stdio::piped());
+//!     let child = command.spawn()?;
+//!     let output = child.wait_with_output().await?;
 //! }
 //! ```
 
@@ -38,20 +51,39 @@ use std::io;
 use tokio::process;
 
 /// Construct the `process` module.
+///
+/// Its functions are gated behind the `process` capability, see
+/// [rune::runtime::Capabilities].
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
-    let mut module = Module::with_crate("process");
+    let mut module = Module::with_crate("process").with_capability("process");
     module.ty::<Command>()?;
     module.ty::<Child>()?;
     module.ty::<ExitStatus>()?;
     module.ty::<Output>()?;
+    module.ty::<Stdio>()?;
 
     module.function(["Command", "new"], Command::new)?;
     module.inst_fn("spawn", Command::spawn)?;
     module.inst_fn("arg", Command::arg)?;
     module.inst_fn("args", Command::args)?;
+    module.inst_fn("env", Command::env)?;
+    module.inst_fn("env_remove", Command::env_remove)?;
+    module.inst_fn("env_clear", Command::env_clear)?;
+    module.inst_fn("current_dir", Command::current_dir)?;
+    module.inst_fn("stdin", Command::stdin)?;
+    module.inst_fn("stdout", Command::stdout)?;
+    module.inst_fn("stderr", Command::stderr)?;
+    module.async_inst_fn("output", Command::output)?;
+    module.async_inst_fn("status", Command::status)?;
+    module.inst_fn("id", Child::id)?;
+    module.inst_fn("kill", Child::kill)?;
     module.async_inst_fn("wait_with_output", Child::wait_with_output)?;
     module.inst_fn(Protocol::STRING_DISPLAY, ExitStatus::display)?;
     module.inst_fn("code", ExitStatus::code)?;
+    module.inst_fn("success", ExitStatus::success)?;
+    module.function(["Stdio", "inherit"], Stdio::inherit)?;
+    module.function(["Stdio", "piped"], Stdio::piped)?;
+    module.function(["Stdio", "null"], Stdio::null)?;
     Ok(module)
 }
 
@@ -92,12 +124,104 @@ impl Command {
         self.inner.arg(arg);
     }
 
+    /// Set an environment variable for the command.
+    fn env(&mut self, key: &str, value: &str) {
+        self.inner.env(key, value);
+    }
+
+    /// Remove an environment variable inherited by the command.
+    fn env_remove(&mut self, key: &str) {
+        self.inner.env_remove(key);
+    }
+
+    /// Clear every environment variable inherited by the command.
+    fn env_clear(&mut self) {
+        self.inner.env_clear();
+    }
+
+    /// Set the working directory of the command.
+    fn current_dir(&mut self, dir: &str) {
+        self.inner.current_dir(dir);
+    }
+
+    /// Configure the standard input of the command.
+    fn stdin(&mut self, stdio: Stdio) {
+        self.inner.stdin(stdio.into_std());
+    }
+
+    /// Configure the standard output of the command.
+    fn stdout(&mut self, stdio: Stdio) {
+        self.inner.stdout(stdio.into_std());
+    }
+
+    /// Configure the standard error of the command.
+    fn stderr(&mut self, stdio: Stdio) {
+        self.inner.stderr(stdio.into_std());
+    }
+
     /// Spawn the command.
     fn spawn(mut self) -> io::Result<Child> {
         Ok(Child {
             inner: Some(self.inner.spawn()?),
         })
     }
+
+    /// Run the command to completion, capturing its standard output and
+    /// error unless they have been configured otherwise.
+    async fn output(mut self) -> io::Result<Output> {
+        Ok(Output::from(self.inner.output().await?))
+    }
+
+    /// Run the command to completion, inheriting its standard output and
+    /// error unless they have been configured otherwise.
+    async fn status(mut self) -> io::Result<ExitStatus> {
+        let status = self.inner.status().await?;
+        Ok(ExitStatus { status })
+    }
+}
+
+/// How the standard input or output of a command is configured.
+#[derive(Clone, Copy, Any)]
+struct Stdio {
+    kind: StdioKind,
+}
+
+#[derive(Clone, Copy)]
+enum StdioKind {
+    Inherit,
+    Piped,
+    Null,
+}
+
+impl Stdio {
+    /// Inherit the stream of the parent process.
+    fn inherit() -> Self {
+        Self {
+            kind: StdioKind::Inherit,
+        }
+    }
+
+    /// Connect the stream to a pipe, so that it's captured.
+    fn piped() -> Self {
+        Self {
+            kind: StdioKind::Piped,
+        }
+    }
+
+    /// Discard the stream.
+    fn null() -> Self {
+        Self {
+            kind: StdioKind::Null,
+        }
+    }
+
+    fn into_std(self) -> std::process::Stdio {
+        match self.kind {
+            StdioKind::Inherit => std::process::Stdio::inherit(),
+            StdioKind::Piped => std::process::Stdio::piped(),
+            StdioKind::Null => std::process::Stdio::null(),
+        }
+    }
 }
 
 #[derive(Any)]
@@ -110,6 +234,20 @@ struct Child {
 }
 
 impl Child {
+    /// The operating system identifier of the process, if it's still
+    /// running.
+    fn id(&self) -> Option<u32> {
+        self.inner.as_ref()?.id()
+    }
+
+    /// Ask the process to exit, without waiting for it to do so.
+    fn kill(&mut self) -> Result<io::Result<()>, VmError> {
+        match &mut self.inner {
+            Some(inner) => Ok(inner.start_kill()),
+            None => Err(VmError::panic("already completed")),
+        }
+    }
+
     // Returns a future that will resolve to an Output, containing the exit
     // status, stdout, and stderr of the child process.
     async fn wait_with_output(self) -> Result<io::Result<Output>, VmError> {
@@ -125,11 +263,7 @@ impl Child {
             Err(error) => return Ok(Err(error)),
         };
 
-        Ok(Ok(Output {
-            status: ExitStatus { status: output.status },
-            stdout: Shared::new(Bytes::from_vec(output.stdout)),
-            stderr: Shared::new(Bytes::from_vec(output.stderr)),
-        }))
+        Ok(Ok(Output::from(output)))
     }
 }
 
@@ -143,6 +277,18 @@ struct Output {
     stderr: Shared<Bytes>,
 }
 
+impl From<std::process::Output> for Output {
+    fn from(output: std::process::Output) -> Self {
+        Self {
+            status: ExitStatus {
+                status: output.status,
+            },
+            stdout: Shared::new(Bytes::from_vec(output.stdout)),
+            stderr: Shared::new(Bytes::from_vec(output.stderr)),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Any)]
 struct ExitStatus {
     status: std::process::ExitStatus,
@@ -157,4 +303,8 @@ impl ExitStatus {
     fn code(&self) -> Option<i32> {
         self.status.code()
     }
+
+    fn success(&self) -> bool {
+        self.status.success()
+    }
 }
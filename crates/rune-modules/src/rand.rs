@@ -11,6 +11,10 @@
 //! rune-modules = { version = "0.12.3", features = ["rand"] }
 //! ```
 //!
+//! Generators which aren't constructed from an explicit seed are seeded
+//! through [rune::runtime::host::random], so their output is reproducible if a
+//! deterministic host is installed.
+//!
 //! Install it into your context:
 //!
 //! ```rust
@@ -28,16 +32,29 @@
 //!     let rng = rand::WyRand::new();
 //!     let rand_int = rng.int();
 //!     println(`Random int: {rand_int}`);
-//!     let rand_int_range = rng.int_range(-100, 100);
+//!     let rand_int_range = rng.int_range(-100, 100)?;
 //!     println(`Random int between -100 and 100: {rand_int_range}`);
+//!
+//!     let die = rand::int_in(1..=6)?;
+//!     let coin = rand::float() < 0.5;
+//!
+//!     // Generators constructed from the same seed produce the same values.
+//!     let rng = rand::WyRand::new_seed(42);
+//!     let deck = [1, 2, 3, 4, 5];
+//!     rng.shuffle(deck);
+//!     let card = rng.choose(deck);
 //! }
 //! ```
 
 use nanorand::Rng;
 use rune::{Any, ContextError, Module};
-use rune::runtime::Value;
+use rune::runtime::{host, Range, RangeLimits, Value, Vec};
+use std::sync::{Arc, Mutex};
 
 /// Construct the `rand` module.
+///
+/// The free functions of the module share a single generator, which is seeded
+/// through [rune::runtime::host::random] the first time it's used.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     let mut module = Module::with_crate("rand");
 
@@ -46,15 +63,40 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     module.function(["WyRand", "new_seed"], WyRand::new_seed)?;
     module.inst_fn("int", WyRand::int)?;
     module.inst_fn("int_range", WyRand::int_range)?;
+    module.inst_fn("float", WyRand::float)?;
+    module.inst_fn("shuffle", WyRand::shuffle)?;
+    module.inst_fn("choose", WyRand::choose)?;
 
     module.ty::<Pcg64>()?;
     module.function(["Pcg64", "new"], Pcg64::new)?;
     module.function(["Pcg64", "new_seed"], Pcg64::new_seed)?;
     module.inst_fn("int", Pcg64::int)?;
     module.inst_fn("int_range", Pcg64::int_range)?;
+    module.inst_fn("float", Pcg64::float)?;
+    module.inst_fn("shuffle", Pcg64::shuffle)?;
+    module.inst_fn("choose", Pcg64::choose)?;
+
+    let rng = SharedRng::default();
+
+    let r = rng.clone();
+    module.function(["int"], move || int(&r))?;
+
+    let r = rng.clone();
+    module.function(["int_in"], move |range: &Range| int_in(&r, range))?;
+
+    let r = rng.clone();
+    module.function(["int_range"], move |lower: i64, upper: i64| {
+        int_range(&r, lower, upper)
+    })?;
+
+    let r = rng.clone();
+    module.function(["float"], move || float(&r))?;
+
+    let r = rng.clone();
+    module.function(["shuffle"], move |vec: &mut Vec| shuffle(&r, vec))?;
 
-    module.function(["int"], int)?;
-    module.function(["int_range"], int_range)?;
+    let r = rng;
+    module.function(["choose"], move |vec: &Vec| choose(&r, vec))?;
 
     Ok(module)
 }
@@ -68,7 +110,7 @@ impl WyRand {
     /// Create a new RNG instance.
     fn new() -> Self {
         Self {
-            inner: nanorand::WyRand::new(),
+            inner: nanorand::WyRand::new_seed(host::random()),
         }
     }
 
@@ -85,8 +127,23 @@ impl WyRand {
     }
 
     /// Generate a random integer within the specified range
-    fn int_range(&mut self, lower: i64, upper: i64) -> Value {
-        Value::Integer(self.inner.generate_range(0..(upper - lower) as u64) as i64 + lower)
+    fn int_range(&mut self, lower: i64, upper: i64) -> rune::Result<Value> {
+        gen_int_range(&mut self.inner, lower, upper)
+    }
+
+    /// Generate a random float in the range `0.0..1.0`.
+    fn float(&mut self) -> f64 {
+        gen_float(&mut self.inner)
+    }
+
+    /// Shuffle the given vector in place.
+    fn shuffle(&mut self, vec: &mut Vec) {
+        self.inner.shuffle(&mut vec[..]);
+    }
+
+    /// Pick a random element from the given vector, or `None` if it's empty.
+    fn choose(&mut self, vec: &Vec) -> Option<Value> {
+        gen_choose(&mut self.inner, vec)
     }
 }
 
@@ -99,7 +156,9 @@ impl Pcg64 {
     /// Create a new RNG instance.
     fn new() -> Self {
         Self {
-            inner: nanorand::Pcg64::new(),
+            inner: nanorand::Pcg64::new_seed(
+                (host::random() as u128) << 64 | host::random() as u128,
+            ),
         }
     }
 
@@ -116,47 +175,184 @@ impl Pcg64 {
     }
 
     /// Generate a random integer within the specified range
-    fn int_range(&mut self, lower: i64, upper: i64) -> Value {
-        Value::Integer(self.inner.generate_range(0..(upper - lower) as u64) as i64 + lower)
+    fn int_range(&mut self, lower: i64, upper: i64) -> rune::Result<Value> {
+        gen_int_range(&mut self.inner, lower, upper)
+    }
+
+    /// Generate a random float in the range `0.0..1.0`.
+    fn float(&mut self) -> f64 {
+        gen_float(&mut self.inner)
+    }
+
+    /// Shuffle the given vector in place.
+    fn shuffle(&mut self, vec: &mut Vec) {
+        self.inner.shuffle(&mut vec[..]);
+    }
+
+    /// Pick a random element from the given vector, or `None` if it's empty.
+    fn choose(&mut self, vec: &Vec) -> Option<Value> {
+        gen_choose(&mut self.inner, vec)
+    }
+}
+
+/// The generator used by the free functions of a module instance.
+#[derive(Default, Clone)]
+struct SharedRng {
+    inner: Arc<Mutex<Option<nanorand::WyRand>>>,
+}
+
+impl SharedRng {
+    /// Access the generator, seeding it from the host if this is its first
+    /// use.
+    fn with<T>(&self, f: impl FnOnce(&mut nanorand::WyRand) -> T) -> T {
+        let mut inner = self.inner.lock().unwrap();
+        f(inner.get_or_insert_with(|| nanorand::WyRand::new_seed(host::random())))
+    }
+}
+
+/// Generate a random integer.
+fn int(rng: &SharedRng) -> rune::Result<Value> {
+    Ok(Value::Integer(rng.with(|rng| rng.generate::<u64>()) as i64))
+}
+
+/// Generate a random integer within the given range, like `0..10` or
+/// `1..=6`. Unbounded ends extend to the limits of an integer.
+fn int_in(rng: &SharedRng, range: &Range) -> rune::Result<Value> {
+    let lower = match &range.start {
+        Some(start) => start.clone().into_integer()?,
+        None => i64::MIN,
+    };
+
+    match (&range.end, range.limits) {
+        (Some(end), RangeLimits::Closed) => {
+            let upper = end.clone().into_integer()?;
+            rng.with(|rng| gen_int_in(rng, lower, upper))
+        }
+        (Some(end), RangeLimits::HalfOpen) => {
+            let upper = end.clone().into_integer()?;
+            rng.with(|rng| gen_int_range(rng, lower, upper))
+        }
+        (None, _) => rng.with(|rng| gen_int_in(rng, lower, i64::MAX)),
+    }
+}
+
+/// Generate a random integer in the range `lower..upper`.
+fn int_range(rng: &SharedRng, lower: i64, upper: i64) -> rune::Result<Value> {
+    rng.with(|rng| gen_int_range(rng, lower, upper))
+}
+
+/// Generate a random float in the range `0.0..1.0`.
+fn float(rng: &SharedRng) -> f64 {
+    rng.with(gen_float)
+}
+
+/// Shuffle the given vector in place.
+fn shuffle(rng: &SharedRng, vec: &mut Vec) {
+    rng.with(|rng| rng.shuffle(&mut vec[..]));
+}
+
+/// Pick a random element from the given vector, or `None` if it's empty.
+fn choose(rng: &SharedRng, vec: &Vec) -> Option<Value> {
+    rng.with(|rng| gen_choose(rng, vec))
+}
+
+/// Generate a random integer in the exclusive range `lower..upper`.
+fn gen_int_range<R, const N: usize>(rng: &mut R, lower: i64, upper: i64) -> rune::Result<Value>
+where
+    R: Rng<N>,
+{
+    match upper.checked_sub(1) {
+        Some(upper) => gen_int_in(rng, lower, upper),
+        None => Err(rune::Error::msg("cannot generate from an empty range")),
     }
 }
 
-fn int() -> rune::Result<Value> {
-    Ok(Value::Integer(
-        nanorand::WyRand::new().generate::<u64>() as i64
-    ))
+/// Generate a random integer in the inclusive range `lower..=upper`.
+fn gen_int_in<R, const N: usize>(rng: &mut R, lower: i64, upper: i64) -> rune::Result<Value>
+where
+    R: Rng<N>,
+{
+    if lower > upper {
+        return Err(rune::Error::msg("cannot generate from an empty range"));
+    }
+
+    let span = upper.wrapping_sub(lower) as u64;
+
+    let offset = if span == u64::MAX {
+        rng.generate::<u64>()
+    } else {
+        rng.generate_range(0..=span)
+    };
+
+    Ok(Value::Integer(lower.wrapping_add(offset as i64)))
 }
 
-fn int_range(lower: i64, upper: i64) -> rune::Result<Value> {
-    Ok(Value::Integer(
-        nanorand::WyRand::new().generate_range(0..(upper - lower) as u64) as i64 + lower,
-    ))
+fn gen_float<R, const N: usize>(rng: &mut R) -> f64
+where
+    R: Rng<N>,
+{
+    // Use the upper 53 bits, which is the precision of a float, so that the
+    // result is uniformly distributed and never reaches `1.0`.
+    (rng.generate::<u64>() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+fn gen_choose<R, const N: usize>(rng: &mut R, vec: &Vec) -> Option<Value>
+where
+    R: Rng<N>,
+{
+    if vec.is_empty() {
+        return None;
+    }
+
+    vec.get(rng.generate_range(0..vec.len())).cloned()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{int, int_range};
+    use super::{int, int_in, int_range, SharedRng};
+    use rune::runtime::{Range, RangeLimits, Value};
 
     #[test]
     fn test_range_is_exclusive() {
+        let rng = SharedRng::default();
+
         for _ in 0..100 {
-            assert_eq!(int_range(0, 1).unwrap().into_integer().unwrap(), 0);
+            assert_eq!(int_range(&rng, 0, 1).unwrap().into_integer().unwrap(), 0);
         }
     }
 
     #[test]
     fn test_range_can_be_negative() {
+        let rng = SharedRng::default();
+
         for _ in 0..100 {
-            assert_eq!(int_range(-2, -1).unwrap().into_integer().unwrap(), -2);
+            assert_eq!(int_range(&rng, -2, -1).unwrap().into_integer().unwrap(), -2);
+        }
+    }
+
+    #[test]
+    fn test_range_bounds_are_checked() {
+        let rng = SharedRng::default();
+
+        assert!(int_range(&rng, 1, 1).is_err());
+        assert!(int_range(&rng, 1, -1).is_err());
+        assert!(int_range(&rng, 0, i64::MIN).is_err());
+
+        for _ in 0..100 {
+            assert!(int_range(&rng, i64::MIN, i64::MAX).is_ok());
+            let v = int_range(&rng, -1, i64::MAX).unwrap().into_integer().unwrap();
+            assert!(v >= -1 && v < i64::MAX);
         }
     }
 
     #[test]
     fn test_int_is_properly_signed() {
+        let rng = SharedRng::default();
         let mut any_negative = false;
         let mut any_positive = false;
+
         for _ in 0..100 {
-            let v = int().unwrap().into_integer().unwrap();
+            let v = int(&rng).unwrap().into_integer().unwrap();
             any_negative = any_negative || v < 0;
             any_positive = any_positive || v > 0;
         }
@@ -164,4 +360,28 @@ mod tests {
         assert!(any_positive);
         assert!(any_negative);
     }
+
+    #[test]
+    fn test_int_in_bounds() {
+        let rng = SharedRng::default();
+
+        let range = Range::new(
+            Some(Value::Integer(1)),
+            Some(Value::Integer(3)),
+            RangeLimits::Closed,
+        );
+
+        for _ in 0..100 {
+            let v = int_in(&rng, &range).unwrap().into_integer().unwrap();
+            assert!((1..=3).contains(&v));
+        }
+
+        let range = Range::new(
+            Some(Value::Integer(1)),
+            Some(Value::Integer(1)),
+            RangeLimits::HalfOpen,
+        );
+
+        assert!(int_in(&rng, &range).is_err());
+    }
 }
@@ -0,0 +1,218 @@
+//! The native `regex` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["regex"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::regex::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use regex::Regex;
+//!
+//! fn main() {
+//!     let re = Regex::new("(?P<year>\\d{4})-(?P<month>\\d{2})")?;
+//!
+//!     if let Some(captures) = re.captures("released 2023-03") {
+//!         dbg(captures.name("year"), captures.get(2));
+//!     }
+//!
+//!     // Patterns given to the `regex!` macro are checked when the script is
+//!     // compiled, and only compiled once no matter how often they're used.
+//!     let words = regex::regex!("\\s+").split("a  b c");
+//! }
+//! ```
+
+use rune::ast;
+use rune::ast::{Spanned, SpannedError};
+use rune::macros::{quote, MacroContext, TokenStream};
+use rune::parse::Parser;
+use rune::runtime::Protocol;
+use rune::{Any, ContextError, Module};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// Construct the `regex` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("regex");
+
+    module.ty::<Regex>()?;
+    module.ty::<Captures>()?;
+
+    module.function(["Regex", "new"], Regex::new)?;
+    module.function(["Regex", "cached"], Regex::cached)?;
+    module.inst_fn("is_match", Regex::is_match)?;
+    module.inst_fn("find", Regex::find)?;
+    module.inst_fn("find_all", Regex::find_all)?;
+    module.inst_fn("captures", Regex::captures)?;
+    module.inst_fn("replace", Regex::replace)?;
+    module.inst_fn("replace_all", Regex::replace_all)?;
+    module.inst_fn("split", Regex::split)?;
+    module.inst_fn("as_str", Regex::as_str)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Regex::string_display)?;
+
+    module.inst_fn("get", Captures::get)?;
+    module.inst_fn("name", Captures::name)?;
+    module.inst_fn("len", Captures::len)?;
+
+    module.macro_(["regex"], regex_macro)?;
+    Ok(module)
+}
+
+/// Regular expressions which have been compiled through [Regex::cached].
+static CACHE: Mutex<Option<HashMap<String, regex::Regex>>> = Mutex::new(None);
+
+/// A compiled regular expression.
+#[derive(Debug, Any, Clone)]
+pub struct Regex {
+    inner: regex::Regex,
+}
+
+impl From<regex::Regex> for Regex {
+    fn from(inner: regex::Regex) -> Self {
+        Self { inner }
+    }
+}
+
+impl Regex {
+    /// Compile a regular expression.
+    fn new(pattern: &str) -> rune::Result<Self> {
+        Ok(Self::from(regex::Regex::new(pattern)?))
+    }
+
+    /// Compile a regular expression, reusing the result of an earlier
+    /// compilation of the same pattern.
+    fn cached(pattern: &str) -> rune::Result<Self> {
+        let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let cache = cache.get_or_insert_with(HashMap::new);
+
+        if let Some(inner) = cache.get(pattern) {
+            return Ok(Self::from(inner.clone()));
+        }
+
+        let inner = regex::Regex::new(pattern)?;
+        cache.insert(pattern.to_owned(), inner.clone());
+        Ok(Self::from(inner))
+    }
+
+    /// Test if the regular expression matches anywhere in `text`.
+    fn is_match(&self, text: &str) -> bool {
+        self.inner.is_match(text)
+    }
+
+    /// The leftmost match in `text`.
+    fn find(&self, text: &str) -> Option<String> {
+        Some(self.inner.find(text)?.as_str().to_owned())
+    }
+
+    /// Every non-overlapping match in `text`.
+    fn find_all(&self, text: &str) -> Vec<String> {
+        self.inner
+            .find_iter(text)
+            .map(|m| m.as_str().to_owned())
+            .collect()
+    }
+
+    /// The capture groups of the leftmost match in `text`.
+    fn captures(&self, text: &str) -> Option<Captures> {
+        let captures = self.inner.captures(text)?;
+
+        let groups = captures
+            .iter()
+            .map(|m| m.map(|m| m.as_str().to_owned()))
+            .collect();
+
+        let names = self
+            .inner
+            .capture_names()
+            .enumerate()
+            .filter_map(|(index, name)| Some((name?.to_owned(), index)))
+            .collect();
+
+        Some(Captures { groups, names })
+    }
+
+    /// Replace the leftmost match with `replacement`, which can refer to
+    /// capture groups like `$1` or `$name`.
+    fn replace(&self, text: &str, replacement: &str) -> String {
+        self.inner.replace(text, replacement).into_owned()
+    }
+
+    /// Replace every non-overlapping match with `replacement`.
+    fn replace_all(&self, text: &str, replacement: &str) -> String {
+        self.inner.replace_all(text, replacement).into_owned()
+    }
+
+    /// Split `text` by the matches of the regular expression.
+    fn split(&self, text: &str) -> Vec<String> {
+        self.inner.split(text).map(str::to_owned).collect()
+    }
+
+    /// The pattern the regular expression was compiled from.
+    fn as_str(&self) -> String {
+        self.inner.as_str().to_owned()
+    }
+
+    fn string_display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+}
+
+/// The capture groups of a match.
+#[derive(Debug, Any)]
+pub struct Captures {
+    groups: Vec<Option<String>>,
+    names: HashMap<String, usize>,
+}
+
+impl Captures {
+    /// The group at the given index, where `0` is the whole match.
+    fn get(&self, index: usize) -> Option<String> {
+        self.groups.get(index)?.clone()
+    }
+
+    /// The group with the given name.
+    fn name(&self, name: &str) -> Option<String> {
+        self.get(*self.names.get(name)?)
+    }
+
+    /// The number of groups, including the whole match.
+    fn len(&self) -> usize {
+        self.groups.len()
+    }
+}
+
+/// Implementation for the `regex!` macro, which checks that a literal pattern
+/// is valid when the script is compiled and expands into a cached compilation
+/// of it.
+pub(crate) fn regex_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> rune::Result<TokenStream> {
+    let mut parser = Parser::from_token_stream(stream, ctx.stream_span());
+    let pattern = parser.parse::<ast::LitStr>()?;
+    parser.eof()?;
+
+    if let Err(error) = regex::Regex::new(&ctx.resolve(pattern)?) {
+        return Err(SpannedError::msg(pattern.span(), error).into());
+    }
+
+    Ok(quote!(::regex::Regex::cached(#pattern).unwrap()).into_token_stream(ctx))
+}
@@ -40,10 +40,12 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     module.function(["from_bytes"], from_bytes)?;
     module.function(["from_string"], from_string)?;
     module.function(["to_string"], to_string)?;
+    module.function(["to_string_pretty"], to_string_pretty)?;
     module.function(["to_bytes"], to_bytes)?;
     Ok(module)
 }
 
+/// Get value from toml bytes.
 fn from_bytes(bytes: &[u8]) -> rune::Result<Value> {
     Ok(toml::from_slice(bytes)?)
 }
@@ -58,6 +60,11 @@ fn to_string(value: Value) -> rune::Result<String> {
     Ok(toml::to_string(&value)?)
 }
 
+/// Convert any value to a pretty-printed toml string.
+fn to_string_pretty(value: Value) -> rune::Result<String> {
+    Ok(toml::to_string_pretty(&value)?)
+}
+
 /// Convert any value to toml bytes.
 fn to_bytes(value: Value) -> rune::Result<Bytes> {
     let bytes = toml::to_vec(&value)?;
@@ -0,0 +1,87 @@
+//! The native `unicode` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! Splits strings into extended grapheme clusters and words according to
+//! [Unicode Standard Annex #29], so that text which isn't ASCII can be
+//! counted, truncated and reversed the way a reader perceives it.
+//!
+//! [Unicode Standard Annex #29]: https://www.unicode.org/reports/tr29/
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["unicode"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::unicode::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! fn main() {
+//!     let text = "cafe\u{301} 👩‍🔬!";
+//!
+//!     assert_eq!(unicode::graphemes(text).count(), 7);
+//!     assert_eq!(unicode::words(text).collect::<Vec>(), ["café"]);
+//!
+//!     let reversed = String::new();
+//!
+//!     for g in unicode::graphemes(text).rev() {
+//!         reversed += g;
+//!     }
+//! }
+//! ```
+
+use rune::runtime::Iterator;
+use rune::{ContextError, Module};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Construct the `unicode` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("unicode");
+    module.function(["graphemes"], graphemes)?;
+    module.function(["grapheme_indices"], grapheme_indices)?;
+    module.function(["words"], words)?;
+    module.function(["word_bounds"], word_bounds)?;
+    Ok(module)
+}
+
+/// The extended grapheme clusters of a string.
+fn graphemes(s: &str) -> Iterator {
+    let iter = s.graphemes(true).map(str::to_owned).collect::<Vec<_>>();
+    Iterator::from_double_ended("unicode::Graphemes", iter.into_iter())
+}
+
+/// The extended grapheme clusters of a string along with their byte offsets.
+fn grapheme_indices(s: &str) -> Iterator {
+    let iter = s
+        .grapheme_indices(true)
+        .map(|(index, g)| (index, g.to_owned()))
+        .collect::<Vec<_>>();
+
+    Iterator::from_double_ended("unicode::GraphemeIndices", iter.into_iter())
+}
+
+/// The words of a string, skipping over whitespace and punctuation.
+fn words(s: &str) -> Iterator {
+    let iter = s.unicode_words().map(str::to_owned).collect::<Vec<_>>();
+    Iterator::from_double_ended("unicode::Words", iter.into_iter())
+}
+
+/// Split a string on word boundaries, such that concatenating the parts
+/// produces the original string.
+fn word_bounds(s: &str) -> Iterator {
+    let iter = s.split_word_bounds().map(str::to_owned).collect::<Vec<_>>();
+    Iterator::from_double_ended("unicode::WordBounds", iter.into_iter())
+}
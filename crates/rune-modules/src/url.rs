@@ -0,0 +1,210 @@
+//! The native `url` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["url"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::url::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use url::Url;
+//!
+//! fn main() {
+//!     let base = Url::parse("https://example.com/api/v1/")?;
+//!     let url = base.join("users?page=2")?;
+//!     url.append_query_pair("sort", "name");
+//!
+//!     dbg(url.host(), url.path(), url.query_map()["page"]);
+//!     println!("{}", url);
+//! }
+//! ```
+
+use rune::runtime::{Object, Protocol, Value};
+use rune::{Any, ContextError, Module};
+use std::fmt;
+use std::fmt::Write;
+
+/// Construct the `url` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("url");
+
+    module.ty::<Url>()?;
+    module.function(["Url", "parse"], Url::parse)?;
+    module.inst_fn("clone", Url::clone)?;
+    module.inst_fn("join", Url::join)?;
+    module.inst_fn("make_relative", Url::make_relative)?;
+    module.inst_fn("scheme", Url::scheme)?;
+    module.inst_fn("username", Url::username)?;
+    module.inst_fn("password", Url::password)?;
+    module.inst_fn("host", Url::host)?;
+    module.inst_fn("port", Url::port)?;
+    module.inst_fn("port_or_known_default", Url::port_or_known_default)?;
+    module.inst_fn("path", Url::path)?;
+    module.inst_fn("path_segments", Url::path_segments)?;
+    module.inst_fn("query", Url::query)?;
+    module.inst_fn("query_pairs", Url::query_pairs)?;
+    module.inst_fn("query_map", Url::query_map)?;
+    module.inst_fn("fragment", Url::fragment)?;
+    module.inst_fn("set_path", Url::set_path)?;
+    module.inst_fn("set_query", Url::set_query)?;
+    module.inst_fn("append_query_pair", Url::append_query_pair)?;
+    module.inst_fn("set_fragment", Url::set_fragment)?;
+    module.inst_fn("to_string", Url::as_str)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Url::string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Url::string_debug)?;
+    Ok(module)
+}
+
+/// A parsed URL.
+#[derive(Debug, Any, Clone, PartialEq, Eq, Hash)]
+#[rune(eq, hash, clone)]
+pub struct Url {
+    inner: url::Url,
+}
+
+impl From<url::Url> for Url {
+    fn from(inner: url::Url) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<Url> for url::Url {
+    fn from(value: Url) -> Self {
+        value.inner
+    }
+}
+
+impl Url {
+    /// Parse an absolute URL.
+    fn parse(input: &str) -> rune::Result<Self> {
+        Ok(Self::from(url::Url::parse(input)?))
+    }
+
+    /// Resolve `input` relative to this URL, the way a browser resolves a
+    /// link.
+    fn join(&self, input: &str) -> rune::Result<Self> {
+        Ok(Self::from(self.inner.join(input)?))
+    }
+
+    /// A relative URL which resolves to `url` when joined with this one, if
+    /// there is one.
+    fn make_relative(&self, url: &Url) -> Option<String> {
+        self.inner.make_relative(&url.inner)
+    }
+
+    fn scheme(&self) -> String {
+        self.inner.scheme().to_owned()
+    }
+
+    /// The username, which is empty if there is none.
+    fn username(&self) -> String {
+        self.inner.username().to_owned()
+    }
+
+    fn password(&self) -> Option<String> {
+        Some(self.inner.password()?.to_owned())
+    }
+
+    /// The host as a domain or IP address.
+    fn host(&self) -> Option<String> {
+        Some(self.inner.host_str()?.to_owned())
+    }
+
+    /// The port if one was given explicitly.
+    fn port(&self) -> Option<u16> {
+        self.inner.port()
+    }
+
+    /// The port if one was given explicitly, otherwise the default port of
+    /// the scheme, like `443` for `https`.
+    fn port_or_known_default(&self) -> Option<u16> {
+        self.inner.port_or_known_default()
+    }
+
+    fn path(&self) -> String {
+        self.inner.path().to_owned()
+    }
+
+    /// The `/`-separated segments of the path, or `None` if the URL can't be
+    /// a base, like `mailto:` URLs.
+    fn path_segments(&self) -> Option<Vec<String>> {
+        Some(self.inner.path_segments()?.map(str::to_owned).collect())
+    }
+
+    /// The query string, without the leading `?`.
+    fn query(&self) -> Option<String> {
+        Some(self.inner.query()?.to_owned())
+    }
+
+    /// The decoded key-value pairs of the query string, in order.
+    fn query_pairs(&self) -> Vec<(String, String)> {
+        self.inner
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+
+    /// The decoded key-value pairs of the query string as an object. If a key
+    /// is repeated its last value is used.
+    fn query_map(&self) -> Object {
+        let mut object = Object::new();
+
+        for (k, v) in self.inner.query_pairs() {
+            object.insert(k.into_owned(), Value::from(v.into_owned()));
+        }
+
+        object
+    }
+
+    fn fragment(&self) -> Option<String> {
+        Some(self.inner.fragment()?.to_owned())
+    }
+
+    fn set_path(&mut self, path: &str) {
+        self.inner.set_path(path);
+    }
+
+    /// Replace the query string, or remove it if `query` is `None`.
+    fn set_query(&mut self, query: Option<String>) {
+        self.inner.set_query(query.as_deref());
+    }
+
+    /// Append an encoded key-value pair to the query string.
+    fn append_query_pair(&mut self, key: &str, value: &str) {
+        self.inner.query_pairs_mut().append_pair(key, value);
+    }
+
+    /// Replace the fragment, or remove it if `fragment` is `None`.
+    fn set_fragment(&mut self, fragment: Option<String>) {
+        self.inner.set_fragment(fragment.as_deref());
+    }
+
+    fn as_str(&self) -> String {
+        self.inner.as_str().to_owned()
+    }
+
+    fn string_display(&self, buf: &mut String) -> fmt::Result {
+        buf.push_str(self.inner.as_str());
+        Ok(())
+    }
+
+    fn string_debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "Url({:?})", self.inner.as_str())
+    }
+}
@@ -0,0 +1,145 @@
+//! The native `uuid` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["uuid"] }
+//! ```
+//!
+//! Random UUIDs are generated through [rune::runtime::host::random], so they
+//! are reproducible if a deterministic host is installed.
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::uuid::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use uuid::Uuid;
+//!
+//! fn main() {
+//!     let id = Uuid::new_v4();
+//!     let parsed = Uuid::parse(id.to_string())?;
+//!     assert_eq!(id, parsed);
+//!
+//!     let seen = #{};
+//!     seen[id.simple()] = true;
+//! }
+//! ```
+
+use rune::runtime::{host, Bytes, Protocol};
+use rune::{Any, ContextError, Module};
+use std::fmt;
+use std::fmt::Write;
+
+/// Construct the `uuid` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("uuid");
+
+    module.ty::<Uuid>()?;
+    module.function(["Uuid", "new_v4"], Uuid::new_v4)?;
+    module.function(["Uuid", "nil"], Uuid::nil)?;
+    module.function(["Uuid", "parse"], Uuid::parse)?;
+    module.function(["Uuid", "from_bytes"], Uuid::from_bytes)?;
+    module.inst_fn("to_string", Uuid::hyphenated)?;
+    module.inst_fn("simple", Uuid::simple)?;
+    module.inst_fn("urn", Uuid::urn)?;
+    module.inst_fn("as_bytes", Uuid::as_bytes)?;
+    module.inst_fn("version", Uuid::version)?;
+    module.inst_fn("is_nil", Uuid::is_nil)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Uuid::string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Uuid::string_debug)?;
+    Ok(module)
+}
+
+/// A universally unique identifier.
+#[derive(Debug, Any, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rune(eq, hash, partial_cmp, cmp)]
+pub struct Uuid {
+    inner: uuid::Uuid,
+}
+
+impl From<uuid::Uuid> for Uuid {
+    fn from(inner: uuid::Uuid) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<Uuid> for uuid::Uuid {
+    fn from(value: Uuid) -> Self {
+        value.inner
+    }
+}
+
+impl Uuid {
+    /// Generate a random version 4 UUID.
+    fn new_v4() -> Self {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&host::random().to_le_bytes());
+        bytes[8..].copy_from_slice(&host::random().to_le_bytes());
+        Self::from(uuid::Builder::from_random_bytes(bytes).into_uuid())
+    }
+
+    /// The UUID with every bit set to zero.
+    fn nil() -> Self {
+        Self::from(uuid::Uuid::nil())
+    }
+
+    /// Parse a UUID in its hyphenated, simple, braced or URN form.
+    fn parse(s: &str) -> rune::Result<Self> {
+        Ok(Self::from(uuid::Uuid::parse_str(s)?))
+    }
+
+    /// Construct a UUID from exactly 16 bytes.
+    fn from_bytes(bytes: &[u8]) -> rune::Result<Self> {
+        Ok(Self::from(uuid::Uuid::from_slice(bytes)?))
+    }
+
+    /// Format in the hyphenated form, like
+    /// `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    fn hyphenated(&self) -> String {
+        self.inner.hyphenated().to_string()
+    }
+
+    /// Format without hyphens, like `67e5504410b1426f9247bb680e5fe0c8`.
+    fn simple(&self) -> String {
+        self.inner.simple().to_string()
+    }
+
+    /// Format as a URN, like `urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    fn urn(&self) -> String {
+        self.inner.urn().to_string()
+    }
+
+    fn as_bytes(&self) -> Bytes {
+        Bytes::from_vec(self.inner.as_bytes().to_vec())
+    }
+
+    /// The version number of the UUID, like `4` for random UUIDs.
+    fn version(&self) -> usize {
+        self.inner.get_version_num()
+    }
+
+    fn is_nil(&self) -> bool {
+        self.inner.is_nil()
+    }
+
+    fn string_display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+
+    fn string_debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
@@ -0,0 +1,67 @@
+//! The native `yaml` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["yaml"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::yaml::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use yaml;
+//!
+//! fn main() {
+//!     let data = yaml::from_string("hello:\n  world: 42")?;
+//!     dbg(data);
+//! }
+//! ```
+
+use rune::runtime::{Bytes, Value};
+use rune::{ContextError, Module};
+
+/// Construct the `yaml` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("yaml");
+    module.function(["from_bytes"], from_bytes)?;
+    module.function(["from_string"], from_string)?;
+    module.function(["to_string"], to_string)?;
+    module.function(["to_bytes"], to_bytes)?;
+    Ok(module)
+}
+
+/// Get value from yaml bytes.
+fn from_bytes(bytes: &[u8]) -> rune::Result<Value> {
+    Ok(serde_yaml::from_slice(bytes)?)
+}
+
+/// Get value from yaml string.
+fn from_string(string: &str) -> rune::Result<Value> {
+    Ok(serde_yaml::from_str(string)?)
+}
+
+/// Convert any value to a yaml string.
+fn to_string(value: Value) -> rune::Result<String> {
+    Ok(serde_yaml::to_string(&value)?)
+}
+
+/// Convert any value to yaml bytes.
+fn to_bytes(value: Value) -> rune::Result<Bytes> {
+    let mut bytes = Vec::new();
+    serde_yaml::to_writer(&mut bytes, &value)?;
+    Ok(Bytes::from_vec(bytes))
+}
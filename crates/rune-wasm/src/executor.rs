@@ -0,0 +1,32 @@
+use futures_channel::oneshot;
+use rune::runtime::{Executor, SpawnFuture, SpawnTask};
+use std::time::Duration;
+use wasm_bindgen_futures::JsFuture;
+
+/// An executor which runs spawned tasks on the browser event loop, since
+/// there are no threads to spawn on `wasm32-unknown-unknown`.
+pub(crate) struct EventLoop;
+
+impl Executor for EventLoop {
+    fn spawn(&self, future: SpawnFuture) {
+        wasm_bindgen_futures::spawn_local(future);
+    }
+
+    fn spawn_blocking(&self, task: SpawnTask) {
+        wasm_bindgen_futures::spawn_local(async move { task() });
+    }
+
+    fn sleep(&self, duration: Duration) -> SpawnFuture {
+        let (sender, receiver) = oneshot::channel();
+        let ms = duration.as_millis().min(i32::MAX as u128) as i32;
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = JsFuture::from(crate::time::sleep(ms)).await;
+            let _ = sender.send(());
+        });
+
+        Box::pin(async move {
+            let _ = receiver.await;
+        })
+    }
+}
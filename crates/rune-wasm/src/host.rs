@@ -0,0 +1,22 @@
+use rune::runtime::Host;
+use std::time::Duration;
+
+/// A host which reads the time and random numbers from the browser, since
+/// `SystemTime` isn't available on `wasm32-unknown-unknown`.
+pub(crate) struct Browser;
+
+impl Host for Browser {
+    fn now(&self) -> Duration {
+        Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+    }
+
+    fn random(&self) -> u64 {
+        let high = (js_sys::Math::random() * u32::MAX as f64) as u64;
+        let low = (js_sys::Math::random() * u32::MAX as f64) as u64;
+        high << 32 | low
+    }
+
+    fn hash_seed(&self) -> u64 {
+        self.random()
+    }
+}
@@ -23,6 +23,36 @@
 //! This is part of the [Rune Language].
 //!
 //! [Rune Language]: https://rune-rs.github.io
+//!
+//! The module exports a single `compile` function, which compiles and runs
+//! the `main` function of the given source and resolves to the outcome:
+//!
+//! ```js
+//! import { compile } from "rune-wasm";
+//!
+//! const result = await compile(`pub fn main() { #{ answer: 42 } }`, {
+//!     budget: 1000000,
+//! });
+//!
+//! if (result.error) {
+//!     for (const d of result.diagnostics) {
+//!         console.log(d.kind, d.start.line, d.start.character, d.message);
+//!     }
+//! } else {
+//!     console.log(result.json.answer);
+//! }
+//! ```
+//!
+//! The outcome has the following fields:
+//! * `error` - the error which stopped compilation or execution, if any.
+//! * `diagnostics` - warnings and errors, with the position of the span they
+//!   refer to.
+//! * `diagnostics_output` - the diagnostics rendered as text.
+//! * `result` - the debug representation of the returned value.
+//! * `json` - the returned value converted to JSON, unless it contains values
+//!   which can't be serialized, like functions.
+//! * `output` - anything the script printed.
+//! * `instructions` - the compiled instructions, if requested.
 
 #![allow(clippy::collapsible_match)]
 #![allow(clippy::single_match)]
@@ -43,6 +73,8 @@ use rune_modules::capture_io::CaptureIo;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+mod executor;
+mod host;
 mod http;
 mod time;
 
@@ -102,6 +134,7 @@ pub struct WasmCompileResult {
     diagnostics_output: Option<String>,
     diagnostics: Vec<WasmDiagnostic>,
     result: Option<String>,
+    json: Option<serde_json::Value>,
     output: Option<String>,
     instructions: Option<String>,
 }
@@ -120,6 +153,7 @@ impl WasmCompileResult {
             diagnostics_output,
             diagnostics,
             result: Some(format!("{:?}", output)),
+            json: serde_json::to_value(&output).ok(),
             output: io.drain_utf8().ok(),
             instructions,
         }
@@ -141,6 +175,7 @@ impl WasmCompileResult {
             diagnostics_output,
             diagnostics,
             result: None,
+            json: None,
             output: io.drain_utf8().ok(),
             instructions,
         }
@@ -150,6 +185,8 @@ impl WasmCompileResult {
 /// Setup a wasm-compatible context.
 fn setup_context(experimental: bool, io: &CaptureIo) -> Result<Context, ContextError> {
     let mut context = Context::with_config(false)?;
+    context.set_host(host::Browser);
+    context.set_executor(executor::EventLoop);
 
     context.install(rune_modules::capture_io::module(io)?)?;
     context.install(time::module()?)?;
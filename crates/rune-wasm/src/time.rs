@@ -6,7 +6,7 @@ use wasm_bindgen_futures::JsFuture;
 
 #[wasm_bindgen(module = "/module.js")]
 extern "C" {
-    fn sleep(ms: i32) -> Promise;
+    pub(crate) fn sleep(ms: i32) -> Promise;
 }
 
 /// The wasm 'time' module.
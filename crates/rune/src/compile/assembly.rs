@@ -31,6 +31,9 @@ pub(crate) struct Assembly {
     pub(crate) instructions: Vec<(AssemblyInst, Span)>,
     /// Comments associated with instructions.
     pub(crate) comments: HashMap<usize, Vec<Box<str>>>,
+    /// Named variables declared, by the instruction they are declared at and
+    /// their stack offset.
+    pub(crate) variables: Vec<(usize, usize, Box<str>)>,
     /// The number of labels.
     pub(crate) label_count: usize,
     /// The collection of functions required by this assembly.
@@ -46,6 +49,7 @@ impl Assembly {
             labels_rev: Default::default(),
             instructions: Default::default(),
             comments: Default::default(),
+            variables: Default::default(),
             label_count,
             required_functions: Default::default(),
         }
@@ -116,6 +120,13 @@ impl Assembly {
             .push((AssemblyInst::IterNext { offset, label }, span));
     }
 
+    /// Record that a named variable with the given stack offset is declared at
+    /// the current instruction.
+    pub(crate) fn declare(&mut self, name: &str, offset: usize) {
+        let pos = self.instructions.len();
+        self.variables.push((pos, offset, name.into()));
+    }
+
     /// Push a raw instruction.
     pub(crate) fn push(&mut self, raw: Inst, span: Span) {
         if let Inst::Call { hash, .. } = raw {
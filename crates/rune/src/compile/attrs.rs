@@ -164,6 +164,25 @@ impl Attribute for Bench {
     const PATH: &'static str = "bench";
 }
 
+/// NB: at this point we don't support attributes beyond the empty `#[ignore]`.
+#[derive(Parse)]
+pub(crate) struct Ignore {}
+
+impl Attribute for Ignore {
+    /// Must match the specified name.
+    const PATH: &'static str = "ignore";
+}
+
+/// NB: at this point we don't support attributes beyond the empty
+/// `#[should_panic]`.
+#[derive(Parse)]
+pub(crate) struct ShouldPanic {}
+
+impl Attribute for ShouldPanic {
+    /// Must match the specified name.
+    const PATH: &'static str = "should_panic";
+}
+
 #[derive(Parse)]
 pub(crate) struct Doc {
     /// The `=` token.
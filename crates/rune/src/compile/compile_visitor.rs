@@ -7,15 +7,29 @@ pub trait CompileVisitor {
     /// Called when a meta item is registered.
     fn register_meta(&mut self, _meta: MetaRef<'_>) {}
 
+    /// Called when an item is indexed at the given location. Unlike
+    /// [CompileVisitor::register_meta] this is called for every item, even
+    /// ones which are never used.
+    fn visit_item(&mut self, _location: Location, _item: &Item) {}
+
     /// Mark that we've resolved a specific compile meta at the given location.
     fn visit_meta(&mut self, _location: Location, _meta: MetaRef<'_>) {}
 
+    /// Visit a variable declared by binding a pattern to a name, like
+    /// `let a = 1` or a function argument.
+    fn visit_variable_declaration(&mut self, _source_id: SourceId, _span: Span) {}
+
     /// Visit a variable use.
     fn visit_variable_use(&mut self, _source_id: SourceId, _var_span: Span, _span: Span) {}
 
     /// Visit something that is a module.
+    #[deprecated = "use `CompileVisitor::visit_mod_declaration`, which also provides the source the module is declared in"]
     fn visit_mod(&mut self, _source_id: SourceId, _span: Span) {}
 
+    /// Visit a module declaration at the given location, which caused the
+    /// source with the given id to be loaded.
+    fn visit_mod_declaration(&mut self, _location: Location, _source_id: SourceId) {}
+
     /// Visit anterior `///`-style comments, and interior `//!`-style doc
     /// comments for an item.
     ///
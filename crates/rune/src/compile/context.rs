@@ -1,18 +1,21 @@
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use crate::collections::{hash_map, HashMap, HashSet};
 use crate::compile::module::{
-    AssocFn, AssocKey, AssocKind, Function, InternalEnum, Macro, Module, ModuleFn, Type,
-    TypeSpecification, UnitType, VariantKind,
+    AssocFn, AssocKey, AssocKind, Function, InternalEnum, Macro, Module, ModuleFn, ModuleProtocol,
+    Type, TypeSpecification, UnitType, VariantKind,
 };
 use crate::compile::{
-    ComponentRef, ContextError, ContextMeta, ContextMetaKind, ContextSignature, ContextTypeInfo,
-    Docs, IntoComponent, Item, ItemBuf, Names, PrivStructMeta, PrivTupleMeta, PrivVariantMeta,
+    ComponentRef, ContextDiff, ContextError, ContextMeta, ContextMetaKind, ContextSignature,
+    ContextTypeInfo, Docs, IntoComponent, Item, ItemBuf, Names, PrivStructMeta, PrivTupleMeta,
+    PrivVariantMeta,
 };
+use crate::runtime::capabilities;
+use crate::runtime::output::Sink;
 use crate::runtime::{
-    ConstValue, FunctionHandler, MacroHandler, Protocol, RuntimeContext, StaticType, TypeCheck,
-    TypeInfo, TypeOf, VariantRtti,
+    ConstValue, Executor, FunctionHandler, Host, Logger, MacroHandler, Protocol, RuntimeContext,
+    StaticType, TypeCheck, TypeInfo, TypeOf, VariantRtti,
 };
 use crate::{Hash, InstFnKind};
 
@@ -65,6 +68,8 @@ pub struct Context {
     associated: HashMap<Hash, Vec<ItemBuf>>,
     /// Registered native macro handlers.
     macros: HashMap<Hash, Arc<MacroHandler>>,
+    /// The items of registered macros.
+    macros_info: HashMap<Hash, ItemBuf>,
     /// Registered types.
     types: HashMap<Hash, PrivTypeInfo>,
     /// Reverse lookup for types, which maps the item type hash to the internal
@@ -78,6 +83,18 @@ pub struct Context {
     crates: HashSet<Box<str>>,
     /// Constants visible in this context
     constants: HashMap<Hash, ConstValue>,
+    /// Declared protocols.
+    protocols: HashMap<Hash, ModuleProtocol>,
+    /// The executor which scripts can spawn tasks on.
+    executor: Option<Arc<dyn Executor>>,
+    /// The host which provides nondeterministic inputs.
+    host: Option<Arc<dyn Host>>,
+    /// The logger which receives messages emitted by scripts.
+    logger: Option<Arc<dyn Logger>>,
+    /// Where standard output is redirected to.
+    stdout: Option<Sink>,
+    /// Where standard error is redirected to.
+    stderr: Option<Sink>,
 }
 
 impl Context {
@@ -100,6 +117,11 @@ impl Context {
     /// * `::std::io::dbg`
     /// * `::std::io::print`
     /// * `::std::io::println`
+    /// * `::std::io::eprint`
+    /// * `::std::io::eprintln`
+    ///
+    /// Output can also be redirected through [Context::set_stdout] and
+    /// [Context::set_stderr].
     pub fn with_config(stdio: bool) -> Result<Self, ContextError> {
         let mut this = Self::new();
         // This must go first, because it includes types which are used in other modules.
@@ -110,22 +132,31 @@ impl Context {
         this.install(crate::modules::char::module()?)?;
         this.install(crate::modules::cmp::module()?)?;
         this.install(crate::modules::collections::module()?)?;
+        this.install(crate::modules::env::module()?)?;
         this.install(crate::modules::float::module()?)?;
         this.install(crate::modules::fmt::module()?)?;
         this.install(crate::modules::future::module()?)?;
         this.install(crate::modules::generator::module()?)?;
+        this.install(crate::modules::im::module()?)?;
         this.install(crate::modules::int::module()?)?;
         this.install(crate::modules::io::module(stdio)?)?;
         this.install(crate::modules::iter::module()?)?;
+        this.install(crate::modules::log::module()?)?;
         this.install(crate::modules::macros::module()?)?;
+        this.install(crate::modules::math::module()?)?;
         this.install(crate::modules::mem::module()?)?;
         this.install(crate::modules::object::module()?)?;
         this.install(crate::modules::ops::module()?)?;
         this.install(crate::modules::option::module()?)?;
+        this.install(crate::modules::panic::module()?)?;
         this.install(crate::modules::result::module()?)?;
         this.install(crate::modules::stream::module()?)?;
         this.install(crate::modules::string::module()?)?;
+        this.install(crate::modules::symbol::module()?)?;
+        this.install(crate::modules::sync::module()?)?;
         this.install(crate::modules::test::module()?)?;
+        this.install(crate::modules::thread::module()?)?;
+        this.install(crate::modules::time::module()?)?;
         this.install(crate::modules::vec::module()?)?;
         this.has_default_modules = true;
         Ok(this)
@@ -155,7 +186,73 @@ impl Context {
     /// # Ok(()) }
     /// ```
     pub fn runtime(&self) -> RuntimeContext {
-        RuntimeContext::new(self.functions.clone(), self.constants.clone())
+        RuntimeContext::new(
+            self.functions.clone(),
+            self.constants.clone(),
+            self.protocols
+                .iter()
+                .map(|(hash, declaration)| (*hash, declaration.args))
+                .collect(),
+            self.executor.clone(),
+            self.host.clone(),
+            self.logger.clone(),
+            self.stdout.clone(),
+            self.stderr.clone(),
+        )
+    }
+
+    /// Set the executor which scripts can spawn tasks on through
+    /// `std::future::spawn` and `std::thread::spawn`.
+    ///
+    /// See [Executor] for more information.
+    pub fn set_executor<E>(&mut self, executor: E)
+    where
+        E: Executor + 'static,
+    {
+        self.executor = Some(Arc::new(executor));
+    }
+
+    /// Set the host which provides the current time, random numbers and the
+    /// seed used to hash map keys, making executions reproducible.
+    ///
+    /// See [Host] for more information.
+    pub fn set_host<H>(&mut self, host: H)
+    where
+        H: Host + 'static,
+    {
+        self.host = Some(Arc::new(host));
+    }
+
+    /// Set the logger which receives the messages scripts emit through
+    /// `std::log`.
+    ///
+    /// See [Logger] for more information.
+    pub fn set_logger<L>(&mut self, logger: L)
+    where
+        L: Logger + 'static,
+    {
+        self.logger = Some(Arc::new(logger));
+    }
+
+    /// Redirect what scripts write to standard output, like through `print`,
+    /// `println` and `dbg`, to the given writer.
+    ///
+    /// See [Capture][crate::runtime::output::Capture] for a writer which
+    /// records the output in memory.
+    pub fn set_stdout<W>(&mut self, stdout: W)
+    where
+        W: std::io::Write + Send + 'static,
+    {
+        self.stdout = Some(Arc::new(Mutex::new(stdout)));
+    }
+
+    /// Redirect what scripts write to standard error, like through `eprint`
+    /// and `eprintln`, to the given writer.
+    pub fn set_stderr<W>(&mut self, stderr: W)
+    where
+        W: std::io::Write + Send + 'static,
+    {
+        self.stderr = Some(Arc::new(Mutex::new(stderr)));
     }
 
     /// Install the specified module.
@@ -203,6 +300,10 @@ impl Context {
             self.install_internal_enum(module, internal_enum, Docs::default())?;
         }
 
+        for declaration in module.protocols.values() {
+            self.install_protocol(declaration)?;
+        }
+
         for (key, inst) in &module.associated_functions {
             self.install_associated_function(module, key, inst)?;
         }
@@ -233,6 +334,62 @@ impl Context {
         })
     }
 
+    /// Iterate over all available macros in the [Context].
+    pub fn iter_macros(&self) -> impl Iterator<Item = (Hash, &Item)> {
+        self.macros_info
+            .iter()
+            .map(|(hash, item)| (*hash, item.as_ref()))
+    }
+
+    /// Iterate over all available constants in the [Context].
+    pub fn iter_constants(&self) -> impl Iterator<Item = (&Item, &ConstValue)> {
+        self.meta
+            .iter()
+            .filter_map(|(item, meta)| match &meta.kind {
+                ContextMetaKind::Const { const_value } => Some((item.as_ref(), const_value)),
+                _ => None,
+            })
+    }
+
+    /// Look up the documentation of the given item.
+    pub fn lookup_docs(&self, item: &Item) -> Option<&Docs> {
+        Some(&self.meta.get(item)?.docs)
+    }
+
+    /// Compare everything installed in this context with `other`, treating
+    /// this context as the old one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Module};
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut old = Module::with_item(["api"]);
+    /// old.function(["add"], |a: i64, b: i64| a + b)?;
+    /// old.function(["sub"], |a: i64, b: i64| a - b)?;
+    ///
+    /// let mut new = Module::with_item(["api"]);
+    /// new.function(["add"], |a: i64, b: i64, c: i64| a + b + c)?;
+    /// new.function(["mul"], |a: i64, b: i64| a * b)?;
+    ///
+    /// let mut a = Context::new();
+    /// a.install(&old)?;
+    ///
+    /// let mut b = Context::new();
+    /// b.install(&new)?;
+    ///
+    /// let diff = a.diff(&b);
+    /// assert_eq!(diff.added, ["fn api::mul(#0, #1)"]);
+    /// assert_eq!(diff.removed, ["fn api::sub(#0, #1)"]);
+    /// assert_eq!(diff.changed.len(), 1);
+    /// assert!(a.diff(&a).is_empty());
+    /// # Ok(()) }
+    /// ```
+    pub fn diff(&self, other: &Context) -> ContextDiff {
+        ContextDiff::new(self, other)
+    }
+
     /// Iterate over known child components of the given name.
     pub(crate) fn iter_components<'a, I: 'a>(
         &'a self,
@@ -251,7 +408,7 @@ impl Context {
     }
 
     /// Look up signature of function.
-    pub(crate) fn lookup_signature(&self, hash: Hash) -> Option<&ContextSignature> {
+    pub fn lookup_signature(&self, hash: Hash) -> Option<&ContextSignature> {
         self.functions_info.get(&hash)
     }
 
@@ -369,7 +526,7 @@ impl Context {
                                 PrivVariantMeta::Struct(PrivStructMeta {
                                     fields: st.fields.clone(),
                                 }),
-                                None,
+                                Some(1),
                             ),
                             VariantKind::Unit => (PrivVariantMeta::Unit, Some(0)),
                         };
@@ -485,7 +642,7 @@ impl Context {
             });
         }
 
-        self.functions.insert(hash, f.handler.clone());
+        self.functions.insert(hash, gated(module, hash, &f.handler));
 
         self.install_meta(ContextMeta::new(
             module,
@@ -515,6 +672,7 @@ impl Context {
         let hash = Hash::type_hash(&item);
 
         self.macros.insert(hash, m.handler.clone());
+        self.macros_info.insert(hash, item);
         Ok(())
     }
 
@@ -546,6 +704,39 @@ impl Context {
         Ok(())
     }
 
+    /// Install a protocol declaration and check that it's compatible with
+    /// existing declarations and implementations.
+    fn install_protocol(&mut self, declaration: &ModuleProtocol) -> Result<(), ContextError> {
+        match self.protocols.entry(declaration.protocol.hash) {
+            hash_map::Entry::Occupied(e) => {
+                if e.get() != declaration {
+                    return Err(ContextError::ConflictingProtocol {
+                        name: declaration.protocol.name,
+                    });
+                }
+
+                return Ok(());
+            }
+            hash_map::Entry::Vacant(e) => {
+                e.insert(*declaration);
+            }
+        }
+
+        for signature in self.functions_info.values() {
+            if let ContextSignature::Instance {
+                name: InstFnKind::Protocol(protocol),
+                args,
+                self_type_info,
+                ..
+            } = signature
+            {
+                check_protocol_args(declaration, *protocol, *args, self_type_info)?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn install_associated_function(
         &mut self,
         module: &Module,
@@ -570,6 +761,12 @@ impl Context {
             .hash(key.type_hash, key.hash)
             .with_parameters(key.parameters);
 
+        if let (InstFnKind::Protocol(protocol), AssocKind::Instance) = (&assoc.name, key.kind) {
+            if let Some(declaration) = self.protocols.get(&protocol.hash) {
+                check_protocol_args(declaration, *protocol, assoc.args, &info.type_info)?;
+            }
+        }
+
         let signature = ContextSignature::Instance {
             type_hash: key.type_hash,
             item: info.item.clone(),
@@ -585,7 +782,8 @@ impl Context {
             });
         }
 
-        self.functions.insert(hash, assoc.handler.clone());
+        self.functions
+            .insert(hash, gated(module, hash, &assoc.handler));
 
         // If the associated function is a named instance function - register it
         // under the name of the item it corresponds to unless it's a field
@@ -624,7 +822,8 @@ impl Context {
                 });
             }
 
-            self.functions.insert(hash, assoc.handler.clone());
+            self.functions
+                .insert(hash, gated(module, hash, &assoc.handler));
 
             if !self.meta.contains_key(&item) {
                 self.install_meta(ContextMeta::new(
@@ -828,3 +1027,37 @@ impl fmt::Debug for Context {
 
 #[cfg(test)]
 static_assertions::assert_impl_all!(Context: Send, Sync);
+
+/// Check that a protocol function takes the number of arguments expected by
+/// the protocol declaration.
+fn check_protocol_args(
+    declaration: &ModuleProtocol,
+    protocol: Protocol,
+    args: Option<usize>,
+    type_info: &TypeInfo,
+) -> Result<(), ContextError> {
+    if protocol != declaration.protocol {
+        return Ok(());
+    }
+
+    // NB: the arguments of a protocol function includes the value it's called
+    // on.
+    match args {
+        Some(args) if args != declaration.args + 1 => Err(ContextError::ProtocolArgumentMismatch {
+            type_info: type_info.clone(),
+            name: protocol.name,
+            expected: declaration.args,
+            actual: args.saturating_sub(1),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Get the handler to install for a native function of the given module, which
+/// checks that the capability of the module has been granted if it has one.
+fn gated(module: &Module, hash: Hash, handler: &Arc<FunctionHandler>) -> Arc<FunctionHandler> {
+    match module.capability {
+        Some(capability) => capabilities::gate(capability, hash, handler),
+        None => handler.clone(),
+    }
+}
@@ -0,0 +1,111 @@
+//! Comparison of everything installed in two contexts.
+
+use crate::collections::BTreeMap;
+use crate::compile::Context;
+use crate::Hash;
+use std::fmt;
+
+/// The difference between the public API of two contexts.
+///
+/// Constructed through [Context::diff].
+///
+/// Every function, type, macro and constant installed in a context is
+/// described by a single line, like `fn std::string::String::len(self:
+/// String)` or `type std::string::String`. An item is considered changed if it
+/// is present in both contexts but its description differs, like a function
+/// whose number of arguments has changed.
+///
+/// The [Display][fmt::Display] implementation prints one line per difference,
+/// which makes the diff suitable for snapshot tests guarding against
+/// accidental API changes.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ContextDiff {
+    /// Descriptions of items which only exist in the new context.
+    pub added: Vec<String>,
+    /// Descriptions of items which only exist in the old context.
+    pub removed: Vec<String>,
+    /// Descriptions of items which exist in both contexts, but which differ,
+    /// as a pair of the old and the new description.
+    pub changed: Vec<(String, String)>,
+}
+
+impl ContextDiff {
+    pub(crate) fn new(old: &Context, new: &Context) -> Self {
+        let mut diff = Self::default();
+        let old = describe(old);
+        let mut new = describe(new);
+
+        for (key, old) in old {
+            match new.remove(&key) {
+                Some(new) if new != old => diff.changed.push((old, new)),
+                Some(..) => {}
+                None => diff.removed.push(old),
+            }
+        }
+
+        diff.added.extend(new.into_values());
+
+        diff.added.sort();
+        diff.removed.sort();
+        diff.changed.sort();
+        diff
+    }
+
+    /// Test if the two compared contexts have an identical API.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for ContextDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for removed in &self.removed {
+            writeln!(f, "- {}", removed)?;
+        }
+
+        for (old, new) in &self.changed {
+            writeln!(f, "- {}", old)?;
+            writeln!(f, "+ {}", new)?;
+        }
+
+        for added in &self.added {
+            writeln!(f, "+ {}", added)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The kind of an item described in a context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    Function,
+    Type,
+    Macro,
+    Const,
+}
+
+/// Describe every item installed in the given context.
+fn describe(context: &Context) -> BTreeMap<(Kind, Hash), String> {
+    let mut out = BTreeMap::new();
+
+    for (hash, signature) in context.iter_functions() {
+        out.insert((Kind::Function, hash), format!("fn {}", signature));
+    }
+
+    for (hash, ty) in context.iter_types() {
+        out.insert((Kind::Type, hash), format!("type {}", ty));
+    }
+
+    for (hash, item) in context.iter_macros() {
+        out.insert((Kind::Macro, hash), format!("macro {}!", item));
+    }
+
+    for (item, value) in context.iter_constants() {
+        let hash = Hash::type_hash(item);
+        out.insert((Kind::Const, hash), format!("const {} = {:?}", item, value));
+    }
+
+    out
+}
@@ -31,6 +31,15 @@ pub enum ContextError {
     ConflictingInstanceFunction { type_info: TypeInfo, name: Box<str> },
     #[error("protocol function `{name}` for type `{type_info}` already exists")]
     ConflictingProtocolFunction { type_info: TypeInfo, name: Box<str> },
+    #[error("protocol `{name}` is already declared with a different signature")]
+    ConflictingProtocol { name: &'static str },
+    #[error("protocol function `{name}` for type `{type_info}` takes {actual} arguments, but the protocol expects {expected}")]
+    ProtocolArgumentMismatch {
+        type_info: TypeInfo,
+        name: &'static str,
+        expected: usize,
+        actual: usize,
+    },
     #[error("protocol function with hash `{hash}` for type `{type_info}` already exists")]
     ConflictingInstanceFunctionHash { type_info: TypeInfo, hash: Hash },
     #[error("module `{item}` with hash `{hash}` already exists")]
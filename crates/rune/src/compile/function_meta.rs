@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::compile::module::{
-    AssocKey, AssocKind, AssocType, AsyncFunction, AsyncInstFn, Function, InstFn,
+    AssocKey, AssocKind, AssocType, AsyncFunction, AsyncInstFn, Function, InstFn, StreamFunction,
 };
 use crate::compile::{IntoComponent, ItemBuf, Named};
 use crate::runtime::FunctionHandler;
@@ -54,6 +54,20 @@ impl FunctionData {
             args: Some(Func::args()),
         }
     }
+
+    #[inline]
+    pub(crate) fn new_stream<Func, Args, N>(name: N, f: Func) -> Self
+    where
+        Func: StreamFunction<Args>,
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        Self {
+            name: ItemBuf::with_item(name),
+            handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
+            args: Some(Func::args()),
+        }
+    }
 }
 
 /// Runtime data for an associated function.
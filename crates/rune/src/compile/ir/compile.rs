@@ -421,6 +421,21 @@ fn path(hir: &hir::Path<'_>, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError>
         return Ok(ir::Ir::new(span, <Box<str>>::from(name)));
     }
 
+    if hir.global.is_none() {
+        let mut path = Vec::with_capacity(hir.rest.len() + 1);
+
+        for segment in std::iter::once(hir.first).chain(hir.rest) {
+            match segment.kind {
+                hir::PathSegmentKind::Ident(ident) => {
+                    path.push(<Box<str>>::from(c.resolve(ident)?));
+                }
+                _ => return Err(IrError::msg(segment, "not supported yet")),
+            }
+        }
+
+        return Ok(ir::Ir::new(span, ir::IrKind::Path(path.into())));
+    }
+
     Err(IrError::msg(span, "not supported yet"))
 }
 
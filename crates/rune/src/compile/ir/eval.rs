@@ -416,6 +416,7 @@ pub(crate) fn eval_ir(
         ir::IrKind::Assign(ir) => eval_ir_assign(ir, interp, used),
         ir::IrKind::Template(ir) => eval_ir_template(ir, interp, used),
         ir::IrKind::Name(name) => Ok(interp.resolve_var(ir.span(), name.as_ref(), used)?),
+        ir::IrKind::Path(path) => Ok(interp.resolve_path(ir.span(), path, used)?),
         ir::IrKind::Target(target) => Ok(interp.scopes.get_target(target)?),
         ir::IrKind::Value(value) => Ok(value.clone()),
         ir::IrKind::Branches(ir) => eval_ir_branches(ir, interp, used),
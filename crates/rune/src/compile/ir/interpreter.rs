@@ -1,6 +1,6 @@
 use crate::ast::{Span, Spanned};
 use crate::compile::{
-    ir, IrError, IrErrorKind, IrEvalOutcome, IrValue, ItemId, ModId, PrivMetaKind,
+    ir, IrError, IrErrorKind, IrEvalOutcome, IrValue, ItemBuf, ItemId, ModId, PrivMetaKind,
 };
 use crate::query::{Query, Used};
 use crate::runtime::{ConstValue, Object, Tuple};
@@ -96,26 +96,8 @@ impl IrInterpreter<'_> {
         let mut base = self.q.pool.item(self.item).to_owned();
 
         loop {
-            let item = self.q.pool.alloc_item(base.extended(name));
-
-            if let Some(const_value) = self.q.consts.get(item) {
-                return Ok(IrValue::from_const(const_value));
-            }
-
-            if let Some(meta) = self.q.query_meta(spanned, item, used)? {
-                match &meta.kind {
-                    PrivMetaKind::Const { const_value, .. } => {
-                        return Ok(IrValue::from_const(const_value));
-                    }
-                    _ => {
-                        return Err(IrError::new(
-                            spanned,
-                            IrErrorKind::UnsupportedMeta {
-                                meta: meta.info(self.q.pool),
-                            },
-                        ));
-                    }
-                }
+            if let Some(value) = self.lookup_const(spanned, base.extended(name), used)? {
+                return Ok(value);
             }
 
             if base.is_empty() {
@@ -138,6 +120,90 @@ impl IrInterpreter<'_> {
         }
     }
 
+    /// Resolve the constant value at the given path, like `math::TAU`.
+    ///
+    /// The path is resolved relative to the item being evaluated and its
+    /// parents, falling back to treating the first component as the name of a
+    /// crate.
+    pub(crate) fn resolve_path(
+        &mut self,
+        spanned: Span,
+        path: &[Box<str>],
+        used: Used,
+    ) -> Result<IrValue, IrError> {
+        let mut base = self.q.pool.item(self.item).to_owned();
+
+        loop {
+            let item = base.join(path.iter().map(AsRef::as_ref));
+
+            if let Some(value) = self.lookup_const(spanned, item, used)? {
+                return Ok(value);
+            }
+
+            if base.is_empty() {
+                break;
+            }
+
+            base.pop();
+        }
+
+        if let Some((first, rest)) = path.split_first() {
+            let mut item = ItemBuf::with_crate(first);
+
+            for c in rest {
+                item.push(c.as_ref());
+            }
+
+            if let Some(value) = self.lookup_const(spanned, item, used)? {
+                return Ok(value);
+            }
+        }
+
+        let name = path.join("::");
+
+        Err(IrError::new(
+            spanned,
+            IrErrorKind::MissingConst { name: name.into() },
+        ))
+    }
+
+    /// Look up the constant value of the given item, among the constants
+    /// declared in the unit and the context, following imports.
+    fn lookup_const(
+        &mut self,
+        spanned: Span,
+        item: ItemBuf,
+        used: Used,
+    ) -> Result<Option<IrValue>, IrError> {
+        let mut id = self.q.pool.alloc_item(&item);
+
+        if let Some(const_value) = self.q.consts.get(id) {
+            return Ok(Some(IrValue::from_const(const_value)));
+        }
+
+        if let Some(target) = self.q.import(spanned, self.module, id, used)? {
+            id = target;
+        }
+
+        let meta = match self.q.query_meta(spanned, id, used)? {
+            Some(meta) => meta,
+            None => match self.q.context.lookup_meta(self.q.pool.item(id)) {
+                Some(meta) => self.q.insert_context_meta(spanned, meta)?,
+                None => return Ok(None),
+            },
+        };
+
+        match &meta.kind {
+            PrivMetaKind::Const { const_value, .. } => Ok(Some(IrValue::from_const(const_value))),
+            _ => Err(IrError::new(
+                spanned,
+                IrErrorKind::UnsupportedMeta {
+                    meta: meta.info(self.q.pool),
+                },
+            )),
+        }
+    }
+
     pub(crate) fn call_const_fn<S>(
         &mut self,
         spanned: S,
@@ -146,6 +146,9 @@ decl_kind! {
         Template(IrTemplate),
         /// A named value.
         Name(Box<str>),
+        /// A path to a named value, like a constant declared in another
+        /// module.
+        Path(Box<[Box<str>]>),
         /// A local name. Could either be a local variable or a reference to
         /// something else, like another const declaration.
         Target(IrTarget),
@@ -68,6 +68,10 @@ pub enum MetaKind {
         is_test: bool,
         /// If the function is a benchmark.
         is_bench: bool,
+        /// If the test or benchmark is ignored unless explicitly requested.
+        is_ignored: bool,
+        /// If the test is expected to panic.
+        should_panic: bool,
     },
     /// Item describes a closure.
     Closure,
@@ -296,6 +300,8 @@ impl ContextMetaKind {
                 args: *args,
                 is_bench: false,
                 is_test: false,
+                is_ignored: false,
+                should_panic: false,
             },
             ContextMetaKind::Const { .. } => MetaKind::Const,
         }
@@ -398,6 +404,10 @@ pub(crate) enum PrivMetaKind {
         is_test: bool,
         /// Whether this function has a `#[bench]` annotation.
         is_bench: bool,
+        /// Whether this function has an `#[ignore]` annotation.
+        is_ignored: bool,
+        /// Whether this function has a `#[should_panic]` annotation.
+        should_panic: bool,
         /// Indicates that the function is an instance function.
         instance_function: bool,
     },
@@ -468,11 +478,15 @@ impl PrivMetaKind {
                 args,
                 is_bench,
                 is_test,
+                is_ignored,
+                should_panic,
                 ..
             } => MetaKind::Function {
                 args: *args,
                 is_bench: *is_bench,
                 is_test: *is_test,
+                is_ignored: *is_ignored,
+                should_panic: *should_panic,
             },
             PrivMetaKind::Closure { .. } => MetaKind::Closure,
             PrivMetaKind::AsyncBlock { .. } => MetaKind::AsyncBlock,
@@ -28,6 +28,9 @@ pub(crate) use self::compile_visitor::NoopCompileVisitor;
 pub(crate) mod context;
 pub use self::context::Context;
 
+mod context_diff;
+pub use self::context_diff::ContextDiff;
+
 pub(crate) mod context_error;
 pub use self::context_error::ContextError;
 
@@ -72,7 +75,8 @@ pub use self::function_meta::{FunctionMeta, FunctionMetaData, FunctionMetaKind};
 
 mod module;
 pub use self::module::{
-    AssocType, AsyncFunction, AsyncInstFn, Function, InstFn, InstallWith, Module, Variant,
+    AssocType, AsyncFunction, AsyncInstFn, Function, InstFn, InstallWith, Module, StreamFunction,
+    Variant,
 };
 
 mod pool;
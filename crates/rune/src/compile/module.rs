@@ -10,10 +10,12 @@ use crate::compile::{
 };
 use crate::macros::{MacroContext, TokenStream};
 use crate::runtime::{
-    ConstValue, FromValue, FunctionHandler, Future, GeneratorState, MacroHandler, Protocol, Stack,
-    StaticType, ToValue, TypeCheck, TypeInfo, TypeOf, UnsafeFromValue, Value, VmError, VmErrorKind,
+    ConstValue, FromValue, FunctionHandler, Future, GeneratorState, MacroHandler, Object, Protocol,
+    Stack, StaticType, Stream, ToValue, TypeCheck, TypeInfo, TypeOf, UnsafeFromValue, Value, Vm,
+    VmError, VmErrorKind,
 };
 use crate::{Hash, InstFnInfo, InstFnKind, InstFnName};
+use futures_util::StreamExt as _;
 use std::fmt;
 use std::future;
 use std::sync::Arc;
@@ -241,6 +243,14 @@ pub(crate) struct Macro {
     pub(crate) handler: Arc<MacroHandler>,
 }
 
+/// A protocol declared in a module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ModuleProtocol {
+    pub(crate) protocol: Protocol,
+    /// The number of arguments, excluding the value the protocol is called on.
+    pub(crate) args: usize,
+}
+
 /// A [Module] that is a collection of native functions and types.
 ///
 /// Needs to be installed into a [Context][crate::compile::Context] using
@@ -249,6 +259,8 @@ pub(crate) struct Macro {
 pub struct Module {
     /// A special identifier for this module, which will cause it to not conflict if installed multiple times.
     pub(crate) unique: Option<&'static str>,
+    /// The capability required to call the functions of this module.
+    pub(crate) capability: Option<&'static str>,
     /// The name of the module.
     pub(crate) item: ItemBuf,
     /// Functions.
@@ -257,6 +269,8 @@ pub struct Module {
     pub(crate) macros: HashMap<ItemBuf, Macro>,
     /// Constant values.
     pub(crate) constants: HashMap<ItemBuf, ConstValue>,
+    /// Declared protocols.
+    pub(crate) protocols: HashMap<Hash, ModuleProtocol>,
     /// Associated functions.
     pub(crate) associated_functions: HashMap<AssocKey, AssocFn>,
     /// Registered types.
@@ -282,6 +296,20 @@ impl Module {
         }
     }
 
+    /// Gate the functions of this module behind the given named capability,
+    /// like `fs`, `net` or `process`.
+    ///
+    /// Virtual machines which have been restricted through
+    /// [Vm::set_capabilities][crate::Vm::set_capabilities] can only call them
+    /// if they have been granted the capability. See
+    /// [Capabilities][crate::runtime::Capabilities] for more information.
+    pub fn with_capability(self, capability: &'static str) -> Self {
+        Self {
+            capability: Some(capability),
+            ..self
+        }
+    }
+
     /// Construct a new module for the given item.
     pub fn with_item<I>(iter: I) -> Self
     where
@@ -308,6 +336,7 @@ impl Module {
     fn inner_new(item: ItemBuf) -> Self {
         Self {
             unique: None,
+            capability: None,
             item,
             functions: HashMap::default(),
             macros: HashMap::default(),
@@ -316,6 +345,7 @@ impl Module {
             unit_type: None,
             internal_enums: Vec::new(),
             constants: HashMap::default(),
+            protocols: HashMap::default(),
         }
     }
 
@@ -469,6 +499,40 @@ impl Module {
     where
         T: Named + TypeOf,
         Func: Function<Args, Return = T>,
+    {
+        self.install_variant_constructor::<T>(
+            index,
+            Arc::new(move |stack, args| constructor.fn_call(stack, args)),
+        )
+    }
+
+    /// Register a constructor for a struct variant of type `T`.
+    ///
+    /// The constructor is called with an object holding the fields the
+    /// variant was constructed with in the script, like `Event::Click { x, y
+    /// }`.
+    pub fn struct_variant_constructor<Func, T>(
+        &mut self,
+        index: usize,
+        constructor: Func,
+    ) -> Result<(), ContextError>
+    where
+        T: Named + TypeOf,
+        Func: Function<(Object,), Return = Result<T, VmError>>,
+    {
+        self.install_variant_constructor::<T>(
+            index,
+            Arc::new(move |stack, args| constructor.fn_call(stack, args)),
+        )
+    }
+
+    fn install_variant_constructor<T>(
+        &mut self,
+        index: usize,
+        constructor: Arc<FunctionHandler>,
+    ) -> Result<(), ContextError>
+    where
+        T: Named + TypeOf,
     {
         let type_hash = T::type_hash();
 
@@ -509,10 +573,7 @@ impl Module {
             });
         }
 
-        variant.constructor = Some(Arc::new(move |stack, args| {
-            constructor.fn_call(stack, args)
-        }));
-
+        variant.constructor = Some(constructor);
         Ok(())
     }
 
@@ -651,15 +712,23 @@ impl Module {
 
     /// Register a constant value, at a crate, module or associated level.
     ///
+    /// Constants can be read from scripts and used in constant expressions,
+    /// like `const TAU_2 = math::TAU * 2.0;`. Composite values can be
+    /// registered by building a [ConstValue] directly.
+    ///
     /// # Examples
     ///
     /// ```
+    /// use rune::runtime::ConstValue;
     ///
     /// let mut module = rune::Module::default();
     ///
     /// module.constant(["TEN"], 10)?; // a global TEN value
     /// module.constant(["MyType", "TEN"], 10)?; // looks like an associated value
     ///
+    /// let origin = ConstValue::Tuple(vec![ConstValue::Float(0.0), ConstValue::Float(0.0)].into());
+    /// module.constant(["ORIGIN"], origin)?;
+    ///
     /// # Ok::<_, rune::Error>(())
     /// ```
     pub fn constant<N, V>(&mut self, name: N, value: V) -> Result<(), ContextError>
@@ -689,6 +758,82 @@ impl Module {
         Ok(())
     }
 
+    /// Declare a custom protocol which takes `args` arguments in addition to
+    /// the value it is called on.
+    ///
+    /// Native types implement the protocol by registering an instance function
+    /// for it through [Module::inst_fn], while types defined in scripts
+    /// implement it through an instance function with the same name as the
+    /// protocol. The protocol can then be called on any value through
+    /// [Value::call_protocol].
+    ///
+    /// Declaring the same protocol multiple times is allowed as long as the
+    /// declarations agree on the number of arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{Protocol, Vm};
+    /// use rune::{Any, Context, Module, Value};
+    /// use std::sync::Arc;
+    ///
+    /// const VALIDATE: Protocol = Protocol::new("validate");
+    ///
+    /// #[derive(Any)]
+    /// struct Port(i64);
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = Module::new();
+    /// module.protocol(VALIDATE, 0)?;
+    /// module.ty::<Port>()?;
+    /// module.inst_fn(VALIDATE, |port: &Port| port.0 > 0 && port.0 < 65536)?;
+    ///
+    /// let mut context = Context::with_default_modules()?;
+    /// context.install(&module)?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         struct Name { value }
+    ///
+    ///         impl Name {
+    ///             fn validate(self) {
+    ///                 self.value.len() > 0
+    ///             }
+    ///         }
+    ///
+    ///         pub fn main() {
+    ///             Name { value: "" }
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    ///
+    /// let name = vm.call(["main"], ())?;
+    /// let port = Value::from(rune::runtime::AnyObj::new(Port(8080)));
+    ///
+    /// let name = vm.with(|| name.call_protocol(VALIDATE, ()))?;
+    /// let port = vm.with(|| port.call_protocol(VALIDATE, ()))?;
+    ///
+    /// assert_eq!(name.into_bool()?, false);
+    /// assert_eq!(port.into_bool()?, true);
+    /// # Ok(()) }
+    /// ```
+    pub fn protocol(&mut self, protocol: Protocol, args: usize) -> Result<(), ContextError> {
+        let declaration = ModuleProtocol { protocol, args };
+
+        if let Some(existing) = self.protocols.insert(protocol.hash, declaration) {
+            if existing != declaration {
+                return Err(ContextError::ConflictingProtocol {
+                    name: protocol.name,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Register a native macro handler.
     pub fn macro_<N, M>(&mut self, name: N, f: M) -> Result<(), ContextError>
     where
@@ -864,6 +1009,34 @@ impl Module {
         self.function_inner(FunctionData::new_async(name, f), Docs::default())
     }
 
+    /// Register a function which returns a [Stream][futures_core::Stream].
+    ///
+    /// Scripts consume the returned stream the same way as an async
+    /// generator, by calling `next().await` on it until it returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_util::stream;
+    ///
+    /// let mut module = rune::Module::default();
+    ///
+    /// fn count(limit: i64) -> impl futures_core::Stream<Item = i64> {
+    ///     stream::iter(0..limit)
+    /// }
+    ///
+    /// module.stream_function(["count"], count)?;
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn stream_function<Func, Args, N>(&mut self, name: N, f: Func) -> Result<(), ContextError>
+    where
+        Func: StreamFunction<Args>,
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        self.function_inner(FunctionData::new_stream(name, f), Docs::default())
+    }
+
     /// Register an instance function.
     ///
     /// If possible, [`Module::function_meta`] should be used since it includes more
@@ -1099,6 +1272,19 @@ pub trait AsyncFunction<Args>: 'static + Send + Sync {
     fn fn_call(&self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
 }
 
+/// Trait used to provide the [stream_function][Module::stream_function]
+/// function.
+pub trait StreamFunction<Args>: 'static + Send + Sync {
+    /// The return type of the function.
+    type Return;
+
+    /// Get the number of arguments.
+    fn args() -> usize;
+
+    /// Perform the vm call.
+    fn fn_call(&self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
+}
+
 /// Trait used to provide the [inst_fn][Module::inst_fn] function.
 pub trait InstFn<Args>: 'static + Send + Sync {
     /// The type of the instance.
@@ -1228,6 +1414,49 @@ macro_rules! impl_register {
             }
         }
 
+        impl<Func, Return, $($ty,)*> StreamFunction<($($ty,)*)> for Func
+        where
+            Func: 'static + Send + Sync + Fn($($ty,)*) -> Return,
+            Return: 'static + futures_core::Stream,
+            Return::Item: ToValue,
+            $($ty: 'static + UnsafeFromValue,)*
+        {
+            type Return = Return;
+
+            fn args() -> usize {
+                $count
+            }
+
+            fn fn_call(&self, stack: &mut Stack, args: usize) -> Result<(), VmError> {
+                impl_register!{@check-args $count, args}
+
+                #[allow(unused_mut)]
+                let mut it = stack.drain($count)?;
+                $(let $var = it.next().unwrap();)*
+                drop(it);
+
+                // Safety: Stream is owned and will only be polled within the
+                // context of the virtual machine, same as futures. The stack
+                // guards are kept alive for as long as the stream is.
+                #[allow(unused_unsafe)]
+                let ret = unsafe {
+                    impl_register!{@unsafe-vars $count, $($ty, $var, $num,)*}
+
+                    let stream = self($(<$ty>::unsafe_coerce($var.0),)*);
+                    #[allow(unused_variables)]
+                    let guards = ($($var.1,)*);
+
+                    Stream::<Vm>::from_stream(stream.map(move |item| {
+                        let _ = &guards;
+                        item
+                    }))
+                };
+
+                impl_register!{@return stack, ret, Return}
+                Ok(())
+            }
+        }
+
         impl<Func, Return, Instance, $($ty,)*> InstFn<(Instance, $($ty,)*)> for Func
         where
             Func: 'static + Send + Sync + Fn(Instance $(, $ty)*) -> Return,
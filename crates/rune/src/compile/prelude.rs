@@ -20,6 +20,8 @@ impl Prelude {
         this.add_prelude("char", ["char"]);
         this.add_prelude("dbg", ["io", "dbg"]);
         this.add_prelude("drop", ["mem", "drop"]);
+        this.add_prelude("eprint", ["io", "eprint"]);
+        this.add_prelude("eprintln", ["io", "eprintln"]);
         this.add_prelude("Err", ["result", "Result", "Err"]);
         this.add_prelude("file", ["macros", "builtin", "file"]);
         this.add_prelude("float", ["float"]);
@@ -39,6 +41,8 @@ impl Prelude {
         this.add_prelude("Some", ["option", "Option", "Some"]);
         this.add_prelude("String", ["string", "String"]);
         this.add_prelude("stringify", ["stringify"]);
+        this.add_prelude("sym", ["symbol", "sym"]);
+        this.add_prelude("Symbol", ["symbol", "Symbol"]);
         this.add_prelude("unit", ["unit"]);
         this.add_prelude("Vec", ["vec", "Vec"]);
 
@@ -10,7 +10,7 @@ use crate::compile::{
     PrivMeta, PrivMetaKind, PrivVariantMeta,
 };
 use crate::query::{QueryError, QueryErrorKind};
-use crate::runtime::debug::{DebugArgs, DebugSignature};
+use crate::runtime::debug::{DebugArgs, DebugSignature, DebugVariable};
 use crate::runtime::{
     Call, ConstValue, DebugInfo, DebugInst, Inst, Label, Protocol, Rtti, StaticString, Unit,
     UnitFn, VariantRtti,
@@ -71,6 +71,8 @@ pub(crate) struct UnitBuilder {
     debug: Option<Box<DebugInfo>>,
     /// Constant values
     constants: HashMap<Hash, ConstValue>,
+    /// The number of inline cache slots allocated.
+    inline_caches: usize,
 }
 
 impl UnitBuilder {
@@ -114,6 +116,18 @@ impl UnitBuilder {
             ));
         }
 
+        // Share strings in constants with the static strings of the unit, so
+        // that identical strings are only stored once.
+        let mut interned = self
+            .static_strings
+            .iter()
+            .map(|s| (s.hash(), s.clone()))
+            .collect::<HashMap<_, _>>();
+
+        for value in self.constants.values_mut() {
+            intern_const_strings(value, &mut interned);
+        }
+
         Ok(Unit::new(
             self.instructions,
             self.functions,
@@ -124,6 +138,7 @@ impl UnitBuilder {
             self.variant_rtti,
             self.debug,
             self.constants,
+            self.inline_caches,
         ))
     }
 
@@ -170,6 +185,14 @@ impl UnitBuilder {
         Ok(new_slot)
     }
 
+    /// Allocate a new inline cache slot for an instruction which dispatches on
+    /// the type of a value.
+    pub(crate) fn new_inline_cache(&mut self) -> usize {
+        let slot = self.inline_caches;
+        self.inline_caches += 1;
+        slot
+    }
+
     /// Insert a static byte string and return its associated slot that can
     /// later be looked up through [lookup_bytes][Unit::lookup_bytes].
     ///
@@ -670,6 +693,15 @@ impl UnitBuilder {
 
         self.required_functions.extend(assembly.required_functions);
 
+        let base = self.instructions.len();
+        let debug = self.debug.get_or_insert_with(Default::default);
+
+        for (pos, offset, name) in &assembly.variables {
+            debug
+                .variables
+                .push(DebugVariable::new(base + pos, *offset, name.clone()));
+        }
+
         for (pos, (inst, span)) in assembly.instructions.into_iter().enumerate() {
             let mut comment = None::<Box<str>>;
             let label = assembly.labels_rev.get(&pos).copied();
@@ -761,3 +793,44 @@ impl UnitBuilder {
         }
     }
 }
+
+/// Replace owned strings in a constant value with shared static strings.
+fn intern_const_strings(value: &mut ConstValue, interned: &mut HashMap<Hash, Arc<StaticString>>) {
+    match value {
+        ConstValue::String(string) => {
+            let hash = Hash::of(&*string);
+
+            let interned = match interned.get(&hash) {
+                Some(existing) if ***existing == *string => existing.clone(),
+                // NB: leave strings with conflicting hashes alone.
+                Some(..) => return,
+                None => {
+                    let new = Arc::new(StaticString::new(string.as_str()));
+                    interned.insert(hash, new.clone());
+                    new
+                }
+            };
+
+            *value = ConstValue::StaticString(interned);
+        }
+        ConstValue::Vec(values) => {
+            for value in values {
+                intern_const_strings(value, interned);
+            }
+        }
+        ConstValue::Tuple(values) => {
+            for value in values.iter_mut() {
+                intern_const_strings(value, interned);
+            }
+        }
+        ConstValue::Object(object) => {
+            for value in object.values_mut() {
+                intern_const_strings(value, interned);
+            }
+        }
+        ConstValue::Option(Some(value)) => {
+            intern_const_strings(value, interned);
+        }
+        _ => (),
+    }
+}
@@ -17,8 +17,8 @@ use crate::hir;
 use crate::parse::{Id, ParseErrorKind, Resolve};
 use crate::query::Named;
 use crate::runtime::{
-    ConstValue, Inst, InstAddress, InstAssignOp, InstOp, InstRangeLimits, InstTarget, InstValue,
-    InstVariant, Label, PanicReason, Protocol, TypeCheck,
+    ConstValue, Inst, InstAddress, InstAssignOp, InstIntOp, InstOp, InstRangeLimits, InstTarget,
+    InstValue, InstVariant, Label, PanicReason, Protocol, TypeCheck,
 };
 use crate::Hash;
 
@@ -292,7 +292,9 @@ fn pat(
 
             if let Some(ident) = named.as_local() {
                 load(c, Needs::Value)?;
-                c.scopes.decl_var(ident, span)?;
+                let offset = c.scopes.decl_var(ident, span)?;
+                c.q.visitor.visit_variable_declaration(c.source_id, span);
+                c.asm.declare(ident, offset);
                 return Ok(false);
             }
 
@@ -802,7 +804,15 @@ fn pat_object(
             Binding::Binding(_, _, p) => {
                 let load = move |c: &mut Assembler<'_>, needs: Needs| {
                     if needs.value() {
-                        c.asm.push(Inst::ObjectIndexGetAt { offset, slot }, span);
+                        let cache = c.q.unit.new_inline_cache();
+                        c.asm.push(
+                            Inst::ObjectIndexGetAt {
+                                offset,
+                                slot,
+                                cache,
+                            },
+                            span,
+                        );
                     }
 
                     Ok(())
@@ -811,8 +821,17 @@ fn pat_object(
                 pat(p, c, false_label, &load)?;
             }
             Binding::Ident(_, key) => {
-                c.asm.push(Inst::ObjectIndexGetAt { offset, slot }, span);
-                c.scopes.decl_var(key, span)?;
+                let cache = c.q.unit.new_inline_cache();
+                c.asm.push(
+                    Inst::ObjectIndexGetAt {
+                        offset,
+                        slot,
+                        cache,
+                    },
+                    span,
+                );
+                let offset = c.scopes.decl_var(key, span)?;
+                c.asm.declare(key, offset);
             }
         }
     }
@@ -876,7 +895,8 @@ pub(crate) fn closure_from_block(
     let guard = c.scopes.push_child(span)?;
 
     for capture in captures {
-        c.scopes.new_var(&capture.ident, span)?;
+        let offset = c.scopes.new_var(&capture.ident, span)?;
+        c.asm.declare(&capture.ident, offset);
     }
 
     return_(c, span, hir, block)?;
@@ -1336,14 +1356,6 @@ fn expr_binary(
         return Ok(Asm::top(span));
     }
 
-    let guard = c.scopes.push_child(span)?;
-
-    // NB: need to declare these as anonymous local variables so that they
-    // get cleaned up in case there is an early break (return, try, ...).
-    let rhs_needs = rhs_needs_of(&hir.op);
-    let a = expr(hir.lhs, c, Needs::Value)?.apply_targeted(c)?;
-    let b = expr(hir.rhs, c, rhs_needs)?.apply_targeted(c)?;
-
     let op = match hir.op {
         ast::BinOp::Eq(..) => InstOp::Eq,
         ast::BinOp::Neq(..) => InstOp::Neq,
@@ -1374,7 +1386,41 @@ fn expr_binary(
         }
     };
 
-    c.asm.push(Inst::Op { op, a, b }, span);
+    let guard = c.scopes.push_child(span)?;
+
+    // NB: need to declare these as anonymous local variables so that they
+    // get cleaned up in case there is an early break (return, try, ...).
+    let rhs_needs = rhs_needs_of(&hir.op);
+    let a = expr(hir.lhs, c, Needs::Value)?.apply_targeted(c)?;
+
+    match InstIntOp::from_op(op) {
+        Some(op) => match integer_literal(hir.rhs, c)? {
+            Some(b) => {
+                c.asm.push(Inst::IntOpConst { op, a, b }, span);
+            }
+            None => {
+                let speculate = speculate_integer(hir.lhs, c)? && speculate_integer(hir.rhs, c)?;
+                let b = expr(hir.rhs, c, rhs_needs)?.apply_targeted(c)?;
+
+                if speculate {
+                    c.asm.push(Inst::IntOp { op, a, b }, span);
+                } else {
+                    c.asm.push(
+                        Inst::Op {
+                            op: op.into_op(),
+                            a,
+                            b,
+                        },
+                        span,
+                    );
+                }
+            }
+        },
+        None => {
+            let b = expr(hir.rhs, c, rhs_needs)?.apply_targeted(c)?;
+            c.asm.push(Inst::Op { op, a, b }, span);
+        }
+    }
 
     // NB: we put it here to preserve the call in case it has side effects.
     // But if we don't need the value, then pop it from the stack.
@@ -1385,6 +1431,37 @@ fn expr_binary(
     c.scopes.pop(guard, span)?;
     return Ok(Asm::top(span));
 
+    /// Resolve the given expression if it's an integer literal.
+    fn integer_literal(hir: &hir::Expr<'_>, c: &mut Assembler<'_>) -> CompileResult<Option<i64>> {
+        let (neg, lit) = match hir.kind {
+            hir::ExprKind::Lit(ast::Lit::Number(lit)) => (false, lit),
+            hir::ExprKind::Unary(hir::ExprUnary {
+                op: ast::UnOp::Neg(..),
+                expr:
+                    hir::Expr {
+                        kind: hir::ExprKind::Lit(ast::Lit::Number(lit)),
+                        ..
+                    },
+            }) => (true, lit),
+            _ => return Ok(None),
+        };
+
+        Ok(match lit.resolve(resolve_context!(c.q))? {
+            ast::Number::Integer(n) if neg => n.neg().to_i64(),
+            ast::Number::Integer(n) => n.to_i64(),
+            ast::Number::Float(..) => None,
+        })
+    }
+
+    /// Test if an integer operation should be speculated for the given
+    /// operand, which is the case unless it's a literal of another kind.
+    fn speculate_integer(hir: &hir::Expr<'_>, c: &mut Assembler<'_>) -> CompileResult<bool> {
+        Ok(match hir.kind {
+            hir::ExprKind::Lit(..) => integer_literal(hir, c)?.is_some(),
+            _ => true,
+        })
+    }
+
     /// Get the need of the right-hand side operator from the type of the
     /// operator.
     fn rhs_needs_of(op: &ast::BinOp) -> Needs {
@@ -1897,7 +1974,8 @@ fn expr_call(
                 c.scopes.decl_anon(span)?;
             }
 
-            c.asm.push(Inst::CallInstance { hash, args }, span);
+            let cache = c.q.unit.new_inline_cache();
+            c.asm.push(Inst::CallInstance { hash, args, cache }, span);
             c.scopes.undecl_anon(span, hir.args.len() + 1)?;
         }
         Call::Meta { meta, hash } => {
@@ -1968,7 +2046,8 @@ pub(crate) fn closure_from_expr_closure(
         c.asm.push(Inst::PushTuple, span);
 
         for capture in captures {
-            c.scopes.new_var(&capture.ident, span)?;
+            let offset = c.scopes.new_var(&capture.ident, span)?;
+            c.asm.declare(&capture.ident, offset);
         }
     }
 
@@ -2140,8 +2219,9 @@ fn expr_field_access(
             if let Some(ident) = path.try_as_ident() {
                 let field = ident.resolve(resolve_context!(c.q))?;
                 let slot = c.q.unit.new_static_string(span, field.as_ref())?;
+                let cache = c.q.unit.new_inline_cache();
 
-                c.asm.push(Inst::ObjectIndexGet { slot }, span);
+                c.asm.push(Inst::ObjectIndexGet { slot, cache }, span);
 
                 if !needs.value() {
                     c.diagnostics.not_used(c.source_id, span, c.context());
@@ -2223,10 +2303,13 @@ fn expr_for(
         expr(hir.iter, c, Needs::Value)?.apply(c)?;
 
         let iter_offset = c.scopes.decl_anon(span)?;
+        let cache = c.q.unit.new_inline_cache();
+
         c.asm.push_with_comment(
             Inst::CallInstance {
                 hash: *Protocol::INTO_ITER,
                 args: 0,
+                cache,
             },
             span,
             format!("into_iter (offset: {})", iter_offset),
@@ -2320,10 +2403,13 @@ fn expr_for(
             hir.iter.span(),
         );
 
+        let cache = c.q.unit.new_inline_cache();
+
         c.asm.push_with_comment(
             Inst::CallInstance {
                 hash: *Protocol::NEXT,
                 args: 0,
+                cache,
             },
             span,
             "next",
@@ -2671,7 +2757,15 @@ fn expr_object(
                     check_object_fields(&st.fields, check_keys, span, item)?;
 
                     let hash = Hash::type_hash(item);
-                    c.asm.push(Inst::StructVariant { hash, slot }, span);
+
+                    // Native variants are constructed by calling their
+                    // constructor with the fields as an object.
+                    if c.context.lookup_function(hash).is_some() {
+                        c.asm.push(Inst::Object { slot }, span);
+                        c.asm.push(Inst::Call { hash, args: 1 }, span);
+                    } else {
+                        c.asm.push(Inst::StructVariant { hash, slot }, span);
+                    }
                 }
                 _ => {
                     return Err(CompileError::new(
@@ -2959,7 +3053,10 @@ fn expr_select(
                     named.assert_not_generic()?;
 
                     if let Some(local) = named.as_local() {
-                        c.scopes.decl_var(local, path.span())?;
+                        let offset = c.scopes.decl_var(local, path.span())?;
+                        c.q.visitor
+                            .visit_variable_declaration(c.source_id, path.span());
+                        c.asm.declare(local, offset);
                         break;
                     }
                 }
@@ -3281,7 +3378,8 @@ pub(crate) fn fn_from_item_fn(
                     return Err(CompileError::new(*span, CompileErrorKind::UnsupportedSelf));
                 }
 
-                c.scopes.new_var(SELF, *span)?;
+                let offset = c.scopes.new_var(SELF, *span)?;
+                c.asm.declare(SELF, offset);
             }
             hir::FnArg::Pat(pat) => {
                 let offset = c.scopes.decl_anon(pat.span())?;
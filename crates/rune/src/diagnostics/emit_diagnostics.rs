@@ -143,6 +143,17 @@ impl EmitDiagnostics for VmError {
                 labels.push(d::Label::primary(source_id, span.range()).with_message("panicked"));
                 ("panic in runtime".to_owned(), vec![reason.to_string()])
             }
+            VmErrorKind::Host { error } => {
+                labels.push(
+                    d::Label::primary(source_id, span.range())
+                        .with_message("raised by this call".to_string()),
+                );
+
+                (
+                    "error raised by host".to_owned(),
+                    error.chain().map(|e| e.to_string()).collect(),
+                )
+            }
             VmErrorKind::UnsupportedBinaryOperation { lhs, rhs, op } => {
                 labels.push(
                     d::Label::primary(source_id, span.range())
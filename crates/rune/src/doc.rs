@@ -8,5 +8,8 @@ mod templating;
 mod html;
 pub use self::html::write_html;
 
+mod markdown;
+pub use self::markdown::write_markdown;
+
 mod visitor;
 pub use self::visitor::Visitor;
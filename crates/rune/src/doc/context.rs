@@ -1,5 +1,5 @@
 use crate::compile::{
-    ComponentRef, ContextMetaKind, ContextSignature, IntoComponent, Item, MetaKind,
+    ComponentRef, ContextMetaKind, ContextSignature, IntoComponent, Item, ItemBuf, MetaKind,
 };
 use crate::doc::Visitor;
 use crate::runtime::ConstValue;
@@ -35,6 +35,17 @@ pub(crate) enum Signature {
     Instance { args: Option<usize> },
 }
 
+/// The kind of entry an item is documented as.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Target {
+    Module,
+    Type,
+    Struct,
+    Function,
+    /// A function associated with a type or struct.
+    Method,
+}
+
 /// Build context for documentation.
 ///
 /// Provides a unified API for querying information about known types.
@@ -142,6 +153,62 @@ impl<'a> Context<'a> {
         })
     }
 
+    /// Resolve a path as written in documentation, like `std::io::println`,
+    /// into a documented item.
+    pub(crate) fn resolve(&self, path: &str) -> Option<(ItemBuf, Target)> {
+        let path = path.trim_matches('`').trim_start_matches("::");
+
+        if path.is_empty() {
+            return None;
+        }
+
+        let mut components = path.split("::");
+        let first = components.next()?;
+
+        // Paths might either refer to a crate, like items installed through
+        // modules do, or to an item in the script being documented.
+        let candidates = [
+            ItemBuf::with_crate_item(first, components),
+            ItemBuf::with_item(path.split("::")),
+        ];
+
+        for item in candidates {
+            if let Some(target) = self.target(&item) {
+                return Some((item, target));
+            }
+        }
+
+        None
+    }
+
+    /// Get the kind of entry the given item is documented as.
+    pub(crate) fn target(&self, item: &Item) -> Option<Target> {
+        if let Some(meta) = self.meta(item) {
+            return match meta.kind {
+                Kind::Unknown => Some(Target::Type),
+                Kind::Struct => Some(Target::Struct),
+                Kind::Function {
+                    signature: Signature::Instance { .. },
+                    ..
+                } => Some(Target::Method),
+                Kind::Function { .. } => match item.parent().and_then(|parent| self.meta(parent)) {
+                    Some(Meta {
+                        kind: Kind::Unknown | Kind::Struct,
+                        ..
+                    }) => Some(Target::Method),
+                    _ => Some(Target::Function),
+                },
+                _ => None,
+            };
+        }
+
+        if self.iter_modules().into_iter().any(|m| m == item) {
+            return Some(Target::Module);
+        }
+
+        None
+    }
+
     /// Iterate over known modules.
     pub(crate) fn iter_modules(&self) -> impl IntoIterator<Item = &Item> {
         self.visitors
@@ -13,7 +13,7 @@ use syntect::parsing::{SyntaxReference, SyntaxSet};
 
 use crate::collections::{BTreeSet, VecDeque};
 use crate::compile::{ComponentRef, Item, ItemBuf};
-use crate::doc::context::{Kind, Signature};
+use crate::doc::context::{Kind, Signature, Target};
 use crate::doc::templating;
 use crate::doc::{Context, Visitor};
 use crate::Hash;
@@ -311,7 +311,7 @@ fn module(
                         item,
                         name,
                         args: args_to_string(args, signature)?,
-                        doc: cx.render_docs(meta.docs)?,
+                        doc: cx.render_docs(meta.docs, dir)?,
                     });
                 }
             }
@@ -381,7 +381,7 @@ fn type_(cx: &Ctxt<'_>, m: &Item, hash: Hash, root: &Path) -> Result<RelativePat
             Kind::Function {
                 args, signature, ..
             } => {
-                let doc = cx.render_docs(meta.docs)?;
+                let doc = cx.render_docs(meta.docs, dir)?;
 
                 methods.push(Method {
                     item,
@@ -449,7 +449,7 @@ fn struct_(cx: &Ctxt<'_>, m: &Item, root: &Path) -> Result<RelativePathBuf> {
             Kind::Function {
                 args, signature, ..
             } => {
-                let doc = cx.render_docs(meta.docs)?;
+                let doc = cx.render_docs(meta.docs, dir)?;
 
                 methods.push(Method {
                     item,
@@ -509,7 +509,7 @@ fn function(cx: &Ctxt<'_>, item: &Item, root: &Path) -> Result<RelativePathBuf>
 
     let name = item.last().context("missing function name")?;
 
-    let doc = cx.render_docs(meta.docs)?;
+    let doc = cx.render_docs(meta.docs, dir)?;
 
     let p = path.to_path(root);
     ensure_parent_dir(&p)?;
@@ -562,9 +562,38 @@ fn item_path(cx: &Ctxt<'_>, item: &Item, kind: ItemPath) -> RelativePathBuf {
 }
 
 impl Ctxt<'_> {
+    /// Get a link to the documentation of the given item, relative to `dir`.
+    fn link(&self, item: &Item, target: Target, dir: &RelativePath) -> Option<String> {
+        let (path, fragment) = match target {
+            Target::Module => (item_path(self, item, ItemPath::Module), None),
+            Target::Type => (item_path(self, item, ItemPath::Type), None),
+            Target::Struct => (item_path(self, item, ItemPath::Struct), None),
+            Target::Function => (item_path(self, item, ItemPath::Function), None),
+            Target::Method => {
+                let parent = item.parent()?;
+
+                let kind = match self.context.target(parent)? {
+                    Target::Type => ItemPath::Type,
+                    Target::Struct => ItemPath::Struct,
+                    _ => return None,
+                };
+
+                let fragment = format!("method.{}", item.last()?);
+                (item_path(self, parent, kind), Some(fragment))
+            }
+        };
+
+        let path = dir.relative(path);
+
+        Some(match fragment {
+            Some(fragment) => format!("{path}#{fragment}"),
+            None => path.to_string(),
+        })
+    }
+
     /// Render documentation.
-    fn render_docs(&self, docs: &[String]) -> Result<Option<String>> {
-        use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag};
+    fn render_docs(&self, docs: &[String], dir: &RelativePath) -> Result<Option<String>> {
+        use pulldown_cmark::{BrokenLink, CodeBlockKind, CowStr, Event, Options, Parser, Tag};
         use std::fmt::Write;
 
         struct Filter<'a> {
@@ -684,9 +713,18 @@ impl Ctxt<'_> {
             input.push('\n');
         }
 
+        // Links like [`std::io::println`] without a definition are resolved
+        // to the documentation of the item they name.
+        let mut resolve = |link: BrokenLink<'_>| {
+            let (item, target) = self.context.resolve(&link.reference)?;
+            let url = self.link(&item, target, dir)?;
+            Some((url.into(), link.reference.into_string().into()))
+        };
+
         let mut options = Options::empty();
         options.insert(Options::ENABLE_STRIKETHROUGH);
-        let parser = Filter::new(self, Parser::new_ext(&input, options));
+        let parser = Parser::new_with_broken_link_callback(&input, options, Some(&mut resolve));
+        let parser = Filter::new(self, parser);
         let mut out = String::new();
         pulldown_cmark::html::push_html(&mut out, parser);
         write!(o, "{out}")?;
@@ -712,7 +750,7 @@ where
 }
 
 /// Ensure parent dir exists.
-fn ensure_parent_dir(path: &Path) -> Result<()> {
+pub(super) fn ensure_parent_dir(path: &Path) -> Result<()> {
     if let Some(p) = path.parent() {
         if p.is_dir() {
             return Ok(());
@@ -731,7 +769,7 @@ fn ensure_parent_dir(path: &Path) -> Result<()> {
 }
 
 /// Coerce args into string.
-fn args_to_string(args: Option<&[String]>, sig: Signature) -> Result<String> {
+pub(super) fn args_to_string(args: Option<&[String]>, sig: Signature) -> Result<String> {
     use std::fmt::Write;
 
     if let Some(args) = args {
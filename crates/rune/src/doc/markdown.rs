@@ -0,0 +1,265 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use pulldown_cmark::{BrokenLink, Options, Parser};
+use relative_path::{RelativePath, RelativePathBuf};
+
+use crate::compile::{ComponentRef, Item};
+use crate::doc::context::{Kind, Signature, Target};
+use crate::doc::html::{args_to_string, ensure_parent_dir};
+use crate::doc::{Context, Visitor};
+
+struct Ctxt<'a> {
+    name: &'a str,
+    context: &'a Context<'a>,
+}
+
+/// Write markdown documentation to the given path.
+///
+/// This produces one file per module, and an `index.md` file linking to all
+/// of them.
+pub fn write_markdown(
+    name: &str,
+    root: &Path,
+    context: &crate::Context,
+    visitors: &[Visitor],
+) -> Result<()> {
+    let context = Context::new(context, visitors);
+    let cx = Ctxt {
+        name,
+        context: &context,
+    };
+
+    let mut modules = cx.context.iter_modules().into_iter().collect::<Vec<_>>();
+    modules.sort();
+    modules.dedup();
+
+    let mut index = String::new();
+    writeln!(index, "# Modules")?;
+    writeln!(index)?;
+
+    for m in modules {
+        let path = module_path(&cx, m);
+        module(&cx, m, &path, root)?;
+        writeln!(index, "* [{}]({})", display(&cx, m), path)?;
+    }
+
+    write(&RelativePath::new("index.md").to_path(root), index)
+}
+
+/// Build a single module.
+#[tracing::instrument(skip_all)]
+fn module(cx: &Ctxt<'_>, m: &Item, path: &RelativePath, root: &Path) -> Result<()> {
+    let dir = path.parent().unwrap_or(RelativePath::new(""));
+
+    let mut types = String::new();
+    let mut structs = String::new();
+    let mut functions = String::new();
+
+    for name in cx.context.iter_components(m) {
+        let item = m.join([name]);
+
+        let meta = match cx.context.meta(&item) {
+            Some(meta) => meta,
+            _ => continue,
+        };
+
+        let (out, anchor) = match meta.kind {
+            Kind::Unknown => (&mut types, "type"),
+            Kind::Struct => (&mut structs, "struct"),
+            Kind::Function {
+                args,
+                signature: signature @ Signature::Function { .. },
+            } => {
+                writeln!(functions, "<a id=\"fn.{name}\"></a>")?;
+                writeln!(
+                    functions,
+                    "### `fn {name}({})`",
+                    args_to_string(args, signature)?
+                )?;
+                writeln!(functions)?;
+                render_docs(cx, meta.docs, dir, &mut functions)?;
+                continue;
+            }
+            _ => continue,
+        };
+
+        writeln!(out, "<a id=\"{anchor}.{name}\"></a>")?;
+        writeln!(out, "### `{name}`")?;
+        writeln!(out)?;
+        render_docs(cx, meta.docs, dir, out)?;
+        methods(cx, &item, dir, out)?;
+    }
+
+    let mut o = String::new();
+    writeln!(o, "# Module `{}`", display(cx, m))?;
+    writeln!(o)?;
+
+    for (title, section) in [
+        ("Types", types),
+        ("Structs", structs),
+        ("Functions", functions),
+    ] {
+        if !section.is_empty() {
+            writeln!(o, "## {title}")?;
+            writeln!(o)?;
+            o.push_str(&section);
+        }
+    }
+
+    write(&path.to_path(root), o)
+}
+
+/// Write the methods associated with a type or struct.
+fn methods(cx: &Ctxt<'_>, parent: &Item, dir: &RelativePath, out: &mut String) -> Result<()> {
+    let mut first = true;
+
+    for name in cx.context.iter_components(parent) {
+        let item = parent.join([name]);
+
+        let (args, signature, docs) = match cx.context.meta(&item) {
+            Some(meta) => match meta.kind {
+                Kind::Function { args, signature } => (args, signature, meta.docs),
+                _ => continue,
+            },
+            _ => continue,
+        };
+
+        if first {
+            writeln!(out, "#### Methods")?;
+            writeln!(out)?;
+            first = false;
+        }
+
+        let parent_name = parent.last().context("missing parent name")?;
+        writeln!(out, "<a id=\"method.{parent_name}.{name}\"></a>")?;
+        writeln!(
+            out,
+            "##### `fn {name}({})`",
+            args_to_string(args, signature)?
+        )?;
+        writeln!(out)?;
+        render_docs(cx, docs, dir, out)?;
+    }
+
+    Ok(())
+}
+
+/// Render documentation, adding link definitions for links to known items.
+fn render_docs(cx: &Ctxt<'_>, docs: &[String], dir: &RelativePath, out: &mut String) -> Result<()> {
+    if docs.is_empty() {
+        return Ok(());
+    }
+
+    let mut input = String::new();
+    let mut in_code = false;
+
+    for line in docs {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let line = line.strip_prefix(' ').unwrap_or(line);
+
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        } else if in_code && line.starts_with('#') {
+            // Hidden lines in examples.
+            continue;
+        }
+
+        input.push_str(line);
+        input.push('\n');
+    }
+
+    let mut references = Vec::new();
+
+    let mut collect = |link: BrokenLink<'_>| {
+        references.push(link.reference.into_string());
+        None
+    };
+
+    Parser::new_with_broken_link_callback(&input, Options::empty(), Some(&mut collect))
+        .for_each(drop);
+
+    references.sort();
+    references.dedup();
+
+    let mut definitions = String::new();
+
+    for reference in references {
+        if let Some((item, target)) = cx.context.resolve(&reference) {
+            if let Some(link) = link(cx, &item, target, dir) {
+                writeln!(definitions, "[{reference}]: {link}")?;
+            }
+        }
+    }
+
+    out.push_str(&input);
+
+    if !definitions.is_empty() {
+        writeln!(out)?;
+        out.push_str(&definitions);
+    }
+
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Get a link to the documentation of the given item, relative to `dir`.
+fn link(cx: &Ctxt<'_>, item: &Item, target: Target, dir: &RelativePath) -> Option<String> {
+    let (module, fragment) = match target {
+        Target::Module => (item, None),
+        Target::Type => (item.parent()?, Some(format!("type.{}", item.last()?))),
+        Target::Struct => (item.parent()?, Some(format!("struct.{}", item.last()?))),
+        Target::Function => (item.parent()?, Some(format!("fn.{}", item.last()?))),
+        Target::Method => {
+            let parent = item.parent()?;
+            let fragment = format!("method.{}.{}", parent.last()?, item.last()?);
+            (parent.parent()?, Some(fragment))
+        }
+    };
+
+    let path = dir.relative(module_path(cx, module));
+
+    Some(match fragment {
+        Some(fragment) => format!("{path}#{fragment}"),
+        None => path.to_string(),
+    })
+}
+
+/// Get the path of the file a module is documented in.
+fn module_path(cx: &Ctxt<'_>, item: &Item) -> RelativePathBuf {
+    let mut path = RelativePathBuf::new();
+
+    if item.is_empty() {
+        path.push(cx.name);
+    } else {
+        for c in item.iter() {
+            let string = match c {
+                ComponentRef::Crate(string) => string,
+                ComponentRef::Str(string) => string,
+                _ => continue,
+            };
+
+            path.push(string);
+        }
+    }
+
+    path.set_extension("md");
+    path
+}
+
+/// Get the name a module is displayed as.
+fn display(cx: &Ctxt<'_>, item: &Item) -> String {
+    if item.is_empty() {
+        cx.name.to_owned()
+    } else {
+        item.to_string()
+    }
+}
+
+fn write(path: &Path, data: String) -> Result<()> {
+    ensure_parent_dir(path)?;
+    tracing::info!("writing: {}", path.display());
+    fs::write(path, data).with_context(|| path.display().to_string())
+}
@@ -5,7 +5,6 @@ use serde::{Deserialize, Serialize};
 use std::any;
 use std::fmt;
 use std::hash::{self, BuildHasher, BuildHasherDefault, Hash as _, Hasher};
-use std::mem;
 use twox_hash::XxHash64;
 
 const SEP: u64 = 0x4bc94d6bd06053ad;
@@ -16,6 +15,7 @@ const FIELD_FUNCTION_HASH: u64 = 0xab53b6a7a53c757e;
 const OBJECT_KEYS: u64 = 0x4473d7017aef7645;
 const INDEX_FUNCTION_HASH: u64 = 0x2579e52d1534901b;
 const INDEX: u64 = 0xe1b2378d7a937035;
+const PROTOCOL: u64 = 0xb227e0ca34a622ef;
 
 /// The primitive hash that among other things is used to reference items,
 /// types, and native functions.
@@ -39,6 +39,23 @@ impl Hash {
         Self(hasher.finish())
     }
 
+    /// Construct the hash of a custom protocol from its name.
+    ///
+    /// This uses FNV-1a since it can be evaluated in a constant context.
+    pub(crate) const fn protocol(name: &str) -> Self {
+        let bytes = name.as_bytes();
+        let mut hash = 0xcbf29ce484222325u64;
+        let mut n = 0;
+
+        while n < bytes.len() {
+            hash ^= bytes[n] as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+            n += 1;
+        }
+
+        Self(PROTOCOL ^ hash)
+    }
+
     /// Construct a hash from an index.
     #[inline]
     pub fn index(index: usize) -> Self {
@@ -62,10 +79,10 @@ impl Hash {
     }
 
     /// Construct a hash from a type id.
-    pub const fn from_type_id(type_id: any::TypeId) -> Self {
-        // Safety: a type id is exactly a 64-bit unsigned integer.
-        // And has an identical bit pattern to `Hash`.
-        unsafe { mem::transmute(type_id) }
+    pub fn from_type_id(type_id: any::TypeId) -> Self {
+        // NB: the size of a type id is unspecified, so it's hashed instead of
+        // being reinterpreted as a `Hash`.
+        Self::of(type_id)
     }
 
     /// Construct a hash to an instance function, where the instance is a
@@ -569,6 +569,11 @@ impl<'a> Indexer<'a> {
         }
 
         let source_id = self.q.sources.insert(source);
+        self.q
+            .visitor
+            .visit_mod_declaration(Location::new(self.source_id, span), source_id);
+
+        #[allow(deprecated)]
         self.q.visitor.visit_mod(source_id, span);
 
         self.queue.push_back(Task::LoadFile {
@@ -739,6 +744,28 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
         _ => false,
     };
 
+    let is_ignored = match attributes.try_parse::<attrs::Ignore>(resolve_context!(idx.q))? {
+        Some((span, _)) if !is_test && !is_bench => {
+            return Err(CompileError::msg(
+                span,
+                "#[ignore] is only supported on #[test] and #[bench] functions",
+            ));
+        }
+        Some(..) => true,
+        None => false,
+    };
+
+    let should_panic = match attributes.try_parse::<attrs::ShouldPanic>(resolve_context!(idx.q))? {
+        Some((span, _)) if !is_test => {
+            return Err(CompileError::msg(
+                span,
+                "#[should_panic] is only supported on #[test] functions",
+            ));
+        }
+        Some(..) => true,
+        None => false,
+    };
+
     if let Some(attrs) = attributes.remaining() {
         return Err(CompileError::msg(attrs, "unrecognized function attribute"));
     }
@@ -777,6 +804,8 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
                 function,
                 is_test,
                 is_bench,
+                is_ignored,
+                should_panic,
             }),
         };
 
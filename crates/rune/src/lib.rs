@@ -239,12 +239,12 @@ cfg_workspace! {
     pub mod workspace;
 }
 
-#[doc(hidden)]
 pub mod testing;
 
 // Macros used internally and re-exported.
 pub(crate) use rune_macros::__internal_impl_any;
 pub use rune_macros::function;
+pub use rune_macros::impl_block;
 
 #[cfg(feature = "doc")]
 pub mod doc;
@@ -5,8 +5,8 @@ use std::fmt;
 use crate::ast;
 use crate::ast::Span;
 use crate::compile::{
-    IrCompiler, IrError, IrEval, IrEvalContext, IrValue, ItemMeta, NoopCompileVisitor, Pool,
-    Prelude, UnitBuilder,
+    Context, IrCompiler, IrError, IrEval, IrEvalContext, IrValue, ItemMeta, NoopCompileVisitor,
+    Pool, Prelude, UnitBuilder,
 };
 use crate::macros::{IntoLit, Storage, ToTokens, TokenStream};
 use crate::parse::{Parse, ParseError, ParseErrorKind, Resolve, ResolveError};
@@ -41,6 +41,7 @@ impl<'a> MacroContext<'a> {
     where
         F: FnOnce(&mut MacroContext<'_>) -> O,
     {
+        let context = Context::default();
         let mut unit = UnitBuilder::default();
         let prelude = Prelude::default();
         let gen = Gen::default();
@@ -52,6 +53,7 @@ impl<'a> MacroContext<'a> {
         let mut inner = Default::default();
 
         let mut query = Query::new(
+            &context,
             &mut unit,
             &prelude,
             &mut consts,
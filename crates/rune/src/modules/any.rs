@@ -1,29 +1,116 @@
 //! `std::any` module.
 
-use crate::runtime::{Protocol, Value};
-use crate::{Any, ContextError, Module};
-use std::any::TypeId as StdTypeId;
+use crate::runtime::{Protocol, Value, VariantData, VmError};
+use crate::{Any, ContextError, Hash, Module};
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
 use std::fmt::Write;
 
 #[derive(Any, Debug)]
 #[rune(module = "crate")]
 #[repr(transparent)]
-struct TypeId(StdTypeId);
+struct TypeId(Hash);
 
-fn type_id_of_val(item: Value) -> TypeId {
-    unsafe { std::mem::transmute(item.type_hash().expect("no type known for item!")) }
+fn type_id_of_val(item: Value) -> Result<TypeId, VmError> {
+    Ok(TypeId(item.type_hash()?))
 }
 
 fn format_type_id(item: &TypeId, buf: &mut String) -> fmt::Result {
     write!(buf, "{:?}", item.0)
 }
 
+/// A first-class description of the type of a value, as returned by
+/// `std::any::type_of`.
+///
+/// Types compare equal if they refer to the same type, and can be used as keys
+/// in maps and sets.
+#[derive(Any, Debug, Clone)]
+#[rune(module = "crate")]
+struct Type {
+    hash: Hash,
+    name: String,
+    fields: Option<Vec<String>>,
+}
+
+impl Type {
+    fn of(value: Value) -> Result<Self, VmError> {
+        let hash = value.type_hash()?;
+
+        let fields = match &value {
+            Value::Struct(st) => Some(st.borrow_ref()?.data().keys().cloned().collect()),
+            Value::Variant(variant) => match variant.borrow_ref()?.data() {
+                VariantData::Struct(data) => Some(data.keys().cloned().collect()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        Ok(Self {
+            hash,
+            name: value.into_type_name()?,
+            fields,
+        })
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// The names of the fields of the value the type was constructed from, if
+    /// it is a struct or a struct variant. Fields are sorted by name.
+    fn fields(&self) -> Option<Vec<String>> {
+        self.fields.clone()
+    }
+
+    /// Test if the given value is of this type.
+    fn is_instance(&self, value: Value) -> Result<bool, VmError> {
+        Ok(value.type_hash()? == self.hash)
+    }
+
+    fn eq(&self, other: Value) -> Result<bool, VmError> {
+        Ok(match other {
+            Value::Type(hash) => self.hash == hash,
+            Value::Any(any) => match any.downcast_borrow_ref::<Type>() {
+                Ok(other) => self.hash == other.hash,
+                Err(..) => false,
+            },
+            _ => false,
+        })
+    }
+
+    fn hash(&self) -> i64 {
+        use std::hash::{Hash as _, Hasher as _};
+
+        let mut hasher = DefaultHasher::new();
+        self.hash.hash(&mut hasher);
+        hasher.finish() as i64
+    }
+
+    fn string_display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.name)
+    }
+
+    fn string_debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "Type({})", self.name)
+    }
+}
+
 /// Construct the `std::any` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", ["any"]);
 
     module.function(["type_name_of_val"], Value::into_type_name)?;
+    module.function(["type_name"], Value::into_type_name)?;
+    module.function(["type_of"], Type::of)?;
+
+    module.ty::<Type>()?;
+    module.inst_fn("name", Type::name)?;
+    module.inst_fn("fields", Type::fields)?;
+    module.inst_fn("is_instance", Type::is_instance)?;
+    module.inst_fn(Protocol::EQ, Type::eq)?;
+    module.inst_fn(Protocol::HASH, Type::hash)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Type::string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Type::string_debug)?;
 
     module.ty::<TypeId>()?;
     module.function(["TypeId", "of_val"], type_id_of_val)?;
@@ -1,7 +1,8 @@
 //! `std::bytes` module.
 
-use crate::runtime::Bytes;
-use crate::{ContextError, Module};
+use crate::runtime::{charge_allocation, Bytes, Shared, VmError, VmErrorKind};
+use crate::{Any, ContextError, Module};
+use std::fmt;
 
 /// Construct the `std::bytes` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -9,21 +10,453 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.ty::<Bytes>()?;
     module.function(["Bytes", "new"], Bytes::new)?;
-    module.function(["Bytes", "with_capacity"], Bytes::with_capacity)?;
+    module.function(["Bytes", "with_capacity"], bytes_with_capacity)?;
     module.function(["Bytes", "from_vec"], Bytes::from_vec)?;
+    module.function(["Bytes", "from_hex"], from_hex)?;
+    module.function(["Bytes", "from_base64"], from_base64)?;
 
     module.inst_fn("into_vec", Bytes::into_vec)?;
-    module.inst_fn("extend", Bytes::extend)?;
-    module.inst_fn("extend_str", Bytes::extend_str)?;
+    module.inst_fn("extend", extend)?;
+    module.inst_fn("extend_str", extend_str)?;
     module.inst_fn("pop", Bytes::pop)?;
     module.inst_fn("last", Bytes::last)?;
 
     module.inst_fn("len", Bytes::len)?;
     module.inst_fn("capacity", Bytes::capacity)?;
     module.inst_fn("clear", Bytes::clear)?;
-    module.inst_fn("reserve", Bytes::reserve)?;
-    module.inst_fn("reserve_exact", Bytes::reserve_exact)?;
-    module.inst_fn("clone", Bytes::clone)?;
+    module.inst_fn("reserve", reserve)?;
+    module.inst_fn("reserve_exact", reserve_exact)?;
+    module.inst_fn("clone", clone)?;
     module.inst_fn("shrink_to_fit", Bytes::shrink_to_fit)?;
+
+    module.inst_fn("view", view)?;
+    module.inst_fn("get", get::<Bytes>)?;
+    module.inst_fn("to_hex", to_hex::<Bytes>)?;
+    module.inst_fn("to_base64", to_base64::<Bytes>)?;
+
+    module.ty::<BytesView>()?;
+    module.inst_fn("len", BytesView::len)?;
+    module.inst_fn("is_empty", BytesView::is_empty)?;
+    module.inst_fn("view", BytesView::view)?;
+    module.inst_fn("to_bytes", BytesView::to_bytes)?;
+    module.inst_fn("get", get::<BytesView>)?;
+    module.inst_fn("to_hex", to_hex::<BytesView>)?;
+    module.inst_fn("to_base64", to_base64::<BytesView>)?;
+    module.inst_fn(
+        crate::runtime::Protocol::STRING_DEBUG,
+        BytesView::string_debug,
+    )?;
+    install_readers(&mut module)?;
+
+    module.ty::<BytesMut>()?;
+    module.function(["BytesMut", "new"], BytesMut::new)?;
+    module.function(["BytesMut", "with_capacity"], BytesMut::with_capacity)?;
+    module.inst_fn("len", BytesMut::len)?;
+    module.inst_fn("is_empty", BytesMut::is_empty)?;
+    module.inst_fn("put_bytes", BytesMut::put_bytes)?;
+    module.inst_fn("put_str", BytesMut::put_str)?;
+    module.inst_fn("freeze", BytesMut::freeze)?;
+    install_writers(&mut module)?;
     Ok(module)
 }
+
+/// Something which can be read from as a slice of bytes.
+trait ByteSource {
+    /// Access the underlying bytes.
+    fn with_bytes<T, F>(&self, f: F) -> Result<T, VmError>
+    where
+        F: FnOnce(&[u8]) -> Result<T, VmError>;
+}
+
+impl ByteSource for Bytes {
+    fn with_bytes<T, F>(&self, f: F) -> Result<T, VmError>
+    where
+        F: FnOnce(&[u8]) -> Result<T, VmError>,
+    {
+        f(self)
+    }
+}
+
+/// A view into a range of a [Bytes] container.
+///
+/// The view shares the underlying container instead of copying it, so changes
+/// to the container are visible through the view. Reading through a view
+/// whose range is no longer valid because the container shrunk errors.
+#[derive(Any, Clone)]
+#[rune(module = "crate")]
+struct BytesView {
+    bytes: Shared<Bytes>,
+    start: usize,
+    end: usize,
+}
+
+impl BytesView {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Construct a view into a sub-range of this view.
+    fn view(&self, start: usize, end: usize) -> Result<Self, VmError> {
+        if start > end || end > self.len() {
+            return Err(VmError::from(VmErrorKind::OutOfRange {
+                index: end.into(),
+                len: self.len().into(),
+            }));
+        }
+
+        Ok(Self {
+            bytes: self.bytes.clone(),
+            start: self.start + start,
+            end: self.start + end,
+        })
+    }
+
+    /// Copy the viewed bytes into a new container.
+    fn to_bytes(&self) -> Result<Bytes, VmError> {
+        self.with_bytes(|bytes| Ok(Bytes::from_vec(bytes.to_vec())))
+    }
+
+    fn string_debug(&self, s: &mut String) -> Result<fmt::Result, VmError> {
+        use std::fmt::Write;
+        self.with_bytes(|bytes| Ok(write!(s, "{:?}", bytes)))
+    }
+}
+
+impl ByteSource for BytesView {
+    fn with_bytes<T, F>(&self, f: F) -> Result<T, VmError>
+    where
+        F: FnOnce(&[u8]) -> Result<T, VmError>,
+    {
+        let bytes = self.bytes.borrow_ref()?;
+
+        match bytes.get(self.start..self.end) {
+            Some(bytes) => f(bytes),
+            None => Err(VmError::from(VmErrorKind::OutOfRange {
+                index: self.end.into(),
+                len: bytes.len().into(),
+            })),
+        }
+    }
+}
+
+/// A builder for [Bytes], with helpers to write fixed-size integers.
+#[derive(Any, Default)]
+#[rune(module = "crate")]
+struct BytesMut {
+    bytes: Vec<u8>,
+}
+
+impl BytesMut {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_capacity(cap: usize) -> Result<Self, VmError> {
+        charge_allocation(cap)?;
+
+        Ok(Self {
+            bytes: Vec::with_capacity(cap),
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn put_bytes(&mut self, bytes: &Bytes) -> Result<(), VmError> {
+        charge_allocation(bytes.len())?;
+        self.bytes.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn put_str(&mut self, s: &str) -> Result<(), VmError> {
+        charge_allocation(s.len())?;
+        self.bytes.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+
+    /// Convert the builder into an immutable [Bytes] container.
+    fn freeze(self) -> Bytes {
+        Bytes::from_vec(self.bytes)
+    }
+}
+
+/// Construct a bytes container with the given capacity, accounting for the
+/// memory it takes up.
+fn bytes_with_capacity(cap: usize) -> Result<Bytes, VmError> {
+    charge_allocation(cap)?;
+    Ok(Bytes::with_capacity(cap))
+}
+
+/// Extend the container with other bytes, accounting for the memory they take
+/// up.
+fn extend(this: &mut Bytes, other: &Bytes) -> Result<(), VmError> {
+    charge_allocation(other.len())?;
+    this.extend(other);
+    Ok(())
+}
+
+/// Extend the container with the bytes of a string, accounting for the memory
+/// they take up.
+fn extend_str(this: &mut Bytes, s: &str) -> Result<(), VmError> {
+    charge_allocation(s.len())?;
+    this.extend_str(s);
+    Ok(())
+}
+
+/// Reserve capacity for at least `additional` more bytes, accounting for the
+/// memory it takes up.
+fn reserve(this: &mut Bytes, additional: usize) -> Result<(), VmError> {
+    charge_allocation(additional)?;
+    this.reserve(additional);
+    Ok(())
+}
+
+/// Reserve capacity for exactly `additional` more bytes, accounting for the
+/// memory it takes up.
+fn reserve_exact(this: &mut Bytes, additional: usize) -> Result<(), VmError> {
+    charge_allocation(additional)?;
+    this.reserve_exact(additional);
+    Ok(())
+}
+
+/// Clone the container, accounting for the memory taken up by the copy.
+fn clone(this: &Bytes) -> Result<Bytes, VmError> {
+    charge_allocation(this.len())?;
+    Ok(this.clone())
+}
+
+/// Construct a view into the given range of a bytes container.
+fn view(bytes: Shared<Bytes>, start: usize, end: usize) -> Result<BytesView, VmError> {
+    let len = bytes.borrow_ref()?.len();
+
+    if start > end || end > len {
+        return Err(VmError::from(VmErrorKind::OutOfRange {
+            index: end.into(),
+            len: len.into(),
+        }));
+    }
+
+    Ok(BytesView { bytes, start, end })
+}
+
+fn get<S>(source: &S, index: usize) -> Result<Option<u8>, VmError>
+where
+    S: ByteSource,
+{
+    source.with_bytes(|bytes| Ok(bytes.get(index).copied()))
+}
+
+/// Read an array of `N` bytes at the given offset.
+fn read_array<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N], VmError> {
+    let array = offset
+        .checked_add(N)
+        .and_then(|end| bytes.get(offset..end))
+        .and_then(|bytes| <[u8; N]>::try_from(bytes).ok());
+
+    match array {
+        Some(array) => Ok(array),
+        None => Err(VmError::from(VmErrorKind::OutOfRange {
+            index: offset.into(),
+            len: bytes.len().into(),
+        })),
+    }
+}
+
+/// Define functions reading fixed-size numbers out of a [ByteSource].
+///
+/// Unsigned 64-bit integers are reinterpreted as signed, since that is the only
+/// integer type available in Rune.
+macro_rules! readers {
+    ($($name:ident, $ty:ty, $from:ident, $out:ty;)*) => {
+        $(
+            fn $name<S>(source: &S, offset: usize) -> Result<$out, VmError>
+            where
+                S: ByteSource,
+            {
+                source.with_bytes(|bytes| Ok(<$ty>::$from(read_array(bytes, offset)?) as $out))
+            }
+        )*
+
+        fn install_readers(module: &mut Module) -> Result<(), ContextError> {
+            $(
+                module.inst_fn(stringify!($name), $name::<Bytes>)?;
+                module.inst_fn(stringify!($name), $name::<BytesView>)?;
+            )*
+            Ok(())
+        }
+    };
+}
+
+readers! {
+    read_u8, u8, from_le_bytes, i64;
+    read_i8, i8, from_le_bytes, i64;
+    read_u16_le, u16, from_le_bytes, i64;
+    read_u16_be, u16, from_be_bytes, i64;
+    read_i16_le, i16, from_le_bytes, i64;
+    read_i16_be, i16, from_be_bytes, i64;
+    read_u32_le, u32, from_le_bytes, i64;
+    read_u32_be, u32, from_be_bytes, i64;
+    read_i32_le, i32, from_le_bytes, i64;
+    read_i32_be, i32, from_be_bytes, i64;
+    read_u64_le, u64, from_le_bytes, i64;
+    read_u64_be, u64, from_be_bytes, i64;
+    read_i64_le, i64, from_le_bytes, i64;
+    read_i64_be, i64, from_be_bytes, i64;
+    read_f32_le, f32, from_le_bytes, f64;
+    read_f32_be, f32, from_be_bytes, f64;
+    read_f64_le, f64, from_le_bytes, f64;
+    read_f64_be, f64, from_be_bytes, f64;
+}
+
+/// Define functions writing fixed-size numbers to a [BytesMut].
+///
+/// Integers are truncated to the size being written.
+macro_rules! writers {
+    ($($name:ident, $in:ty, $ty:ty, $to:ident;)*) => {
+        impl BytesMut {
+            $(
+                fn $name(&mut self, value: $in) -> Result<(), VmError> {
+                    charge_allocation(std::mem::size_of::<$ty>())?;
+                    self.bytes.extend_from_slice(&(value as $ty).$to());
+                    Ok(())
+                }
+            )*
+        }
+
+        fn install_writers(module: &mut Module) -> Result<(), ContextError> {
+            $(module.inst_fn(stringify!($name), BytesMut::$name)?;)*
+            Ok(())
+        }
+    };
+}
+
+writers! {
+    put_u8, i64, u8, to_le_bytes;
+    put_u16_le, i64, u16, to_le_bytes;
+    put_u16_be, i64, u16, to_be_bytes;
+    put_u32_le, i64, u32, to_le_bytes;
+    put_u32_be, i64, u32, to_be_bytes;
+    put_u64_le, i64, u64, to_le_bytes;
+    put_u64_be, i64, u64, to_be_bytes;
+    put_f32_le, f64, f32, to_le_bytes;
+    put_f32_be, f64, f32, to_be_bytes;
+    put_f64_le, f64, f64, to_le_bytes;
+    put_f64_be, f64, f64, to_be_bytes;
+}
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as a lowercase hex string.
+fn to_hex<S>(source: &S) -> Result<String, VmError>
+where
+    S: ByteSource,
+{
+    source.with_bytes(|bytes| {
+        let mut out = String::with_capacity(bytes.len() * 2);
+
+        for &b in bytes {
+            out.push(HEX[(b >> 4) as usize] as char);
+            out.push(HEX[(b & 0xf) as usize] as char);
+        }
+
+        Ok(out)
+    })
+}
+
+/// Decode a hex string into bytes.
+fn from_hex(s: &str) -> Result<Bytes, String> {
+    fn digit(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let s = s.as_bytes();
+
+    if s.len() % 2 != 0 {
+        return Err(String::from("hex string must have an even length"));
+    }
+
+    let mut out = Vec::with_capacity(s.len() / 2);
+
+    for (n, pair) in s.chunks(2).enumerate() {
+        match (digit(pair[0]), digit(pair[1])) {
+            (Some(hi), Some(lo)) => out.push(hi << 4 | lo),
+            _ => return Err(format!("invalid hex digit at offset {}", n * 2)),
+        }
+    }
+
+    Ok(Bytes::from_vec(out))
+}
+
+/// Encode bytes using standard base64 with padding.
+fn to_base64<S>(source: &S) -> Result<String, VmError>
+where
+    S: ByteSource,
+{
+    source.with_bytes(|bytes| {
+        let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+
+        for chunk in bytes.chunks(3) {
+            let b = [
+                chunk[0],
+                chunk.get(1).copied().unwrap_or(0),
+                chunk.get(2).copied().unwrap_or(0),
+            ];
+
+            let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(BASE64[(n >> (18 - i * 6) & 0x3f) as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+
+        Ok(out)
+    })
+}
+
+/// Decode standard base64 into bytes. Padding is optional.
+fn from_base64(s: &str) -> Result<Bytes, String> {
+    let s = s.trim_end_matches('=').as_bytes();
+
+    if s.len() % 4 == 1 {
+        return Err(String::from("invalid base64 length"));
+    }
+
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+
+    for (n, chunk) in s.chunks(4).enumerate() {
+        let mut acc = 0u32;
+
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = match BASE64.iter().position(|&b| b == c) {
+                Some(value) => value as u32,
+                None => return Err(format!("invalid base64 character at offset {}", n * 4 + i)),
+            };
+
+            acc |= value << (18 - i * 6);
+        }
+
+        let decoded = [(acc >> 16) as u8, (acc >> 8) as u8, acc as u8];
+        out.extend_from_slice(&decoded[..chunk.len() - 1]);
+    }
+
+    Ok(Bytes::from_vec(out))
+}
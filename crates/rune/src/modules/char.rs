@@ -12,15 +12,27 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<ParseCharError>()?;
 
     module.function_meta(from_int)?;
+    module.function_meta(from_u32)?;
     module.function_meta(to_int)?;
     module.function_meta(is_alphabetic)?;
     module.function_meta(is_alphanumeric)?;
-    module.function(["is_control"], char::is_control)?;
-    module.function(["is_lowercase"], char::is_lowercase)?;
-    module.function(["is_numeric"], char::is_numeric)?;
-    module.function(["is_uppercase"], char::is_uppercase)?;
-    module.function(["is_whitespace"], char::is_whitespace)?;
-    module.function(["to_digit"], char::to_digit)?;
+    module.function_meta(is_control)?;
+    module.function_meta(is_lowercase)?;
+    module.function_meta(is_numeric)?;
+    module.function_meta(is_uppercase)?;
+    module.function_meta(is_whitespace)?;
+    module.function_meta(to_digit)?;
+    module.function_meta(to_lowercase)?;
+    module.function_meta(to_uppercase)?;
+    module.function_meta(is_ascii)?;
+    module.function_meta(is_ascii_alphabetic)?;
+    module.function_meta(is_ascii_alphanumeric)?;
+    module.function_meta(is_ascii_digit)?;
+    module.function_meta(is_ascii_punctuation)?;
+    module.function_meta(is_ascii_whitespace)?;
+    module.function_meta(to_ascii_lowercase)?;
+    module.function_meta(to_ascii_uppercase)?;
+    module.function_meta(eq_ignore_ascii_case)?;
     Ok(module)
 }
 
@@ -106,4 +118,263 @@ fn is_alphanumeric(c: char) -> bool {
     char::is_alphanumeric(c)
 }
 
+/// Returns `true` if this `char` has the `White_Space` property.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!(' '.is_whitespace());
+/// assert!('\n'.is_whitespace());
+/// assert!(!'a'.is_whitespace());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_whitespace(c: char) -> bool {
+    char::is_whitespace(c)
+}
+
+/// Returns `true` if this `char` has one of the general categories for
+/// numbers, which includes more than the ASCII digits.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('7'.is_numeric());
+/// assert!('¾'.is_numeric());
+/// assert!(!'K'.is_numeric());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_numeric(c: char) -> bool {
+    char::is_numeric(c)
+}
+
+/// Returns `true` if this `char` has the general category for control codes.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('\u{9c}'.is_control());
+/// assert!(!'q'.is_control());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_control(c: char) -> bool {
+    char::is_control(c)
+}
+
+/// Returns `true` if this `char` has the `Lowercase` property.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('a'.is_lowercase());
+/// assert!(!'A'.is_lowercase());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_lowercase(c: char) -> bool {
+    char::is_lowercase(c)
+}
+
+/// Returns `true` if this `char` has the `Uppercase` property.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('A'.is_uppercase());
+/// assert!(!'a'.is_uppercase());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_uppercase(c: char) -> bool {
+    char::is_uppercase(c)
+}
+
+/// Converts a `char` to a digit in the given radix, which must be in the
+/// range `2..=36`.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!('7'.to_digit(10), Some(7));
+/// assert_eq!('f'.to_digit(16), Some(15));
+/// assert_eq!('z'.to_digit(10), None);
+/// ```
+#[rune::function(instance)]
+fn to_digit(c: char, radix: u32) -> Result<Option<u32>, VmError> {
+    if !(2..=36).contains(&radix) {
+        return Err(VmError::panic(format!(
+            "radix must be in the range 2..=36, but was {}",
+            radix
+        )));
+    }
+
+    Ok(c.to_digit(radix))
+}
+
+/// Try to convert a unicode scalar value into a character, returning `None`
+/// for surrogates and values above `0x10FFFF`.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(char::from_u32(0x1F496), Some('💖'));
+/// assert_eq!(char::from_u32(0xD800), None);
+/// ```
+#[rune::function]
+fn from_u32(value: i64) -> Option<char> {
+    std::char::from_u32(u32::try_from(value).ok()?)
+}
+
+/// Converts a `char` to lowercase, which might produce several characters.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!('Q'.to_lowercase(), "q");
+/// ```
+#[rune::function(instance)]
+fn to_lowercase(c: char) -> String {
+    c.to_lowercase().collect()
+}
+
+/// Converts a `char` to uppercase, which might produce several characters.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!('ß'.to_uppercase(), "SS");
+/// ```
+#[rune::function(instance)]
+fn to_uppercase(c: char) -> String {
+    c.to_uppercase().collect()
+}
+
+/// Returns `true` if this `char` is within the ASCII range.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('a'.is_ascii());
+/// assert!(!'é'.is_ascii());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_ascii(c: char) -> bool {
+    c.is_ascii()
+}
+
+/// Returns `true` if this `char` is an ASCII letter.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('a'.is_ascii_alphabetic());
+/// assert!(!'é'.is_ascii_alphabetic());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_ascii_alphabetic(c: char) -> bool {
+    c.is_ascii_alphabetic()
+}
+
+/// Returns `true` if this `char` is an ASCII letter or digit.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('7'.is_ascii_alphanumeric());
+/// assert!(!'_'.is_ascii_alphanumeric());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_ascii_alphanumeric(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+}
+
+/// Returns `true` if this `char` is an ASCII decimal digit.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('7'.is_ascii_digit());
+/// assert!(!'٣'.is_ascii_digit());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_ascii_digit(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
+/// Returns `true` if this `char` is ASCII punctuation, like `!` or `{`.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('{'.is_ascii_punctuation());
+/// assert!(!'a'.is_ascii_punctuation());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_ascii_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation()
+}
+
+/// Returns `true` if this `char` is ASCII whitespace.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('\t'.is_ascii_whitespace());
+/// assert!(!'\u{a0}'.is_ascii_whitespace());
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn is_ascii_whitespace(c: char) -> bool {
+    c.is_ascii_whitespace()
+}
+
+/// Converts an ASCII letter to lowercase, leaving other characters unchanged.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!('A'.to_ascii_lowercase(), 'a');
+/// assert_eq!('Ä'.to_ascii_lowercase(), 'Ä');
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn to_ascii_lowercase(c: char) -> char {
+    c.to_ascii_lowercase()
+}
+
+/// Converts an ASCII letter to uppercase, leaving other characters unchanged.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!('a'.to_ascii_uppercase(), 'A');
+/// assert_eq!('ä'.to_ascii_uppercase(), 'ä');
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn to_ascii_uppercase(c: char) -> char {
+    c.to_ascii_uppercase()
+}
+
+/// Checks that two characters are the same, ignoring ASCII case.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!('a'.eq_ignore_ascii_case('A'));
+/// assert!(!'a'.eq_ignore_ascii_case('b'));
+/// ```
+#[rune::function(instance)]
+#[inline]
+fn eq_ignore_ascii_case(c: char, other: char) -> bool {
+    c.eq_ignore_ascii_case(&other)
+}
+
 crate::__internal_impl_any!(ParseCharError);
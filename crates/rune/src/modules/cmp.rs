@@ -1,12 +1,42 @@
 //! The `std::cmp` module.
 
+use crate::compile::Variant;
+use crate::runtime::Protocol;
 use crate::{ContextError, Module};
+use std::cmp::Ordering;
+use std::fmt;
 
 /// Construct the `std::cmp` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", ["cmp"]);
 
-    module.ty::<std::cmp::Ordering>()?;
+    module.protocol(Protocol::PARTIAL_CMP, 1)?;
+    module.protocol(Protocol::CMP, 1)?;
+
+    module.ty::<Ordering>()?;
+    module.enum_meta::<Ordering, 3>([
+        ("Less", Variant::unit()),
+        ("Equal", Variant::unit()),
+        ("Greater", Variant::unit()),
+    ])?;
+    module.variant_constructor(0, || Ordering::Less)?;
+    module.variant_constructor(1, || Ordering::Equal)?;
+    module.variant_constructor(2, || Ordering::Greater)?;
+    module.inst_fn(Protocol::IS_VARIANT, ordering_is_variant)?;
+    module.inst_fn(Protocol::EQ, |a: &Ordering, b: &Ordering| a == b)?;
+    module.inst_fn(Protocol::STRING_DEBUG, ordering_string_debug)?;
 
     Ok(module)
 }
+
+fn ordering_is_variant(ordering: &Ordering, index: usize) -> bool {
+    matches!(
+        (ordering, index),
+        (Ordering::Less, 0) | (Ordering::Equal, 1) | (Ordering::Greater, 2)
+    )
+}
+
+fn ordering_string_debug(ordering: &Ordering, s: &mut String) -> fmt::Result {
+    use std::fmt::Write;
+    write!(s, "{:?}", ordering)
+}
@@ -1,22 +1,43 @@
 //! `std::collections` module.
 
-use crate::runtime::{Iterator, IteratorTrait, Key, Protocol, Ref, Value, VmError, VmErrorKind};
+use crate::runtime::host::MapHasher;
+use crate::runtime::{
+    Function, Iterator, IteratorTrait, Key, Protocol, Range, RangeLimits, Ref, Shared, Value,
+    VmError, VmErrorKind,
+};
 use crate::{Any, ContextError, Module};
+use std::cmp::Ordering;
 use std::fmt;
+use std::ops::Bound;
 
 #[derive(Any, Clone)]
 #[rune(module = "crate")]
-struct HashMap {
-    map: crate::collections::HashMap<Key, Value>,
+pub(crate) struct HashMap {
+    map: crate::collections::HashMap<Key, Value, MapHasher>,
 }
 
 impl HashMap {
     fn new() -> Self {
         Self {
-            map: crate::collections::HashMap::new(),
+            map: crate::collections::HashMap::with_hasher(MapHasher::new()),
         }
     }
 
+    /// Construct a hashmap from an iterator of key-value pairs.
+    pub(crate) fn from_iterator(mut it: Iterator) -> Result<Self, VmError> {
+        use crate::runtime::FromValue;
+
+        let (cap, _) = it.size_hint();
+        let mut map = crate::collections::HashMap::with_capacity_and_hasher(cap, MapHasher::new());
+
+        while let Some(value) = it.next()? {
+            let (key, value) = <(Key, Value)>::from_value(value)?;
+            map.insert(key, value);
+        }
+
+        Ok(Self { map })
+    }
+
     /// Extend this hashmap from an iterator.
     #[inline]
     fn extend(&mut self, value: Value) -> Result<(), VmError> {
@@ -77,7 +98,7 @@ impl HashMap {
         let value = self.map.get(&key).ok_or_else(|| {
             VmError::from(VmErrorKind::MissingIndexKey {
                 target: Self::type_info(),
-                index: key,
+                index: format!("{:?}", key),
             })
         })?;
 
@@ -100,8 +121,8 @@ impl HashMap {
     }
 
     #[inline]
-    fn remove(&mut self, key: Key) {
-        self.map.remove(&key);
+    fn remove(&mut self, key: Key) -> Option<Value> {
+        self.map.remove(&key)
     }
 
     #[inline]
@@ -113,17 +134,29 @@ impl HashMap {
 
 #[derive(Any, Clone)]
 #[rune(module = "crate")]
-struct HashSet {
-    set: crate::collections::HashSet<Key>,
+pub(crate) struct HashSet {
+    set: crate::collections::HashSet<Key, MapHasher>,
 }
 
 impl HashSet {
     fn new() -> Self {
         Self {
-            set: crate::collections::HashSet::new(),
+            set: crate::collections::HashSet::with_hasher(MapHasher::new()),
         }
     }
 
+    /// Construct a set from an iterator of keys.
+    pub(crate) fn from_iterator(mut it: Iterator) -> Result<Self, VmError> {
+        let (cap, _) = it.size_hint();
+        let mut set = crate::collections::HashSet::with_capacity_and_hasher(cap, MapHasher::new());
+
+        while let Some(value) = it.next()? {
+            set.insert(Key::from_value(&value)?);
+        }
+
+        Ok(Self { set })
+    }
+
     /// Extend this set from an iterator.
     #[inline]
     fn extend(&mut self, value: Value) -> Result<(), VmError> {
@@ -169,8 +202,8 @@ impl HashSet {
     }
 
     #[inline]
-    fn remove(&mut self, key: Key) {
-        self.set.remove(&key);
+    fn remove(&mut self, key: Key) -> bool {
+        self.set.remove(&key)
     }
 
     #[inline]
@@ -308,6 +341,300 @@ impl IteratorTrait for Union {
     }
 }
 
+#[derive(Any, Clone, Default)]
+#[rune(module = "crate")]
+pub(crate) struct BTreeMap {
+    map: crate::collections::BTreeMap<Key, Value>,
+}
+
+impl BTreeMap {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct an ordered map from an iterator of key-value pairs.
+    pub(crate) fn from_iterator(mut it: Iterator) -> Result<Self, VmError> {
+        use crate::runtime::FromValue;
+
+        let mut map = crate::collections::BTreeMap::new();
+
+        while let Some(value) = it.next()? {
+            let (key, value) = <(Key, Value)>::from_value(value)?;
+            map.insert(key, value);
+        }
+
+        Ok(Self { map })
+    }
+
+    /// Extend this map from an iterator.
+    #[inline]
+    fn extend(&mut self, value: Value) -> Result<(), VmError> {
+        use crate::runtime::FromValue;
+
+        let mut it = value.into_iter()?;
+
+        while let Some(value) = it.next()? {
+            let (key, value) = <(Key, Value)>::from_value(value)?;
+            self.map.insert(key, value);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.map.clone().into_iter();
+        Iterator::from_double_ended("std::collections::btree_map::Iter", iter)
+    }
+
+    #[inline]
+    fn keys(&self) -> Iterator {
+        let iter = self.map.keys().cloned().collect::<Vec<_>>().into_iter();
+        Iterator::from_double_ended("std::collections::btree_map::Keys", iter)
+    }
+
+    #[inline]
+    fn values(&self) -> Iterator {
+        let iter = self.map.values().cloned().collect::<Vec<_>>().into_iter();
+        Iterator::from_double_ended("std::collections::btree_map::Values", iter)
+    }
+
+    /// Iterate over the entries whose keys are in the given range.
+    #[inline]
+    fn range(&self, range: Range) -> Result<Iterator, VmError> {
+        let iter = match range_bounds(&range)? {
+            Some(bounds) => self
+                .map
+                .range(bounds)
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+
+        Ok(Iterator::from_double_ended(
+            "std::collections::btree_map::Range",
+            iter.into_iter(),
+        ))
+    }
+
+    #[inline]
+    fn first(&self) -> Option<(Key, Value)> {
+        let (key, value) = self.map.iter().next()?;
+        Some((key.clone(), value.clone()))
+    }
+
+    #[inline]
+    fn last(&self) -> Option<(Key, Value)> {
+        let (key, value) = self.map.iter().next_back()?;
+        Some((key.clone(), value.clone()))
+    }
+
+    #[inline]
+    fn pop_first(&mut self) -> Option<(Key, Value)> {
+        let key = self.map.keys().next()?.clone();
+        let value = self.map.remove(&key)?;
+        Some((key, value))
+    }
+
+    #[inline]
+    fn pop_last(&mut self) -> Option<(Key, Value)> {
+        let key = self.map.keys().next_back()?.clone();
+        let value = self.map.remove(&key)?;
+        Some((key, value))
+    }
+
+    #[inline]
+    fn contains_key(&self, key: Key) -> bool {
+        self.map.contains_key(&key)
+    }
+
+    #[inline]
+    fn index_set(&mut self, key: Key, value: Value) {
+        let _ = self.map.insert(key, value);
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Key, value: Value) -> Option<Value> {
+        self.map.insert(key, value)
+    }
+
+    #[inline]
+    fn get(&self, key: Key) -> Option<Value> {
+        self.map.get(&key).cloned()
+    }
+
+    #[inline]
+    fn index_get(&self, key: Key) -> Result<Value, VmError> {
+        use crate::runtime::TypeOf;
+
+        let value = self.map.get(&key).ok_or_else(|| {
+            VmError::from(VmErrorKind::MissingIndexKey {
+                target: Self::type_info(),
+                index: format!("{:?}", key),
+            })
+        })?;
+
+        Ok(value.clone())
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Key) -> Option<Value> {
+        self.map.remove(&key)
+    }
+
+    #[inline]
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write;
+        write!(s, "{:?}", self.map)
+    }
+}
+
+#[derive(Any, Clone, Default)]
+#[rune(module = "crate")]
+pub(crate) struct BTreeSet {
+    set: crate::collections::BTreeSet<Key>,
+}
+
+impl BTreeSet {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct an ordered set from an iterator of keys.
+    pub(crate) fn from_iterator(mut it: Iterator) -> Result<Self, VmError> {
+        let mut set = crate::collections::BTreeSet::new();
+
+        while let Some(value) = it.next()? {
+            set.insert(Key::from_value(&value)?);
+        }
+
+        Ok(Self { set })
+    }
+
+    /// Extend this set from an iterator.
+    #[inline]
+    fn extend(&mut self, value: Value) -> Result<(), VmError> {
+        let mut it = value.into_iter()?;
+
+        while let Some(value) = it.next()? {
+            self.set.insert(Key::from_value(&value)?);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.set.clone().into_iter();
+        Iterator::from_double_ended("std::collections::btree_set::Iter", iter)
+    }
+
+    /// Iterate over the keys in the given range.
+    #[inline]
+    fn range(&self, range: Range) -> Result<Iterator, VmError> {
+        let iter = match range_bounds(&range)? {
+            Some(bounds) => self.set.range(bounds).cloned().collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+
+        Ok(Iterator::from_double_ended(
+            "std::collections::btree_set::Range",
+            iter.into_iter(),
+        ))
+    }
+
+    #[inline]
+    fn first(&self) -> Option<Key> {
+        self.set.iter().next().cloned()
+    }
+
+    #[inline]
+    fn last(&self) -> Option<Key> {
+        self.set.iter().next_back().cloned()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Key) -> bool {
+        self.set.insert(key)
+    }
+
+    #[inline]
+    fn contains(&self, key: Key) -> bool {
+        self.set.contains(&key)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.set.clear()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Key) -> bool {
+        self.set.remove(&key)
+    }
+
+    #[inline]
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write;
+        write!(s, "{:?}", self.set)
+    }
+
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.set == other.set
+    }
+}
+
+/// Convert a range into bounds over keys.
+///
+/// Returns `None` if the range is trivially empty, since ordered collections
+/// panic when queried with a range which starts after it ends.
+fn range_bounds(range: &Range) -> Result<Option<(Bound<Key>, Bound<Key>)>, VmError> {
+    let start = match &range.start {
+        Some(start) => Bound::Included(Key::from_value(start)?),
+        None => Bound::Unbounded,
+    };
+
+    let end = match (&range.end, range.limits) {
+        (Some(end), RangeLimits::HalfOpen) => Bound::Excluded(Key::from_value(end)?),
+        (Some(end), RangeLimits::Closed) => Bound::Included(Key::from_value(end)?),
+        (None, _) => Bound::Unbounded,
+    };
+
+    if let (Bound::Included(start), Bound::Included(end) | Bound::Excluded(end)) = (&start, &end) {
+        if start > end {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some((start, end)))
+}
+
 #[derive(Any, Clone, Default)]
 #[rune(module = "crate")]
 struct VecDeque {
@@ -342,7 +669,7 @@ impl VecDeque {
     }
 
     fn rotate_right(&mut self, mid: usize) {
-        self.inner.rotate_left(mid);
+        self.inner.rotate_right(mid);
     }
 
     fn push_front(&mut self, v: Value) {
@@ -369,12 +696,28 @@ impl VecDeque {
         self.inner.reserve(index);
     }
 
-    fn len(&mut self) -> usize {
+    fn len(&self) -> usize {
         self.inner.len()
     }
 
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    fn front(&self) -> Option<Value> {
+        self.inner.front().cloned()
+    }
+
+    fn back(&self) -> Option<Value> {
+        self.inner.back().cloned()
+    }
+
     fn get(&self, index: usize) -> Result<Value, VmError> {
-        if index > self.inner.len() {
+        if index >= self.inner.len() {
             return Err(VmError::from(VmErrorKind::OutOfRange {
                 index: index.into(),
                 len: self.inner.len().into(),
@@ -384,7 +727,7 @@ impl VecDeque {
     }
 
     fn set(&mut self, index: usize, value: Value) -> Result<(), VmError> {
-        if index > self.inner.len() {
+        if index >= self.inner.len() {
             return Err(VmError::from(VmErrorKind::OutOfRange {
                 index: index.into(),
                 len: self.inner.len().into(),
@@ -401,7 +744,7 @@ impl VecDeque {
     #[inline]
     fn iter(&self) -> Iterator {
         let iter = self.inner.clone().into_iter();
-        Iterator::from("std::collections::VecDeque::Iter", iter)
+        Iterator::from_double_ended("std::collections::VecDeque::Iter", iter)
     }
 
     #[inline]
@@ -411,6 +754,155 @@ impl VecDeque {
     }
 }
 
+/// A priority queue implemented with a binary heap.
+///
+/// This is a max-heap, so the greatest value according to [Value::cmp] is
+/// popped first. A custom comparator can be provided through
+/// `BinaryHeap::with_comparator`, which is called with two values and is
+/// expected to return an `Ordering`.
+#[derive(Any, Clone, Default)]
+#[rune(module = "crate")]
+struct BinaryHeap {
+    data: Vec<Value>,
+    comparator: Option<Shared<Function>>,
+}
+
+impl BinaryHeap {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_comparator(comparator: Value) -> Result<Self, VmError> {
+        Ok(Self {
+            data: Vec::new(),
+            comparator: Some(comparator.into_function()?),
+        })
+    }
+
+    /// Extend this heap with something that implements the into_iter
+    /// protocol.
+    fn extend(&mut self, value: Value) -> Result<(), VmError> {
+        let mut it = value.into_iter()?;
+
+        while let Some(value) = it.next()? {
+            self.push(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn push(&mut self, value: Value) -> Result<(), VmError> {
+        self.data.push(value);
+        self.sift_up(self.data.len() - 1)
+    }
+
+    fn pop(&mut self) -> Result<Option<Value>, VmError> {
+        let mut value = match self.data.pop() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        if !self.data.is_empty() {
+            std::mem::swap(&mut value, &mut self.data[0]);
+            self.sift_down(0)?;
+        }
+
+        Ok(Some(value))
+    }
+
+    fn peek(&self) -> Option<Value> {
+        self.data.first().cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Iterate over the heap in arbitrary order.
+    #[inline]
+    fn iter(&self) -> Iterator {
+        let iter = self.data.clone().into_iter();
+        Iterator::from_double_ended("std::collections::BinaryHeap::Iter", iter)
+    }
+
+    /// Consume the heap into a vector sorted in ascending order.
+    fn into_sorted_vec(mut self) -> Result<crate::runtime::Vec, VmError> {
+        let mut out = Vec::with_capacity(self.data.len());
+
+        while let Some(value) = self.pop()? {
+            out.push(value);
+        }
+
+        out.reverse();
+        Ok(crate::runtime::Vec::from(out))
+    }
+
+    #[inline]
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write;
+        write!(s, "{:?}", self.data)
+    }
+
+    fn compare(&self, a: &Value, b: &Value) -> Result<Ordering, VmError> {
+        match &self.comparator {
+            Some(comparator) => comparator
+                .borrow_ref()?
+                .call::<_, Ordering>((a.clone(), b.clone())),
+            None => Value::cmp(a, b),
+        }
+    }
+
+    fn sift_up(&mut self, mut pos: usize) -> Result<(), VmError> {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+
+            if self.compare(&self.data[pos], &self.data[parent])? != Ordering::Greater {
+                break;
+            }
+
+            self.data.swap(pos, parent);
+            pos = parent;
+        }
+
+        Ok(())
+    }
+
+    fn sift_down(&mut self, mut pos: usize) -> Result<(), VmError> {
+        let len = self.data.len();
+
+        loop {
+            let mut child = 2 * pos + 1;
+
+            if child >= len {
+                break;
+            }
+
+            if child + 1 < len
+                && self.compare(&self.data[child + 1], &self.data[child])? == Ordering::Greater
+            {
+                child += 1;
+            }
+
+            if self.compare(&self.data[child], &self.data[pos])? != Ordering::Greater {
+                break;
+            }
+
+            self.data.swap(pos, child);
+            pos = child;
+        }
+
+        Ok(())
+    }
+}
+
 /// The `std::collections` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", ["collections"]);
@@ -453,13 +945,61 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn(Protocol::STRING_DEBUG, HashSet::string_debug)?;
     module.inst_fn(Protocol::EQ, HashSet::eq)?;
 
+    module.ty::<BTreeMap>()?;
+    module.function(["BTreeMap", "new"], BTreeMap::new)?;
+    module.function(["BTreeMap", "from"], btreemap_from)?;
+    module.inst_fn("clear", BTreeMap::clear)?;
+    module.inst_fn("clone", BTreeMap::clone)?;
+    module.inst_fn("contains_key", BTreeMap::contains_key)?;
+    module.inst_fn("extend", BTreeMap::extend)?;
+    module.inst_fn("first", BTreeMap::first)?;
+    module.inst_fn("get", BTreeMap::get)?;
+    module.inst_fn("insert", BTreeMap::insert)?;
+    module.inst_fn("is_empty", BTreeMap::is_empty)?;
+    module.inst_fn("iter", BTreeMap::iter)?;
+    module.inst_fn("keys", BTreeMap::keys)?;
+    module.inst_fn("last", BTreeMap::last)?;
+    module.inst_fn("len", BTreeMap::len)?;
+    module.inst_fn("pop_first", BTreeMap::pop_first)?;
+    module.inst_fn("pop_last", BTreeMap::pop_last)?;
+    module.inst_fn("range", BTreeMap::range)?;
+    module.inst_fn("remove", BTreeMap::remove)?;
+    module.inst_fn("values", BTreeMap::values)?;
+    module.inst_fn(Protocol::INTO_ITER, BTreeMap::iter)?;
+    module.inst_fn(Protocol::INDEX_SET, BTreeMap::index_set)?;
+    module.inst_fn(Protocol::INDEX_GET, BTreeMap::index_get)?;
+    module.inst_fn(Protocol::STRING_DEBUG, BTreeMap::string_debug)?;
+
+    module.ty::<BTreeSet>()?;
+    module.function(["BTreeSet", "new"], BTreeSet::new)?;
+    module.function(["BTreeSet", "from"], btreeset_from)?;
+    module.inst_fn("clear", BTreeSet::clear)?;
+    module.inst_fn("clone", BTreeSet::clone)?;
+    module.inst_fn("contains", BTreeSet::contains)?;
+    module.inst_fn("extend", BTreeSet::extend)?;
+    module.inst_fn("first", BTreeSet::first)?;
+    module.inst_fn("insert", BTreeSet::insert)?;
+    module.inst_fn("is_empty", BTreeSet::is_empty)?;
+    module.inst_fn("iter", BTreeSet::iter)?;
+    module.inst_fn("last", BTreeSet::last)?;
+    module.inst_fn("len", BTreeSet::len)?;
+    module.inst_fn("range", BTreeSet::range)?;
+    module.inst_fn("remove", BTreeSet::remove)?;
+    module.inst_fn(Protocol::INTO_ITER, BTreeSet::iter)?;
+    module.inst_fn(Protocol::STRING_DEBUG, BTreeSet::string_debug)?;
+    module.inst_fn(Protocol::EQ, BTreeSet::eq)?;
+
     module.ty::<VecDeque>()?;
     module.function(["VecDeque", "new"], VecDeque::new)?;
     module.function(["VecDeque", "with_capacity"], VecDeque::with_capacity)?;
     module.function(["VecDeque", "from"], vecdeque_from)?;
 
+    module.inst_fn("back", VecDeque::back)?;
+    module.inst_fn("clear", VecDeque::clear)?;
     module.inst_fn("extend", VecDeque::extend)?;
+    module.inst_fn("front", VecDeque::front)?;
     module.inst_fn("insert", VecDeque::insert)?;
+    module.inst_fn("is_empty", VecDeque::is_empty)?;
     module.inst_fn("iter", VecDeque::iter)?;
     module.inst_fn("len", VecDeque::len)?;
     module.inst_fn("pop_back", VecDeque::pop_back)?;
@@ -475,21 +1015,39 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn(Protocol::INTO_ITER, VecDeque::iter)?;
     module.inst_fn(Protocol::STRING_DEBUG, VecDeque::string_debug)?;
 
+    module.ty::<BinaryHeap>()?;
+    module.function(["BinaryHeap", "new"], BinaryHeap::new)?;
+    module.function(
+        ["BinaryHeap", "with_comparator"],
+        BinaryHeap::with_comparator,
+    )?;
+    module.function(["BinaryHeap", "from"], binaryheap_from)?;
+    module.inst_fn("clear", BinaryHeap::clear)?;
+    module.inst_fn("clone", BinaryHeap::clone)?;
+    module.inst_fn("extend", BinaryHeap::extend)?;
+    module.inst_fn("into_sorted_vec", BinaryHeap::into_sorted_vec)?;
+    module.inst_fn("is_empty", BinaryHeap::is_empty)?;
+    module.inst_fn("iter", BinaryHeap::iter)?;
+    module.inst_fn("len", BinaryHeap::len)?;
+    module.inst_fn("peek", BinaryHeap::peek)?;
+    module.inst_fn("pop", BinaryHeap::pop)?;
+    module.inst_fn("push", BinaryHeap::push)?;
+    module.inst_fn(Protocol::INTO_ITER, BinaryHeap::iter)?;
+    module.inst_fn(Protocol::STRING_DEBUG, BinaryHeap::string_debug)?;
+
     Ok(module)
 }
 
 fn hashmap_from(value: Value) -> Result<HashMap, VmError> {
-    use crate::runtime::FromValue;
-
-    let mut map = HashMap::new();
-    let mut it = value.into_iter()?;
+    HashMap::from_iterator(value.into_iter()?)
+}
 
-    while let Some(value) = it.next()? {
-        let (key, value) = <(Key, Value)>::from_value(value)?;
-        map.insert(key, value);
-    }
+fn btreemap_from(value: Value) -> Result<BTreeMap, VmError> {
+    BTreeMap::from_iterator(value.into_iter()?)
+}
 
-    Ok(map)
+fn btreeset_from(value: Value) -> Result<BTreeSet, VmError> {
+    BTreeSet::from_iterator(value.into_iter()?)
 }
 
 fn vecdeque_from(value: Value) -> Result<VecDeque, VmError> {
@@ -503,13 +1061,12 @@ fn vecdeque_from(value: Value) -> Result<VecDeque, VmError> {
     Ok(cont)
 }
 
-fn hashset_from(value: Value) -> Result<HashSet, VmError> {
-    let mut set = HashSet::new();
-    let mut it = value.into_iter()?;
-
-    while let Some(value) = it.next()? {
-        set.insert(Key::from_value(&value)?);
-    }
+fn binaryheap_from(value: Value) -> Result<BinaryHeap, VmError> {
+    let mut heap = BinaryHeap::new();
+    heap.extend(value)?;
+    Ok(heap)
+}
 
-    Ok(set)
+fn hashset_from(value: Value) -> Result<HashSet, VmError> {
+    HashSet::from_iterator(value.into_iter()?)
 }
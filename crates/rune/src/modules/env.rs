@@ -0,0 +1,16 @@
+//! The `std::env` module.
+
+use crate::runtime::{ambient, Value, VmError};
+use crate::{ContextError, Module};
+
+/// Construct the `std::env` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["env"]);
+    module.function(["ambient"], ambient)?;
+    Ok(module)
+}
+
+/// Get the ambient value provided by the host under the given name.
+fn ambient(name: &str) -> Result<Option<Value>, VmError> {
+    ambient::get(name)
+}
@@ -1,6 +1,7 @@
 //! The `std::float` module.
 
 use crate::{ContextError, Module};
+use std::cmp::Ordering;
 use std::num::ParseFloatError;
 
 /// Parse an integer.
@@ -8,11 +9,29 @@ fn parse(s: &str) -> Result<f64, ParseFloatError> {
     str::parse::<f64>(s)
 }
 
+/// Format a float with exactly the given number of digits after the decimal
+/// point.
+fn to_fixed(value: f64, digits: usize) -> String {
+    format!("{:.*}", digits, value)
+}
+
 /// Convert a float to a whole number.
 fn to_integer(value: f64) -> i64 {
     value as i64
 }
 
+/// Compare two floats, where NaN is equal to itself and sorts after every
+/// other value.
+fn cmp(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+/// Compare two floats, or `None` if either of them is NaN.
+fn partial_cmp(a: f64, b: f64) -> Option<Ordering> {
+    a.partial_cmp(&b)
+}
+
 crate::__internal_impl_any!(ParseFloatError);
 
 /// Install the core package into the given functions namespace.
@@ -26,8 +45,11 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("abs", f64::abs)?;
     module.inst_fn("powf", f64::powf)?;
     module.inst_fn("powi", f64::powi)?;
+    module.inst_fn("cmp", cmp)?;
+    module.inst_fn("partial_cmp", partial_cmp)?;
 
     module.inst_fn("to_integer", to_integer)?;
+    module.inst_fn("to_fixed", to_fixed)?;
 
     Ok(module)
 }
@@ -2,10 +2,14 @@
 
 use std::fmt;
 use std::fmt::Write;
+use std::iter::Peekable;
+use std::num::NonZeroUsize;
+use std::str::Chars;
 
 use crate::macros::{FormatArgs, MacroContext, TokenStream};
 use crate::parse::Parser;
-use crate::runtime::{Format, Protocol};
+use crate::runtime::format::{Alignment, Flag, Flags, FormatSpec, Type};
+use crate::runtime::{EnvProtocolCaller, Format, FromValue, Protocol, Stack, Value, VmError};
 use crate::{ContextError, Module};
 
 /// Construct the `std::fmt` module.
@@ -14,6 +18,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<std::fmt::Error>()?;
     module.inst_fn(Protocol::STRING_DISPLAY, format_fmt_error)?;
     module.macro_(["format"], format_macro)?;
+    module.raw_fn(["format"], format_impl)?;
 
     module.ty::<Format>()?;
     Ok(module)
@@ -34,3 +39,225 @@ pub(crate) fn format_macro(
     let expanded = args.expand(ctx)?;
     Ok(expanded.into_token_stream(ctx))
 }
+
+/// Implementation for `std::fmt::format(spec, args...)`, which formats its
+/// arguments according to a format string only known at runtime.
+fn format_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    if args == 0 {
+        return Err(VmError::panic("`format` requires a format string"));
+    }
+
+    let mut values = stack.drain(args)?;
+    let spec = values.next().expect("checked argument count");
+    let values = values.collect::<Vec<_>>();
+
+    let string = match spec {
+        Value::String(spec) => format_values(&spec.borrow_ref()?, &values)?,
+        Value::StaticString(spec) => format_values(spec.as_str(), &values)?,
+        actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+    };
+
+    stack.push(Value::from(string));
+    Ok(())
+}
+
+/// Format `args` according to the format string `input`, which supports the
+/// same syntax as the `format!` macro.
+///
+/// Arguments are referenced by position, either implicitly with `{}` or
+/// explicitly with `{1}`. Named arguments like `{name}` are looked up in the
+/// last argument, which must then be an object.
+pub(crate) fn format_values(input: &str, args: &[Value]) -> Result<String, VmError> {
+    let mut out = String::new();
+    let mut buf = String::new();
+    let mut chars = input.chars().peekable();
+    let mut count = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '}' => {
+                return Err(VmError::panic(
+                    "unsupported close `}`, if you meant to escape this use `}}`",
+                ));
+            }
+            '{' => {
+                let (name, spec) = parse_group(&mut chars, args, &mut count)?;
+
+                let value = match name {
+                    Some(Ok(index)) => positional(args, index)?,
+                    Some(Err(name)) => named(args, &name)?,
+                    None => {
+                        let value = positional(args, count)?;
+                        count += 1;
+                        value
+                    }
+                };
+
+                buf.clear();
+                spec.format(&value, &mut out, &mut buf, EnvProtocolCaller)?;
+            }
+            c => out.push(c),
+        }
+    }
+
+    Ok(out)
+}
+
+/// Parse a single `{...}` group, after its opening brace, into the argument
+/// it refers to and its specification.
+#[allow(clippy::type_complexity)]
+fn parse_group(
+    chars: &mut Peekable<Chars<'_>>,
+    args: &[Value],
+    count: &mut usize,
+) -> Result<(Option<Result<usize, String>>, FormatSpec), VmError> {
+    let mut name = String::new();
+
+    loop {
+        match chars.peek() {
+            Some(':' | '}') => break,
+            Some(&c) => {
+                name.push(c);
+                chars.next();
+            }
+            None => return Err(VmError::panic("unexpected end of format string")),
+        }
+    }
+
+    let name = if name.is_empty() {
+        None
+    } else {
+        Some(str::parse::<usize>(&name).map_err(|_| name))
+    };
+
+    let mut flags = Flags::default();
+    let mut fill = ' ';
+    let mut align = Alignment::Left;
+    let mut width = None;
+    let mut precision = None;
+    let mut format_type = Type::Display;
+
+    if chars.next_if_eq(&':').is_some() {
+        let mut lookahead = chars.clone();
+
+        match (lookahead.next(), lookahead.next()) {
+            (Some(a @ ('<' | '^' | '>')), _) => {
+                align = parse_align(a);
+                chars.next();
+            }
+            (Some(f), Some(a @ ('<' | '^' | '>'))) => {
+                fill = f;
+                align = parse_align(a);
+                chars.next();
+                chars.next();
+            }
+            _ => (),
+        }
+
+        if chars.next_if_eq(&'+').is_some() {
+            flags.set(Flag::SignPlus);
+        } else if chars.next_if_eq(&'-').is_some() {
+            flags.set(Flag::SignMinus);
+        }
+
+        if chars.next_if_eq(&'#').is_some() {
+            flags.set(Flag::Alternate);
+        }
+
+        if chars.next_if_eq(&'0').is_some() {
+            flags.set(Flag::SignAwareZeroPad);
+        }
+
+        width = parse_number(chars);
+
+        if chars.next_if_eq(&'.').is_some() {
+            precision = if chars.next_if_eq(&'*').is_some() {
+                let value = positional(args, *count)?;
+                *count += 1;
+                Some(<usize as FromValue>::from_value(value)?)
+            } else {
+                parse_number(chars)
+            };
+        }
+
+        format_type = match chars.peek() {
+            Some('?') => Type::Debug,
+            Some('x') => Type::LowerHex,
+            Some('X') => Type::UpperHex,
+            Some('b') => Type::Binary,
+            Some('p') => Type::Pointer,
+            _ => Type::Display,
+        };
+
+        if format_type != Type::Display {
+            chars.next();
+        }
+    }
+
+    match chars.next() {
+        Some('}') => (),
+        Some(c) => {
+            return Err(VmError::panic(format!("unsupported char `{}` in spec", c)));
+        }
+        None => return Err(VmError::panic("unexpected end of format string")),
+    }
+
+    let spec = FormatSpec::new(
+        flags,
+        fill,
+        align,
+        width.and_then(NonZeroUsize::new),
+        precision.and_then(NonZeroUsize::new),
+        format_type,
+    );
+
+    Ok((name, spec))
+}
+
+fn parse_number(chars: &mut Peekable<Chars<'_>>) -> Option<usize> {
+    let mut number = None::<usize>;
+
+    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+        chars.next();
+        let n = number.unwrap_or_default();
+        number = Some(n.saturating_mul(10).saturating_add(digit as usize));
+    }
+
+    number
+}
+
+fn parse_align(c: char) -> Alignment {
+    match c {
+        '<' => Alignment::Left,
+        '^' => Alignment::Center,
+        _ => Alignment::Right,
+    }
+}
+
+fn positional(args: &[Value], index: usize) -> Result<Value, VmError> {
+    match args.get(index) {
+        Some(value) => Ok(value.clone()),
+        None => Err(VmError::panic(format!(
+            "missing positional argument #{}",
+            index
+        ))),
+    }
+}
+
+fn named(args: &[Value], name: &str) -> Result<Value, VmError> {
+    if let Some(Value::Object(object)) = args.last() {
+        if let Some(value) = object.borrow_ref()?.get(name) {
+            return Ok(value.clone());
+        }
+    }
+
+    Err(VmError::panic(format!("missing named argument `{}`", name)))
+}
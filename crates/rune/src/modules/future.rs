@@ -1,7 +1,9 @@
 //! The `std::future` module.
 
+use crate::modules::time::{Duration, Elapsed};
+use crate::runtime::executor;
 use crate::runtime::future::SelectFuture;
-use crate::runtime::{Future, Shared, Stack, Value, VmError, VmErrorKind};
+use crate::runtime::{Function, Future, Mut, Select, Shared, Stack, Value, VmError, VmErrorKind};
 use crate::{ContextError, Module};
 
 /// Construct the `std::future` module.
@@ -9,6 +11,9 @@ pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", ["future"]);
     module.ty::<Future>()?;
     module.raw_fn(["join"], raw_join)?;
+    module.function(["select_all"], select_all)?;
+    module.function(["spawn"], spawn)?;
+    module.inst_fn("timeout", timeout)?;
     Ok(module)
 }
 
@@ -54,6 +59,48 @@ async fn join(value: Value) -> Result<Value, VmError> {
     }
 }
 
+fn select_all_impl<'a, I>(values: I) -> Result<Select, VmError>
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    let futures = futures_util::stream::FuturesUnordered::new();
+
+    for (index, value) in values.into_iter().enumerate() {
+        let future = match value {
+            Value::Future(future) => future.clone().into_mut()?,
+            value => return Err(VmError::bad_argument::<Future>(index, value)?),
+        };
+
+        if !future.is_completed() {
+            futures.push(SelectFuture::new(index, future));
+        }
+    }
+
+    Ok(Select::new(futures))
+}
+
+/// Wait for the first of a collection of futures to complete, resolving to
+/// `Some((index, value))`, or `None` if every future has already completed.
+///
+/// Futures which don't complete are left untouched, so the same collection can
+/// be passed in again to wait for the next one.
+fn select_all(value: Value) -> Result<Future, VmError> {
+    let select = match value {
+        Value::Tuple(tuple) => select_all_impl(tuple.borrow_ref()?.iter())?,
+        Value::Vec(vec) => select_all_impl(vec.borrow_ref()?.iter())?,
+        value => return Err(VmError::bad_argument::<Vec<Value>>(0, &value)?),
+    };
+
+    Ok(Future::new(async move {
+        if select.is_empty() {
+            return Ok(None);
+        }
+
+        let (index, value) = select.await?;
+        Ok(Some((index, value)))
+    }))
+}
+
 /// The join implementation.
 fn raw_join(stack: &mut Stack, args: usize) -> Result<(), VmError> {
     if args != 1 {
@@ -68,3 +115,24 @@ fn raw_join(stack: &mut Stack, args: usize) -> Result<(), VmError> {
     stack.push(value);
     Ok(())
 }
+
+/// Spawn the given function on the executor of the context, returning a future
+/// which resolves to the value it returns.
+fn spawn(function: &Function) -> Result<Future, VmError> {
+    executor::spawn(function.to_sync()?)
+}
+
+/// Wait for the future to complete within the given duration, resolving to
+/// `Ok(value)` if it does and `Err(Elapsed)` otherwise.
+///
+/// A future which times out isn't completed, so it can still be awaited.
+fn timeout(mut future: Mut<Future>, duration: &Duration) -> Result<Future, VmError> {
+    let sleep = executor::sleep(duration.as_inner())?;
+
+    Ok(Future::new(async move {
+        match futures_util::future::select(&mut *future, sleep).await {
+            futures_util::future::Either::Left((value, _)) => Ok(Ok(value?)),
+            futures_util::future::Either::Right(((), _)) => Ok(Err(Elapsed::new())),
+        }
+    }))
+}
@@ -0,0 +1,600 @@
+//! The `std::collections::im` module.
+//!
+//! Persistent collections which never modify a value in place. Every update
+//! returns a new version of the collection which shares most of its structure
+//! with the version it was derived from, so keeping many versions around is
+//! cheap.
+
+use crate::runtime::{FromValue, Iterator, Key, Protocol, Value, VmError, VmErrorKind};
+use crate::{Any, ContextError, Module};
+use std::cmp::Ordering;
+use std::fmt;
+use std::rc::Rc;
+
+/// Construct the `std::collections::im` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["collections", "im"]);
+
+    module.ty::<Vector>()?;
+    module.function(["Vector", "new"], Vector::new)?;
+    module.function(["Vector", "from"], Vector::from_value)?;
+    module.inst_fn("get", Vector::get)?;
+    module.inst_fn("is_empty", Vector::is_empty)?;
+    module.inst_fn("iter", Vector::iter)?;
+    module.inst_fn("last", Vector::last)?;
+    module.inst_fn("len", Vector::len)?;
+    module.inst_fn("pop", Vector::pop)?;
+    module.inst_fn("push", Vector::push)?;
+    module.inst_fn("set", Vector::set)?;
+    module.inst_fn(Protocol::INDEX_GET, Vector::index_get)?;
+    module.inst_fn(Protocol::INTO_ITER, Vector::iter)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Vector::string_debug)?;
+
+    module.ty::<OrdMap>()?;
+    module.function(["OrdMap", "new"], OrdMap::new)?;
+    module.function(["OrdMap", "from"], OrdMap::from_value)?;
+    module.inst_fn("contains_key", OrdMap::contains_key)?;
+    module.inst_fn("get", OrdMap::get)?;
+    module.inst_fn("insert", OrdMap::insert)?;
+    module.inst_fn("is_empty", OrdMap::is_empty)?;
+    module.inst_fn("iter", OrdMap::iter)?;
+    module.inst_fn("keys", OrdMap::keys)?;
+    module.inst_fn("len", OrdMap::len)?;
+    module.inst_fn("remove", OrdMap::remove)?;
+    module.inst_fn("values", OrdMap::values)?;
+    module.inst_fn(Protocol::INDEX_GET, OrdMap::index_get)?;
+    module.inst_fn(Protocol::INTO_ITER, OrdMap::iter)?;
+    module.inst_fn(Protocol::STRING_DEBUG, OrdMap::string_debug)?;
+    Ok(module)
+}
+
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+/// A node in the trie backing a [Vector].
+#[derive(Clone)]
+enum Node {
+    Branch(Rc<Vec<Node>>),
+    Leaf(Rc<Vec<Value>>),
+}
+
+impl Node {
+    /// Construct a path of nodes down to a leaf containing only `value`.
+    fn path(level: usize, value: Value) -> Self {
+        if level == 0 {
+            Node::Leaf(Rc::new(vec![value]))
+        } else {
+            Node::Branch(Rc::new(vec![Self::path(level - BITS, value)]))
+        }
+    }
+
+    fn get(&self, level: usize, index: usize) -> Option<&Value> {
+        match self {
+            Node::Branch(children) => children
+                .get((index >> level) & MASK)?
+                .get(level - BITS, index),
+            Node::Leaf(values) => values.get(index & MASK),
+        }
+    }
+
+    /// Copy the path to the last element and append `value` to it.
+    fn push(&self, level: usize, index: usize, value: Value) -> Self {
+        match self {
+            Node::Branch(children) => {
+                let mut children = Vec::clone(children);
+                let sub = (index >> level) & MASK;
+
+                match children.get(sub) {
+                    Some(child) => children[sub] = child.push(level - BITS, index, value),
+                    None => children.push(Self::path(level - BITS, value)),
+                }
+
+                Node::Branch(Rc::new(children))
+            }
+            Node::Leaf(values) => {
+                let mut values = Vec::clone(values);
+                values.push(value);
+                Node::Leaf(Rc::new(values))
+            }
+        }
+    }
+
+    /// Copy the path to the last element and remove it, returning `None` if
+    /// the node becomes empty.
+    fn pop(&self, level: usize, index: usize) -> Option<Self> {
+        match self {
+            Node::Branch(children) => {
+                let mut children = Vec::clone(children);
+                let sub = (index >> level) & MASK;
+
+                match children[sub].pop(level - BITS, index) {
+                    Some(child) => children[sub] = child,
+                    None => {
+                        children.pop();
+                    }
+                }
+
+                if children.is_empty() {
+                    return None;
+                }
+
+                Some(Node::Branch(Rc::new(children)))
+            }
+            Node::Leaf(values) => {
+                if values.len() <= 1 {
+                    return None;
+                }
+
+                let mut values = Vec::clone(values);
+                values.pop();
+                Some(Node::Leaf(Rc::new(values)))
+            }
+        }
+    }
+
+    /// Copy the path to the given element and replace it with `value`.
+    fn set(&self, level: usize, index: usize, value: Value) -> Self {
+        match self {
+            Node::Branch(children) => {
+                let mut children = Vec::clone(children);
+                let sub = (index >> level) & MASK;
+                children[sub] = children[sub].set(level - BITS, index, value);
+                Node::Branch(Rc::new(children))
+            }
+            Node::Leaf(values) => {
+                let mut values = Vec::clone(values);
+                values[index & MASK] = value;
+                Node::Leaf(Rc::new(values))
+            }
+        }
+    }
+
+    fn collect(&self, out: &mut Vec<Value>) {
+        match self {
+            Node::Branch(children) => {
+                for child in children.iter() {
+                    child.collect(out);
+                }
+            }
+            Node::Leaf(values) => {
+                out.extend(values.iter().cloned());
+            }
+        }
+    }
+}
+
+/// A persistent vector.
+///
+/// Values are stored in a trie with a branching factor of 32, so updates only
+/// copy the nodes along the path to the element being modified.
+#[derive(Any, Clone, Default)]
+#[rune(module = "crate")]
+struct Vector {
+    len: usize,
+    shift: usize,
+    root: Option<Node>,
+}
+
+impl Vector {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        let mut it = value.into_iter()?;
+        let mut vector = Self::new();
+
+        while let Some(value) = it.next()? {
+            vector = vector.push(value);
+        }
+
+        Ok(vector)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn get(&self, index: usize) -> Option<Value> {
+        if index >= self.len {
+            return None;
+        }
+
+        self.root.as_ref()?.get(self.shift, index).cloned()
+    }
+
+    fn last(&self) -> Option<Value> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    fn index_get(&self, index: usize) -> Result<Value, VmError> {
+        match self.get(index) {
+            Some(value) => Ok(value),
+            None => Err(VmError::from(VmErrorKind::OutOfRange {
+                index: index.into(),
+                len: self.len.into(),
+            })),
+        }
+    }
+
+    /// Construct a new vector with `value` appended to it.
+    fn push(&self, value: Value) -> Self {
+        let root = match &self.root {
+            None => {
+                return Self {
+                    len: 1,
+                    shift: 0,
+                    root: Some(Node::path(0, value)),
+                };
+            }
+            Some(root) => root,
+        };
+
+        // NB: the trie is full, so grow it by one level.
+        if self.len == 1 << (self.shift + BITS) {
+            let shift = self.shift + BITS;
+            let children = vec![root.clone(), Node::path(self.shift, value)];
+
+            return Self {
+                len: self.len + 1,
+                shift,
+                root: Some(Node::Branch(Rc::new(children))),
+            };
+        }
+
+        Self {
+            len: self.len + 1,
+            shift: self.shift,
+            root: Some(root.push(self.shift, self.len, value)),
+        }
+    }
+
+    /// Construct a new vector with the last value removed.
+    fn pop(&self) -> Self {
+        let root = match (&self.root, self.len) {
+            (Some(root), len) if len > 1 => root,
+            _ => return Self::new(),
+        };
+
+        let mut shift = self.shift;
+        let mut root = root.pop(shift, self.len - 1);
+
+        // NB: shrink the trie if the root only has a single child left.
+        while let Some(Node::Branch(children)) = &root {
+            if children.len() != 1 {
+                break;
+            }
+
+            root = Some(children[0].clone());
+            shift -= BITS;
+        }
+
+        Self {
+            len: self.len - 1,
+            shift,
+            root,
+        }
+    }
+
+    /// Construct a new vector where the value at `index` is replaced.
+    fn set(&self, index: usize, value: Value) -> Result<Self, VmError> {
+        let root = match &self.root {
+            Some(root) if index < self.len => root,
+            _ => {
+                return Err(VmError::from(VmErrorKind::OutOfRange {
+                    index: index.into(),
+                    len: self.len.into(),
+                }))
+            }
+        };
+
+        Ok(Self {
+            len: self.len,
+            shift: self.shift,
+            root: Some(root.set(self.shift, index, value)),
+        })
+    }
+
+    fn to_vec(&self) -> Vec<Value> {
+        let mut out = Vec::with_capacity(self.len);
+
+        if let Some(root) = &self.root {
+            root.collect(&mut out);
+        }
+
+        out
+    }
+
+    fn iter(&self) -> Iterator {
+        Iterator::from_double_ended(
+            "std::collections::im::vector::Iter",
+            self.to_vec().into_iter(),
+        )
+    }
+
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write;
+        write!(s, "{:?}", self.to_vec())
+    }
+}
+
+type Link = Option<Rc<MapNode>>;
+
+/// A node in the balanced tree backing an [OrdMap].
+struct MapNode {
+    key: Key,
+    value: Value,
+    left: Link,
+    right: Link,
+    height: usize,
+}
+
+fn height(link: &Link) -> usize {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+fn node(key: Key, value: Value, left: Link, right: Link) -> Rc<MapNode> {
+    let height = height(&left).max(height(&right)) + 1;
+
+    Rc::new(MapNode {
+        key,
+        value,
+        left,
+        right,
+        height,
+    })
+}
+
+/// Construct a node, rotating the tree if necessary to keep it balanced.
+fn balance(key: Key, value: Value, left: Link, right: Link) -> Rc<MapNode> {
+    let (hl, hr) = (height(&left), height(&right));
+
+    if hl > hr + 1 {
+        if let Some(l) = &left {
+            if height(&l.left) >= height(&l.right) {
+                let right = node(key, value, l.right.clone(), right);
+                return node(l.key.clone(), l.value.clone(), l.left.clone(), Some(right));
+            }
+
+            if let Some(lr) = &l.right {
+                let left = node(
+                    l.key.clone(),
+                    l.value.clone(),
+                    l.left.clone(),
+                    lr.left.clone(),
+                );
+                let right = node(key, value, lr.right.clone(), right);
+                return node(lr.key.clone(), lr.value.clone(), Some(left), Some(right));
+            }
+        }
+    }
+
+    if hr > hl + 1 {
+        if let Some(r) = &right {
+            if height(&r.right) >= height(&r.left) {
+                let left = node(key, value, left, r.left.clone());
+                return node(r.key.clone(), r.value.clone(), Some(left), r.right.clone());
+            }
+
+            if let Some(rl) = &r.left {
+                let left = node(key, value, left, rl.left.clone());
+                let right = node(
+                    r.key.clone(),
+                    r.value.clone(),
+                    rl.right.clone(),
+                    r.right.clone(),
+                );
+                return node(rl.key.clone(), rl.value.clone(), Some(left), Some(right));
+            }
+        }
+    }
+
+    node(key, value, left, right)
+}
+
+/// Insert into the tree, returning the new tree and whether a new key was
+/// added.
+fn insert(link: &Link, key: Key, value: Value) -> (Rc<MapNode>, bool) {
+    let n = match link {
+        Some(n) => n,
+        None => return (node(key, value, None, None), true),
+    };
+
+    match key.cmp(&n.key) {
+        Ordering::Less => {
+            let (left, added) = insert(&n.left, key, value);
+            let node = balance(n.key.clone(), n.value.clone(), Some(left), n.right.clone());
+            (node, added)
+        }
+        Ordering::Greater => {
+            let (right, added) = insert(&n.right, key, value);
+            let node = balance(n.key.clone(), n.value.clone(), n.left.clone(), Some(right));
+            (node, added)
+        }
+        Ordering::Equal => (node(key, value, n.left.clone(), n.right.clone()), false),
+    }
+}
+
+/// Remove from the tree, returning `None` if the key isn't present.
+fn remove(link: &Link, key: &Key) -> Option<Link> {
+    let n = link.as_ref()?;
+
+    Some(match key.cmp(&n.key) {
+        Ordering::Less => {
+            let left = remove(&n.left, key)?;
+            Some(balance(
+                n.key.clone(),
+                n.value.clone(),
+                left,
+                n.right.clone(),
+            ))
+        }
+        Ordering::Greater => {
+            let right = remove(&n.right, key)?;
+            Some(balance(
+                n.key.clone(),
+                n.value.clone(),
+                n.left.clone(),
+                right,
+            ))
+        }
+        Ordering::Equal => match (&n.left, &n.right) {
+            (None, right) => right.clone(),
+            (left, None) => left.clone(),
+            (left, Some(right)) => {
+                let (key, value, right) = remove_min(right);
+                Some(balance(key, value, left.clone(), right))
+            }
+        },
+    })
+}
+
+fn remove_min(n: &Rc<MapNode>) -> (Key, Value, Link) {
+    match &n.left {
+        None => (n.key.clone(), n.value.clone(), n.right.clone()),
+        Some(left) => {
+            let (key, value, left) = remove_min(left);
+            let n = balance(n.key.clone(), n.value.clone(), left, n.right.clone());
+            (key, value, Some(n))
+        }
+    }
+}
+
+fn collect_entries(link: &Link, out: &mut Vec<(Key, Value)>) {
+    if let Some(n) = link {
+        collect_entries(&n.left, out);
+        out.push((n.key.clone(), n.value.clone()));
+        collect_entries(&n.right, out);
+    }
+}
+
+/// A persistent map ordered by its keys.
+///
+/// Entries are stored in a balanced binary tree, so updates only copy the
+/// nodes along the path to the entry being modified.
+#[derive(Any, Clone, Default)]
+#[rune(module = "crate")]
+struct OrdMap {
+    len: usize,
+    root: Link,
+}
+
+impl OrdMap {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        let mut it = value.into_iter()?;
+        let mut map = Self::new();
+
+        while let Some(value) = it.next()? {
+            let (key, value) = <(Key, Value)>::from_value(value)?;
+            map = map.insert(key, value);
+        }
+
+        Ok(map)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn get(&self, key: Key) -> Option<Value> {
+        let mut link = &self.root;
+
+        while let Some(n) = link {
+            link = match key.cmp(&n.key) {
+                Ordering::Less => &n.left,
+                Ordering::Greater => &n.right,
+                Ordering::Equal => return Some(n.value.clone()),
+            };
+        }
+
+        None
+    }
+
+    fn contains_key(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    fn index_get(&self, key: Key) -> Result<Value, VmError> {
+        use crate::runtime::TypeOf;
+
+        match self.get(key.clone()) {
+            Some(value) => Ok(value),
+            None => Err(VmError::from(VmErrorKind::MissingIndexKey {
+                target: Self::type_info(),
+                index: format!("{:?}", key),
+            })),
+        }
+    }
+
+    /// Construct a new map with the given entry inserted.
+    fn insert(&self, key: Key, value: Value) -> Self {
+        let (root, added) = insert(&self.root, key, value);
+
+        Self {
+            len: if added { self.len + 1 } else { self.len },
+            root: Some(root),
+        }
+    }
+
+    /// Construct a new map with the given key removed.
+    fn remove(&self, key: Key) -> Self {
+        match remove(&self.root, &key) {
+            Some(root) => Self {
+                len: self.len - 1,
+                root,
+            },
+            None => self.clone(),
+        }
+    }
+
+    fn entries(&self) -> Vec<(Key, Value)> {
+        let mut out = Vec::with_capacity(self.len);
+        collect_entries(&self.root, &mut out);
+        out
+    }
+
+    fn iter(&self) -> Iterator {
+        Iterator::from_double_ended(
+            "std::collections::im::ord_map::Iter",
+            self.entries().into_iter(),
+        )
+    }
+
+    fn keys(&self) -> Iterator {
+        let iter = self.entries().into_iter().map(|(key, _)| key);
+        Iterator::from_double_ended("std::collections::im::ord_map::Keys", iter)
+    }
+
+    fn values(&self) -> Iterator {
+        let iter = self.entries().into_iter().map(|(_, value)| value);
+        Iterator::from_double_ended("std::collections::im::ord_map::Values", iter)
+    }
+
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write;
+        write!(s, "{:?}", DebugEntries(&self.entries()))
+    }
+}
+
+struct DebugEntries<'a>(&'a [(Key, Value)]);
+
+impl fmt::Debug for DebugEntries<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(key, value)| (key, value)))
+            .finish()
+    }
+}
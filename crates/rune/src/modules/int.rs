@@ -1,6 +1,8 @@
 //! The `std::int` module.
 
+use crate::runtime::VmError;
 use crate::{ContextError, Module};
+use std::cmp::Ordering;
 use std::num::ParseIntError;
 
 /// Construct the `std::int` module.
@@ -10,18 +12,25 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<ParseIntError>()?;
 
     module.function(["parse"], parse)?;
+    module.function(["parse_radix"], parse_radix)?;
     module.inst_fn("to_float", to_float)?;
+    module.inst_fn("to_string_radix", to_string_radix)?;
+    module.inst_fn("to_string_separated", to_string_separated)?;
 
     module.inst_fn("max", i64::max)?;
     module.inst_fn("min", i64::min)?;
     module.inst_fn("abs", i64::abs)?;
     module.inst_fn("pow", i64::pow)?;
+    module.inst_fn("cmp", cmp)?;
 
     module.inst_fn("checked_add", i64::checked_add)?;
     module.inst_fn("checked_sub", i64::checked_sub)?;
     module.inst_fn("checked_div", i64::checked_div)?;
     module.inst_fn("checked_mul", i64::checked_mul)?;
     module.inst_fn("checked_rem", i64::checked_rem)?;
+    module.inst_fn("checked_pow", i64::checked_pow)?;
+    module.inst_fn("checked_neg", i64::checked_neg)?;
+    module.inst_fn("checked_abs", i64::checked_abs)?;
 
     module.inst_fn("wrapping_add", i64::wrapping_add)?;
     module.inst_fn("wrapping_sub", i64::wrapping_sub)?;
@@ -32,17 +41,86 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("saturating_add", i64::saturating_add)?;
     module.inst_fn("saturating_sub", i64::saturating_sub)?;
     module.inst_fn("saturating_mul", i64::saturating_mul)?;
+    module.inst_fn("saturating_div", i64::saturating_div)?;
     module.inst_fn("saturating_abs", i64::saturating_abs)?;
     module.inst_fn("saturating_pow", i64::saturating_pow)?;
 
     Ok(module)
 }
 
+/// Compare two integers.
+fn cmp(a: i64, b: i64) -> Ordering {
+    a.cmp(&b)
+}
+
 /// Parse an integer.
 fn parse(s: &str) -> Result<i64, ParseIntError> {
     str::parse::<i64>(s)
 }
 
+/// Parse an integer in the given radix, which must be in the range `2..=36`.
+fn parse_radix(s: &str, radix: u32) -> Result<Result<i64, ParseIntError>, VmError> {
+    check_radix(radix)?;
+    Ok(i64::from_str_radix(s, radix))
+}
+
+/// Format an integer in the given radix, which must be in the range `2..=36`,
+/// using lowercase letters for digits above 9.
+fn to_string_radix(value: i64, radix: u32) -> Result<String, VmError> {
+    check_radix(radix)?;
+
+    let mut n = value.unsigned_abs();
+    let mut digits = Vec::new();
+
+    loop {
+        let digit = (n % radix as u64) as u32;
+        digits.push(std::char::from_digit(digit, radix).expect("digit is within radix"));
+        n /= radix as u64;
+
+        if n == 0 {
+            break;
+        }
+    }
+
+    if value < 0 {
+        digits.push('-');
+    }
+
+    Ok(digits.into_iter().rev().collect())
+}
+
+/// Format an integer with the given separator between every group of three
+/// digits, like `1,234,567`.
+fn to_string_separated(value: i64, separator: char) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let mut string = String::with_capacity(digits.len() * 2);
+
+    if value < 0 {
+        string.push('-');
+    }
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            string.push(separator);
+        }
+
+        string.push(c);
+    }
+
+    string
+}
+
+fn check_radix(radix: u32) -> Result<(), VmError> {
+    if !(2..=36).contains(&radix) {
+        return Err(VmError::panic(format!(
+            "radix must be in the range 2..=36, but was {}",
+            radix
+        )));
+    }
+
+    Ok(())
+}
+
 /// Convert a whole number to float.
 fn to_float(value: i64) -> f64 {
     value as f64
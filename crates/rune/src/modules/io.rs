@@ -3,11 +3,11 @@
 use std::fmt;
 use std::fmt::Write as _;
 use std::io;
-use std::io::Write as _;
 
 use crate as rune;
 use crate::macros::{quote, FormatArgs, MacroContext, TokenStream};
 use crate::parse::Parser;
+use crate::runtime::output;
 use crate::runtime::{Panic, Protocol, Stack, Value, VmError};
 use crate::{ContextError, Module};
 
@@ -21,6 +21,8 @@ pub fn module(stdio: bool) -> Result<Module, ContextError> {
     if stdio {
         module.function_meta(print_impl)?;
         module.function_meta(println_impl)?;
+        module.function_meta(eprint_impl)?;
+        module.function_meta(eprintln_impl)?;
         module.raw_fn(["dbg"], dbg_impl)?;
     }
 
@@ -29,6 +31,8 @@ pub fn module(stdio: bool) -> Result<Module, ContextError> {
     module.macro_(["dbg"], dbg_macro)?;
     module.macro_(["print"], print_macro)?;
     module.macro_(["println"], println_macro)?;
+    module.macro_(["eprint"], eprint_macro)?;
+    module.macro_(["eprintln"], eprintln_macro)?;
     Ok(module)
 }
 
@@ -37,12 +41,16 @@ fn format_io_error(error: &std::io::Error, buf: &mut String) -> fmt::Result {
 }
 
 fn dbg_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
+    let values = stack.drain(args)?;
 
-    for value in stack.drain(args)? {
-        writeln!(stdout, "{:?}", value).map_err(VmError::panic)?;
-    }
+    output::with_stdout(|stdout| {
+        for value in values {
+            writeln!(stdout, "{:?}", value)?;
+        }
+
+        Ok(())
+    })
+    .map_err(VmError::panic)?;
 
     stack.push(Value::Unit);
     Ok(())
@@ -79,9 +87,7 @@ pub(crate) fn print_macro(
 /// ```
 #[rune::function(path = print)]
 fn print_impl(m: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    write!(stdout, "{}", m).map_err(Panic::custom)
+    output::with_stdout(|stdout| write!(stdout, "{}", m)).map_err(Panic::custom)
 }
 
 /// Implementation for the `println!` macro.
@@ -107,7 +113,57 @@ pub(crate) fn println_macro(
 /// ```
 #[rune::function(path = println)]
 fn println_impl(message: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    writeln!(stdout, "{}", message).map_err(Panic::custom)
+    output::with_stdout(|stdout| writeln!(stdout, "{}", message)).map_err(Panic::custom)
+}
+
+/// Implementation for the `eprint!` macro.
+pub(crate) fn eprint_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> crate::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, ctx.stream_span());
+    let args = p.parse_all::<FormatArgs>()?;
+    let expanded = args.expand(ctx)?;
+    Ok(quote!(::std::io::eprint(#expanded)).into_token_stream(ctx))
+}
+
+/// Print to stderr.
+///
+/// This is provided on top of the [`eprint!`] macro so that it can be used as
+/// a function.
+///
+/// # Examples
+///
+/// ```rune
+/// eprint("Hi!");
+/// ```
+#[rune::function(path = eprint)]
+fn eprint_impl(m: &str) -> Result<(), Panic> {
+    output::with_stderr(|stderr| write!(stderr, "{}", m)).map_err(Panic::custom)
+}
+
+/// Implementation for the `eprintln!` macro.
+pub(crate) fn eprintln_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> crate::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, ctx.stream_span());
+    let args = p.parse_all::<FormatArgs>()?;
+    let expanded = args.expand(ctx)?;
+    Ok(quote!(::std::io::eprintln(#expanded)).into_token_stream(ctx))
+}
+
+/// Print to stderr adding a newline to what is being printed.
+///
+/// This is provided on top of the [`eprintln!`] macro so that it can be used
+/// as a function.
+///
+/// # Examples
+///
+/// ```rune
+/// eprintln("Hi!");
+/// ```
+#[rune::function(path = eprintln)]
+fn eprintln_impl(message: &str) -> Result<(), Panic> {
+    output::with_stderr(|stderr| writeln!(stderr, "{}", message)).map_err(Panic::custom)
 }
@@ -1,5 +1,6 @@
 //! The `std::iter` module.
 
+use crate::modules::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use crate::runtime::{FromValue, Iterator, Object, Protocol, Tuple, TypeOf, Value, Vec, VmError};
 use crate::{ContextError, Module, Params};
 
@@ -10,6 +11,17 @@ pub fn module() -> Result<Module, ContextError> {
 
     // Sorted for ease of finding
     module.inst_fn("chain", Iterator::chain)?;
+    module.inst_fn("chunks", Iterator::chunks)?;
+    module.inst_fn(
+        Params("collect", [BTreeMap::type_hash()]),
+        collect_btree_map,
+    )?;
+    module.inst_fn(
+        Params("collect", [BTreeSet::type_hash()]),
+        collect_btree_set,
+    )?;
+    module.inst_fn(Params("collect", [HashMap::type_hash()]), collect_hash_map)?;
+    module.inst_fn(Params("collect", [HashSet::type_hash()]), collect_hash_set)?;
     module.inst_fn(Params("collect", [Object::type_hash()]), collect_object)?;
     module.inst_fn(Params("collect", [Vec::type_hash()]), collect_vec)?;
     module.inst_fn(Params("collect", [Tuple::type_hash()]), collect_tuple)?;
@@ -17,18 +29,26 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("filter", Iterator::filter)?;
     module.inst_fn("find", Iterator::find)?;
     module.inst_fn("flat_map", Iterator::flat_map)?;
+    module.inst_fn("group_by", Iterator::group_by)?;
     module.inst_fn("map", Iterator::map)?;
     module.inst_fn("next", Iterator::next)?;
     module.inst_fn("next_back", Iterator::next_back)?;
+    module.inst_fn("partition", Iterator::partition)?;
     module.inst_fn("peek", Iterator::peek)?;
     module.inst_fn("peekable", Iterator::peekable)?;
     module.inst_fn("product", Iterator::product)?;
     module.inst_fn("fold", Iterator::fold)?;
+    module.inst_fn("is_empty", Iterator::is_empty)?;
+    module.inst_fn("len", Iterator::len)?;
     module.inst_fn("rev", Iterator::rev)?;
     module.inst_fn("size_hint", Iterator::size_hint)?;
     module.inst_fn("sum", Iterator::sum)?;
     module.inst_fn("skip", Iterator::skip)?;
+    module.inst_fn("skip_while", Iterator::skip_while)?;
     module.inst_fn("take", Iterator::take)?;
+    module.inst_fn("take_while", Iterator::take_while)?;
+    module.inst_fn("windows", Iterator::windows)?;
+    module.inst_fn("zip", Iterator::zip)?;
     module.inst_fn("count", Iterator::count)?;
     module.inst_fn("all", Iterator::all)?;
     module.inst_fn(Protocol::NEXT, Iterator::next)?;
@@ -52,6 +72,22 @@ fn new_range(start: i64, end: i64) -> Iterator {
     Iterator::from_double_ended("std::iter::Range", start..end)
 }
 
+fn collect_btree_map(it: Iterator) -> Result<BTreeMap, VmError> {
+    BTreeMap::from_iterator(it)
+}
+
+fn collect_btree_set(it: Iterator) -> Result<BTreeSet, VmError> {
+    BTreeSet::from_iterator(it)
+}
+
+fn collect_hash_map(it: Iterator) -> Result<HashMap, VmError> {
+    HashMap::from_iterator(it)
+}
+
+fn collect_hash_set(it: Iterator) -> Result<HashSet, VmError> {
+    HashSet::from_iterator(it)
+}
+
 fn collect_vec(it: Iterator) -> Result<Vec, VmError> {
     Ok(Vec::from(it.collect::<Value>()?))
 }
@@ -0,0 +1,184 @@
+//! The `std::log` module.
+
+use crate as rune;
+use crate::macros::{quote, FormatArgs, MacroContext, TokenStream};
+use crate::parse::Parser;
+use crate::runtime::log::{self, Level};
+use crate::runtime::VmError;
+use crate::{ContextError, Module};
+
+/// Construct the `std::log` module.
+///
+/// Messages are routed to the logger installed through
+/// [`Context::set_logger`][crate::Context::set_logger].
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["log"]).with_unique("std::log");
+    module.function_meta(enabled)?;
+    module.function_meta(trace)?;
+    module.function_meta(debug)?;
+    module.function_meta(info)?;
+    module.function_meta(warn)?;
+    module.function_meta(error)?;
+    module.macro_(["trace"], trace_macro)?;
+    module.macro_(["debug"], debug_macro)?;
+    module.macro_(["info"], info_macro)?;
+    module.macro_(["warn"], warn_macro)?;
+    module.macro_(["error"], error_macro)?;
+    Ok(module)
+}
+
+/// Test if messages at the given level, one of `"trace"`, `"debug"`,
+/// `"info"`, `"warn"` or `"error"`, are of interest to the logger.
+///
+/// # Examples
+///
+/// ```rune
+/// if std::log::enabled("debug") {
+///     std::log::debug("expensive message");
+/// }
+/// ```
+#[rune::function]
+fn enabled(level: &str) -> Result<bool, VmError> {
+    let level = match level {
+        "trace" => Level::Trace,
+        "debug" => Level::Debug,
+        "info" => Level::Info,
+        "warn" => Level::Warn,
+        "error" => Level::Error,
+        level => {
+            return Err(VmError::panic(format!("unsupported log level `{}`", level)));
+        }
+    };
+
+    Ok(log::enabled(level))
+}
+
+/// Log a message at the trace level.
+///
+/// This is provided on top of the [`trace!`] macro so that it can be used as
+/// a function.
+///
+/// # Examples
+///
+/// ```rune
+/// std::log::trace("Hi!");
+/// ```
+#[rune::function]
+fn trace(message: &str) {
+    log::log(Level::Trace, message);
+}
+
+/// Log a message at the debug level.
+///
+/// This is provided on top of the [`debug!`] macro so that it can be used as
+/// a function.
+///
+/// # Examples
+///
+/// ```rune
+/// std::log::debug("Hi!");
+/// ```
+#[rune::function]
+fn debug(message: &str) {
+    log::log(Level::Debug, message);
+}
+
+/// Log a message at the info level.
+///
+/// This is provided on top of the [`info!`] macro so that it can be used as
+/// a function.
+///
+/// # Examples
+///
+/// ```rune
+/// std::log::info("Hi!");
+/// ```
+#[rune::function]
+fn info(message: &str) {
+    log::log(Level::Info, message);
+}
+
+/// Log a message at the warn level.
+///
+/// This is provided on top of the [`warn!`] macro so that it can be used as
+/// a function.
+///
+/// # Examples
+///
+/// ```rune
+/// std::log::warn("Hi!");
+/// ```
+#[rune::function]
+fn warn(message: &str) {
+    log::log(Level::Warn, message);
+}
+
+/// Log a message at the error level.
+///
+/// This is provided on top of the [`error!`] macro so that it can be used as
+/// a function.
+///
+/// # Examples
+///
+/// ```rune
+/// std::log::error("Hi!");
+/// ```
+#[rune::function]
+fn error(message: &str) {
+    log::log(Level::Error, message);
+}
+
+/// Implementation for the `trace!` macro.
+pub(crate) fn trace_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> crate::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, ctx.stream_span());
+    let args = p.parse_all::<FormatArgs>()?;
+    let expanded = args.expand(ctx)?;
+    Ok(quote!(::std::log::trace(#expanded)).into_token_stream(ctx))
+}
+
+/// Implementation for the `debug!` macro.
+pub(crate) fn debug_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> crate::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, ctx.stream_span());
+    let args = p.parse_all::<FormatArgs>()?;
+    let expanded = args.expand(ctx)?;
+    Ok(quote!(::std::log::debug(#expanded)).into_token_stream(ctx))
+}
+
+/// Implementation for the `info!` macro.
+pub(crate) fn info_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> crate::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, ctx.stream_span());
+    let args = p.parse_all::<FormatArgs>()?;
+    let expanded = args.expand(ctx)?;
+    Ok(quote!(::std::log::info(#expanded)).into_token_stream(ctx))
+}
+
+/// Implementation for the `warn!` macro.
+pub(crate) fn warn_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> crate::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, ctx.stream_span());
+    let args = p.parse_all::<FormatArgs>()?;
+    let expanded = args.expand(ctx)?;
+    Ok(quote!(::std::log::warn(#expanded)).into_token_stream(ctx))
+}
+
+/// Implementation for the `error!` macro.
+pub(crate) fn error_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> crate::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, ctx.stream_span());
+    let args = p.parse_all::<FormatArgs>()?;
+    let expanded = args.expand(ctx)?;
+    Ok(quote!(::std::log::error(#expanded)).into_token_stream(ctx))
+}
@@ -0,0 +1,91 @@
+//! The `std::math` module.
+
+use crate::runtime::VmError;
+use crate::{ContextError, Module};
+use std::f64::consts;
+
+/// Construct the `std::math` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["math"]);
+
+    module.constant(["E"], consts::E)?;
+    module.constant(["INFINITY"], f64::INFINITY)?;
+    module.constant(["LN_10"], consts::LN_10)?;
+    module.constant(["LN_2"], consts::LN_2)?;
+    module.constant(["NAN"], f64::NAN)?;
+    module.constant(["NEG_INFINITY"], f64::NEG_INFINITY)?;
+    module.constant(["PI"], consts::PI)?;
+    module.constant(["SQRT_2"], consts::SQRT_2)?;
+    module.constant(["TAU"], consts::TAU)?;
+
+    // Sorted for ease of finding
+    module.function(["acos"], f64::acos)?;
+    module.function(["asin"], f64::asin)?;
+    module.function(["atan"], f64::atan)?;
+    module.function(["atan2"], f64::atan2)?;
+    module.function(["cbrt"], f64::cbrt)?;
+    module.function(["ceil"], f64::ceil)?;
+    module.function(["clamp"], clamp)?;
+    module.function(["cos"], f64::cos)?;
+    module.function(["cosh"], f64::cosh)?;
+    module.function(["exp"], f64::exp)?;
+    module.function(["exp2"], f64::exp2)?;
+    module.function(["floor"], f64::floor)?;
+    module.function(["fract"], f64::fract)?;
+    module.function(["hypot"], f64::hypot)?;
+    module.function(["lerp"], lerp)?;
+    module.function(["ln"], f64::ln)?;
+    module.function(["log"], f64::log)?;
+    module.function(["log10"], f64::log10)?;
+    module.function(["log2"], f64::log2)?;
+    module.function(["round"], f64::round)?;
+    module.function(["round_ties_even"], round_ties_even)?;
+    module.function(["round_to"], round_to)?;
+    module.function(["signum"], f64::signum)?;
+    module.function(["sin"], f64::sin)?;
+    module.function(["sinh"], f64::sinh)?;
+    module.function(["sqrt"], f64::sqrt)?;
+    module.function(["tan"], f64::tan)?;
+    module.function(["tanh"], f64::tanh)?;
+    module.function(["to_degrees"], f64::to_degrees)?;
+    module.function(["to_radians"], f64::to_radians)?;
+    module.function(["trunc"], f64::trunc)?;
+    Ok(module)
+}
+
+/// Restrict a value to the range `min..=max`.
+fn clamp(value: f64, min: f64, max: f64) -> Result<f64, VmError> {
+    if min.is_nan() || max.is_nan() || min > max {
+        return Err(VmError::panic(format!(
+            "clamp requires min <= max, but got {} > {}",
+            min, max
+        )));
+    }
+
+    Ok(value.clamp(min, max))
+}
+
+/// Linearly interpolate between `a` and `b`, where a `t` of `0.0` gives `a`
+/// and `1.0` gives `b`.
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Round to the nearest integer, rounding half-way cases to the nearest even
+/// integer.
+fn round_ties_even(value: f64) -> f64 {
+    let rounded = value.round();
+
+    if (value - value.trunc()).abs() == 0.5 && rounded % 2.0 != 0.0 {
+        rounded - value.signum()
+    } else {
+        rounded
+    }
+}
+
+/// Round to the given number of digits after the decimal point, rounding
+/// half-way cases away from zero.
+fn round_to(value: f64, digits: i32) -> f64 {
+    let factor = 10f64.powi(digits);
+    (value * factor).round() / factor
+}
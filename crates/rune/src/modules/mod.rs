@@ -10,20 +10,29 @@ pub mod char;
 pub mod cmp;
 pub mod collections;
 pub mod core;
+pub mod env;
 pub mod float;
 pub mod fmt;
 pub mod future;
 pub mod generator;
+pub mod im;
 pub mod int;
 pub mod io;
 pub mod iter;
+pub mod log;
 pub mod macros;
+pub mod math;
 pub mod mem;
 pub mod object;
 pub mod ops;
 pub mod option;
+pub mod panic;
 pub mod result;
 pub mod stream;
 pub mod string;
+pub mod symbol;
+pub mod sync;
 pub mod test;
+pub mod thread;
+pub mod time;
 pub mod vec;
@@ -1,6 +1,6 @@
 //! The `std::object` module.
 
-use crate::runtime::{Iterator, Object, Protocol, Value};
+use crate::runtime::{Function, Iterator, Object, Protocol, Value, VmError};
 use crate::{ContextError, Module};
 
 /// Construct the `std::object` module.
@@ -13,8 +13,13 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("insert", Object::insert)?;
     module.inst_fn("remove", remove)?;
     module.inst_fn("clear", Object::clear)?;
+    module.inst_fn("clone_deep", Object::clone_deep)?;
     module.inst_fn("contains_key", contains_key)?;
     module.inst_fn("get", get)?;
+    module.inst_fn("merge", merge)?;
+    module.inst_fn("retain", retain)?;
+    module.inst_fn("map", map)?;
+    module.inst_fn("filter", filter)?;
 
     module.inst_fn("iter", Object::into_iterator)?;
     module.inst_fn(Protocol::INTO_ITER, Object::into_iterator)?;
@@ -35,6 +40,65 @@ fn get(object: &Object, key: &str) -> Option<Value> {
     object.get(key).cloned()
 }
 
+/// Insert every key-value pair of `other`, replacing the values of keys which
+/// are already present.
+fn merge(object: &mut Object, other: &Object) {
+    for (key, value) in other {
+        object.insert(key.clone(), value.clone());
+    }
+}
+
+/// Retain only the entries for which `predicate(key, value)` returns `true`.
+fn retain(object: &mut Object, predicate: &Function) -> Result<(), VmError> {
+    let mut error = None;
+
+    object.retain(|key, value| {
+        if error.is_some() {
+            return true;
+        }
+
+        match predicate.call::<_, bool>((key.as_str(), value.clone())) {
+            Ok(retain) => retain,
+            Err(e) => {
+                error = Some(e);
+                true
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Construct a new object with the same keys, where each value is replaced
+/// with `f(key, value)`.
+fn map(object: &Object, f: &Function) -> Result<Object, VmError> {
+    let mut output = Object::with_capacity(object.len());
+
+    for (key, value) in object {
+        let value = f.call::<_, Value>((key.as_str(), value.clone()))?;
+        output.insert(key.clone(), value);
+    }
+
+    Ok(output)
+}
+
+/// Construct a new object with the entries for which `predicate(key, value)`
+/// returns `true`.
+fn filter(object: &Object, predicate: &Function) -> Result<Object, VmError> {
+    let mut output = Object::new();
+
+    for (key, value) in object {
+        if predicate.call::<_, bool>((key.as_str(), value.clone()))? {
+            output.insert(key.clone(), value.clone());
+        }
+    }
+
+    Ok(output)
+}
+
 fn keys(object: &Object) -> Iterator {
     let iter = object.keys().cloned().collect::<Vec<_>>().into_iter();
     Iterator::from_double_ended("std::object::Keys", iter)
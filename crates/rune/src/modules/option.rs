@@ -1,7 +1,9 @@
 //! The `std::option` module.
 
-use crate::runtime::{Function, Iterator, Protocol, Shared, Value, VmError};
-use crate::{ContextError, Module};
+use crate::runtime::{
+    FromValue, Function, Iterator, Object, Protocol, Shared, ToValue, TypeOf, Value, Vec, VmError,
+};
+use crate::{ContextError, Module, Params};
 
 /// Construct the `std::option` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -10,16 +12,48 @@ pub fn module() -> Result<Module, ContextError> {
     // Sorted for ease of finding
     module.inst_fn("and_then", and_then_impl)?;
     module.inst_fn("expect", expect_impl)?;
+    module.inst_fn("filter", filter_impl)?;
+    module.inst_fn("flatten", flatten_impl)?;
     module.inst_fn("is_none", Option::<Value>::is_none)?;
     module.inst_fn("is_some", Option::<Value>::is_some)?;
     module.inst_fn("iter", option_iter)?;
     module.inst_fn("map", map_impl)?;
+    module.inst_fn("ok_or", Option::<Value>::ok_or::<Value>)?;
+    module.inst_fn("ok_or_else", ok_or_else_impl)?;
+    module.inst_fn("or_else", or_else_impl)?;
     module.inst_fn("take", take_impl)?;
     module.inst_fn("transpose", transpose_impl)?;
     module.inst_fn("unwrap", unwrap_impl)?;
     module.inst_fn("unwrap_or", Option::<Value>::unwrap_or)?;
     module.inst_fn("unwrap_or_else", unwrap_or_else_impl)?;
+    module.inst_fn("zip", Option::<Value>::zip::<Value>)?;
     module.inst_fn(Protocol::INTO_ITER, option_iter)?;
+
+    // TODO: parameterize with generics.
+    module.inst_fn(
+        Params("unwrap_or_default", [bool::type_hash()]),
+        unwrap_or_default_impl::<bool>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [f64::type_hash()]),
+        unwrap_or_default_impl::<f64>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [i64::type_hash()]),
+        unwrap_or_default_impl::<i64>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [Object::type_hash()]),
+        unwrap_or_default_impl::<Object>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [String::type_hash()]),
+        unwrap_or_default_impl::<String>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [Vec::type_hash()]),
+        unwrap_or_default_impl::<std::vec::Vec<Value>>,
+    )?;
     Ok(module)
 }
 
@@ -31,6 +65,47 @@ fn unwrap_or_else_impl(this: &Option<Value>, default: Function) -> Result<Value,
     default.call(())
 }
 
+/// Unwrap the option, or construct the default value of the type it's
+/// parameterized with, like `option.unwrap_or_default::<int>()`.
+fn unwrap_or_default_impl<T>(this: Option<Value>) -> Result<Value, VmError>
+where
+    T: Default + ToValue,
+{
+    match this {
+        Some(value) => Ok(value),
+        None => T::default().to_value(),
+    }
+}
+
+fn or_else_impl(this: &Option<Value>, default: Function) -> Result<Option<Value>, VmError> {
+    match this {
+        Some(v) => Ok(Some(v.clone())),
+        None => default.call(()),
+    }
+}
+
+fn ok_or_else_impl(this: &Option<Value>, err: Function) -> Result<Result<Value, Value>, VmError> {
+    match this {
+        Some(v) => Ok(Ok(v.clone())),
+        None => Ok(Err(err.call(())?)),
+    }
+}
+
+fn filter_impl(this: &Option<Value>, predicate: Function) -> Result<Option<Value>, VmError> {
+    match this {
+        Some(v) if predicate.call::<_, bool>((v,))? => Ok(Some(v.clone())),
+        _ => Ok(None),
+    }
+}
+
+/// Flatten an `Option<Option<T>>` into an `Option<T>`.
+fn flatten_impl(this: &Option<Value>) -> Result<Option<Value>, VmError> {
+    match this {
+        Some(v) => <Option<Value>>::from_value(v.clone()),
+        None => Ok(None),
+    }
+}
+
 /// Transpose functions, translates an Option<Result<T, E>> into a `Result<Option<T>, E>`.
 fn transpose_impl(this: &Option<Value>) -> Result<Value, VmError> {
     Ok(Value::from(Shared::new(match this.clone() {
@@ -0,0 +1,26 @@
+//! The `std::panic` module.
+
+use crate::runtime::{Function, Value, VmError};
+use crate::{ContextError, Module};
+
+/// Construct the `std::panic` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["panic"]);
+    module.function(["catch"], catch)?;
+    Ok(module)
+}
+
+/// Call the given function, converting any panic raised while it executes
+/// into an error containing the panic message.
+///
+/// Errors which are not panics, like exceeding the limits imposed on the
+/// virtual machine, are propagated as usual.
+fn catch(function: Function) -> Result<Result<Value, String>, VmError> {
+    match function.call::<_, Value>(()) {
+        Ok(value) => Ok(Ok(value)),
+        Err(error) => match error.as_panic() {
+            Some(reason) => Ok(Err(reason.to_string())),
+            None => Err(error),
+        },
+    }
+}
@@ -1,7 +1,7 @@
 //! The `std::result` module.
 
-use crate::runtime::{Function, Value, VmError};
-use crate::{ContextError, Module};
+use crate::runtime::{Function, Object, ToValue, TypeOf, Value, Vec, VmError};
+use crate::{ContextError, Module, Params};
 
 /// Construct the `std::result` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -16,6 +16,34 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("expect", expect_impl)?;
     module.inst_fn("and_then", and_then_impl)?;
     module.inst_fn("map", map_impl)?;
+    module.inst_fn("map_err", map_err_impl)?;
+    module.inst_fn("or_else", or_else_impl)?;
+
+    // TODO: parameterize with generics.
+    module.inst_fn(
+        Params("unwrap_or_default", [bool::type_hash()]),
+        unwrap_or_default_impl::<bool>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [f64::type_hash()]),
+        unwrap_or_default_impl::<f64>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [i64::type_hash()]),
+        unwrap_or_default_impl::<i64>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [Object::type_hash()]),
+        unwrap_or_default_impl::<Object>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [String::type_hash()]),
+        unwrap_or_default_impl::<String>,
+    )?;
+    module.inst_fn(
+        Params("unwrap_or_default", [Vec::type_hash()]),
+        unwrap_or_default_impl::<std::vec::Vec<Value>>,
+    )?;
     Ok(module)
 }
 
@@ -62,3 +90,35 @@ fn map_impl(this: &Result<Value, Value>, then: Function) -> Result<Result<Value,
         Err(e) => Ok(Err(e.clone())),
     }
 }
+
+fn map_err_impl(
+    this: &Result<Value, Value>,
+    then: Function,
+) -> Result<Result<Value, Value>, VmError> {
+    match this {
+        Ok(v) => Ok(Ok(v.clone())),
+        Err(e) => Ok(Err(then.call::<_, _>((e,))?)),
+    }
+}
+
+fn or_else_impl(
+    this: &Result<Value, Value>,
+    then: Function,
+) -> Result<Result<Value, Value>, VmError> {
+    match this {
+        Ok(v) => Ok(Ok(v.clone())),
+        Err(e) => Ok(then.call::<_, _>((e,))?),
+    }
+}
+
+/// Unwrap the result, or construct the default value of the type it's
+/// parameterized with, like `result.unwrap_or_default::<int>()`.
+fn unwrap_or_default_impl<T>(this: Result<Value, Value>) -> Result<Value, VmError>
+where
+    T: Default + ToValue,
+{
+    match this {
+        Ok(value) => Ok(value),
+        Err(..) => T::default().to_value(),
+    }
+}
@@ -1,7 +1,8 @@
 //! The `std::string` module.
 
-use crate::runtime::{Bytes, Iterator, Protocol, Value, VmError, VmErrorKind};
+use crate::runtime::{charge_allocation, Bytes, Iterator, Protocol, Value, VmError, VmErrorKind};
 use crate::{Any, ContextError, Module};
+use std::fmt;
 
 /// Construct the `std::string` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -11,7 +12,7 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.function(["String", "from_str"], <String as From<&str>>::from)?;
     module.function(["String", "new"], String::new)?;
-    module.function(["String", "with_capacity"], String::with_capacity)?;
+    module.function(["String", "with_capacity"], string_with_capacity)?;
 
     module.inst_fn("cmp", str::cmp)?;
     module.inst_fn("len", String::len)?;
@@ -20,10 +21,10 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("capacity", String::capacity)?;
     module.inst_fn("clear", String::clear)?;
     module.inst_fn("contains", str::contains::<&str>)?;
-    module.inst_fn("push", String::push)?;
-    module.inst_fn("push_str", String::push_str)?;
-    module.inst_fn("reserve", String::reserve)?;
-    module.inst_fn("reserve_exact", String::reserve_exact)?;
+    module.inst_fn("push", string_push)?;
+    module.inst_fn("push_str", string_push_str)?;
+    module.inst_fn("reserve", string_reserve)?;
+    module.inst_fn("reserve_exact", string_reserve_exact)?;
     module.inst_fn("into_bytes", into_bytes)?;
     module.inst_fn("clone", String::clone)?;
     module.inst_fn("shrink_to_fit", String::shrink_to_fit)?;
@@ -36,11 +37,37 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("split_str", string_split)?;
     module.inst_fn("is_empty", str::is_empty)?;
     module.inst_fn("chars", string_chars)?;
+    module.inst_fn("char_indices", string_char_indices)?;
+    module.inst_fn("to_uppercase", str::to_uppercase)?;
+    module.inst_fn("to_lowercase", str::to_lowercase)?;
+    module.inst_fn("pad_start", string_pad_start)?;
+    module.inst_fn("pad_end", string_pad_end)?;
+    module.inst_fn("split_whitespace", string_split_whitespace)?;
+    module.inst_fn("splitn", string_splitn)?;
+    module.inst_fn("strip_prefix", string_strip_prefix)?;
+    module.inst_fn("strip_suffix", string_strip_suffix)?;
+    module.inst_fn("repeat", string_repeat)?;
+    module.inst_fn("format", string_format)?;
     module.inst_fn(Protocol::ADD, add)?;
-    module.inst_fn(Protocol::ADD_ASSIGN, String::push_str)?;
+    module.inst_fn(Protocol::ADD_ASSIGN, string_push_str)?;
     module.inst_fn(Protocol::INDEX_GET, string_index_get)?;
     module.inst_fn("get", string_get)?;
 
+    module.ty::<Builder>()?;
+    module.function(["Builder", "new"], Builder::new)?;
+    module.function(["Builder", "with_capacity"], Builder::with_capacity)?;
+    module.inst_fn("push", Builder::push)?;
+    module.inst_fn("push_str", Builder::push_str)?;
+    module.inst_fn("append", Builder::append)?;
+    module.inst_fn("len", Builder::len)?;
+    module.inst_fn("is_empty", Builder::is_empty)?;
+    module.inst_fn("capacity", Builder::capacity)?;
+    module.inst_fn("reserve", Builder::reserve)?;
+    module.inst_fn("clear", Builder::clear)?;
+    module.inst_fn("build", Builder::build)?;
+    module.inst_fn(Protocol::ADD_ASSIGN, Builder::append)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Builder::string_display)?;
+
     // TODO: parameterize once generics are available.
     module.function(["parse_int"], parse_int)?;
     module.function(["parse_char"], parse_char)?;
@@ -64,6 +91,85 @@ impl NotCharBoundary {
     }
 }
 
+/// A buffer used to efficiently build a string out of many pieces.
+///
+/// Unlike concatenating strings with `+`, which allocates a new string for
+/// every operation, the builder grows a single buffer.
+#[derive(Any, Default)]
+#[rune(module = "crate")]
+struct Builder {
+    buf: String,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_capacity(capacity: usize) -> Result<Self, VmError> {
+        Ok(Self {
+            buf: string_with_capacity(capacity)?,
+        })
+    }
+
+    fn push(&mut self, c: char) -> Result<(), VmError> {
+        string_push(&mut self.buf, c)
+    }
+
+    fn push_str(&mut self, s: &str) -> Result<(), VmError> {
+        string_push_str(&mut self.buf, s)
+    }
+
+    /// Append the display representation of any value.
+    fn append(&mut self, value: Value) -> Result<(), VmError> {
+        match value {
+            Value::String(s) => string_push_str(&mut self.buf, &s.borrow_ref()?),
+            Value::StaticString(s) => string_push_str(&mut self.buf, s.as_str()),
+            Value::Char(c) => string_push(&mut self.buf, c),
+            value => {
+                let len = self.buf.len();
+                let mut buf = String::new();
+
+                if let Err(fmt::Error) = value.string_display(&mut self.buf, &mut buf)? {
+                    return Err(VmError::from(VmErrorKind::FormatError));
+                }
+
+                charge_allocation(self.buf.len() - len)
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    fn reserve(&mut self, additional: usize) -> Result<(), VmError> {
+        string_reserve(&mut self.buf, additional)
+    }
+
+    fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Construct a string out of the current contents of the builder.
+    fn build(&self) -> String {
+        self.buf.clone()
+    }
+
+    fn string_display(&self, s: &mut String) -> fmt::Result {
+        s.push_str(&self.buf);
+        Ok(())
+    }
+}
+
 /// into_bytes shim for strings.
 fn into_bytes(s: String) -> Bytes {
     Bytes::from_vec(s.into_bytes())
@@ -97,6 +203,72 @@ fn string_split(this: &str, value: Value) -> Result<Iterator, VmError> {
     ))
 }
 
+fn string_splitn(this: &str, n: usize, value: Value) -> Result<Iterator, VmError> {
+    let parts = match value {
+        Value::String(s) => this
+            .splitn(n, s.borrow_ref()?.as_str())
+            .map(String::from)
+            .collect::<Vec<String>>(),
+        Value::StaticString(s) => this
+            .splitn(n, s.as_str())
+            .map(String::from)
+            .collect::<Vec<String>>(),
+        Value::Char(pat) => this
+            .splitn(n, pat)
+            .map(String::from)
+            .collect::<Vec<String>>(),
+        value => return Err(VmError::bad_argument::<String>(1, &value)?),
+    };
+
+    Ok(Iterator::from_double_ended(
+        "std::str::SplitN",
+        parts.into_iter(),
+    ))
+}
+
+fn string_split_whitespace(this: &str) -> Iterator {
+    let iter = this
+        .split_whitespace()
+        .map(String::from)
+        .collect::<Vec<String>>()
+        .into_iter();
+
+    Iterator::from_double_ended("std::str::SplitWhitespace", iter)
+}
+
+fn string_strip_prefix(this: &str, prefix: &str) -> Option<String> {
+    this.strip_prefix(prefix).map(String::from)
+}
+
+fn string_strip_suffix(this: &str, suffix: &str) -> Option<String> {
+    this.strip_suffix(suffix).map(String::from)
+}
+
+/// Use the string as a format string for the given arguments, see
+/// `std::fmt::format`.
+fn string_format(this: &str, args: Vec<Value>) -> Result<String, VmError> {
+    crate::modules::fmt::format_values(this, &args)
+}
+
+/// Pad the start of the string with `fill` until it's `width` characters
+/// long.
+fn string_pad_start(this: &str, width: usize, fill: char) -> String {
+    let padding = width.saturating_sub(this.chars().count());
+    let mut string = String::with_capacity(this.len() + padding * fill.len_utf8());
+    string.extend(std::iter::repeat(fill).take(padding));
+    string.push_str(this);
+    string
+}
+
+/// Pad the end of the string with `fill` until it's `width` characters long.
+fn string_pad_end(this: &str, width: usize, fill: char) -> String {
+    let padding = width.saturating_sub(this.chars().count());
+    let mut string = String::with_capacity(this.len() + padding * fill.len_utf8());
+    string.push_str(this);
+    string.extend(std::iter::repeat(fill).take(padding));
+    string
+}
+
 fn string_trim(this: &str) -> String {
     this.trim().to_owned()
 }
@@ -114,11 +286,55 @@ fn parse_char(s: &str) -> Result<char, std::char::ParseCharError> {
 }
 
 /// The add operation for strings.
-fn add(a: &str, b: &str) -> String {
-    let mut string = String::with_capacity(a.len() + b.len());
+fn add(a: &str, b: &str) -> Result<String, VmError> {
+    let mut string = string_with_capacity(a.len() + b.len())?;
     string.push_str(a);
     string.push_str(b);
-    string
+    Ok(string)
+}
+
+/// Construct a string with the given capacity, accounting for the memory it
+/// takes up.
+fn string_with_capacity(capacity: usize) -> Result<String, VmError> {
+    charge_allocation(capacity)?;
+    Ok(String::with_capacity(capacity))
+}
+
+/// Append a character to the string, accounting for the memory it takes up.
+fn string_push(this: &mut String, c: char) -> Result<(), VmError> {
+    charge_allocation(c.len_utf8())?;
+    this.push(c);
+    Ok(())
+}
+
+/// Append a string to the string, accounting for the memory it takes up.
+fn string_push_str(this: &mut String, s: &str) -> Result<(), VmError> {
+    charge_allocation(s.len())?;
+    this.push_str(s);
+    Ok(())
+}
+
+/// Reserve capacity for at least `additional` more bytes, accounting for the
+/// memory it takes up.
+fn string_reserve(this: &mut String, additional: usize) -> Result<(), VmError> {
+    charge_allocation(additional)?;
+    this.reserve(additional);
+    Ok(())
+}
+
+/// Reserve capacity for exactly `additional` more bytes, accounting for the
+/// memory it takes up.
+fn string_reserve_exact(this: &mut String, additional: usize) -> Result<(), VmError> {
+    charge_allocation(additional)?;
+    this.reserve_exact(additional);
+    Ok(())
+}
+
+/// Repeat the string `n` times, accounting for the memory the result takes
+/// up.
+fn string_repeat(this: &str, n: usize) -> Result<String, VmError> {
+    charge_allocation(this.len().saturating_mul(n))?;
+    Ok(this.repeat(n))
 }
 
 fn string_chars(s: &str) -> Iterator {
@@ -126,6 +342,11 @@ fn string_chars(s: &str) -> Iterator {
     Iterator::from_double_ended("std::str::Chars", iter)
 }
 
+fn string_char_indices(s: &str) -> Iterator {
+    let iter = s.char_indices().collect::<Vec<_>>().into_iter();
+    Iterator::from_double_ended("std::str::CharIndices", iter)
+}
+
 /// Get a specific string index.
 fn string_get(s: &str, key: Value) -> Result<Option<String>, VmError> {
     use crate::runtime::{FromValue, RangeLimits, TypeOf};
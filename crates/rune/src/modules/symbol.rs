@@ -0,0 +1,53 @@
+//! The `std::symbol` module.
+
+use crate::macros::{quote, MacroContext, TokenStream};
+use crate::runtime::{Protocol, Symbol};
+use crate::{ContextError, Module};
+use std::fmt;
+use std::fmt::Write as _;
+
+/// Construct the `std::symbol` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["symbol"]);
+
+    module.ty::<Symbol>()?;
+    module.function(["Symbol", "new"], Symbol::new)?;
+    module.inst_fn("as_str", as_str)?;
+    module.inst_fn(Protocol::EQ, eq)?;
+    module.inst_fn(Protocol::HASH, hash)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, string_debug)?;
+
+    module.macro_(["sym"], sym_macro)?;
+    Ok(module)
+}
+
+fn as_str(symbol: &Symbol) -> String {
+    symbol.as_str().to_owned()
+}
+
+fn eq(a: &Symbol, b: &Symbol) -> bool {
+    a == b
+}
+
+fn hash(symbol: &Symbol) -> i64 {
+    symbol.name_hash() as i64
+}
+
+fn string_display(symbol: &Symbol, s: &mut String) -> fmt::Result {
+    write!(s, "{}", symbol)
+}
+
+fn string_debug(symbol: &Symbol, s: &mut String) -> fmt::Result {
+    write!(s, "{:?}", symbol)
+}
+
+/// Implementation for the `sym!` macro.
+pub(crate) fn sym_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> crate::Result<TokenStream> {
+    use crate as rune;
+
+    Ok(quote!(::std::symbol::Symbol::new(#stream)).into_token_stream(ctx))
+}
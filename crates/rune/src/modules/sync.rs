@@ -0,0 +1,51 @@
+//! The `std::sync` module.
+
+use crate::runtime::channel::{self, Receiver, Sender};
+use crate::runtime::{FromValue, SendValue, Value, VmError};
+use crate::{ContextError, Module};
+
+/// Construct the `std::sync` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["sync", "mpsc"]);
+    module.ty::<Sender>()?;
+    module.ty::<Receiver>()?;
+    module.function(["channel"], channel::bounded)?;
+    module.function(["unbounded"], channel::unbounded)?;
+    module.async_inst_fn("send", send)?;
+    module.inst_fn("try_send", try_send)?;
+    module.inst_fn("clone", Sender::clone)?;
+    module.inst_fn("is_closed", Sender::is_closed)?;
+    module.async_inst_fn("recv", recv)?;
+    module.inst_fn("try_recv", try_recv)?;
+    module.inst_fn("clone", Receiver::clone)?;
+    module.inst_fn("is_closed", Receiver::is_closed)?;
+    module.inst_fn("len", Receiver::len)?;
+    module.inst_fn("is_empty", Receiver::is_empty)?;
+    Ok(module)
+}
+
+/// Send a value, waiting for the channel to have room for it. The value is
+/// handed back as an error if every receiver has been dropped.
+async fn send(sender: &Sender, value: Value) -> Result<Result<(), Value>, VmError> {
+    let value = SendValue::from_value(value)?;
+    Ok(sender.send(value).await.map_err(SendValue::into_value))
+}
+
+/// Try to send a value without waiting. The value is handed back as an error
+/// if the channel is full or every receiver has been dropped.
+fn try_send(sender: &Sender, value: Value) -> Result<Result<(), Value>, VmError> {
+    let value = SendValue::from_value(value)?;
+    Ok(sender
+        .try_send(value)
+        .map_err(|error| error.into_inner().into_value()))
+}
+
+/// Receive a value, waiting for one to be sent.
+async fn recv(receiver: &Receiver) -> Option<Value> {
+    receiver.recv().await.map(SendValue::into_value)
+}
+
+/// Try to receive a value without waiting.
+fn try_recv(receiver: &Receiver) -> Option<Value> {
+    receiver.try_recv().map(SendValue::into_value)
+}
@@ -0,0 +1,18 @@
+//! The `std::thread` module.
+
+use crate::runtime::executor;
+use crate::runtime::{Function, Future, VmError};
+use crate::{ContextError, Module};
+
+/// Construct the `std::thread` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["thread"]);
+    module.function(["spawn"], spawn)?;
+    Ok(module)
+}
+
+/// Spawn a blocking task calling the given function on the executor of the
+/// context, returning a future which resolves to the value it returns.
+fn spawn(function: &Function) -> Result<Future, VmError> {
+    executor::spawn_blocking(function.to_sync()?)
+}
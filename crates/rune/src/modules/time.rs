@@ -0,0 +1,124 @@
+//! The `std::time` module.
+
+use crate::runtime::{executor, host};
+use crate::runtime::{Future, Protocol, VmError};
+use crate::{Any, ContextError, Module};
+use std::fmt;
+use std::time;
+
+/// Construct the `std::time` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["time"]);
+    module.ty::<Duration>()?;
+    module.ty::<Elapsed>()?;
+    module.function(["Duration", "from_secs"], Duration::from_secs)?;
+    module.function(["Duration", "from_millis"], Duration::from_millis)?;
+    module.function(["Duration", "from_secs_f64"], Duration::from_secs_f64)?;
+    module.inst_fn("as_secs_f64", Duration::as_secs_f64)?;
+    module.inst_fn("as_millis", Duration::as_millis)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Duration::string_debug)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Elapsed::string_display)?;
+    module.function(["sleep"], sleep)?;
+    module.function(["now"], now)?;
+    Ok(module)
+}
+
+/// A span of time.
+///
+/// This is the `std::time::Duration` type seen by scripts, which native
+/// functions can accept to take a duration as an argument.
+#[derive(Any, Debug, Clone, Copy)]
+#[rune(module = "crate")]
+pub struct Duration {
+    inner: time::Duration,
+}
+
+impl From<time::Duration> for Duration {
+    fn from(inner: time::Duration) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<Duration> for time::Duration {
+    fn from(duration: Duration) -> Self {
+        duration.inner
+    }
+}
+
+impl Duration {
+    fn from_secs(secs: u64) -> Self {
+        Self {
+            inner: time::Duration::from_secs(secs),
+        }
+    }
+
+    fn from_millis(millis: u64) -> Self {
+        Self {
+            inner: time::Duration::from_millis(millis),
+        }
+    }
+
+    fn from_secs_f64(secs: f64) -> Result<Self, VmError> {
+        // NB: `Duration::from_secs_f64` panics on values it can't represent.
+        if !(secs.is_finite() && secs >= 0.0 && secs < u64::MAX as f64) {
+            return Err(VmError::panic(format!(
+                "invalid duration of {} seconds",
+                secs
+            )));
+        }
+
+        Ok(Self {
+            inner: time::Duration::from_secs_f64(secs),
+        })
+    }
+
+    fn as_secs_f64(&self) -> f64 {
+        self.inner.as_secs_f64()
+    }
+
+    fn as_millis(&self) -> u64 {
+        u64::try_from(self.inner.as_millis()).unwrap_or(u64::MAX)
+    }
+
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write as _;
+        write!(s, "{:?}", self.inner)
+    }
+
+    /// Get the wrapped duration.
+    pub(crate) fn as_inner(&self) -> time::Duration {
+        self.inner
+    }
+}
+
+/// Error produced when a future times out.
+#[derive(Any, Debug, Clone, Copy)]
+#[rune(module = "crate")]
+pub(crate) struct Elapsed(());
+
+impl Elapsed {
+    /// Construct a new timeout error.
+    pub(crate) fn new() -> Self {
+        Self(())
+    }
+
+    fn string_display(&self, s: &mut String) -> fmt::Result {
+        use std::fmt::Write as _;
+        write!(s, "deadline has elapsed")
+    }
+}
+
+/// Get the current time as a duration since the unix epoch.
+fn now() -> Duration {
+    Duration { inner: host::now() }
+}
+
+/// Construct a future which completes once the given duration has elapsed.
+fn sleep(duration: &Duration) -> Result<Future, VmError> {
+    let sleep = executor::sleep(duration.as_inner())?;
+
+    Ok(Future::new(async move {
+        sleep.await;
+        Ok::<_, VmError>(())
+    }))
+}
@@ -1,7 +1,9 @@
 //! The `std::vec` module.
 
-use crate::runtime::{Function, Protocol, TypeOf, Value, Vec};
+use crate::runtime::{charge_allocation, Function, Protocol, TypeOf, Value, Vec, VmError};
 use crate::{ContextError, Module, Params};
+use std::cmp;
+use std::mem;
 
 /// Construct the `std::vec` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -10,17 +12,29 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<Vec>()?;
 
     module.function(["Vec", "new"], Vec::new)?;
+    module.inst_fn("binary_search_by", binary_search_by)?;
     module.inst_fn("clear", Vec::clear)?;
-    module.inst_fn("clone", Vec::clone)?;
-    module.inst_fn("extend", Vec::extend)?;
+    module.inst_fn("clone", clone)?;
+    module.inst_fn("clone_deep", Vec::clone_deep)?;
+    module.inst_fn("dedup", dedup)?;
+    module.inst_fn("extend", extend)?;
     module.inst_fn("get", vec_get)?;
     module.inst_fn("iter", Vec::into_iterator)?;
     module.inst_fn("len", Vec::len)?;
+    module.inst_fn("max_by_key", max_by_key)?;
+    module.inst_fn("min_by_key", min_by_key)?;
     module.inst_fn("pop", Vec::pop)?;
-    module.inst_fn("push", Vec::push)?;
+    module.inst_fn("push", push)?;
     module.inst_fn("remove", Vec::remove)?;
+    module.inst_fn("retain", retain)?;
+    module.inst_fn("rotate_left", Vec::rotate_left)?;
+    module.inst_fn("rotate_right", Vec::rotate_right)?;
+    module.inst_fn("sort", sort)?;
     module.inst_fn("sort_by", sort_by)?;
-    module.inst_fn("insert", Vec::insert)?;
+    module.inst_fn("sort_by_key", sort_by_key)?;
+    module.inst_fn("splice", Vec::splice)?;
+    module.inst_fn("swap_remove", Vec::swap_remove)?;
+    module.inst_fn("insert", insert)?;
     module.inst_fn(Protocol::INTO_ITER, Vec::into_iterator)?;
     module.inst_fn(Protocol::INDEX_SET, Vec::set)?;
 
@@ -29,6 +43,34 @@ pub fn module() -> Result<Module, ContextError> {
     Ok(module)
 }
 
+/// Append a value to the vector, accounting for the memory it takes up.
+fn push(vec: &mut Vec, value: Value) -> Result<(), VmError> {
+    charge_allocation(mem::size_of::<Value>())?;
+    vec.push(value);
+    Ok(())
+}
+
+/// Insert a value into the vector, accounting for the memory it takes up.
+fn insert(vec: &mut Vec, index: usize, value: Value) -> Result<(), VmError> {
+    charge_allocation(mem::size_of::<Value>())?;
+    vec.insert(index, value);
+    Ok(())
+}
+
+/// Extend the vector, accounting for the memory taken up by the values it's
+/// extended with.
+fn extend(vec: &mut Vec, value: Value) -> Result<(), VmError> {
+    let len = vec.len();
+    vec.extend(value)?;
+    charge_allocation((vec.len() - len) * mem::size_of::<Value>())
+}
+
+/// Clone the vector, accounting for the memory taken up by the copy.
+fn clone(vec: &Vec) -> Result<Vec, VmError> {
+    charge_allocation(vec.len() * mem::size_of::<Value>())?;
+    Ok(vec.clone())
+}
+
 /// Sort a vector of integers.
 fn sort_int(vec: &mut Vec) {
     vec.sort_by(|a, b| match (a, b) {
@@ -38,14 +80,180 @@ fn sort_int(vec: &mut Vec) {
     });
 }
 
-fn vec_get(vec: &Vec, index: usize) -> Option<Value> {
-    vec.get(index).cloned()
+/// Sort a vector of values using [Value::cmp].
+fn sort(vec: &mut Vec) -> Result<(), VmError> {
+    sort_with(vec, Value::cmp)
+}
+
+/// Sort a vector using a comparator function returning an `Ordering`.
+fn sort_by(vec: &mut Vec, comparator: &Function) -> Result<(), VmError> {
+    sort_with(vec, |a, b| comparator.call::<_, cmp::Ordering>((a, b)))
+}
+
+/// Sort a vector by the key produced by the given function, which is called
+/// exactly once per element.
+fn sort_by_key(vec: &mut Vec, key: &Function) -> Result<(), VmError> {
+    let mut keyed = std::vec::Vec::with_capacity(vec.len());
+
+    for value in vec.iter() {
+        keyed.push((key.call::<_, Value>((value,))?, value.clone()));
+    }
+
+    let mut error = None;
+
+    keyed.sort_by(|(a, _), (b, _)| match Value::cmp(a, b) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            error.get_or_insert(e);
+            cmp::Ordering::Equal
+        }
+    });
+
+    for (slot, (_, value)) in vec.iter_mut().zip(keyed) {
+        *slot = value;
+    }
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Sort using a fallible comparison, reporting the first error encountered.
+fn sort_with<F>(vec: &mut Vec, mut compare: F) -> Result<(), VmError>
+where
+    F: FnMut(&Value, &Value) -> Result<cmp::Ordering, VmError>,
+{
+    let mut error = None;
+
+    vec.sort_by(|a, b| match compare(a, b) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            error.get_or_insert(e);
+            cmp::Ordering::Equal
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Binary search a sorted vector with a function comparing each probed
+/// element to the target, returning `Ok(index)` of a match or `Err(index)`
+/// where it could be inserted.
+fn binary_search_by(vec: &Vec, comparator: &Function) -> Result<Result<usize, usize>, VmError> {
+    let mut error = None;
+
+    let result = vec.binary_search_by(|value| {
+        if error.is_some() {
+            return cmp::Ordering::Equal;
+        }
+
+        match comparator.call::<_, cmp::Ordering>((value,)) {
+            Ok(ordering) => ordering,
+            Err(e) => {
+                error = Some(e);
+                cmp::Ordering::Equal
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(result),
+    }
+}
+
+/// The last element with the greatest key, like `Iterator::max_by_key` in
+/// Rust.
+fn max_by_key(vec: &Vec, key: &Function) -> Result<Option<Value>, VmError> {
+    extreme_by_key(vec, key, true)
+}
+
+/// The first element with the smallest key, like `Iterator::min_by_key` in
+/// Rust.
+fn min_by_key(vec: &Vec, key: &Function) -> Result<Option<Value>, VmError> {
+    extreme_by_key(vec, key, false)
+}
+
+fn extreme_by_key(vec: &Vec, key: &Function, max: bool) -> Result<Option<Value>, VmError> {
+    let mut best = None::<(Value, &Value)>;
+
+    for value in vec.iter() {
+        let k = key.call::<_, Value>((value,))?;
+
+        let replace = match &best {
+            Some((best, _)) => {
+                let ordering = Value::cmp(best, &k)?;
+
+                if max {
+                    ordering != cmp::Ordering::Greater
+                } else {
+                    ordering == cmp::Ordering::Greater
+                }
+            }
+            None => true,
+        };
+
+        if replace {
+            best = Some((k, value));
+        }
+    }
+
+    Ok(best.map(|(_, value)| value.clone()))
 }
 
-fn sort_by(vec: &mut Vec, comparator: &Function) {
-    vec.sort_by(|a, b| {
-        comparator
-            .call::<_, std::cmp::Ordering>((a, b))
-            .unwrap_or(std::cmp::Ordering::Equal)
-    })
+/// Retain only the elements for which the predicate returns `true`.
+fn retain(vec: &mut Vec, predicate: &Function) -> Result<(), VmError> {
+    let mut error = None;
+
+    vec.retain(|value| {
+        if error.is_some() {
+            return true;
+        }
+
+        match predicate.call::<_, bool>((value,)) {
+            Ok(retain) => retain,
+            Err(e) => {
+                error = Some(e);
+                true
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Remove consecutive repeated elements, where elements are considered the
+/// same if they compare as equal using [Value::partial_cmp].
+fn dedup(vec: &mut Vec) -> Result<(), VmError> {
+    let mut error = None;
+
+    vec.dedup_by(|a, b| {
+        if error.is_some() {
+            return false;
+        }
+
+        match Value::partial_cmp(a, b) {
+            Ok(ordering) => ordering == Some(cmp::Ordering::Equal),
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+fn vec_get(vec: &Vec, index: usize) -> Option<Value> {
+    vec.get(index).cloned()
 }
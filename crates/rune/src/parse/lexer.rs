@@ -769,7 +769,14 @@ impl<'a> Lexer<'a> {
                     }
                     '[' => ast::Kind::Open(ast::Delimiter::Bracket),
                     ']' => ast::Kind::Close(ast::Delimiter::Bracket),
-                    '_' => ast::Kind::Underscore,
+                    // NB: an underscore is only an identifier if it's followed
+                    // by more of one, like `_unused`.
+                    '_' => match self.iter.peek() {
+                        Some('a'..='z' | 'A'..='Z' | '_' | '0'..='9') => {
+                            return self.next_ident(start);
+                        }
+                        _ => ast::Kind::Underscore,
+                    },
                     ',' => ast::Kind::Comma,
                     ':' => ast::Kind::Colon,
                     '#' => ast::Kind::Pound,
@@ -1229,6 +1236,19 @@ mod tests {
                 kind: ast::Kind::Close(ast::Delimiter::Parenthesis),
             },
         };
+
+        test_lexer! {
+            "_a _",
+            ast::Token {
+                span: span!(0, 2),
+                kind: ast::Kind::Ident(ast::LitSource::Text(SourceId::EMPTY)),
+            },
+            _,
+            ast::Token {
+                span: span!(3, 4),
+                kind: ast::Kind::Underscore,
+            },
+        };
     }
 
     #[test]
@@ -45,6 +45,47 @@ impl ParseError {
     }
 }
 
+impl ParseError {
+    /// Test if the error was caused by the input ending in the middle of a
+    /// construct, like an unclosed brace, parenthesis or string literal.
+    ///
+    /// This is what distinguishes input which is incomplete from input which
+    /// is invalid, and can for example be used by a REPL to decide whether to
+    /// prompt for more input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::ast;
+    /// use rune::parse::parse_all;
+    /// use rune::SourceId;
+    ///
+    /// let error = parse_all::<ast::File>("fn main() {", SourceId::empty(), false).unwrap_err();
+    /// assert!(error.is_incomplete());
+    ///
+    /// let error = parse_all::<ast::File>("const S = \"hello", SourceId::empty(), false).unwrap_err();
+    /// assert!(error.is_incomplete());
+    ///
+    /// let error = parse_all::<ast::File>("fn main() { ) }", SourceId::empty(), false).unwrap_err();
+    /// assert!(!error.is_incomplete());
+    /// ```
+    pub fn is_incomplete(&self) -> bool {
+        match &*self.kind {
+            ParseErrorKind::UnexpectedEof
+            | ParseErrorKind::UnterminatedStrLit
+            | ParseErrorKind::UnterminatedByteStrLit
+            | ParseErrorKind::ExpectedMultilineCommentTerm => true,
+            // This is how the end of input is described when it's encountered
+            // in place of an expected token.
+            ParseErrorKind::Expected {
+                actual: Expectation::Description("eof"),
+                ..
+            } => true,
+            _ => false,
+        }
+    }
+}
+
 impl From<ParseError> for SpannedError {
     fn from(error: ParseError) -> Self {
         SpannedError::new(error.span, *error.kind)
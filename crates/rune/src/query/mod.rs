@@ -123,6 +123,8 @@ pub(crate) struct QueryInner {
 ///
 /// Once an item is queried for it is queued up for compilation.
 pub(crate) struct Query<'a> {
+    /// The context used for the compilation.
+    pub(crate) context: &'a Context,
     /// The current unit being built.
     pub(crate) unit: &'a mut UnitBuilder,
     /// The prelude in effect.
@@ -146,6 +148,7 @@ pub(crate) struct Query<'a> {
 impl<'a> Query<'a> {
     /// Construct a new compilation context.
     pub(crate) fn new(
+        context: &'a Context,
         unit: &'a mut UnitBuilder,
         prelude: &'a Prelude,
         consts: &'a mut Consts,
@@ -157,6 +160,7 @@ impl<'a> Query<'a> {
         inner: &'a mut QueryInner,
     ) -> Self {
         Self {
+            context,
             unit,
             prelude,
             consts,
@@ -172,6 +176,7 @@ impl<'a> Query<'a> {
     /// Reborrow the query engine from a reference to `self`.
     pub(crate) fn borrow(&mut self) -> Query<'_> {
         Query {
+            context: self.context,
             unit: self.unit,
             prelude: self.prelude,
             consts: self.consts,
@@ -325,6 +330,8 @@ impl<'a> Query<'a> {
             }
         }
 
+        self.visitor.visit_item(location, self.pool.item(item));
+
         let item_meta = ItemMeta {
             location,
             id: Id::new(id),
@@ -646,6 +653,8 @@ impl<'a> Query<'a> {
                 args,
                 is_test: false,
                 is_bench: false,
+                is_ignored: false,
+                should_panic: false,
                 instance_function,
             },
             ContextMetaKind::Const { ref const_value } => PrivMetaKind::Const {
@@ -1074,6 +1083,8 @@ impl<'a> Query<'a> {
                     args: Some(f.function.ast.args.len()),
                     is_test: f.is_test,
                     is_bench: f.is_bench,
+                    is_ignored: f.is_ignored,
+                    should_panic: f.should_panic,
                     instance_function: false,
                 };
 
@@ -1092,6 +1103,8 @@ impl<'a> Query<'a> {
                     args: Some(f.function.ast.args.len()),
                     is_test: false,
                     is_bench: false,
+                    is_ignored: false,
+                    should_panic: false,
                     instance_function: true,
                 };
 
@@ -1546,6 +1559,10 @@ pub(crate) struct IndexedFunction {
     pub(crate) is_test: bool,
     /// If this is a bench function.
     pub(crate) is_bench: bool,
+    /// If this test or bench function is ignored by default.
+    pub(crate) is_ignored: bool,
+    /// If this test function is expected to panic.
+    pub(crate) should_panic: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,145 @@
+//! Values provided by the host to an execution.
+//!
+//! See [Ambient] for more information.
+
+use crate::collections::HashMap;
+use crate::runtime::{env, FromValue, SendValue, ToValue, Value, VmError};
+use std::fmt;
+
+/// A collection of named values which the host provides to an execution,
+/// like the context of the request being served, a logger or the identity of
+/// the current user.
+///
+/// Ambient values are installed on a virtual machine through
+/// [Vm::set_ambient] and can be retrieved by name from anywhere in the
+/// scripts it executes through `std::env::ambient`, without having to be
+/// passed through every function which needs them. They are shared with the
+/// virtual machines spawned to execute generators, streams, async functions
+/// and function values. Functions spawned through `std::thread::spawn` or
+/// `std::future::spawn` are provided with a copy of them, so spawning fails if
+/// one of them can't be represented as a [SendValue].
+///
+/// Native functions can retrieve them through [get].
+///
+/// [Vm::set_ambient]: crate::Vm::set_ambient
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::Ambient;
+/// use rune::{Context, FromValue, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> rune::Result<()> {
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn greeting() {
+///             format!("Hello, {}!", std::env::ambient("user").unwrap_or("stranger"))
+///         }
+///
+///         pub fn main() {
+///             greeting()
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+///
+/// let output = String::from_value(vm.call(["main"], ())?)?;
+/// assert_eq!(output, "Hello, stranger!");
+///
+/// let mut ambient = Ambient::new();
+/// ambient.insert("user", "John")?;
+/// vm.set_ambient(ambient);
+///
+/// let output = String::from_value(vm.call(["main"], ())?)?;
+/// assert_eq!(output, "Hello, John!");
+/// # Ok(()) }
+/// ```
+#[derive(Default)]
+pub struct Ambient {
+    values: HashMap<String, Value>,
+}
+
+impl Ambient {
+    /// Construct an empty collection of ambient values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value under the given name, replacing any value previously
+    /// stored under it.
+    pub fn insert<T>(&mut self, name: impl Into<String>, value: T) -> Result<(), VmError>
+    where
+        T: ToValue,
+    {
+        self.values.insert(name.into(), value.to_value()?);
+        Ok(())
+    }
+
+    /// Get the value stored under the given name.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+
+    /// Remove the value stored under the given name, returning it if present.
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        self.values.remove(name)
+    }
+
+    /// The number of values stored.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Test if no values are stored.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Copy the values into a form which can be sent to another thread.
+    pub(crate) fn to_send(&self) -> Result<Vec<(String, SendValue)>, VmError> {
+        self.values
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), SendValue::from_ref(value)?)))
+            .collect()
+    }
+
+    /// Construct ambient values from ones which were sent from another
+    /// thread.
+    pub(crate) fn from_send(values: Vec<(String, SendValue)>) -> Self {
+        Self {
+            values: values
+                .into_iter()
+                .map(|(name, value)| (name, value.into_value()))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Debug for Ambient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.values.keys()).finish()
+    }
+}
+
+/// Get the ambient value stored under the given name in the virtual machine
+/// which is currently executing, converted into `T`.
+///
+/// This errors if called outside of a virtual machine, or if the value can't
+/// be converted.
+pub fn get<T>(name: &str) -> Result<Option<T>, VmError>
+where
+    T: FromValue,
+{
+    let value = env::with_ambient(|ambient| ambient.and_then(|a| a.get(name)).cloned())?;
+
+    match value {
+        Some(value) => Ok(Some(T::from_value(value)?)),
+        None => Ok(None),
+    }
+}
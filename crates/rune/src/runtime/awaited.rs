@@ -1,4 +1,7 @@
 use crate::runtime::{Future, Select, Shared, ToValue, Vm, VmError};
+use std::future::{self, Future as _};
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 /// A stored await task.
 #[derive(Debug)]
@@ -11,21 +14,39 @@ pub(crate) enum Awaited {
 
 impl Awaited {
     /// Wait for the given awaited into the specified virtual machine.
-    pub(crate) async fn into_vm(self, vm: &mut Vm) -> Result<(), VmError> {
+    pub(crate) async fn into_vm(mut self, vm: &mut Vm) -> Result<(), VmError> {
+        future::poll_fn(|cx| self.poll_into_vm(vm, cx)).await
+    }
+
+    /// Poll the awaited task, pushing its output into the specified virtual
+    /// machine once it completes.
+    pub(crate) fn poll_into_vm(
+        &mut self,
+        vm: &mut Vm,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), VmError>> {
         match self {
             Self::Future(future) => {
-                let value = future.borrow_mut()?.await?;
+                let value = match Pin::new(&mut *future.borrow_mut()?).poll(cx) {
+                    Poll::Ready(value) => value?,
+                    Poll::Pending => return Poll::Pending,
+                };
+
                 vm.stack_mut().push(value);
                 vm.advance();
             }
             Self::Select(select) => {
-                let (branch, value) = select.await?;
+                let (branch, value) = match Pin::new(select).poll(cx) {
+                    Poll::Ready(output) => output?,
+                    Poll::Pending => return Poll::Pending,
+                };
+
                 vm.stack_mut().push(value);
                 vm.stack_mut().push(ToValue::to_value(branch)?);
                 vm.advance();
             }
         }
 
-        Ok(())
+        Poll::Ready(Ok(()))
     }
 }
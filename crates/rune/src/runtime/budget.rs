@@ -29,6 +29,11 @@ pub fn with<T>(budget: usize, value: T) -> Budget<T> {
     Budget { budget, value }
 }
 
+/// Test if the current budget has been used up.
+pub(crate) fn exhausted() -> bool {
+    BUDGET.with(|tls| tls.get() == 0)
+}
+
 /// Take a ticket from the budget, indicating with `true` if the budget is
 /// maintained
 #[inline(never)]
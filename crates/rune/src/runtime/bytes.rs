@@ -4,7 +4,7 @@
 
 use crate::compile::{InstallWith, Named};
 use crate::runtime::{
-    FromValue, Mut, RawMut, RawRef, RawStr, Ref, UnsafeFromValue, Value, VmError,
+    FromValue, Mut, RawMut, RawRef, RawStr, Ref, Shared, UnsafeFromValue, Value, VmError,
 };
 use serde::{Deserialize, Serialize};
 use std::cmp;
@@ -132,6 +132,12 @@ impl FromValue for Bytes {
     }
 }
 
+impl FromValue for Shared<Bytes> {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        value.into_bytes()
+    }
+}
+
 impl<'a> UnsafeFromValue for &'a Bytes {
     type Output = *const Bytes;
     type Guard = RawRef;
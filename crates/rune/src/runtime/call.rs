@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// The calling convention of a function.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum Call {
     /// Function is `async` and returns a future that must be await:ed to make
@@ -25,7 +25,7 @@ impl Call {
             Call::Stream => Value::from(Stream::new(vm)),
             Call::Generator => Value::from(Generator::new(vm)),
             Call::Immediate => vm.complete()?,
-            Call::Async => Value::from(Future::new(vm.async_complete())),
+            Call::Async => Value::from(Future::new(vm.async_complete_awaited())),
         })
     }
 }
@@ -0,0 +1,141 @@
+use crate::collections::HashSet;
+use crate::runtime::{env, FunctionHandler, VmError, VmErrorKind};
+use crate::Hash;
+use std::fmt;
+use std::sync::Arc;
+
+/// A set of capabilities granted to a virtual machine.
+///
+/// Modules can be gated behind a named capability through
+/// [Module::with_capability], like `fs`, `net` or `process`. Once a virtual
+/// machine has been restricted to a set of capabilities through
+/// [Vm::set_capabilities], calling a native function from a module whose
+/// capability is not in the set results in a
+/// [VmErrorKind::MissingCapability] error.
+///
+/// Virtual machines which have not been restricted can call every function.
+/// Restrictions are shared with the virtual machines started to execute
+/// generators, streams, async functions, function values called by native
+/// functions and functions spawned through `std::thread::spawn` or
+/// `std::future::spawn`. Gated functions can't be called outside of a virtual
+/// machine, since it is unknown which capabilities have been granted there.
+///
+/// [Module::with_capability]: crate::Module::with_capability
+/// [Vm::set_capabilities]: crate::Vm::set_capabilities
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{Capabilities, VmErrorKind};
+/// use rune::{Context, FromValue, Module, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> rune::Result<()> {
+/// let mut module = Module::with_item(["secret"]).with_capability("secret");
+/// module.function(["read"], || 42i64)?;
+///
+/// let mut context = Context::with_default_modules()?;
+/// context.install(&module)?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             secret::read()
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+///
+/// vm.set_capabilities(Capabilities::new());
+/// let error = vm.call(["main"], ()).unwrap_err();
+///
+/// assert!(matches!(
+///     error.as_unwound().0,
+///     VmErrorKind::MissingCapability { capability, .. } if &**capability == "secret"
+/// ));
+///
+/// vm.set_capabilities(Capabilities::new().with("secret"));
+/// assert_eq!(i64::from_value(vm.call(["main"], ())?)?, 42);
+/// # Ok(()) }
+/// ```
+#[derive(Default, Clone)]
+pub struct Capabilities {
+    names: HashSet<Box<str>>,
+}
+
+impl Capabilities {
+    /// Construct an empty set of capabilities, which only permits calling
+    /// functions which are not gated.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grant the given capability.
+    pub fn with(mut self, name: &str) -> Self {
+        self.insert(name);
+        self
+    }
+
+    /// Grant the given capability.
+    pub fn insert(&mut self, name: &str) {
+        self.names.insert(name.into());
+    }
+
+    /// Revoke the given capability, returning `true` if it was granted.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.names.remove(name)
+    }
+
+    /// Test if the given capability is granted.
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Capabilities {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        Self {
+            names: iter.into_iter().map(Box::from).collect(),
+        }
+    }
+}
+
+impl fmt::Debug for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.names).finish()
+    }
+}
+
+/// Wrap the handler of a native function so that calling it requires the
+/// given capability.
+pub(crate) fn gate(
+    capability: &'static str,
+    hash: Hash,
+    handler: &Arc<FunctionHandler>,
+) -> Arc<FunctionHandler> {
+    let handler = handler.clone();
+
+    Arc::new(move |stack, args| {
+        // NB: outside of a virtual machine it is unknown which capabilities
+        // have been granted, so access is denied.
+        let granted = env::with_capabilities(|capabilities| {
+            capabilities.map_or(true, |c| c.contains(capability))
+        })
+        .unwrap_or(false);
+
+        if !granted {
+            return Err(VmError::from(VmErrorKind::MissingCapability {
+                capability: capability.into(),
+                hash,
+            }));
+        }
+
+        handler(stack, args)
+    })
+}
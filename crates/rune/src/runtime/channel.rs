@@ -0,0 +1,335 @@
+//! Channels for communicating between tasks.
+//!
+//! Channels transfer [SendValue]s, so their endpoints can be shared between
+//! tasks executing on different threads, like the ones spawned through an
+//! [Executor][crate::runtime::Executor]. Both endpoints can be cloned, and
+//! they are exposed to scripts as `std::sync::mpsc::Sender` and
+//! `std::sync::mpsc::Receiver` through `std::sync::mpsc::channel` and
+//! `std::sync::mpsc::unbounded`.
+//!
+//! Since receiving produces a future, it can be combined with other futures
+//! in a `select` expression.
+//!
+//! # Examples
+//!
+//! ```
+//! use rune::runtime::channel;
+//! use rune::runtime::SendValue;
+//! use rune::{Context, FromValue, Vm};
+//! use std::sync::Arc;
+//!
+//! # fn main() -> rune::Result<()> {
+//! let context = Context::with_default_modules()?;
+//! let runtime = Arc::new(context.runtime());
+//!
+//! let mut sources = rune::sources! {
+//!     entry => {
+//!         pub fn main(sender) {
+//!             for n in 0..3 {
+//!                 sender.try_send(n)?;
+//!             }
+//!         }
+//!     }
+//! };
+//!
+//! let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+//! let mut vm = Vm::new(runtime, Arc::new(unit));
+//!
+//! let (sender, receiver) = channel::bounded(4);
+//! vm.call(["main"], (sender,))?;
+//!
+//! let mut values = Vec::new();
+//!
+//! while let Some(SendValue::Integer(n)) = receiver.try_recv() {
+//!     values.push(n);
+//! }
+//!
+//! assert_eq!(values, [0, 1, 2]);
+//! # Ok(()) }
+//! ```
+
+use crate::runtime::SendValue;
+use crate::Any;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future;
+use std::mem;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Poll, Waker};
+
+/// Construct a channel which holds at most `capacity` values which haven't
+/// been received yet.
+///
+/// A capacity of zero is treated as a capacity of one.
+pub fn bounded(capacity: usize) -> (Sender, Receiver) {
+    channel(Some(capacity.max(1)))
+}
+
+/// Construct a channel which can hold any number of values.
+pub fn unbounded() -> (Sender, Receiver) {
+    channel(None)
+}
+
+fn channel(capacity: Option<usize>) -> (Sender, Receiver) {
+    let channel = Arc::new(Channel {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            capacity,
+            senders: 1,
+            receivers: 1,
+            send_wakers: Vec::new(),
+            recv_wakers: Vec::new(),
+        }),
+    });
+
+    let sender = Sender {
+        channel: channel.clone(),
+    };
+
+    let receiver = Receiver { channel };
+    (sender, receiver)
+}
+
+/// Error raised when trying to send a value over a channel.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TrySendError {
+    /// The channel is full.
+    Full(SendValue),
+    /// Every receiver has been dropped.
+    Closed(SendValue),
+}
+
+impl TrySendError {
+    /// Get the value which couldn't be sent.
+    pub fn into_inner(self) -> SendValue {
+        match self {
+            Self::Full(value) | Self::Closed(value) => value,
+        }
+    }
+}
+
+/// The sending half of a channel.
+#[derive(Any)]
+#[rune(module = "crate")]
+pub struct Sender {
+    channel: Arc<Channel>,
+}
+
+impl Sender {
+    /// Send a value, waiting for the channel to have room for it.
+    ///
+    /// The value is handed back if every receiver has been dropped.
+    pub async fn send(&self, value: SendValue) -> Result<(), SendValue> {
+        let mut value = Some(value);
+
+        future::poll_fn(|cx| {
+            let mut state = self.channel.lock();
+
+            if state.receivers == 0 {
+                return Poll::Ready(Err(value.take().expect("polled after completion")));
+            }
+
+            if state.is_full() {
+                register(&mut state.send_wakers, cx.waker());
+                return Poll::Pending;
+            }
+
+            state
+                .queue
+                .push_back(value.take().expect("polled after completion"));
+            wake_all(state, |state| &mut state.recv_wakers);
+            Poll::Ready(Ok(()))
+        })
+        .await
+    }
+
+    /// Try to send a value without waiting.
+    pub fn try_send(&self, value: SendValue) -> Result<(), TrySendError> {
+        let mut state = self.channel.lock();
+
+        if state.receivers == 0 {
+            return Err(TrySendError::Closed(value));
+        }
+
+        if state.is_full() {
+            return Err(TrySendError::Full(value));
+        }
+
+        state.queue.push_back(value);
+        wake_all(state, |state| &mut state.recv_wakers);
+        Ok(())
+    }
+
+    /// Test if every receiver has been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.channel.lock().receivers == 0
+    }
+}
+
+impl Clone for Sender {
+    fn clone(&self) -> Self {
+        self.channel.lock().senders += 1;
+
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl Drop for Sender {
+    fn drop(&mut self) {
+        let mut state = self.channel.lock();
+        state.senders -= 1;
+
+        if state.senders == 0 {
+            wake_all(state, |state| &mut state.recv_wakers);
+        }
+    }
+}
+
+impl fmt::Debug for Sender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of a channel.
+#[derive(Any)]
+#[rune(module = "crate")]
+pub struct Receiver {
+    channel: Arc<Channel>,
+}
+
+impl Receiver {
+    /// Receive a value, waiting for one to be sent.
+    ///
+    /// Returns `None` once every sender has been dropped and the channel is
+    /// empty.
+    pub async fn recv(&self) -> Option<SendValue> {
+        future::poll_fn(|cx| {
+            let mut state = self.channel.lock();
+
+            if let Some(value) = state.queue.pop_front() {
+                wake_all(state, |state| &mut state.send_wakers);
+                return Poll::Ready(Some(value));
+            }
+
+            if state.senders == 0 {
+                return Poll::Ready(None);
+            }
+
+            register(&mut state.recv_wakers, cx.waker());
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Try to receive a value without waiting.
+    pub fn try_recv(&self) -> Option<SendValue> {
+        let mut state = self.channel.lock();
+        let value = state.queue.pop_front()?;
+        wake_all(state, |state| &mut state.send_wakers);
+        Some(value)
+    }
+
+    /// The number of values waiting to be received.
+    pub fn len(&self) -> usize {
+        self.channel.lock().queue.len()
+    }
+
+    /// Test if there are no values waiting to be received.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Test if every sender has been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.channel.lock().senders == 0
+    }
+}
+
+impl Clone for Receiver {
+    fn clone(&self) -> Self {
+        self.channel.lock().receivers += 1;
+
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl Drop for Receiver {
+    fn drop(&mut self) {
+        let mut state = self.channel.lock();
+        state.receivers -= 1;
+
+        if state.receivers == 0 {
+            wake_all(state, |state| &mut state.send_wakers);
+        }
+    }
+}
+
+impl fmt::Debug for Receiver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+struct Channel {
+    state: Mutex<State>,
+}
+
+impl Channel {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(error) => error.into_inner(),
+        }
+    }
+}
+
+struct State {
+    /// Values which haven't been received yet.
+    queue: VecDeque<SendValue>,
+    /// The maximum number of values in the queue, if bounded.
+    capacity: Option<usize>,
+    /// The number of live senders.
+    senders: usize,
+    /// The number of live receivers.
+    receivers: usize,
+    /// Tasks waiting for room in the queue.
+    send_wakers: Vec<Waker>,
+    /// Tasks waiting for a value to be sent.
+    recv_wakers: Vec<Waker>,
+}
+
+impl State {
+    fn is_full(&self) -> bool {
+        matches!(self.capacity, Some(capacity) if self.queue.len() >= capacity)
+    }
+}
+
+/// Register a waker, unless an equivalent one is already registered.
+fn register(wakers: &mut Vec<Waker>, waker: &Waker) {
+    if !wakers.iter().any(|w| w.will_wake(waker)) {
+        wakers.push(waker.clone());
+    }
+}
+
+/// Wake all tasks registered in the given set of wakers, after the state has
+/// been unlocked.
+///
+/// Woken tasks re-check the state of the channel, so waking more tasks than
+/// necessary is harmless.
+fn wake_all<F>(mut state: MutexGuard<'_, State>, wakers: F)
+where
+    F: FnOnce(&mut State) -> &mut Vec<Waker>,
+{
+    let wakers = mem::take(wakers(&mut state));
+    drop(state);
+
+    for waker in wakers {
+        waker.wake();
+    }
+}
@@ -0,0 +1,465 @@
+//! Collection of reference cycles between values.
+//!
+//! Values are reference counted, so values which directly or indirectly
+//! reference themselves, like an object storing a closure which captures the
+//! object, are never freed. This module provides an optional collector which
+//! can reclaim such cycles.
+//!
+//! Tracking is disabled by default, and is enabled for a virtual machine by
+//! installing a [CycleCollector] through [Vm::set_cycle_collector]. While
+//! installed, the virtual machine keeps track of containers which are mutated,
+//! since that is the only way a cycle can be introduced.
+//!
+//! Tracked values are only kept for as long as an execution is running. Once
+//! every execution using the collector has finished, every unreachable cycle
+//! among them is freed and the remaining values are released. Cycles which are
+//! still reachable at that point, like ones returned to the host, are not
+//! collected.
+//!
+//! # Examples
+//!
+//! ```
+//! use rune::runtime::cycles::CycleCollector;
+//! use rune::{Context, Vm};
+//! use std::rc::Rc;
+//! use std::sync::Arc;
+//!
+//! # fn main() -> rune::Result<()> {
+//! let context = Context::with_default_modules()?;
+//! let runtime = Arc::new(context.runtime());
+//!
+//! let mut sources = rune::sources!(entry => {
+//!     pub fn main() {
+//!         let a = #{};
+//!         a.this = a;
+//!     }
+//! });
+//!
+//! let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+//! let mut vm = Vm::new(runtime, Arc::new(unit));
+//!
+//! let collector = Rc::new(CycleCollector::new());
+//! vm.set_cycle_collector(collector.clone());
+//!
+//! vm.call(["main"], ())?;
+//! assert_eq!(collector.collected(), 1);
+//! assert_eq!(collector.tracked(), 0);
+//! # Ok(()) }
+//! ```
+//!
+//! [Vm::set_cycle_collector]: crate::Vm::set_cycle_collector
+
+use crate::collections::HashMap;
+use crate::runtime::{AccessError, GeneratorState, Shared, Value, VariantData};
+use std::cell::RefCell;
+use std::fmt;
+use std::mem;
+use std::rc::Rc;
+
+/// Collects reference cycles between the values of the virtual machines it is
+/// installed in.
+///
+/// See the [module level documentation][self] for more information.
+#[derive(Default)]
+pub struct CycleCollector {
+    state: RefCell<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Possible roots of cycles.
+    roots: Vec<Value>,
+    /// Automatically collect once this many values are tracked.
+    threshold: Option<usize>,
+    /// The number of roots which survived the last collection.
+    retained: usize,
+    /// The number of executions which are running.
+    executions: usize,
+    /// The number of values which have been freed.
+    collected: usize,
+}
+
+impl CycleCollector {
+    /// Construct a new collector which collects cycles once every execution
+    /// using it has finished.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct a new collector which also collects cycles while executing,
+    /// once `threshold` values have been tracked since the last collection.
+    pub fn with_threshold(threshold: usize) -> Self {
+        let collector = Self::new();
+        collector.state.borrow_mut().threshold = Some(threshold);
+        collector
+    }
+
+    /// The number of values currently tracked as possible roots of cycles.
+    pub fn tracked(&self) -> usize {
+        self.state.borrow().roots.len()
+    }
+
+    /// The total number of values which have been freed by this collector.
+    pub fn collected(&self) -> usize {
+        self.state.borrow().collected
+    }
+
+    /// Collect every unreachable cycle among the tracked values, returning the
+    /// number of values which were freed.
+    ///
+    /// Values which are currently being accessed are conservatively treated as
+    /// reachable. Tracked values which are still reachable remain tracked until
+    /// every execution has finished.
+    pub fn collect(&self) -> usize {
+        let roots = mem::take(&mut self.state.borrow_mut().roots);
+        let (count, retained) = collect(roots);
+
+        let mut state = self.state.borrow_mut();
+        state.retained = retained.len();
+        state.roots.extend(retained);
+        state.collected += count;
+        count
+    }
+
+    /// Track the given value as a possible root of a cycle.
+    #[inline]
+    pub(crate) fn track(&self, value: &Value) {
+        if identity(value).is_some() {
+            self.track_slow(value.clone());
+        }
+    }
+
+    /// Track every value in the given slice as a possible root of a cycle.
+    #[inline]
+    pub(crate) fn track_all(&self, values: &[Value]) {
+        for value in values {
+            self.track(value);
+        }
+    }
+
+    fn track_slow(&self, value: Value) {
+        let collect = {
+            let mut state = self.state.borrow_mut();
+            state.roots.push(value);
+
+            match state.threshold {
+                Some(threshold) => state.roots.len() >= state.retained.saturating_add(threshold),
+                None => false,
+            }
+        };
+
+        if collect {
+            self.collect();
+        }
+    }
+
+    /// Enter an execution, which keeps the tracked values around until the
+    /// returned guard is dropped.
+    pub(crate) fn enter(self: &Rc<Self>) -> Execution {
+        self.state.borrow_mut().executions += 1;
+        Execution(self.clone())
+    }
+}
+
+impl fmt::Debug for CycleCollector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // NB: tracked values are not printed, since they might be cyclic.
+        f.debug_struct("CycleCollector")
+            .field("tracked", &self.tracked())
+            .field("collected", &self.collected())
+            .finish()
+    }
+}
+
+/// Guard for an execution using a [CycleCollector].
+///
+/// Once the last execution has finished, cycles are collected and the values
+/// which are still tracked are released.
+pub(crate) struct Execution(Rc<CycleCollector>);
+
+impl Drop for Execution {
+    fn drop(&mut self) {
+        let finished = {
+            let mut state = self.0.state.borrow_mut();
+            state.executions -= 1;
+            state.executions == 0
+        };
+
+        if finished {
+            self.0.collect();
+
+            let roots = {
+                let mut state = self.0.state.borrow_mut();
+                state.retained = 0;
+                mem::take(&mut state.roots)
+            };
+
+            drop(roots);
+        }
+    }
+}
+
+/// Collect every unreachable cycle among the given roots, returning the number
+/// of values which were freed and the roots which are still reachable.
+fn collect(roots: Vec<Value>) -> (usize, Vec<Value>) {
+    let mut graph = Graph::default();
+    let mut queue = Vec::new();
+
+    for root in roots {
+        graph.insert(root, &mut queue);
+    }
+
+    // NB: values only referenced by the graph aren't part of any cycle, so
+    // they are released up front. Releasing one might release the last
+    // outside reference to another.
+    loop {
+        let before = graph.nodes.len();
+        graph.release_unreferenced();
+
+        if graph.nodes.len() == before {
+            break;
+        }
+    }
+
+    queue.clear();
+    queue.extend(0..graph.nodes.len());
+
+    let tracked = graph.nodes.len();
+
+    let mut children = Vec::new();
+
+    while let Some(index) = queue.pop() {
+        let node = &mut graph.nodes[index];
+        node.traced = trace(&node.value, &mut children).is_ok();
+
+        for child in children.drain(..) {
+            let child = graph.insert(child, &mut queue);
+            graph.nodes[index].children.push(child);
+        }
+    }
+
+    // NB: the graph holds exactly one reference to every node, so whatever
+    // remains after subtracting references from other nodes are references
+    // from outside of the graph.
+    for node in &mut graph.nodes {
+        node.refs = strong_count(&node.value).saturating_sub(1);
+    }
+
+    for index in 0..graph.nodes.len() {
+        if !graph.nodes[index].traced {
+            continue;
+        }
+
+        for n in 0..graph.nodes[index].children.len() {
+            let child = graph.nodes[index].children[n];
+            let refs = &mut graph.nodes[child].refs;
+            *refs = refs.saturating_sub(1);
+        }
+    }
+
+    let mut reachable = vec![false; graph.nodes.len()];
+
+    let mut stack = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.refs > 0 || !node.traced)
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    while let Some(index) = stack.pop() {
+        if mem::replace(&mut reachable[index], true) {
+            continue;
+        }
+
+        stack.extend(graph.nodes[index].children.iter().copied());
+    }
+
+    let mut garbage = Vec::new();
+    let mut retained = Vec::new();
+    let mut count = 0;
+
+    for (index, node) in graph.nodes.into_iter().enumerate() {
+        if reachable[index] {
+            // NB: reachable roots are still tracked, since they might become
+            // part of an unreachable cycle once they are released.
+            if index < tracked {
+                retained.push(node.value);
+            }
+        } else if clear(&node.value, &mut garbage).is_ok() {
+            count += 1;
+        }
+    }
+
+    drop(garbage);
+    (count, retained)
+}
+
+/// A graph of values being collected.
+#[derive(Default)]
+struct Graph {
+    nodes: Vec<Node>,
+    index: HashMap<*const (), usize>,
+}
+
+impl Graph {
+    /// Release every value which is only referenced by the graph.
+    fn release_unreferenced(&mut self) {
+        let nodes = mem::take(&mut self.nodes);
+        self.index.clear();
+
+        for node in nodes {
+            if strong_count(&node.value) > 1 {
+                let ptr = match identity(&node.value) {
+                    Some(ptr) => ptr,
+                    None => unreachable!("only containers are inserted"),
+                };
+
+                self.index.insert(ptr, self.nodes.len());
+                self.nodes.push(node);
+            }
+        }
+    }
+
+    /// Insert the given value into the graph, returning its index and queueing
+    /// it to be traced if it wasn't already present.
+    fn insert(&mut self, value: Value, queue: &mut Vec<usize>) -> usize {
+        let ptr = match identity(&value) {
+            Some(ptr) => ptr,
+            None => unreachable!("only containers are inserted"),
+        };
+
+        if let Some(index) = self.index.get(&ptr) {
+            return *index;
+        }
+
+        let index = self.nodes.len();
+
+        self.nodes.push(Node {
+            value,
+            children: Vec::new(),
+            refs: 0,
+            traced: false,
+        });
+
+        self.index.insert(ptr, index);
+        queue.push(index);
+        index
+    }
+}
+
+/// A single value in the graph.
+struct Node {
+    value: Value,
+    children: Vec<usize>,
+    refs: usize,
+    traced: bool,
+}
+
+/// Get the identity of a value which can participate in a cycle.
+fn identity(value: &Value) -> Option<*const ()> {
+    Some(match value {
+        Value::Vec(v) => Shared::as_ptr(v),
+        Value::Tuple(v) => Shared::as_ptr(v),
+        Value::Object(v) => Shared::as_ptr(v),
+        Value::TupleStruct(v) => Shared::as_ptr(v),
+        Value::Struct(v) => Shared::as_ptr(v),
+        Value::Variant(v) => Shared::as_ptr(v),
+        Value::Option(v) => Shared::as_ptr(v),
+        Value::Result(v) => Shared::as_ptr(v),
+        Value::GeneratorState(v) => Shared::as_ptr(v),
+        Value::Function(v) => Shared::as_ptr(v),
+        _ => return None,
+    })
+}
+
+/// The number of strong references to a value which can participate in a
+/// cycle.
+fn strong_count(value: &Value) -> usize {
+    match value {
+        Value::Vec(v) => Shared::strong_count(v),
+        Value::Tuple(v) => Shared::strong_count(v),
+        Value::Object(v) => Shared::strong_count(v),
+        Value::TupleStruct(v) => Shared::strong_count(v),
+        Value::Struct(v) => Shared::strong_count(v),
+        Value::Variant(v) => Shared::strong_count(v),
+        Value::Option(v) => Shared::strong_count(v),
+        Value::Result(v) => Shared::strong_count(v),
+        Value::GeneratorState(v) => Shared::strong_count(v),
+        Value::Function(v) => Shared::strong_count(v),
+        _ => 0,
+    }
+}
+
+/// Collect the values referenced by the given value which can participate in
+/// a cycle.
+fn trace(value: &Value, out: &mut Vec<Value>) -> Result<(), AccessError> {
+    let mut push = |value: &Value| {
+        if identity(value).is_some() {
+            out.push(value.clone());
+        }
+    };
+
+    match value {
+        Value::Vec(v) => v.borrow_ref()?.iter().for_each(push),
+        Value::Tuple(v) => v.borrow_ref()?.iter().for_each(push),
+        Value::Object(v) => v.borrow_ref()?.values().for_each(push),
+        Value::TupleStruct(v) => v.borrow_ref()?.data.iter().for_each(push),
+        Value::Struct(v) => v.borrow_ref()?.data.values().for_each(push),
+        Value::Variant(v) => match &v.borrow_ref()?.data {
+            VariantData::Unit => (),
+            VariantData::Struct(data) => data.values().for_each(push),
+            VariantData::Tuple(data) => data.iter().for_each(push),
+        },
+        Value::Option(v) => v.borrow_ref()?.iter().for_each(push),
+        Value::Result(v) => match &*v.borrow_ref()? {
+            Ok(value) | Err(value) => push(value),
+        },
+        Value::GeneratorState(v) => match &*v.borrow_ref()? {
+            GeneratorState::Yielded(value) | GeneratorState::Complete(value) => push(value),
+        },
+        Value::Function(v) => v.borrow_ref()?.environment().iter().for_each(push),
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Clear the contents of an unreachable value to break the cycles it
+/// participates in, moving them into `garbage`.
+fn clear(value: &Value, garbage: &mut Vec<Value>) -> Result<(), AccessError> {
+    fn take_all(values: &mut [Value], garbage: &mut Vec<Value>) {
+        garbage.extend(values.iter_mut().map(|v| mem::replace(v, Value::Unit)));
+    }
+
+    match value {
+        Value::Vec(v) => take_all(&mut v.borrow_mut()?, garbage),
+        Value::Tuple(v) => take_all(&mut v.borrow_mut()?, garbage),
+        Value::Object(v) => {
+            garbage.extend(mem::take(&mut *v.borrow_mut()?).into_inner().into_values());
+        }
+        Value::TupleStruct(v) => take_all(&mut v.borrow_mut()?.data, garbage),
+        Value::Struct(v) => {
+            let data = mem::take(&mut v.borrow_mut()?.data);
+            garbage.extend(data.into_inner().into_values());
+        }
+        Value::Variant(v) => match &mut v.borrow_mut()?.data {
+            VariantData::Unit => (),
+            VariantData::Struct(data) => garbage.extend(mem::take(data).into_inner().into_values()),
+            VariantData::Tuple(data) => take_all(data, garbage),
+        },
+        Value::Option(v) => garbage.extend(v.borrow_mut()?.take()),
+        Value::Result(v) => match &mut *v.borrow_mut()? {
+            Ok(value) | Err(value) => garbage.push(mem::replace(value, Value::Unit)),
+        },
+        Value::GeneratorState(v) => match &mut *v.borrow_mut()? {
+            GeneratorState::Yielded(value) | GeneratorState::Complete(value) => {
+                garbage.push(mem::replace(value, Value::Unit))
+            }
+        },
+        Value::Function(v) => take_all(v.borrow_mut()?.environment_mut(), garbage),
+        _ => (),
+    }
+
+    Ok(())
+}
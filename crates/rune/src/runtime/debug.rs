@@ -18,6 +18,8 @@ pub struct DebugInfo {
     pub functions: HashMap<Hash, DebugSignature>,
     /// Reverse lookup of a function.
     pub functions_rev: HashMap<usize, Hash>,
+    /// Named variables, sorted by the instruction they are declared at.
+    pub variables: Vec<DebugVariable>,
 }
 
 impl DebugInfo {
@@ -32,6 +34,61 @@ impl DebugInfo {
         let signature = self.functions.get(&hash)?;
         Some((hash, signature))
     }
+
+    /// Get the function which contains the given instruction pointer.
+    ///
+    /// Unlike [DebugInfo::function_at], this does not require the instruction
+    /// pointer to be at the start of the function.
+    pub fn function_containing(&self, ip: usize) -> Option<(Hash, &DebugSignature)> {
+        let (_, hash) = self
+            .functions_rev
+            .iter()
+            .filter(|(offset, _)| **offset <= ip)
+            .max_by_key(|(offset, _)| **offset)?;
+
+        let signature = self.functions.get(hash)?;
+        Some((*hash, signature))
+    }
+
+    /// Get the name of the variable stored at the given stack offset, relative
+    /// to the bottom of the call frame, when executing the instruction at the
+    /// given instruction pointer.
+    pub fn variable_at(&self, ip: usize, offset: usize) -> Option<&str> {
+        let start = self
+            .functions_rev
+            .keys()
+            .copied()
+            .filter(|start| *start <= ip)
+            .max()?;
+
+        let end = self.variables.partition_point(|v| v.ip <= ip);
+
+        self.variables[..end]
+            .iter()
+            .rev()
+            .take_while(|v| v.ip >= start)
+            .find(|v| v.offset == offset)
+            .map(|v| v.name.as_ref())
+    }
+}
+
+/// Debug information on a named variable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DebugVariable {
+    /// The instruction at which the variable is declared.
+    pub ip: usize,
+    /// The stack offset of the variable relative to its call frame.
+    pub offset: usize,
+    /// The name of the variable.
+    pub name: Box<str>,
+}
+
+impl DebugVariable {
+    /// Construct a new debug variable.
+    pub fn new(ip: usize, offset: usize, name: Box<str>) -> Self {
+        Self { ip, offset, name }
+    }
 }
 
 /// Debug information for every instruction.
@@ -0,0 +1,348 @@
+//! Interactive debugging of a virtual machine execution.
+//!
+//! See [Debugger] for more information.
+
+use crate::ast::Span;
+use crate::runtime::debug::{DebugInst, DebugSignature};
+use crate::runtime::{UnitFn, Value, Vm, VmError, VmExecution};
+use crate::{Hash, SourceId};
+
+/// A breakpoint which pauses execution in a [Debugger].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Breakpoint {
+    /// Break when execution enters code belonging to the given span in the
+    /// given source.
+    Span {
+        /// The source the breakpoint is in.
+        source_id: SourceId,
+        /// The span of the breakpoint.
+        span: Span,
+    },
+    /// Break when entering the function with the given hash.
+    Function(Hash),
+}
+
+/// How far a [Debugger] should run before pausing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StepMode {
+    /// Run until a breakpoint is hit or the execution completes.
+    Continue,
+    /// Run until a different source location is reached, entering any called
+    /// functions.
+    Into,
+    /// Run until a different source location is reached in the current
+    /// function or one of its callers.
+    Over,
+    /// Run until the current function returns.
+    Out,
+}
+
+/// The reason why a [Debugger] paused.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DebugEvent {
+    /// The breakpoint with the given identifier was hit.
+    Breakpoint(usize),
+    /// The requested step has completed.
+    Step,
+    /// The execution completed with the given value.
+    Complete(Value),
+}
+
+/// A snapshot of a single call frame in a paused execution.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DebugFrame {
+    /// The instruction pointer of the frame.
+    pub ip: usize,
+    /// The function the frame belongs to, if debug info is available.
+    pub function: Option<(Hash, DebugSignature)>,
+    /// Debug information on the instruction being executed in the frame.
+    pub inst: Option<DebugInst>,
+    /// The values on the stack which belongs to the frame, starting with its
+    /// arguments followed by its local variables.
+    pub values: Vec<Value>,
+}
+
+/// An interactive debugger for a [VmExecution].
+///
+/// The debugger executes one instruction at a time, pausing execution when
+/// a breakpoint is hit or a requested step has completed. While paused the
+/// call frames of the virtual machine can be inspected through
+/// [Debugger::frames].
+///
+/// Breaking on source spans requires the unit to be compiled with debug info.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{Breakpoint, DebugEvent, Debugger, StepMode};
+/// use rune::{FromValue, Hash, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> rune::Result<()> {
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn add(a, b) {
+///             a + b
+///         }
+///
+///         pub fn main() {
+///             add(1, 2)
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+///
+/// let mut vm = Vm::without_runtime(Arc::new(unit));
+/// let mut debugger = Debugger::new(vm.execute(["main"], ())?);
+/// let id = debugger.add_breakpoint(Breakpoint::Function(Hash::type_hash(["add"])));
+///
+/// assert!(matches!(debugger.resume(StepMode::Continue)?, DebugEvent::Breakpoint(n) if n == id));
+///
+/// let frames = debugger.frames();
+/// let frame = frames.last().expect("missing frame");
+/// let args = frame.values.iter().map(|v| i64::from_value(v.clone())).collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(args, vec![1, 2]);
+///
+/// match debugger.resume(StepMode::Continue)? {
+///     DebugEvent::Complete(value) => assert_eq!(i64::from_value(value)?, 3),
+///     event => panic!("unexpected event: {:?}", event),
+/// }
+/// # Ok(()) }
+/// ```
+pub struct Debugger<T = Vm>
+where
+    T: AsRef<Vm> + AsMut<Vm>,
+{
+    /// The execution being debugged.
+    execution: VmExecution<T>,
+    /// Registered breakpoints.
+    breakpoints: Vec<Option<Breakpoint>>,
+    /// The location of the last executed instruction.
+    last: Option<(SourceId, Span)>,
+    /// Whether the execution has started or not.
+    started: bool,
+}
+
+impl<T> Debugger<T>
+where
+    T: AsRef<Vm> + AsMut<Vm>,
+{
+    /// Construct a new debugger for the given execution.
+    pub fn new(execution: VmExecution<T>) -> Self {
+        Self {
+            execution,
+            breakpoints: Vec::new(),
+            last: None,
+            started: false,
+        }
+    }
+
+    /// Add a breakpoint, returning its identifier.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        let id = self.breakpoints.len();
+        self.breakpoints.push(Some(breakpoint));
+        id
+    }
+
+    /// Remove the breakpoint with the given identifier, returning it if it
+    /// exists.
+    pub fn remove_breakpoint(&mut self, id: usize) -> Option<Breakpoint> {
+        self.breakpoints.get_mut(id)?.take()
+    }
+
+    /// Remove all breakpoints.
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Access the execution being debugged.
+    pub fn execution(&self) -> &VmExecution<T> {
+        &self.execution
+    }
+
+    /// Access the execution being debugged mutably.
+    pub fn execution_mut(&mut self) -> &mut VmExecution<T> {
+        &mut self.execution
+    }
+
+    /// Stop debugging, returning the underlying execution.
+    pub fn into_execution(self) -> VmExecution<T> {
+        self.execution
+    }
+
+    /// Inspect the call frames of the currently executing virtual machine,
+    /// starting with the outermost one.
+    pub fn frames(&self) -> Vec<DebugFrame> {
+        let vm = self.execution.vm();
+        let stack = vm.stack();
+        let debug = vm.unit().debug_info();
+
+        let mut frames = Vec::new();
+        let call_frames = vm.call_frames();
+
+        for (n, frame) in call_frames.iter().enumerate() {
+            let top = match call_frames.get(n + 1) {
+                Some(next) => next.stack_bottom(),
+                None => stack.stack_bottom(),
+            };
+
+            // NB: the stored instruction pointer points to the call
+            // instruction in the calling function.
+            frames.push(DebugFrame {
+                ip: frame.ip(),
+                function: debug
+                    .and_then(|d| d.function_containing(frame.ip()))
+                    .map(|(hash, s)| (hash, s.clone())),
+                inst: debug.and_then(|d| d.instruction_at(frame.ip())).cloned(),
+                values: stack
+                    .get(frame.stack_bottom()..top)
+                    .unwrap_or_default()
+                    .to_vec(),
+            });
+        }
+
+        frames.push(DebugFrame {
+            ip: vm.ip(),
+            function: debug
+                .and_then(|d| d.function_containing(vm.ip()))
+                .map(|(hash, s)| (hash, s.clone())),
+            inst: debug.and_then(|d| d.instruction_at(vm.ip())).cloned(),
+            values: stack
+                .get(stack.stack_bottom()..)
+                .unwrap_or_default()
+                .to_vec(),
+        });
+
+        frames
+    }
+
+    /// Resume execution without support for async instructions until the
+    /// given step has completed, a breakpoint is hit, or the execution
+    /// completes.
+    pub fn resume(&mut self, mode: StepMode) -> Result<DebugEvent, VmError> {
+        let start = self.start();
+
+        if let Some(id) = self.initial_breakpoint() {
+            return Ok(DebugEvent::Breakpoint(id));
+        }
+
+        loop {
+            if let Some(value) = self.execution.step()? {
+                return Ok(DebugEvent::Complete(value));
+            }
+
+            if let Some(event) = self.check(mode, start) {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Resume execution with support for async instructions until the given
+    /// step has completed, a breakpoint is hit, or the execution completes.
+    pub async fn async_resume(&mut self, mode: StepMode) -> Result<DebugEvent, VmError> {
+        let start = self.start();
+
+        if let Some(id) = self.initial_breakpoint() {
+            return Ok(DebugEvent::Breakpoint(id));
+        }
+
+        loop {
+            if let Some(value) = self.execution.async_step().await? {
+                return Ok(DebugEvent::Complete(value));
+            }
+
+            if let Some(event) = self.check(mode, start) {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// The depth and location at which a step starts.
+    fn start(&self) -> (usize, Option<(SourceId, Span)>) {
+        (self.execution.call_depth(), self.location())
+    }
+
+    /// Check for breakpoints at the very start of the execution, before any
+    /// instructions have been executed.
+    fn initial_breakpoint(&mut self) -> Option<usize> {
+        if std::mem::replace(&mut self.started, true) {
+            return None;
+        }
+
+        self.hit_breakpoint()
+    }
+
+    /// Check if execution should pause after an instruction has been
+    /// executed.
+    fn check(
+        &mut self,
+        mode: StepMode,
+        (depth, location): (usize, Option<(SourceId, Span)>),
+    ) -> Option<DebugEvent> {
+        if let Some(id) = self.hit_breakpoint() {
+            return Some(DebugEvent::Breakpoint(id));
+        }
+
+        let current = self.location();
+
+        let done = match mode {
+            StepMode::Continue => false,
+            StepMode::Into => current.is_some() && current != location,
+            StepMode::Over => {
+                current.is_some() && current != location && self.execution.call_depth() <= depth
+            }
+            StepMode::Out => self.execution.call_depth() < depth,
+        };
+
+        if done {
+            return Some(DebugEvent::Step);
+        }
+
+        None
+    }
+
+    /// Test if the current instruction hits a breakpoint.
+    fn hit_breakpoint(&mut self) -> Option<usize> {
+        let location = self.location();
+        let last = std::mem::replace(&mut self.last, location);
+        let vm = self.execution.vm();
+
+        for (id, breakpoint) in self.breakpoints.iter().enumerate() {
+            let hit = match breakpoint {
+                Some(Breakpoint::Span { source_id, span }) => {
+                    let contains = |location: Option<(SourceId, Span)>| match location {
+                        Some((s, inst)) => {
+                            s == *source_id && span.start <= inst.start && inst.start < span.end
+                        }
+                        None => false,
+                    };
+
+                    contains(location) && !contains(last)
+                }
+                Some(Breakpoint::Function(hash)) => matches!(
+                    vm.unit().function(*hash),
+                    Some(UnitFn::Offset { offset, .. }) if offset == vm.ip()
+                ),
+                None => false,
+            };
+
+            if hit {
+                return Some(id);
+            }
+        }
+
+        None
+    }
+
+    /// The source location of the instruction about to be executed.
+    fn location(&self) -> Option<(SourceId, Span)> {
+        let vm = self.execution.vm();
+        let inst = vm.unit().debug_info()?.instruction_at(vm.ip())?;
+        Some((inst.source_id, inst.span))
+    }
+}
@@ -7,7 +7,9 @@
 //!
 //! See the corresponding function for documentation.
 
-use crate::runtime::{RuntimeContext, Unit, VmError, VmErrorKind};
+use crate::runtime::{
+    Ambient, Capabilities, Inherited, RuntimeContext, Unit, VmError, VmErrorKind,
+};
 use std::cell::Cell;
 use std::ptr;
 use std::sync::Arc;
@@ -20,7 +22,7 @@ where
     F: FnOnce(&Arc<RuntimeContext>, &Arc<Unit>) -> Result<T, VmError>,
 {
     let env = ENV.with(|env| env.get());
-    let Env { context, unit } = env;
+    let Env { context, unit, .. } = env;
 
     if context.is_null() || unit.is_null() {
         return Err(VmError::from(VmErrorKind::MissingInterfaceEnvironment));
@@ -32,6 +34,70 @@ where
     c(unsafe { &*context }, unsafe { &*unit })
 }
 
+/// Call the given closure with access to the ambient values of the virtual
+/// machine which is currently executing.
+pub(crate) fn with_ambient<F, T>(c: F) -> Result<T, VmError>
+where
+    F: FnOnce(Option<&Ambient>) -> T,
+{
+    let inherited = current()?;
+    Ok(c(inherited.ambient.as_deref()))
+}
+
+/// Call the given closure with access to the capabilities granted to the
+/// virtual machine which is currently executing, where `None` means that it
+/// is unrestricted.
+pub(crate) fn with_capabilities<F, T>(c: F) -> Result<T, VmError>
+where
+    F: FnOnce(Option<&Capabilities>) -> T,
+{
+    let inherited = current()?;
+    Ok(c(inherited.capabilities.as_deref()))
+}
+
+/// Get the state which the virtual machine which is currently executing
+/// shares with the virtual machines it starts, if any.
+pub(crate) fn inherited() -> Option<Inherited> {
+    let mut inherited = current().ok()?.clone();
+    inherited.outer = inherited.nested.get();
+    Some(inherited)
+}
+
+/// Account for the given number of bytes being allocated by a native function
+/// on behalf of the virtual machine which is currently executing.
+///
+/// Nothing is accounted for outside of a virtual machine.
+pub(crate) fn charge(bytes: usize) -> Result<(), VmError> {
+    match current() {
+        Ok(inherited) => inherited.charge(bytes),
+        Err(..) => Ok(()),
+    }
+}
+
+/// Call the given closure with the given state installed, as if it was called
+/// by a native function of a virtual machine sharing it.
+///
+/// This is used to execute functions on another thread with the state of the
+/// virtual machine which spawned them.
+pub(crate) fn scope<F, T>(inherited: &Inherited, c: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let _guard = Guard::new(ptr::null(), ptr::null(), inherited);
+    c()
+}
+
+fn current<'a>() -> Result<&'a Inherited, VmError> {
+    let env = ENV.with(|env| env.get());
+
+    if env.inherited.is_null() {
+        return Err(VmError::from(VmErrorKind::MissingInterfaceEnvironment));
+    }
+
+    // Safety: see [with].
+    Ok(unsafe { &*env.inherited })
+}
+
 pub(crate) struct Guard {
     old: Env,
 }
@@ -42,8 +108,18 @@ impl Guard {
     /// # Safety
     ///
     /// The returned guard must be dropped before the pointed to elements are.
-    pub(crate) fn new(context: *const Arc<RuntimeContext>, unit: *const Arc<Unit>) -> Guard {
-        let old = ENV.with(|e| e.replace(Env { context, unit }));
+    pub(crate) fn new(
+        context: *const Arc<RuntimeContext>,
+        unit: *const Arc<Unit>,
+        inherited: *const Inherited,
+    ) -> Guard {
+        let old = ENV.with(|e| {
+            e.replace(Env {
+                context,
+                unit,
+                inherited,
+            })
+        });
 
         Guard { old }
     }
@@ -59,6 +135,7 @@ impl Drop for Guard {
 struct Env {
     context: *const Arc<RuntimeContext>,
     unit: *const Arc<Unit>,
+    inherited: *const Inherited,
 }
 
 impl Env {
@@ -66,6 +143,7 @@ impl Env {
         Self {
             context: ptr::null(),
             unit: ptr::null(),
+            inherited: ptr::null(),
         }
     }
 }
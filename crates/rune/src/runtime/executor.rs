@@ -0,0 +1,234 @@
+//! Spawning of tasks from scripts.
+//!
+//! See [Executor] for more information.
+
+use crate::runtime::{
+    env, AllocationUsage, Ambient, Capabilities, Future, Inherited, Profiler, SendValue,
+    SyncFunction, Tracer, VmError, VmErrorKind, VmLimits,
+};
+use futures_channel::oneshot;
+use std::future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A future which can be spawned on an [Executor].
+pub type SpawnFuture = Pin<Box<dyn future::Future<Output = ()> + Send + 'static>>;
+
+/// A task which can be spawned on an [Executor].
+pub type SpawnTask = Box<dyn FnOnce() + Send + 'static>;
+
+/// An executor provided by the host which scripts can spawn tasks on.
+///
+/// Installing an executor through [Context::set_executor] enables
+/// `std::future::spawn` and `std::thread::spawn`, which both take a function
+/// without arguments and return a future resolving to the value it returns.
+/// The former spawns it as a future through [Executor::spawn], which is
+/// suitable for IO-bound work, while the latter calls it through
+/// [Executor::spawn_blocking], which is suitable for CPU-bound work.
+///
+/// It also enables `std::time::sleep` and `Future::timeout`, which wait using
+/// the timers provided through [Executor::sleep].
+///
+/// Spawned functions execute on a virtual machine of their own, so closures
+/// can only capture values which can be represented as a [SendValue], and
+/// their output has to be representable as one as well. That virtual machine
+/// is subject to the same limits and capabilities as the one which spawned
+/// it, and is provided with a copy of its ambient values.
+///
+/// [Context::set_executor]: crate::Context::set_executor
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{Executor, SpawnFuture, SpawnTask};
+/// use rune::{Context, FromValue, Vm};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// struct Tokio(tokio::runtime::Handle);
+///
+/// impl Executor for Tokio {
+///     fn spawn(&self, future: SpawnFuture) {
+///         self.0.spawn(future);
+///     }
+///
+///     fn spawn_blocking(&self, task: SpawnTask) {
+///         self.0.spawn_blocking(task);
+///     }
+///
+///     fn sleep(&self, duration: Duration) -> SpawnFuture {
+///         Box::pin(tokio::time::sleep(duration))
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> rune::Result<()> {
+/// let mut context = Context::with_default_modules()?;
+/// context.set_executor(Tokio(tokio::runtime::Handle::current()));
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         async fn fetch(n) {
+///             std::time::sleep(std::time::Duration::from_millis(10)).await;
+///             n * 2
+///         }
+///
+///         pub async fn main() {
+///             let n = 10;
+///             let a = std::future::spawn(|| fetch(n));
+///             let b = std::thread::spawn(|| n + 1);
+///             a.await + b.await
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+///
+/// let output = i64::from_value(vm.async_call(["main"], ()).await?)?;
+/// assert_eq!(output, 31);
+/// # Ok(()) }
+/// ```
+pub trait Executor: Send + Sync {
+    /// Spawn a future which drives a task to completion.
+    fn spawn(&self, future: SpawnFuture);
+
+    /// Spawn a task which might block the thread it runs on.
+    ///
+    /// By default the task is run on a thread of its own.
+    fn spawn_blocking(&self, task: SpawnTask) {
+        std::thread::spawn(task);
+    }
+
+    /// Construct a future which completes once the given duration has
+    /// elapsed.
+    ///
+    /// By default this sleeps on a thread of its own, so executors which
+    /// provide timers should override it.
+    fn sleep(&self, duration: Duration) -> SpawnFuture {
+        let (sender, receiver) = oneshot::channel();
+
+        std::thread::spawn(move || {
+            std::thread::sleep(duration);
+            let _ = sender.send(());
+        });
+
+        Box::pin(async move {
+            let _ = receiver.await;
+        })
+    }
+}
+
+/// Spawn a future calling the given function on the current executor.
+pub(crate) fn spawn(function: SyncFunction) -> Result<Future, VmError> {
+    let inherited = SendInherited::current()?;
+    let (sender, receiver) = oneshot::channel();
+
+    let future = Box::pin(async move {
+        let output = function
+            .async_send_call_inherited::<_, SendValue>((), inherited)
+            .await;
+        let _ = sender.send(output);
+    });
+
+    current()?.spawn(future);
+    Ok(join(receiver))
+}
+
+/// Spawn a blocking task calling the given function on the current executor.
+pub(crate) fn spawn_blocking(function: SyncFunction) -> Result<Future, VmError> {
+    let inherited = SendInherited::current()?;
+    let (sender, receiver) = oneshot::channel();
+
+    let task = Box::new(move || {
+        let inherited = inherited.into_inherited();
+        let output = env::scope(&inherited, || function.call::<_, SendValue>(()));
+        let _ = sender.send(output);
+    });
+
+    current()?.spawn_blocking(task);
+    Ok(join(receiver))
+}
+
+/// Construct a future which completes once the given duration has elapsed,
+/// using the current executor.
+pub(crate) fn sleep(duration: Duration) -> Result<SpawnFuture, VmError> {
+    Ok(current()?.sleep(duration))
+}
+
+/// Get the executor of the current context.
+fn current() -> Result<Arc<dyn Executor>, VmError> {
+    env::with(|context, _| match context.executor() {
+        Some(executor) => Ok(executor.clone()),
+        None => Err(VmError::from(VmErrorKind::MissingExecutor)),
+    })
+}
+
+/// Construct a future which waits for the output of a spawned task.
+fn join(receiver: oneshot::Receiver<Result<SendValue, VmError>>) -> Future {
+    Future::new(async move {
+        match receiver.await {
+            Ok(output) => Ok(output?.into_value()),
+            Err(oneshot::Canceled) => Err(VmError::from(VmErrorKind::TaskCancelled)),
+        }
+    })
+}
+
+/// The state of a virtual machine spawning a task, in a form which can be sent
+/// to the thread executing it.
+pub(crate) struct SendInherited {
+    limits: VmLimits,
+    allocated: Option<Arc<AllocationUsage>>,
+    tracer: Option<Tracer>,
+    profiler: Option<Arc<Profiler>>,
+    ambient: Option<Vec<(String, SendValue)>>,
+    capabilities: Option<Arc<Capabilities>>,
+}
+
+impl SendInherited {
+    /// Capture the state of the virtual machine which is currently executing.
+    fn current() -> Result<Self, VmError> {
+        let Inherited {
+            limits,
+            allocated,
+            tracer,
+            profiler,
+            ambient,
+            capabilities,
+            // NB: cycle collectors are bound to the thread they were created
+            // on, so cycles aren't tracked in spawned tasks.
+            cycles: _,
+            // NB: spawned tasks execute on a native stack of their own, so
+            // they start over from an empty call stack.
+            outer: _,
+            nested: _,
+        } = env::inherited().ok_or(VmErrorKind::MissingInterfaceEnvironment)?;
+
+        Ok(Self {
+            limits,
+            allocated,
+            tracer,
+            profiler,
+            ambient: ambient.map(|ambient| ambient.to_send()).transpose()?,
+            capabilities,
+        })
+    }
+
+    /// Convert into the state of the virtual machine executing the task.
+    pub(crate) fn into_inherited(self) -> Inherited {
+        Inherited {
+            limits: self.limits,
+            allocated: self.allocated,
+            tracer: self.tracer,
+            profiler: self.profiler,
+            ambient: self
+                .ambient
+                .map(|values| Rc::new(Ambient::from_send(values))),
+            capabilities: self.capabilities,
+            ..Inherited::default()
+        }
+    }
+}
@@ -49,7 +49,7 @@ impl FromValue for Format {
 }
 
 /// A format specification.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct FormatSpec {
     /// Formatting flags.
@@ -246,7 +246,8 @@ impl FormatSpec {
                 self.format_fill(out, buf, align, fill, sign);
             }
             value => {
-                let result = value.string_debug_with(out, caller)?;
+                let pretty = self.flags.test(Flag::Alternate);
+                let result = value.string_debug_with(out, pretty, caller)?;
                 result.map_err(|_| VmErrorKind::FormatError)?;
             }
         }
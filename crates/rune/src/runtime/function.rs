@@ -1,12 +1,16 @@
+use crate::runtime::env;
+use crate::runtime::executor::SendInherited;
 use crate::runtime::{
-    Args, Call, ConstValue, FromValue, FunctionHandler, RawRef, Ref, Rtti, RuntimeContext, Shared,
-    Stack, Tuple, Unit, UnsafeFromValue, Value, VariantRtti, Vm, VmCall, VmError, VmErrorKind,
-    VmHalt,
+    Args, Call, FromValue, FunctionHandler, RawRef, Ref, Rtti, RuntimeContext, SendValue, Shared,
+    Stack, ToValue, Tuple, Unit, UnitGeneration, UnsafeFromValue, Value, VariantRtti, Vm, VmCall,
+    VmError, VmErrorKind, VmHalt,
 };
 use crate::shared::AssertSend;
 use crate::Hash;
 use std::fmt;
 use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
 use std::sync::Arc;
 
 /// A callable non-sync function.
@@ -105,7 +109,7 @@ impl Function {
         A: Send + Args,
         T: Send + FromValue,
     {
-        self.0.async_send_call(args).await
+        self.0.async_send_call(args, None).await
     }
 
     /// Perform a call over the function represented by this function pointer.
@@ -154,6 +158,23 @@ impl Function {
         self.0.call_with_vm(vm, args)
     }
 
+    /// The environment captured by the function, if it's a closure.
+    pub(crate) fn environment(&self) -> &[Value] {
+        match &self.0.inner {
+            Inner::FnClosureOffset(closure) => &closure.environment,
+            _ => &[],
+        }
+    }
+
+    /// Mutable access to the environment captured by the function, if it's a
+    /// closure.
+    pub(crate) fn environment_mut(&mut self) -> &mut [Value] {
+        match &mut self.0.inner {
+            Inner::FnClosureOffset(closure) => &mut closure.environment,
+            _ => &mut [],
+        }
+    }
+
     /// Create a function pointer from a handler.
     pub(crate) fn from_handler(handler: Arc<FunctionHandler>, hash: Hash) -> Self {
         Self(FunctionImpl::from_handler(handler, hash))
@@ -163,13 +184,14 @@ impl Function {
     pub(crate) fn from_vm_offset(
         context: Arc<RuntimeContext>,
         unit: Arc<Unit>,
+        generation: UnitGeneration,
         offset: usize,
         call: Call,
         args: usize,
         hash: Hash,
     ) -> Self {
         Self(FunctionImpl::from_offset(
-            context, unit, offset, call, args, hash,
+            context, unit, generation, offset, call, args, hash,
         ))
     }
 
@@ -177,6 +199,7 @@ impl Function {
     pub(crate) fn from_vm_closure(
         context: Arc<RuntimeContext>,
         unit: Arc<Unit>,
+        generation: UnitGeneration,
         offset: usize,
         call: Call,
         args: usize,
@@ -186,6 +209,7 @@ impl Function {
         Self(FunctionImpl::from_closure(
             context,
             unit,
+            generation,
             offset,
             call,
             args,
@@ -280,8 +304,8 @@ impl Function {
     /// ```
     ///
     /// The following *does not* work, because we return a closure which tries
-    /// to make use of a [Generator][crate::runtime::Generator] which is not a
-    /// constant value.
+    /// to make use of a [Generator][crate::runtime::Generator] which can't be
+    /// sent across threads.
     ///
     /// ```
     /// use rune::{Hash, Vm, FromValue};
@@ -311,20 +335,118 @@ impl Function {
     /// let closure = Function::from_value(closure)?;
     ///
     /// // This is *not* fine since the returned closure has captured a
-    /// // generator which is not a constant value.
+    /// // generator which can't be sent across threads.
     /// assert!(closure.into_sync().is_err());
     /// # Ok(()) }
     /// ```
     pub fn into_sync(self) -> Result<SyncFunction, VmError> {
         Ok(SyncFunction(self.0.into_sync()?))
     }
+
+    /// Try to convert a copy of the function into a [SyncFunction], leaving
+    /// this function intact.
+    pub(crate) fn to_sync(&self) -> Result<SyncFunction, VmError> {
+        Ok(SyncFunction(self.0.clone().into_sync()?))
+    }
+
+    /// Convert the function into a Rust closure taking the arguments in the
+    /// tuple `A` and converting its output into `T`.
+    ///
+    /// This allows script callbacks to be stored and called like any other
+    /// closure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Vm, FromValue};
+    /// use rune::runtime::Function;
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             |a, b| a * b
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let mut vm = Vm::without_runtime(Arc::new(unit));
+    /// let mul = Function::from_value(vm.call(["main"], ())?)?;
+    ///
+    /// let mul = mul.into_typed::<(i64, i64), i64>();
+    /// assert_eq!(mul(6, 7)?, 42);
+    /// # Ok(()) }
+    /// ```
+    pub fn into_typed<A, T>(self) -> Box<A::Fn>
+    where
+        A: TypedArgs<T>,
+    {
+        A::into_fn(self)
+    }
+
+    /// Convert the function into a Rust closure taking the arguments in the
+    /// tuple `A`, which returns a future resolving to its output converted
+    /// into `T`.
+    ///
+    /// If the function returns a future, like an `async` function does, the
+    /// returned future awaits it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Vm, FromValue};
+    /// use rune::runtime::Function;
+    /// use std::sync::Arc;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> rune::Result<()> {
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         async fn add(a, b) {
+    ///             a + b
+    ///         }
+    ///
+    ///         pub fn main() { add }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let mut vm = Vm::without_runtime(Arc::new(unit));
+    /// let add = Function::from_value(vm.call(["main"], ())?)?;
+    ///
+    /// let add = add.into_async_typed::<(i64, i64), i64>();
+    /// assert_eq!(add(1, 2).await?, 3);
+    /// # Ok(()) }
+    /// ```
+    pub fn into_async_typed<A, T>(self) -> Box<A::AsyncFn>
+    where
+        A: TypedArgs<T>,
+    {
+        A::into_async_fn(self)
+    }
+
+    /// Call the function, awaiting its output if it is a future.
+    async fn async_call<A, T>(&self, args: A) -> Result<T, VmError>
+    where
+        A: Args,
+        T: FromValue,
+    {
+        let value = match self.call(args)? {
+            Value::Future(future) => future.take()?.await?,
+            other => other,
+        };
+
+        T::from_value(value)
+    }
 }
 
-/// A callable sync function. This currently only supports a subset of values
-/// that are supported by the Vm.
+/// A callable sync function. This only supports closures which have captured
+/// values that can be represented as a [SendValue].
 #[derive(Clone)]
 #[repr(transparent)]
-pub struct SyncFunction(FunctionImpl<ConstValue>);
+pub struct SyncFunction(FunctionImpl<SendValue>);
 
 impl SyncFunction {
     /// Perform an asynchronous call over the function which also implements
@@ -363,7 +485,21 @@ impl SyncFunction {
         A: Send + Args,
         T: Send + FromValue,
     {
-        self.0.async_send_call(args).await
+        self.0.async_send_call(args, None).await
+    }
+
+    /// Perform an asynchronous call over the function, as if it was called by
+    /// a native function of a virtual machine with the given state.
+    pub(crate) async fn async_send_call_inherited<A, T>(
+        &self,
+        args: A,
+        inherited: SendInherited,
+    ) -> Result<T, VmError>
+    where
+        A: Send + Args,
+        T: Send + FromValue,
+    {
+        self.0.async_send_call(args, Some(inherited)).await
     }
 
     /// Perform a call over the function represented by this function pointer.
@@ -459,7 +595,7 @@ where
         let value = match &self.inner {
             Inner::FnHandler(handler) => {
                 let arg_count = args.count();
-                let mut stack = Stack::with_capacity(arg_count);
+                let mut stack = Stack::pooled(arg_count);
                 args.into_stack(&mut stack)?;
                 (handler.handler)(&mut stack, arg_count)?;
                 stack.pop()?
@@ -492,13 +628,17 @@ where
     fn async_send_call<'a, A, T>(
         &'a self,
         args: A,
+        inherited: Option<SendInherited>,
     ) -> impl Future<Output = Result<T, VmError>> + Send + 'a
     where
         A: 'a + Send + Args,
         T: 'a + Send + FromValue,
     {
         let future = async move {
-            let value = self.call(args)?;
+            let value = match inherited {
+                Some(inherited) => env::scope(&inherited.into_inherited(), || self.call(args))?,
+                None => self.call(args)?,
+            };
 
             let value = match value {
                 Value::Future(future) => {
@@ -527,6 +667,7 @@ where
     pub(crate) fn call_with_vm(&self, vm: &mut Vm, args: usize) -> Result<Option<VmHalt>, VmError> {
         let reason = match &self.inner {
             Inner::FnHandler(handler) => {
+                vm.enter_native(args);
                 (handler.handler)(vm.stack_mut(), args)?;
                 None
             }
@@ -592,6 +733,7 @@ where
     pub(crate) fn from_offset(
         context: Arc<RuntimeContext>,
         unit: Arc<Unit>,
+        generation: UnitGeneration,
         offset: usize,
         call: Call,
         args: usize,
@@ -601,6 +743,7 @@ where
             inner: Inner::FnOffset(FnOffset {
                 context,
                 unit,
+                generation,
                 offset,
                 call,
                 args,
@@ -613,6 +756,7 @@ where
     pub(crate) fn from_closure(
         context: Arc<RuntimeContext>,
         unit: Arc<Unit>,
+        generation: UnitGeneration,
         offset: usize,
         call: Call,
         args: usize,
@@ -624,6 +768,7 @@ where
                 fn_offset: FnOffset {
                     context,
                     unit,
+                    generation,
                     offset,
                     call,
                     args,
@@ -679,7 +824,7 @@ where
 
 impl FunctionImpl<Value> {
     /// Try to convert into a [SyncFunction].
-    fn into_sync(self) -> Result<FunctionImpl<ConstValue>, VmError> {
+    fn into_sync(self) -> Result<FunctionImpl<SendValue>, VmError> {
         let inner = match self.inner {
             Inner::FnClosureOffset(closure) => {
                 let mut env = Vec::with_capacity(closure.environment.len());
@@ -783,6 +928,8 @@ struct FnOffset {
     context: Arc<RuntimeContext>,
     /// The unit where the function resides.
     unit: Arc<Unit>,
+    /// The generation of the unit, which is retired if it has been replaced.
+    generation: UnitGeneration,
     /// The offset of the function.
     offset: usize,
     /// The calling convention.
@@ -800,9 +947,22 @@ impl FnOffset {
         A: Args,
         E: Args,
     {
+        self.check_generation()?;
         check_args(args.count(), self.args)?;
 
-        let mut vm = Vm::new(self.context.clone(), self.unit.clone());
+        let stack = Stack::pooled(self.args + extra.count());
+        let mut vm = Vm::with_generation(
+            self.context.clone(),
+            self.unit.clone(),
+            stack,
+            self.generation.clone(),
+        );
+
+        // NB: functions called by native functions are subject to the same
+        // limits and capabilities as the virtual machine which called them.
+        if let Some(inherited) = env::inherited() {
+            vm.set_inherited(inherited)?;
+        }
 
         vm.set_ip(self.offset);
         args.into_stack(vm.stack_mut())?;
@@ -819,7 +979,9 @@ impl FnOffset {
     where
         E: Args,
     {
+        self.check_generation()?;
         check_args(args, self.args)?;
+        vm.trace_call(self.hash, args);
 
         // Fast past, just allocate a call frame and keep running.
         if let Call::Immediate = self.call {
@@ -832,9 +994,26 @@ impl FnOffset {
 
         let mut new_stack = vm.stack_mut().drain(args)?.collect::<Stack>();
         extra.into_stack(&mut new_stack)?;
-        let mut vm = Vm::with_stack(self.context.clone(), self.unit.clone(), new_stack);
-        vm.set_ip(self.offset);
-        Ok(Some(VmCall::new(self.call, vm)))
+        let mut new_vm = Vm::with_generation(
+            self.context.clone(),
+            self.unit.clone(),
+            new_stack,
+            self.generation.clone(),
+        );
+        new_vm.inherit(vm);
+        new_vm.set_ip(self.offset);
+        Ok(Some(VmCall::new(self.call, new_vm)))
+    }
+
+    /// Error if the unit of the function has been replaced.
+    fn check_generation(&self) -> Result<(), VmError> {
+        if self.generation.is_retired(self.hash) {
+            return Err(VmError::from(VmErrorKind::StaleFunction {
+                hash: self.hash,
+            }));
+        }
+
+        Ok(())
     }
 }
 
@@ -936,6 +1115,64 @@ fn check_args(actual: usize, expected: usize) -> Result<(), VmError> {
     Ok(())
 }
 
+/// A future produced by a closure constructed through
+/// [Function::into_async_typed].
+pub type TypedFuture<T> = Pin<Box<dyn Future<Output = Result<T, VmError>>>>;
+
+/// A tuple of arguments which a [Function] can be converted into a typed
+/// closure for, through [Function::into_typed] and
+/// [Function::into_async_typed].
+pub trait TypedArgs<T>: Args {
+    /// The type of the closure taking the arguments in the tuple.
+    type Fn: ?Sized;
+
+    /// The type of the asynchronous closure taking the arguments in the tuple.
+    type AsyncFn: ?Sized;
+
+    #[doc(hidden)]
+    fn into_fn(function: Function) -> Box<Self::Fn>;
+
+    #[doc(hidden)]
+    fn into_async_fn(function: Function) -> Box<Self::AsyncFn>;
+}
+
+macro_rules! impl_typed_args {
+    () => {
+        impl_typed_args!{@impl}
+    };
+
+    ({$ty:ident, $value:ident, $count:expr}, $({$l_ty:ident, $l_value:ident, $l_count:expr},)*) => {
+        impl_typed_args!{@impl {$ty, $value}, $({$l_ty, $l_value},)*}
+        impl_typed_args!{$({$l_ty, $l_value, $l_count},)*}
+    };
+
+    (@impl $({$ty:ident, $value:ident},)*) => {
+        impl<T, $($ty,)*> TypedArgs<T> for ($($ty,)*)
+        where
+            T: 'static + FromValue,
+            $($ty: 'static + ToValue,)*
+        {
+            type Fn = dyn Fn($($ty),*) -> Result<T, VmError>;
+            type AsyncFn = dyn Fn($($ty),*) -> TypedFuture<T>;
+
+            fn into_fn(function: Function) -> Box<Self::Fn> {
+                Box::new(move |$($value),*| function.call(($($value,)*)))
+            }
+
+            fn into_async_fn(function: Function) -> Box<Self::AsyncFn> {
+                let function = Rc::new(function);
+
+                Box::new(move |$($value),*| {
+                    let function = function.clone();
+                    Box::pin(async move { function.async_call(($($value,)*)).await })
+                })
+            }
+        }
+    };
+}
+
+repeat_macro!(impl_typed_args);
+
 #[cfg(test)]
 mod tests {
     use super::SyncFunction;
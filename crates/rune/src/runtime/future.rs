@@ -115,6 +115,12 @@ impl FromValue for Shared<Future> {
     }
 }
 
+impl FromValue for Mut<Future> {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        Ok(value.into_shared_future()?.into_mut()?)
+    }
+}
+
 impl FromValue for Future {
     fn from_value(value: Value) -> Result<Self, VmError> {
         value.into_future()
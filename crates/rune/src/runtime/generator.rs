@@ -1,7 +1,7 @@
 use crate::compile::Named;
 use crate::runtime::{
     FromValue, GeneratorState, Iterator, Mut, RawMut, RawRef, RawStr, Ref, Shared, UnsafeFromValue,
-    Value, Vm, VmError, VmErrorKind, VmExecution,
+    Value, Vm, VmError, VmErrorKind, VmExecution, VmSnapshot,
 };
 use crate::InstallWith;
 use std::fmt;
@@ -60,6 +60,23 @@ where
 
         Ok(state)
     }
+
+    /// Take a serializable snapshot of the suspended generator.
+    ///
+    /// The snapshot can be restored into an execution through
+    /// [VmSnapshot::restore] and converted back into a generator with
+    /// [VmExecution::into_generator].
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmError>
+    where
+        T: AsRef<Vm>,
+    {
+        let execution = self
+            .execution
+            .as_ref()
+            .ok_or(VmErrorKind::GeneratorComplete)?;
+
+        execution.snapshot()
+    }
 }
 
 impl Generator<&mut Vm> {
@@ -0,0 +1,178 @@
+//! Hooks through which the host provides nondeterministic inputs.
+//!
+//! See [Host] for more information.
+
+use crate::runtime::env;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use twox_hash::XxHash64;
+
+/// A host which provides every source of nondeterminism scripts can observe.
+///
+/// Installing a host through [Context::set_host] puts virtual machines in a
+/// deterministic mode, where the current time, random numbers and the
+/// iteration order of `std::collections::HashMap` and `HashSet` are all
+/// derived from the host. Running the same script against a host which
+/// produces the same sequence of inputs therefore replays it exactly.
+///
+/// Without a host these are taken from the system instead.
+///
+/// Native modules which need the current time or randomness should go
+/// through [now] and [random] so that they also respect the installed host.
+///
+/// Note that tasks spawned on an [Executor][crate::runtime::Executor] run
+/// concurrently, so their interleaving is up to the executor.
+///
+/// [Context::set_host]: crate::Context::set_host
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::host::Deterministic;
+/// use rune::{Context, FromValue, Vm};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// # fn main() -> rune::Result<()> {
+/// let mut context = Context::with_default_modules()?;
+/// context.set_host(Deterministic::new(42).with_time(Duration::from_secs(1000)));
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             std::time::now().as_millis()
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+///
+/// let output = u64::from_value(vm.call(["main"], ())?)?;
+/// assert_eq!(output, 1_000_000);
+/// # Ok(()) }
+/// ```
+pub trait Host: Send + Sync {
+    /// The current time, as a duration since the unix epoch.
+    fn now(&self) -> Duration;
+
+    /// Produce a random number.
+    fn random(&self) -> u64;
+
+    /// The seed used when hashing the keys of maps and sets, which determines
+    /// the order they are iterated in.
+    fn hash_seed(&self) -> u64;
+}
+
+/// A [Host] which produces a reproducible sequence of inputs out of a seed.
+///
+/// Time stands still unless it is moved through [Deterministic::advance].
+pub struct Deterministic {
+    seed: u64,
+    state: AtomicU64,
+    time: Mutex<Duration>,
+}
+
+impl Deterministic {
+    /// Construct a deterministic host from the given seed, with the time set
+    /// to the unix epoch.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            state: AtomicU64::new(seed),
+            time: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Set the time reported by the host, as a duration since the unix epoch.
+    pub fn with_time(self, time: Duration) -> Self {
+        *self.time.lock().unwrap_or_else(|e| e.into_inner()) = time;
+        self
+    }
+
+    /// Move the time reported by the host forward.
+    pub fn advance(&self, duration: Duration) {
+        *self.time.lock().unwrap_or_else(|e| e.into_inner()) += duration;
+    }
+}
+
+impl Host for Deterministic {
+    fn now(&self) -> Duration {
+        *self.time.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn random(&self) -> u64 {
+        // splitmix64, which is good enough to seed other generators with.
+        let state = self
+            .state
+            .fetch_add(0x9e3779b97f4a7c15, Ordering::Relaxed)
+            .wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn hash_seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// Get the current time as a duration since the unix epoch, using the host
+/// of the current context if one is installed.
+pub fn now() -> Duration {
+    match current() {
+        Some(host) => host.now(),
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default(),
+    }
+}
+
+/// Produce a random number, using the host of the current context if one is
+/// installed.
+pub fn random() -> u64 {
+    match current() {
+        Some(host) => host.random(),
+        None => RandomState::new().build_hasher().finish(),
+    }
+}
+
+/// Get the host of the current context, if one is installed and we are
+/// running inside of a virtual machine.
+fn current() -> Option<Arc<dyn Host>> {
+    env::with(|context, _| Ok(context.host().cloned()))
+        .ok()
+        .flatten()
+}
+
+/// Builds the hashers used by maps and sets, seeded through the host.
+#[derive(Clone)]
+pub(crate) struct MapHasher {
+    seed: u64,
+}
+
+impl MapHasher {
+    /// Construct a hasher for a new map or set.
+    pub(crate) fn new() -> Self {
+        let seed = match current() {
+            Some(host) => host.hash_seed(),
+            None => random(),
+        };
+
+        Self { seed }
+    }
+}
+
+impl BuildHasher for MapHasher {
+    type Hasher = XxHash64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        XxHash64::with_seed(self.seed)
+    }
+}
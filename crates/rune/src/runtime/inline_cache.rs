@@ -0,0 +1,123 @@
+//! Inline caches used to speed up dynamic dispatch.
+//!
+//! Instructions which dispatch on the type of a value, like instance function
+//! calls and field accesses, carry a cache slot allocated when the unit is
+//! compiled. The first time such an instruction executes, the function it
+//! resolves to is stored in its slot together with the type hash of the value
+//! it was resolved for. Subsequent executions with a value of the same type
+//! skip hashing and looking up the function in the unit and the context.
+//!
+//! Each slot only remembers the most recently seen type, so an instruction
+//! which sees values of many different types simply falls back to a regular
+//! lookup.
+
+use crate::runtime::{Call, FunctionHandler};
+use crate::Hash;
+use std::fmt;
+use std::sync::Arc;
+
+/// The inline caches of a virtual machine.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InlineCaches {
+    entries: Vec<InlineCache>,
+}
+
+impl InlineCaches {
+    /// Construct an empty collection of inline caches.
+    pub(crate) const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Get the entry cached in the given slot for the given type.
+    #[inline]
+    pub(crate) fn get(&self, slot: usize, type_hash: Hash) -> Option<&InlineCache> {
+        let entry = self.entries.get(slot)?;
+
+        if entry.type_hash()? != type_hash {
+            return None;
+        }
+
+        Some(entry)
+    }
+
+    /// Store an entry in the given slot, where `len` is the number of slots
+    /// used by the unit being executed.
+    pub(crate) fn store(&mut self, slot: usize, len: usize, entry: InlineCache) {
+        if slot >= self.entries.len() {
+            self.entries.resize(len.max(slot + 1), InlineCache::Empty);
+        }
+
+        self.entries[slot] = entry;
+    }
+
+    /// Clear all cached entries.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// A single inline cache entry.
+#[derive(Clone)]
+pub(crate) enum InlineCache {
+    /// Nothing has been cached.
+    Empty,
+    /// An instance function implemented in the unit.
+    Offset {
+        /// The type the function was resolved for.
+        type_hash: Hash,
+        /// The full hash of the function.
+        hash: Hash,
+        /// The offset of the function.
+        offset: usize,
+        /// The way the function is called.
+        call: Call,
+        /// The number of arguments the function takes.
+        args: usize,
+    },
+    /// A native function from the context.
+    Handler {
+        /// The type the function was resolved for.
+        type_hash: Hash,
+        /// The handler of the function.
+        handler: Arc<FunctionHandler>,
+    },
+}
+
+impl InlineCache {
+    /// The type hash the entry was resolved for.
+    #[inline]
+    fn type_hash(&self) -> Option<Hash> {
+        match self {
+            Self::Empty => None,
+            Self::Offset { type_hash, .. } | Self::Handler { type_hash, .. } => Some(*type_hash),
+        }
+    }
+}
+
+impl fmt::Debug for InlineCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("Empty"),
+            Self::Offset {
+                type_hash,
+                hash,
+                offset,
+                call,
+                args,
+            } => f
+                .debug_struct("Offset")
+                .field("type_hash", type_hash)
+                .field("hash", hash)
+                .field("offset", offset)
+                .field("call", call)
+                .field("args", args)
+                .finish(),
+            Self::Handler { type_hash, .. } => f
+                .debug_struct("Handler")
+                .field("type_hash", type_hash)
+                .finish_non_exhaustive(),
+        }
+    }
+}
@@ -6,7 +6,7 @@ use std::fmt;
 /// Pre-canned panic reasons.
 ///
 /// To formulate a custom reason, use [crate::runtime::Panic::custom].
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PanicReason {
     /// Not implemented.
     NotImplemented,
@@ -42,7 +42,7 @@ impl fmt::Display for PanicReason {
 }
 
 /// Type checks for built-in types.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum TypeCheck {
     /// Matches a unit type.
@@ -79,7 +79,7 @@ impl fmt::Display for TypeCheck {
 }
 
 /// An operation in the stack-based virtual machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Inst {
     /// Not operator. Takes a boolean from the top of the stack  and inverts its
     /// logical value.
@@ -134,6 +134,8 @@ pub enum Inst {
         hash: Hash,
         /// The number of arguments expected on the stack for this call.
         args: usize,
+        /// The inline cache slot remembering which function was called.
+        cache: usize,
     },
     /// Lookup the specified instance function and put it on the stack.
     /// This might help in cases where a single instance function is called many
@@ -236,6 +238,8 @@ pub enum Inst {
     ObjectIndexGet {
         /// The static string slot corresponding to the index to fetch.
         slot: usize,
+        /// The inline cache slot remembering how the field was fetched.
+        cache: usize,
     },
     /// Set the given index out of an object on the top of the stack.
     /// Errors if the item doesn't exist or the item is not an object.
@@ -270,6 +274,8 @@ pub enum Inst {
         offset: usize,
         /// The static string slot corresponding to the index to fetch.
         slot: usize,
+        /// The inline cache slot remembering how the field was fetched.
+        cache: usize,
     },
     /// Perform an index set operation.
     ///
@@ -953,6 +959,44 @@ pub enum Inst {
         /// The address of the second argument.
         b: InstAddress,
     },
+    /// A built-in operation like `a + b` which is speculated to operate on
+    /// integers.
+    ///
+    /// If either operand isn't an integer, this behaves exactly like the
+    /// corresponding [Inst::Op].
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    IntOp {
+        /// The actual operation.
+        op: InstIntOp,
+        /// The address of the first argument.
+        a: InstAddress,
+        /// The address of the second argument.
+        b: InstAddress,
+    },
+    /// A built-in operation like `a < 10` where the second operand is a
+    /// constant integer.
+    ///
+    /// If the first operand isn't an integer, this behaves exactly like the
+    /// corresponding [Inst::Op] with the constant pushed to the stack.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    IntOpConst {
+        /// The actual operation.
+        op: InstIntOp,
+        /// The address of the first argument.
+        a: InstAddress,
+        /// The constant second argument.
+        b: i64,
+    },
     /// A built-in operation that assigns to the left-hand side operand. Like
     /// `a += b`.
     ///
@@ -1046,8 +1090,12 @@ impl fmt::Display for Inst {
             Self::Call { hash, args } => {
                 write!(fmt, "call hash={}, args={}", hash, args)?;
             }
-            Self::CallInstance { hash, args } => {
-                write!(fmt, "call-instance hash={}, args={}", hash, args)?;
+            Self::CallInstance { hash, args, cache } => {
+                write!(
+                    fmt,
+                    "call-instance hash={}, args={}, cache={}",
+                    hash, args, cache
+                )?;
             }
             Self::Closure { hash, count } => {
                 write!(fmt, "closure hash={}, count={}", hash, count)?;
@@ -1070,14 +1118,22 @@ impl fmt::Display for Inst {
             Self::TupleIndexGetAt { offset, index } => {
                 write!(fmt, "tuple-index-get-at offset={}, index={}", offset, index)?;
             }
-            Self::ObjectIndexGet { slot } => {
-                write!(fmt, "object-index-get slot={}", slot)?;
+            Self::ObjectIndexGet { slot, cache } => {
+                write!(fmt, "object-index-get slot={}, cache={}", slot, cache)?;
             }
             Self::ObjectIndexSet { slot } => {
                 write!(fmt, "object-index-set slot={}", slot)?;
             }
-            Self::ObjectIndexGetAt { offset, slot } => {
-                write!(fmt, "object-index-get-at offset={}, slot={}", offset, slot)?;
+            Self::ObjectIndexGetAt {
+                offset,
+                slot,
+                cache,
+            } => {
+                write!(
+                    fmt,
+                    "object-index-get-at offset={}, slot={}, cache={}",
+                    offset, slot, cache
+                )?;
             }
             Self::IndexSet => {
                 write!(fmt, "index-set")?;
@@ -1278,6 +1334,12 @@ impl fmt::Display for Inst {
             Self::Op { op, a, b } => {
                 write!(fmt, "op op={}, a={}, b={}", op, a, b)?;
             }
+            Self::IntOp { op, a, b } => {
+                write!(fmt, "int-op op={}, a={}, b={}", op, a, b)?;
+            }
+            Self::IntOpConst { op, a, b } => {
+                write!(fmt, "int-op-const op={}, a={}, b={}", op, a, b)?;
+            }
             Self::Assign { target, op } => {
                 write!(fmt, "assign target={}, op={}", target, op)?;
             }
@@ -1312,7 +1374,7 @@ impl fmt::Display for Inst {
 }
 
 /// How an instruction addresses a value.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstAddress {
     /// Addressed from the top of the stack.
     Top,
@@ -1330,7 +1392,7 @@ impl fmt::Display for InstAddress {
 }
 
 /// Range limits of a range expression.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstRangeLimits {
     /// A half-open range `a .. b`.
     HalfOpen,
@@ -1348,7 +1410,7 @@ impl fmt::Display for InstRangeLimits {
 }
 
 /// The target of an operation.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstTarget {
     /// Target is an offset to the current call frame.
     Offset(usize),
@@ -1369,7 +1431,7 @@ impl fmt::Display for InstTarget {
 }
 
 /// An operation between two values on the machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstAssignOp {
     /// The add operation. `a + b`.
     Add,
@@ -1432,8 +1494,81 @@ impl fmt::Display for InstAssignOp {
     }
 }
 
+/// An operation between two values which has a fast path for integers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum InstIntOp {
+    /// The add operation. `a + b`.
+    Add,
+    /// The sub operation. `a - b`.
+    Sub,
+    /// The multiply operation. `a * b`.
+    Mul,
+    /// The division operation. `a / b`.
+    Div,
+    /// The remainder operation. `a % b`.
+    Rem,
+    /// Test if the first operand is less than the second operand. `a < b`.
+    Lt,
+    /// Test if the first operand is greater than the second operand. `a > b`.
+    Gt,
+    /// Test if the first operand is less than or equal to the second operand.
+    /// `a <= b`.
+    Lte,
+    /// Test if the first operand is greater than or equal to the second
+    /// operand. `a >= b`.
+    Gte,
+    /// Test if the two operands are equal. `a == b`.
+    Eq,
+    /// Test if the two operands are not equal. `a != b`.
+    Neq,
+}
+
+impl InstIntOp {
+    /// Get the integer operation corresponding to the given operation, if
+    /// there is one.
+    pub fn from_op(op: InstOp) -> Option<Self> {
+        Some(match op {
+            InstOp::Add => Self::Add,
+            InstOp::Sub => Self::Sub,
+            InstOp::Mul => Self::Mul,
+            InstOp::Div => Self::Div,
+            InstOp::Rem => Self::Rem,
+            InstOp::Lt => Self::Lt,
+            InstOp::Gt => Self::Gt,
+            InstOp::Lte => Self::Lte,
+            InstOp::Gte => Self::Gte,
+            InstOp::Eq => Self::Eq,
+            InstOp::Neq => Self::Neq,
+            _ => return None,
+        })
+    }
+
+    /// Get the generic operation this is a specialization of.
+    pub fn into_op(self) -> InstOp {
+        match self {
+            Self::Add => InstOp::Add,
+            Self::Sub => InstOp::Sub,
+            Self::Mul => InstOp::Mul,
+            Self::Div => InstOp::Div,
+            Self::Rem => InstOp::Rem,
+            Self::Lt => InstOp::Lt,
+            Self::Gt => InstOp::Gt,
+            Self::Lte => InstOp::Lte,
+            Self::Gte => InstOp::Gte,
+            Self::Eq => InstOp::Eq,
+            Self::Neq => InstOp::Neq,
+        }
+    }
+}
+
+impl fmt::Display for InstIntOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.into_op().fmt(f)
+    }
+}
+
 /// An operation between two values on the machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstOp {
     /// The add operation. `a + b`.
     Add,
@@ -1604,7 +1739,7 @@ impl fmt::Display for InstOp {
 }
 
 /// A literal value that can be pushed.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstValue {
     /// A unit.
     Unit,
@@ -1660,7 +1795,7 @@ impl fmt::Display for InstValue {
 }
 
 /// A variant that can be constructed.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstVariant {
     /// `Option::Some`, which uses one value.
     Some,
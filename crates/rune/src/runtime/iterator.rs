@@ -1,9 +1,10 @@
 use crate::compile::Named;
 use crate::runtime::{
-    FromValue, Function, Mut, RawMut, RawRef, RawStr, Ref, ToValue, UnsafeFromValue, Value,
+    FromValue, Function, Key, Mut, RawMut, RawRef, RawStr, Ref, ToValue, UnsafeFromValue, Value,
     VmError, VmErrorKind,
 };
 use crate::InstallWith;
+use std::collections::VecDeque;
 use std::fmt;
 use std::iter;
 use std::vec;
@@ -123,6 +124,22 @@ impl Iterator {
         self.iter.size_hint()
     }
 
+    /// Get the number of remaining elements in the iterator.
+    ///
+    /// This errors if the iterator is not exact-sized, that is if its
+    /// [size_hint][Iterator::size_hint] doesn't have matching bounds.
+    pub fn len(&self) -> Result<usize, VmError> {
+        self.iter.len()
+    }
+
+    /// Test if the iterator has no remaining elements.
+    ///
+    /// Like [len][Iterator::len], this errors if the iterator is not
+    /// exact-sized.
+    pub fn is_empty(&self) -> Result<bool, VmError> {
+        Ok(self.len()? == 0)
+    }
+
     /// Get the next value out of the iterator.
     pub fn next(&mut self) -> Result<Option<Value>, VmError> {
         self.iter.next()
@@ -267,6 +284,100 @@ impl Iterator {
         }
     }
 
+    /// Zip this iterator with another, producing tuples of their values until
+    /// either of them runs out.
+    pub fn zip(self, other: Value) -> Result<Self, VmError> {
+        let other = other.into_iter()?;
+
+        Ok(Self {
+            iter: IterRepr::Zip(Box::new(Zip {
+                a: self.iter,
+                b: other.iter,
+            })),
+        })
+    }
+
+    /// Split the iterator into vectors of `n` elements, where the last one
+    /// might be shorter.
+    pub fn chunks(self, n: usize) -> Result<Self, VmError> {
+        if n == 0 {
+            return Err(VmError::panic("chunk size must be non-zero"));
+        }
+
+        Ok(Self {
+            iter: IterRepr::Chunks(Box::new(Chunks { iter: self.iter, n })),
+        })
+    }
+
+    /// Produce vectors of `n` consecutive elements, overlapping by all but
+    /// one.
+    pub fn windows(self, n: usize) -> Result<Self, VmError> {
+        if n == 0 {
+            return Err(VmError::panic("window size must be non-zero"));
+        }
+
+        Ok(Self {
+            iter: IterRepr::Windows(Box::new(Windows {
+                iter: self.iter,
+                n,
+                window: VecDeque::with_capacity(n),
+            })),
+        })
+    }
+
+    /// Take elements while the given predicate matches.
+    pub fn take_while(self, predicate: Function) -> Self {
+        Self {
+            iter: IterRepr::TakeWhile(Box::new(TakeWhile {
+                iter: self.iter,
+                predicate,
+                done: false,
+            })),
+        }
+    }
+
+    /// Skip over elements while the given predicate matches.
+    pub fn skip_while(self, predicate: Function) -> Self {
+        Self {
+            iter: IterRepr::SkipWhile(Box::new(SkipWhile {
+                iter: self.iter,
+                predicate: Some(predicate),
+            })),
+        }
+    }
+
+    /// Group consecutive elements which map to the same key, producing tuples
+    /// of the key and a vector of the elements.
+    pub fn group_by(self, key: Function) -> Self {
+        Self {
+            iter: IterRepr::GroupBy(Box::new(GroupBy {
+                iter: self.iter,
+                key,
+                pending: None,
+            })),
+        }
+    }
+
+    /// Split the iterator into the elements which match the given predicate,
+    /// and the ones which don't.
+    pub fn partition(
+        mut self,
+        predicate: Function,
+    ) -> Result<(vec::Vec<Value>, vec::Vec<Value>), VmError> {
+        let mut matched = vec::Vec::new();
+        let mut unmatched = vec::Vec::new();
+
+        while let Some(value) = self.next()? {
+            if predicate.call::<_, bool>((value.clone(),))? {
+                matched.push(value);
+            } else {
+                unmatched.push(value);
+            }
+        }
+
+        Ok((matched, unmatched))
+    }
+
     /// Count the number of elements remaining in the iterator.
     pub fn count(&mut self) -> Result<usize, VmError> {
         let mut c = 0;
@@ -397,6 +508,12 @@ enum IterRepr {
     Skip(Box<Skip<Self>>),
     Take(Box<Take<Self>>),
     Peekable(Box<Peekable<Self>>),
+    Zip(Box<Zip<Self, Self>>),
+    Chunks(Box<Chunks<Self>>),
+    Windows(Box<Windows<Self>>),
+    TakeWhile(Box<TakeWhile<Self>>),
+    SkipWhile(Box<SkipWhile<Self>>),
+    GroupBy(Box<GroupBy<Self>>),
     Empty,
     Once(Option<Value>),
 }
@@ -416,6 +533,12 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.is_double_ended(),
             Self::Take(iter) => iter.is_double_ended(),
             Self::Peekable(iter) => iter.is_double_ended(),
+            Self::Zip(iter) => iter.is_double_ended(),
+            Self::Chunks(iter) => iter.is_double_ended(),
+            Self::Windows(iter) => iter.is_double_ended(),
+            Self::TakeWhile(iter) => iter.is_double_ended(),
+            Self::SkipWhile(iter) => iter.is_double_ended(),
+            Self::GroupBy(iter) => iter.is_double_ended(),
             Self::Empty => true,
             Self::Once(..) => true,
         }
@@ -435,6 +558,12 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.size_hint(),
             Self::Take(iter) => iter.size_hint(),
             Self::Peekable(iter) => iter.size_hint(),
+            Self::Zip(iter) => iter.size_hint(),
+            Self::Chunks(iter) => iter.size_hint(),
+            Self::Windows(iter) => iter.size_hint(),
+            Self::TakeWhile(iter) => iter.size_hint(),
+            Self::SkipWhile(iter) => iter.size_hint(),
+            Self::GroupBy(iter) => iter.size_hint(),
             Self::Empty => (0, Some(0)),
             Self::Once(..) => (1, Some(1)),
         }
@@ -453,6 +582,12 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.next(),
             Self::Take(iter) => iter.next(),
             Self::Peekable(iter) => iter.next(),
+            Self::Zip(iter) => iter.next(),
+            Self::Chunks(iter) => iter.next(),
+            Self::Windows(iter) => iter.next(),
+            Self::TakeWhile(iter) => iter.next(),
+            Self::SkipWhile(iter) => iter.next(),
+            Self::GroupBy(iter) => iter.next(),
             Self::Empty => Ok(None),
             Self::Once(v) => Ok(v.take()),
         }
@@ -474,6 +609,12 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.next_back(),
             Self::Take(iter) => iter.next_back(),
             Self::Peekable(iter) => iter.next_back(),
+            Self::Zip(iter) => iter.next_back(),
+            Self::Chunks(iter) => iter.next_back(),
+            Self::Windows(iter) => iter.next_back(),
+            Self::TakeWhile(iter) => iter.next_back(),
+            Self::SkipWhile(iter) => iter.next_back(),
+            Self::GroupBy(iter) => iter.next_back(),
             Self::Empty => Ok(None),
             Self::Once(v) => Ok(v.take()),
         }
@@ -494,6 +635,12 @@ impl fmt::Debug for IterRepr {
             Self::Skip(iter) => write!(f, "{:?}", iter),
             Self::Take(iter) => write!(f, "{:?}", iter),
             Self::Peekable(iter) => write!(f, "{:?}", iter),
+            Self::Zip(iter) => write!(f, "{:?}", iter),
+            Self::Chunks(iter) => write!(f, "{:?}", iter),
+            Self::Windows(iter) => write!(f, "{:?}", iter),
+            Self::TakeWhile(iter) => write!(f, "{:?}", iter),
+            Self::SkipWhile(iter) => write!(f, "{:?}", iter),
+            Self::GroupBy(iter) => write!(f, "{:?}", iter),
             Self::Empty => write!(f, "std::iter::Empty"),
             Self::Once(..) => write!(f, "std::iter::Once"),
         }
@@ -573,11 +720,11 @@ where
             return false;
         }
 
-        if !matches!(&self.frontiter, Some(iter) if !iter.is_double_ended()) {
+        if matches!(&self.frontiter, Some(iter) if !iter.is_double_ended()) {
             return false;
         }
 
-        if !matches!(&self.backiter, Some(iter) if !iter.is_double_ended()) {
+        if matches!(&self.backiter, Some(iter) if !iter.is_double_ended()) {
             return false;
         }
 
@@ -648,7 +795,8 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
     }
 
     fn next(&mut self) -> Result<Option<Value>, VmError> {
@@ -978,7 +1126,17 @@ where
             return Ok(None);
         }
 
+        // NB: skip over the trailing elements which are not part of the taken
+        // range, which requires the inner iterator to be exact-sized.
+        let skip = self.iter.len()?.saturating_sub(self.n);
         self.n -= 1;
+
+        for _ in 0..skip {
+            if self.iter.next_back()?.is_none() {
+                return Ok(None);
+            }
+        }
+
         self.iter.next_back()
     }
 }
@@ -1048,6 +1206,334 @@ where
     }
 }
 
+#[derive(Debug)]
+struct Zip<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> RuneIterator for Zip<A, B>
+where
+    A: RuneIterator,
+    B: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+
+        let lower = std::cmp::min(a_lower, b_lower);
+
+        let upper = match (a_upper, b_upper) {
+            (Some(x), Some(y)) => Some(std::cmp::min(x, y)),
+            (Some(x), None) => Some(x),
+            (None, Some(y)) => Some(y),
+            (None, None) => None,
+        };
+
+        (lower, upper)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let a = match self.a.next()? {
+            Some(a) => a,
+            None => return Ok(None),
+        };
+
+        let b = match self.b.next()? {
+            Some(b) => b,
+            None => return Ok(None),
+        };
+
+        Ok(Some((a, b).to_value()?))
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(VmError::panic(format!(
+            "`{:?}` is not a double-ended iterator",
+            self
+        )))
+    }
+}
+
+#[derive(Debug)]
+struct Chunks<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<I> RuneIterator for Chunks<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let div = |x: usize| x / self.n + usize::from(x % self.n != 0);
+        (div(lower), upper.map(div))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let mut chunk = vec::Vec::with_capacity(self.n);
+
+        while chunk.len() < self.n {
+            match self.iter.next()? {
+                Some(value) => chunk.push(value),
+                None => break,
+            }
+        }
+
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(chunk.to_value()?))
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(VmError::panic(format!(
+            "`{:?}` is not a double-ended iterator",
+            self
+        )))
+    }
+}
+
+#[derive(Debug)]
+struct Windows<I> {
+    iter: I,
+    n: usize,
+    window: VecDeque<Value>,
+}
+
+impl<I> RuneIterator for Windows<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // NB: once the window is full, every element produces one window.
+        let missing = self.n - std::cmp::max(self.window.len(), 1);
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_sub(missing),
+            upper.map(|x| x.saturating_sub(missing)),
+        )
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.window.len() == self.n {
+            self.window.pop_front();
+        }
+
+        while self.window.len() < self.n {
+            match self.iter.next()? {
+                Some(value) => self.window.push_back(value),
+                None => return Ok(None),
+            }
+        }
+
+        let window = self.window.iter().cloned().collect::<vec::Vec<_>>();
+        Ok(Some(window.to_value()?))
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(VmError::panic(format!(
+            "`{:?}` is not a double-ended iterator",
+            self
+        )))
+    }
+}
+
+#[derive(Debug)]
+struct TakeWhile<I> {
+    iter: I,
+    predicate: Function,
+    done: bool,
+}
+
+impl<I> RuneIterator for TakeWhile<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        if let Some(value) = self.iter.next()? {
+            if self.predicate.call::<_, bool>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        self.done = true;
+        Ok(None)
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(VmError::panic(format!(
+            "`{:?}` is not a double-ended iterator",
+            self
+        )))
+    }
+}
+
+#[derive(Debug)]
+struct SkipWhile<I> {
+    iter: I,
+    /// The predicate, which is cleared once it stops matching.
+    predicate: Option<Function>,
+}
+
+impl<I> RuneIterator for SkipWhile<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+
+        match self.predicate {
+            Some(..) => (0, upper),
+            None => (lower, upper),
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let predicate = match self.predicate.take() {
+            Some(predicate) => predicate,
+            None => return self.iter.next(),
+        };
+
+        while let Some(value) = self.iter.next()? {
+            if !predicate.call::<_, bool>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(VmError::panic(format!(
+            "`{:?}` is not a double-ended iterator",
+            self
+        )))
+    }
+}
+
+#[derive(Debug)]
+struct GroupBy<I> {
+    iter: I,
+    key: Function,
+    /// The first element of the next group along with its key, which was read
+    /// while finishing the previous group.
+    pending: Option<(Key, Value, Value)>,
+}
+
+impl<I> RuneIterator for GroupBy<I>
+where
+    I: RuneIterator,
+{
+    #[inline]
+    fn is_double_ended(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = usize::from(self.pending.is_some());
+        let (lower, upper) = self.iter.size_hint();
+        (
+            usize::from(lower > 0 || pending > 0),
+            upper.and_then(|x| x.checked_add(pending)),
+        )
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let (key, key_value, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => match self.iter.next()? {
+                Some(value) => {
+                    let key_value = self.key.call::<_, Value>((value.clone(),))?;
+                    (Key::from_value(&key_value)?, key_value, value)
+                }
+                None => return Ok(None),
+            },
+        };
+
+        let mut group = vec![first];
+
+        while let Some(value) = self.iter.next()? {
+            let next_value = self.key.call::<_, Value>((value.clone(),))?;
+            let next = Key::from_value(&next_value)?;
+
+            if next != key {
+                self.pending = Some((next, next_value, value));
+                break;
+            }
+
+            group.push(value);
+        }
+
+        Ok(Some((key_value, group).to_value()?))
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Err(VmError::panic(format!(
+            "`{:?}` is not a double-ended iterator",
+            self
+        )))
+    }
+}
+
 #[derive(Debug)]
 struct Fuse<I> {
     iter: Option<I>,
@@ -1,7 +1,9 @@
 use crate::runtime::{
-    Bytes, FromValue, Object, Shared, StaticString, ToValue, Tuple, TypeInfo, Value, Variant,
-    VariantData, VariantRtti, Vec, VmError, VmErrorKind,
+    AnyObj, Bytes, EnvProtocolCaller, FromValue, Object, Protocol, ProtocolCaller, Shared,
+    StaticString, ToValue, Tuple, TypeInfo, Value, Variant, VariantData, VariantRtti, Vec, VmError,
+    VmErrorKind,
 };
+use crate::Hash;
 use serde::{de, ser};
 use std::cmp;
 use std::fmt;
@@ -34,6 +36,8 @@ pub enum Key {
     Option(Option<Box<Key>>),
     /// A variant.
     Variant(VariantKey),
+    /// An external value which implements the [Protocol::HASH] protocol.
+    Any(AnyKey),
 }
 
 impl Key {
@@ -88,6 +92,7 @@ impl Key {
                     data,
                 })
             }
+            Value::Any(any) => Key::Any(AnyKey::new(any.clone())?),
             value => {
                 return Err(VmError::from(VmErrorKind::KeyNotSupported {
                     actual: value.type_info()?,
@@ -158,6 +163,7 @@ impl Key {
                     data,
                 }))
             }
+            Self::Any(key) => Value::Any(key.value),
         };
 
         fn tuple_into_value(data: Box<[Key]>) -> Tuple {
@@ -203,6 +209,7 @@ impl Key {
             Self::Tuple(..) => TypeInfo::StaticType(crate::runtime::TUPLE_TYPE),
             Self::Option(..) => TypeInfo::StaticType(crate::runtime::OPTION_TYPE),
             Self::Variant(variant) => TypeInfo::Variant(variant.rtti.clone()),
+            Self::Any(key) => key.type_info.clone(),
         }
     }
 }
@@ -221,6 +228,7 @@ impl fmt::Debug for Key {
             Key::Tuple(tuple) => write!(f, "{:?}", tuple),
             Key::Option(opt) => write!(f, "{:?}", opt),
             Key::Variant(variant) => write!(f, "{:?}", variant),
+            Key::Any(key) => write!(f, "{:?}", key.type_info),
         }
     }
 }
@@ -283,6 +291,7 @@ impl ser::Serialize for Key {
             }
             Self::Option(option) => <Option<Box<Key>>>::serialize(option, serializer),
             Self::Variant(..) => Err(ser::Error::custom("cannot serialize variants")),
+            Self::Any(..) => Err(ser::Error::custom("cannot serialize external values")),
         }
     }
 }
@@ -556,3 +565,100 @@ pub enum VariantKeyData {
     /// An struct variant with a specific type hash.
     Struct(Box<[(Box<str>, Key)]>),
 }
+
+/// An external value that has been converted into a key.
+///
+/// The hash of the value is calculated once through the [Protocol::HASH]
+/// protocol when the key is constructed, and equality is tested through the
+/// [Protocol::EQ] protocol. Modifying a value after it has been used as a key
+/// in such a way that its hash changes is a logic error.
+#[derive(Clone)]
+pub struct AnyKey {
+    type_hash: Hash,
+    type_info: TypeInfo,
+    hash: i64,
+    value: Shared<AnyObj>,
+}
+
+impl AnyKey {
+    fn new(value: Shared<AnyObj>) -> Result<Self, VmError> {
+        let (type_hash, type_info) = {
+            let any = value.borrow_ref()?;
+            (any.type_hash(), TypeInfo::Any(any.type_name()))
+        };
+
+        let target = Value::Any(value.clone());
+
+        let hash = match EnvProtocolCaller.call_protocol_fn(Protocol::HASH, target, ()) {
+            Ok(hash) => i64::from_value(hash)?,
+            Err(error) => match error.kind() {
+                VmErrorKind::MissingFunction { hash }
+                    if *hash == Hash::instance_function(type_hash, Protocol::HASH) =>
+                {
+                    return Err(VmError::from(VmErrorKind::KeyNotSupported {
+                        actual: type_info,
+                    }));
+                }
+                _ => return Err(error),
+            },
+        };
+
+        Ok(Self {
+            type_hash,
+            type_info,
+            hash,
+            value,
+        })
+    }
+}
+
+impl cmp::PartialEq for AnyKey {
+    fn eq(&self, other: &Self) -> bool {
+        if self.type_hash != other.type_hash || self.hash != other.hash {
+            return false;
+        }
+
+        if Shared::as_ptr(&self.value) == Shared::as_ptr(&other.value) {
+            return true;
+        }
+
+        // NB: errors can't be propagated from here, so a failure to compare
+        // two keys causes them to be treated as distinct.
+        let result = EnvProtocolCaller.call_protocol_fn(
+            Protocol::EQ,
+            Value::Any(self.value.clone()),
+            (Value::Any(other.value.clone()),),
+        );
+
+        matches!(result.and_then(bool::from_value), Ok(true))
+    }
+}
+
+impl cmp::Eq for AnyKey {}
+
+impl hash::Hash for AnyKey {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.type_hash.hash(state);
+        self.hash.hash(state);
+    }
+}
+
+impl cmp::PartialOrd for AnyKey {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for AnyKey {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        let ordering = (self.type_hash, self.hash).cmp(&(other.type_hash, other.hash));
+
+        if ordering.is_ne() || self == other {
+            return ordering;
+        }
+
+        // Keys with colliding hashes which aren't equal are ordered by
+        // identity.
+        Shared::as_ptr(&self.value).cmp(&Shared::as_ptr(&other.value))
+    }
+}
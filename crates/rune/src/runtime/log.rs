@@ -0,0 +1,155 @@
+//! Routing of log messages emitted by scripts.
+//!
+//! See [Logger] for more information.
+
+use crate::runtime::env;
+use std::fmt;
+use std::sync::Arc;
+
+/// The level of a log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Level {
+    /// Very verbose information, like `std::log::trace`.
+    Trace,
+    /// Debugging information, like `std::log::debug`.
+    Debug,
+    /// Useful information, like `std::log::info`.
+    Info,
+    /// Potentially hazardous situations, like `std::log::warn`.
+    Warn,
+    /// Serious errors, like `std::log::error`.
+    Error,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Trace => "TRACE",
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+        };
+
+        f.pad(name)
+    }
+}
+
+/// A logger provided by the host which receives the messages scripts emit
+/// through the `std::log` module.
+///
+/// Installing a logger through [Context::set_logger] routes messages to it,
+/// which makes it possible to bridge them into whichever logging framework
+/// the application uses. Without a logger messages are emitted as [tracing]
+/// events with the `rune::script` target.
+///
+/// [Context::set_logger]: crate::Context::set_logger
+/// [tracing]: https://docs.rs/tracing
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::log::{Level, Logger};
+/// use rune::{Context, Vm};
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Default)]
+/// struct Recorder(Mutex<Vec<(Level, String)>>);
+///
+/// impl Logger for Recorder {
+///     fn log(&self, level: Level, message: &str) {
+///         self.0.lock().unwrap().push((level, message.to_owned()));
+///     }
+/// }
+///
+/// # fn main() -> rune::Result<()> {
+/// let recorder = Arc::new(Recorder::default());
+///
+/// let mut context = Context::with_default_modules()?;
+/// context.set_logger(recorder.clone());
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             let n = 42;
+///             std::log::info!("the answer is {}", n);
+///             std::log::warn("careful");
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+/// vm.call(["main"], ())?;
+///
+/// let records = recorder.0.lock().unwrap();
+/// assert_eq!(records[0], (Level::Info, String::from("the answer is 42")));
+/// assert_eq!(records[1], (Level::Warn, String::from("careful")));
+/// # Ok(()) }
+/// ```
+pub trait Logger: Send + Sync {
+    /// Test if messages at the given level are of interest.
+    ///
+    /// Scripts can use this through `std::log::enabled` to avoid building
+    /// expensive messages. By default every level is enabled.
+    fn enabled(&self, level: Level) -> bool {
+        let _ = level;
+        true
+    }
+
+    /// Log a message at the given level.
+    fn log(&self, level: Level, message: &str);
+}
+
+impl<L> Logger for Arc<L>
+where
+    L: ?Sized + Logger,
+{
+    fn enabled(&self, level: Level) -> bool {
+        (**self).enabled(level)
+    }
+
+    fn log(&self, level: Level, message: &str) {
+        (**self).log(level, message)
+    }
+}
+
+/// Test if messages at the given level are of interest, using the logger of
+/// the current context if one is installed.
+pub fn enabled(level: Level) -> bool {
+    match current() {
+        Some(logger) => logger.enabled(level),
+        None => match level {
+            Level::Trace => tracing::enabled!(target: "rune::script", tracing::Level::TRACE),
+            Level::Debug => tracing::enabled!(target: "rune::script", tracing::Level::DEBUG),
+            Level::Info => tracing::enabled!(target: "rune::script", tracing::Level::INFO),
+            Level::Warn => tracing::enabled!(target: "rune::script", tracing::Level::WARN),
+            Level::Error => tracing::enabled!(target: "rune::script", tracing::Level::ERROR),
+        },
+    }
+}
+
+/// Log a message at the given level, using the logger of the current context
+/// if one is installed.
+pub fn log(level: Level, message: &str) {
+    match current() {
+        Some(logger) => logger.log(level, message),
+        None => match level {
+            Level::Trace => tracing::trace!(target: "rune::script", "{}", message),
+            Level::Debug => tracing::debug!(target: "rune::script", "{}", message),
+            Level::Info => tracing::info!(target: "rune::script", "{}", message),
+            Level::Warn => tracing::warn!(target: "rune::script", "{}", message),
+            Level::Error => tracing::error!(target: "rune::script", "{}", message),
+        },
+    }
+}
+
+/// Get the logger of the current context, if one is installed and we are
+/// running inside of a virtual machine.
+fn current() -> Option<Arc<dyn Logger>> {
+    env::with(|context, _| Ok(context.logger().cloned()))
+        .ok()
+        .flatten()
+}
@@ -1,15 +1,21 @@
 //! Runtime module for Rune.
 
 mod access;
+pub mod ambient;
 mod any_obj;
 mod args;
 mod awaited;
 pub mod budget;
 mod bytes;
 mod call;
+pub(crate) mod capabilities;
+pub mod channel;
 mod const_value;
+pub mod cycles;
 pub mod debug;
+mod debugger;
 mod env;
+pub(crate) mod executor;
 pub mod format;
 mod from_value;
 mod function;
@@ -17,65 +23,89 @@ pub(crate) mod future;
 mod generator;
 mod generator_state;
 mod guarded_args;
+pub mod host;
+mod inline_cache;
 mod inst;
 mod iterator;
 mod key;
 mod label;
+pub mod log;
 mod object;
+pub mod output;
 mod panic;
+mod profiler;
 mod protocol;
 mod protocol_caller;
 mod range;
 mod raw_str;
 mod runtime_context;
 mod select;
+mod send_value;
 mod shared;
 mod stack;
 mod static_string;
 mod static_type;
 mod stream;
+mod symbol;
 mod to_value;
 mod tuple;
 mod type_info;
 mod type_of;
+mod typed_function;
 mod unit;
+mod unit_diff;
+mod unit_report;
 mod value;
+mod value_serde;
 mod variant;
 mod vec;
 mod vec_tuple;
 mod vm;
+mod vm_backtrace;
 mod vm_call;
 mod vm_error;
 mod vm_execution;
 mod vm_halt;
+mod vm_limits;
+mod vm_pool;
+mod vm_snapshot;
+mod vm_tracer;
 
 pub(crate) use self::access::{Access, AccessKind};
 pub use self::access::{
     AccessError, BorrowMut, BorrowRef, NotAccessibleMut, NotAccessibleRef, RawAccessGuard,
 };
+pub use self::ambient::Ambient;
 pub use self::any_obj::{AnyObj, AnyObjError, AnyObjVtable};
 pub use self::args::Args;
 pub(crate) use self::awaited::Awaited;
 pub use self::bytes::Bytes;
 pub use self::call::Call;
+pub use self::capabilities::Capabilities;
 pub use self::const_value::ConstValue;
-pub use self::debug::{DebugInfo, DebugInst};
+pub use self::cycles::CycleCollector;
+pub use self::debug::{DebugInfo, DebugInst, DebugVariable};
+pub use self::debugger::{Breakpoint, DebugEvent, DebugFrame, Debugger, StepMode};
+pub use self::executor::{Executor, SpawnFuture, SpawnTask};
 pub use self::format::{Format, FormatSpec};
 pub use self::from_value::{FromValue, UnsafeFromValue};
-pub use self::function::{Function, SyncFunction};
+pub use self::function::{Function, SyncFunction, TypedArgs, TypedFuture};
 pub use self::future::Future;
 pub use self::generator::Generator;
 pub use self::generator_state::GeneratorState;
 pub use self::guarded_args::GuardedArgs;
+pub use self::host::Host;
 pub use self::inst::{
-    Inst, InstAddress, InstAssignOp, InstOp, InstRangeLimits, InstTarget, InstValue, InstVariant,
-    PanicReason, TypeCheck,
+    Inst, InstAddress, InstAssignOp, InstIntOp, InstOp, InstRangeLimits, InstTarget, InstValue,
+    InstVariant, PanicReason, TypeCheck,
 };
 pub use self::iterator::{Iterator, IteratorTrait};
 pub use self::key::Key;
 pub use self::label::{DebugLabel, Label};
+pub use self::log::Logger;
 pub use self::object::Object;
 pub use self::panic::Panic;
+pub use self::profiler::{FunctionProfile, Profile, Profiler, StackProfile};
 pub use self::protocol::Protocol;
 pub(crate) use self::protocol_caller::{EnvProtocolCaller, ProtocolCaller};
 pub use self::range::{Range, RangeLimits};
@@ -83,6 +113,7 @@ pub use self::raw_str::RawStr;
 pub use self::runtime_context::RuntimeContext;
 pub(crate) use self::runtime_context::{FunctionHandler, MacroHandler};
 pub use self::select::Select;
+pub use self::send_value::SendValue;
 pub use self::shared::{Mut, RawMut, RawRef, Ref, Shared, SharedPointerGuard};
 pub use self::stack::{Stack, StackError};
 pub use self::static_string::StaticString;
@@ -93,18 +124,32 @@ pub use self::static_type::{
     UNIT_TYPE, VEC_TYPE,
 };
 pub use self::stream::Stream;
+pub use self::symbol::Symbol;
 pub use self::to_value::{ToValue, UnsafeToValue};
 pub use self::tuple::Tuple;
 pub use self::type_info::TypeInfo;
 pub use self::type_of::TypeOf;
+pub use self::typed_function::TypedFunction;
+pub(crate) use self::unit::{LazyGeneration, UnitGeneration};
 pub use self::unit::{Unit, UnitFn};
+pub use self::unit_diff::UnitDiff;
+pub use self::unit_report::{FunctionReport, UnitReport};
 pub use self::value::{Rtti, Struct, TupleStruct, UnitStruct, Value, VariantRtti};
+pub use self::value_serde::{from_value, to_value, ValueSerializer};
 pub use self::variant::{Variant, VariantData};
 pub use self::vec::Vec;
 pub use self::vec_tuple::VecTuple;
+pub(crate) use self::vm::Inherited;
 pub use self::vm::{CallFrame, Vm};
+pub use self::vm_backtrace::{BacktraceFrame, VmBacktrace};
 pub(crate) use self::vm_call::VmCall;
 pub use self::vm_error::{VmError, VmErrorKind, VmIntegerRepr};
-pub use self::vm_execution::{ExecutionState, VmExecution, VmSendExecution};
+pub use self::vm_execution::{ExecutionState, FuelState, VmExecution, VmSendExecution};
 pub(crate) use self::vm_halt::VmHalt;
 pub use self::vm_halt::VmHaltInfo;
+pub use self::vm_limits::VmLimits;
+pub(crate) use self::vm_limits::{charge_allocation, AllocationUsage};
+pub use self::vm_pool::{PooledVm, VmPool};
+pub use self::vm_snapshot::VmSnapshot;
+pub(crate) use self::vm_tracer::Tracer;
+pub use self::vm_tracer::VmTracer;
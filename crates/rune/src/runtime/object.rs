@@ -97,6 +97,19 @@ impl Object {
         }
     }
 
+    /// Construct a deep copy of the object.
+    ///
+    /// See [Value::clone_deep].
+    pub fn clone_deep(&self) -> Result<Self, VmError> {
+        let mut inner = BTreeMap::new();
+
+        for (key, value) in &self.inner {
+            inner.insert(key.clone(), value.clone_deep()?);
+        }
+
+        Ok(Self { inner })
+    }
+
     /// Returns the number of elements in the object.
     #[inline]
     pub fn len(&self) -> usize {
@@ -182,6 +195,16 @@ impl Object {
         self.inner.insert(k, v)
     }
 
+    /// Retains only the key-value pairs for which the given predicate returns
+    /// `true`.
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&String, &mut Value) -> bool,
+    {
+        self.inner.retain(f);
+    }
+
     /// Clears the object, removing all key-value pairs. Keeps the allocated
     /// memory for reuse.
     #[inline]
@@ -0,0 +1,119 @@
+//! Redirection of the output written by scripts.
+//!
+//! See [Context::set_stdout] for more information.
+//!
+//! [Context::set_stdout]: crate::Context::set_stdout
+
+use crate::runtime::{env, RuntimeContext};
+use std::io;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+/// A sink which output can be redirected to.
+pub(crate) type Sink = Arc<Mutex<dyn io::Write + Send>>;
+
+/// A sink which captures everything written to it in memory, intended to be
+/// used when testing the output of scripts.
+///
+/// Clones share the same buffer, so one clone can be installed through
+/// [Context::set_stdout] while another is used to inspect what was written.
+///
+/// [Context::set_stdout]: crate::Context::set_stdout
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::output::Capture;
+/// use rune::{Context, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> rune::Result<()> {
+/// let capture = Capture::new();
+///
+/// let mut context = Context::with_default_modules()?;
+/// context.set_stdout(capture.clone());
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             println!("Hello {}", "World");
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+/// vm.call(["main"], ())?;
+///
+/// assert_eq!(capture.drain_utf8()?, "Hello World\n");
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Capture {
+    buf: Arc<Mutex<Vec<u8>>>,
+}
+
+impl Capture {
+    /// Construct a new empty capture.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take everything which has been captured so far, leaving the capture
+    /// empty.
+    pub fn drain(&self) -> Vec<u8> {
+        mem::take(&mut *self.buf.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Take everything which has been captured so far as a string.
+    pub fn drain_utf8(&self) -> Result<String, std::string::FromUtf8Error> {
+        String::from_utf8(self.drain())
+    }
+}
+
+impl io::Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Write to the standard output of the current context, which is the
+/// standard output of the process unless it has been redirected.
+pub(crate) fn with_stdout<F, T>(f: F) -> io::Result<T>
+where
+    F: FnOnce(&mut dyn io::Write) -> io::Result<T>,
+{
+    match current(|context| context.stdout()) {
+        Some(sink) => f(&mut *sink.lock().unwrap_or_else(|e| e.into_inner())),
+        None => f(&mut io::stdout().lock()),
+    }
+}
+
+/// Write to the standard error of the current context, which is the standard
+/// error of the process unless it has been redirected.
+pub(crate) fn with_stderr<F, T>(f: F) -> io::Result<T>
+where
+    F: FnOnce(&mut dyn io::Write) -> io::Result<T>,
+{
+    match current(|context| context.stderr()) {
+        Some(sink) => f(&mut *sink.lock().unwrap_or_else(|e| e.into_inner())),
+        None => f(&mut io::stderr().lock()),
+    }
+}
+
+/// Get a sink of the current context, if one is installed and we are running
+/// inside of a virtual machine.
+fn current(sink: impl FnOnce(&RuntimeContext) -> Option<&Sink>) -> Option<Sink> {
+    env::with(|context, _| Ok(sink(context).cloned()))
+        .ok()
+        .flatten()
+}
@@ -0,0 +1,364 @@
+//! An opt-in profiler for virtual machines.
+//!
+//! See [Profiler] for more information.
+
+use crate::collections::HashMap;
+use crate::runtime::{Unit, UnitFn, Vm};
+use crate::Hash;
+use std::fmt;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A profiler which samples the call stack of a [Vm] while it executes.
+///
+/// Every `interval` instructions the call stack of the executing virtual
+/// machine is sampled, and the instructions and time elapsed since the last
+/// sample are attributed to it. With the default interval of `1` every
+/// instruction is accounted for exactly.
+///
+/// A profiler is installed through [Vm::set_profiler] and is inherited by the
+/// virtual machines spawned to execute generators, streams, async functions
+/// and function values. Since these execute on a virtual machine of their
+/// own, their samples only include the call stack of that virtual machine.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::Profiler;
+/// use rune::{Context, Hash, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> rune::Result<()> {
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn work(n) {
+///             let out = 0;
+///
+///             for i in 0..n {
+///                 out += i;
+///             }
+///
+///             out
+///         }
+///
+///         pub fn main() {
+///             work(10) + work(20)
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+///
+/// let profiler = Arc::new(Profiler::new());
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+/// vm.set_profiler(profiler.clone());
+/// vm.call(["main"], ())?;
+///
+/// let profile = profiler.profile();
+/// let work = profile.function(Hash::type_hash(["work"])).expect("missing work");
+/// assert_eq!(work.name.as_deref(), Some("work"));
+/// assert!(work.self_instructions > 0);
+///
+/// let mut collapsed = Vec::new();
+/// profile.write_collapsed(&mut collapsed)?;
+/// let collapsed = String::from_utf8(collapsed)?;
+/// assert!(collapsed.lines().any(|line| line.starts_with("main;work ")));
+/// # Ok(()) }
+/// ```
+pub struct Profiler {
+    /// The number of instructions between each sample.
+    interval: usize,
+    /// Instructions executed since the last sample.
+    counter: AtomicUsize,
+    /// Accumulated samples.
+    state: Mutex<State>,
+}
+
+impl Profiler {
+    /// Construct a new profiler which samples every instruction.
+    pub fn new() -> Self {
+        Self::with_interval(1)
+    }
+
+    /// Construct a new profiler which samples every `interval` instructions.
+    ///
+    /// A larger interval reduces the overhead of profiling at the cost of
+    /// precision.
+    pub fn with_interval(interval: usize) -> Self {
+        Self {
+            interval: interval.max(1),
+            counter: AtomicUsize::new(0),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Clear all samples collected so far.
+    pub fn reset(&self) {
+        *self.lock() = State::default();
+    }
+
+    /// Construct a profile out of the samples collected so far.
+    pub fn profile(&self) -> Profile {
+        let state = self.lock();
+
+        let mut functions = HashMap::<Hash, FunctionProfile>::new();
+        let mut stacks = Vec::with_capacity(state.stacks.len());
+
+        for (stack, sample) in &state.stacks {
+            for (n, hash) in stack.iter().enumerate() {
+                let function = functions.entry(*hash).or_insert_with(|| FunctionProfile {
+                    hash: *hash,
+                    name: state.names.get(hash).cloned(),
+                    self_instructions: 0,
+                    self_time: Duration::ZERO,
+                    total_instructions: 0,
+                    total_time: Duration::ZERO,
+                });
+
+                // NB: only count recursive frames once towards the total.
+                if !stack[..n].contains(hash) {
+                    function.total_instructions += sample.instructions;
+                    function.total_time += sample.time;
+                }
+
+                if n + 1 == stack.len() {
+                    function.self_instructions += sample.instructions;
+                    function.self_time += sample.time;
+                }
+            }
+
+            let names = stack
+                .iter()
+                .map(|hash| match state.names.get(hash) {
+                    Some(name) => name.clone(),
+                    None => hash.to_string(),
+                })
+                .collect();
+
+            stacks.push(StackProfile {
+                stack: stack.clone(),
+                names,
+                instructions: sample.instructions,
+                time: sample.time,
+            });
+        }
+
+        let mut functions = functions.into_values().collect::<Vec<_>>();
+
+        functions.sort_by(|a, b| {
+            b.self_instructions
+                .cmp(&a.self_instructions)
+                .then(a.hash.cmp(&b.hash))
+        });
+
+        stacks.sort_by(|a, b| a.names.cmp(&b.names));
+
+        Profile { functions, stacks }
+    }
+
+    /// Account for a single instruction about to be executed by `vm`.
+    #[inline]
+    pub(crate) fn tick(&self, vm: &Vm) {
+        let count = self.counter.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if count >= self.interval {
+            self.counter.fetch_sub(count, Ordering::Relaxed);
+            self.sample(vm, count);
+        }
+    }
+
+    /// Sample the call stack of the given virtual machine.
+    fn sample(&self, vm: &Vm, instructions: usize) {
+        let now = Instant::now();
+        let mut state = self.lock();
+        let state = &mut *state;
+
+        let time = match state.last.replace(now) {
+            Some(last) => now.saturating_duration_since(last),
+            None => Duration::ZERO,
+        };
+
+        let functions = state.functions(vm.unit());
+
+        let stack = vm
+            .call_frames()
+            .iter()
+            .map(|frame| frame.ip())
+            .chain(std::iter::once(vm.ip()))
+            .filter_map(|ip| functions.containing(ip))
+            .collect::<Vec<_>>();
+
+        for hash in &stack {
+            if !state.names.contains_key(hash) {
+                if let Some(signature) = vm
+                    .unit()
+                    .debug_info()
+                    .and_then(|debug| debug.functions.get(hash))
+                {
+                    state.names.insert(*hash, signature.path.to_string());
+                }
+            }
+        }
+
+        let sample = state.stacks.entry(stack).or_default();
+        sample.instructions += instructions as u64;
+        sample.time += time;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(error) => error.into_inner(),
+        }
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Profiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profiler")
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The state of a profiler.
+#[derive(Default)]
+struct State {
+    /// When the last sample was taken.
+    last: Option<Instant>,
+    /// Function offsets by unit.
+    units: Vec<(Arc<Unit>, Arc<FunctionOffsets>)>,
+    /// Resolved function names.
+    names: HashMap<Hash, String>,
+    /// Samples by call stack.
+    stacks: HashMap<Vec<Hash>, Sample>,
+}
+
+impl State {
+    /// Get function offsets for the given unit.
+    fn functions(&mut self, unit: &Arc<Unit>) -> Arc<FunctionOffsets> {
+        if let Some((_, functions)) = self.units.iter().find(|(u, _)| Arc::ptr_eq(u, unit)) {
+            return functions.clone();
+        }
+
+        let functions = Arc::new(FunctionOffsets::new(unit));
+        self.units.push((unit.clone(), functions.clone()));
+        functions
+    }
+}
+
+/// Sorted function offsets in a unit.
+struct FunctionOffsets {
+    offsets: Vec<(usize, Hash)>,
+}
+
+impl FunctionOffsets {
+    fn new(unit: &Unit) -> Self {
+        let mut offsets = unit
+            .iter_functions()
+            .filter_map(|(hash, f)| match f {
+                UnitFn::Offset { offset, .. } => Some((*offset, hash)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        offsets.sort();
+        offsets.dedup_by_key(|(offset, _)| *offset);
+        Self { offsets }
+    }
+
+    /// Find the function containing the given instruction pointer.
+    fn containing(&self, ip: usize) -> Option<Hash> {
+        let n = self.offsets.partition_point(|(offset, _)| *offset <= ip);
+        let (_, hash) = self.offsets.get(n.checked_sub(1)?)?;
+        Some(*hash)
+    }
+}
+
+/// Accumulated instructions and time for a single call stack.
+#[derive(Default)]
+struct Sample {
+    instructions: u64,
+    time: Duration,
+}
+
+/// A profile constructed through [Profiler::profile].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Profile {
+    /// Per-function profiles, sorted by the number of instructions executed
+    /// in the function itself in descending order.
+    pub functions: Vec<FunctionProfile>,
+    /// Per call stack profiles.
+    pub stacks: Vec<StackProfile>,
+}
+
+impl Profile {
+    /// Get the profile of the function with the given hash.
+    pub fn function(&self, hash: Hash) -> Option<&FunctionProfile> {
+        self.functions.iter().find(|f| f.hash == hash)
+    }
+
+    /// Write the profile in the collapsed stack format, which is understood
+    /// by flamegraph tools like [inferno] and [flamegraph.pl].
+    ///
+    /// Each line contains a call stack separated by `;`, followed by the
+    /// number of instructions sampled in it.
+    ///
+    /// [inferno]: https://github.com/jonhoo/inferno
+    /// [flamegraph.pl]: https://github.com/brendangregg/FlameGraph
+    pub fn write_collapsed<O>(&self, mut out: O) -> io::Result<()>
+    where
+        O: io::Write,
+    {
+        for stack in &self.stacks {
+            writeln!(out, "{} {}", stack.names.join(";"), stack.instructions)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The profile of a single function in a [Profile].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FunctionProfile {
+    /// The hash of the function.
+    pub hash: Hash,
+    /// The name of the function, if debug info is available.
+    pub name: Option<String>,
+    /// Instructions executed in the function itself.
+    pub self_instructions: u64,
+    /// Time spent in the function itself.
+    pub self_time: Duration,
+    /// Instructions executed in the function and the functions it calls.
+    pub total_instructions: u64,
+    /// Time spent in the function and the functions it calls.
+    pub total_time: Duration,
+}
+
+/// The profile of a single call stack in a [Profile].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct StackProfile {
+    /// The call stack, starting with the outermost function.
+    pub stack: Vec<Hash>,
+    /// The names of the functions in the call stack, falling back to their
+    /// hash if debug info is not available.
+    pub names: Vec<String>,
+    /// Instructions sampled in the call stack.
+    pub instructions: u64,
+    /// Time sampled in the call stack.
+    pub time: Duration,
+}
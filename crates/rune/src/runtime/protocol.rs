@@ -75,12 +75,70 @@ impl hash::Hash for Protocol {
 }
 
 impl Protocol {
+    /// Construct a custom protocol with the given name.
+    ///
+    /// The hash of the protocol is derived from its name, so the name should be
+    /// unique to avoid conflicting with protocols declared elsewhere. Custom
+    /// protocols are declared in a module through [Module::protocol].
+    ///
+    /// [Module::protocol]: crate::Module::protocol
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::Protocol;
+    ///
+    /// const VALIDATE: Protocol = Protocol::new("validate");
+    /// assert_eq!(VALIDATE.name, "validate");
+    /// ```
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            hash: Hash::protocol(name),
+        }
+    }
+
+    /// Construct a deep copy of a value.
+    ///
+    /// Signature: `fn(&self) -> Self`.
+    pub const CLONE: Protocol = Protocol {
+        name: "clone",
+        hash: Hash::new(0x2af2c875e36971eb),
+    };
+
     /// Check two types for equality.
     pub const EQ: Protocol = Protocol {
         name: "eq",
         hash: Hash::new(0x418f5becbf885806),
     };
 
+    /// Hash a value, allowing it to be used as a key in maps and sets.
+    ///
+    /// Values which are equal according to [Protocol::EQ] must produce the
+    /// same hash.
+    ///
+    /// Signature: `fn(&self) -> i64`.
+    pub const HASH: Protocol = Protocol {
+        name: "hash",
+        hash: Hash::new(0x1d5f45005e333838),
+    };
+
+    /// Perform a partial ordered comparison between two values.
+    ///
+    /// Signature: `fn(&self, other: &Self) -> Option<Ordering>`.
+    pub const PARTIAL_CMP: Protocol = Protocol {
+        name: "partial_cmp",
+        hash: Hash::new(0x9f29f61b0521467e),
+    };
+
+    /// Perform a total ordered comparison between two values.
+    ///
+    /// Signature: `fn(&self, other: &Self) -> Ordering`.
+    pub const CMP: Protocol = Protocol {
+        name: "cmp",
+        hash: Hash::new(0x722eed64385491f5),
+    };
+
     /// The function to access a field.
     pub const GET: Protocol = Protocol {
         name: "get",
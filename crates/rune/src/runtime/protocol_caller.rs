@@ -18,6 +18,12 @@ pub(crate) trait ProtocolCaller {
 /// Use the global environment caller.
 ///
 /// This allocates its own stack and virtual machine for the call.
+///
+/// Types defined in scripts can't implement protocols directly, so for
+/// protocols declared through [Module::protocol] an instance function with the
+/// same name as the protocol is used for them instead.
+///
+/// [Module::protocol]: crate::Module::protocol
 pub(crate) struct EnvProtocolCaller;
 
 impl ProtocolCaller for EnvProtocolCaller {
@@ -32,23 +38,41 @@ impl ProtocolCaller for EnvProtocolCaller {
     {
         return crate::runtime::env::with(|context, unit| {
             let count = args.count() + 1;
-            let hash = Hash::instance_function(target.type_hash()?, protocol.hash);
+            let type_hash = target.type_hash()?;
+            let hash = Hash::instance_function(type_hash, protocol.hash);
+            let declared = context.protocol_args(protocol.hash);
+
+            if let Some(expected) = declared {
+                check_args(count, expected + 1)?;
+            }
+
+            let unit_fn = match unit.function(hash) {
+                None if declared.is_some() && context.function(hash).is_none() => {
+                    unit.function(Hash::instance_function(type_hash, protocol.name))
+                }
+                unit_fn => unit_fn,
+            };
 
             if let Some(UnitFn::Offset {
                 offset,
                 args: expected,
                 call,
-            }) = unit.function(hash)
+            }) = unit_fn
             {
                 check_args(count, expected)?;
 
-                let mut stack = Stack::with_capacity(count);
+                let mut stack = Stack::pooled(count);
                 stack.push(target);
 
                 // Safety: We hold onto the guard until the vm has completed.
                 let _guard = unsafe { args.unsafe_into_stack(&mut stack)? };
 
                 let mut vm = Vm::with_stack(context.clone(), unit.clone(), stack);
+
+                if let Some(inherited) = crate::runtime::env::inherited() {
+                    vm.set_inherited(inherited)?;
+                }
+
                 vm.set_ip(offset);
                 return call.call_with_vm(vm);
             }
@@ -58,7 +82,7 @@ impl ProtocolCaller for EnvProtocolCaller {
                 None => return Err(VmError::from(VmErrorKind::MissingFunction { hash })),
             };
 
-            let mut stack = Stack::with_capacity(count);
+            let mut stack = Stack::pooled(count);
             stack.push(target);
 
             // Safety: We hold onto the guard until the vm has completed.
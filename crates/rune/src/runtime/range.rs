@@ -3,6 +3,7 @@ use crate::runtime::{
     FromValue, Iterator, Mut, Panic, RawMut, RawRef, RawStr, Ref, ToValue, UnsafeFromValue, Value,
     Vm, VmError, VmErrorKind,
 };
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::ops;
 
@@ -127,7 +128,7 @@ impl fmt::Debug for Range {
 }
 
 /// The limits of a range.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RangeLimits {
     /// A half-open range `..`.
     HalfOpen,
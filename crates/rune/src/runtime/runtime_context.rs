@@ -1,6 +1,7 @@
 use crate::collections::HashMap;
 use crate::macros::{MacroContext, TokenStream};
-use crate::runtime::{ConstValue, Stack, VmError};
+use crate::runtime::output::Sink;
+use crate::runtime::{ConstValue, Executor, Host, Logger, Stack, VmError};
 use crate::Hash;
 use std::fmt;
 use std::sync::Arc;
@@ -24,16 +25,40 @@ pub struct RuntimeContext {
     functions: HashMap<Hash, Arc<FunctionHandler>>,
     /// Named constant values
     constants: HashMap<Hash, ConstValue>,
+    /// Declared protocols and the number of arguments they take.
+    protocols: HashMap<Hash, usize>,
+    /// The executor which scripts can spawn tasks on.
+    executor: Option<Arc<dyn Executor>>,
+    /// The host which provides nondeterministic inputs.
+    host: Option<Arc<dyn Host>>,
+    /// The logger which receives messages emitted by scripts.
+    logger: Option<Arc<dyn Logger>>,
+    /// Where standard output is redirected to.
+    stdout: Option<Sink>,
+    /// Where standard error is redirected to.
+    stderr: Option<Sink>,
 }
 
 impl RuntimeContext {
     pub(crate) fn new(
         functions: HashMap<Hash, Arc<FunctionHandler>>,
         constants: HashMap<Hash, ConstValue>,
+        protocols: HashMap<Hash, usize>,
+        executor: Option<Arc<dyn Executor>>,
+        host: Option<Arc<dyn Host>>,
+        logger: Option<Arc<dyn Logger>>,
+        stdout: Option<Sink>,
+        stderr: Option<Sink>,
     ) -> Self {
         Self {
             functions,
             constants,
+            protocols,
+            executor,
+            host,
+            logger,
+            stdout,
+            stderr,
         }
     }
 
@@ -46,6 +71,45 @@ impl RuntimeContext {
     pub fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
     }
+
+    /// Get the number of arguments taken by the given declared protocol, not
+    /// counting the value it is called on.
+    ///
+    /// Returns `None` if the protocol hasn't been declared.
+    pub(crate) fn protocol_args(&self, hash: Hash) -> Option<usize> {
+        self.protocols.get(&hash).copied()
+    }
+
+    /// Access the executor which scripts can spawn tasks on.
+    pub(crate) fn executor(&self) -> Option<&Arc<dyn Executor>> {
+        self.executor.as_ref()
+    }
+
+    /// Access the host which provides nondeterministic inputs.
+    pub(crate) fn host(&self) -> Option<&Arc<dyn Host>> {
+        self.host.as_ref()
+    }
+
+    /// Access the logger which receives messages emitted by scripts.
+    pub(crate) fn logger(&self) -> Option<&Arc<dyn Logger>> {
+        self.logger.as_ref()
+    }
+
+    /// Access the sink standard output is redirected to.
+    pub(crate) fn stdout(&self) -> Option<&Sink> {
+        self.stdout.as_ref()
+    }
+
+    /// Access the sink standard error is redirected to.
+    pub(crate) fn stderr(&self) -> Option<&Sink> {
+        self.stderr.as_ref()
+    }
+
+    /// Redirect both standard output and standard error to the given sink.
+    pub(crate) fn set_output(&mut self, sink: Sink) {
+        self.stdout = Some(sink.clone());
+        self.stderr = Some(sink);
+    }
 }
 
 impl fmt::Debug for RuntimeContext {
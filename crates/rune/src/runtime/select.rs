@@ -17,6 +17,11 @@ impl Select {
     pub(crate) fn new(futures: FuturesUnordered<SelectFuture<usize, Mut<Future>>>) -> Self {
         Self { futures }
     }
+
+    /// Test if there are no futures to select over.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.futures.is_empty()
+    }
 }
 
 impl future::Future for Select {
@@ -0,0 +1,347 @@
+use crate::compile::Named;
+use crate::runtime::channel::{Receiver, Sender};
+use crate::runtime::{
+    AnyObj, Bytes, ConstValue, FromValue, Object, Range, RangeLimits, Rtti, Shared, StaticString,
+    Struct, ToValue, Tuple, TupleStruct, TypeInfo, Value, Variant, VariantData, VariantRtti, Vec,
+    VmError, VmErrorKind,
+};
+use crate::Hash;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::vec;
+
+/// A deep copy of a value which implements [Send] and [Sync], allowing it to
+/// be moved across threads.
+///
+/// Values in the virtual machine are reference counted and can't cross
+/// threads. A `SendValue` on the other hand owns all of its data, and can be
+/// converted back into a value through [SendValue::into_value] on the other
+/// side. It is a superset of [ConstValue], which can be converted into it
+/// infallibly.
+///
+/// Converting a value which can't be represented, like a function or an
+/// external type other than the endpoints of a
+/// [channel][crate::runtime::channel], results in a
+/// [VmErrorKind::SendNotSupported] error. Values
+/// which reference themselves can't be represented either.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::SendValue;
+/// use rune::{Context, FromValue, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> rune::Result<()> {
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             #{ numbers: [1, 2, 3], name: Some("rune") }
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+///
+/// let value = SendValue::from_value(vm.call(["main"], ())?)?;
+///
+/// let value = std::thread::spawn(move || match value {
+///     SendValue::Object(object) => object.len(),
+///     _ => 0,
+/// });
+///
+/// assert_eq!(value.join().unwrap(), 2);
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum SendValue {
+    /// The unit value.
+    Unit,
+    /// A boolean.
+    Bool(bool),
+    /// A byte.
+    Byte(u8),
+    /// A character.
+    Char(char),
+    /// An integer.
+    Integer(i64),
+    /// A float.
+    Float(f64),
+    /// A type hash.
+    Type(Hash),
+    /// A UTF-8 string.
+    String(String),
+    /// A static string.
+    StaticString(Arc<StaticString>),
+    /// A byte string.
+    Bytes(Bytes),
+    /// A vector of values.
+    Vec(vec::Vec<SendValue>),
+    /// An anonymous tuple.
+    Tuple(Box<[SendValue]>),
+    /// An anonymous object.
+    Object(BTreeMap<String, SendValue>),
+    /// A range.
+    Range(Option<Box<SendValue>>, Option<Box<SendValue>>, RangeLimits),
+    /// An option.
+    Option(Option<Box<SendValue>>),
+    /// A result.
+    Result(Result<Box<SendValue>, Box<SendValue>>),
+    /// An empty struct.
+    UnitStruct(Arc<Rtti>),
+    /// A tuple struct.
+    TupleStruct(Arc<Rtti>, Box<[SendValue]>),
+    /// A struct with named fields.
+    Struct(Arc<Rtti>, BTreeMap<String, SendValue>),
+    /// An empty variant.
+    UnitVariant(Arc<VariantRtti>),
+    /// A tuple variant.
+    TupleVariant(Arc<VariantRtti>, Box<[SendValue]>),
+    /// A variant with named fields.
+    StructVariant(Arc<VariantRtti>, BTreeMap<String, SendValue>),
+    /// The sending half of a channel.
+    #[serde(skip)]
+    Sender(Sender),
+    /// The receiving half of a channel.
+    #[serde(skip)]
+    Receiver(Receiver),
+}
+
+impl SendValue {
+    /// Convert into virtual machine value.
+    ///
+    /// We provide this associated method since a sendable value can be
+    /// converted into a value infallibly, which is not captured by the trait
+    /// otherwise.
+    pub fn into_value(self) -> Value {
+        match self {
+            Self::Unit => Value::Unit,
+            Self::Bool(b) => Value::Bool(b),
+            Self::Byte(b) => Value::Byte(b),
+            Self::Char(c) => Value::Char(c),
+            Self::Integer(n) => Value::Integer(n),
+            Self::Float(n) => Value::Float(n),
+            Self::Type(hash) => Value::Type(hash),
+            Self::String(s) => Value::String(Shared::new(s)),
+            Self::StaticString(s) => Value::StaticString(s),
+            Self::Bytes(b) => Value::Bytes(Shared::new(b)),
+            Self::Vec(vec) => Value::Vec(Shared::new(Vec::from(into_values(vec)))),
+            Self::Tuple(tuple) => Value::Tuple(Shared::new(into_tuple(tuple))),
+            Self::Object(object) => Value::Object(Shared::new(into_object(object))),
+            Self::Range(start, end, limits) => Value::Range(Shared::new(Range::new(
+                start.map(|v| v.into_value()),
+                end.map(|v| v.into_value()),
+                limits,
+            ))),
+            Self::Option(option) => Value::Option(Shared::new(option.map(|v| v.into_value()))),
+            Self::Result(result) => Value::Result(Shared::new(match result {
+                Ok(v) => Ok(v.into_value()),
+                Err(v) => Err(v.into_value()),
+            })),
+            Self::UnitStruct(rtti) => Value::unit_struct(rtti),
+            Self::TupleStruct(rtti, tuple) => Value::TupleStruct(Shared::new(TupleStruct {
+                rtti,
+                data: into_tuple(tuple),
+            })),
+            Self::Struct(rtti, object) => Value::Struct(Shared::new(Struct {
+                rtti,
+                data: into_object(object),
+            })),
+            Self::UnitVariant(rtti) => Value::unit_variant(rtti),
+            Self::TupleVariant(rtti, tuple) => {
+                Value::Variant(Shared::new(Variant::tuple(rtti, into_tuple(tuple))))
+            }
+            Self::StructVariant(rtti, object) => {
+                Value::Variant(Shared::new(Variant::struct_(rtti, into_object(object))))
+            }
+            Self::Sender(sender) => Value::Any(Shared::new(AnyObj::new(sender))),
+            Self::Receiver(receiver) => Value::Any(Shared::new(AnyObj::new(receiver))),
+        }
+    }
+
+    /// Get the type information of the value.
+    pub fn type_info(&self) -> TypeInfo {
+        match self {
+            Self::Unit => TypeInfo::StaticType(crate::runtime::UNIT_TYPE),
+            Self::Bool(..) => TypeInfo::StaticType(crate::runtime::BOOL_TYPE),
+            Self::Byte(..) => TypeInfo::StaticType(crate::runtime::BYTE_TYPE),
+            Self::Char(..) => TypeInfo::StaticType(crate::runtime::CHAR_TYPE),
+            Self::Integer(..) => TypeInfo::StaticType(crate::runtime::INTEGER_TYPE),
+            Self::Float(..) => TypeInfo::StaticType(crate::runtime::FLOAT_TYPE),
+            Self::Type(..) => TypeInfo::StaticType(crate::runtime::TYPE),
+            Self::String(..) => TypeInfo::StaticType(crate::runtime::STRING_TYPE),
+            Self::StaticString(..) => TypeInfo::StaticType(crate::runtime::STRING_TYPE),
+            Self::Bytes(..) => TypeInfo::StaticType(crate::runtime::BYTES_TYPE),
+            Self::Vec(..) => TypeInfo::StaticType(crate::runtime::VEC_TYPE),
+            Self::Tuple(..) => TypeInfo::StaticType(crate::runtime::TUPLE_TYPE),
+            Self::Object(..) => TypeInfo::StaticType(crate::runtime::OBJECT_TYPE),
+            Self::Range(..) => TypeInfo::StaticType(crate::runtime::RANGE_TYPE),
+            Self::Option(..) => TypeInfo::StaticType(crate::runtime::OPTION_TYPE),
+            Self::Result(..) => TypeInfo::StaticType(crate::runtime::RESULT_TYPE),
+            Self::UnitStruct(rtti) | Self::TupleStruct(rtti, ..) | Self::Struct(rtti, ..) => {
+                TypeInfo::Typed(rtti.clone())
+            }
+            Self::UnitVariant(rtti)
+            | Self::TupleVariant(rtti, ..)
+            | Self::StructVariant(rtti, ..) => TypeInfo::Variant(rtti.clone()),
+            Self::Sender(..) => TypeInfo::Any(Sender::BASE_NAME),
+            Self::Receiver(..) => TypeInfo::Any(Receiver::BASE_NAME),
+        }
+    }
+
+    /// Construct a deep copy of the given value.
+    pub(crate) fn from_ref(value: &Value) -> Result<Self, VmError> {
+        // NB: exclusive access is acquired to containers while they are being
+        // copied, which causes values referencing themselves to error instead
+        // of recursing indefinitely.
+        Ok(match value {
+            Value::Unit => Self::Unit,
+            Value::Bool(b) => Self::Bool(*b),
+            Value::Byte(b) => Self::Byte(*b),
+            Value::Char(c) => Self::Char(*c),
+            Value::Integer(n) => Self::Integer(*n),
+            Value::Float(n) => Self::Float(*n),
+            Value::Type(hash) => Self::Type(*hash),
+            Value::String(s) => Self::String(s.borrow_ref()?.clone()),
+            Value::StaticString(s) => Self::StaticString(s.clone()),
+            Value::Bytes(b) => Self::Bytes(b.borrow_ref()?.clone()),
+            Value::Vec(vec) => Self::Vec(from_values(&vec.borrow_mut()?)?),
+            Value::Tuple(tuple) => Self::Tuple(from_values(&tuple.borrow_mut()?)?.into()),
+            Value::Object(object) => Self::Object(from_object(&*object.borrow_mut()?)?),
+            Value::Range(range) => {
+                let range = range.borrow_mut()?;
+
+                Self::Range(
+                    from_option(range.start.as_ref())?,
+                    from_option(range.end.as_ref())?,
+                    range.limits,
+                )
+            }
+            Value::Option(option) => Self::Option(from_option(option.borrow_mut()?.as_ref())?),
+            Value::Result(result) => Self::Result(match &*result.borrow_mut()? {
+                Ok(v) => Ok(Box::new(Self::from_ref(v)?)),
+                Err(v) => Err(Box::new(Self::from_ref(v)?)),
+            }),
+            Value::UnitStruct(empty) => Self::UnitStruct(empty.borrow_ref()?.rtti.clone()),
+            Value::TupleStruct(tuple) => {
+                let tuple = tuple.borrow_mut()?;
+                Self::TupleStruct(tuple.rtti.clone(), from_values(&tuple.data)?.into())
+            }
+            Value::Struct(object) => {
+                let object = object.borrow_mut()?;
+                Self::Struct(object.rtti.clone(), from_object(&object.data)?)
+            }
+            Value::Variant(variant) => {
+                let variant = variant.borrow_mut()?;
+                let rtti = variant.rtti.clone();
+
+                match &variant.data {
+                    VariantData::Unit => Self::UnitVariant(rtti),
+                    VariantData::Tuple(tuple) => {
+                        Self::TupleVariant(rtti, from_values(tuple)?.into())
+                    }
+                    VariantData::Struct(object) => Self::StructVariant(rtti, from_object(object)?),
+                }
+            }
+            Value::Any(any) => {
+                let any = any.borrow_ref()?;
+
+                if let Some(sender) = any.downcast_borrow_ref::<Sender>() {
+                    Self::Sender(sender.clone())
+                } else if let Some(receiver) = any.downcast_borrow_ref::<Receiver>() {
+                    Self::Receiver(receiver.clone())
+                } else {
+                    return Err(VmError::from(VmErrorKind::SendNotSupported {
+                        actual: TypeInfo::Any(any.type_name()),
+                    }));
+                }
+            }
+            value => {
+                return Err(VmError::from(VmErrorKind::SendNotSupported {
+                    actual: value.type_info()?,
+                }))
+            }
+        })
+    }
+}
+
+impl From<ConstValue> for SendValue {
+    fn from(value: ConstValue) -> Self {
+        match value {
+            ConstValue::Unit => Self::Unit,
+            ConstValue::Byte(b) => Self::Byte(b),
+            ConstValue::Char(c) => Self::Char(c),
+            ConstValue::Bool(b) => Self::Bool(b),
+            ConstValue::Integer(n) => Self::Integer(n),
+            ConstValue::Float(n) => Self::Float(n),
+            ConstValue::String(s) => Self::String(s),
+            ConstValue::StaticString(s) => Self::StaticString(s),
+            ConstValue::Bytes(b) => Self::Bytes(b),
+            ConstValue::Vec(vec) => Self::Vec(vec.into_iter().map(Self::from).collect()),
+            ConstValue::Tuple(tuple) => {
+                Self::Tuple(vec::Vec::from(tuple).into_iter().map(Self::from).collect())
+            }
+            ConstValue::Object(object) => Self::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| (key, Self::from(value)))
+                    .collect(),
+            ),
+            ConstValue::Option(option) => Self::Option(option.map(|v| Box::new(Self::from(*v)))),
+        }
+    }
+}
+
+impl FromValue for SendValue {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        Self::from_ref(&value)
+    }
+}
+
+impl ToValue for SendValue {
+    fn to_value(self) -> Result<Value, VmError> {
+        Ok(SendValue::into_value(self))
+    }
+}
+
+fn from_values(values: &[Value]) -> Result<vec::Vec<SendValue>, VmError> {
+    values.iter().map(SendValue::from_ref).collect()
+}
+
+fn from_object(object: &Object) -> Result<BTreeMap<String, SendValue>, VmError> {
+    object
+        .iter()
+        .map(|(key, value)| Ok((key.clone(), SendValue::from_ref(value)?)))
+        .collect()
+}
+
+fn from_option(value: Option<&Value>) -> Result<Option<Box<SendValue>>, VmError> {
+    Ok(match value {
+        Some(value) => Some(Box::new(SendValue::from_ref(value)?)),
+        None => None,
+    })
+}
+
+fn into_values(values: vec::Vec<SendValue>) -> vec::Vec<Value> {
+    values.into_iter().map(SendValue::into_value).collect()
+}
+
+fn into_tuple(tuple: Box<[SendValue]>) -> Tuple {
+    Tuple::from(into_values(vec::Vec::from(tuple)))
+}
+
+fn into_object(object: BTreeMap<String, SendValue>) -> Object {
+    let mut o = Object::with_capacity(object.len());
+
+    for (key, value) in object {
+        o.insert(key, value.into_value());
+    }
+
+    o
+}
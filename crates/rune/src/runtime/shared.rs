@@ -3,7 +3,7 @@ use crate::runtime::{
 };
 use crate::{Any, Hash};
 use std::any;
-use std::cell::{Cell, UnsafeCell};
+use std::cell::{Cell, RefCell, UnsafeCell};
 use std::fmt;
 use std::future::Future;
 use std::mem;
@@ -273,6 +273,19 @@ impl<T> Shared<T> {
 }
 
 impl<T: ?Sized> Shared<T> {
+    /// The number of strong references to the shared value.
+    pub(crate) fn strong_count(this: &Self) -> usize {
+        // Safety: by virtue of holding onto a shared we can safely access
+        // `inner` because it must outlive any `Shared` instances.
+        unsafe { this.inner.as_ref().count.get() }
+    }
+
+    /// A pointer which uniquely identifies the shared value for as long as it
+    /// is alive.
+    pub(crate) fn as_ptr(this: &Self) -> *const () {
+        this.inner.as_ptr() as *const ()
+    }
+
     /// Get a reference to the interior value while checking for shared access.
     ///
     /// This prevents other exclusive accesses from being performed while the
@@ -701,14 +714,39 @@ where
             let inner = self.inner.as_ref();
 
             if !inner.access.is_shared() {
-                write!(fmt, "*not accessible*")
-            } else {
-                write!(fmt, "{:?}", &&*inner.data.get())
+                return write!(fmt, "*not accessible*");
+            }
+
+            // NB: a value which is already being formatted further up the
+            // stack is part of a reference cycle, so we elide it instead of
+            // recursing forever.
+            let ptr = Shared::as_ptr(self);
+
+            if DEBUGGING.with(|debugging| debugging.borrow().contains(&ptr)) {
+                return write!(fmt, "...");
             }
+
+            DEBUGGING.with(|debugging| debugging.borrow_mut().push(ptr));
+            let _guard = DebuggingGuard;
+            fmt::Debug::fmt(&*inner.data.get(), fmt)
         }
     }
 }
 
+thread_local! {
+    /// Shared values which are currently being debug formatted on this thread.
+    static DEBUGGING: RefCell<Vec<*const ()>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Guard which pops the value being debug formatted once it's done.
+struct DebuggingGuard;
+
+impl Drop for DebuggingGuard {
+    fn drop(&mut self) {
+        DEBUGGING.with(|debugging| debugging.borrow_mut().pop());
+    }
+}
+
 /// A debug helper that prints detailed diagnostics on the type being debugged.
 ///
 /// Constructed using [debug][Shared::debug].
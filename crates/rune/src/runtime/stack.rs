@@ -1,5 +1,6 @@
 use crate::runtime::{InstAddress, Value};
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::iter;
 use std::mem;
 use std::slice;
@@ -10,6 +11,15 @@ use thiserror::Error;
 #[error("tried to access out-of-bounds stack entry")]
 pub struct StackError(());
 
+/// The maximum number of stack allocations kept around for reuse per thread.
+const POOL_SIZE: usize = 16;
+
+/// Stacks which have grown beyond this capacity are not reused, to avoid
+/// holding on to large allocations.
+const POOL_MAX_CAPACITY: usize = 1024;
+
+thread_local!(static POOL: RefCell<Vec<Vec<Value>>> = const { RefCell::new(Vec::new()) });
+
 /// The stack of the virtual machine, where all values are stored.
 #[derive(Default, Debug, Clone)]
 pub struct Stack {
@@ -62,6 +72,49 @@ impl Stack {
         }
     }
 
+    /// Construct a new stack with the given capacity, reusing an allocation
+    /// from a previously dropped stack if one is available.
+    pub(crate) fn pooled(capacity: usize) -> Self {
+        let mut stack = POOL
+            .try_with(|pool| pool.try_borrow_mut().ok()?.pop())
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+
+        stack.reserve(capacity);
+
+        Self {
+            stack,
+            stack_bottom: 0,
+        }
+    }
+
+    /// Reserve capacity for at least `additional` more values to be pushed
+    /// onto the stack.
+    ///
+    /// ```
+    /// use rune::runtime::Stack;
+    ///
+    /// let mut stack = Stack::new();
+    /// stack.reserve(16);
+    /// assert!(stack.capacity() >= 16);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.stack.reserve(additional);
+    }
+
+    /// The number of values the stack can hold without reallocating.
+    ///
+    /// ```
+    /// use rune::runtime::Stack;
+    ///
+    /// let stack = Stack::with_capacity(16);
+    /// assert!(stack.capacity() >= 16);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.stack.capacity()
+    }
+
     /// Check if the stack is empty.
     ///
     /// This ignores [stack_bottom] and will just check if the full stack is
@@ -336,6 +389,11 @@ impl Stack {
         Err(StackError(()))
     }
 
+    /// Set the bottom of the current stack frame.
+    pub(crate) fn set_stack_bottom(&mut self, stack_bottom: usize) {
+        self.stack_bottom = stack_bottom;
+    }
+
     /// Pop the current stack top and modify it to a different one.
     ///
     /// This asserts that the size of the current stack frame is exactly zero
@@ -349,10 +407,34 @@ impl Stack {
 
 impl iter::FromIterator<Value> for Stack {
     fn from_iter<T: IntoIterator<Item = Value>>(iter: T) -> Self {
-        Self {
-            stack: iter.into_iter().collect(),
-            stack_bottom: 0,
+        let iter = iter.into_iter();
+        let mut stack = Self::pooled(iter.size_hint().0);
+        stack.stack.extend(iter);
+        stack
+    }
+}
+
+impl Drop for Stack {
+    fn drop(&mut self) {
+        let capacity = self.stack.capacity();
+
+        if capacity == 0 || capacity > POOL_MAX_CAPACITY {
+            return;
         }
+
+        let mut stack = mem::take(&mut self.stack);
+
+        // NB: values are dropped before the pool is accessed, since dropping
+        // them might release other stacks.
+        stack.clear();
+
+        let _ = POOL.try_with(|pool| {
+            if let Ok(mut pool) = pool.try_borrow_mut() {
+                if pool.len() < POOL_SIZE {
+                    pool.push(stack);
+                }
+            }
+        });
     }
 }
 
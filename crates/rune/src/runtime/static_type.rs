@@ -103,6 +103,7 @@ pub static BYTES_TYPE: &StaticType = &StaticType {
 };
 
 impl_static_type!(rt::Bytes => BYTES_TYPE);
+impl_static_type!(rt::Shared<rt::Bytes> => BYTES_TYPE);
 impl_static_type!([u8] => BYTES_TYPE);
 
 /// The specialized type information for a vector type.
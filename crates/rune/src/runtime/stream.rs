@@ -1,16 +1,32 @@
 use crate::compile::{InstallWith, Named};
 use crate::runtime::{
-    FromValue, GeneratorState, Mut, RawMut, RawRef, RawStr, Ref, Shared, UnsafeFromValue, Value,
-    Vm, VmError, VmErrorKind, VmExecution,
+    FromValue, GeneratorState, Mut, RawMut, RawRef, RawStr, Ref, Shared, ToValue, UnsafeFromValue,
+    Value, Vm, VmError, VmErrorKind, VmExecution,
 };
+use futures_util::StreamExt as _;
 use std::fmt;
+use std::pin::Pin;
+
+/// dyn stream alias.
+type DynStream = dyn futures_core::Stream<Item = Result<Value, VmError>> + 'static;
+
+/// The thing producing the values of a [Stream].
+enum StreamRepr<T>
+where
+    T: AsMut<Vm>,
+{
+    /// An async generator running in a virtual machine.
+    Vm(VmExecution<T>),
+    /// A native stream provided by the host.
+    Native(Pin<Box<DynStream>>),
+}
 
 /// A stream with a stored virtual machine.
 pub struct Stream<T>
 where
     T: AsMut<Vm>,
 {
-    execution: Option<VmExecution<T>>,
+    repr: Option<StreamRepr<T>>,
 }
 
 impl<T> Stream<T>
@@ -20,14 +36,14 @@ where
     /// Construct a stream from a virtual machine.
     pub(crate) fn new(vm: T) -> Self {
         Self {
-            execution: Some(VmExecution::new(vm)),
+            repr: Some(StreamRepr::Vm(VmExecution::new(vm))),
         }
     }
 
     /// Construct a generator from a complete execution.
     pub(crate) fn from_execution(execution: VmExecution<T>) -> Self {
         Self {
-            execution: Some(execution),
+            repr: Some(StreamRepr::Vm(execution)),
         }
     }
 
@@ -40,31 +56,65 @@ where
     }
 
     /// Get the next value produced by this stream.
+    ///
+    /// Native streams can't receive values, so `value` is ignored for them.
     pub async fn resume(&mut self, value: Value) -> Result<GeneratorState, VmError> {
-        let execution = self
-            .execution
-            .as_mut()
-            .ok_or(VmErrorKind::GeneratorComplete)?;
-
-        let state = if execution.is_resumed() {
-            execution.async_resume_with(value).await?
-        } else {
-            execution.async_resume().await?
+        let repr = self.repr.as_mut().ok_or(VmErrorKind::GeneratorComplete)?;
+
+        let state = match repr {
+            StreamRepr::Vm(execution) => {
+                if execution.is_resumed() {
+                    execution.async_resume_with(value).await?
+                } else {
+                    execution.async_resume().await?
+                }
+            }
+            StreamRepr::Native(stream) => match stream.next().await {
+                Some(value) => GeneratorState::Yielded(value?),
+                None => GeneratorState::Complete(Value::Unit),
+            },
         };
 
         if state.is_complete() {
-            self.execution = None;
+            self.repr = None;
         }
 
         Ok(state)
     }
 }
 
+impl Stream<Vm> {
+    /// Construct a stream from a native [Stream][futures_core::Stream].
+    ///
+    /// Every item produced by the stream is converted into a [Value], and
+    /// scripts consume it the same way as an async generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{Stream, Vm};
+    ///
+    /// let stream = Stream::<Vm>::from_stream(futures_util::stream::iter([1i64, 2, 3]));
+    /// ```
+    pub fn from_stream<S>(stream: S) -> Self
+    where
+        S: 'static + futures_core::Stream,
+        S::Item: ToValue,
+    {
+        Self {
+            repr: Some(StreamRepr::Native(Box::pin(stream.map(ToValue::to_value)))),
+        }
+    }
+}
+
 impl Stream<&mut Vm> {
     /// Convert the current stream into one which owns its virtual machine.
     pub fn into_owned(self) -> Stream<Vm> {
         Stream {
-            execution: self.execution.map(|e| e.into_owned()),
+            repr: self.repr.map(|repr| match repr {
+                StreamRepr::Vm(execution) => StreamRepr::Vm(execution.into_owned()),
+                StreamRepr::Native(stream) => StreamRepr::Native(stream),
+            }),
         }
     }
 }
@@ -84,7 +134,7 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Stream")
-            .field("completed", &self.execution.is_none())
+            .field("completed", &self.repr.is_none())
             .finish()
     }
 }
@@ -0,0 +1,98 @@
+//! Interned strings.
+//!
+//! A [Symbol] is a string which has been interned in a process-wide table, so
+//! that two symbols with the same name share a single allocation. Symbols are
+//! compared by identity and the hash of their name is computed once when they
+//! are interned, which makes them cheap to use as tags or keys. They are
+//! exposed to scripts as `std::symbol::Symbol` and through the `sym!` macro.
+//!
+//! Interned names are never freed.
+//!
+//! # Examples
+//!
+//! ```
+//! use rune::runtime::Symbol;
+//!
+//! let a = Symbol::new("hello");
+//! let b = Symbol::new(&String::from("hello"));
+//!
+//! assert_eq!(a, b);
+//! assert_eq!(a.as_str(), "hello");
+//! assert_ne!(a, Symbol::new("world"));
+//! ```
+
+use crate::collections::HashMap;
+use crate::Any;
+use std::cmp;
+use std::fmt;
+use std::hash;
+use std::hash::Hasher as _;
+use std::ptr;
+use std::sync::Mutex;
+use twox_hash::XxHash64;
+
+static INTERNED: Mutex<Option<HashMap<&'static str, u64>>> = Mutex::new(None);
+
+/// An interned string.
+#[derive(Any, Clone, Copy)]
+#[rune(module = "crate")]
+pub struct Symbol {
+    name: &'static str,
+    hash: u64,
+}
+
+impl Symbol {
+    /// Intern the given name, returning the symbol associated with it.
+    pub fn new(name: &str) -> Self {
+        let mut interned = INTERNED.lock().unwrap_or_else(|e| e.into_inner());
+        let interned = interned.get_or_insert_with(HashMap::new);
+
+        if let Some((name, hash)) = interned.get_key_value(name) {
+            return Self { name, hash: *hash };
+        }
+
+        let mut hasher = XxHash64::default();
+        hasher.write(name.as_bytes());
+        let hash = hasher.finish();
+
+        let name: &'static str = Box::leak(name.into());
+        interned.insert(name, hash);
+        Self { name, hash }
+    }
+
+    /// Get the name of the symbol.
+    pub fn as_str(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get a hash of the name of the symbol, which is stable across runs.
+    pub(crate) fn name_hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl cmp::PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.name, other.name)
+    }
+}
+
+impl cmp::Eq for Symbol {}
+
+impl hash::Hash for Symbol {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sym!({:?})", self.name)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name.fmt(f)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::runtime::{
-    ConstValue, FromValue, Mut, Ref, ToValue, Value, Vm, VmError, VmErrorKind, TUPLE_TYPE,
+    ConstValue, FromValue, Mut, Ref, SendValue, ToValue, Value, Vm, VmError, VmErrorKind,
+    TUPLE_TYPE,
 };
 use std::fmt;
 use std::ops;
@@ -58,6 +59,17 @@ impl Tuple {
         self.inner.get_mut(index)
     }
 
+    /// Construct a deep copy of the tuple.
+    ///
+    /// See [Value::clone_deep].
+    pub fn clone_deep(&self) -> Result<Self, VmError> {
+        Ok(Self::from(
+            self.iter()
+                .map(Value::clone_deep)
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+
     /// Value pointer equals implementation for a Tuple.
     pub(crate) fn value_ptr_eq(vm: &mut Vm, a: &Self, b: &Self) -> Result<bool, VmError> {
         if a.len() != b.len() {
@@ -76,6 +88,16 @@ impl Tuple {
 
 impl fmt::Debug for Tuple {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            let mut d = f.debug_tuple("");
+
+            for el in self.inner.iter() {
+                d.field(el);
+            }
+
+            return d.finish();
+        }
+
         write!(f, "(")?;
 
         let mut it = self.inner.iter();
@@ -156,6 +178,20 @@ impl From<Box<[ConstValue]>> for Tuple {
     }
 }
 
+impl From<Box<[SendValue]>> for Tuple {
+    fn from(inner: Box<[SendValue]>) -> Self {
+        let mut out = Vec::with_capacity(inner.len());
+
+        for value in inner.into_vec() {
+            out.push(value.into_value());
+        }
+
+        Self {
+            inner: out.into_boxed_slice(),
+        }
+    }
+}
+
 impl FromValue for Mut<Tuple> {
     fn from_value(value: Value) -> Result<Self, VmError> {
         Ok(value.into_tuple()?.into_mut()?)
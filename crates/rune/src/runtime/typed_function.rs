@@ -0,0 +1,95 @@
+use crate::runtime::{FromValue, GuardedArgs, Unit, Vm, VmError, VmErrorKind};
+use crate::Hash;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// A handle to a function in a unit, which is called with arguments of type
+/// `A` and produces output of type `T`.
+///
+/// The function is resolved once when the handle is constructed through
+/// [Vm::function], so calling it only needs to check the number of arguments
+/// before jumping straight into it.
+///
+/// A handle can only be used with virtual machines running the unit it was
+/// constructed from. Calling it on any other virtual machine, including one
+/// whose unit has been replaced through [Vm::swap_unit], results in an error.
+pub struct TypedFunction<A, T> {
+    unit: Arc<Unit>,
+    hash: Hash,
+    offset: usize,
+    args: usize,
+    _marker: PhantomData<fn(A) -> T>,
+}
+
+impl<A, T> TypedFunction<A, T> {
+    pub(crate) fn new(unit: Arc<Unit>, hash: Hash, offset: usize, args: usize) -> Self {
+        Self {
+            unit,
+            hash,
+            offset,
+            args,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get the hash of the function.
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Get the number of arguments the function takes.
+    pub fn args(&self) -> usize {
+        self.args
+    }
+}
+
+impl<A, T> TypedFunction<A, T>
+where
+    A: GuardedArgs,
+    T: FromValue,
+{
+    /// Call the function on the given virtual machine.
+    ///
+    /// See [Vm::call] for the restrictions which apply to passing references.
+    pub fn call(&self, vm: &mut Vm, args: A) -> Result<T, VmError> {
+        self.enter(vm, &args)?;
+        T::from_value(vm.call_entry(self.hash, args)?)
+    }
+
+    /// Call the function asynchronously on the given virtual machine.
+    ///
+    /// See [Vm::async_call] for the restrictions which apply to passing
+    /// references.
+    pub async fn async_call(&self, vm: &mut Vm, args: A) -> Result<T, VmError> {
+        self.enter(vm, &args)?;
+        T::from_value(vm.async_call_entry(self.hash, args).await?)
+    }
+
+    fn enter(&self, vm: &mut Vm, args: &A) -> Result<(), VmError> {
+        if !Arc::ptr_eq(vm.unit(), &self.unit) {
+            return Err(VmError::from(VmErrorKind::ForeignFunction {
+                hash: self.hash,
+            }));
+        }
+
+        Vm::check_args(args.count(), self.args)?;
+        vm.set_entry_offset(self.offset);
+        Ok(())
+    }
+}
+
+impl<A, T> Clone for TypedFunction<A, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.unit.clone(), self.hash, self.offset, self.args)
+    }
+}
+
+impl<A, T> fmt::Debug for TypedFunction<A, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedFunction")
+            .field("hash", &self.hash)
+            .field("args", &self.args)
+            .finish()
+    }
+}
@@ -5,12 +5,15 @@
 
 use crate::collections::HashMap;
 use crate::runtime::{
-    Call, ConstValue, DebugInfo, Inst, Rtti, StaticString, VariantRtti, VmError, VmErrorKind,
+    Call, ConstValue, DebugInfo, Inst, Rtti, StaticString, UnitDiff, UnitReport, VariantRtti,
+    VmError, VmErrorKind,
 };
 use crate::Hash;
 use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::fmt;
-use std::sync::Arc;
+use std::ops::Range;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 /// Instructions from a single source file.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -38,6 +41,10 @@ pub struct Unit {
     debug: Option<Box<DebugInfo>>,
     /// Named constants
     constants: HashMap<Hash, ConstValue>,
+    /// The number of inline cache slots used by instructions.
+    inline_caches: usize,
+    /// The range of instructions making up the body of each function.
+    function_bodies: HashMap<Hash, Range<usize>>,
 }
 
 impl Unit {
@@ -53,7 +60,10 @@ impl Unit {
         variant_rtti: HashMap<Hash, Arc<VariantRtti>>,
         debug: Option<Box<DebugInfo>>,
         constants: HashMap<Hash, ConstValue>,
+        inline_caches: usize,
     ) -> Self {
+        let function_bodies = function_bodies(&functions, instructions.len());
+
         Self {
             instructions,
             functions,
@@ -64,6 +74,8 @@ impl Unit {
             variant_rtti,
             debug,
             constants,
+            inline_caches,
+            function_bodies,
         }
     }
 
@@ -73,6 +85,11 @@ impl Unit {
         Some(&**debug)
     }
 
+    /// The number of inline cache slots used by instructions in the unit.
+    pub fn inline_caches(&self) -> usize {
+        self.inline_caches
+    }
+
     /// Get the instruction at the given instruction pointer.
     pub fn instruction_at(&self, ip: usize) -> Option<&Inst> {
         self.instructions.get(ip)
@@ -83,6 +100,11 @@ impl Unit {
         self.static_strings.iter()
     }
 
+    /// Iterate over all static byte strings in the unit.
+    pub fn iter_static_bytes(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.static_bytes.iter().map(|b| &b[..])
+    }
+
     /// Iterate over all constants in the unit.
     pub fn iter_constants(&self) -> impl Iterator<Item = (&Hash, &ConstValue)> + '_ {
         self.constants.iter()
@@ -149,6 +171,55 @@ impl Unit {
     pub fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
     }
+
+    /// Get the instructions making up the body of the function with the given
+    /// hash.
+    ///
+    /// The body of a function extends up until the offset of the next
+    /// function, or the end of the unit.
+    pub(crate) fn function_body(&self, hash: Hash) -> &[Inst] {
+        match self.function_bodies.get(&hash) {
+            Some(range) => self.instructions.get(range.clone()).unwrap_or_default(),
+            None => &[],
+        }
+    }
+
+    /// Compare the functions in this unit with those of `other`, treating
+    /// this unit as the old one.
+    pub fn diff(&self, other: &Unit) -> UnitDiff {
+        UnitDiff::new(self, other)
+    }
+
+    /// Construct a report over the instruction counts and static data sizes
+    /// of this unit.
+    pub fn report(&self) -> UnitReport {
+        UnitReport::new(self)
+    }
+}
+
+/// Index the range of instructions making up the body of each function.
+fn function_bodies(functions: &HashMap<Hash, UnitFn>, len: usize) -> HashMap<Hash, Range<usize>> {
+    let mut offsets = functions
+        .iter()
+        .filter_map(|(hash, f)| match f {
+            UnitFn::Offset { offset, .. } => Some((*offset, *hash)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    offsets.sort();
+
+    let mut bodies = HashMap::new();
+
+    for (start, hash) in &offsets {
+        // NB: functions sharing an offset share a body, which extends up to
+        // the next greater offset.
+        let next = offsets.partition_point(|(offset, _)| offset <= start);
+        let end = offsets.get(next).map(|(offset, _)| *offset).unwrap_or(len);
+        bodies.insert(*hash, *start..end);
+    }
+
+    bodies
 }
 
 /// The kind and necessary information on registered functions.
@@ -216,3 +287,108 @@ impl fmt::Display for UnitFn {
 
 #[cfg(test)]
 static_assertions::assert_impl_all!(Unit: Send, Sync);
+
+/// The generation of the unit a virtual machine is running, which is shared
+/// with the functions created from it.
+///
+/// Replacing the unit through [Vm::swap_unit] retires the functions which
+/// changed or were removed in the new unit, so that function values created
+/// from the replaced unit can tell that they are stale. Since units can be
+/// replaced more than once, a retired generation links to the generation which
+/// replaced it.
+///
+/// [Vm::swap_unit]: crate::runtime::Vm::swap_unit
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UnitGeneration {
+    retired: Arc<Mutex<Option<Retired>>>,
+}
+
+#[derive(Debug)]
+struct Retired {
+    /// Sorted hashes of the functions which were retired.
+    hashes: Vec<Hash>,
+    /// The generation of the unit which replaced this one.
+    next: UnitGeneration,
+}
+
+impl UnitGeneration {
+    fn lock(&self) -> MutexGuard<'_, Option<Retired>> {
+        match self.retired.lock() {
+            Ok(guard) => guard,
+            Err(error) => error.into_inner(),
+        }
+    }
+
+    /// Test if the function with the given hash has been retired by the unit
+    /// being replaced, or by any of the units replacing it.
+    pub(crate) fn is_retired(&self, hash: Hash) -> bool {
+        let mut current = self.clone();
+
+        loop {
+            let next = match &*current.lock() {
+                Some(retired) if retired.hashes.binary_search(&hash).is_ok() => return true,
+                Some(retired) => retired.next.clone(),
+                None => return false,
+            };
+
+            current = next;
+        }
+    }
+
+    /// Mark the unit as replaced by the unit of the `next` generation,
+    /// retiring the functions with the given hashes.
+    pub(crate) fn retire(&self, mut hashes: Vec<Hash>, next: UnitGeneration) {
+        hashes.sort();
+        *self.lock() = Some(Retired { hashes, next });
+    }
+}
+
+/// A generation which is only allocated once it's needed, so that virtual
+/// machines can be constructed in constant contexts.
+pub(crate) struct LazyGeneration {
+    generation: Cell<Option<UnitGeneration>>,
+}
+
+impl LazyGeneration {
+    /// Construct a generation which is allocated once it's needed.
+    pub(crate) const fn new() -> Self {
+        Self {
+            generation: Cell::new(None),
+        }
+    }
+
+    /// Construct from an existing generation.
+    pub(crate) const fn from_generation(generation: UnitGeneration) -> Self {
+        Self {
+            generation: Cell::new(Some(generation)),
+        }
+    }
+
+    /// Get the generation, allocating it if necessary.
+    pub(crate) fn get(&self) -> UnitGeneration {
+        let generation = self.generation.take().unwrap_or_default();
+        self.generation.set(Some(generation.clone()));
+        generation
+    }
+
+    /// Take the generation if it has been allocated, leaving a lazily
+    /// allocated one in its place.
+    pub(crate) fn take(&mut self) -> Option<UnitGeneration> {
+        self.generation.get_mut().take()
+    }
+}
+
+impl Clone for LazyGeneration {
+    fn clone(&self) -> Self {
+        Self::from_generation(self.get())
+    }
+}
+
+impl fmt::Debug for LazyGeneration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let generation = self.generation.take();
+        let result = f.debug_tuple("LazyGeneration").field(&generation).finish();
+        self.generation.set(generation);
+        result
+    }
+}
@@ -0,0 +1,144 @@
+//! Comparison of the functions of two compiled units.
+
+use crate::collections::HashSet;
+use crate::runtime::{Inst, Unit, UnitFn};
+use crate::Hash;
+
+/// The difference between the functions of two units.
+///
+/// Constructed through [Unit::diff] or returned by [Vm::swap_unit].
+///
+/// A function is considered unchanged if it has the same calling convention,
+/// the same number of arguments, and an identical sequence of instructions in
+/// both units. Since instructions refer to static data by slot, this is
+/// conservative: a function might be reported as changed even though it would
+/// behave identically. Inline cache slots are ignored, since they don't affect
+/// behavior.
+///
+/// [Vm::swap_unit]: crate::runtime::Vm::swap_unit
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct UnitDiff {
+    /// Functions which only exist in the new unit.
+    pub added: Vec<Hash>,
+    /// Functions which only exist in the old unit.
+    pub removed: Vec<Hash>,
+    /// Functions which exist in both units, but which are not compatible.
+    pub changed: Vec<Hash>,
+    /// Functions which exist in both units and are compatible.
+    pub unchanged: Vec<Hash>,
+}
+
+impl UnitDiff {
+    pub(crate) fn new(old: &Unit, new: &Unit) -> Self {
+        let mut diff = Self::default();
+        let mut seen = HashSet::new();
+
+        for (hash, old_fn) in old.iter_functions() {
+            seen.insert(hash);
+
+            let new_fn = match new.function(hash) {
+                Some(new_fn) => new_fn,
+                None => {
+                    diff.removed.push(hash);
+                    continue;
+                }
+            };
+
+            if is_compatible(hash, old, old_fn, new, &new_fn) {
+                diff.unchanged.push(hash);
+            } else {
+                diff.changed.push(hash);
+            }
+        }
+
+        for (hash, _) in new.iter_functions() {
+            if !seen.contains(&hash) {
+                diff.added.push(hash);
+            }
+        }
+
+        diff.added.sort();
+        diff.removed.sort();
+        diff.changed.sort();
+        diff.unchanged.sort();
+        diff
+    }
+
+    /// Test if the two compared units have identical functions.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Test if two functions are compatible with each other.
+fn is_compatible(hash: Hash, old: &Unit, old_fn: &UnitFn, new: &Unit, new_fn: &UnitFn) -> bool {
+    match (old_fn, new_fn) {
+        (
+            UnitFn::Offset {
+                call: old_call,
+                args: old_args,
+                ..
+            },
+            UnitFn::Offset {
+                call: new_call,
+                args: new_args,
+                ..
+            },
+        ) => {
+            old_call == new_call
+                && old_args == new_args
+                && same_body(old.function_body(hash), new.function_body(hash))
+        }
+        (UnitFn::UnitStruct { hash: a }, UnitFn::UnitStruct { hash: b }) => a == b,
+        (
+            UnitFn::TupleStruct {
+                hash: a,
+                args: a_args,
+            },
+            UnitFn::TupleStruct {
+                hash: b,
+                args: b_args,
+            },
+        ) => a == b && a_args == b_args,
+        (UnitFn::UnitVariant { hash: a }, UnitFn::UnitVariant { hash: b }) => a == b,
+        (
+            UnitFn::TupleVariant {
+                hash: a,
+                args: a_args,
+            },
+            UnitFn::TupleVariant {
+                hash: b,
+                args: b_args,
+            },
+        ) => a == b && a_args == b_args,
+        _ => false,
+    }
+}
+
+/// Test if two function bodies are identical, ignoring inline cache slots.
+fn same_body(old: &[Inst], new: &[Inst]) -> bool {
+    old.len() == new.len()
+        && old
+            .iter()
+            .zip(new)
+            .all(|(old, new)| without_cache(*old) == without_cache(*new))
+}
+
+/// Erase the inline cache slot of an instruction.
+fn without_cache(inst: Inst) -> Inst {
+    match inst {
+        Inst::CallInstance { hash, args, .. } => Inst::CallInstance {
+            hash,
+            args,
+            cache: 0,
+        },
+        Inst::ObjectIndexGet { slot, .. } => Inst::ObjectIndexGet { slot, cache: 0 },
+        Inst::ObjectIndexGetAt { offset, slot, .. } => Inst::ObjectIndexGetAt {
+            offset,
+            slot,
+            cache: 0,
+        },
+        inst => inst,
+    }
+}
@@ -0,0 +1,140 @@
+//! Size and instruction count report for a compiled unit.
+
+use crate::runtime::debug::DebugSignature;
+use crate::runtime::{Unit, UnitFn};
+use crate::Hash;
+use std::fmt;
+
+/// A report over where the weight of a compiled [Unit] is located.
+///
+/// Constructed through [Unit::report].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct UnitReport {
+    /// The total number of instructions in the unit.
+    pub instructions: usize,
+    /// Per-function reports, sorted by the number of instructions in
+    /// descending order.
+    pub functions: Vec<FunctionReport>,
+    /// The number of static strings.
+    pub static_strings: usize,
+    /// The combined size in bytes of all static strings.
+    pub static_strings_size: usize,
+    /// The number of static byte strings.
+    pub static_bytes: usize,
+    /// The combined size in bytes of all static byte strings.
+    pub static_bytes_size: usize,
+    /// The number of static object key sets.
+    pub static_object_keys: usize,
+    /// The combined size in bytes of all static object keys.
+    pub static_object_keys_size: usize,
+    /// The number of entries in the constant pool.
+    pub constants: usize,
+}
+
+/// Instruction counts for a single function in a [UnitReport].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FunctionReport {
+    /// The hash of the function.
+    pub hash: Hash,
+    /// The instruction offset the function starts at.
+    pub offset: usize,
+    /// The number of instructions that belong to the function.
+    pub instructions: usize,
+    /// The signature of the function, if debug info is available.
+    pub signature: Option<DebugSignature>,
+}
+
+impl UnitReport {
+    pub(crate) fn new(unit: &Unit) -> Self {
+        let mut offsets = unit
+            .iter_functions()
+            .filter_map(|(hash, f)| match f {
+                UnitFn::Offset { offset, .. } => Some((*offset, hash)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        offsets.sort();
+        offsets.dedup_by_key(|(offset, _)| *offset);
+
+        let instructions = unit.iter_instructions().count();
+        let mut functions = Vec::with_capacity(offsets.len());
+        let mut it = offsets.iter().peekable();
+
+        while let Some(&(offset, hash)) = it.next() {
+            let end = it.peek().map(|(o, _)| *o).unwrap_or(instructions);
+
+            let signature = unit
+                .debug_info()
+                .and_then(|d| d.functions.get(&hash))
+                .cloned();
+
+            functions.push(FunctionReport {
+                hash,
+                offset,
+                instructions: end.saturating_sub(offset),
+                signature,
+            });
+        }
+
+        functions.sort_by(|a, b| {
+            b.instructions
+                .cmp(&a.instructions)
+                .then(a.offset.cmp(&b.offset))
+        });
+
+        Self {
+            instructions,
+            functions,
+            static_strings: unit.iter_static_strings().count(),
+            static_strings_size: unit.iter_static_strings().map(|s| s.len()).sum(),
+            static_bytes: unit.iter_static_bytes().count(),
+            static_bytes_size: unit.iter_static_bytes().map(|b| b.len()).sum(),
+            static_object_keys: unit.iter_static_object_keys().count(),
+            static_object_keys_size: unit
+                .iter_static_object_keys()
+                .flat_map(|(_, keys)| keys.iter().map(|k| k.len()))
+                .sum(),
+            constants: unit.iter_constants().count(),
+        }
+    }
+}
+
+impl fmt::Display for UnitReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "instructions: {}", self.instructions)?;
+        writeln!(
+            f,
+            "static strings: {} ({} bytes)",
+            self.static_strings, self.static_strings_size
+        )?;
+        writeln!(
+            f,
+            "static bytes: {} ({} bytes)",
+            self.static_bytes, self.static_bytes_size
+        )?;
+        writeln!(
+            f,
+            "static object keys: {} ({} bytes)",
+            self.static_object_keys, self.static_object_keys_size
+        )?;
+        writeln!(f, "constants: {}", self.constants)?;
+
+        if !self.functions.is_empty() {
+            writeln!(f, "functions:")?;
+
+            for function in &self.functions {
+                write!(f, "  {:>6} {:04}", function.instructions, function.offset)?;
+
+                match &function.signature {
+                    Some(signature) => writeln!(f, " {} ({})", signature, function.hash)?,
+                    None => writeln!(f, " {}", function.hash)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -2,9 +2,9 @@ use crate::compile::ItemBuf;
 use crate::runtime::vm::CallResult;
 use crate::runtime::{
     AccessKind, AnyObj, Bytes, ConstValue, EnvProtocolCaller, Format, FromValue, Function, Future,
-    Generator, GeneratorState, Iterator, Mut, Object, Protocol, ProtocolCaller, Range, RawMut,
-    RawRef, Ref, Shared, StaticString, Stream, ToValue, Tuple, TypeInfo, Variant, Vec, Vm, VmError,
-    VmErrorKind,
+    Generator, GeneratorState, GuardedArgs, Iterator, Mut, Object, Protocol, ProtocolCaller, Range,
+    RawMut, RawRef, Ref, Shared, StaticString, Stream, ToValue, Tuple, TypeInfo, Variant,
+    VariantData, Vec, Vm, VmError, VmErrorKind,
 };
 use crate::{Any, Hash};
 use serde::{de, ser, Deserialize, Serialize};
@@ -81,7 +81,8 @@ impl TupleStruct {
 
 impl fmt::Debug for TupleStruct {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{:?}", self.rtti.item, self.data)
+        write!(f, "{}", self.rtti.item)?;
+        fmt::Debug::fmt(&self.data, f)
     }
 }
 
@@ -141,7 +142,7 @@ impl Struct {
 
 impl fmt::Debug for Struct {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.data.debug_struct(&self.rtti.item))
+        fmt::Display::fmt(&self.data.debug_struct(&self.rtti.item), f)
     }
 }
 
@@ -365,13 +366,17 @@ impl Value {
     ///
     /// This function will panic if called outside of a virtual machine.
     pub fn string_debug(&self, s: &mut String) -> Result<fmt::Result, VmError> {
-        self.string_debug_with(s, EnvProtocolCaller)
+        self.string_debug_with(s, false, EnvProtocolCaller)
     }
 
     /// Internal impl of string_debug with a customizable caller.
+    ///
+    /// If `pretty` is set, values are formatted using the alternate
+    /// multi-line format, like `{:#?}` does.
     pub(crate) fn string_debug_with(
         &self,
         s: &mut String,
+        pretty: bool,
         caller: impl ProtocolCaller,
     ) -> Result<fmt::Result, VmError> {
         use std::fmt::Write as _;
@@ -380,84 +385,34 @@ impl Value {
             Value::Unit => {
                 write!(s, "()")
             }
-            Value::Bool(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Byte(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Char(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Integer(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Float(value) => {
-                write!(s, "{:?}", value)
-            }
+            Value::Bool(value) => write_debug(s, value, pretty),
+            Value::Byte(value) => write_debug(s, value, pretty),
+            Value::Char(value) => write_debug(s, value, pretty),
+            Value::Integer(value) => write_debug(s, value, pretty),
+            Value::Float(value) => write_debug(s, value, pretty),
             Value::Type(value) => {
                 write!(s, "Type({})", value)
             }
-            Value::StaticString(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::String(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Bytes(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Vec(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Tuple(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Object(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Range(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Future(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Stream(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Generator(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::GeneratorState(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Option(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Result(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::UnitStruct(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::TupleStruct(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Struct(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Variant(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Function(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Format(value) => {
-                write!(s, "{:?}", value)
-            }
-            Value::Iterator(value) => {
-                write!(s, "{:?}", value)
-            }
+            Value::StaticString(value) => write_debug(s, value, pretty),
+            Value::String(value) => write_debug(s, value, pretty),
+            Value::Bytes(value) => write_debug(s, value, pretty),
+            Value::Vec(value) => write_debug(s, value, pretty),
+            Value::Tuple(value) => write_debug(s, value, pretty),
+            Value::Object(value) => write_debug(s, value, pretty),
+            Value::Range(value) => write_debug(s, value, pretty),
+            Value::Future(value) => write_debug(s, value, pretty),
+            Value::Stream(value) => write_debug(s, value, pretty),
+            Value::Generator(value) => write_debug(s, value, pretty),
+            Value::GeneratorState(value) => write_debug(s, value, pretty),
+            Value::Option(value) => write_debug(s, value, pretty),
+            Value::Result(value) => write_debug(s, value, pretty),
+            Value::UnitStruct(value) => write_debug(s, value, pretty),
+            Value::TupleStruct(value) => write_debug(s, value, pretty),
+            Value::Struct(value) => write_debug(s, value, pretty),
+            Value::Variant(value) => write_debug(s, value, pretty),
+            Value::Function(value) => write_debug(s, value, pretty),
+            Value::Format(value) => write_debug(s, value, pretty),
+            Value::Iterator(value) => write_debug(s, value, pretty),
             value => {
                 let b = Shared::new(std::mem::take(s));
 
@@ -476,6 +431,29 @@ impl Value {
         Ok(result)
     }
 
+    /// Call the given protocol on the value with the given arguments.
+    ///
+    /// This is primarily used to call custom protocols declared through
+    /// [Module::protocol], which can be implemented both by native types and
+    /// by types defined in scripts. Errors with
+    /// [VmErrorKind::MissingFunction] if the value doesn't implement the
+    /// protocol.
+    ///
+    /// You must use [Vm::with] to specify which virtual machine this function
+    /// is called inside.
+    ///
+    /// [Module::protocol]: crate::Module::protocol
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called outside of a virtual machine.
+    pub fn call_protocol<A>(&self, protocol: Protocol, args: A) -> Result<Value, VmError>
+    where
+        A: GuardedArgs,
+    {
+        EnvProtocolCaller.call_protocol_fn(protocol, self.clone(), args)
+    }
+
     /// Convert value into an iterator using the [Protocol::INTO_ITER] protocol.
     ///
     /// You must use [Vm::with] to specify which virtual machine this function
@@ -953,6 +931,121 @@ impl Value {
         })
     }
 
+    /// Construct a deep copy of the value.
+    ///
+    /// Containers like vectors, objects and tuples are copied recursively, so
+    /// that modifying the copy doesn't affect the original. External types are
+    /// copied through the [Protocol::CLONE] protocol.
+    ///
+    /// Values which can't be copied, like generators or external types which
+    /// don't implement [Protocol::CLONE], result in a
+    /// [VmErrorKind::CloneNotSupported] error. Values which reference
+    /// themselves can't be copied either.
+    ///
+    /// ```
+    /// use rune::Value;
+    /// use rune::runtime::Shared;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let inner = Value::from(Shared::new(String::from("hello")));
+    /// let a = Value::vec(vec![inner]);
+    /// let b = a.clone_deep()?;
+    ///
+    /// let a = a.into_vec()?.take()?;
+    /// let b = b.into_vec()?.take()?;
+    ///
+    /// a[0].clone().into_string()?.borrow_mut()?.push_str(" world");
+    ///
+    /// assert_eq!(&*a[0].clone().into_string()?.borrow_ref()?, "hello world");
+    /// assert_eq!(&*b[0].clone().into_string()?.borrow_ref()?, "hello");
+    /// # Ok(()) }
+    /// ```
+    pub fn clone_deep(&self) -> Result<Value, VmError> {
+        // NB: exclusive access is acquired to containers while they are being
+        // copied, which causes values referencing themselves to error instead
+        // of recursing indefinitely.
+        Ok(match self {
+            Value::Unit
+            | Value::Bool(..)
+            | Value::Byte(..)
+            | Value::Char(..)
+            | Value::Integer(..)
+            | Value::Float(..)
+            | Value::Type(..)
+            | Value::StaticString(..)
+            | Value::Function(..)
+            | Value::Format(..) => self.clone(),
+            Value::String(string) => Value::from(string.borrow_ref()?.clone()),
+            Value::Bytes(bytes) => Value::from(bytes.borrow_ref()?.clone()),
+            Value::Vec(vec) => Value::from(vec.borrow_mut()?.clone_deep()?),
+            Value::Tuple(tuple) => Value::from(tuple.borrow_mut()?.clone_deep()?),
+            Value::Object(object) => Value::from(object.borrow_mut()?.clone_deep()?),
+            Value::Range(range) => {
+                let range = range.borrow_mut()?;
+
+                Value::from(Range::new(
+                    clone_deep_option(range.start.as_ref())?,
+                    clone_deep_option(range.end.as_ref())?,
+                    range.limits,
+                ))
+            }
+            Value::Option(option) => Value::Option(Shared::new(clone_deep_option(
+                option.borrow_mut()?.as_ref(),
+            )?)),
+            Value::Result(result) => Value::Result(Shared::new(match &*result.borrow_mut()? {
+                Ok(value) => Ok(value.clone_deep()?),
+                Err(value) => Err(value.clone_deep()?),
+            })),
+            Value::UnitStruct(empty) => Value::unit_struct(empty.borrow_ref()?.rtti.clone()),
+            Value::TupleStruct(tuple) => {
+                let tuple = tuple.borrow_mut()?;
+
+                Value::from(TupleStruct {
+                    rtti: tuple.rtti.clone(),
+                    data: tuple.data.clone_deep()?,
+                })
+            }
+            Value::Struct(object) => {
+                let object = object.borrow_mut()?;
+
+                Value::from(Struct {
+                    rtti: object.rtti.clone(),
+                    data: object.data.clone_deep()?,
+                })
+            }
+            Value::Variant(variant) => {
+                let variant = variant.borrow_mut()?;
+                let rtti = variant.rtti.clone();
+
+                Value::from(match &variant.data {
+                    VariantData::Unit => Variant::unit(rtti),
+                    VariantData::Tuple(tuple) => Variant::tuple(rtti, tuple.clone_deep()?),
+                    VariantData::Struct(object) => Variant::struct_(rtti, object.clone_deep()?),
+                })
+            }
+            Value::Any(..) => {
+                let hash = Hash::instance_function(self.type_hash()?, Protocol::CLONE);
+
+                match EnvProtocolCaller.call_protocol_fn(Protocol::CLONE, self.clone(), ()) {
+                    Ok(value) => value,
+                    Err(error) => match error.kind() {
+                        VmErrorKind::MissingFunction { hash: missing } if *missing == hash => {
+                            return Err(VmError::from(VmErrorKind::CloneNotSupported {
+                                actual: self.type_info()?,
+                            }));
+                        }
+                        _ => return Err(error),
+                    },
+                }
+            }
+            value => {
+                return Err(VmError::from(VmErrorKind::CloneNotSupported {
+                    actual: value.type_info()?,
+                }))
+            }
+        })
+    }
+
     /// Optimized function to test if two value pointers are deeply equal to
     /// each other.
     ///
@@ -1056,6 +1149,129 @@ impl Value {
             rhs: b.type_info()?,
         }))
     }
+
+    /// Perform a partial ordered comparison between two values.
+    ///
+    /// Primitives, strings and bytes are compared by value, while vectors,
+    /// tuples and options are compared lexicographically. External types are
+    /// compared through the [Protocol::PARTIAL_CMP] protocol, falling back to
+    /// [Protocol::CMP]. Types defined in scripts are compared through instance
+    /// functions named `partial_cmp` or `cmp`.
+    ///
+    /// This is used by the `<`, `<=`, `>` and `>=` operators.
+    ///
+    /// ```
+    /// use rune::{ToValue, Value};
+    /// use std::cmp::Ordering;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let a = (1i64, 2.0f64).to_value()?;
+    /// let b = (1i64, 3.0f64).to_value()?;
+    ///
+    /// assert_eq!(Value::partial_cmp(&a, &b)?, Some(Ordering::Less));
+    /// assert_eq!(Value::partial_cmp(&Value::from(f64::NAN), &Value::from(1.0f64))?, None);
+    /// # Ok(()) }
+    /// ```
+    pub fn partial_cmp(a: &Value, b: &Value) -> Result<Option<cmp::Ordering>, VmError> {
+        Self::partial_cmp_with(a, b, "partial_cmp")
+    }
+
+    /// Perform a total ordered comparison between two values.
+    ///
+    /// This behaves like [Value::partial_cmp], except that external types are
+    /// compared through the [Protocol::CMP] protocol first and values which
+    /// can't be ordered, like a float which is NaN, result in an error.
+    ///
+    /// This is used by `Vec::sort`.
+    pub fn cmp(a: &Value, b: &Value) -> Result<cmp::Ordering, VmError> {
+        if a.is_user_defined() {
+            if let Some(ordering) = call_cmp_protocol(Protocol::CMP, a, b)? {
+                return cmp::Ordering::from_value(ordering);
+            }
+        }
+
+        match Self::partial_cmp_with(a, b, "cmp")? {
+            Some(ordering) => Ok(ordering),
+            None => Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                op: "cmp",
+                lhs: a.type_info()?,
+                rhs: b.type_info()?,
+            })),
+        }
+    }
+
+    /// Perform a partial ordered comparison between two values, reporting the
+    /// given operation if they can't be compared.
+    pub(crate) fn partial_cmp_with(
+        a: &Value,
+        b: &Value,
+        op: &'static str,
+    ) -> Result<Option<cmp::Ordering>, VmError> {
+        match (a, b) {
+            (Self::Unit, Self::Unit) => return Ok(Some(cmp::Ordering::Equal)),
+            (Self::Bool(a), Self::Bool(b)) => return Ok(a.partial_cmp(b)),
+            (Self::Byte(a), Self::Byte(b)) => return Ok(a.partial_cmp(b)),
+            (Self::Char(a), Self::Char(b)) => return Ok(a.partial_cmp(b)),
+            (Self::Integer(a), Self::Integer(b)) => return Ok(a.partial_cmp(b)),
+            (Self::Float(a), Self::Float(b)) => return Ok(a.partial_cmp(b)),
+            (Self::String(a), Self::String(b)) => {
+                return Ok(a.borrow_ref()?.partial_cmp(&*b.borrow_ref()?));
+            }
+            (Self::StaticString(a), Self::String(b)) => {
+                return Ok(a.as_str().partial_cmp(b.borrow_ref()?.as_str()));
+            }
+            (Self::String(a), Self::StaticString(b)) => {
+                return Ok(a.borrow_ref()?.as_str().partial_cmp(b.as_str()));
+            }
+            (Self::StaticString(a), Self::StaticString(b)) => {
+                return Ok(a.as_str().partial_cmp(b.as_str()));
+            }
+            (Self::Bytes(a), Self::Bytes(b)) => {
+                return Ok(a.borrow_ref()?.partial_cmp(&*b.borrow_ref()?));
+            }
+            (Self::Vec(a), Self::Vec(b)) => {
+                return partial_cmp_slices(&a.borrow_ref()?, &b.borrow_ref()?, op);
+            }
+            (Self::Tuple(a), Self::Tuple(b)) => {
+                return partial_cmp_slices(&a.borrow_ref()?, &b.borrow_ref()?, op);
+            }
+            (Self::Option(a), Self::Option(b)) => {
+                return match (&*a.borrow_ref()?, &*b.borrow_ref()?) {
+                    (Some(a), Some(b)) => Self::partial_cmp_with(a, b, op),
+                    (a, b) => Ok(a.is_some().partial_cmp(&b.is_some())),
+                };
+            }
+            (a, b) if a.is_user_defined() => {
+                if let Some(ordering) = call_cmp_protocol(Protocol::PARTIAL_CMP, a, b)? {
+                    return <Option<cmp::Ordering>>::from_value(ordering);
+                }
+
+                if let Some(ordering) = call_cmp_protocol(Protocol::CMP, a, b)? {
+                    return Ok(Some(cmp::Ordering::from_value(ordering)?));
+                }
+            }
+            _ => {}
+        }
+
+        Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+            op,
+            lhs: a.type_info()?,
+            rhs: b.type_info()?,
+        }))
+    }
+
+    /// Test if the value is an external type or a type defined in a script,
+    /// which are the types that can implement comparison protocols.
+    fn is_user_defined(&self) -> bool {
+        matches!(
+            self,
+            Self::Any(..)
+                | Self::UnitStruct(..)
+                | Self::TupleStruct(..)
+                | Self::Struct(..)
+                | Self::Variant(..)
+        )
+    }
 }
 
 impl fmt::Debug for Value {
@@ -1065,86 +1281,87 @@ impl fmt::Debug for Value {
                 write!(f, "()")?;
             }
             Value::Bool(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Byte(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Char(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Integer(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Float(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Type(value) => {
                 write!(f, "Type({})", value)?;
             }
             Value::StaticString(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::String(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Bytes(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Vec(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Tuple(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Object(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Range(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Future(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Stream(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Generator(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::GeneratorState(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Option(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Result(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::UnitStruct(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::TupleStruct(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Struct(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Variant(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Function(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Format(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Iterator(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             value => {
                 let mut s = String::new();
-                let result = match value.string_debug(&mut s) {
+                let result = match value.string_debug_with(&mut s, f.alternate(), EnvProtocolCaller)
+                {
                     Ok(s) => s,
                     Err(_) => {
                         // this isn't very nice, but if protocol string fails
@@ -1553,6 +1770,57 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     }
 }
 
+/// Compare two slices of values lexicographically.
+fn partial_cmp_slices(
+    a: &[Value],
+    b: &[Value],
+    op: &'static str,
+) -> Result<Option<cmp::Ordering>, VmError> {
+    for (a, b) in a.iter().zip(b) {
+        match Value::partial_cmp_with(a, b, op)? {
+            Some(cmp::Ordering::Equal) => continue,
+            ordering => return Ok(ordering),
+        }
+    }
+
+    Ok(a.len().partial_cmp(&b.len()))
+}
+
+/// Call the given comparison protocol with `b` as an argument to `a`.
+///
+/// Returns `None` if the protocol isn't implemented.
+fn call_cmp_protocol(protocol: Protocol, a: &Value, b: &Value) -> Result<Option<Value>, VmError> {
+    match EnvProtocolCaller.call_protocol_fn(protocol, a.clone(), (b.clone(),)) {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => match error.kind() {
+            VmErrorKind::MissingFunction { hash }
+                if *hash == Hash::instance_function(a.type_hash()?, protocol) =>
+            {
+                Ok(None)
+            }
+            _ => Err(error),
+        },
+    }
+}
+
+fn clone_deep_option(value: Option<&Value>) -> Result<Option<Value>, VmError> {
+    value.map(Value::clone_deep).transpose()
+}
+
+/// Debug format a value, optionally using the alternate pretty format.
+fn write_debug<T>(s: &mut String, value: &T, pretty: bool) -> fmt::Result
+where
+    T: ?Sized + fmt::Debug,
+{
+    use std::fmt::Write as _;
+
+    if pretty {
+        write!(s, "{:#?}", value)
+    } else {
+        write!(s, "{:?}", value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Value;
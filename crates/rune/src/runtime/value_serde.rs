@@ -0,0 +1,680 @@
+//! Conversion between [Value] and any type implementing [Serialize] or
+//! [Deserialize], without going through an intermediate format.
+
+use crate::runtime::{Bytes, Object, Shared, Tuple, Value, Vec, VmError, VmErrorKind};
+use serde::de::{self, IntoDeserializer};
+use serde::ser::{self, Serialize};
+use std::fmt;
+use std::vec;
+
+/// Convert any [Serialize] value into a [Value].
+///
+/// Structs and maps are converted into objects, sequences into vectors and
+/// tuples into tuples. Enum variants are represented the same way as in
+/// `serde_json`, so a unit variant becomes a string and every other variant
+/// becomes an object with a single key naming the variant.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// let value = rune::runtime::to_value(&Point { x: 1, y: 2 })?;
+/// let object = value.into_object()?;
+/// let object = object.borrow_ref()?;
+/// assert_eq!(object.len(), 2);
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub fn to_value<T>(value: &T) -> Result<Value, VmError>
+where
+    T: ?Sized + Serialize,
+{
+    value.serialize(ValueSerializer)
+}
+
+/// Convert a [Value] into any type implementing [Deserialize].
+///
+/// This is the inverse of [to_value].
+///
+/// # Examples
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     Empty,
+/// }
+///
+/// let value = rune::runtime::to_value(&Shape::Circle { radius: 2.0 })?;
+/// let shape: Shape = rune::runtime::from_value(value)?;
+/// assert_eq!(shape, Shape::Circle { radius: 2.0 });
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub fn from_value<T>(value: Value) -> Result<T, VmError>
+where
+    T: de::DeserializeOwned,
+{
+    T::deserialize(value)
+}
+
+impl ser::Error for VmError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        VmError::from(VmErrorKind::Serde {
+            message: msg.to_string().into(),
+        })
+    }
+}
+
+impl de::Error for VmError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        VmError::from(VmErrorKind::Serde {
+            message: msg.to_string().into(),
+        })
+    }
+}
+
+/// A [Serializer][ser::Serializer] which produces a [Value].
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = VmError;
+
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeTuple;
+    type SerializeTupleStruct = SerializeTuple;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, VmError> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, VmError> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, VmError> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, VmError> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, VmError> {
+        Ok(Value::Integer(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value, VmError> {
+        Ok(Value::Integer(
+            i64::try_from(v).map_err(|_| VmErrorKind::Overflow)?,
+        ))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, VmError> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, VmError> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, VmError> {
+        Ok(Value::Integer(v as i64))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, VmError> {
+        Ok(Value::Integer(
+            i64::try_from(v).map_err(|_| VmErrorKind::Overflow)?,
+        ))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value, VmError> {
+        Ok(Value::Integer(
+            i64::try_from(v).map_err(|_| VmErrorKind::Overflow)?,
+        ))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, VmError> {
+        Ok(Value::Float(v as f64))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, VmError> {
+        Ok(Value::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, VmError> {
+        Ok(Value::Char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, VmError> {
+        Ok(Value::from(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, VmError> {
+        Ok(Value::from(Bytes::from_vec(v.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<Value, VmError> {
+        Ok(Value::Option(Shared::new(None)))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value, VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = value.serialize(self)?;
+        Ok(Value::Option(Shared::new(Some(value))))
+    }
+
+    fn serialize_unit(self) -> Result<Value, VmError> {
+        Ok(Value::Unit)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, VmError> {
+        Ok(Value::Unit)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Value, VmError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Value, VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut object = Object::with_capacity(1);
+        object.insert(variant.to_owned(), value.serialize(self)?);
+        Ok(Value::from(object))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVec, VmError> {
+        Ok(SerializeVec {
+            vec: vec::Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeTuple, VmError> {
+        Ok(SerializeTuple {
+            vec: vec::Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<SerializeTuple, VmError> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, VmError> {
+        Ok(SerializeTupleVariant {
+            variant,
+            vec: vec::Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeObject, VmError> {
+        Ok(SerializeObject {
+            object: Object::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<SerializeObject, VmError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeStructVariant, VmError> {
+        Ok(SerializeStructVariant {
+            variant,
+            object: Object::with_capacity(len),
+        })
+    }
+}
+
+/// Serializer for sequences, producing a [Vec].
+#[doc(hidden)]
+pub struct SerializeVec {
+    vec: vec::Vec<Value>,
+}
+
+impl ser::SerializeSeq for SerializeVec {
+    type Ok = Value;
+    type Error = VmError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.vec.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, VmError> {
+        Ok(Value::from(Vec::from(self.vec)))
+    }
+}
+
+/// Serializer for tuples and tuple structs, producing a [Tuple].
+#[doc(hidden)]
+pub struct SerializeTuple {
+    vec: vec::Vec<Value>,
+}
+
+impl ser::SerializeTuple for SerializeTuple {
+    type Ok = Value;
+    type Error = VmError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.vec.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, VmError> {
+        Ok(Value::from(Tuple::from(self.vec)))
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeTuple {
+    type Ok = Value;
+    type Error = VmError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeTuple::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, VmError> {
+        ser::SerializeTuple::end(self)
+    }
+}
+
+/// Serializer for tuple variants, producing an [Object] with a single [Tuple]
+/// entry.
+#[doc(hidden)]
+pub struct SerializeTupleVariant {
+    variant: &'static str,
+    vec: vec::Vec<Value>,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Value;
+    type Error = VmError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.vec.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, VmError> {
+        let mut object = Object::with_capacity(1);
+        object.insert(self.variant.to_owned(), Value::from(Tuple::from(self.vec)));
+        Ok(Value::from(object))
+    }
+}
+
+/// Serializer for maps and structs, producing an [Object].
+#[doc(hidden)]
+pub struct SerializeObject {
+    object: Object,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = Value;
+    type Error = VmError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.key = Some(object_key(key.serialize(ValueSerializer)?)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Err(ser::Error::custom("value serialized before its key")),
+        };
+
+        self.object.insert(key, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, VmError> {
+        Ok(Value::from(self.object))
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = Value;
+    type Error = VmError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.object
+            .insert(key.to_owned(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, VmError> {
+        Ok(Value::from(self.object))
+    }
+}
+
+/// Serializer for struct variants, producing an [Object] with a single
+/// [Object] entry.
+#[doc(hidden)]
+pub struct SerializeStructVariant {
+    variant: &'static str,
+    object: Object,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Value;
+    type Error = VmError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), VmError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.object
+            .insert(key.to_owned(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, VmError> {
+        let mut object = Object::with_capacity(1);
+        object.insert(self.variant.to_owned(), Value::from(self.object));
+        Ok(Value::from(object))
+    }
+}
+
+/// Convert a serialized map key into an object key.
+fn object_key(key: Value) -> Result<String, VmError> {
+    Ok(match key {
+        Value::String(string) => string.borrow_ref()?.clone(),
+        Value::StaticString(string) => string.as_str().to_owned(),
+        Value::Char(c) => c.to_string(),
+        Value::Integer(integer) => integer.to_string(),
+        Value::Bool(b) => b.to_string(),
+        actual => {
+            return Err(ser::Error::custom(format_args!(
+                "unsupported object key `{}`",
+                actual.type_info()?
+            )))
+        }
+    })
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = VmError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, VmError>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            Value::Unit => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::Byte(b) => visitor.visit_u8(b),
+            Value::Char(c) => visitor.visit_char(c),
+            Value::Integer(integer) => visitor.visit_i64(integer),
+            Value::Float(float) => visitor.visit_f64(float),
+            Value::StaticString(string) => visitor.visit_str(string.as_str()),
+            Value::String(string) => visitor.visit_string(string.borrow_ref()?.clone()),
+            Value::Bytes(bytes) => visitor.visit_byte_buf(bytes.borrow_ref()?.to_vec()),
+            Value::Vec(vec) => {
+                let values = vec.borrow_ref()?.iter().cloned().collect::<vec::Vec<_>>();
+                visit_seq(values, visitor)
+            }
+            Value::Tuple(tuple) => {
+                let values = tuple.borrow_ref()?.iter().cloned().collect::<vec::Vec<_>>();
+                visit_seq(values, visitor)
+            }
+            Value::Object(object) => {
+                let entries = object
+                    .borrow_ref()?
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect::<vec::Vec<_>>();
+
+                let mut map = de::value::MapDeserializer::<_, VmError>::new(entries.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            Value::Option(option) => match option.borrow_ref()?.clone() {
+                Some(value) => visitor.visit_some(value),
+                None => visitor.visit_none(),
+            },
+            Value::UnitStruct(..) => visitor.visit_unit(),
+            actual => Err(de::Error::custom(format_args!(
+                "cannot deserialize `{}`",
+                actual.type_info()?
+            ))),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, VmError>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            Value::Unit => visitor.visit_none(),
+            Value::Option(option) => match option.borrow_ref()?.clone() {
+                Some(value) => visitor.visit_some(value),
+                None => visitor.visit_none(),
+            },
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, VmError>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, VmError>
+    where
+        V: de::Visitor<'de>,
+    {
+        let (variant, value) = match self {
+            Value::String(string) => (string.borrow_ref()?.clone(), None),
+            Value::StaticString(string) => (string.as_str().to_owned(), None),
+            Value::Object(object) => {
+                let object = object.borrow_ref()?;
+                let mut it = object.iter();
+
+                match (it.next(), it.next()) {
+                    (Some((variant, value)), None) => (variant.clone(), Some(value.clone())),
+                    _ => {
+                        return Err(de::Error::custom(
+                            "expected an object with a single key naming the variant",
+                        ));
+                    }
+                }
+            }
+            actual => {
+                return Err(de::Error::custom(format_args!(
+                    "expected string or object for enum, but got `{}`",
+                    actual.type_info()?
+                )));
+            }
+        };
+
+        visitor.visit_enum(EnumDeserializer { variant, value })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, VmError> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Visit a sequence of values.
+fn visit_seq<'de, V>(values: vec::Vec<Value>, visitor: V) -> Result<V::Value, VmError>
+where
+    V: de::Visitor<'de>,
+{
+    let mut seq = de::value::SeqDeserializer::<_, VmError>::new(values.into_iter());
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+/// Access to the variant of an enum.
+struct EnumDeserializer {
+    variant: String,
+    value: Option<Value>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = VmError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantDeserializer), VmError>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant =
+            seed.deserialize(de::value::StringDeserializer::<VmError>::new(self.variant))?;
+        Ok((variant, VariantDeserializer { value: self.value }))
+    }
+}
+
+/// Access to the content of an enum variant.
+struct VariantDeserializer {
+    value: Option<Value>,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = VmError;
+
+    fn unit_variant(self) -> Result<(), VmError> {
+        match self.value {
+            None | Some(Value::Unit) => Ok(()),
+            Some(value) => de::Deserialize::deserialize(value),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, VmError>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _: usize, visitor: V) -> Result<V::Value, VmError>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => de::Deserializer::deserialize_seq(value, visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(self, _: &'static [&'static str], visitor: V) -> Result<V::Value, VmError>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => de::Deserializer::deserialize_map(value, visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
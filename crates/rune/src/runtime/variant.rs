@@ -92,10 +92,10 @@ impl fmt::Debug for Variant {
         match &self.data {
             VariantData::Unit => {}
             VariantData::Struct(st) => {
-                write!(f, "{:?}", st)?;
+                fmt::Debug::fmt(st, f)?;
             }
             VariantData::Tuple(tuple) => {
-                write!(f, "{:?}", tuple)?;
+                fmt::Debug::fmt(tuple, f)?;
             }
         }
 
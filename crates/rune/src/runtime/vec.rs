@@ -1,7 +1,7 @@
 use crate::compile::{InstallWith, Named};
 use crate::runtime::{
-    FromValue, Iterator, Mut, RawMut, RawRef, RawStr, Ref, Shared, ToValue, UnsafeFromValue, Value,
-    Vm, VmError, VmErrorKind,
+    FromValue, Iterator, Mut, Range, RangeLimits, RawMut, RawRef, RawStr, Ref, Shared, ToValue,
+    UnsafeFromValue, Value, Vm, VmError, VmErrorKind,
 };
 use std::cmp;
 use std::fmt;
@@ -57,6 +57,19 @@ impl Vec {
         }
     }
 
+    /// Construct a deep copy of the vector.
+    ///
+    /// See [Value::clone_deep].
+    pub fn clone_deep(&self) -> Result<Self, VmError> {
+        Ok(Self {
+            inner: self
+                .inner
+                .iter()
+                .map(Value::clone_deep)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
     /// Convert into inner std vector.
     pub fn into_inner(self) -> vec::Vec<Value> {
         self.inner
@@ -135,6 +148,108 @@ impl Vec {
         self.inner.remove(index);
     }
 
+    /// Removes the element at the specified index and returns it, replacing
+    /// it with the last element of the vector.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    pub fn swap_remove(&mut self, index: usize) -> Result<Value, VmError> {
+        if index >= self.len() {
+            return Err(VmError::from(VmErrorKind::OutOfRange {
+                index: index.into(),
+                len: self.len().into(),
+            }));
+        }
+
+        Ok(self.inner.swap_remove(index))
+    }
+
+    /// Retains only the elements for which the given predicate returns
+    /// `true`, preserving their order.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Value) -> bool,
+    {
+        self.inner.retain(f)
+    }
+
+    /// Removes consecutive elements which the given function considers to be
+    /// the same, keeping the first one.
+    pub fn dedup_by<F>(&mut self, mut same: F)
+    where
+        F: FnMut(&Value, &Value) -> bool,
+    {
+        self.inner.dedup_by(|a, b| same(b, a))
+    }
+
+    /// Rotates the vector in-place such that the first `mid` elements move to
+    /// the end.
+    pub fn rotate_left(&mut self, mid: usize) -> Result<(), VmError> {
+        if mid > self.len() {
+            return Err(VmError::from(VmErrorKind::OutOfRange {
+                index: mid.into(),
+                len: self.len().into(),
+            }));
+        }
+
+        self.inner.rotate_left(mid);
+        Ok(())
+    }
+
+    /// Rotates the vector in-place such that the last `k` elements move to
+    /// the front.
+    pub fn rotate_right(&mut self, k: usize) -> Result<(), VmError> {
+        if k > self.len() {
+            return Err(VmError::from(VmErrorKind::OutOfRange {
+                index: k.into(),
+                len: self.len().into(),
+            }));
+        }
+
+        self.inner.rotate_right(k);
+        Ok(())
+    }
+
+    /// Replaces the elements in the given range with the values produced by
+    /// something that implements the into_iter protocol, returning the
+    /// removed elements.
+    pub fn splice(&mut self, range: &Range, replace_with: Value) -> Result<Self, VmError> {
+        let start = match range.start.clone() {
+            Some(value) => <usize as FromValue>::from_value(value)?,
+            None => 0,
+        };
+
+        let end = match (range.end.clone(), range.limits) {
+            (Some(value), RangeLimits::HalfOpen) => <usize as FromValue>::from_value(value)?,
+            (Some(value), RangeLimits::Closed) => {
+                <usize as FromValue>::from_value(value)?.saturating_add(1)
+            }
+            (None, RangeLimits::HalfOpen) => self.len(),
+            (None, RangeLimits::Closed) => {
+                return Err(VmError::from(VmErrorKind::UnsupportedRange))
+            }
+        };
+
+        if start > end || end > self.len() {
+            return Err(VmError::from(VmErrorKind::OutOfRange {
+                index: end.into(),
+                len: self.len().into(),
+            }));
+        }
+
+        let mut it = replace_with.into_iter()?;
+        let mut values = vec::Vec::new();
+
+        while let Some(value) = it.next()? {
+            values.push(value);
+        }
+
+        Ok(Self::from(
+            self.inner
+                .splice(start..end, values)
+                .collect::<vec::Vec<_>>(),
+        ))
+    }
+
     /// Clears the vector, removing all values.
     ///
     /// Note that this method has no effect on the allocated capacity of the
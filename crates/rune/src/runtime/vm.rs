@@ -1,16 +1,23 @@
 use crate::runtime::budget;
 use crate::runtime::future::SelectFuture;
+use crate::runtime::inline_cache::{InlineCache, InlineCaches};
 use crate::runtime::unit::UnitFn;
 use crate::runtime::{
-    Args, Awaited, BorrowMut, Bytes, Call, Format, FormatSpec, FromValue, Function, Future,
-    Generator, GuardedArgs, Inst, InstAddress, InstAssignOp, InstOp, InstRangeLimits, InstTarget,
-    InstValue, InstVariant, Object, Panic, Protocol, Range, RangeLimits, RuntimeContext, Select,
-    Shared, Stack, Stream, Struct, Tuple, TypeCheck, Unit, UnitStruct, Value, Variant, VariantData,
-    Vec, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmSendExecution,
+    AllocationUsage, Ambient, Args, Awaited, BorrowMut, Bytes, Call, Capabilities, CycleCollector,
+    Format, FormatSpec, FromValue, Function, Future, Generator, GuardedArgs, Inst, InstAddress,
+    InstAssignOp, InstIntOp, InstOp, InstRangeLimits, InstTarget, InstValue, InstVariant,
+    LazyGeneration, Object, Panic, Profiler, Protocol, Range, RangeLimits, RuntimeContext, Select,
+    Shared, Stack, Stream, Struct, Symbol, Tracer, Tuple, TypeCheck, TypedFunction, Unit, UnitDiff,
+    UnitGeneration, UnitStruct, Value, Variant, VariantData, Vec, VmError, VmErrorKind,
+    VmExecution, VmHalt, VmIntegerRepr, VmLimits, VmSendExecution, VmTracer,
 };
 use crate::{Hash, IntoTypeHash};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::cmp::Ordering;
 use std::fmt;
 use std::mem;
+use std::rc::Rc;
 use std::sync::Arc;
 use std::vec;
 
@@ -69,6 +76,31 @@ macro_rules! target_value {
     }};
 }
 
+/// Perform an integer operation, raising the same errors as the generic
+/// operation would.
+fn int_op(op: InstIntOp, a: i64, b: i64) -> Result<Value, VmError> {
+    Ok(match op {
+        InstIntOp::Add => Value::Integer(a.checked_add(b).ok_or(VmErrorKind::Overflow)?),
+        InstIntOp::Sub => Value::Integer(a.checked_sub(b).ok_or(VmErrorKind::Underflow)?),
+        InstIntOp::Mul => Value::Integer(a.checked_mul(b).ok_or(VmErrorKind::Overflow)?),
+        InstIntOp::Div => Value::Integer(a.checked_div(b).ok_or(VmErrorKind::DivideByZero)?),
+        InstIntOp::Rem => Value::Integer(a.checked_rem(b).ok_or(VmErrorKind::DivideByZero)?),
+        InstIntOp::Lt => Value::Bool(a < b),
+        InstIntOp::Gt => Value::Bool(a > b),
+        InstIntOp::Lte => Value::Bool(a <= b),
+        InstIntOp::Gte => Value::Bool(a >= b),
+        InstIntOp::Eq => Value::Bool(a == b),
+        InstIntOp::Neq => Value::Bool(a != b),
+    })
+}
+
+/// The approximate size of an object with the given keys.
+fn object_size(keys: &[String]) -> usize {
+    keys.iter()
+        .map(|key| key.len() + mem::size_of::<(String, Value)>())
+        .sum()
+}
+
 /// A stack which references variables indirectly from a slab.
 #[derive(Debug, Clone)]
 pub struct Vm {
@@ -76,12 +108,84 @@ pub struct Vm {
     context: Arc<RuntimeContext>,
     /// Unit associated with virtual machine.
     unit: Arc<Unit>,
+    /// The generation of the unit, shared with the functions created from it.
+    generation: LazyGeneration,
     /// The current instruction pointer.
     ip: usize,
     /// The current stack.
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: vec::Vec<CallFrame>,
+    /// Inline caches used by instructions which dispatch on types.
+    caches: InlineCaches,
+    /// State shared with the virtual machines started by this one.
+    inherited: Inherited,
+}
+
+/// State which a virtual machine shares with the virtual machines it starts,
+/// like the ones executing generators, async functions or functions called by
+/// native functions.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Inherited {
+    /// Limits imposed on the virtual machine.
+    pub(crate) limits: VmLimits,
+    /// Memory allocated by instructions, accounted for when an instruction
+    /// allocation budget is in place.
+    pub(crate) allocated: Option<Arc<AllocationUsage>>,
+    /// Tracer notified of calls, returns and other events.
+    pub(crate) tracer: Option<Tracer>,
+    /// Profiler sampling the execution.
+    pub(crate) profiler: Option<Arc<Profiler>>,
+    /// Ambient values provided by the host.
+    pub(crate) ambient: Option<Rc<Ambient>>,
+    /// Capabilities granted to the virtual machine, if it is restricted.
+    pub(crate) capabilities: Option<Arc<Capabilities>>,
+    /// Collector of reference cycles, if cycles are being tracked.
+    pub(crate) cycles: Option<Rc<CycleCollector>>,
+    /// The call depth and stack size of the virtual machines which started
+    /// this one, which count towards its limits.
+    pub(crate) outer: Usage,
+    /// The call depth and stack size which virtual machines started by the
+    /// native function currently being called start from.
+    pub(crate) nested: Cell<Usage>,
+}
+
+impl Inherited {
+    /// Construct state where nothing is inherited.
+    pub(crate) const fn new() -> Self {
+        Self {
+            limits: VmLimits::new(),
+            allocated: None,
+            tracer: None,
+            profiler: None,
+            ambient: None,
+            capabilities: None,
+            cycles: None,
+            outer: Usage { depth: 0, stack: 0 },
+            nested: Cell::new(Usage { depth: 0, stack: 0 }),
+        }
+    }
+
+    /// Account for the given number of bytes being allocated, erroring if it
+    /// exceeds the allocation budget.
+    #[inline]
+    pub(crate) fn charge(&self, bytes: usize) -> Result<(), VmError> {
+        if let (Some(allocated), Some(max)) =
+            (&self.allocated, self.limits.max_instruction_allocation)
+        {
+            allocated.charge(bytes, max)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The call depth and stack size of a virtual machine, including the ones
+/// which started it.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Usage {
+    pub(crate) depth: usize,
+    pub(crate) stack: usize,
 }
 
 impl Vm {
@@ -95,9 +199,25 @@ impl Vm {
         Self {
             context,
             unit,
+            generation: LazyGeneration::new(),
             ip: 0,
             stack,
             call_frames: vec::Vec::new(),
+            caches: InlineCaches::new(),
+            inherited: Inherited::new(),
+        }
+    }
+
+    /// Construct a new virtual machine running a unit of the given generation.
+    pub(crate) fn with_generation(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        stack: Stack,
+        generation: UnitGeneration,
+    ) -> Self {
+        Self {
+            generation: LazyGeneration::from_generation(generation),
+            ..Self::with_stack(context, unit, stack)
         }
     }
 
@@ -125,6 +245,11 @@ impl Vm {
         &self.call_frames
     }
 
+    /// Replace the call frames of the virtual machine.
+    pub(crate) fn set_call_frames(&mut self, call_frames: vec::Vec<CallFrame>) {
+        self.call_frames = call_frames;
+    }
+
     /// Get the stack.
     #[inline]
     pub fn stack(&self) -> &Stack {
@@ -149,18 +274,344 @@ impl Vm {
         &self.unit
     }
 
+    /// Access the generation of the current unit.
+    pub(crate) fn generation(&self) -> UnitGeneration {
+        self.generation.get()
+    }
+
     /// Access the current instruction pointer.
     #[inline]
     pub fn ip(&self) -> usize {
         self.ip
     }
 
+    /// Set the limits imposed on this virtual machine.
+    ///
+    /// This resets the instruction allocations which have been accounted for
+    /// so far.
+    pub fn set_limits(&mut self, limits: VmLimits) {
+        self.inherited.allocated = limits
+            .max_instruction_allocation
+            .map(|_| Arc::new(AllocationUsage::default()));
+        self.inherited.limits = limits;
+    }
+
+    /// Access the limits imposed on this virtual machine.
+    #[inline]
+    pub fn limits(&self) -> &VmLimits {
+        &self.inherited.limits
+    }
+
+    /// The approximate number of bytes allocated by instructions since the
+    /// instruction allocation budget was set, or `0` if no budget is in place.
+    pub fn instruction_allocation(&self) -> usize {
+        self.inherited
+            .allocated
+            .as_ref()
+            .map(|allocated| allocated.used())
+            .unwrap_or_default()
+    }
+
+    /// Install a tracer which is notified as this virtual machine executes.
+    ///
+    /// See [VmTracer] for more information.
+    pub fn set_tracer(&mut self, tracer: Arc<dyn VmTracer>) {
+        self.inherited.tracer = Some(Tracer(tracer));
+    }
+
+    /// Remove the installed tracer, returning it if present.
+    pub fn take_tracer(&mut self) -> Option<Arc<dyn VmTracer>> {
+        self.inherited.tracer.take().map(|Tracer(tracer)| tracer)
+    }
+
+    /// Access the installed tracer, if any.
+    #[inline]
+    pub fn tracer(&self) -> Option<&Arc<dyn VmTracer>> {
+        self.inherited.tracer.as_ref().map(|Tracer(tracer)| tracer)
+    }
+
+    /// Install a profiler which samples this virtual machine as it executes.
+    ///
+    /// See [Profiler] for more information.
+    pub fn set_profiler(&mut self, profiler: Arc<Profiler>) {
+        self.inherited.profiler = Some(profiler);
+    }
+
+    /// Remove the installed profiler, returning it if present.
+    pub fn take_profiler(&mut self) -> Option<Arc<Profiler>> {
+        self.inherited.profiler.take()
+    }
+
+    /// Provide ambient values to the scripts executed by this virtual
+    /// machine, replacing any which were previously provided.
+    ///
+    /// See [Ambient] for more information.
+    pub fn set_ambient(&mut self, ambient: Ambient) {
+        self.inherited.ambient = Some(Rc::new(ambient));
+    }
+
+    /// Remove the ambient values provided to this virtual machine.
+    pub fn clear_ambient(&mut self) {
+        self.inherited.ambient = None;
+    }
+
+    /// Access the ambient values provided to this virtual machine, if any.
+    #[inline]
+    pub fn ambient(&self) -> Option<&Ambient> {
+        self.inherited.ambient.as_deref()
+    }
+
+    /// Restrict this virtual machine to only call native functions which
+    /// require one of the given capabilities, or none at all.
+    ///
+    /// See [Capabilities] for more information.
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.inherited.capabilities = Some(Arc::new(capabilities));
+    }
+
+    /// Lift the restrictions imposed through [Vm::set_capabilities].
+    pub fn clear_capabilities(&mut self) {
+        self.inherited.capabilities = None;
+    }
+
+    /// Access the capabilities granted to this virtual machine, or `None` if
+    /// it is unrestricted.
+    #[inline]
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.inherited.capabilities.as_deref()
+    }
+
+    /// Track reference cycles between values using the given collector.
+    ///
+    /// See [cycles][crate::runtime::cycles] for more information.
+    pub fn set_cycle_collector(&mut self, collector: Rc<CycleCollector>) {
+        self.inherited.cycles = Some(collector);
+    }
+
+    /// Remove the installed collector of reference cycles, returning it if
+    /// present.
+    pub fn take_cycle_collector(&mut self) -> Option<Rc<CycleCollector>> {
+        self.inherited.cycles.take()
+    }
+
+    /// Access the collector of reference cycles used by this virtual machine,
+    /// if any.
+    #[inline]
+    pub fn cycle_collector(&self) -> Option<&Rc<CycleCollector>> {
+        self.inherited.cycles.as_ref()
+    }
+
+    /// Test if this virtual machine holds nothing which is bound to the thread
+    /// it is used on, so that it can be sent to another thread.
+    ///
+    /// The fields are destructured exhaustively, so adding a field fails to
+    /// compile until it is accounted for here.
+    pub(crate) fn is_thread_unbound(&self) -> bool {
+        fn send<T: Send>(_: &T) {}
+
+        let Self {
+            context,
+            unit,
+            generation,
+            ip,
+            stack,
+            call_frames,
+            caches,
+            inherited,
+        } = self;
+
+        let Inherited {
+            limits,
+            allocated,
+            tracer,
+            profiler,
+            ambient,
+            capabilities,
+            cycles,
+            outer,
+            nested,
+        } = inherited;
+
+        send(context);
+        send(unit);
+        send(generation);
+        send(ip);
+        send(call_frames);
+        send(limits);
+        send(allocated);
+        send(tracer);
+        send(profiler);
+        send(caches);
+        send(capabilities);
+        send(outer);
+        send(nested);
+
+        // NB: values are reference counted without synchronization, so these
+        // are only thread unbound while they are empty.
+        stack.is_empty() && ambient.is_none() && cycles.is_none()
+    }
+
+    /// Make this virtual machine subject to the same limits as `parent`,
+    /// sharing its instruction allocation budget, tracer, profiler, ambient
+    /// values, capabilities and cycle collector.
+    ///
+    /// The call depth and stack size of `parent` count towards the limits of
+    /// this virtual machine.
+    pub(crate) fn inherit(&mut self, parent: &Vm) {
+        self.inherited = parent.inherited.clone();
+        self.inherited.outer = parent.usage();
+    }
+
+    /// Make this virtual machine share the given state, like the state of the
+    /// virtual machine which called the native function starting it.
+    ///
+    /// This errors if the virtual machines it is started from have already
+    /// exhausted the call depth or stack size they are limited to.
+    pub(crate) fn set_inherited(&mut self, inherited: Inherited) -> Result<(), VmError> {
+        self.inherited = inherited;
+
+        let Usage { depth, stack } = self.inherited.outer;
+        self.inherited
+            .limits
+            .check_call(depth, stack.saturating_add(self.stack.len()))
+    }
+
+    /// The call depth and stack size of this virtual machine, including the
+    /// ones which started it.
+    #[inline]
+    fn usage(&self) -> Usage {
+        let Usage { depth, stack } = self.inherited.outer;
+
+        Usage {
+            depth: depth.saturating_add(self.call_frames.len() + 1),
+            stack: stack.saturating_add(self.stack.len()),
+        }
+    }
+
+    /// Notify the tracer of a call to the function with the given hash,
+    /// where the top `args` values on the stack are its arguments.
+    #[inline]
+    pub(crate) fn trace_call(&self, hash: Hash, args: usize) {
+        if let Some(Tracer(tracer)) = &self.inherited.tracer {
+            let start = self.stack.len().saturating_sub(args);
+            let args = self.stack.get(start..).unwrap_or_default();
+            tracer.on_call(self, hash, args);
+        }
+    }
+
+    /// Prepare for calling a native function with the top `args` values on
+    /// the stack as its arguments.
+    ///
+    /// This records the call depth and stack size which the virtual machines
+    /// the function might start continue from, and tracks the arguments as
+    /// possible roots of cycles since the function might store them in one
+    /// another.
+    #[inline]
+    pub(crate) fn enter_native(&self, args: usize) {
+        self.inherited.nested.set(self.usage());
+
+        if let Some(cycles) = &self.inherited.cycles {
+            let start = self.stack.len().saturating_sub(args);
+            cycles.track_all(self.stack.get(start..).unwrap_or_default());
+        }
+    }
+
+    /// Track the given value as a possible root of a cycle before it is
+    /// mutated.
+    #[inline]
+    fn track(&self, value: &Value) {
+        if let Some(cycles) = &self.inherited.cycles {
+            cycles.track(value);
+        }
+    }
+
+    /// Notify the tracer that execution is unwinding with the given error.
+    #[inline]
+    pub(crate) fn trace_unwind(&self, error: &VmError) {
+        if let Some(Tracer(tracer)) = &self.inherited.tracer {
+            tracer.on_unwind(self, error);
+        }
+    }
+
+    /// Account for the given number of bytes being allocated by an
+    /// instruction.
+    #[inline]
+    fn charge(&self, bytes: usize) -> Result<(), VmError> {
+        self.inherited.charge(bytes)
+    }
+
     /// Advance the instruction pointer.
     #[inline]
     pub(crate) fn advance(&mut self) {
         self.ip = self.ip.wrapping_add(1);
     }
 
+    /// Swap the unit of this virtual machine for a new one, returning the
+    /// difference between the functions of the old and the new unit.
+    ///
+    /// Function values which were created from the old unit for functions
+    /// which changed or were removed in the new unit become stale, and calling
+    /// them errors with [VmErrorKind::StaleFunction]. Function values for
+    /// unchanged functions keep working. The returned [UnitDiff] can be used
+    /// to determine which functions changed and should be looked up again.
+    ///
+    /// This errors if the virtual machine is currently executing, since the
+    /// instruction pointer and call frames refer to the old unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Hash, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources!(entry => {
+    ///     pub fn main() { 1 }
+    ///     pub fn helper() { 2 }
+    /// });
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    /// assert_eq!(vm.call(["main"], ())?.into_integer()?, 1);
+    ///
+    /// let mut sources = rune::sources!(entry => {
+    ///     pub fn main() { 42 }
+    ///     pub fn helper() { 2 }
+    /// });
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let diff = vm.swap_unit(Arc::new(unit))?;
+    ///
+    /// assert_eq!(diff.changed, vec![Hash::type_hash(["main"])]);
+    /// assert_eq!(diff.unchanged, vec![Hash::type_hash(["helper"])]);
+    /// assert_eq!(vm.call(["main"], ())?.into_integer()?, 42);
+    /// # Ok(()) }
+    /// ```
+    pub fn swap_unit(&mut self, unit: Arc<Unit>) -> Result<UnitDiff, VmError> {
+        if !self.call_frames.is_empty() {
+            return Err(VmError::from(VmErrorKind::SwapUnitWhileExecuting));
+        }
+
+        let diff = self.unit.diff(&unit);
+        self.unit = unit;
+
+        // NB: if no generation has been allocated, no functions have been
+        // created from the old unit which would need to be retired.
+        if let Some(generation) = self.generation.take() {
+            let next = UnitGeneration::default();
+            let retired = diff.changed.iter().chain(&diff.removed).copied().collect();
+            generation.retire(retired, next.clone());
+            self.generation = LazyGeneration::from_generation(next);
+        }
+
+        self.caches.clear();
+        self.ip = 0;
+        Ok(diff)
+    }
+
     /// Reset this virtual machine, freeing all memory used.
     pub fn clear(&mut self) {
         self.ip = 0;
@@ -234,6 +685,70 @@ impl Vm {
         self.lookup_function_by_hash(name.into_type_hash())
     }
 
+    /// Look up a function in the unit of the virtual machine once, returning
+    /// a handle which calls it with arguments of type `A` and converts its
+    /// output into `T`.
+    ///
+    /// Calling through the handle avoids resolving the function again on
+    /// every call, which matters when the same function is called many times.
+    ///
+    /// See [TypedFunction] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::TypedFunction;
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let context = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn add(a, b) {
+    ///             a + b
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let mut vm = Vm::new(context, Arc::new(unit));
+    ///
+    /// let add: TypedFunction<(i64, i64), i64> = vm.function(["add"])?;
+    ///
+    /// let mut sum = 0;
+    ///
+    /// for n in 0..10 {
+    ///     sum = add.call(&mut vm, (sum, n))?;
+    /// }
+    ///
+    /// assert_eq!(sum, 45);
+    /// # Ok(()) }
+    /// ```
+    pub fn function<A, T, N>(&self, name: N) -> Result<TypedFunction<A, T>, VmError>
+    where
+        N: IntoTypeHash,
+    {
+        let hash = name.into_type_hash();
+
+        let info = self.unit.function(hash).ok_or_else(|| {
+            if let Some(item) = name.into_item() {
+                VmError::from(VmErrorKind::MissingEntry { hash, item })
+            } else {
+                VmError::from(VmErrorKind::MissingEntryHash { hash })
+            }
+        })?;
+
+        match info {
+            UnitFn::Offset { offset, args, .. } => {
+                Ok(TypedFunction::new(self.unit.clone(), hash, offset, args))
+            }
+            _ => Err(VmError::from(VmErrorKind::MissingFunction { hash })),
+        }
+    }
+
     /// Run the given vm to completion.
     ///
     /// If any async instructions are encountered, this will error.
@@ -248,6 +763,13 @@ impl Vm {
         execution.async_complete().await
     }
 
+    /// Run the given vm to completion as a future which is being awaited by
+    /// another virtual machine.
+    pub(crate) async fn async_complete_awaited(self) -> Result<Value, VmError> {
+        let mut execution = VmExecution::new(self);
+        execution.async_complete_awaited().await
+    }
+
     /// Call the function identified by the given name.
     ///
     /// Computing the function hash from the name can be a bit costly, so it's
@@ -314,8 +836,10 @@ impl Vm {
         N: IntoTypeHash,
         A: Args,
     {
-        self.set_entrypoint(name, args.count())?;
+        let count = args.count();
+        let hash = self.set_entrypoint(name, count)?;
         args.into_stack(&mut self.stack)?;
+        self.trace_call(hash, count);
         Ok(VmExecution::new(self))
     }
 
@@ -325,6 +849,12 @@ impl Vm {
     /// This is accomplished by preventing values escaping from being
     /// non-exclusively sent with the execution or escaping the execution. We
     /// only support encoding arguments which themselves are `Send`.
+    ///
+    /// Ambient values are copied for the execution, so this errors if any of
+    /// them can't be sent to another thread. Collectors of reference cycles
+    /// are bound to the thread they were created on, so any collector which
+    /// has been installed is detached and cycles aren't tracked during the
+    /// execution.
     pub fn send_execute<A, N>(mut self, name: N, args: A) -> Result<VmSendExecution, VmError>
     where
         N: IntoTypeHash,
@@ -334,8 +864,21 @@ impl Vm {
         // being sent along with the virtual machine.
         self.stack.clear();
 
-        self.set_entrypoint(name, args.count())?;
+        // Safety: ambient values might be shared with the host, so they are
+        // rebuilt into ones which are exclusively owned by the execution.
+        if let Some(ambient) = self.inherited.ambient.take() {
+            let values = ambient.to_send()?;
+            self.inherited.ambient = Some(Rc::new(Ambient::from_send(values)));
+        }
+
+        // Safety: the collector is shared with the host and holds onto values
+        // created on this thread, so it can't be sent along.
+        self.inherited.cycles = None;
+
+        let count = args.count();
+        let hash = self.set_entrypoint(name, count)?;
         args.into_stack(&mut self.stack)?;
+        self.trace_call(hash, count);
         Ok(VmSendExecution(VmExecution::new(self)))
     }
 
@@ -358,18 +901,29 @@ impl Vm {
         N: IntoTypeHash,
         A: GuardedArgs,
     {
-        self.set_entrypoint(name, args.count())?;
+        let hash = self.set_entrypoint(name, args.count())?;
+        self.call_entry(hash, args)
+    }
+
+    /// Call the function which has been set up as the entrypoint.
+    pub(crate) fn call_entry<A>(&mut self, hash: Hash, args: A) -> Result<Value, VmError>
+    where
+        A: GuardedArgs,
+    {
+        let count = args.count();
 
         // Safety: We hold onto the guard until the vm has completed and
         // `VmExecution` will clear the stack before this function returns.
         // Erronously or not.
         let guard = unsafe { args.unsafe_into_stack(&mut self.stack)? };
+        self.trace_call(hash, count);
 
         let value = {
             // Clearing the stack here on panics has safety implications - see
             // above.
             let vm = ClearStack(self);
-            VmExecution::new(&mut *vm.0).complete()?
+            let mut execution = VmExecution::new(&mut *vm.0);
+            execution.complete()?
         };
 
         // Note: this might panic if something in the vm is holding on to a
@@ -399,18 +953,34 @@ impl Vm {
         N: IntoTypeHash,
         A: GuardedArgs,
     {
-        self.set_entrypoint(name, args.count())?;
+        let hash = self.set_entrypoint(name, args.count())?;
+        self.async_call_entry(hash, args).await
+    }
+
+    /// Asynchronously call the function which has been set up as the
+    /// entrypoint.
+    pub(crate) async fn async_call_entry<A>(
+        &mut self,
+        hash: Hash,
+        args: A,
+    ) -> Result<Value, VmError>
+    where
+        A: GuardedArgs,
+    {
+        let count = args.count();
 
         // Safety: We hold onto the guard until the vm has completed and
         // `VmExecution` will clear the stack before this function returns.
         // Erronously or not.
         let guard = unsafe { args.unsafe_into_stack(&mut self.stack)? };
+        self.trace_call(hash, count);
 
         let value = {
             // Clearing the stack here on panics has safety implications - see
             // above.
             let vm = ClearStack(self);
-            VmExecution::new(&mut *vm.0).async_complete().await?
+            let mut execution = VmExecution::new(&mut *vm.0);
+            execution.async_complete().await?
         };
 
         // Note: this might panic if something in the vm is holding on to a
@@ -422,7 +992,7 @@ impl Vm {
 
     /// Update the instruction pointer to match the function matching the given
     /// name and check that the number of argument matches.
-    fn set_entrypoint<N>(&mut self, name: N, count: usize) -> Result<(), VmError>
+    fn set_entrypoint<N>(&mut self, name: N, count: usize) -> Result<Hash, VmError>
     where
         N: IntoTypeHash,
     {
@@ -452,10 +1022,15 @@ impl Vm {
             }
         };
 
+        self.set_entry_offset(offset);
+        Ok(hash)
+    }
+
+    /// Set up the function at the given offset as the entrypoint.
+    pub(crate) fn set_entry_offset(&mut self, offset: usize) {
         self.ip = offset;
         self.stack.clear();
         self.call_frames.clear();
-        Ok(())
     }
 
     /// Helper function to call an instance function.
@@ -487,11 +1062,12 @@ impl Vm {
         }) = self.unit.function(hash)
         {
             Self::check_args(full_count, expected)?;
-            self.call_offset_fn(offset, call, full_count)?;
+            self.call_offset_fn(hash, offset, call, full_count)?;
             return Ok(CallResult::Ok(()));
         }
 
         if let Some(handler) = self.context.function(hash) {
+            self.enter_native(full_count);
             handler(&mut self.stack, full_count)?;
             return Ok(CallResult::Ok(()));
         }
@@ -523,6 +1099,7 @@ impl Vm {
         args.into_stack(&mut self.stack)?;
 
         if let Some(handler) = self.context.function(hash) {
+            self.enter_native(full_count);
             handler(&mut self.stack, full_count)?;
             return Ok(CallResult::Ok(()));
         }
@@ -553,6 +1130,7 @@ impl Vm {
         args.into_stack(&mut self.stack)?;
 
         if let Some(handler) = self.context.function(hash) {
+            self.enter_native(full_count);
             handler(&mut self.stack, full_count)?;
             return Ok(CallResult::Ok(()));
         }
@@ -567,6 +1145,7 @@ impl Vm {
         &mut self,
         int_op: fn(i64, i64) -> bool,
         float_op: fn(f64, f64) -> bool,
+        cmp_op: fn(Ordering) -> bool,
         op: &'static str,
         lhs: InstAddress,
         rhs: InstAddress,
@@ -577,13 +1156,10 @@ impl Vm {
         let out = match (lhs, rhs) {
             (Value::Integer(lhs), Value::Integer(rhs)) => int_op(lhs, rhs),
             (Value::Float(lhs), Value::Float(rhs)) => float_op(lhs, rhs),
-            (lhs, rhs) => {
-                return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                    op,
-                    lhs: lhs.type_info()?,
-                    rhs: rhs.type_info()?,
-                }))
-            }
+            (lhs, rhs) => match Value::partial_cmp_with(&lhs, &rhs, op)? {
+                Some(ordering) => cmp_op(ordering),
+                None => false,
+            },
         };
 
         self.stack.push(out);
@@ -595,6 +1171,11 @@ impl Vm {
     /// This will cause the `args` number of elements on the stack to be
     /// associated and accessible to the new call frame.
     pub(crate) fn push_call_frame(&mut self, ip: usize, args: usize) -> Result<(), VmError> {
+        let Usage { depth, stack } = self.inherited.outer;
+        self.inherited.limits.check_call(
+            depth.saturating_add(self.call_frames.len()),
+            stack.saturating_add(self.stack.len()),
+        )?;
         let stack_top = self.stack.swap_stack_bottom(args)?;
 
         self.call_frames.push(CallFrame {
@@ -898,6 +1479,7 @@ impl Vm {
         &mut self,
         target: Value,
         string_slot: usize,
+        cache: usize,
     ) -> Result<CallResult<Value>, VmError> {
         let index = self.unit.lookup_string(string_slot)?;
 
@@ -926,12 +1508,31 @@ impl Vm {
                 }
             }
             target => {
-                let hash = index.hash();
+                let type_hash = target.type_hash()?;
+
+                if let Some(InlineCache::Handler { handler, .. }) =
+                    self.caches.get(cache, type_hash)
+                {
+                    self.stack.push(target);
+                    self.enter_native(1);
+                    handler(&mut self.stack, 1)?;
+                    return Ok(CallResult::Ok(self.stack.pop()?));
+                }
 
-                return Ok(match self.call_field_fn(Protocol::GET, target, hash, ())? {
-                    CallResult::Ok(()) => CallResult::Ok(self.stack.pop()?),
-                    CallResult::Unsupported(target) => CallResult::Unsupported(target),
-                });
+                let hash = Hash::field_fn(Protocol::GET, type_hash, index.hash());
+
+                let handler = match self.context.function(hash) {
+                    Some(handler) => handler.clone(),
+                    None => return Ok(CallResult::Unsupported(target)),
+                };
+
+                self.stack.push(target);
+                self.enter_native(1);
+                handler(&mut self.stack, 1)?;
+
+                let entry = InlineCache::Handler { type_hash, handler };
+                self.caches.store(cache, self.unit.inline_caches(), entry);
+                return Ok(CallResult::Ok(self.stack.pop()?));
             }
         }
 
@@ -1085,6 +1686,7 @@ impl Vm {
     fn call_generator_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain(args)?.collect::<Stack>();
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.inherit(self);
         vm.ip = offset;
         self.stack.push(Generator::new(vm));
         Ok(())
@@ -1094,6 +1696,7 @@ impl Vm {
     fn call_stream_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain(args)?.collect::<Stack>();
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.inherit(self);
         vm.ip = offset;
         self.stack.push(Stream::new(vm));
         Ok(())
@@ -1103,18 +1706,22 @@ impl Vm {
     fn call_async_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain(args)?.collect::<Stack>();
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.inherit(self);
         vm.ip = offset;
-        self.stack.push(Future::new(vm.async_complete()));
+        self.stack.push(Future::new(vm.async_complete_awaited()));
         Ok(())
     }
 
     /// Helper function to call the function at the given offset.
     pub(crate) fn call_offset_fn(
         &mut self,
+        hash: Hash,
         offset: usize,
         call: Call,
         args: usize,
     ) -> Result<(), VmError> {
+        self.trace_call(hash, args);
+
         match call {
             Call::Async => {
                 self.call_async_fn(offset, args)?;
@@ -1386,7 +1993,7 @@ impl Vm {
     }
 
     /// Check that arguments matches expected or raise the appropriate error.
-    fn check_args(args: usize, expected: usize) -> Result<(), VmError> {
+    pub(crate) fn check_args(args: usize, expected: usize) -> Result<(), VmError> {
         if args != expected {
             return Err(VmError::from(VmErrorKind::BadArgumentCount {
                 actual: args,
@@ -1566,6 +2173,7 @@ impl Vm {
     /// Construct a new vec.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_vec(&mut self, count: usize) -> Result<(), VmError> {
+        self.charge(count * mem::size_of::<Value>())?;
         let vec = Vec::from(self.stack.pop_sequence(count)?);
         self.stack.push(Shared::new(vec));
         Ok(())
@@ -1574,6 +2182,7 @@ impl Vm {
     /// Construct a new tuple.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_tuple(&mut self, count: usize) -> Result<(), VmError> {
+        self.charge(count * mem::size_of::<Value>())?;
         let tuple = self.stack.pop_sequence(count)?;
         self.stack.push(Tuple::from(tuple));
         Ok(())
@@ -1582,6 +2191,7 @@ impl Vm {
     /// Construct a new tuple with a fixed number of arguments.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_tuple_n(&mut self, args: &[InstAddress]) -> Result<(), VmError> {
+        self.charge(args.len() * mem::size_of::<Value>())?;
         let mut tuple = vec![Value::Unit; args.len()];
 
         for (n, arg) in args.iter().enumerate().rev() {
@@ -1738,16 +2348,44 @@ impl Vm {
                 self.internal_infallible_bitwise(Protocol::SHR, std::ops::Shr::shr, lhs, rhs)?;
             }
             InstOp::Gt => {
-                self.internal_boolean_ops(|a, b| a > b, |a, b| a > b, ">", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a > b,
+                    |a, b| a > b,
+                    Ordering::is_gt,
+                    ">",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Gte => {
-                self.internal_boolean_ops(|a, b| a >= b, |a, b| a >= b, ">=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a >= b,
+                    |a, b| a >= b,
+                    Ordering::is_ge,
+                    ">=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lt => {
-                self.internal_boolean_ops(|a, b| a < b, |a, b| a < b, "<", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a < b,
+                    |a, b| a < b,
+                    Ordering::is_lt,
+                    "<",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lte => {
-                self.internal_boolean_ops(|a, b| a <= b, |a, b| a <= b, "<=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a <= b,
+                    |a, b| a <= b,
+                    Ordering::is_le,
+                    "<=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Eq => {
                 let rhs = self.stack.address(rhs)?;
@@ -1780,6 +2418,70 @@ impl Vm {
         Ok(())
     }
 
+    /// Perform an operation speculated to operate on integers, falling back to
+    /// the generic operation if it doesn't.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_int_op(
+        &mut self,
+        op: InstIntOp,
+        lhs: InstAddress,
+        rhs: InstAddress,
+    ) -> Result<(), VmError> {
+        // NB: if both operands are addressed from the top, the right-hand side
+        // is on top of the left-hand side.
+        let depth = usize::from(rhs == InstAddress::Top);
+
+        let (a, b) = match (self.peek_address(lhs, depth)?, self.peek_address(rhs, 0)?) {
+            (Value::Integer(a), Value::Integer(b)) => (*a, *b),
+            _ => return self.op_op(op.into_op(), lhs, rhs),
+        };
+
+        let value = int_op(op, a, b)?;
+        let top = usize::from(lhs == InstAddress::Top) + depth;
+        self.stack.popn(top)?;
+        self.stack.push(value);
+        Ok(())
+    }
+
+    /// Perform an operation with a constant integer, falling back to the
+    /// generic operation if the other operand isn't an integer.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_int_op_const(
+        &mut self,
+        op: InstIntOp,
+        lhs: InstAddress,
+        rhs: i64,
+    ) -> Result<(), VmError> {
+        let a = match self.peek_address(lhs, 0)? {
+            Value::Integer(a) => *a,
+            _ => {
+                // NB: the constant ends up on top of the stack, so it's
+                // addressed before the left-hand side.
+                self.stack.push(rhs);
+                return self.op_op(op.into_op(), lhs, InstAddress::Top);
+            }
+        };
+
+        let value = int_op(op, a, rhs)?;
+
+        if lhs == InstAddress::Top {
+            self.stack.pop()?;
+        }
+
+        self.stack.push(value);
+        Ok(())
+    }
+
+    /// Look at an addressed value without consuming it, where `depth` is the
+    /// number of values above it if it's addressed from the top.
+    #[inline]
+    fn peek_address(&self, address: InstAddress, depth: usize) -> Result<&Value, VmError> {
+        Ok(match address {
+            InstAddress::Top => self.stack.at_offset_from_top(depth + 1)?,
+            InstAddress::Offset(offset) => self.stack.at_offset(offset)?,
+        })
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_assign(&mut self, target: InstTarget, op: InstAssignOp) -> Result<(), VmError> {
         use std::convert::TryFrom as _;
@@ -1877,6 +2579,7 @@ impl Vm {
         let index = self.stack.pop()?;
         let target = self.stack.pop()?;
         let value = self.stack.pop()?;
+        self.track(&target);
 
         // This is a useful pattern.
         #[allow(clippy::never_loop)]
@@ -1890,6 +2593,10 @@ impl Vm {
                     local_field.as_str()
                 }
                 Value::StaticString(string) => string.as_ref(),
+                Value::Any(any) => match any.downcast_borrow_ref::<Symbol>() {
+                    Ok(symbol) => symbol.as_str(),
+                    Err(..) => break,
+                },
                 _ => break,
             };
 
@@ -1955,6 +2662,10 @@ impl Vm {
             self.stack.popn(clean)?;
         }
 
+        if let Some(Tracer(tracer)) = &self.inherited.tracer {
+            tracer.on_return(self, &return_value);
+        }
+
         let exit = self.pop_call_frame()?;
         self.stack.push(return_value);
         Ok(exit)
@@ -1968,6 +2679,10 @@ impl Vm {
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_return_unit(&mut self) -> Result<bool, VmError> {
+        if let Some(Tracer(tracer)) = &self.inherited.tracer {
+            tracer.on_return(self, &Value::Unit);
+        }
+
         let exit = self.pop_call_frame()?;
         self.stack.push(());
         Ok(exit)
@@ -2004,6 +2719,15 @@ impl Vm {
                     return Ok(());
                 }
             }
+            Value::Any(any) => {
+                if let Ok(symbol) = any.downcast_borrow_ref::<Symbol>() {
+                    if let Some(value) = Self::try_object_like_index_get(&target, symbol.as_str())?
+                    {
+                        self.stack.push(value);
+                        return Ok(());
+                    }
+                }
+            }
             Value::Integer(index) => {
                 use std::convert::TryInto as _;
 
@@ -2066,6 +2790,7 @@ impl Vm {
     fn op_tuple_index_set(&mut self, index: usize) -> Result<(), VmError> {
         let tuple = self.stack.pop()?;
         let value = self.stack.pop()?;
+        self.track(&tuple);
 
         if Self::try_tuple_like_index_set(&tuple, index, value)? {
             return Ok(());
@@ -2113,10 +2838,10 @@ impl Vm {
 
     /// Perform a specialized index get operation on an object.
     #[cfg_attr(feature = "bench", inline(never))]
-    fn op_object_index_get(&mut self, string_slot: usize) -> Result<(), VmError> {
+    fn op_object_index_get(&mut self, string_slot: usize, cache: usize) -> Result<(), VmError> {
         let target = self.stack.pop()?;
 
-        match self.try_object_slot_index_get(target, string_slot)? {
+        match self.try_object_slot_index_get(target, string_slot, cache)? {
             CallResult::Ok(value) => {
                 self.stack.push(value);
                 Ok(())
@@ -2134,6 +2859,7 @@ impl Vm {
     fn op_object_index_set(&mut self, string_slot: usize) -> Result<(), VmError> {
         let target = self.stack.pop()?;
         let value = self.stack.pop()?;
+        self.track(&target);
 
         if let CallResult::Unsupported(target) =
             self.try_object_slot_index_set(target, string_slot, value)?
@@ -2148,10 +2874,15 @@ impl Vm {
 
     /// Perform a specialized index get operation on an object.
     #[cfg_attr(feature = "bench", inline(never))]
-    fn op_object_index_get_at(&mut self, offset: usize, string_slot: usize) -> Result<(), VmError> {
+    fn op_object_index_get_at(
+        &mut self,
+        offset: usize,
+        string_slot: usize,
+        cache: usize,
+    ) -> Result<(), VmError> {
         let target = self.stack.at_offset(offset)?.clone();
 
-        match self.try_object_slot_index_get(target, string_slot)? {
+        match self.try_object_slot_index_get(target, string_slot, cache)? {
             CallResult::Ok(value) => {
                 self.stack.push(value);
                 Ok(())
@@ -2172,6 +2903,8 @@ impl Vm {
             .lookup_object_keys(slot)
             .ok_or(VmErrorKind::MissingStaticObjectKeys { slot })?;
 
+        self.charge(object_size(keys))?;
+
         let mut object = Object::with_capacity(keys.len());
         let values = self.stack.drain(keys.len())?;
 
@@ -2224,6 +2957,8 @@ impl Vm {
             .lookup_rtti(hash)
             .ok_or(VmErrorKind::MissingRtti { hash })?;
 
+        self.charge(object_size(keys))?;
+
         let values = self.stack.drain(keys.len())?;
         let mut data = Object::with_capacity(keys.len());
 
@@ -2264,6 +2999,8 @@ impl Vm {
             .lookup_variant_rtti(hash)
             .ok_or(VmErrorKind::MissingVariantRtti { hash })?;
 
+        self.charge(object_size(keys))?;
+
         let mut data = Object::with_capacity(keys.len());
         let values = self.stack.drain(keys.len())?;
 
@@ -2284,7 +3021,9 @@ impl Vm {
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_bytes(&mut self, slot: usize) -> Result<(), VmError> {
-        let bytes = self.unit.lookup_bytes(slot)?.to_owned();
+        let bytes = self.unit.lookup_bytes(slot)?;
+        self.charge(bytes.len())?;
+        let bytes = bytes.to_owned();
         self.stack.push(Bytes::from_vec(bytes));
         Ok(())
     }
@@ -2305,6 +3044,7 @@ impl Vm {
             }
         }
 
+        self.charge(out.len())?;
         self.stack.push(out);
         Ok(())
     }
@@ -2610,6 +3350,7 @@ impl Vm {
                 UnitFn::Offset { offset, call, args } => Function::from_vm_offset(
                     self.context.clone(),
                     self.unit.clone(),
+                    self.generation.get(),
                     offset,
                     call,
                     args,
@@ -2677,6 +3418,7 @@ impl Vm {
         let function = Function::from_vm_closure(
             self.context.clone(),
             self.unit.clone(),
+            self.generation.get(),
             offset,
             call,
             args,
@@ -2699,7 +3441,7 @@ impl Vm {
                     args: expected,
                 } => {
                     Self::check_args(args, expected)?;
-                    self.call_offset_fn(offset, call, args)?;
+                    self.call_offset_fn(hash, offset, call, args)?;
                 }
                 UnitFn::UnitStruct { hash } => {
                     Self::check_args(args, 0)?;
@@ -2756,6 +3498,7 @@ impl Vm {
                     .function(hash)
                     .ok_or(VmErrorKind::MissingFunction { hash })?;
 
+                self.enter_native(args);
                 handler(&mut self.stack, args)?;
             }
         }
@@ -2764,11 +3507,31 @@ impl Vm {
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
-    fn op_call_instance(&mut self, hash: Hash, args: usize) -> Result<(), VmError> {
+    fn op_call_instance(&mut self, hash: Hash, args: usize, cache: usize) -> Result<(), VmError> {
         // NB: +1 to include the instance itself.
         let args = args + 1;
-        let instance = self.stack.at_offset_from_top(args)?;
-        let type_hash = instance.type_hash()?;
+        let type_hash = self.stack.at_offset_from_top(args)?.type_hash()?;
+
+        match self.caches.get(cache, type_hash) {
+            Some(&InlineCache::Offset {
+                hash,
+                offset,
+                call,
+                args: expected,
+                ..
+            }) => {
+                Self::check_args(args, expected)?;
+                self.call_offset_fn(hash, offset, call, args)?;
+                return Ok(());
+            }
+            Some(InlineCache::Handler { handler, .. }) => {
+                self.enter_native(args);
+                handler(&mut self.stack, args)?;
+                return Ok(());
+            }
+            _ => (),
+        }
+
         let hash = Hash::instance_function(type_hash, hash);
 
         if let Some(UnitFn::Offset {
@@ -2777,18 +3540,32 @@ impl Vm {
             args: expected,
         }) = self.unit.function(hash)
         {
+            let entry = InlineCache::Offset {
+                type_hash,
+                hash,
+                offset,
+                call,
+                args: expected,
+            };
+
+            self.caches.store(cache, self.unit.inline_caches(), entry);
             Self::check_args(args, expected)?;
-            self.call_offset_fn(offset, call, args)?;
+            self.call_offset_fn(hash, offset, call, args)?;
             return Ok(());
         }
 
         if let Some(handler) = self.context.function(hash) {
+            let handler = handler.clone();
+            self.enter_native(args);
             handler(&mut self.stack, args)?;
+
+            let entry = InlineCache::Handler { type_hash, handler };
+            self.caches.store(cache, self.unit.inline_caches(), entry);
             return Ok(());
         }
 
         Err(VmError::from(VmErrorKind::MissingInstanceFunction {
-            instance: instance.type_info()?,
+            instance: self.stack.at_offset_from_top(args)?.type_info()?,
             hash,
         }))
     }
@@ -2878,7 +3655,7 @@ impl Vm {
     where
         F: FnOnce() -> T,
     {
-        let _guard = crate::runtime::env::Guard::new(&self.context, &self.unit);
+        let _guard = crate::runtime::env::Guard::new(&self.context, &self.unit, &self.inherited);
         f()
     }
 
@@ -2886,7 +3663,7 @@ impl Vm {
     pub(crate) fn run(&mut self) -> Result<VmHalt, VmError> {
         // NB: set up environment so that native function can access context and
         // unit.
-        let _guard = crate::runtime::env::Guard::new(&self.context, &self.unit);
+        let _guard = crate::runtime::env::Guard::new(&self.context, &self.unit, &self.inherited);
 
         loop {
             if !budget::take() {
@@ -2900,6 +3677,10 @@ impl Vm {
 
             tracing::trace!("{}: {}", self.ip, inst);
 
+            if let Some(profiler) = &self.inherited.profiler {
+                profiler.tick(self);
+            }
+
             match inst {
                 Inst::Not => {
                     self.op_not()?;
@@ -2913,8 +3694,8 @@ impl Vm {
                 Inst::Call { hash, args } => {
                     self.op_call(hash, args)?;
                 }
-                Inst::CallInstance { hash, args } => {
-                    self.op_call_instance(hash, args)?;
+                Inst::CallInstance { hash, args, cache } => {
+                    self.op_call_instance(hash, args, cache)?;
                 }
                 Inst::CallFn { args } => {
                     if let Some(reason) = self.op_call_fn(args)? {
@@ -2936,14 +3717,18 @@ impl Vm {
                 Inst::TupleIndexGetAt { offset, index } => {
                     self.op_tuple_index_get_at(offset, index)?;
                 }
-                Inst::ObjectIndexGet { slot } => {
-                    self.op_object_index_get(slot)?;
+                Inst::ObjectIndexGet { slot, cache } => {
+                    self.op_object_index_get(slot, cache)?;
                 }
                 Inst::ObjectIndexSet { slot } => {
                     self.op_object_index_set(slot)?;
                 }
-                Inst::ObjectIndexGetAt { offset, slot } => {
-                    self.op_object_index_get_at(offset, slot)?;
+                Inst::ObjectIndexGetAt {
+                    offset,
+                    slot,
+                    cache,
+                } => {
+                    self.op_object_index_get_at(offset, slot, cache)?;
                 }
                 Inst::IndexSet => {
                     self.op_index_set()?;
@@ -2962,11 +3747,20 @@ impl Vm {
                 }
                 Inst::Await => {
                     let future = self.op_await()?;
+
+                    if let Some(Tracer(tracer)) = &self.inherited.tracer {
+                        tracer.on_await(self);
+                    }
+
                     // NB: the future itself will advance the virtual machine.
                     return Ok(VmHalt::Awaited(Awaited::Future(future)));
                 }
                 Inst::Select { len } => {
                     if let Some(select) = self.op_select(len)? {
+                        if let Some(Tracer(tracer)) = &self.inherited.tracer {
+                            tracer.on_await(self);
+                        }
+
                         // NB: the future itself will advance the virtual machine.
                         return Ok(VmHalt::Awaited(Awaited::Select(select)));
                     }
@@ -3125,10 +3919,18 @@ impl Vm {
                     self.op_match_object(slot, exact)?;
                 }
                 Inst::Yield => {
+                    if let Some(Tracer(tracer)) = &self.inherited.tracer {
+                        tracer.on_yield(self, self.stack.last()?);
+                    }
+
                     self.advance();
                     return Ok(VmHalt::Yielded);
                 }
                 Inst::YieldUnit => {
+                    if let Some(Tracer(tracer)) = &self.inherited.tracer {
+                        tracer.on_yield(self, &Value::Unit);
+                    }
+
                     self.advance();
                     self.stack.push(Value::Unit);
                     return Ok(VmHalt::Yielded);
@@ -3139,6 +3941,12 @@ impl Vm {
                 Inst::Op { op, a, b } => {
                     self.op_op(op, a, b)?;
                 }
+                Inst::IntOp { op, a, b } => {
+                    self.op_int_op(op, a, b)?;
+                }
+                Inst::IntOpConst { op, a, b } => {
+                    self.op_int_op_const(op, a, b)?;
+                }
                 Inst::Assign { target, op } => {
                     self.op_assign(target, op)?;
                 }
@@ -3152,6 +3960,9 @@ impl Vm {
                 }
             }
 
+            self.inherited
+                .limits
+                .check_stack_size(self.inherited.outer.stack.saturating_add(self.stack.len()))?;
             self.advance();
         }
     }
@@ -3172,7 +3983,7 @@ impl AsRef<Vm> for Vm {
 /// A call frame.
 ///
 /// This is used to store the return point after an instruction has been run.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct CallFrame {
     /// The stored instruction pointer.
     ip: usize,
@@ -0,0 +1,166 @@
+//! Backtraces captured when a virtual machine errors.
+
+use crate::ast::Span;
+use crate::runtime::debug::DebugSignature;
+use crate::runtime::Vm;
+use crate::{Hash, SourceId};
+use std::fmt;
+
+/// The call stack of a [Vm] at the point where a [VmError][crate::runtime::VmError]
+/// was raised.
+///
+/// Function names, source locations and variable names are only available if
+/// the unit was compiled with debug info.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> rune::Result<()> {
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn inner(value) {
+///             let doubled = value * 2;
+///             doubled + ()
+///         }
+///
+///         pub fn main() {
+///             inner(21)
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+///
+/// let error = vm.call(["main"], ()).unwrap_err();
+/// let backtrace = error.backtrace().expect("missing backtrace");
+///
+/// let names = backtrace
+///     .frames()
+///     .iter()
+///     .map(|f| f.function.as_ref().map(|(_, s)| s.path.to_string()))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(names, [Some(String::from("inner")), Some(String::from("main"))]);
+///
+/// let inner = &backtrace.frames()[0];
+/// assert!(inner.variables.iter().any(|v| v.as_deref() == Some("value")));
+/// assert!(inner.variables.iter().any(|v| v.as_deref() == Some("doubled")));
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct VmBacktrace {
+    frames: Vec<BacktraceFrame>,
+}
+
+impl VmBacktrace {
+    /// Capture the call stack of the given virtual machine.
+    pub(crate) fn capture(vm: &Vm) -> Self {
+        let debug = vm.unit().debug_info();
+        let stack = vm.stack();
+        let call_frames = vm.call_frames();
+
+        let mut frames = Vec::with_capacity(call_frames.len() + 1);
+        let mut top = stack.len();
+
+        let current = std::iter::once((vm.ip(), stack.stack_bottom()));
+        // NB: each call frame stores the instruction pointer and stack bottom
+        // of its caller.
+        let callers = call_frames
+            .iter()
+            .rev()
+            .map(|frame| (frame.ip(), frame.stack_bottom()));
+
+        for (ip, bottom) in current.chain(callers) {
+            let len = top.saturating_sub(bottom);
+            top = bottom;
+
+            let frame = match debug {
+                Some(debug) => BacktraceFrame {
+                    ip,
+                    function: debug
+                        .function_containing(ip)
+                        .map(|(hash, signature)| (hash, signature.clone())),
+                    location: debug
+                        .instruction_at(ip)
+                        .map(|inst| (inst.source_id, inst.span)),
+                    variables: (0..len)
+                        .map(|offset| debug.variable_at(ip, offset).map(Box::from))
+                        .collect(),
+                },
+                None => BacktraceFrame {
+                    ip,
+                    function: None,
+                    location: None,
+                    variables: vec![None; len],
+                },
+            };
+
+            frames.push(frame);
+        }
+
+        Self { frames }
+    }
+
+    /// The frames of the backtrace, starting with the innermost one where the
+    /// error was raised.
+    pub fn frames(&self) -> &[BacktraceFrame] {
+        &self.frames
+    }
+}
+
+impl fmt::Display for VmBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, frame) in self.frames.iter().enumerate() {
+            write!(f, "{:>4}: ", n)?;
+
+            match &frame.function {
+                Some((_, signature)) => write!(f, "{}", signature)?,
+                None => write!(f, "<unknown>")?,
+            }
+
+            write!(f, " (at inst {})", frame.ip)?;
+
+            if let Some((source_id, span)) = &frame.location {
+                write!(f, " in source {} at {}", source_id, span)?;
+            }
+
+            writeln!(f)?;
+
+            let names = frame.variables.iter().flatten().collect::<Vec<_>>();
+
+            if !names.is_empty() {
+                write!(f, "        locals:")?;
+
+                for name in names {
+                    write!(f, " {}", name)?;
+                }
+
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A single frame in a [VmBacktrace].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BacktraceFrame {
+    /// The instruction pointer of the frame.
+    pub ip: usize,
+    /// The function the frame belongs to.
+    pub function: Option<(Hash, DebugSignature)>,
+    /// The source location of the instruction being executed in the frame.
+    pub location: Option<(SourceId, Span)>,
+    /// The names of the values on the stack of the frame, indexed by their
+    /// offset. Anonymous values, like temporaries, don't have a name.
+    pub variables: Vec<Option<Box<str>>>,
+}
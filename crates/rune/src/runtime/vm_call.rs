@@ -19,7 +19,7 @@ impl VmCall {
         T: AsMut<Vm>,
     {
         let value = match self.call {
-            Call::Async => Value::from(Future::new(self.vm.async_complete())),
+            Call::Async => Value::from(Future::new(self.vm.async_complete_awaited())),
             Call::Immediate => {
                 execution.push_vm(self.vm);
                 return Ok(());
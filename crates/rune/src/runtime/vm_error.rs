@@ -1,8 +1,8 @@
 use crate::compile::ItemBuf;
 use crate::runtime::panic::BoxedPanic;
 use crate::runtime::{
-    AccessError, CallFrame, ExecutionState, Key, Panic, Protocol, StackError, TypeInfo, TypeOf,
-    Unit, Value, VmHaltInfo,
+    AccessError, CallFrame, ExecutionState, Panic, Protocol, StackError, TypeInfo, TypeOf, Unit,
+    Value, Vm, VmBacktrace, VmHaltInfo,
 };
 use crate::Hash;
 use std::fmt;
@@ -65,20 +65,31 @@ impl VmError {
         *self.kind
     }
 
-    /// Convert into an unwinded vm error.
-    pub(crate) fn into_unwinded(self, unit: &Arc<Unit>, ip: usize, frames: Vec<CallFrame>) -> Self {
+    /// Convert into an unwinded vm error, capturing the state of the given
+    /// virtual machine.
+    pub(crate) fn into_unwinded(self, vm: &Vm) -> Self {
         if let VmErrorKind::Unwound { .. } = &*self.kind {
             return self;
         }
 
         Self::from(VmErrorKind::Unwound {
             kind: self.kind,
-            unit: unit.clone(),
-            ip,
-            frames,
+            unit: vm.unit().clone(),
+            ip: vm.ip(),
+            frames: vm.call_frames().to_vec(),
+            backtrace: VmBacktrace::capture(vm),
         })
     }
 
+    /// Access the backtrace captured when the error was raised, if the error
+    /// was raised by a virtual machine.
+    pub fn backtrace(&self) -> Option<&VmBacktrace> {
+        match &*self.kind {
+            VmErrorKind::Unwound { backtrace, .. } => Some(backtrace),
+            _ => None,
+        }
+    }
+
     /// Unpack an unwinded error, if it is present.
     pub fn as_unwound(&self) -> (&VmErrorKind, Option<(&Arc<Unit>, usize, &[CallFrame])>) {
         match &*self.kind {
@@ -87,6 +98,7 @@ impl VmError {
                 unit,
                 ip,
                 frames,
+                ..
             } => (kind, Some((unit, *ip, frames))),
             kind => (kind, None),
         }
@@ -100,6 +112,7 @@ impl VmError {
                 unit,
                 ip,
                 frames,
+                ..
             } => {
                 let error = Self { kind };
                 (error, Some((unit, ip, frames)))
@@ -108,6 +121,116 @@ impl VmError {
         }
     }
 
+    /// Access the reason of the panic which caused this error, if it was
+    /// caused by a panic.
+    ///
+    /// This allows a host to treat script panics differently from other
+    /// errors, like converting them into a value instead of aborting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources!(entry => {
+    ///     pub fn main() { panic("oh no") }
+    /// });
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    ///
+    /// let error = vm.call(["main"], ()).unwrap_err();
+    /// assert_eq!(error.as_panic().map(|p| p.to_string()), Some(String::from("oh no")));
+    /// # Ok(()) }
+    /// ```
+    pub fn as_panic(&self) -> Option<&Panic> {
+        match self.as_unwound().0 {
+            VmErrorKind::Panic { reason } => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Construct an error which wraps an error raised by the host, like a
+    /// native function.
+    ///
+    /// The wrapped error is preserved as-is, including any context attached
+    /// to it through [anyhow], so that it can be recovered with
+    /// [VmError::downcast_ref] once it reaches the host again.
+    pub fn host<E>(error: E) -> Self
+    where
+        anyhow::Error: From<E>,
+    {
+        Self::from(VmErrorKind::Host {
+            error: anyhow::Error::from(error),
+        })
+    }
+
+    /// Access the host error which caused this error, if it was caused by
+    /// one.
+    pub fn as_host(&self) -> Option<&anyhow::Error> {
+        match self.as_unwound().0 {
+            VmErrorKind::Host { error } => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Attempt to downcast the host error which caused this error into a
+    /// concrete error type.
+    ///
+    /// This looks through any context which has been attached to the host
+    /// error, so the original error raised by a native function can be
+    /// recovered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Module, Vm};
+    /// use rune::runtime::VmError;
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Debug, thiserror::Error)]
+    /// #[error("the answer is not {0}")]
+    /// struct WrongAnswer(i64);
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = Module::new();
+    ///
+    /// module.function(["check"], |n: i64| {
+    ///     if n != 42 {
+    ///         return Err(VmError::host(WrongAnswer(n)));
+    ///     }
+    ///
+    ///     Ok(n)
+    /// })?;
+    ///
+    /// let mut context = Context::with_default_modules()?;
+    /// context.install(module)?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources!(entry => {
+    ///     pub fn main() { check(41) }
+    /// });
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    ///
+    /// let error = vm.call(["main"], ()).unwrap_err();
+    /// let wrong = error.downcast_ref::<WrongAnswer>().expect("host error");
+    /// assert_eq!(wrong.0, 41);
+    /// # Ok(()) }
+    /// ```
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: 'static + fmt::Display + fmt::Debug + Send + Sync,
+    {
+        self.as_host()?.downcast_ref::<E>()
+    }
+
     /// Unsmuggles the vm error, returning Ok(Self) in case the error is
     /// critical and should be propagated unaltered.
     pub(crate) fn unpack_critical(self) -> Result<Self, Self> {
@@ -124,6 +247,7 @@ impl VmError {
     fn is_critical(&self) -> bool {
         match &*self.kind {
             VmErrorKind::Panic { .. } => true,
+            VmErrorKind::Host { .. } => true,
             VmErrorKind::Unwound { .. } => true,
             _ => false,
         }
@@ -141,6 +265,12 @@ where
     }
 }
 
+impl From<anyhow::Error> for VmErrorKind {
+    fn from(error: anyhow::Error) -> Self {
+        Self::Host { error }
+    }
+}
+
 /// The kind of error encountered.
 #[allow(missing_docs)]
 #[derive(Debug, Error)]
@@ -159,6 +289,8 @@ pub enum VmErrorKind {
         ip: usize,
         /// All lower call frames before the unwind trigger point
         frames: Vec<CallFrame>,
+        /// The backtrace captured at the unwind trigger point.
+        backtrace: VmBacktrace,
     },
     #[error("{error}")]
     AccessError {
@@ -167,12 +299,16 @@ pub enum VmErrorKind {
     },
     #[error("panicked: {reason}")]
     Panic { reason: Panic },
+    #[error("{error}")]
+    Host { error: anyhow::Error },
     #[error("no running virtual machines")]
     NoRunningVm,
     #[error("halted for unexpected reason `{halt}`")]
     Halted { halt: VmHaltInfo },
     #[error("failed to format argument")]
     FormatError,
+    #[error("serde error: {message}")]
+    Serde { message: Box<str> },
     #[error("stack error: {error}")]
     StackError {
         #[from]
@@ -192,6 +328,12 @@ pub enum VmErrorKind {
     MissingEntryHash { hash: Hash },
     #[error("missing function with hash `{hash}`")]
     MissingFunction { hash: Hash },
+    #[error("function with hash `{hash}` belongs to a different unit")]
+    ForeignFunction { hash: Hash },
+    #[error("function with hash `{hash}` belongs to a unit which has been replaced")]
+    StaleFunction { hash: Hash },
+    #[error("missing capability `{capability}` required to call function with hash `{hash}`")]
+    MissingCapability { capability: Box<str>, hash: Hash },
     #[error("missing instance function `{hash}` for `{instance}`")]
     MissingInstanceFunction { hash: Hash, instance: TypeInfo },
     #[error("instruction pointer is out-of-bounds")]
@@ -263,8 +405,8 @@ pub enum VmErrorKind {
         target: TypeInfo,
         index: VmIntegerRepr,
     },
-    #[error("`{target}` missing index `{index:?}`")]
-    MissingIndexKey { target: TypeInfo, index: Key },
+    #[error("`{target}` missing index `{index}`")]
+    MissingIndexKey { target: TypeInfo, index: String },
     #[error("index out of bounds: the len is ${len} but the index is {index}")]
     OutOfRange {
         index: VmIntegerRepr,
@@ -321,8 +463,16 @@ pub enum VmErrorKind {
     ExpectedVariant { actual: TypeInfo },
     #[error("{actual} can't be converted to a constant value")]
     ConstNotSupported { actual: TypeInfo },
+    #[error("{actual} can't be sent across threads")]
+    SendNotSupported { actual: TypeInfo },
+    #[error("{actual} can't be cloned")]
+    CloneNotSupported { actual: TypeInfo },
     #[error("{actual} can't be converted to a hash key")]
     KeyNotSupported { actual: TypeInfo },
+    #[error("no executor is available to spawn tasks on")]
+    MissingExecutor,
+    #[error("spawned task was cancelled before it completed")]
+    TaskCancelled,
     #[error("missing interface environment")]
     MissingInterfaceEnvironment,
     #[error("index out of bounds")]
@@ -336,6 +486,18 @@ pub enum VmErrorKind {
     },
     #[error("future already completed")]
     FutureCompleted,
+    #[error("cannot swap the unit of a virtual machine which is executing")]
+    SwapUnitWhileExecuting,
+    #[error("call depth exceeded the maximum of {max}")]
+    CallDepthExceeded { max: usize },
+    #[error("stack size exceeded the maximum of {max}")]
+    StackSizeExceeded { max: usize },
+    #[error("instruction allocation budget exceeded the maximum of {max} bytes")]
+    InstructionAllocationExceeded { max: usize },
+    #[error("execution can't be snapshotted while calling into another unit or awaiting a task")]
+    SnapshotNotSupported,
+    #[error("snapshot doesn't match the unit it is being restored with")]
+    SnapshotMismatch,
 }
 
 impl VmErrorKind {
@@ -347,6 +509,7 @@ impl VmErrorKind {
                 unit,
                 ip,
                 frames,
+                ..
             } => (kind, Some((unit.clone(), *ip, frames.clone()))),
             kind => (kind, None),
         }
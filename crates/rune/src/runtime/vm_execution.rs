@@ -1,17 +1,22 @@
 use crate::runtime::budget;
+use crate::runtime::cycles;
+use crate::runtime::VmSnapshot;
 use crate::runtime::{
-    Generator, GeneratorState, Stream, Value, Vm, VmError, VmErrorKind, VmHalt, VmHaltInfo,
+    Awaited, FromValue, Generator, GeneratorState, Stream, Value, Vm, VmError, VmErrorKind, VmHalt,
+    VmHaltInfo,
 };
 use crate::shared::AssertSend;
+use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::future::Future;
+use std::future::{self, Future};
 use std::mem::take;
+use std::task::Poll;
 
 /// The state of an execution. We keep track of this because it's important to
 /// correctly interact with functions that yield (like generators and streams)
 /// by initially just calling the function, then by providing a value pushed
 /// onto the stack.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum ExecutionState {
     /// The initial state of an execution.
@@ -19,6 +24,9 @@ pub enum ExecutionState {
     /// The resumed state of an execution. This expects a value to be pushed
     /// onto the virtual machine before it is continued.
     Resumed,
+    /// The execution ran out of fuel. It can be continued without providing a
+    /// value.
+    Exhausted,
 }
 
 impl fmt::Display for ExecutionState {
@@ -26,14 +34,39 @@ impl fmt::Display for ExecutionState {
         match self {
             ExecutionState::Initial => write!(f, "initial"),
             ExecutionState::Resumed => write!(f, "resumed"),
+            ExecutionState::Exhausted => write!(f, "exhausted"),
         }
     }
 }
 
-/// The execution environment for a virtual machine.
+/// The outcome of resuming an execution with a limited amount of fuel.
 ///
-/// When an execution is dropped, the stack of the stack of the head machine
-/// will be cleared.
+/// See [VmExecution::resume_with_fuel].
+#[derive(Debug)]
+pub enum FuelState {
+    /// The execution yielded the given value.
+    Yielded(Value),
+    /// The execution completed with the given value.
+    Complete(Value),
+    /// The execution ran out of fuel before it could yield or complete.
+    Exhausted,
+}
+
+impl FuelState {
+    /// Convert into a generator state, erroring if the execution ran out of
+    /// fuel.
+    fn into_generator_state(self) -> Result<GeneratorState, VmError> {
+        match self {
+            FuelState::Yielded(value) => Ok(GeneratorState::Yielded(value)),
+            FuelState::Complete(value) => Ok(GeneratorState::Complete(value)),
+            FuelState::Exhausted => Err(VmError::from(VmErrorKind::Halted {
+                halt: VmHaltInfo::Limited,
+            })),
+        }
+    }
+}
+
+/// The execution environment for a virtual machine.
 pub struct VmExecution<T = Vm>
 where
     T: AsMut<Vm>,
@@ -45,6 +78,11 @@ where
     /// The current stack of virtual machines and the execution state that must
     /// be restored once one is popped.
     vms: Vec<(Vm, ExecutionState)>,
+    /// A task which was being awaited when the execution ran out of fuel.
+    awaited: Option<Awaited>,
+    /// Keeps the values tracked by the cycle collector of the head machine
+    /// around while the execution is running.
+    cycles: Option<cycles::Execution>,
 }
 
 macro_rules! vm {
@@ -71,10 +109,22 @@ where
 {
     /// Construct an execution from a virtual machine.
     pub(crate) fn new(head: T) -> Self {
+        Self::with_state(head, ExecutionState::Initial)
+    }
+
+    /// Construct an execution from a virtual machine in the given state.
+    pub(crate) fn with_state(mut head: T, state: ExecutionState) -> Self {
+        let cycles = head
+            .as_mut()
+            .cycle_collector()
+            .map(|collector| collector.enter());
+
         Self {
             head,
             vms: vec![],
-            state: ExecutionState::Initial,
+            state,
+            awaited: None,
+            cycles,
         }
     }
 
@@ -164,6 +214,38 @@ where
         vm_mut!(self)
     }
 
+    /// Take a serializable snapshot of the suspended execution, which can be
+    /// restored through [VmSnapshot::restore].
+    ///
+    /// This errors with [VmErrorKind::SnapshotNotSupported] if the execution
+    /// is calling into another unit or awaiting a task, and with
+    /// [VmErrorKind::SendNotSupported] if any value on the stack can't be
+    /// represented in a snapshot.
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmError>
+    where
+        T: AsRef<Vm>,
+    {
+        if !self.vms.is_empty() || self.awaited.is_some() {
+            return Err(VmError::from(VmErrorKind::SnapshotNotSupported));
+        }
+
+        VmSnapshot::new(self.head.as_ref(), self.state)
+    }
+
+    /// The total number of call frames across all virtual machines in this
+    /// execution.
+    pub(crate) fn call_depth(&self) -> usize
+    where
+        T: AsRef<Vm>,
+    {
+        self.head.as_ref().call_frames().len()
+            + self
+                .vms
+                .iter()
+                .map(|(vm, _)| vm.call_frames().len() + 1)
+                .sum::<usize>()
+    }
+
     /// Complete the current execution without support for async instructions.
     ///
     /// This will error if the execution is suspended through yielding.
@@ -209,24 +291,77 @@ where
     /// If the function being executed is a generator or stream this will resume
     /// it while returning a unit from the current `yield`.
     pub async fn async_resume(&mut self) -> Result<GeneratorState, VmError> {
-        if matches!(self.state, ExecutionState::Resumed) {
-            vm_mut!(self).stack_mut().push(Value::Unit);
-        } else {
-            self.state = ExecutionState::Resumed;
-        }
-
+        self.prepare_resume();
         self.inner_async_resume().await
     }
 
+    /// Resume the current execution with support for async instructions,
+    /// executing at most `fuel` instructions.
+    ///
+    /// If the fuel runs out, [FuelState::Exhausted] is returned and the
+    /// execution can be continued by calling this function again. This allows
+    /// for cooperatively time-slicing many executions on a single thread.
+    pub async fn async_resume_with_fuel(&mut self, fuel: usize) -> Result<FuelState, VmError> {
+        self.prepare_resume();
+        budget::with(fuel, self.inner_async_resume_fuel()).await
+    }
+
     async fn inner_async_resume(&mut self) -> Result<GeneratorState, VmError> {
+        self.inner_async_resume_fuel().await?.into_generator_state()
+    }
+
+    /// Complete the current execution as a future which is being awaited by
+    /// another execution.
+    ///
+    /// If the awaiting execution runs out of fuel while this is executing, this
+    /// yields so that the awaiting execution can be suspended.
+    pub(crate) async fn async_complete_awaited(&mut self) -> Result<Value, VmError> {
+        self.prepare_resume();
+
         loop {
+            match self.inner_async_resume_fuel().await? {
+                FuelState::Complete(value) => return Ok(value),
+                FuelState::Yielded(..) => {
+                    return Err(VmError::from(VmErrorKind::Halted {
+                        halt: VmHaltInfo::Yielded,
+                    }))
+                }
+                FuelState::Exhausted => {
+                    yield_now().await;
+                    self.prepare_resume();
+                }
+            }
+        }
+    }
+
+    async fn inner_async_resume_fuel(&mut self) -> Result<FuelState, VmError> {
+        loop {
+            if let Some(mut awaited) = self.awaited.take() {
+                let vm = vm_mut!(self);
+
+                let completed = future::poll_fn(|cx| match awaited.poll_into_vm(vm, cx) {
+                    Poll::Ready(result) => Poll::Ready(result.map(|()| true)),
+                    // NB: the awaited task is an execution which ran out of
+                    // the fuel shared with this one.
+                    Poll::Pending if budget::exhausted() => Poll::Ready(Ok(false)),
+                    Poll::Pending => Poll::Pending,
+                })
+                .await?;
+
+                if !completed {
+                    self.awaited = Some(awaited);
+                    self.state = ExecutionState::Exhausted;
+                    return Ok(FuelState::Exhausted);
+                }
+            }
+
             let len = self.vms.len();
             let vm = vm_mut!(self);
 
             match Self::run(vm)? {
                 VmHalt::Exited => (),
                 VmHalt::Awaited(awaited) => {
-                    awaited.into_vm(vm).await?;
+                    self.awaited = Some(awaited);
                     continue;
                 }
                 VmHalt::VmCall(vm_call) => {
@@ -235,18 +370,17 @@ where
                 }
                 VmHalt::Yielded => {
                     let value = vm.stack_mut().pop()?;
-                    return Ok(GeneratorState::Yielded(value));
+                    return Ok(FuelState::Yielded(value));
                 }
-                halt => {
-                    return Err(VmError::from(VmErrorKind::Halted {
-                        halt: halt.into_info(),
-                    }))
+                VmHalt::Limited => {
+                    self.state = ExecutionState::Exhausted;
+                    return Ok(FuelState::Exhausted);
                 }
             }
 
             if len == 0 {
                 let value = self.end()?;
-                return Ok(GeneratorState::Complete(value));
+                return Ok(FuelState::Complete(value));
             }
 
             self.pop_vm()?;
@@ -276,16 +410,77 @@ where
     /// If any async instructions are encountered, this will error with
     /// [VmErrorKind::Halted].
     pub fn resume(&mut self) -> Result<GeneratorState, VmError> {
-        if matches!(self.state, ExecutionState::Resumed) {
-            vm_mut!(self).stack_mut().push(Value::Unit);
-        } else {
-            self.state = ExecutionState::Resumed;
-        }
-
+        self.prepare_resume();
         self.inner_resume()
     }
 
+    /// Resume the current execution without support for async instructions,
+    /// executing at most `fuel` instructions.
+    ///
+    /// If the fuel runs out, [FuelState::Exhausted] is returned and the
+    /// execution can be continued by calling this function again. This allows
+    /// for cooperatively time-slicing many executions on a single thread.
+    ///
+    /// ```
+    /// use rune::runtime::FuelState;
+    /// use rune::{FromValue, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             let n = 0;
+    ///
+    ///             while n < 100 {
+    ///                 n += 1;
+    ///             }
+    ///
+    ///             n
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    ///
+    /// let mut vm = Vm::without_runtime(Arc::new(unit));
+    /// let mut execution = vm.execute(["main"], ())?;
+    /// let mut slices = 0;
+    ///
+    /// let value = loop {
+    ///     match execution.resume_with_fuel(10)? {
+    ///         FuelState::Complete(value) => break value,
+    ///         FuelState::Exhausted => slices += 1,
+    ///         FuelState::Yielded(..) => panic!("unexpected yield"),
+    ///     }
+    /// };
+    ///
+    /// assert!(slices > 0);
+    /// assert_eq!(i64::from_value(value)?, 100);
+    /// # Ok(()) }
+    /// ```
+    pub fn resume_with_fuel(&mut self, fuel: usize) -> Result<FuelState, VmError> {
+        self.prepare_resume();
+        budget::with(fuel, || self.inner_resume_fuel()).call()
+    }
+
+    /// Prepare the execution to be resumed without a value.
+    fn prepare_resume(&mut self) {
+        match self.state {
+            ExecutionState::Resumed => {
+                vm_mut!(self).stack_mut().push(Value::Unit);
+            }
+            ExecutionState::Initial | ExecutionState::Exhausted => {
+                self.state = ExecutionState::Resumed;
+            }
+        }
+    }
+
     fn inner_resume(&mut self) -> Result<GeneratorState, VmError> {
+        self.inner_resume_fuel()?.into_generator_state()
+    }
+
+    fn inner_resume_fuel(&mut self) -> Result<FuelState, VmError> {
         loop {
             let len = self.vms.len();
             let vm = vm_mut!(self);
@@ -298,7 +493,11 @@ where
                 }
                 VmHalt::Yielded => {
                     let value = vm.stack_mut().pop()?;
-                    return Ok(GeneratorState::Yielded(value));
+                    return Ok(FuelState::Yielded(value));
+                }
+                VmHalt::Limited => {
+                    self.state = ExecutionState::Exhausted;
+                    return Ok(FuelState::Exhausted);
                 }
                 halt => {
                     return Err(VmError::from(VmErrorKind::Halted {
@@ -309,7 +508,7 @@ where
 
             if len == 0 {
                 let value = self.end()?;
-                return Ok(GeneratorState::Complete(value));
+                return Ok(FuelState::Complete(value));
             }
 
             self.pop_vm()?;
@@ -414,21 +613,50 @@ where
     fn run(vm: &mut Vm) -> Result<VmHalt, VmError> {
         match vm.run() {
             Ok(reason) => Ok(reason),
-            Err(error) => Err(error.into_unwinded(vm.unit(), vm.ip(), vm.call_frames().to_vec())),
+            Err(error) => {
+                let error = error.into_unwinded(vm);
+                vm.trace_unwind(&error);
+                Err(error)
+            }
         }
     }
 }
 
 impl VmExecution<&mut Vm> {
     /// Convert the current execution into one which owns its virtual machine.
-    pub fn into_owned(self) -> VmExecution<Vm> {
+    pub fn into_owned(mut self) -> VmExecution<Vm> {
         let stack = take(self.head.stack_mut());
-        let head = Vm::with_stack(self.head.context().clone(), self.head.unit().clone(), stack);
+        let mut head = Vm::with_generation(
+            self.head.context().clone(),
+            self.head.unit().clone(),
+            stack,
+            self.head.generation(),
+        );
+        head.inherit(self.head);
 
         VmExecution {
             head,
-            vms: self.vms,
+            vms: take(&mut self.vms),
             state: self.state,
+            awaited: self.awaited.take(),
+            cycles: self.cycles.take(),
+        }
+    }
+}
+
+impl<T> Drop for VmExecution<T>
+where
+    T: AsMut<Vm>,
+{
+    fn drop(&mut self) {
+        if let Some(cycles) = self.cycles.take() {
+            // NB: values left behind by an execution which didn't run to
+            // completion would otherwise keep the cycles they are part of
+            // reachable when they are collected.
+            self.vms.clear();
+            self.awaited = None;
+            self.head.as_mut().stack_mut().clear();
+            drop(cycles);
         }
     }
 }
@@ -471,4 +699,55 @@ impl VmSendExecution {
         // from escaping from contained virtual machine.
         unsafe { AssertSend::new(future) }
     }
+
+    /// Complete the current execution with support for async instructions,
+    /// converting its result into `T` before it escapes the virtual machine.
+    ///
+    /// Since `T` is required to be [Send], the returned future can safely be
+    /// driven by a multi-threaded executor and have its output moved to
+    /// another thread. Use [SendValue][crate::runtime::SendValue] to return
+    /// any value which can be sent across threads.
+    pub fn async_complete_into<T>(
+        mut self,
+    ) -> impl Future<Output = Result<T, VmError>> + Send + 'static
+    where
+        T: FromValue + Send + 'static,
+    {
+        let future = async move {
+            let value = match self.0.async_resume().await? {
+                GeneratorState::Complete(value) => value,
+                GeneratorState::Yielded(..) => {
+                    return Err(VmError::from(VmErrorKind::Halted {
+                        halt: VmHaltInfo::Yielded,
+                    }))
+                }
+            };
+
+            // NB: the value is converted while still owned by the execution,
+            // so only the sendable output escapes.
+            let value = self.0.vm_mut().with(move || T::from_value(value))?;
+            Ok(value)
+        };
+
+        // Safety: we wrap all APIs around the [VmExecution], and the only
+        // value escaping the contained virtual machine is required to be
+        // `Send`.
+        unsafe { AssertSend::new(future) }
+    }
+}
+
+/// Yield once to the task polling the current future.
+async fn yield_now() {
+    let mut yielded = false;
+
+    future::poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
 }
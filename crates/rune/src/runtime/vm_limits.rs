@@ -0,0 +1,163 @@
+//! Limits which can be imposed on the execution of a virtual machine.
+
+use crate::runtime::{env, VmError, VmErrorKind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Limits imposed on the execution of a [Vm][crate::Vm].
+///
+/// Exceeding any of these limits causes execution to fail with a [VmError],
+/// allowing hosts to recover from runaway recursion or allocation in untrusted
+/// scripts instead of aborting.
+///
+/// The allocation budget covers values constructed by instructions and the
+/// growth of vectors, strings and bytes through the native functions in the
+/// standard library, like `String::push_str`. Memory allocated by other native
+/// functions isn't accounted for, so hosts exposing such functions to untrusted
+/// scripts still need to limit the memory of the process.
+///
+/// Limits are inherited by the virtual machines which are spawned to execute
+/// generators, streams, async functions and function values called from a
+/// limited virtual machine. Call depth and stack size are counted across these,
+/// so recursing through a native function like `Iterator::map` is limited just
+/// like recursing directly.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use rune::runtime::{VmErrorKind, VmLimits};
+/// use std::sync::Arc;
+///
+/// # fn main() -> rune::Result<()> {
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources!(entry => {
+///     fn recurse(n) { recurse(n + 1) }
+///     pub fn main() { recurse(0) }
+/// });
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+///
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+/// vm.set_limits(VmLimits::new().with_max_call_depth(128));
+///
+/// let error = vm.call(["main"], ()).unwrap_err();
+/// assert!(matches!(error.as_unwound().0, VmErrorKind::CallDepthExceeded { max: 128 }));
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VmLimits {
+    /// The maximum number of nested call frames.
+    pub max_call_depth: Option<usize>,
+    /// The maximum number of values on the stack, which is checked after
+    /// every instruction.
+    pub max_stack_size: Option<usize>,
+    /// The instruction allocation budget, which is the approximate maximum
+    /// number of bytes which can be allocated by instructions.
+    ///
+    /// This accounts for the values constructed by instructions, like vector,
+    /// tuple and object literals, string templates and concatenation, and for
+    /// vectors, strings and bytes grown by the standard library. Memory being
+    /// released is not accounted for, so this budgets the total amount
+    /// allocated over the lifetime of the limited execution.
+    pub max_instruction_allocation: Option<usize>,
+}
+
+impl VmLimits {
+    /// Construct a new set of limits where nothing is limited.
+    pub const fn new() -> Self {
+        Self {
+            max_call_depth: None,
+            max_stack_size: None,
+            max_instruction_allocation: None,
+        }
+    }
+
+    /// Limit the number of nested call frames.
+    pub const fn with_max_call_depth(self, max_call_depth: usize) -> Self {
+        Self {
+            max_call_depth: Some(max_call_depth),
+            ..self
+        }
+    }
+
+    /// Limit the number of values on the stack.
+    pub const fn with_max_stack_size(self, max_stack_size: usize) -> Self {
+        Self {
+            max_stack_size: Some(max_stack_size),
+            ..self
+        }
+    }
+
+    /// Limit the approximate number of bytes which can be allocated by
+    /// instructions.
+    ///
+    /// See [VmLimits::max_instruction_allocation] for what is accounted for.
+    pub const fn with_max_instruction_allocation(self, max_instruction_allocation: usize) -> Self {
+        Self {
+            max_instruction_allocation: Some(max_instruction_allocation),
+            ..self
+        }
+    }
+
+    /// Check that a call frame can be pushed given the current call depth and
+    /// stack size.
+    #[inline]
+    pub(crate) fn check_call(&self, depth: usize, stack_size: usize) -> Result<(), VmError> {
+        if let Some(max) = self.max_call_depth {
+            if depth >= max {
+                return Err(VmError::from(VmErrorKind::CallDepthExceeded { max }));
+            }
+        }
+
+        self.check_stack_size(stack_size)
+    }
+
+    /// Check that the stack hasn't grown beyond its maximum size.
+    #[inline]
+    pub(crate) fn check_stack_size(&self, stack_size: usize) -> Result<(), VmError> {
+        if let Some(max) = self.max_stack_size {
+            if stack_size > max {
+                return Err(VmError::from(VmErrorKind::StackSizeExceeded { max }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Memory allocated by instructions, which is shared between a virtual machine
+/// and the virtual machines it spawns.
+#[derive(Debug, Default)]
+pub(crate) struct AllocationUsage {
+    used: AtomicUsize,
+}
+
+impl AllocationUsage {
+    /// The number of bytes accounted for so far.
+    pub(crate) fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Charge the given number of bytes, erroring if it exceeds `max`.
+    pub(crate) fn charge(&self, bytes: usize, max: usize) -> Result<(), VmError> {
+        let used = self.used.fetch_add(bytes, Ordering::Relaxed);
+
+        if used.saturating_add(bytes) > max {
+            return Err(VmError::from(VmErrorKind::InstructionAllocationExceeded {
+                max,
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+/// Account for the given number of bytes being allocated by a native function,
+/// like a vector or a string growing, against the allocation budget of the
+/// virtual machine which is currently executing.
+pub(crate) fn charge_allocation(bytes: usize) -> Result<(), VmError> {
+    env::charge(bytes)
+}
@@ -0,0 +1,241 @@
+//! A pool of reusable virtual machines.
+
+use crate::runtime::{RuntimeContext, Unit, Vm, VmLimits};
+use std::fmt;
+use std::ops;
+use std::sync::{Arc, Mutex};
+
+/// A pool of virtual machines which share a [RuntimeContext] and a [Unit].
+///
+/// This is intended for hosts which execute many short invocations of the
+/// same unit concurrently, like servers handling a script per request. A
+/// virtual machine is checked out of the pool through [VmPool::checkout] and
+/// returned to it once the [PooledVm] guard is dropped, so the memory it has
+/// allocated for its stack and call frames is reused by later invocations.
+///
+/// Virtual machines are reset when they are returned to the pool. Their stack
+/// is cleared, any installed tracer, profiler, ambient values, capability
+/// restrictions or cycle collector are removed and the limits of the pool are
+/// re-applied, which also resets the instruction allocations accounted for.
+/// Virtual machines whose unit has been swapped out are discarded.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::VmPool;
+/// use rune::{Context, FromValue};
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// # fn main() -> rune::Result<()> {
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main(n) {
+///             n * 2
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let pool = Arc::new(VmPool::new(runtime, Arc::new(unit)));
+///
+/// let handles = (0..4i64)
+///     .map(|n| {
+///         let pool = pool.clone();
+///
+///         thread::spawn(move || {
+///             let mut vm = pool.checkout();
+///             i64::from_value(vm.call(["main"], (n,)).unwrap()).unwrap()
+///         })
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let output = handles
+///     .into_iter()
+///     .map(|handle| handle.join().unwrap())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(output, [0, 2, 4, 6]);
+/// # Ok(()) }
+/// ```
+pub struct VmPool {
+    context: Arc<RuntimeContext>,
+    unit: Arc<Unit>,
+    limits: VmLimits,
+    max_idle: usize,
+    idle: Mutex<Vec<IdleVm>>,
+}
+
+impl VmPool {
+    /// Construct a new empty pool of virtual machines running the given unit.
+    pub fn new(context: Arc<RuntimeContext>, unit: Arc<Unit>) -> Self {
+        Self {
+            context,
+            unit,
+            limits: VmLimits::new(),
+            max_idle: usize::MAX,
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Set the limits imposed on every virtual machine checked out of the
+    /// pool.
+    pub fn with_limits(mut self, limits: VmLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Set the maximum number of idle virtual machines kept around by the
+    /// pool. Virtual machines which are returned to a full pool are dropped.
+    pub fn with_max_idle(mut self, max_idle: usize) -> Self {
+        self.max_idle = max_idle;
+        self
+    }
+
+    /// Access the context shared by the virtual machines in the pool.
+    pub fn context(&self) -> &Arc<RuntimeContext> {
+        &self.context
+    }
+
+    /// Access the unit shared by the virtual machines in the pool.
+    pub fn unit(&self) -> &Arc<Unit> {
+        &self.unit
+    }
+
+    /// The number of idle virtual machines currently held by the pool.
+    pub fn idle(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Check out a virtual machine from the pool, constructing a new one if
+    /// there are none idle.
+    pub fn checkout(&self) -> PooledVm<'_> {
+        let vm = match self.lock().pop() {
+            Some(IdleVm(vm)) => vm,
+            None => {
+                let mut vm = Vm::new(self.context.clone(), self.unit.clone());
+                vm.set_limits(self.limits);
+                vm
+            }
+        };
+
+        PooledVm {
+            pool: self,
+            vm: Some(vm),
+        }
+    }
+
+    /// Reset the given virtual machine and return it to the pool.
+    fn checkin(&self, mut vm: Vm) {
+        if !vm.is_same(&self.context, &self.unit) {
+            return;
+        }
+
+        vm.clear();
+        vm.take_tracer();
+        vm.take_profiler();
+        vm.clear_ambient();
+        vm.clear_capabilities();
+        vm.take_cycle_collector();
+        vm.set_limits(self.limits);
+
+        let vm = match IdleVm::new(vm) {
+            Some(vm) => vm,
+            None => return,
+        };
+
+        let mut idle = self.lock();
+
+        if idle.len() < self.max_idle {
+            idle.push(vm);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<IdleVm>> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for VmPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VmPool")
+            .field("limits", &self.limits)
+            .field("max_idle", &self.max_idle)
+            .field("idle", &self.idle())
+            .finish()
+    }
+}
+
+/// A virtual machine checked out of a [VmPool], which is returned to the pool
+/// when dropped.
+pub struct PooledVm<'a> {
+    pool: &'a VmPool,
+    vm: Option<Vm>,
+}
+
+impl PooledVm<'_> {
+    /// Detach the virtual machine from the pool, so that it is not returned to
+    /// it when dropped.
+    pub fn detach(mut self) -> Vm {
+        self.vm.take().expect("virtual machine already taken")
+    }
+}
+
+impl ops::Deref for PooledVm<'_> {
+    type Target = Vm;
+
+    fn deref(&self) -> &Self::Target {
+        self.vm.as_ref().expect("virtual machine already taken")
+    }
+}
+
+impl ops::DerefMut for PooledVm<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.vm.as_mut().expect("virtual machine already taken")
+    }
+}
+
+impl Drop for PooledVm<'_> {
+    fn drop(&mut self) {
+        if let Some(vm) = self.vm.take() {
+            self.pool.checkin(vm);
+        }
+    }
+}
+
+impl fmt::Debug for PooledVm<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PooledVm").field(&self.vm).finish()
+    }
+}
+
+/// A virtual machine which is idle in a pool.
+struct IdleVm(Vm);
+
+impl IdleVm {
+    /// Wrap a virtual machine which has been reset, or return `None` if it
+    /// still holds something bound to the current thread.
+    fn new(vm: Vm) -> Option<Self> {
+        if vm.is_thread_unbound() {
+            Some(Self(vm))
+        } else {
+            None
+        }
+    }
+}
+
+// SAFETY: the only parts of a virtual machine which are not `Send` are:
+// * the values on its stack,
+// * its ambient values, stored in an `Rc<Ambient>`,
+// * its cycle collector, stored in an `Rc<CycleCollector>`.
+//
+// An idle virtual machine is only constructed through `IdleVm::new`, which
+// checks with `Vm::is_thread_unbound` that the stack is empty and that no
+// ambient values or cycle collector remain. That check destructures every
+// field of the virtual machine and asserts that every other field is `Send`,
+// so a new field can't be left behind. The tracer, profiler and capabilities
+// are `Send`, but are still removed so that they don't leak between checkouts.
+unsafe impl Send for IdleVm {}
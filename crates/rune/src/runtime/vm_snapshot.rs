@@ -0,0 +1,140 @@
+use crate::runtime::{
+    CallFrame, ExecutionState, FromValue, RuntimeContext, SendValue, Stack, Unit, Vm, VmError,
+    VmErrorKind, VmExecution,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A serializable snapshot of a suspended [VmExecution].
+///
+/// A snapshot captures the instruction pointer, call frames and stack of an
+/// execution, allowing it to be persisted and resumed later through
+/// [VmSnapshot::restore]. It must be restored with the same unit it was taken
+/// from.
+///
+/// Values on the stack are deep copied in the same manner as [SendValue],
+/// which means that values which can't be represented there, like functions,
+/// futures or iterators, can't be part of a snapshot. Values which are shared
+/// between multiple places on the stack are restored as independent copies.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{GeneratorState, VmSnapshot};
+/// use rune::{FromValue, Vm};
+/// use std::sync::Arc;
+///
+/// # fn main() -> rune::Result<()> {
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             let total = 0;
+///             let n = 0;
+///
+///             while n < 3 {
+///                 total += yield n;
+///                 n += 1;
+///             }
+///
+///             total
+///         }
+///     }
+/// };
+///
+/// let unit = Arc::new(rune::prepare(&mut sources).build()?);
+///
+/// let mut vm = Vm::without_runtime(unit.clone());
+/// let mut execution = vm.execute(["main"], ())?;
+/// execution.resume()?;
+///
+/// let snapshot = execution.snapshot()?;
+/// let bytes = bincode::serialize(&snapshot)?;
+/// drop(execution);
+///
+/// let snapshot: VmSnapshot = bincode::deserialize(&bytes)?;
+/// let mut execution = snapshot.restore(Default::default(), unit)?;
+///
+/// let mut total = None;
+///
+/// while total.is_none() {
+///     if let GeneratorState::Complete(value) = execution.resume_with(10i64.into())? {
+///         total = Some(i64::from_value(value)?);
+///     }
+/// }
+///
+/// assert_eq!(total, Some(30));
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VmSnapshot {
+    /// The number of instructions in the unit the snapshot was taken from.
+    instructions: usize,
+    /// The state of the execution.
+    state: ExecutionState,
+    /// The instruction pointer.
+    ip: usize,
+    /// The values on the stack.
+    stack: Vec<SendValue>,
+    /// The bottom of the current stack frame.
+    stack_bottom: usize,
+    /// The call frames of the execution.
+    call_frames: Vec<CallFrame>,
+}
+
+impl VmSnapshot {
+    /// Take a snapshot of the given virtual machine.
+    pub(crate) fn new(vm: &Vm, state: ExecutionState) -> Result<Self, VmError> {
+        let stack = vm
+            .stack()
+            .iter()
+            .map(|value| SendValue::from_value(value.clone()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            instructions: vm.unit().iter_instructions().count(),
+            state,
+            ip: vm.ip(),
+            stack,
+            stack_bottom: vm.stack().stack_bottom(),
+            call_frames: vm.call_frames().to_vec(),
+        })
+    }
+
+    /// Restore the snapshot into an execution using the given context and
+    /// unit.
+    ///
+    /// This errors with [VmErrorKind::SnapshotMismatch] if the snapshot is
+    /// inconsistent with the unit.
+    pub fn restore(
+        self,
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+    ) -> Result<VmExecution<Vm>, VmError> {
+        let len = self.stack.len();
+
+        let consistent = self.instructions == unit.iter_instructions().count()
+            && self.ip < self.instructions
+            && self.stack_bottom <= len
+            && self
+                .call_frames
+                .iter()
+                .all(|frame| frame.ip() < self.instructions && frame.stack_bottom() <= len);
+
+        if !consistent {
+            return Err(VmError::from(VmErrorKind::SnapshotMismatch));
+        }
+
+        let mut stack = self
+            .stack
+            .into_iter()
+            .map(SendValue::into_value)
+            .collect::<Stack>();
+
+        stack.set_stack_bottom(self.stack_bottom);
+
+        let mut vm = Vm::with_stack(context, unit, stack);
+        vm.set_ip(self.ip);
+        vm.set_call_frames(self.call_frames);
+        Ok(VmExecution::with_state(vm, self.state))
+    }
+}
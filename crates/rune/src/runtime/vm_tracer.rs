@@ -0,0 +1,99 @@
+//! Hooks for tracing the execution of a virtual machine.
+
+use crate::runtime::{Value, Vm, VmError};
+use crate::Hash;
+use std::fmt;
+use std::sync::Arc;
+
+/// Callbacks which are invoked as a [Vm] executes.
+///
+/// All callbacks default to doing nothing, so implementors only need to
+/// provide the ones they are interested in. A tracer is installed through
+/// [Vm::set_tracer] and is inherited by the virtual machines spawned to
+/// execute generators, streams, async functions and function values.
+///
+/// Only calls to functions defined in the unit are traced, native functions
+/// are not. Every traced call is eventually matched by a call to
+/// [VmTracer::on_return] once the function returns, unless execution unwinds
+/// or is abandoned before that.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::VmTracer;
+/// use rune::{FromValue, Hash, Value, Vm};
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Default)]
+/// struct Recorder {
+///     calls: Mutex<Vec<(Hash, Vec<i64>)>>,
+/// }
+///
+/// impl VmTracer for Recorder {
+///     fn on_call(&self, _: &Vm, hash: Hash, args: &[Value]) {
+///         let args = args.iter().map(|v| i64::from_value(v.clone()).unwrap()).collect();
+///         self.calls.lock().unwrap().push((hash, args));
+///     }
+/// }
+///
+/// # fn main() -> rune::Result<()> {
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn add(a, b) { a + b }
+///         pub fn main(n) { add(n, 2) }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+///
+/// let recorder = Arc::new(Recorder::default());
+/// let mut vm = Vm::without_runtime(Arc::new(unit));
+/// vm.set_tracer(recorder.clone());
+///
+/// let output = i64::from_value(vm.call(["main"], (1i64,))?)?;
+/// assert_eq!(output, 3);
+///
+/// let calls = recorder.calls.lock().unwrap();
+/// assert_eq!(*calls, vec![
+///     (Hash::type_hash(["main"]), vec![1]),
+///     (Hash::type_hash(["add"]), vec![1, 2]),
+/// ]);
+/// # Ok(()) }
+/// ```
+pub trait VmTracer: Send + Sync {
+    /// Called when a function is entered, with a snapshot of the arguments it
+    /// is called with.
+    fn on_call(&self, vm: &Vm, hash: Hash, args: &[Value]) {
+        let _ = (vm, hash, args);
+    }
+
+    /// Called when a function returns the given value.
+    fn on_return(&self, vm: &Vm, value: &Value) {
+        let _ = (vm, value);
+    }
+
+    /// Called when a generator or stream yields the given value.
+    fn on_yield(&self, vm: &Vm, value: &Value) {
+        let _ = (vm, value);
+    }
+
+    /// Called when execution suspends to await a future or a select.
+    fn on_await(&self, vm: &Vm) {
+        let _ = vm;
+    }
+
+    /// Called when execution unwinds because of an error.
+    fn on_unwind(&self, vm: &Vm, error: &VmError) {
+        let _ = (vm, error);
+    }
+}
+
+/// A tracer installed in a virtual machine.
+#[derive(Clone)]
+pub(crate) struct Tracer(pub(crate) Arc<dyn VmTracer>);
+
+impl fmt::Debug for Tracer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Tracer").finish()
+    }
+}
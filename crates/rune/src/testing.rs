@@ -1,15 +1,468 @@
-//! Internal testing module.
+//! Running tests written in scripts.
+//!
+//! Functions marked with `#[test]` can be collected and run with
+//! [run_tests], which reports the outcome of every test in a [TestReport] so
+//! that it can be presented however is suitable.
 
 use std::fmt;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use futures_channel::mpsc;
+use futures_util::StreamExt;
+
+use crate::compile::{CompileVisitor, ItemBuf, MetaKind, MetaRef};
 use crate::macros::{MacroContext, ToTokens, TokenStream};
 use crate::parse::{Parse, Parser};
-use crate::SourceId;
+use crate::runtime::output::Capture;
+use crate::runtime::{RuntimeContext, Unit, Value, Vm, VmError};
+use crate::{Context, Diagnostics, Hash, Options, SourceId, Sources};
+
+/// Options for running tests.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct TestOptions {
+    /// Options to build the sources with. The test configuration is enabled by
+    /// default.
+    pub compile: Options,
+    /// Only run tests whose name contains any of the given strings. All tests
+    /// are run if this is empty.
+    pub filters: Vec<String>,
+    /// Only run tests marked with `#[ignore]`.
+    pub ignored: bool,
+    /// Run tests marked with `#[ignore]` along with all other tests.
+    pub include_ignored: bool,
+    /// Stop running tests once one of them has failed.
+    pub fail_fast: bool,
+    /// The number of tests to run in parallel, which defaults to one.
+    ///
+    /// If more than one, tests are run on tasks spawned through
+    /// [Executor::spawn_blocking] of the executor installed in the context,
+    /// or on threads of their own if there is none.
+    ///
+    /// [Executor::spawn_blocking]: crate::runtime::Executor::spawn_blocking
+    pub jobs: NonZeroUsize,
+    /// Capture what tests write to standard output and standard error in
+    /// [TestResult::output], instead of writing it wherever the context sends
+    /// it.
+    pub capture: bool,
+}
+
+impl Default for TestOptions {
+    fn default() -> Self {
+        let mut compile = Options::default();
+        compile.test(true);
+
+        Self {
+            compile,
+            filters: Vec::new(),
+            ignored: false,
+            include_ignored: false,
+            fail_fast: true,
+            jobs: NonZeroUsize::new(1).expect("one is not zero"),
+            capture: false,
+        }
+    }
+}
+
+/// The outcome of running the tests in a collection of sources.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct TestReport {
+    /// Diagnostics reported while building the sources. If building failed no
+    /// tests are run.
+    pub diagnostics: Diagnostics,
+    /// The tests which were found, in the order they were declared in.
+    pub tests: Vec<TestResult>,
+    /// The number of tests which didn't match any filter.
+    pub filtered: usize,
+    /// How long it took to run the tests.
+    pub elapsed: Duration,
+}
+
+impl TestReport {
+    /// Test if the sources could be built and none of the tests failed.
+    pub fn is_success(&self) -> bool {
+        !self.diagnostics.has_error() && self.failures().next().is_none()
+    }
+
+    /// Iterate over the tests which failed.
+    pub fn failures(&self) -> impl Iterator<Item = &TestResult> + '_ {
+        self.tests
+            .iter()
+            .filter(|test| matches!(test.outcome, TestOutcome::Failed(..)))
+    }
+}
+
+/// A test which was found, and its outcome.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct TestResult {
+    /// The item of the test function.
+    pub item: ItemBuf,
+    /// The hash of the test function.
+    pub hash: Hash,
+    /// The outcome of running the test.
+    pub outcome: TestOutcome,
+    /// What the test wrote to standard output and standard error, if it was
+    /// captured through [TestOptions::capture].
+    pub output: Vec<u8>,
+}
+
+/// The outcome of a single test.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TestOutcome {
+    /// The test passed.
+    Passed,
+    /// The test is marked with `#[ignore]` and wasn't run.
+    Ignored,
+    /// The test wasn't run since an earlier one failed.
+    Skipped,
+    /// The test failed.
+    Failed(TestFailure),
+}
+
+/// Why a test failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TestFailure {
+    /// The test raised an error.
+    Crash(VmError),
+    /// The test returned `None`.
+    ReturnedNone,
+    /// The test returned an error, which is formatted with its debug
+    /// representation.
+    ReturnedErr(String),
+    /// The test is marked with `#[should_panic]` but didn't panic.
+    DidNotPanic,
+}
+
+impl fmt::Display for TestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Crash(error) => write!(f, "{}", error),
+            Self::ReturnedNone => write!(f, "Returned none"),
+            Self::ReturnedErr(error) => write!(f, "Error: {}", error),
+            Self::DidNotPanic => write!(f, "Test was expected to panic"),
+        }
+    }
+}
+
+/// Build the given sources and run the tests declared in them.
+///
+/// Unless [TestOptions::jobs] says otherwise, tests are run one after another
+/// on the task awaiting this function.
+///
+/// ```
+/// use rune::testing::{self, TestOptions, TestOutcome};
+/// use rune::{Context, Source, Sources};
+///
+/// # #[tokio::main] async fn main() -> rune::Result<()> {
+/// let context = Context::with_default_modules()?;
+///
+/// let mut sources = Sources::new();
+///
+/// sources.insert(Source::new("entry", r#"
+///     #[test]
+///     fn adds() {
+///         assert_eq!(1 + 1, 2);
+///     }
+///
+///     #[test]
+///     fn subtracts() {
+///         println!("subtracting");
+///         assert_eq!(2 - 1, 2);
+///     }
+/// "#));
+///
+/// let mut options = TestOptions::default();
+/// options.capture = true;
+///
+/// let report = testing::run_tests(&context, &mut sources, &options).await;
+///
+/// assert!(!report.is_success());
+/// assert_eq!(report.tests.len(), 2);
+/// assert!(matches!(report.tests[0].outcome, TestOutcome::Passed));
+/// assert!(matches!(report.tests[1].outcome, TestOutcome::Failed(..)));
+/// assert_eq!(report.tests[1].output, b"subtracting\n");
+/// # Ok(()) }
+/// ```
+pub async fn run_tests(
+    context: &Context,
+    sources: &mut Sources,
+    options: &TestOptions,
+) -> TestReport {
+    let mut diagnostics = Diagnostics::new();
+    let mut visitor = TestVisitor::default();
+
+    let result = crate::prepare(sources)
+        .with_context(context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(&options.compile)
+        .with_visitor(&mut visitor)
+        .build();
+
+    let mut report = TestReport {
+        diagnostics,
+        tests: Vec::new(),
+        filtered: 0,
+        elapsed: Duration::default(),
+    };
+
+    let unit = match result {
+        Ok(unit) => Arc::new(unit),
+        Err(..) => return report,
+    };
+
+    // The tests which should be executed, as indexes into `report.tests`.
+    let mut pending = Vec::new();
+
+    for test in visitor.tests {
+        if !options.filters.is_empty() {
+            let name = test.item.to_string();
+
+            if !options
+                .filters
+                .iter()
+                .any(|filter| name.contains(filter.as_str()))
+            {
+                report.filtered += 1;
+                continue;
+            }
+        }
+
+        let run = if options.ignored {
+            test.is_ignored
+        } else {
+            options.include_ignored || !test.is_ignored
+        };
+
+        let outcome = if run {
+            pending.push(Pending {
+                index: report.tests.len(),
+                hash: test.hash,
+                should_panic: test.should_panic,
+            });
+
+            TestOutcome::Skipped
+        } else {
+            TestOutcome::Ignored
+        };
+
+        report.tests.push(TestResult {
+            item: test.item,
+            hash: test.hash,
+            outcome,
+            output: Vec::new(),
+        });
+    }
+
+    let start = Instant::now();
+
+    let runner = Arc::new(Runner {
+        context: context.runtime(),
+        unit,
+        pending,
+        next: AtomicUsize::new(0),
+        stop: AtomicBool::new(false),
+        fail_fast: options.fail_fast,
+        capture: options.capture,
+    });
+
+    if options.jobs.get() == 1 {
+        let mut worker = runner.worker();
+
+        while let Some((index, outcome, output)) = worker.next().await {
+            report.tests[index].outcome = outcome;
+            report.tests[index].output = output;
+        }
+    } else {
+        let executor = runner.context.executor().cloned();
+        let (sender, mut receiver) = mpsc::unbounded();
+
+        for _ in 0..options.jobs.get().min(runner.pending.len()) {
+            let (runner, sender) = (runner.clone(), sender.clone());
+
+            let task = move || {
+                let mut worker = runner.worker();
+
+                while let Some(result) = futures_executor::block_on(worker.next()) {
+                    if sender.unbounded_send(result).is_err() {
+                        break;
+                    }
+                }
+            };
+
+            match &executor {
+                Some(executor) => executor.spawn_blocking(Box::new(task)),
+                None => {
+                    std::thread::spawn(task);
+                }
+            }
+        }
+
+        drop(sender);
+
+        while let Some((index, outcome, output)) = receiver.next().await {
+            report.tests[index].outcome = outcome;
+            report.tests[index].output = output;
+        }
+    }
+
+    report.elapsed = start.elapsed();
+    report
+}
+
+/// Run the test function with the given hash on a virtual machine, where
+/// `should_panic` indicates if it's expected to panic.
+///
+/// Tests pass unless they raise an error, or return `None` or an error. Tests
+/// which should panic only pass if they raise a panic, any other error is
+/// still reported as a crash.
+pub async fn run_test(mut vm: Vm, hash: Hash, should_panic: bool) -> TestOutcome {
+    let result = match vm.execute(hash, ()) {
+        Err(error) => Err(error),
+        Ok(mut execution) => execution.async_complete().await,
+    };
+
+    let failure = match result {
+        Err(error) if should_panic && error.as_panic().is_some() => return TestOutcome::Passed,
+        Err(error) => Some(TestFailure::Crash(error)),
+        Ok(Value::Result(result)) => match result.take() {
+            Ok(Ok(..)) => None,
+            Ok(Err(error)) => Some(TestFailure::ReturnedErr(format!("{:?}", error))),
+            Err(error) => Some(TestFailure::Crash(error.into())),
+        },
+        Ok(Value::Option(option)) => match option.borrow_ref() {
+            Ok(option) => option.is_none().then_some(TestFailure::ReturnedNone),
+            Err(error) => Some(TestFailure::Crash(error.into())),
+        },
+        Ok(..) => None,
+    };
+
+    match failure {
+        Some(failure) => TestOutcome::Failed(failure),
+        None if should_panic => TestOutcome::Failed(TestFailure::DidNotPanic),
+        None => TestOutcome::Passed,
+    }
+}
+
+/// A test which should be run.
+struct Pending {
+    index: usize,
+    hash: Hash,
+    should_panic: bool,
+}
+
+/// The state shared between everything running tests.
+struct Runner {
+    context: RuntimeContext,
+    unit: Arc<Unit>,
+    pending: Vec<Pending>,
+    next: AtomicUsize,
+    stop: AtomicBool,
+    fail_fast: bool,
+    capture: bool,
+}
+
+impl Runner {
+    /// Construct a worker which runs tests, with a context of its own so that
+    /// the output of tests which run in parallel can be captured separately.
+    fn worker(self: &Arc<Self>) -> Worker {
+        let mut context = self.context.clone();
+        let mut capture = None;
+
+        if self.capture {
+            let sink = Capture::new();
+            context.set_output(Arc::new(Mutex::new(sink.clone())));
+            capture = Some(sink);
+        }
+
+        Worker {
+            runner: self.clone(),
+            context: Arc::new(context),
+            capture,
+        }
+    }
+}
+
+/// Something which runs tests until there are none left.
+struct Worker {
+    runner: Arc<Runner>,
+    context: Arc<RuntimeContext>,
+    capture: Option<Capture>,
+}
+
+impl Worker {
+    /// Run the next test, returning its index, outcome and captured output.
+    async fn next(&mut self) -> Option<(usize, TestOutcome, Vec<u8>)> {
+        let runner = &*self.runner;
+
+        if runner.stop.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let test = runner
+            .pending
+            .get(runner.next.fetch_add(1, Ordering::Relaxed))?;
+
+        let vm = Vm::new(self.context.clone(), runner.unit.clone());
+        let outcome = run_test(vm, test.hash, test.should_panic).await;
+
+        if runner.fail_fast && matches!(outcome, TestOutcome::Failed(..)) {
+            runner.stop.store(true, Ordering::Relaxed);
+        }
+
+        let output = self
+            .capture
+            .as_ref()
+            .map(Capture::drain)
+            .unwrap_or_default();
+        Some((test.index, outcome, output))
+    }
+}
+
+/// A function marked with `#[test]`.
+struct Test {
+    hash: Hash,
+    item: ItemBuf,
+    is_ignored: bool,
+    should_panic: bool,
+}
+
+/// A compile visitor which collects test functions.
+#[derive(Default)]
+struct TestVisitor {
+    tests: Vec<Test>,
+}
+
+impl CompileVisitor for TestVisitor {
+    fn register_meta(&mut self, meta: MetaRef<'_>) {
+        if let MetaKind::Function {
+            is_test: true,
+            is_ignored,
+            should_panic,
+            ..
+        } = meta.kind
+        {
+            self.tests.push(Test {
+                hash: meta.hash,
+                item: meta.item.to_owned(),
+                is_ignored,
+                should_panic,
+            });
+        }
+    }
+}
 
 /// Function used during parse testing to take the source, parse it as the given
 /// type, tokenize it using [ToTokens], and parse the token stream.
 ///
 /// The results should be identical.
+#[doc(hidden)]
 pub fn roundtrip<T>(source: &str) -> T
 where
     T: Parse + ToTokens + PartialEq + Eq + fmt::Debug,
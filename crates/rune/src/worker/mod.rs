@@ -58,7 +58,7 @@ impl<'a> Worker<'a> {
             diagnostics,
             source_loader,
             q: Query::new(
-                unit, prelude, consts, storage, sources, pool, visitor, gen, inner,
+                context, unit, prelude, consts, storage, sources, pool, visitor, gen, inner,
             ),
             gen,
             loaded: HashMap::new(),
@@ -178,6 +178,30 @@ macro_rules! rune_vm {
     }};
 }
 
+/// Same as [rune_vm!] macro, except it takes an external context, allowing
+/// the context to be configured or to have native modules installed.
+///
+/// # Examples
+///
+/// ```
+/// use rune_tests::*;
+///
+/// # fn main() {
+/// let context = rune::Context::with_default_modules().unwrap();
+/// let mut vm = rune_tests::rune_vm_with!(context => pub fn main() { true || false });
+/// let result = vm.execute(["main"], ()).unwrap().complete().unwrap();
+/// assert_eq!(result.into_bool().unwrap(), true);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! rune_vm_with {
+    ($context:expr => $($tt:tt)*) => {{
+        let mut diagnostics = Default::default();
+        let mut sources = $crate::sources(stringify!($($tt)*));
+        $crate::vm(&$context, &mut sources, &mut diagnostics).expect("program to compile successfully")
+    }};
+}
+
 /// Construct a rune virtual machine from the given program which will capture
 /// all output into a buffer, which can be retrieved from
 /// `rune_tests::capture_output::drain_output()`
@@ -4,7 +4,8 @@
     clippy::approx_constant,
     clippy::identity_op,
     clippy::bool_assert_comparison,
-    clippy::needless_return
+    clippy::needless_return,
+    clippy::type_complexity
 )]
 
 include!(concat!(env!("OUT_DIR"), "/tests.rs"));
@@ -0,0 +1,122 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_bytes_view() {
+    let out: (usize, Option<u8>, usize, Option<u8>, String) = rune!(
+        pub fn main() {
+            let bytes = b"hello world";
+            let view = bytes.view(6, 11);
+            let inner = view.view(1, 3);
+            bytes.extend(b"!");
+            (view.len(), view.get(0), inner.len(), inner.get(1), format!("{:?}", inner))
+        }
+    );
+
+    assert_eq!(out, (5, Some(b'w'), 2, Some(b'r'), String::from("[111, 114]")));
+
+    assert_vm_error!(
+        r#"pub fn main() { b"abc".view(2, 4) }"#,
+        OutOfRange { index, len } => {
+            assert_eq!(index.to_string(), "4");
+            assert_eq!(len.to_string(), "3");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let b = b"abc"; let v = b.view(1, 3); b.clear(); v.get(0) }"#,
+        OutOfRange { .. } => {}
+    );
+}
+
+#[test]
+fn test_bytes_read() {
+    let out: (i64, i64, i64, i64, i64, i64, f64) = rune!(
+        pub fn main() {
+            let bytes = b"\x01\x02\x03\x04\xff\xff\xff\xff\x00\x00\x80\x3f";
+            (
+                bytes.read_u8(0),
+                bytes.read_u16_be(0),
+                bytes.read_u32_le(0),
+                bytes.read_u32_be(0),
+                bytes.read_i32_le(4),
+                bytes.view(4, 8).read_u32_le(0),
+                bytes.read_f32_le(8),
+            )
+        }
+    );
+
+    assert_eq!(out, (1, 0x0102, 0x04030201, 0x01020304, -1, 0xffffffff, 1.0));
+
+    assert_vm_error!(
+        r#"pub fn main() { b"\x01\x02\x03".read_u32_le(0) }"#,
+        OutOfRange { index, len } => {
+            assert_eq!(index.to_string(), "0");
+            assert_eq!(len.to_string(), "3");
+        }
+    );
+}
+
+#[test]
+fn test_bytes_codecs() {
+    let out: (String, String, String, String, String) = rune!(
+        pub fn main() {
+            (
+                b"\x00\xab\xff".to_hex(),
+                b"".to_base64(),
+                b"f".to_base64(),
+                b"fo".to_base64(),
+                b"foobar".to_base64(),
+            )
+        }
+    );
+
+    assert_eq!(
+        out,
+        (
+            String::from("00abff"),
+            String::new(),
+            String::from("Zg=="),
+            String::from("Zm8="),
+            String::from("Zm9vYmFy"),
+        )
+    );
+
+    let out: (Vec<u8>, Vec<u8>, Vec<u8>, bool, bool) = rune!(
+        use std::bytes::Bytes;
+
+        pub fn main() {
+            (
+                Bytes::from_hex("00ABff").unwrap().into_vec(),
+                Bytes::from_base64("Zm8=").unwrap().into_vec(),
+                Bytes::from_base64("Zm9vYg").unwrap().into_vec(),
+                Bytes::from_hex("abc").is_err(),
+                Bytes::from_base64("Zm9v!").is_err(),
+            )
+        }
+    );
+
+    assert_eq!(
+        out,
+        (vec![0x00, 0xab, 0xff], b"fo".to_vec(), b"foob".to_vec(), true, true)
+    );
+}
+
+#[test]
+fn test_bytes_mut() {
+    let out: (usize, Vec<u8>) = rune!(
+        use std::bytes::BytesMut;
+
+        pub fn main() {
+            let b = BytesMut::new();
+            b.put_u8(1);
+            b.put_u16_be(0x0203);
+            b.put_u32_le(0x07060504);
+            b.put_str("ab");
+            b.put_bytes(b"c");
+            (b.len(), b.freeze().into_vec())
+        }
+    );
+
+    assert_eq!(out, (10, vec![1, 2, 3, 4, 5, 6, 7, b'a', b'b', b'c']));
+}
@@ -0,0 +1,83 @@
+use rune_capi::*;
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+#[test]
+fn test_call() {
+    unsafe {
+        let context = rune_context_new();
+        assert!(!context.is_null());
+
+        let sources = rune_sources_new();
+        let name = CString::new("entry").unwrap();
+        let source = CString::new("pub fn add(a, b) { a + b } pub fn greet(name) { `Hello, ${name}!` }").unwrap();
+        assert!(rune_sources_insert(sources, name.as_ptr(), source.as_ptr()));
+
+        let mut error = ptr::null_mut();
+        let unit = rune_unit_build(context, sources, &mut error);
+        assert!(!unit.is_null());
+        assert!(error.is_null());
+
+        let vm = rune_vm_new(context, unit);
+
+        let args = [rune_value_integer(1), rune_value_integer(2)];
+        let args = [args[0] as *const _, args[1] as *const _];
+        let add = CString::new("add").unwrap();
+        let output = rune_vm_call(vm, add.as_ptr(), args.as_ptr(), 2, &mut error);
+        assert!(error.is_null());
+
+        let mut n = 0;
+        assert!(rune_value_as_integer(output, &mut n));
+        assert_eq!(n, 3);
+        assert!(rune_value_as_string(output).is_null());
+        rune_value_free(output);
+
+        for arg in args {
+            rune_value_free(arg as *mut _);
+        }
+
+        let world = CString::new("World").unwrap();
+        let arg = rune_value_string(world.as_ptr()) as *const _;
+        let greet = CString::new("greet").unwrap();
+        let output = rune_vm_call(vm, greet.as_ptr(), &arg, 1, &mut error);
+        let string = rune_value_as_string(output);
+        assert_eq!(CStr::from_ptr(string).to_str(), Ok("Hello, World!"));
+        rune_string_free(string);
+        rune_value_free(output);
+        rune_value_free(arg as *mut _);
+
+        let missing = CString::new("missing").unwrap();
+        let output = rune_vm_call(vm, missing.as_ptr(), ptr::null(), 0, &mut error);
+        assert!(output.is_null());
+        assert!(!error.is_null());
+        rune_error_free(error);
+
+        rune_vm_free(vm);
+        rune_unit_free(unit);
+        rune_sources_free(sources);
+        rune_context_free(context);
+    }
+}
+
+#[test]
+fn test_build_error() {
+    unsafe {
+        let context = rune_context_new();
+        let sources = rune_sources_new();
+        let name = CString::new("entry").unwrap();
+        let source = CString::new("pub fn main() { let }").unwrap();
+        assert!(rune_sources_insert(sources, name.as_ptr(), source.as_ptr()));
+
+        let mut error = ptr::null_mut();
+        let unit = rune_unit_build(context, sources, &mut error);
+        assert!(unit.is_null());
+        assert!(!error.is_null());
+
+        let message = CStr::from_ptr(rune_error_message(error));
+        assert!(!message.to_bytes().is_empty());
+
+        rune_error_free(error);
+        rune_sources_free(sources);
+        rune_context_free(context);
+    }
+}
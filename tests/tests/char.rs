@@ -22,3 +22,51 @@ fn test_int_conversion() {
 
     assert_eq!(result, 'A');
 }
+
+#[test]
+fn test_classification() {
+    let result: (bool, bool, bool, bool, bool, bool) = rune! {
+        pub fn main() {
+            (
+                'é'.is_alphabetic(),
+                '٣'.is_numeric(),
+                '\t'.is_whitespace(),
+                char::is_uppercase('Q'),
+                '٣'.is_ascii_digit(),
+                '-'.is_ascii_punctuation(),
+            )
+        }
+    };
+
+    assert_eq!(result, (true, true, true, true, false, true));
+}
+
+#[test]
+fn test_conversion() {
+    let result: (Option<u32>, Option<u32>, Option<char>, Option<char>, char, String, bool) = rune! {
+        pub fn main() {
+            (
+                'b'.to_digit(16),
+                '9'.to_digit(8),
+                char::from_u32(0x41),
+                char::from_u32(0xD800),
+                'x'.to_ascii_uppercase(),
+                'ß'.to_uppercase(),
+                'R'.eq_ignore_ascii_case('r'),
+            )
+        }
+    };
+
+    assert_eq!(
+        result,
+        (
+            Some(11),
+            None,
+            Some('A'),
+            None,
+            'X',
+            String::from("SS"),
+            true
+        )
+    );
+}
@@ -1,5 +1,26 @@
+use rune::runtime::VmErrorKind::*;
+use rune::{Any, ContextError, Module};
 use rune_tests::*;
 
+#[derive(Any, Debug, Clone, PartialEq, Eq, Hash)]
+#[rune(eq, hash)]
+struct Point {
+    #[rune(get)]
+    x: i64,
+    #[rune(get)]
+    y: i64,
+}
+
+#[derive(Any)]
+struct Unhashable;
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Point>()?;
+    module.ty::<Unhashable>()?;
+    Ok(module)
+}
+
 #[test]
 fn test_hash_map_tile() {
     let _: () = rune! {
@@ -41,3 +62,278 @@ fn test_hash_set_tuple() {
         }
     };
 }
+
+#[test]
+fn test_hash_map_external_key() {
+    let out: (i64, i64, bool, bool, usize) = rune_n! {
+        make_module().expect("failed making module"),
+        (Point { x: 1, y: 2 }, Point { x: 1, y: 2 }, Point { x: 2, y: 1 }),
+        (i64, i64, bool, bool, usize) =>
+        pub fn main(a, b, c) {
+            use std::collections::{HashMap, HashSet};
+
+            let m = HashMap::new();
+            m.insert(a, 1);
+            m.insert(b, 2);
+            m[(c, "c")] = 3;
+
+            let s = HashSet::new();
+            s.insert(a);
+
+            (m[a], m[(c, "c")], s.contains(b), s.contains(c), m.len())
+        }
+    };
+    assert_eq!(out, (2, 3, true, false, 2));
+}
+
+#[test]
+fn test_hash_map_key_not_supported() {
+    let mut context = rune_tests::modules::default_context().expect("failed to build context");
+    context.install(make_module().expect("failed making module")).expect("failed to install module");
+
+    let error = rune_tests::run::<_, _, ()>(
+        &context,
+        "pub fn main(a) { let m = std::collections::HashMap::new(); m.insert(a, 1); }",
+        ["main"],
+        (Unhashable,),
+    )
+    .expect_err("expected error");
+
+    let (error, _) = error.expect_vm_error("expected vm error").into_unwound();
+    match error.into_kind() {
+        BadArgument { error, arg: 1 } => {
+            assert!(matches!(error.kind(), KeyNotSupported { .. }), "{:?}", error);
+        }
+        actual => panic!("expected bad argument, but was {:?}", actual),
+    }
+}
+
+#[test]
+fn test_collection_conversions() {
+    let out: (Vec<i64>, i64, Option<i64>, bool, Vec<(String, i64)>) = rune! {
+        use std::collections::{HashMap, HashSet};
+
+        pub fn main() {
+            let m = HashMap::from(#{a: 1, b: 2});
+            let s = [3, 1, 2, 1].iter().collect::<HashSet>();
+            let pairs = [("c", 3)].iter().chain(m.iter()).collect::<HashMap>();
+
+            let removed = pairs.remove("b");
+            let gone = s.remove(1) && !s.remove(1);
+
+            let values = s.iter().collect::<Vec>();
+            values.sort();
+
+            let o = pairs.iter().collect::<Object>();
+            let entries = o.iter().collect::<Vec>();
+            (values, m["a"], removed, gone, entries)
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            vec![2, 3],
+            1,
+            Some(2),
+            true,
+            vec![(String::from("a"), 1), (String::from("c"), 3)]
+        )
+    );
+}
+
+#[test]
+fn test_btree_map() {
+    let out: (Vec<(i64, String)>, Vec<i64>, Option<(i64, String)>, Vec<i64>, usize) = rune! {
+        use std::collections::BTreeMap;
+
+        pub fn main() {
+            let m = BTreeMap::new();
+            m.insert(3, "c");
+            m[1] = "a";
+            m.extend([(4, "d"), (2, "b")]);
+
+            let entries = m.iter().collect::<Vec>();
+            let range = m.range(2..=3).rev().map(|(k, _)| k).collect::<Vec>();
+            let last = m.pop_last();
+            let empty = m.range(3..1).map(|(k, _)| k).collect::<Vec>();
+            (entries, range, last, empty, m.len())
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            vec![
+                (1, String::from("a")),
+                (2, String::from("b")),
+                (3, String::from("c")),
+                (4, String::from("d")),
+            ],
+            vec![3, 2],
+            Some((4, String::from("d"))),
+            vec![],
+            3
+        )
+    );
+}
+
+#[test]
+fn test_btree_set() {
+    let out: (Vec<i64>, Vec<i64>, Option<i64>, bool) = rune! {
+        use std::collections::BTreeSet;
+
+        pub fn main() {
+            let s = [5, 1, 4, 1, 3].iter().collect::<BTreeSet>();
+            let values = s.iter().collect::<Vec>();
+            let tail = s.range(3..).collect::<Vec>();
+            (values, tail, s.first(), s == BTreeSet::from([1, 3, 4, 5]))
+        }
+    };
+
+    assert_eq!(out, (vec![1, 3, 4, 5], vec![3, 4, 5], Some(1), true));
+}
+
+#[test]
+fn test_vec_deque() {
+    let out: (Vec<i64>, Option<i64>, Option<i64>, i64) = rune! {
+        use std::collections::VecDeque;
+
+        pub fn main() {
+            let d = VecDeque::from([2, 3]);
+            d.push_front(1);
+            d.push_back(4);
+            d.rotate_right(1);
+
+            let front = d.pop_front();
+            let back = d.back();
+            d[0] = 10;
+            (d.iter().collect::<Vec>(), front, back, d[0])
+        }
+    };
+
+    assert_eq!(out, (vec![10, 2, 3], Some(4), Some(3), 10));
+
+    assert_vm_error!(
+        "pub fn main() { let d = std::collections::VecDeque::new(); d[0] }",
+        OutOfRange { .. } => {}
+    );
+}
+
+#[test]
+fn test_binary_heap() {
+    let out: (Vec<i64>, Option<i64>, Vec<i64>) = rune! {
+        use std::cmp::Ordering;
+        use std::collections::BinaryHeap;
+
+        pub fn main() {
+            let heap = BinaryHeap::from([3, 1, 4, 1, 5, 9, 2, 6]);
+            let popped = [];
+
+            while let Some(n) = heap.pop() {
+                popped.push(n);
+            }
+
+            let min = BinaryHeap::with_comparator(|a, b| {
+                if a < b {
+                    Ordering::Greater
+                } else if a > b {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            });
+
+            min.extend([5, 2, 8]);
+            let first = min.peek();
+            (popped, first, min.into_sorted_vec())
+        }
+    };
+
+    assert_eq!(
+        out,
+        (vec![9, 6, 5, 4, 3, 2, 1, 1], Some(2), vec![8, 5, 2])
+    );
+}
+
+#[test]
+fn test_im_vector() {
+    let out: (Vec<i64>, Vec<i64>, Vec<i64>, Option<i64>, usize) = rune! {
+        use std::collections::im::Vector;
+
+        pub fn main() {
+            let versions = [Vector::new()];
+
+            for n in 0..1100 {
+                let last = versions[versions.len() - 1];
+                versions.push(last.push(n));
+            }
+
+            let full = versions[1100];
+            let changed = full.set(1050, -1);
+
+            let popped = full;
+
+            for n in 0..1060 {
+                popped = popped.pop();
+            }
+
+            (
+                versions[3].iter().collect::<Vec>(),
+                popped.iter().collect::<Vec>(),
+                [full[1050], changed[1050], changed[1049]],
+                popped.push(99).last(),
+                full.len(),
+            )
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            vec![0, 1, 2],
+            (0..40).collect::<Vec<i64>>(),
+            vec![1050, -1, 1049],
+            Some(99),
+            1100
+        )
+    );
+}
+
+#[test]
+fn test_im_ord_map() {
+    let out: (Vec<i64>, Vec<i64>, Option<i64>, usize, usize) = rune! {
+        use std::collections::im::OrdMap;
+
+        pub fn main() {
+            let a = OrdMap::new();
+
+            for n in 0..100 {
+                a = a.insert((n * 37) % 100, n);
+            }
+
+            let b = a;
+
+            for n in 0..100 {
+                if n % 3 != 0 {
+                    b = b.remove(n);
+                }
+            }
+
+            let c = b.insert(1, "one").remove(1000);
+
+            (
+                b.keys().take(5).collect::<Vec>(),
+                a.keys().rev().take(3).collect::<Vec>(),
+                b.get(99),
+                a.len(),
+                c.len(),
+            )
+        }
+    };
+
+    assert_eq!(
+        out,
+        (vec![0, 3, 6, 9, 12], vec![99, 98, 97], Some(27), 100, 35)
+    );
+}
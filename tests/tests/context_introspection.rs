@@ -0,0 +1,111 @@
+use rune::compile::{Item, ItemBuf};
+use rune::{Any, Context, Module};
+
+#[derive(Any)]
+struct Widget;
+
+impl Widget {
+    fn size(&self) -> i64 {
+        1
+    }
+}
+
+fn module(version: i64) -> rune::Result<Module> {
+    let mut module = Module::with_item(["api"]);
+    module.ty::<Widget>()?;
+    module.inst_fn("size", Widget::size)?;
+    module.constant(["VERSION"], version)?;
+
+    if version == 1 {
+        module.function(["widget"], || Widget)?;
+    } else {
+        module.function(["widget"], |_: i64| Widget)?;
+    }
+
+    Ok(module)
+}
+
+#[test]
+fn test_context_introspection() -> rune::Result<()> {
+    let mut context = Context::new();
+    context.install(module(1)?)?;
+
+    let functions = context
+        .iter_functions()
+        .map(|(_, signature)| signature.to_string())
+        .collect::<Vec<_>>();
+
+    assert!(functions.contains(&String::from("api::widget()")));
+    assert!(functions.contains(&String::from("api::Widget::size(#0)")));
+
+    let types = context
+        .iter_types()
+        .map(|(_, ty)| ty.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(types, ["api::Widget"]);
+
+    let constants = context
+        .iter_constants()
+        .map(|(item, _)| item.to_owned())
+        .collect::<Vec<_>>();
+
+    assert_eq!(constants, [ItemBuf::with_item(["api", "VERSION"])]);
+    assert!(context
+        .lookup_docs(&ItemBuf::with_item(["api", "widget"]))
+        .is_some());
+
+    let default = Context::with_default_modules()?;
+
+    assert!(default
+        .iter_macros()
+        .any(|(_, item)| item == &*ItemBuf::with_crate_item("std", ["symbol", "sym"])));
+    assert!(default.lookup_docs(Item::new()).is_none());
+    Ok(())
+}
+
+#[test]
+fn test_context_diff() -> rune::Result<()> {
+    let mut old = Context::new();
+    old.install(module(1)?)?;
+
+    let mut new = Context::new();
+    new.install(module(2)?)?;
+
+    assert!(old.diff(&old).is_empty());
+
+    let diff = old.diff(&new);
+    assert!(diff.added.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(
+        diff.changed,
+        [
+            (
+                String::from("const api::VERSION = Integer(1)"),
+                String::from("const api::VERSION = Integer(2)")
+            ),
+            (
+                String::from("fn api::widget()"),
+                String::from("fn api::widget(#0)")
+            ),
+        ]
+    );
+
+    assert_eq!(
+        diff.to_string(),
+        "- const api::VERSION = Integer(1)\n+ const api::VERSION = Integer(2)\n- fn api::widget()\n+ fn api::widget(#0)\n"
+    );
+
+    let diff = Context::new().diff(&old);
+    assert_eq!(
+        diff.added,
+        [
+            "const api::VERSION = Integer(1)",
+            "fn api::Widget::size(#0)",
+            "fn api::Widget::size(self: test::context_introspection::Widget, #0)",
+            "fn api::widget()",
+            "type api::Widget",
+        ]
+    );
+    Ok(())
+}
@@ -121,3 +121,44 @@ fn test_number_formatting() {
     test_case!("{:/^13b}", 42);
     test_case!("{:/>13b}", 42);
 }
+
+#[test]
+fn test_pretty_debug() {
+    test_case!("{:#?}", [1, 2]);
+    test_case!("{:#?}", (1, "a"));
+    test_case!("{:#?}", [(1, "a"), (2, "b")]);
+    test_case!("{:#?}", Some([1, 2]));
+
+    let out: String = rune!(
+        struct Point { x, y }
+
+        pub fn main() {
+            format!("{:#?}", Point { x: 1, y: 2 })
+        }
+    );
+    assert_eq!(out, "Point {\n    x: 1,\n    y: 2,\n}");
+}
+
+#[test]
+fn test_debug_cycles() {
+    let out: (String, String, String) = rune!(
+        struct Node { value, next }
+
+        pub fn main() {
+            let a = [1];
+            a.push(a);
+
+            let o = #{};
+            o.this = o;
+
+            let n = Node { value: 1, next: None };
+            n.next = Some(n);
+
+            (format!("{:?}", a), format!("{:?}", o), format!("{:?}", n))
+        }
+    );
+
+    assert_eq!(out.0, "[1, ...]");
+    assert_eq!(out.1, "{\"this\": ...}");
+    assert_eq!(out.2, "Node { next: Some(...), value: 1 }");
+}
@@ -72,3 +72,51 @@ fn test_external_enum() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(output, External::Output(2 * 3 * 4));
     Ok(())
 }
+
+/// Tests constructing an external struct variant from within Rune.
+#[test]
+fn test_external_struct_variant() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Debug, Any, PartialEq, Eq)]
+    enum HostEvent {
+        #[rune(constructor)]
+        Click {
+            #[rune(get)]
+            x: u32,
+            #[rune(get)]
+            y: u32,
+        },
+        #[rune(constructor)]
+        Close,
+    }
+
+    let mut module = Module::new();
+    module.ty::<HostEvent>()?;
+
+    let mut context = Context::new();
+    context.install(module)?;
+    let runtime = Arc::new(context.runtime());
+
+    let mut sources = rune::sources! {
+        entry => {
+            pub fn main(event) {
+                match event {
+                    HostEvent::Click { x, y } => HostEvent::Click { x: y, y: x },
+                    HostEvent::Close => HostEvent::Click { x: 0, y: 0 },
+                }
+            }
+        }
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+
+    let mut vm = Vm::new(runtime, Arc::new(unit));
+
+    let output = vm.call(["main"], (HostEvent::Click { x: 1, y: 2 },))?;
+    let output = HostEvent::from_value(output)?;
+    assert_eq!(output, HostEvent::Click { x: 2, y: 1 });
+
+    let output = vm.call(["main"], (HostEvent::Close,))?;
+    let output = HostEvent::from_value(output)?;
+    assert_eq!(output, HostEvent::Click { x: 0, y: 0 });
+    Ok(())
+}
@@ -44,3 +44,50 @@ fn test_getter_setter() -> rune::Result<()> {
     assert!(matches!(output, Value::Unit));
     Ok(())
 }
+
+#[derive(Any, Debug, Default)]
+#[rune(fields)]
+struct Bar {
+    #[rune(copy)]
+    number: i64,
+    string: String,
+    #[rune(skip)]
+    hidden: i64,
+}
+
+#[test]
+fn test_fields_getter_setter() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.ty::<Bar>()?;
+
+    let mut context = rune_modules::default_context()?;
+    context.install(module)?;
+
+    let mut sources = rune::sources! {
+        entry => {
+            pub fn main(bar) {
+                bar.number = bar.number + 1;
+                bar.string = format!("{} World", bar.string);
+            }
+        }
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+
+    let mut bar = Bar {
+        number: 42,
+        string: String::from("Hello"),
+        hidden: 1,
+    };
+
+    let output = vm.call(["main"], (&mut bar,))?;
+
+    assert_eq!(bar.number, 43);
+    assert_eq!(bar.string, "Hello World");
+    assert_eq!(bar.hidden, 1);
+
+    assert!(matches!(output, Value::Unit));
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anyhow::Context as _;
+use rune::runtime::VmError;
+use rune::{Any, Context, ContextError, FromValue, Module, Vm};
+use rune_tests::build;
+use std::sync::Arc;
+
+#[derive(Debug, Any, thiserror::Error)]
+enum StorageError {
+    #[error("missing key {key}")]
+    #[rune(constructor)]
+    NotFound {
+        #[rune(get)]
+        key: String,
+    },
+    #[error("access denied")]
+    #[rune(constructor)]
+    Denied,
+}
+
+fn lookup(key: &str) -> Result<i64, StorageError> {
+    match key {
+        "answer" => Ok(42),
+        "secret" => Err(StorageError::Denied),
+        key => Err(StorageError::NotFound {
+            key: key.to_owned(),
+        }),
+    }
+}
+
+fn load(key: &str) -> Result<i64, VmError> {
+    let value = lookup(key).with_context(|| format!("loading `{}`", key))?;
+    Ok(value)
+}
+
+fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<StorageError>()?;
+    module.function(["lookup"], lookup)?;
+    module.function(["load"], load)?;
+    Ok(module)
+}
+
+fn vm() -> rune::Result<Vm> {
+    let mut context = Context::with_default_modules()?;
+    context.install(module()?)?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub fn lookup_or(key, fallback) {
+            match lookup(key) {
+                Ok(value) => value,
+                Err(StorageError::NotFound { .. }) => fallback,
+                Err(StorageError::Denied) => -1,
+            }
+        }
+
+        pub fn load_key(key) {
+            load(key)
+        }
+        "#,
+    )?;
+
+    Ok(Vm::new(Arc::new(context.runtime()), unit))
+}
+
+#[test]
+fn test_match_host_error() -> rune::Result<()> {
+    let mut vm = vm()?;
+
+    let output = i64::from_value(vm.call(["lookup_or"], ("answer", 0i64))?)?;
+    assert_eq!(output, 42);
+
+    let output = i64::from_value(vm.call(["lookup_or"], ("missing", 7i64))?)?;
+    assert_eq!(output, 7);
+
+    let output = i64::from_value(vm.call(["lookup_or"], ("secret", 7i64))?)?;
+    assert_eq!(output, -1);
+    Ok(())
+}
+
+#[test]
+fn test_downcast_host_error() -> rune::Result<()> {
+    let mut vm = vm()?;
+
+    let error = vm.call(["load_key"], ("missing",)).unwrap_err();
+
+    let host = error.as_host().expect("expected host error");
+    assert_eq!(host.to_string(), "loading `missing`");
+
+    match error.downcast_ref::<StorageError>() {
+        Some(StorageError::NotFound { key }) => assert_eq!(key, "missing"),
+        actual => panic!("unexpected error {:?}", actual),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use rune::{Any, Context, ContextError, FromValue, Module, Vm};
+use rune_tests::build;
+use std::sync::Arc;
+
+#[derive(Any)]
+struct Counter {
+    value: i64,
+}
+
+#[rune::impl_block]
+impl Counter {
+    /// Construct a new counter.
+    fn new(value: i64) -> Self {
+        Self { value }
+    }
+
+    /// Increment the counter.
+    fn increment(&mut self) {
+        self.value += 1;
+    }
+
+    /// Get the value of the counter.
+    async fn get(&self) -> i64 {
+        self.value
+    }
+
+    #[rune(skip)]
+    fn hidden(&self) -> i64 {
+        self.value
+    }
+}
+
+fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Counter>()?;
+    Counter::install_functions(&mut module)?;
+    Ok(module)
+}
+
+#[test]
+fn test_impl_block() -> rune::Result<()> {
+    let mut context = Context::with_default_modules()?;
+    context.install(module()?)?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub async fn main() {
+            let counter = Counter::new(40);
+            counter.increment();
+            counter.increment();
+            counter.get().await
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+    let output = futures_executor::block_on(vm.async_call(["main"], ()))?;
+    assert_eq!(i64::from_value(output)?, 42);
+    Ok(())
+}
+
+#[test]
+fn test_impl_block_skip() -> rune::Result<()> {
+    let mut context = Context::with_default_modules()?;
+    context.install(module()?)?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub fn main(counter) {
+            counter.hidden()
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+    assert!(vm.call(["main"], (Counter { value: 1 },)).is_err());
+    assert_eq!(Counter { value: 1 }.hidden(), 1);
+    Ok(())
+}
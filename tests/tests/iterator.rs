@@ -163,3 +163,137 @@ fn test_flat_map() {
 
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn test_size_hint() {
+    let out: Vec<(i64, Option<i64>)> = rune! {
+        use std::iter::range;
+
+        pub fn main() {
+            [
+                range(0, 10).size_hint(),
+                range(0, 10).map(|n| n).skip(3).size_hint(),
+                range(0, 10).take(4).enumerate().size_hint(),
+                range(0, 10).filter(|n| n % 2 == 0).size_hint(),
+                range(0, 10).chain([1, 2].iter()).rev().size_hint(),
+            ]
+        }
+    };
+
+    assert_eq!(
+        out,
+        vec![
+            (10, Some(10)),
+            (7, Some(7)),
+            (4, Some(4)),
+            (0, Some(10)),
+            (12, Some(12)),
+        ]
+    );
+}
+
+#[test]
+fn test_len() {
+    let out: (i64, i64) = rune! {
+        pub fn main() {
+            let it = [1, 2, 3, 4].iter();
+            it.next();
+            (it.len(), it.skip(1).take(10).len())
+        }
+    };
+
+    assert_eq!(out, (3, 2));
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2].iter().filter(|n| true).len() }"#,
+        Panic { reason } => {
+            assert!(reason.to_string().contains("is not an exact-sized iterator"));
+        }
+    );
+}
+
+#[test]
+fn test_is_empty() {
+    let out: (bool, bool, bool) = rune! {
+        pub fn main() {
+            let it = [1].iter();
+            let before = it.is_empty();
+            it.next();
+            (before, it.is_empty(), [1, 2].iter().skip(2).is_empty())
+        }
+    };
+
+    assert_eq!(out, (false, true, true));
+}
+
+#[test]
+fn test_take_rev() {
+    let out: (Vec<i64>, Vec<i64>) = rune! {
+        use std::iter::range;
+
+        pub fn main() {
+            (
+                range(0, 10).take(3).rev().collect::<Vec>(),
+                range(1, 4).flat_map(|n| range(0, n)).rev().collect::<Vec>(),
+            )
+        }
+    };
+
+    assert_eq!(out.0, vec![2, 1, 0]);
+    assert_eq!(
+        out.1,
+        (1..4).flat_map(|n| 0..n).rev().collect::<Vec<i64>>()
+    );
+}
+
+#[test]
+fn test_zip_chunks_windows() {
+    let out: (Vec<(i64, char)>, Vec<Vec<(i64, i64)>>, Vec<Vec<i64>>, Vec<Vec<i64>>) = rune! {
+        use std::iter::range;
+
+        pub fn main() {
+            (
+                range(0, 10).zip(['a', 'b', 'c']).collect::<Vec>(),
+                [10, 20, 30].iter().enumerate().chunks(2).collect::<Vec>(),
+                range(0, 4).windows(3).collect::<Vec>(),
+                range(0, 2).windows(3).collect::<Vec>(),
+            )
+        }
+    };
+
+    assert_eq!(out.0, vec![(0, 'a'), (1, 'b'), (2, 'c')]);
+    assert_eq!(out.1, vec![vec![(0, 10), (1, 20)], vec![(2, 30)]]);
+    assert_eq!(out.2, vec![vec![0, 1, 2], vec![1, 2, 3]]);
+    assert!(out.3.is_empty());
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2].iter().chunks(0) }"#,
+        Panic { reason } => {
+            assert!(reason.to_string().contains("chunk size must be non-zero"));
+        }
+    );
+}
+
+#[test]
+fn test_while_partition_group_by() {
+    let out: (Vec<i64>, Vec<i64>, (Vec<i64>, Vec<i64>), Vec<(bool, Vec<i64>)>) = rune! {
+        use std::iter::range;
+
+        pub fn main() {
+            (
+                range(0, 10).take_while(|n| n < 3).collect::<Vec>(),
+                [1, 2, 5, 1].iter().skip_while(|n| n < 3).collect::<Vec>(),
+                range(0, 6).partition(|n| n % 2 == 0),
+                [1, 3, 4, 6, 7].iter().group_by(|n| n % 2 == 0).collect::<Vec>(),
+            )
+        }
+    };
+
+    assert_eq!(out.0, vec![0, 1, 2]);
+    assert_eq!(out.1, vec![5, 1]);
+    assert_eq!(out.2, (vec![0, 2, 4], vec![1, 3, 5]));
+    assert_eq!(
+        out.3,
+        vec![(false, vec![1, 3]), (true, vec![4, 6]), (false, vec![7])]
+    );
+}
@@ -0,0 +1,50 @@
+use rune::runtime::ConstValue;
+use rune::{Context, Module};
+use rune_tests::*;
+
+#[test]
+fn test_module_constants() -> rune::Result<()> {
+    let mut module = Module::with_item(["math"]);
+    module.constant(["TAU"], 6.28)?;
+    module.constant(
+        ["PAIR"],
+        ConstValue::Tuple(vec![ConstValue::Integer(1), ConstValue::String("two".into())].into()),
+    )?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let out: f64 = run(&context, "pub fn main() { math::TAU }", ["main"], ())?;
+    assert_eq!(out, 6.28);
+
+    let out: f64 = run(&context, "const DOUBLE = math::TAU * 2.0; pub fn main() { DOUBLE }", ["main"], ())?;
+    assert_eq!(out, 12.56);
+
+    let out: (i64, String) = run(&context, "pub fn main() { math::PAIR }", ["main"], ())?;
+    assert_eq!(out, (1, String::from("two")));
+
+    let out: ((i64, String), bool) = run(
+        &context,
+        "const NESTED = (math::PAIR, true); pub fn main() { NESTED }",
+        ["main"],
+        (),
+    )?;
+    assert_eq!(out, ((1, String::from("two")), true));
+
+    let out: f64 = run(
+        &context,
+        "use math::TAU; const HALF = TAU / 2.0; pub fn main() { HALF }",
+        ["main"],
+        (),
+    )?;
+    assert_eq!(out, 3.14);
+
+    let out: f64 = run(
+        &context,
+        "mod inner { pub const QUARTER = math::TAU / 4.0; } pub fn main() { inner::QUARTER }",
+        ["main"],
+        (),
+    )?;
+    assert_eq!(out, 1.57);
+    Ok(())
+}
@@ -12,6 +12,17 @@ fn test_patterns() {
     );
     assert_eq!(out, 10);
 
+    let out: i64 = rune!(
+        pub fn main() {
+            let _unused = 1;
+
+            match 10 {
+                _n => _n + _unused,
+            }
+        }
+    );
+    assert_eq!(out, 11);
+
     let out: i64 = rune!(
         pub fn main() {
             match 10 {
@@ -0,0 +1,89 @@
+use rune_tests::*;
+
+#[test]
+fn test_type_of() {
+    let out: (String, String, bool, bool, bool, String) = rune!(
+        use std::any::{type_name, type_of};
+
+        struct Point { x, y }
+
+        pub fn main() {
+            let p = Point { x: 1, y: 2 };
+            let t = type_of(p);
+
+            (
+                type_name(p),
+                t.name(),
+                t == type_of(Point { x: 3, y: 4 }),
+                t == type_of("hello"),
+                t.is_instance(p) && !t.is_instance(1),
+                format!("{:?}", type_of("hello")),
+            )
+        }
+    );
+
+    assert_eq!(
+        out,
+        (
+            String::from("Point"),
+            String::from("Point"),
+            true,
+            false,
+            true,
+            String::from("Type(::std::string::String)"),
+        )
+    );
+}
+
+#[test]
+fn test_type_fields() {
+    let out: (Option<Vec<String>>, Option<Vec<String>>, Option<Vec<String>>, String) = rune!(
+        use std::any::type_of;
+
+        struct Point { y, x }
+        enum Shape { Circle { radius }, Empty }
+
+        pub fn main() {
+            let kind = match type_of(1).name() {
+                "::std::string::String" => "string",
+                "::std::int" => "int",
+                _ => "other",
+            };
+
+            (
+                type_of(Point { x: 1, y: 2 }).fields(),
+                type_of(Shape::Circle { radius: 1.0 }).fields(),
+                type_of(Shape::Empty).fields(),
+                kind,
+            )
+        }
+    );
+
+    assert_eq!(
+        out,
+        (
+            Some(vec![String::from("x"), String::from("y")]),
+            Some(vec![String::from("radius")]),
+            None,
+            String::from("int"),
+        )
+    );
+}
+
+#[test]
+fn test_type_id_of_val() {
+    let out: (bool, bool) = rune!(
+        use std::any::TypeId;
+
+        struct Point { x, y }
+
+        pub fn main() {
+            let a = format!("{}", TypeId::of_val(Point { x: 1, y: 2 }));
+            let b = format!("{}", TypeId::of_val(Point { x: 3, y: 4 }));
+            let c = format!("{}", TypeId::of_val("hello"));
+            (a == b, a == c)
+        }
+    );
+
+    assert_eq!(out, (true, false));
+}
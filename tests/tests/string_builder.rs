@@ -0,0 +1,37 @@
+use rune_tests::*;
+
+#[test]
+fn test_string_builder() {
+    let out: (String, usize, bool) = rune!(
+        use std::string::Builder;
+
+        pub fn main() {
+            let b = Builder::with_capacity(16);
+            let empty = b.is_empty();
+
+            for n in 0..3 {
+                b.append(n);
+                b.push(',');
+            }
+
+            b.push_str("a");
+            b += 'b';
+            b += true;
+            (b.build(), b.len(), empty)
+        }
+    );
+
+    assert_eq!(out, (String::from("0,1,2,abtrue"), 12, true));
+
+    let out: String = rune!(
+        use std::string::Builder;
+
+        pub fn main() {
+            let b = Builder::new();
+            b += "hello";
+            format!("{} world", b)
+        }
+    );
+
+    assert_eq!(out, "hello world");
+}
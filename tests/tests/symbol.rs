@@ -0,0 +1,45 @@
+use rune::runtime::Symbol;
+use rune_tests::*;
+
+#[test]
+fn test_symbol() {
+    let out: (bool, bool, String, String, Symbol) = rune!(
+        pub fn main() {
+            let name = "red";
+            let a = sym!("red");
+            let b = Symbol::new(name);
+            (a == b, a == sym!("blue"), a.as_str(), format!("{} {:?}", a, a), a)
+        }
+    );
+
+    assert_eq!(
+        out,
+        (
+            true,
+            false,
+            String::from("red"),
+            String::from("red sym!(\"red\")"),
+            Symbol::new("red")
+        )
+    );
+}
+
+#[test]
+fn test_symbol_keys() {
+    let out: (i64, i64, Option<i64>) = rune!(
+        use std::collections::HashMap;
+
+        pub fn main() {
+            let map = HashMap::new();
+            map.insert(sym!("a"), 1);
+            map.insert(Symbol::new("a"), 2);
+
+            let object = #{};
+            object[sym!("b")] = 3;
+
+            (map.len() + map[sym!("a")], object[sym!("b")] + object.b, map.get(sym!("c")))
+        }
+    );
+
+    assert_eq!(out, (3, 6, None));
+}
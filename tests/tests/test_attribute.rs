@@ -89,3 +89,269 @@ fn deny_nested_bench() {
         }
     }
 }
+
+#[test]
+fn ignore_and_should_panic() {
+    let _: () = rune! {
+        #[test]
+        #[ignore]
+        fn ignored() {
+            assert!(true != true);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics() {
+            panic("expected");
+        }
+
+        #[bench]
+        #[ignore]
+        fn ignored_bench(b) {
+        }
+
+        pub fn main() {
+        }
+    };
+}
+
+#[test]
+fn deny_ignore_outside_of_test() {
+    assert_compile_error! {
+        r#"#[ignore] fn function() {}"#,
+        span, Custom { message } => {
+            assert_eq!(message.as_ref(), "#[ignore] is only supported on #[test] and #[bench] functions");
+            assert_eq!(span, span!(0, 9));
+        }
+    }
+
+    assert_compile_error! {
+        r#"#[bench] #[should_panic] fn function(b) {}"#,
+        span, Custom { message } => {
+            assert_eq!(message.as_ref(), "#[should_panic] is only supported on #[test] functions");
+            assert_eq!(span, span!(9, 24));
+        }
+    }
+}
+
+#[test]
+fn run_tests() -> rune::Result<()> {
+    use rune::testing::{self, TestFailure, TestOptions, TestOutcome};
+    use rune::Context;
+
+    let context = Context::with_default_modules()?;
+
+    let source = r#"
+    #[test]
+    fn passes() {
+        assert_eq!(1 + 1, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics() {
+        panic("expected");
+    }
+
+    #[test]
+    #[ignore]
+    fn ignored() {
+        assert!(true != true);
+    }
+
+    #[test]
+    fn returns_none() {
+        None
+    }
+
+    #[test]
+    fn after_failure() {
+    }
+    "#;
+
+    let report = futures_executor::block_on(testing::run_tests(
+        &context,
+        &mut sources(source),
+        &TestOptions::default(),
+    ));
+
+    let outcomes = report
+        .tests
+        .iter()
+        .map(|test| (test.item.to_string(), &test.outcome))
+        .collect::<Vec<_>>();
+
+    assert!(!report.is_success());
+    assert!(matches!(
+        &outcomes[..],
+        [
+            (_, TestOutcome::Passed),
+            (_, TestOutcome::Passed),
+            (_, TestOutcome::Ignored),
+            (_, TestOutcome::Failed(TestFailure::ReturnedNone)),
+            (_, TestOutcome::Skipped),
+        ]
+    ));
+    assert_eq!(outcomes[0].0, "passes");
+
+    let mut options = TestOptions::default();
+    options.filters.push(String::from("pa"));
+
+    let report =
+        futures_executor::block_on(testing::run_tests(&context, &mut sources(source), &options));
+
+    assert!(report.is_success());
+    assert_eq!(report.tests.len(), 2);
+    assert_eq!(report.filtered, 3);
+
+    let report = futures_executor::block_on(testing::run_tests(
+        &context,
+        &mut sources("#[test] fn broken() { missing }"),
+        &TestOptions::default(),
+    ));
+
+    assert!(!report.is_success());
+    assert!(report.diagnostics.has_error());
+    assert!(report.tests.is_empty());
+    Ok(())
+}
+
+#[test]
+fn run_tests_should_panic() -> rune::Result<()> {
+    use rune::testing::{self, TestFailure, TestOptions, TestOutcome};
+    use rune::Context;
+
+    let context = Context::with_default_modules()?;
+
+    let source = r#"
+    #[test]
+    #[should_panic]
+    fn panics() {
+        assert_eq!(1, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn type_error() {
+        1 + "a"
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_a_function() {
+        let f = 42;
+        f()
+    }
+
+    #[test]
+    #[should_panic]
+    fn does_not_panic() {
+    }
+    "#;
+
+    let mut options = TestOptions::default();
+    options.fail_fast = false;
+
+    let report =
+        futures_executor::block_on(testing::run_tests(&context, &mut sources(source), &options));
+
+    let outcomes = report
+        .tests
+        .iter()
+        .map(|test| &test.outcome)
+        .collect::<Vec<_>>();
+
+    assert!(matches!(
+        &outcomes[..],
+        [
+            TestOutcome::Passed,
+            TestOutcome::Failed(TestFailure::Crash(..)),
+            TestOutcome::Failed(TestFailure::Crash(..)),
+            TestOutcome::Failed(TestFailure::DidNotPanic),
+        ]
+    ));
+    Ok(())
+}
+
+#[test]
+fn run_tests_in_parallel() -> rune::Result<()> {
+    use rune::testing::{self, TestOptions, TestOutcome};
+    use rune::Context;
+    use std::num::NonZeroUsize;
+
+    let context = Context::with_default_modules()?;
+
+    let source = r#"
+    #[test]
+    fn first() {
+        println!("first");
+    }
+
+    #[test]
+    fn second() {
+        println!("second");
+        eprintln!("to stderr");
+    }
+
+    #[test]
+    fn third() {
+        println!("third");
+        assert!(false);
+    }
+
+    #[test]
+    #[ignore]
+    fn ignored() {
+        println!("ignored");
+    }
+
+    #[test]
+    fn fourth() {
+    }
+    "#;
+
+    let mut options = TestOptions::default();
+    options.jobs = NonZeroUsize::new(3).expect("not zero");
+    options.capture = true;
+    options.fail_fast = false;
+
+    let report =
+        futures_executor::block_on(testing::run_tests(&context, &mut sources(source), &options));
+
+    let outcomes = report
+        .tests
+        .iter()
+        .map(|test| {
+            let output = String::from_utf8(test.output.clone()).expect("utf-8 output");
+            (test.item.to_string(), output, &test.outcome)
+        })
+        .collect::<Vec<_>>();
+
+    assert!(matches!(
+        &outcomes[..],
+        [
+            (_, _, TestOutcome::Passed),
+            (_, _, TestOutcome::Passed),
+            (_, _, TestOutcome::Failed(..)),
+            (_, _, TestOutcome::Ignored),
+            (_, _, TestOutcome::Passed),
+        ]
+    ));
+
+    let outputs = outcomes
+        .iter()
+        .map(|(item, output, _)| (item.as_str(), output.as_str()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        outputs,
+        [
+            ("first", "first\n"),
+            ("second", "second\nto stderr\n"),
+            ("third", "third\n"),
+            ("ignored", ""),
+            ("fourth", ""),
+        ]
+    );
+    Ok(())
+}
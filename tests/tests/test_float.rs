@@ -58,3 +58,39 @@ fn test_float_fns() {
     };
     assert_eq!(n, 1728.0);
 }
+
+#[test]
+fn test_float_to_fixed() {
+    let out: (String, String, String) = rune! {
+        pub fn main() {
+            (3.14159.to_fixed(2), 2.0.to_fixed(3), (-0.5).to_fixed(0))
+        }
+    };
+    assert_eq!(
+        out,
+        (
+            String::from("3.14"),
+            String::from("2.000"),
+            String::from("-0")
+        )
+    );
+}
+
+#[test]
+fn test_float_cmp() {
+    let out: (bool, bool, bool, bool) = rune! {
+        use std::cmp::Ordering;
+
+        pub fn main() {
+            let nan = 0.0 / 0.0;
+
+            (
+                1.5.cmp(2.0) == Ordering::Less,
+                nan.cmp(1.0) == Ordering::Greater && (-nan).cmp(nan) == Ordering::Equal,
+                1.0.partial_cmp(1.0) == Some(Ordering::Equal),
+                nan.partial_cmp(1.0).is_none(),
+            )
+        }
+    };
+    assert_eq!(out, (true, true, true, true));
+}
@@ -0,0 +1,64 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_dynamic_format() {
+    let out: (String, String, String, String, String) = rune! {
+        pub fn main() {
+            (
+                std::fmt::format("{} + {} = {}", 1, 2, 3),
+                std::fmt::format("{1}{0}{{}}", "a", "b"),
+                std::fmt::format("[{:*^7}][{:>4}][{:<4}]", "mid", 12, 3),
+                std::fmt::format("{:+.2} {:x} {:08b}", 3.14159, 255, 5),
+                std::fmt::format("{:.*} {name}", 1, 1.26, #{ name: "joe" }),
+            )
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            String::from("1 + 2 = 3"),
+            String::from("ba{}"),
+            String::from("[**mid**][  12][3   ]"),
+            String::from("+3.14 ff 00000101"),
+            String::from("1.3 joe"),
+        )
+    );
+}
+
+#[test]
+fn test_string_format() {
+    let out: String = rune! {
+        pub fn main() {
+            let spec = "{:?} and {}";
+            spec.format(["debug", "display"])
+        }
+    };
+
+    assert_eq!(out, "\"debug\" and display");
+}
+
+#[test]
+fn test_format_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { std::fmt::format("{} {}", 1) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "missing positional argument #1");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::fmt::format("{name}", 1) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "missing named argument `name`");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::fmt::format("{") }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "unexpected end of format string");
+        }
+    );
+}
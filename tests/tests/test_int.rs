@@ -1,3 +1,4 @@
+use rune::runtime::VmErrorKind::*;
 use rune_tests::*;
 
 #[test]
@@ -58,3 +59,80 @@ fn test_int_fns() {
     };
     assert_eq!(n, 1728);
 }
+
+#[test]
+fn test_int_radix() {
+    let out: (Result<i64, ()>, bool, String, String) = rune! {
+        pub fn main() {
+            (
+                std::int::parse_radix("-ff", 16).map_err(|_| ()),
+                std::int::parse_radix("12", 2).is_err(),
+                255.to_string_radix(2),
+                (-35).to_string_radix(36),
+            )
+        }
+    };
+    assert_eq!(
+        out,
+        (Ok(-255), true, String::from("11111111"), String::from("-z"))
+    );
+
+    assert_vm_error!(
+        "pub fn main() { 10.to_string_radix(1) }",
+        Panic { reason } => {
+            assert!(reason.to_string().contains("radix must be in the range 2..=36"));
+        }
+    );
+}
+
+#[test]
+fn test_int_separated() {
+    let out: (String, String, String) = rune! {
+        pub fn main() {
+            (
+                1234567.to_string_separated(','),
+                (-1000).to_string_separated('_'),
+                999.to_string_separated(','),
+            )
+        }
+    };
+    assert_eq!(
+        out,
+        (
+            String::from("1,234,567"),
+            String::from("-1_000"),
+            String::from("999")
+        )
+    );
+}
+
+#[test]
+fn test_int_checked() {
+    let out: (Option<i64>, Option<i64>, Option<i64>, i64) = rune! {
+        pub fn main() {
+            (
+                2.checked_pow(62),
+                2.checked_pow(63),
+                std::int::checked_neg(-9223372036854775807 - 1),
+                (-9223372036854775807 - 1).saturating_div(-1),
+            )
+        }
+    };
+    assert_eq!(out, (Some(1 << 62), None, None, i64::MAX));
+}
+
+#[test]
+fn test_int_cmp() {
+    let out: (bool, bool, bool) = rune! {
+        use std::cmp::Ordering;
+
+        pub fn main() {
+            (
+                1.cmp(2) == Ordering::Less,
+                2.cmp(2) == Ordering::Equal,
+                std::int::cmp(3, 2) == Ordering::Greater,
+            )
+        }
+    };
+    assert_eq!(out, (true, true, true));
+}
@@ -0,0 +1,72 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_constants() {
+    let out: (f64, f64, f64) = rune! {
+        use std::math;
+
+        const HALF_TAU = math::TAU / 2.0;
+
+        pub fn main() {
+            (HALF_TAU, math::PI, math::E)
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            std::f64::consts::PI,
+            std::f64::consts::PI,
+            std::f64::consts::E
+        )
+    );
+}
+
+#[test]
+fn test_functions() {
+    let out: (f64, f64, f64, f64, f64, f64) = rune! {
+        use std::math;
+
+        pub fn main() {
+            (
+                math::hypot(3.0, 4.0),
+                math::sin(math::PI / 2.0),
+                math::log2(8.0),
+                math::clamp(12.0, 0.0, 10.0),
+                math::lerp(10.0, 20.0, 0.25),
+                math::sqrt(16.0),
+            )
+        }
+    };
+
+    assert_eq!(out, (5.0, 1.0, 3.0, 10.0, 12.5, 4.0));
+}
+
+#[test]
+fn test_rounding() {
+    let out: (f64, f64, f64, f64, f64, f64, f64) = rune! {
+        use std::math;
+
+        pub fn main() {
+            (
+                math::floor(-1.5),
+                math::ceil(-1.5),
+                math::round(2.5),
+                math::trunc(-2.7),
+                math::round_ties_even(2.5),
+                math::round_ties_even(-3.5),
+                math::round_to(3.14159, 2),
+            )
+        }
+    };
+
+    assert_eq!(out, (-2.0, -1.0, 3.0, -2.0, 2.0, -4.0, 3.14));
+
+    assert_vm_error!(
+        "pub fn main() { std::math::clamp(1.0, 2.0, 0.0) }",
+        Panic { reason } => {
+            assert!(reason.to_string().contains("clamp requires min <= max"));
+        }
+    );
+}
@@ -0,0 +1,63 @@
+use rune_tests::*;
+use std::collections::HashMap;
+
+#[test]
+fn test_iter() {
+    let out: (Vec<(String, i64)>, Vec<String>, Vec<i64>) = rune! {
+        pub fn main() {
+            let object = #{ b: 2, a: 1 };
+
+            (
+                object.iter().collect::<Vec>(),
+                object.keys().collect::<Vec>(),
+                object.values().collect::<Vec>(),
+            )
+        }
+    };
+
+    assert_eq!(out.0, vec![(String::from("a"), 1), (String::from("b"), 2)]);
+    assert_eq!(out.1, vec!["a", "b"]);
+    assert_eq!(out.2, vec![1, 2]);
+}
+
+#[test]
+fn test_merge_retain_remove() {
+    let out: (HashMap<String, i64>, Option<i64>, Option<i64>) = rune! {
+        pub fn main() {
+            let object = #{ a: 1, b: 2, c: 3 };
+            object.merge(#{ b: 20, d: 4 });
+            object.retain(|key, value| key != "c");
+
+            let removed = object.remove("a");
+            let missing = object.remove("a");
+            (object, removed, missing)
+        }
+    };
+
+    let expected = [("b", 20), ("d", 4)]
+        .into_iter()
+        .map(|(k, v)| (String::from(k), v))
+        .collect::<HashMap<_, _>>();
+
+    assert_eq!(out.0, expected);
+    assert_eq!(out.1, Some(1));
+    assert_eq!(out.2, None);
+}
+
+#[test]
+fn test_map_filter() {
+    let out: (HashMap<String, String>, Vec<String>) = rune! {
+        pub fn main() {
+            let object = #{ a: 1, b: 2, c: 3 };
+
+            (
+                object.map(|key, value| format!("{}={}", key, value)),
+                object.filter(|key, value| value % 2 == 1).keys().collect::<Vec>(),
+            )
+        }
+    };
+
+    assert_eq!(out.0["b"], "b=2");
+    assert_eq!(out.0.len(), 3);
+    assert_eq!(out.1, vec!["a", "c"]);
+}
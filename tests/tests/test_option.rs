@@ -70,3 +70,54 @@ fn test_unwrap() {
         }
     );
 }
+
+#[test]
+fn test_or_else() {
+    let out: (Option<i64>, Option<i64>) = rune! {
+        pub fn main() {
+            (Some(1).or_else(|| Some(2)), None.or_else(|| Some(2)))
+        }
+    };
+    assert_eq!(out, (Some(1), Some(2)))
+}
+
+#[test]
+fn test_ok_or() {
+    let out: (Result<i64, String>, Result<i64, String>) = rune! {
+        pub fn main() {
+            (Some(1).ok_or("missing"), None.ok_or_else(|| "missing"))
+        }
+    };
+    assert_eq!(out, (Ok(1), Err(String::from("missing"))))
+}
+
+#[test]
+fn test_filter_zip_flatten() {
+    let out: (Option<i64>, Option<i64>, Option<(i64, char)>, Option<i64>, Option<i64>) = rune! {
+        pub fn main() {
+            (
+                Some(4).filter(|n| n % 2 == 0),
+                Some(3).filter(|n| n % 2 == 0),
+                Some(1).zip(Some('a')),
+                Some(Some(1)).flatten(),
+                Some(None).flatten(),
+            )
+        }
+    };
+    assert_eq!(out, (Some(4), None, Some((1, 'a')), Some(1), None))
+}
+
+#[test]
+fn test_unwrap_or_default() {
+    let out: (i64, i64, String, Vec<i64>) = rune! {
+        pub fn main() {
+            (
+                Some(7).unwrap_or_default::<int>(),
+                None.unwrap_or_default::<int>(),
+                None.unwrap_or_default::<String>(),
+                None.unwrap_or_default::<Vec>(),
+            )
+        }
+    };
+    assert_eq!(out, (7, 0, String::new(), Vec::new()))
+}
@@ -0,0 +1,70 @@
+//! Tests for catching panics.
+
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_catch_ok() {
+    let out: Result<i64, String> = rune! {
+        pub fn main() {
+            std::panic::catch(|| 42)
+        }
+    };
+    assert_eq!(out, Ok(42));
+}
+
+#[test]
+fn test_catch_panic() {
+    let out: Result<i64, String> = rune! {
+        fn fail(n) {
+            if n > 2 {
+                panic("too deep");
+            }
+
+            fail(n + 1)
+        }
+
+        pub fn main() {
+            std::panic::catch(|| fail(0))
+        }
+    };
+    assert_eq!(out, Err("too deep".to_owned()));
+}
+
+#[test]
+fn test_catch_continues_execution() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let caught = 0;
+
+            for n in 0..4 {
+                let result = std::panic::catch(|| {
+                    if n % 2 == 0 {
+                        panic("even");
+                    }
+
+                    n
+                });
+
+                if result.is_err() {
+                    caught += 1;
+                }
+            }
+
+            caught
+        }
+    };
+    assert_eq!(out, 2);
+}
+
+#[test]
+fn test_catch_propagates_errors() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            std::panic::catch(|| 1 / 0)
+        }
+        "#,
+        DivideByZero => {}
+    );
+}
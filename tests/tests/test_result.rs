@@ -93,3 +93,39 @@ fn test_unwrap_or() {
     };
     assert_eq!(out, 10);
 }
+
+#[test]
+fn test_map_err() {
+    let out: Result<i64, i64> = rune! {
+        pub fn main() {
+            Err(1).map_err(|e| e + 1)
+        }
+    };
+    assert_eq!(out, Err(2));
+}
+
+#[test]
+fn test_or_else() {
+    let out: (Result<i64, String>, Result<i64, String>) = rune! {
+        pub fn main() {
+            (
+                Err("fail").or_else(|e| Ok(1)),
+                Err("fail").or_else(|e| Err(e + "ed")),
+            )
+        }
+    };
+    assert_eq!(out, (Ok(1), Err(String::from("failed"))));
+}
+
+#[test]
+fn test_unwrap_or_default() {
+    let out: (i64, bool) = rune! {
+        pub fn main() {
+            (
+                Err("Error").unwrap_or_default::<int>(),
+                Ok(true).unwrap_or_default::<bool>(),
+            )
+        }
+    };
+    assert_eq!(out, (0, true));
+}
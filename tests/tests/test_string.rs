@@ -0,0 +1,62 @@
+use rune_tests::*;
+
+#[test]
+fn test_case_and_padding() {
+    let out: (String, String, String, String, String) = rune! {
+        pub fn main() {
+            (
+                "Straße".to_uppercase(),
+                "ÀB".to_lowercase(),
+                "7".pad_start(3, '0'),
+                "é".pad_end(3, '.'),
+                "ab".repeat(3),
+            )
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            String::from("STRASSE"),
+            String::from("àb"),
+            String::from("007"),
+            String::from("é.."),
+            String::from("ababab"),
+        )
+    );
+}
+
+#[test]
+fn test_splitting() {
+    let out: (Vec<String>, Vec<String>, Vec<(usize, char)>) = rune! {
+        pub fn main() {
+            (
+                "  a \t b\nc ".split_whitespace().collect::<Vec>(),
+                "k=v=w".splitn(2, '=').collect::<Vec>(),
+                "aé!".char_indices().collect::<Vec>(),
+            )
+        }
+    };
+
+    assert_eq!(out.0, vec!["a", "b", "c"]);
+    assert_eq!(out.1, vec!["k", "v=w"]);
+    assert_eq!(out.2, vec![(0, 'a'), (1, 'é'), (3, '!')]);
+}
+
+#[test]
+fn test_strip() {
+    let out: (Option<String>, Option<String>, Option<String>) = rune! {
+        pub fn main() {
+            (
+                "v1.2".strip_prefix("v"),
+                "file.rs".strip_suffix(".rs"),
+                "file.rs".strip_prefix("x"),
+            )
+        }
+    };
+
+    assert_eq!(
+        out,
+        (Some(String::from("1.2")), Some(String::from("file")), None)
+    );
+}
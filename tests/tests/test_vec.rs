@@ -0,0 +1,135 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_sort_by() {
+    let out: (Vec<i64>, Vec<f64>, Vec<String>) = rune! {
+        pub fn main() {
+            let numbers = [3, 1, 2];
+            numbers.sort_by(|a, b| b.cmp(a));
+
+            let floats = [0.5, -1.0, 2.5];
+            floats.sort_by(|a, b| a.cmp(b));
+
+            let words = ["pear", "fig", "banana", "kiwi"];
+            words.sort_by_key(|w| w.len());
+
+            (numbers, floats, words)
+        }
+    };
+
+    assert_eq!(out.0, vec![3, 2, 1]);
+    assert_eq!(out.1, vec![-1.0, 0.5, 2.5]);
+    assert_eq!(out.2, vec!["fig", "pear", "kiwi", "banana"]);
+}
+
+#[test]
+fn test_binary_search_by() {
+    let out: (Result<usize, usize>, Result<usize, usize>) = rune! {
+        pub fn main() {
+            let numbers = [1, 3, 5, 7];
+            (
+                numbers.binary_search_by(|n| n.cmp(5)),
+                numbers.binary_search_by(|n| n.cmp(4)),
+            )
+        }
+    };
+
+    assert_eq!(out, (Ok(2), Err(2)));
+}
+
+#[test]
+fn test_min_max_by_key() {
+    let out: (Option<(i64, i64)>, Option<(i64, i64)>, Option<i64>) = rune! {
+        pub fn main() {
+            let pairs = [(0, 2), (1, 1), (2, 2), (3, 1)];
+            (
+                pairs.max_by_key(|p| p.1),
+                pairs.min_by_key(|p| p.1),
+                [].max_by_key(|n| n),
+            )
+        }
+    };
+
+    assert_eq!(out, (Some((2, 2)), Some((1, 1)), None));
+}
+
+#[test]
+fn test_sort_by_errors() {
+    assert_vm_error!(
+        "pub fn main() { [1, 2].sort_by(|a, b| panic(\"boom\")) }",
+        Panic { reason } => {
+            assert!(reason.to_string().contains("boom"));
+        }
+    );
+}
+
+#[test]
+fn test_retain_dedup() {
+    let out: (Vec<i64>, Vec<String>) = rune! {
+        pub fn main() {
+            let numbers = [1, 2, 3, 4, 5, 6];
+            numbers.retain(|n| n % 2 == 0);
+
+            let words = ["a", "a", "b", "a", "c", "c"];
+            words.dedup();
+
+            (numbers, words)
+        }
+    };
+
+    assert_eq!(out.0, vec![2, 4, 6]);
+    assert_eq!(out.1, vec!["a", "b", "a", "c"]);
+}
+
+#[test]
+fn test_rotate_swap_remove() {
+    let out: (Vec<i64>, Vec<i64>, i64, Vec<i64>) = rune! {
+        pub fn main() {
+            let left = [1, 2, 3, 4];
+            left.rotate_left(1);
+
+            let right = [1, 2, 3, 4];
+            right.rotate_right(1);
+
+            let v = [1, 2, 3, 4];
+            let removed = v.swap_remove(0);
+
+            (left, right, removed, v)
+        }
+    };
+
+    assert_eq!(out.0, vec![2, 3, 4, 1]);
+    assert_eq!(out.1, vec![4, 1, 2, 3]);
+    assert_eq!(out.2, 1);
+    assert_eq!(out.3, vec![4, 2, 3]);
+
+    assert_vm_error!(
+        "pub fn main() { [1].swap_remove(1) }",
+        OutOfRange { .. } => {}
+    );
+}
+
+#[test]
+fn test_splice() {
+    let out: (Vec<i64>, Vec<i64>, Vec<i64>) = rune! {
+        pub fn main() {
+            let v = [1, 2, 3, 4, 5];
+            let removed = v.splice(1..3, [10, 20, 30]);
+
+            let tail = [1, 2, 3];
+            tail.splice(1.., std::iter::once(9));
+
+            (v, removed, tail)
+        }
+    };
+
+    assert_eq!(out.0, vec![1, 10, 20, 30, 4, 5]);
+    assert_eq!(out.1, vec![2, 3]);
+    assert_eq!(out.2, vec![1, 9]);
+
+    assert_vm_error!(
+        "pub fn main() { [1, 2].splice(1..5, []) }",
+        OutOfRange { .. } => {}
+    );
+}
@@ -1,4 +1,6 @@
+use rune::runtime::ConstValue;
 use rune::Hash;
+use std::sync::Arc;
 
 #[test]
 fn test_get_const() -> rune::Result<()> {
@@ -77,3 +79,47 @@ fn test_get_const_nested() -> rune::Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_const_strings_interned() -> rune::Result<()> {
+    let context = rune_modules::default_context()?;
+
+    let mut sources = rune::sources! {
+        entry => {
+            pub const FIRST = "hello world";
+            pub const SECOND = ("hello world", ["hello world"]);
+
+            pub fn main() {
+                "hello world"
+            }
+        },
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+
+    let literal = unit
+        .iter_static_strings()
+        .find(|s| ****s == "hello world")
+        .expect("static string");
+
+    let first = match unit.constant(Hash::type_hash(["FIRST"])) {
+        Some(ConstValue::StaticString(s)) => s,
+        value => panic!("unexpected constant {:?}", value),
+    };
+
+    assert!(Arc::ptr_eq(first, literal));
+
+    let second = match unit.constant(Hash::type_hash(["SECOND"])) {
+        Some(ConstValue::Tuple(values)) => match &values[..] {
+            [ConstValue::StaticString(a), ConstValue::Vec(vec)] => match &vec[..] {
+                [ConstValue::StaticString(b)] => [a, b],
+                value => panic!("unexpected constant {:?}", value),
+            },
+            value => panic!("unexpected constant {:?}", value),
+        },
+        value => panic!("unexpected constant {:?}", value),
+    };
+
+    assert!(second.iter().all(|s| Arc::ptr_eq(s, literal)));
+    Ok(())
+}
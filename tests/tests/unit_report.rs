@@ -0,0 +1,36 @@
+#[test]
+fn test_unit_report() -> rune::Result<()> {
+    let context = rune_modules::default_context()?;
+
+    let mut sources = rune::sources! {
+        entry => {
+            const NAME = "constant";
+
+            fn helper(a, b) {
+                let c = a + b;
+                c * 2
+            }
+
+            pub fn main() {
+                let s = "hello world";
+                let b = b"bytes";
+                helper(1, 2)
+            }
+        }
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    let report = unit.report();
+
+    assert_eq!(report.functions.len(), 2);
+    assert_eq!(
+        report.functions.iter().map(|f| f.instructions).sum::<usize>(),
+        report.instructions
+    );
+    assert!(report.functions[0].instructions >= report.functions[1].instructions);
+    assert!(report.static_strings_size >= "hello world".len());
+    assert_eq!(report.static_bytes, 1);
+    assert_eq!(report.static_bytes_size, 5);
+    assert!(report.constants >= 1);
+    Ok(())
+}
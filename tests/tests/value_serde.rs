@@ -0,0 +1,86 @@
+use rune::runtime::{from_value, to_value};
+use rune::{Context, FromValue, Vm};
+use rune_tests::build;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Event {
+    Click { x: i64, y: i64 },
+    Key(char),
+    Move(i64, i64),
+    Close,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    retries: Option<u32>,
+    ratio: f64,
+    tags: Vec<String>,
+    pair: (i64, bool),
+    events: Vec<Event>,
+    limits: BTreeMap<String, i64>,
+}
+
+fn config() -> Config {
+    Config {
+        name: String::from("server"),
+        retries: Some(3),
+        ratio: 0.5,
+        tags: vec![String::from("a"), String::from("b")],
+        pair: (1, true),
+        events: vec![
+            Event::Click { x: 1, y: 2 },
+            Event::Key('q'),
+            Event::Move(3, 4),
+            Event::Close,
+        ],
+        limits: [(String::from("cpu"), 4)].into_iter().collect(),
+    }
+}
+
+#[test]
+fn test_roundtrip() -> rune::Result<()> {
+    let value = to_value(&config())?;
+    let output: Config = from_value(value)?;
+    assert_eq!(output, config());
+    Ok(())
+}
+
+#[test]
+fn test_script_access() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub fn main(config) {
+            config.retries = None;
+            config.tags.push("c");
+            config.events.push("Close");
+            config
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+    let output = vm.call(["main"], (to_value(&config())?,))?;
+    let output: Config = from_value(output)?;
+
+    let mut expected = config();
+    expected.retries = None;
+    expected.tags.push(String::from("c"));
+    expected.events.push(Event::Close);
+    assert_eq!(output, expected);
+
+    let name = String::from_value(to_value("hello")?)?;
+    assert_eq!(name, "hello");
+    Ok(())
+}
+
+#[test]
+fn test_overflow() {
+    assert!(to_value(&u64::MAX).is_err());
+}
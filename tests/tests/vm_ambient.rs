@@ -0,0 +1,103 @@
+use futures_executor::block_on;
+use rune::runtime::{Ambient, VmPool};
+use rune::{Context, FromValue, Module, Vm};
+use rune_tests::build;
+use std::sync::Arc;
+
+fn user_name() -> Result<String, rune::runtime::VmError> {
+    Ok(rune::runtime::ambient::get::<String>("user")?.unwrap_or_else(|| String::from("nobody")))
+}
+
+#[test]
+fn test_ambient() -> rune::Result<()> {
+    let mut module = Module::with_item(["host"]);
+    module.function(["user_name"], user_name)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let unit = build(
+        &context,
+        r#"
+        fn inner() {
+            let request = std::env::ambient("request")?;
+            Some(request.id)
+        }
+
+        pub fn main() {
+            let closure = || std::env::ambient("request").map(|r| r.path);
+            let gen = { fn g() { yield host::user_name(); } g() };
+            Some((inner()?, closure()?, gen.next()?))
+        }
+
+        pub async fn async_main() {
+            host::user_name()
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+    assert!(Option::<(i64, String, String)>::from_value(vm.call(["main"], ())?)?.is_none());
+
+    let mut request = rune::runtime::Object::new();
+    request.insert(String::from("id"), rune::Value::from(42i64));
+    request.insert(String::from("path"), rune::Value::from(String::from("/")));
+
+    let mut ambient = Ambient::new();
+    ambient.insert("request", request)?;
+    ambient.insert("user", "john")?;
+    vm.set_ambient(ambient);
+
+    let output = Option::<(i64, String, String)>::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, Some((42, String::from("/"), String::from("john"))));
+
+    let output = String::from_value(block_on(vm.async_call(["async_main"], ()))?)?;
+    assert_eq!(output, "john");
+
+    vm.clear_ambient();
+    let output = String::from_value(block_on(vm.async_call(["async_main"], ()))?)?;
+    assert_eq!(output, "nobody");
+
+    let pool = VmPool::new(vm.context().clone(), vm.unit().clone());
+
+    {
+        let mut vm = pool.checkout();
+        let mut ambient = Ambient::new();
+        ambient.insert("user", "jane")?;
+        vm.set_ambient(ambient);
+    }
+
+    assert!(pool.checkout().ambient().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_ambient_send_execute() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub async fn main() {
+            std::env::ambient("user")
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+
+    let mut ambient = Ambient::new();
+    ambient.insert("user", "john")?;
+    vm.set_ambient(ambient);
+
+    let future = vm
+        .send_execute(["main"], ())?
+        .async_complete_into::<Option<String>>();
+
+    let output = std::thread::spawn(move || block_on(future))
+        .join()
+        .unwrap()?;
+
+    assert_eq!(output.as_deref(), Some("john"));
+    Ok(())
+}
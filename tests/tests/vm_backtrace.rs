@@ -0,0 +1,87 @@
+use rune_tests::*;
+
+fn function_names(error: &rune::runtime::VmError) -> Vec<String> {
+    error
+        .backtrace()
+        .expect("missing backtrace")
+        .frames()
+        .iter()
+        .map(|f| match &f.function {
+            Some((_, signature)) => signature.path.to_string(),
+            None => String::from("?"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_backtrace_frames() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        fn third(n) {
+            let limit = 10;
+
+            if n > limit {
+                panic("too large");
+            }
+
+            n
+        }
+
+        fn second(n) {
+            third(n * 2)
+        }
+
+        fn first(a, b) {
+            let sum = a + b;
+            second(sum)
+        }
+
+        pub fn main() {
+            first(3, 4)
+        }
+    };
+
+    let error = vm.call(["main"], ()).unwrap_err();
+    assert_eq!(function_names(&error), ["third", "second", "first", "main"]);
+
+    let backtrace = error.backtrace().expect("missing backtrace");
+    let frames = backtrace.frames();
+
+    let names = |n: usize| {
+        frames[n]
+            .variables
+            .iter()
+            .flatten()
+            .map(|name| name.as_ref())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names(0), ["n", "limit"]);
+    assert_eq!(names(1), ["n"]);
+    assert_eq!(names(2), ["a", "b", "sum"]);
+    assert!(frames.iter().all(|f| f.location.is_some()));
+
+    let rendered = backtrace.to_string();
+    assert!(rendered.contains("third(n)"));
+    assert!(rendered.contains("locals: a b sum"));
+    Ok(())
+}
+
+#[test]
+fn test_backtrace_shadowed_scope() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            {
+                let inner = 1;
+            }
+
+            let outer = 2;
+            outer + ()
+        }
+    };
+
+    let error = vm.call(["main"], ()).unwrap_err();
+    let backtrace = error.backtrace().expect("missing backtrace");
+    let frame = &backtrace.frames()[0];
+    assert_eq!(frame.variables.first().and_then(|v| v.as_deref()), Some("outer"));
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use futures_executor::block_on;
+use rune::runtime::{Capabilities, Executor, Function, SpawnFuture, VmError, VmErrorKind};
+use rune::{Any, Context, FromValue, Module, Value, Vm};
+use rune_tests::build;
+use std::sync::Arc;
+
+/// An executor which drives every spawned future on a thread of its own.
+struct Threads;
+
+impl Executor for Threads {
+    fn spawn(&self, future: SpawnFuture) {
+        std::thread::spawn(move || block_on(future));
+    }
+}
+
+#[derive(Any)]
+struct Socket;
+
+impl Socket {
+    fn send(&self, n: i64) -> i64 {
+        n
+    }
+}
+
+fn vm() -> rune::Result<Vm> {
+    let mut net = Module::with_item(["net"]).with_capability("net");
+    net.ty::<Socket>()?;
+    net.function(["connect"], || Socket)?;
+    net.inst_fn("send", Socket::send)?;
+
+    let mut util = Module::with_item(["util"]);
+    util.function(["double"], |n: i64| n * 2)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(net)?;
+    context.install(util)?;
+    context.set_executor(Threads);
+
+    let unit = build(
+        &context,
+        r#"
+        pub fn connect() {
+            let f = net::connect;
+            f().send(1)
+        }
+
+        pub fn socket() {
+            net::connect()
+        }
+
+        pub fn send(socket) {
+            let gen = { fn g(s) { yield s.send(2); } g(socket) };
+            gen.next()
+        }
+
+        pub fn double() {
+            util::double(21)
+        }
+
+        pub fn connect_fn() {
+            net::connect
+        }
+
+        pub fn map() {
+            [3].iter().map(|n| net::connect().send(n)).collect::<Vec>()
+        }
+
+        pub async fn spawn_thread() {
+            std::thread::spawn(|| net::connect().send(4)).await
+        }
+
+        pub async fn spawn_future() {
+            std::future::spawn(|| net::connect().send(5)).await
+        }
+        "#,
+    )?;
+
+    Ok(Vm::new(Arc::new(context.runtime()), unit))
+}
+
+fn assert_missing_capability(error: VmError, expected: &str) {
+    match error.into_unwound().0.into_kind() {
+        VmErrorKind::MissingCapability { capability, .. } => assert_eq!(&*capability, expected),
+        actual => panic!("expected missing capability but got `{:?}`", actual),
+    }
+}
+
+#[test]
+fn test_capabilities() -> rune::Result<()> {
+    let mut vm = vm()?;
+
+    assert_eq!(i64::from_value(vm.call(["connect"], ())?)?, 1);
+    let socket = vm.call(["socket"], ())?;
+
+    vm.set_capabilities(Capabilities::new());
+    assert_eq!(i64::from_value(vm.call(["double"], ())?)?, 42);
+
+    assert_missing_capability(vm.call(["connect"], ()).unwrap_err(), "net");
+
+    assert_missing_capability(vm.call(["send"], (socket.clone(),)).unwrap_err(), "net");
+
+    vm.set_capabilities(["fs", "net"].into_iter().collect());
+    assert_eq!(i64::from_value(vm.call(["connect"], ())?)?, 1);
+    assert_eq!(
+        Option::<i64>::from_value(vm.call(["send"], (socket,))?)?,
+        Some(2)
+    );
+
+    vm.clear_capabilities();
+    assert!(vm.capabilities().is_none());
+    assert_eq!(i64::from_value(vm.call(["connect"], ())?)?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_capabilities_in_started_virtual_machines() -> rune::Result<()> {
+    let mut vm = vm()?;
+
+    assert_eq!(Vec::<i64>::from_value(vm.call(["map"], ())?)?, [3]);
+    assert_eq!(i64::from_value(block_on(vm.async_call(["spawn_thread"], ()))?)?, 4);
+    assert_eq!(i64::from_value(block_on(vm.async_call(["spawn_future"], ()))?)?, 5);
+
+    vm.set_capabilities(Capabilities::new());
+    assert_missing_capability(vm.call(["map"], ()).unwrap_err(), "net");
+
+    for name in ["spawn_thread", "spawn_future"] {
+        let error = block_on(vm.async_call([name], ())).unwrap_err();
+        assert_missing_capability(error, "net");
+    }
+
+    vm.set_capabilities(Capabilities::new().with("net"));
+    assert_eq!(i64::from_value(block_on(vm.async_call(["spawn_thread"], ()))?)?, 4);
+    Ok(())
+}
+
+#[test]
+fn test_capabilities_outside_of_virtual_machine() -> rune::Result<()> {
+    let mut vm = vm()?;
+    let connect = Function::from_value(vm.call(["connect_fn"], ())?)?;
+
+    // NB: it is unknown which capabilities have been granted outside of a
+    // virtual machine, so gated functions can't be called.
+    assert_missing_capability(connect.call::<_, Value>(()).unwrap_err(), "net");
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use rune::runtime::{channel, Executor, SendValue, SpawnFuture};
+use rune::{Context, FromValue};
+use rune_tests::*;
+
+struct Threads;
+
+impl Executor for Threads {
+    fn spawn(&self, future: SpawnFuture) {
+        std::thread::spawn(move || futures_executor::block_on(future));
+    }
+}
+
+fn context() -> rune::Result<Context> {
+    let mut context = rune_modules::default_context()?;
+    context.set_executor(Threads);
+    Ok(context)
+}
+
+#[test]
+fn test_channel_between_tasks() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context()? =>
+        use std::sync::mpsc;
+
+        async fn produce(sender, from) {
+            for n in from..from + 10 {
+                sender.send(n).await?;
+            }
+        }
+
+        pub async fn main() {
+            let (sender, receiver) = mpsc::channel(2);
+
+            let a = std::future::spawn(|| produce(sender.clone(), 0));
+            let b = std::future::spawn(|| produce(sender.clone(), 100));
+            drop(sender);
+
+            let sum = 0;
+            let count = 0;
+
+            while let Some(n) = receiver.recv().await {
+                sum += n;
+                count += 1;
+            }
+
+            a.await;
+            b.await;
+            (count, sum)
+        }
+    };
+
+    let output = futures_executor::block_on(vm.async_call(["main"], ()))?;
+    let (count, sum) = <(i64, i64)>::from_value(output)?;
+    assert_eq!(count, 20);
+    assert_eq!(sum, 45 + 1045);
+    Ok(())
+}
+
+#[test]
+fn test_channel_select() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context()? =>
+        use std::sync::mpsc;
+
+        pub async fn main() {
+            let (a_sender, a) = mpsc::channel(1);
+            let (b_sender, b) = mpsc::unbounded();
+            b_sender.try_send("b")?;
+
+            let value = select {
+                value = a.recv() => value,
+                value = b.recv() => value,
+            };
+
+            (value, a_sender.is_closed(), b.is_empty())
+        }
+    };
+
+    let output = futures_executor::block_on(vm.async_call(["main"], ()))?;
+    let (value, closed, empty) = <(Option<String>, bool, bool)>::from_value(output)?;
+    assert_eq!(value.as_deref(), Some("b"));
+    assert!(!closed);
+    assert!(empty);
+    Ok(())
+}
+
+#[test]
+fn test_channel_bounded_and_closed() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context()? =>
+        use std::sync::mpsc;
+
+        pub fn main() {
+            let (sender, receiver) = mpsc::channel(1);
+            let first = sender.try_send(1);
+            let full = sender.try_send(2);
+            let len = receiver.len();
+            drop(receiver);
+            let closed = sender.try_send(3);
+            (first, full, len, closed, sender.is_closed())
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let (first, full, len, closed, is_closed) = <(
+        Result<(), i64>,
+        Result<(), i64>,
+        usize,
+        Result<(), i64>,
+        bool,
+    )>::from_value(output)?;
+
+    assert_eq!(first, Ok(()));
+    assert_eq!(full, Err(2));
+    assert_eq!(len, 1);
+    assert_eq!(closed, Err(3));
+    assert!(is_closed);
+    Ok(())
+}
+
+#[test]
+fn test_channel_from_rust() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context()? =>
+        pub async fn main(receiver, sender) {
+            let out = [];
+
+            while let Some(value) = receiver.recv().await {
+                out.push(value);
+            }
+
+            sender.send(out).await?;
+        }
+    };
+
+    let (to_script, receiver) = channel::unbounded();
+    let (sender, from_script) = channel::bounded(1);
+
+    let thread = std::thread::spawn(move || {
+        for n in 0..3 {
+            to_script.try_send(SendValue::Integer(n)).unwrap();
+        }
+    });
+
+    futures_executor::block_on(vm.async_call(["main"], (receiver, sender)))?;
+    thread.join().unwrap();
+
+    let output = futures_executor::block_on(from_script.recv()).expect("missing output");
+    let output = Vec::<i64>::from_value(output.into_value())?;
+    assert_eq!(output, vec![0, 1, 2]);
+    assert!(from_script.is_closed());
+    Ok(())
+}
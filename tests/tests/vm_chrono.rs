@@ -0,0 +1,76 @@
+use rune::FromValue;
+use rune_tests::*;
+
+#[test]
+fn test_parse_and_format() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        use chrono::DateTime;
+
+        pub fn main() {
+            let a = DateTime::parse_rfc3339("2023-03-01T12:30:15+01:00")?;
+            let b = DateTime::parse_from_str("2023-03-01 12:30:15 +0100", "%Y-%m-%d %H:%M:%S %z")?;
+
+            Ok((
+                a == b,
+                a.to_rfc3339(),
+                a.format("%Y/%m/%d"),
+                (a.year(), a.month(), a.day(), a.hour(), a.minute(), a.second()),
+                a.offset_seconds(),
+                DateTime::parse_rfc3339("not a date").is_err(),
+            ))
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let (eq, rfc3339, formatted, parts, offset, error) = <Result<
+        (
+            bool,
+            String,
+            Result<String, rune::Value>,
+            (i64, i64, i64, i64, i64, i64),
+            i64,
+            bool,
+        ),
+        rune::Value,
+    >>::from_value(output)?
+    .map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert!(eq);
+    assert_eq!(rfc3339, "2023-03-01T12:30:15+01:00");
+    assert_eq!(formatted.ok().as_deref(), Some("2023/03/01"));
+    assert_eq!(parts, (2023, 3, 1, 12, 30, 15));
+    assert_eq!(offset, 3600);
+    assert!(error);
+    Ok(())
+}
+
+#[test]
+fn test_arithmetic() -> rune::Result<()> {
+    let output: Result<(i64, i64, bool, i64), rune::Value> = rune! {
+        use chrono::{DateTime, Duration};
+
+        pub fn main() {
+            let start = DateTime::from_timestamp(1_000_000).unwrap();
+            let end = (start + (Duration::days(2)? + Duration::hours(3)?)?)?;
+            let back = (end - Duration::minutes(30)?)?;
+
+            Ok((
+                end.timestamp(),
+                end.signed_duration_since(start).num_hours(),
+                back < end,
+                Duration::seconds(90)?.num_minutes(),
+            ))
+        }
+    };
+    assert_eq!(output.ok(), Some((1_000_000 + 51 * 3600, 51, true, 1)));
+    Ok(())
+}
+
+#[test]
+fn test_host_conversion() -> rune::Result<()> {
+    let now = chrono::Utc::now();
+    let value = rune_modules::chrono::DateTime::from(now);
+    let back = chrono::DateTime::<chrono::Utc>::from(value);
+    assert_eq!(now, back);
+    Ok(())
+}
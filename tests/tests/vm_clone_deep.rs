@@ -0,0 +1,83 @@
+use rune::runtime::VmErrorKind::*;
+use rune::{Any, ContextError, Module};
+use rune_tests::*;
+
+#[derive(Any, Debug, Clone, PartialEq)]
+#[rune(eq, clone)]
+struct Counter {
+    #[rune(get, set)]
+    value: i64,
+}
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Counter>()?;
+    Ok(module)
+}
+
+#[test]
+fn test_clone_deep_aggregates() {
+    let out: (i64, i64, i64, i64) = rune! {
+        struct Point { x, y }
+
+        pub fn main() {
+            let a = [[1], #{ b: [2] }, (3,), Point { x: [4], y: 0 }];
+            let b = a.clone_deep();
+
+            a[0].push(10);
+            a[1].b.push(20);
+            a[2].0 = 30;
+            a[3].x.push(40);
+
+            (b[0].len(), b[1].b.len(), b[2].0, b[3].x.len())
+        }
+    };
+    assert_eq!(out, (1, 1, 3, 1));
+
+    let out: bool = rune! {
+        pub fn main() {
+            let a = #{ a: [1, (2, 3)], b: Some(#{ c: "d" }) };
+            a == a.clone_deep()
+        }
+    };
+    assert!(out);
+}
+
+#[test]
+fn test_clone_deep_native() {
+    let out: (i64, i64, bool, bool) = rune_n! {
+        make_module().expect("failed making module"),
+        (Counter { value: 1 },),
+        (i64, i64, bool, bool) =>
+        pub fn main(counter) {
+            let a = [counter];
+            let b = a.clone_deep();
+            let equal = a == b;
+            a[0].value = 10;
+            (a[0].value, b[0].value, equal, a == b)
+        }
+    };
+    assert_eq!(out, (10, 1, true, false));
+}
+
+#[test]
+fn test_clone_deep_errors() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let a = [];
+            a.push(a);
+            a.clone_deep()
+        }
+        "#,
+        AccessError { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        fn gen() { yield 1; }
+        pub fn main() { [gen()].clone_deep() }
+        "#,
+        CloneNotSupported { .. } => {}
+    );
+}
@@ -0,0 +1,113 @@
+use rune::runtime::VmErrorKind::*;
+use rune::{Any, ContextError, Module};
+use rune_tests::*;
+
+#[derive(Any, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[rune(eq, partial_cmp, cmp)]
+struct Version {
+    #[rune(get)]
+    major: i64,
+    #[rune(get)]
+    minor: i64,
+}
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Version>()?;
+    Ok(module)
+}
+
+#[test]
+fn test_compare_builtin() {
+    let out: (bool, bool, bool, bool, bool, bool) = rune! {
+        pub fn main() {
+            let b = "b";
+            (
+                "a" < b,
+                (1, 2) < (1, 3),
+                [1, 2] < [1, 2, 0],
+                Some(1) > None,
+                [Some("a"), None] >= [Some("a"), None],
+                1.0 / 0.0 > 0.0,
+            )
+        }
+    };
+    assert_eq!(out, (true, true, true, true, true, true));
+}
+
+#[test]
+fn test_compare_script_type() {
+    let out: (bool, bool, bool, Vec<i64>) = rune! {
+        use std::cmp::Ordering;
+
+        struct Point { x, y }
+
+        impl Point {
+            fn cmp(self, other) {
+                let a = self.x * self.x + self.y * self.y;
+                let b = other.x * other.x + other.y * other.y;
+
+                if a < b {
+                    Ordering::Less
+                } else if a > b {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            }
+        }
+
+        pub fn main() {
+            let points = [Point { x: 3, y: 4 }, Point { x: 0, y: 1 }, Point { x: 2, y: 2 }];
+            let lt = Point { x: 1, y: 1 } < Point { x: 0, y: 2 };
+            let ge = Point { x: 2, y: 0 } >= Point { x: 0, y: 2 };
+            let is_greater = match points[0].cmp(points[1]) {
+                Ordering::Greater => true,
+                _ => false,
+            };
+
+            points.sort();
+            (lt, ge, is_greater, points.iter().map(|p| p.x).collect::<Vec>())
+        }
+    };
+    assert_eq!(out, (true, true, true, vec![0, 2, 3]));
+}
+
+#[test]
+fn test_compare_external_type() {
+    let out: (bool, Vec<i64>, bool) = rune_n! {
+        make_module().expect("failed making module"),
+        (
+            Version { major: 1, minor: 2 },
+            Version { major: 0, minor: 9 },
+            Version { major: 1, minor: 0 },
+        ),
+        (bool, Vec<i64>, bool) =>
+        pub fn main(a, b, c) {
+            let versions = [a, b, c];
+            versions.sort();
+
+            let minors = versions.iter().map(|v| v.minor).collect::<Vec>();
+            (a > c, minors, b <= c)
+        }
+    };
+    assert_eq!(out, (true, vec![9, 0, 2], true));
+}
+
+#[test]
+fn test_compare_errors() {
+    assert_vm_error!(
+        "pub fn main() { [1, \"a\"].sort() }",
+        UnsupportedBinaryOperation { op: "cmp", .. } => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { [1.0, 0.0 / 0.0].sort() }",
+        UnsupportedBinaryOperation { op: "cmp", .. } => {}
+    );
+
+    assert_vm_error!(
+        "struct Foo; pub fn main() { Foo < Foo }",
+        UnsupportedBinaryOperation { op: "<", .. } => {}
+    );
+}
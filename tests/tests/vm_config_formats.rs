@@ -0,0 +1,37 @@
+use rune_tests::*;
+
+#[test]
+fn test_toml() -> rune::Result<()> {
+    let output: Result<(i64, Vec<String>, String), rune::Value> = rune! {
+        pub fn main() {
+            let data = toml::from_string("[server]\nport = 8080\nhosts = [\"a\", \"b\"]")?;
+            let port = data["server"]["port"];
+            let hosts = data["server"]["hosts"];
+            Ok((port, hosts, toml::to_string(#{ name: "rune" })?))
+        }
+    };
+    let (port, hosts, encoded) = output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert_eq!(port, 8080);
+    assert_eq!(hosts, ["a", "b"]);
+    assert_eq!(encoded, "name = \"rune\"\n");
+    Ok(())
+}
+
+#[test]
+fn test_yaml() -> rune::Result<()> {
+    let output: Result<(i64, Vec<String>, String), rune::Value> = rune! {
+        pub fn main() {
+            let data = yaml::from_string("server:\n  port: 8080\n  hosts:\n    - a\n    - b\n")?;
+            let port = data["server"]["port"];
+            let hosts = data["server"]["hosts"];
+            Ok((port, hosts, yaml::to_string(#{ name: "rune" })?))
+        }
+    };
+    let (port, hosts, encoded) = output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert_eq!(port, 8080);
+    assert_eq!(hosts, ["a", "b"]);
+    assert_eq!(encoded, "name: rune\n");
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use rune::runtime::Bytes;
+use rune::FromValue;
+use rune_tests::*;
+use std::fmt::Write;
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::new();
+
+    for b in bytes {
+        write!(out, "{:02x}", b).unwrap();
+    }
+
+    out
+}
+
+#[test]
+fn test_digests() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            (
+                crypto::md5("abc"),
+                crypto::sha1(b"abc"),
+                crypto::sha256("abc"),
+                crypto::sha512("abc"),
+            )
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let (md5, sha1, sha256, sha512) = <(Bytes, Bytes, Bytes, Bytes)>::from_value(output)?;
+
+    assert_eq!(hex(&md5), "900150983cd24fb0d6963f7d28e17f72");
+    assert_eq!(hex(&sha1), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(
+        hex(&sha256),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(sha512.len(), 64);
+    Ok(())
+}
+
+#[test]
+fn test_hmac_and_compare() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            let mac = crypto::hmac_sha256("key", "The quick brown fox jumps over the lazy dog");
+            let other = crypto::hmac_sha256(b"key", b"The quick brown fox jumps over the lazy dog");
+
+            (
+                mac,
+                crypto::constant_time_eq(mac, other),
+                crypto::constant_time_eq("secret", "secreT"),
+                crypto::constant_time_eq("secret", "secret!"),
+            )
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let (mac, same, differs, longer) = <(Bytes, bool, bool, bool)>::from_value(output)?;
+
+    assert_eq!(
+        hex(&mac),
+        "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+    );
+    assert!(same);
+    assert!(!differs);
+    assert!(!longer);
+    Ok(())
+}
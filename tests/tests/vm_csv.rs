@@ -0,0 +1,77 @@
+use rune::FromValue;
+use rune_tests::*;
+use std::collections::HashMap;
+
+#[test]
+fn test_read() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        use csv::Reader;
+
+        pub fn main() {
+            let objects = csv::from_string("name,age\nAda,36\n\"Turing, Alan\",41\n")?;
+            let records = csv::records_from_string("a,b\n1,2\n")?;
+
+            let reader = Reader::from_string_with("x;y\n1;2\n3;4\n", #{ delimiter: ";" })?;
+            let headers = reader.headers()?;
+            let rows = [];
+
+            while let Some(row) = reader.read_record()? {
+                rows.push(row);
+            }
+
+            Ok((objects, records, headers, rows))
+        }
+    };
+
+    type Output = (
+        Vec<HashMap<String, String>>,
+        Vec<Vec<String>>,
+        Vec<String>,
+        Vec<Vec<String>>,
+    );
+
+    let output = vm.call(["main"], ())?;
+    let (objects, records, headers, rows) = <Result<Output, rune::Value>>::from_value(output)?
+        .map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert_eq!(objects.len(), 2);
+    assert_eq!(objects[1]["name"], "Turing, Alan");
+    assert_eq!(objects[1]["age"], "41");
+    assert_eq!(records, [["a", "b"], ["1", "2"]]);
+    assert_eq!(headers, ["x", "y"]);
+    assert_eq!(rows, [["1", "2"], ["3", "4"]]);
+    Ok(())
+}
+
+#[test]
+fn test_write() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        use csv::Writer;
+
+        pub fn main() {
+            let writer = Writer::new();
+            writer.write_record(["name", "score", "active"])?;
+            writer.write_record(["Grace, H.", 4.5, true])?;
+            writer.write_record(["Linus", 3, None])?;
+
+            let objects = csv::to_string([
+                #{ b: 2, a: "one" },
+                #{ a: "three" },
+            ])?;
+
+            let tabs = Writer::with_options(#{ delimiter: "\t" })?;
+            tabs.write_record([1, 2])?;
+
+            Ok((writer.finish()?, objects, tabs.finish()?))
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let (records, objects, tabs) = <Result<(String, String, String), rune::Value>>::from_value(output)?
+        .map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert_eq!(records, "name,score,active\n\"Grace, H.\",4.5,true\nLinus,3,\n");
+    assert_eq!(objects, "a,b\none,2\nthree,\n");
+    assert_eq!(tabs, "1\t2\n");
+    Ok(())
+}
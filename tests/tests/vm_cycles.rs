@@ -0,0 +1,133 @@
+use rune::runtime::CycleCollector;
+use rune::{FromValue, Value, Vm};
+use rune_tests::*;
+use std::rc::Rc;
+
+
+fn with_collector(mut vm: Vm, collector: CycleCollector) -> (Vm, Rc<CycleCollector>) {
+    let collector = Rc::new(collector);
+    vm.set_cycle_collector(collector.clone());
+    (vm, collector)
+}
+
+#[test]
+fn test_collect_self_referencing_vec() -> rune::Result<()> {
+    let (mut vm, collector) = with_collector(rune_vm! {
+        pub fn main() {
+            let v = [];
+            v.push(v);
+        }
+    }, CycleCollector::new());
+
+    vm.call(["main"], ())?;
+    assert_eq!(collector.collected(), 1);
+    assert_eq!(collector.tracked(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_collect_closure_cycle() -> rune::Result<()> {
+    let (mut vm, collector) = with_collector(rune_vm! {
+        pub fn main() {
+            let o = #{ value: 42 };
+            o.get = || o.value;
+            (o.get)()
+        }
+    }, CycleCollector::new());
+
+    let output = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 42);
+    // NB: the object and the closure.
+    assert_eq!(collector.collected(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_collect_cycles_from_functions_called_natively() -> rune::Result<()> {
+    let (mut vm, collector) = with_collector(rune_vm! {
+        pub fn main() {
+            [1, 2, 3].iter().map(|n| {
+                let v = [];
+                v.push(v);
+                n
+            }).collect::<Vec>()
+        }
+    }, CycleCollector::new());
+
+    vm.call(["main"], ())?;
+    assert_eq!(collector.collected(), 3);
+    assert_eq!(collector.tracked(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_reachable_cycles_are_released() -> rune::Result<()> {
+    let (mut vm, collector) = with_collector(rune_vm! {
+        pub fn main() {
+            let o = #{};
+            o.this = o;
+            o
+        }
+    }, CycleCollector::new());
+
+    let output = vm.call(["main"], ())?;
+    assert_eq!(collector.collected(), 0);
+    assert_eq!(collector.tracked(), 0);
+
+    let object = output.into_object()?.take()?;
+    assert!(matches!(object.get("this"), Some(Value::Object(..))));
+    Ok(())
+}
+
+#[test]
+fn test_collect_at_threshold() -> rune::Result<()> {
+    let (mut vm, collector) = with_collector(
+        rune_vm! {
+            pub fn main() {
+                for n in 0..10 {
+                    let v = [];
+                    v.push(v);
+                }
+            }
+        },
+        CycleCollector::with_threshold(4),
+    );
+
+    let mut execution = vm.execute(["main"], ())?;
+
+    while !matches!(
+        execution.resume_with_fuel(1)?,
+        rune::runtime::FuelState::Complete(..)
+    ) {
+        assert!(collector.tracked() < 4);
+    }
+
+    drop(execution);
+    assert_eq!(collector.collected(), 10);
+    assert_eq!(collector.tracked(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_send_execute_detaches_collector() -> rune::Result<()> {
+    let (vm, collector) = with_collector(rune_vm! {
+        pub async fn main() {
+            let v = [];
+            v.push(v);
+            1
+        }
+    }, CycleCollector::new());
+
+    let future = vm
+        .send_execute(["main"], ())?
+        .async_complete_into::<i64>();
+
+    let output = std::thread::spawn(move || futures_executor::block_on(future))
+        .join()
+        .unwrap()?;
+
+    assert_eq!(output, 1);
+    assert_eq!(Rc::strong_count(&collector), 1);
+    assert_eq!(collector.tracked(), 0);
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use rune::ast::Span;
+use rune::runtime::{Breakpoint, DebugEvent, Debugger, StepMode};
+use rune::{FromValue, Hash, SourceId, Vm};
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+fn inner(n) {
+    let a = n + 1;
+    a * 2
+}
+
+pub fn main() {
+    let x = inner(1);
+    let y = inner(x);
+    x + y
+}
+"#;
+
+fn vm() -> rune::Result<Vm> {
+    let mut sources = rune::Sources::new();
+    sources.insert(rune::Source::new("entry", SOURCE));
+    let context = rune_modules::default_context()?;
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+fn span_of(needle: &str) -> Span {
+    let start = SOURCE.find(needle).expect("missing needle");
+    Span::new(start, start + needle.len())
+}
+
+#[test]
+fn test_function_breakpoint() -> rune::Result<()> {
+    let mut vm = vm()?;
+    let mut debugger = Debugger::new(vm.execute(["main"], ())?);
+    let id = debugger.add_breakpoint(Breakpoint::Function(Hash::type_hash(["inner"])));
+
+    let mut args = Vec::new();
+
+    loop {
+        match debugger.resume(StepMode::Continue)? {
+            DebugEvent::Breakpoint(n) => {
+                assert_eq!(n, id);
+                let frames = debugger.frames();
+                assert_eq!(frames.len(), 2);
+                let frame = frames.last().expect("missing frame");
+                args.push(i64::from_value(frame.values[0].clone())?);
+            }
+            DebugEvent::Complete(value) => {
+                assert_eq!(i64::from_value(value)?, 4 + 10);
+                break;
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+
+    assert_eq!(args, vec![1, 4]);
+    Ok(())
+}
+
+#[test]
+fn test_span_breakpoint_and_step_out() -> rune::Result<()> {
+    let mut vm = vm()?;
+    let mut debugger = Debugger::new(vm.execute(["main"], ())?);
+    let id = debugger.add_breakpoint(Breakpoint::Span {
+        source_id: SourceId::new(0),
+        span: span_of("a * 2"),
+    });
+
+    assert!(matches!(debugger.resume(StepMode::Continue)?, DebugEvent::Breakpoint(n) if n == id));
+    assert_eq!(debugger.frames().len(), 2);
+
+    assert!(debugger.remove_breakpoint(id).is_some());
+
+    assert!(matches!(debugger.resume(StepMode::Out)?, DebugEvent::Step));
+    assert_eq!(debugger.frames().len(), 1);
+
+    match debugger.resume(StepMode::Continue)? {
+        DebugEvent::Complete(value) => assert_eq!(i64::from_value(value)?, 14),
+        event => panic!("unexpected event: {:?}", event),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_step_over() -> rune::Result<()> {
+    let mut vm = vm()?;
+    let mut debugger = Debugger::new(vm.execute(["main"], ())?);
+
+    let mut steps = 0;
+
+    loop {
+        match debugger.resume(StepMode::Over)? {
+            DebugEvent::Step => {
+                // Stepping over never enters `inner`.
+                assert_eq!(debugger.frames().len(), 1);
+                steps += 1;
+            }
+            DebugEvent::Complete(value) => {
+                assert_eq!(i64::from_value(value)?, 14);
+                break;
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+
+    assert!(steps > 0);
+    Ok(())
+}
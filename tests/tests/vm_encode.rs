@@ -0,0 +1,60 @@
+use rune::runtime::Bytes;
+use rune::FromValue;
+use rune_tests::*;
+
+#[test]
+fn test_encode() -> rune::Result<()> {
+    let output: (String, String, String, String) = rune! {
+        pub fn main() {
+            (
+                encode::base64_encode("hello?>"),
+                encode::base64_url_encode(b"hello?>"),
+                encode::hex_encode(b"\x00\xffab"),
+                encode::url_encode("a b&c/ä~"),
+            )
+        }
+    };
+
+    assert_eq!(
+        output,
+        (
+            String::from("aGVsbG8/Pg=="),
+            String::from("aGVsbG8_Pg=="),
+            String::from("00ff6162"),
+            String::from("a%20b%26c%2F%C3%A4~"),
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decode() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            Ok((
+                encode::base64_decode("aGVsbG8/Pg==")?,
+                encode::base64_url_decode("aGVsbG8_Pg==")?,
+                encode::hex_decode("00FF6162")?,
+                encode::url_decode("a%20b%26c%2F%C3%A4~")?,
+                encode::url_decode_bytes("%ff"),
+                encode::hex_decode("abc").is_err(),
+                encode::url_decode("%ff").is_err(),
+            ))
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let output =
+        <Result<(Bytes, Bytes, Bytes, String, Bytes, bool, bool), rune::Value>>::from_value(output)?;
+    let (base64, base64_url, hex, url, url_bytes, bad_hex, bad_utf8) =
+        output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert_eq!(&base64[..], b"hello?>");
+    assert_eq!(&base64_url[..], b"hello?>");
+    assert_eq!(&hex[..], b"\x00\xffab");
+    assert_eq!(url, "a b&c/ä~");
+    assert_eq!(&url_bytes[..], b"\xff");
+    assert!(bad_hex);
+    assert!(bad_utf8);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use rune::{Context, FromValue};
+use rune_modules::env::Config;
+use rune_tests::*;
+use std::collections::HashMap;
+
+fn context(config: Config) -> rune::Result<Context> {
+    let mut context = rune_modules::default_context()?;
+    context.install(rune_modules::env::module(config)?)?;
+    Ok(context)
+}
+
+#[test]
+fn test_args() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context(Config::new().args(["--verbose", "input.txt"]))? =>
+        pub fn main() {
+            std::env::args()
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let args = Vec::<String>::from_value(output)?;
+    assert_eq!(args, ["--verbose", "input.txt"]);
+    Ok(())
+}
+
+#[test]
+fn test_var_allowlist() -> rune::Result<()> {
+    std::env::set_var("RUNE_TEST_ENV_ALLOWED", "yes");
+    std::env::set_var("RUNE_TEST_ENV_HIDDEN", "no");
+
+    let mut vm = rune_vm_with! {
+        context(Config::new().allow_var("RUNE_TEST_ENV_ALLOWED"))? =>
+        pub fn main() {
+            (
+                std::env::var("RUNE_TEST_ENV_ALLOWED"),
+                std::env::var("RUNE_TEST_ENV_HIDDEN"),
+                std::env::vars(),
+            )
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let (allowed, hidden, vars) =
+        <(Option<String>, Option<String>, HashMap<String, String>)>::from_value(output)?;
+
+    assert_eq!(allowed.as_deref(), Some("yes"));
+    assert_eq!(hidden, None);
+    assert_eq!(vars.len(), 1);
+    assert_eq!(vars.get("RUNE_TEST_ENV_ALLOWED").map(String::as_str), Some("yes"));
+    Ok(())
+}
+
+#[test]
+fn test_all_vars() -> rune::Result<()> {
+    std::env::set_var("RUNE_TEST_ENV_ALL", "1");
+
+    let mut vm = rune_vm_with! {
+        context(Config::new().allow_all_vars())? =>
+        pub fn main() {
+            std::env::var("RUNE_TEST_ENV_ALL")
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    assert_eq!(Option::<String>::from_value(output)?.as_deref(), Some("1"));
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use rune::runtime::Value;
+use rune::{Context, FromValue};
+use rune_tests::*;
+use std::path::PathBuf;
+
+fn context(root: &PathBuf) -> rune::Result<Context> {
+    let mut context = rune::Context::with_default_modules()?;
+    context.install(rune_modules::fs::module_with_roots([root.clone()])?)?;
+    Ok(context)
+}
+
+#[tokio::test]
+async fn test_sandbox_roots() -> rune::Result<()> {
+    let base = std::env::temp_dir().join(format!("rune-fs-sandbox-{}", std::process::id()));
+    let root = base.join("root");
+    std::fs::create_dir_all(&root)?;
+    std::fs::write(base.join("secret.txt"), "secret")?;
+
+    let mut vm = rune_vm_with! {
+        context(&root)? =>
+        pub async fn main() {
+            fs::create_dir_all("a/b").await?;
+            fs::write("a/b/hello.txt", "Hello").await?;
+            let contents = fs::read_to_string("a/b/hello.txt").await?;
+            let entries = fs::read_dir("a/b").await?;
+            let len = fs::metadata("a/b/hello.txt").await?.len();
+
+            Ok((
+                contents,
+                entries,
+                len,
+                fs::read_to_string("../secret.txt").await.is_err(),
+                fs::exists("../secret.txt").await,
+                fs::write("a/../../escape.txt", "nope").await.is_err(),
+            ))
+        }
+    };
+
+    let output = vm.async_call(["main"], ()).await?;
+    let output = <Result<(String, Vec<String>, u64, bool, bool, bool), Value>>::from_value(output)?;
+    let (contents, entries, len, read_denied, exists, write_denied) =
+        output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert_eq!(contents, "Hello");
+    assert_eq!(entries, ["hello.txt"]);
+    assert_eq!(len, 5);
+    assert!(read_denied);
+    assert!(!exists);
+    assert!(write_denied);
+    assert!(!base.join("escape.txt").exists());
+
+    std::fs::remove_dir_all(&base)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sandbox_relative_escapes() -> rune::Result<()> {
+    let base = std::env::temp_dir().join(format!("rune-fs-relative-{}", std::process::id()));
+    let root = base.join("root");
+    std::fs::create_dir_all(root.join("a"))?;
+    std::fs::write(base.join("secret.txt"), "secret")?;
+
+    let mut vm = rune_vm_with! {
+        context(&root)? =>
+        pub async fn main(absolute) {
+            [
+                fs::read_to_string("./../secret.txt").await.is_err(),
+                fs::read_to_string("a/./../../secret.txt").await.is_err(),
+                fs::read_to_string("a/missing/../../../secret.txt").await.is_err(),
+                fs::read_to_string(absolute).await.is_err(),
+                fs::write("./a/../../escape.txt", "nope").await.is_err(),
+            ]
+        }
+    };
+
+    let absolute = base.join("secret.txt").display().to_string();
+    let output = Vec::<bool>::from_value(vm.async_call(["main"], (absolute,)).await?)?;
+
+    assert!(output.iter().all(|denied| *denied), "{:?}", output);
+    assert!(!base.join("escape.txt").exists());
+
+    std::fs::remove_dir_all(&base)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_sandbox_symlink_escapes() -> rune::Result<()> {
+    use std::os::unix::fs::symlink;
+
+    let base = std::env::temp_dir().join(format!("rune-fs-symlink-{}", std::process::id()));
+    let root = base.join("root");
+    std::fs::create_dir_all(&root)?;
+    std::fs::write(base.join("secret.txt"), "secret")?;
+
+    // A link to a directory outside of the root, and a dangling link which
+    // would create a file outside of the root when written to.
+    symlink(&base, root.join("outside"))?;
+    symlink(base.join("created.txt"), root.join("dangling"))?;
+
+    let mut vm = rune_vm_with! {
+        context(&root)? =>
+        pub async fn main() {
+            [
+                fs::read_to_string("outside/secret.txt").await.is_err(),
+                fs::write("outside/escape.txt", "nope").await.is_err(),
+                fs::write("dangling", "nope").await.is_err(),
+                fs::create_dir_all("dangling/nested").await.is_err(),
+            ]
+        }
+    };
+
+    let output = Vec::<bool>::from_value(vm.async_call(["main"], ()).await?)?;
+
+    assert!(output.iter().all(|denied| *denied), "{:?}", output);
+    assert!(!base.join("escape.txt").exists());
+    assert!(!base.join("created.txt").exists());
+
+    std::fs::remove_dir_all(&base)?;
+    Ok(())
+}
+
+#[test]
+fn test_sandbox_missing_root() {
+    let root = std::env::temp_dir().join(format!("rune-fs-missing-{}", std::process::id()));
+    assert!(rune_modules::fs::module_with_roots([root]).is_err());
+}
@@ -0,0 +1,88 @@
+use rune::runtime::{FuelState, GeneratorState};
+use rune::FromValue;
+use rune_tests::*;
+
+#[test]
+fn test_fuel_generator() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            for n in 0..3 {
+                let i = 0;
+
+                while i < 20 {
+                    i += 1;
+                }
+
+                yield n;
+            }
+        }
+    };
+
+    let mut execution = vm.execute(["main"], ())?;
+    let mut yielded = Vec::new();
+    let mut exhausted = 0;
+
+    loop {
+        match execution.resume_with_fuel(5)? {
+            FuelState::Yielded(value) => yielded.push(i64::from_value(value)?),
+            FuelState::Complete(..) => break,
+            FuelState::Exhausted => exhausted += 1,
+        }
+    }
+
+    assert_eq!(yielded, vec![0, 1, 2]);
+    assert!(exhausted > 0);
+    Ok(())
+}
+
+#[test]
+fn test_fuel_resume_after_exhausted() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        fn add(a, b) { a + b }
+        pub fn main() { add(1, 2) + add(3, 4) }
+    };
+
+    let mut execution = vm.execute(["main"], ())?;
+    assert!(matches!(execution.resume_with_fuel(1)?, FuelState::Exhausted));
+
+    match execution.resume()? {
+        GeneratorState::Complete(value) => assert_eq!(i64::from_value(value)?, 10),
+        GeneratorState::Yielded(..) => panic!("unexpected yield"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_async_fuel() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        async fn inner(n) { n * 2 }
+
+        pub async fn main() {
+            let out = 0;
+
+            for n in 0..10 {
+                out += inner(n).await;
+            }
+
+            out
+        }
+    };
+
+    let mut execution = vm.execute(["main"], ())?;
+    let mut exhausted = 0;
+
+    let value = futures_executor::block_on(async {
+        loop {
+            match execution.async_resume_with_fuel(8).await? {
+                FuelState::Complete(value) => break Ok::<_, rune::runtime::VmError>(value),
+                FuelState::Exhausted => exhausted += 1,
+                FuelState::Yielded(..) => panic!("unexpected yield"),
+            }
+        }
+    })?;
+
+    assert!(exhausted > 0);
+    assert_eq!(i64::from_value(value)?, 90);
+    Ok(())
+}
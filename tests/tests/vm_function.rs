@@ -1,5 +1,5 @@
-use rune::runtime::Function;
-use rune::Value;
+use rune::runtime::{Function, TypedFunction, VmError, VmErrorKind};
+use rune::{FromValue, Value, Vm};
 use rune_tests::*;
 use std::sync::Arc;
 
@@ -69,3 +69,190 @@ fn test_function() {
     let value: Value = function.call(()).unwrap();
     assert!(matches!(value, Value::Integer(3)));
 }
+
+#[test]
+fn test_function_repeated_calls() {
+    let function: Function = rune! {
+        fn counter(n) {
+            for i in 0..n {
+                yield i;
+            }
+        }
+
+        pub fn main() { counter }
+    };
+
+    let mut generators = Vec::new();
+
+    for n in 0..64i64 {
+        let generator: Value = function.call((n,)).unwrap();
+        generators.push(generator);
+    }
+
+    // Drop every other generator, releasing its stack while the rest are
+    // still in use.
+    let generators = generators
+        .into_iter()
+        .enumerate()
+        .filter(|(n, _)| n % 2 == 1)
+        .map(|(_, generator)| generator);
+
+    for (n, generator) in (1..64i64).step_by(2).zip(generators) {
+        let mut generator = generator.into_generator().unwrap().take().unwrap();
+        let mut count = 0;
+
+        while let Some(value) = generator.next().unwrap() {
+            assert_eq!(value.into_integer().unwrap(), count);
+            count += 1;
+        }
+
+        assert_eq!(count, n);
+    }
+}
+
+#[test]
+fn test_typed_function() -> rune::Result<()> {
+    let context = rune_modules::default_context()?;
+    let runtime = Arc::new(context.runtime());
+
+    let unit = build(&context, r#"pub fn add(a, b) { a + b } pub fn other() { 1 }"#)?;
+    let mut vm = Vm::new(runtime.clone(), unit);
+
+    let add: TypedFunction<(i64, i64), i64> = vm.function(["add"])?;
+    assert_eq!(add.args(), 2);
+
+    let mut sum = 0;
+
+    for n in 0..100 {
+        sum = add.call(&mut vm, (sum, n))?;
+    }
+
+    assert_eq!(sum, 4950);
+
+    let add_one: TypedFunction<(i64,), i64> = vm.function(["add"])?;
+    assert!(matches!(
+        add_one.call(&mut vm, (1,)).unwrap_err().kind(),
+        VmErrorKind::BadArgumentCount { actual: 1, expected: 2 }
+    ));
+
+    assert!(matches!(
+        vm.function::<(), i64, _>(["missing"]).unwrap_err().kind(),
+        VmErrorKind::MissingEntry { .. }
+    ));
+
+    let mut other = Vm::new(runtime, build(&context, r#"pub fn add(a, b) { a - b }"#)?);
+
+    assert!(matches!(
+        add.call(&mut other, (1, 2)).unwrap_err().kind(),
+        VmErrorKind::ForeignFunction { .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_function_into_typed() -> rune::Result<()> {
+    struct Callbacks {
+        greet: Box<dyn Fn(String) -> Result<String, VmError>>,
+        count: Box<dyn Fn() -> Result<i64, VmError>>,
+    }
+
+    let (greet, count): (Function, Function) = rune! {
+        pub fn main() {
+            (|name| "Hello, " + name + "!", || 42)
+        }
+    };
+
+    let callbacks = Callbacks {
+        greet: greet.into_typed::<(String,), String>(),
+        count: count.into_typed::<(), i64>(),
+    };
+
+    assert_eq!((callbacks.greet)(String::from("World"))?, "Hello, World!");
+    assert_eq!((callbacks.count)()?, 42);
+
+    let add: Function = rune! {
+        async fn add(a, b) { a + b }
+        pub fn main() { add }
+    };
+
+    let add = add.into_async_typed::<(i64, i64), i64>();
+    assert_eq!(futures_executor::block_on(add(1, 2))?, 3);
+    Ok(())
+}
+
+#[test]
+fn test_function_stale_after_swap_unit() -> rune::Result<()> {
+    let context = rune_modules::default_context()?;
+    let runtime = Arc::new(context.runtime());
+
+    let unit = build(
+        &context,
+        r#"
+        fn add(a, b) { a + b }
+        pub fn main() { (add, |n| n * 2) }
+        pub fn apply(f) { f(1, 2) }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(runtime, unit);
+    let (add, double): (Function, Function) = FromValue::from_value(vm.call(["main"], ())?)?;
+    let apply = vm.lookup_function(["apply"])?;
+
+    assert_eq!(add.call::<_, i64>((1i64, 2i64))?, 3);
+    assert_eq!(double.call::<_, i64>((2i64,))?, 4);
+
+    let unit = build(
+        &context,
+        r#"
+        fn add(a, b) { a - b }
+        pub fn main() { (add, |n| n * 3) }
+        pub fn apply(f) { f(1, 2) }
+        "#,
+    )?;
+
+    vm.swap_unit(unit)?;
+
+    for function in [&add, &double] {
+        assert!(matches!(
+            function.call::<_, Value>((1i64, 2i64)).unwrap_err().kind(),
+            VmErrorKind::StaleFunction { .. }
+        ));
+    }
+
+    // Stale functions passed into the new unit can't be called either.
+    let error = vm.call(["apply"], (add,)).unwrap_err();
+    assert!(matches!(
+        error.as_unwound().0,
+        VmErrorKind::StaleFunction { .. }
+    ));
+
+    let (add, double): (Function, Function) = FromValue::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(add.call::<_, i64>((1i64, 2i64))?, -1);
+    assert_eq!(double.call::<_, i64>((2i64,))?, 6);
+
+    // Functions which didn't change keep working.
+    assert_eq!(apply.call::<_, i64>((add,))?, -1);
+
+    let unit = build(
+        &context,
+        r#"
+        fn add(a, b) { a - b }
+        pub fn main() { (add, |n| n * 3) }
+        pub fn apply(f) { f(2, 1) }
+        "#,
+    )?;
+
+    vm.swap_unit(unit)?;
+
+    // Functions are retired by any unit replacing the one they were created
+    // from.
+    let (add, _): (Function, Function) = FromValue::from_value(vm.call(["main"], ())?)?;
+    assert!(matches!(
+        apply.call::<_, Value>((add,)).unwrap_err().kind(),
+        VmErrorKind::StaleFunction { .. }
+    ));
+
+    assert_eq!(double.call::<_, i64>((2i64,))?, 6);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use rune::runtime::host::Deterministic;
+use rune::{Context, FromValue};
+use rune_tests::*;
+use std::time::Duration;
+
+fn context(seed: u64) -> rune::Result<Context> {
+    let mut context = rune_modules::default_context()?;
+    context.set_host(Deterministic::new(seed).with_time(Duration::from_secs(60)));
+    Ok(context)
+}
+
+fn replay(seed: u64) -> rune::Result<(u64, i64, i64, Vec<i64>)> {
+    let mut vm = rune_vm_with! {
+        context(seed)? =>
+        use std::collections::{HashMap, HashSet};
+
+        pub fn main() {
+            let map = HashMap::new();
+            let set = HashSet::new();
+
+            for n in 0..64 {
+                map.insert(n, n);
+                set.insert(Symbol::new(format!("sym{}", n)));
+            }
+
+            let order = map.keys().collect::<Vec>();
+
+            for s in set {
+                order.push(s.as_str().len());
+            }
+
+            (std::time::now().as_millis(), rand::int()?, rand::WyRand::new().int(), order)
+        }
+    };
+
+    Ok(FromValue::from_value(vm.call(["main"], ())?)?)
+}
+
+#[test]
+fn test_deterministic_host() -> rune::Result<()> {
+    let a = replay(42)?;
+    let b = replay(42)?;
+
+    assert_eq!(a.0, 60_000);
+    assert_eq!(a, b);
+    assert_ne!(a.1, a.2);
+
+    let c = replay(7)?;
+    assert_ne!(a.1, c.1);
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use rune::{Any, Context, ContextError, FromValue, Module, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+
+#[derive(Any, Clone, Copy)]
+struct First {
+    #[rune(get)]
+    value: i64,
+}
+
+#[derive(Any, Clone, Copy)]
+struct Second {
+    #[rune(get)]
+    value: i64,
+}
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<First>()?;
+    module.ty::<Second>()?;
+    Ok(module)
+}
+
+#[test]
+fn test_polymorphic_call_site() {
+    let out: i64 = rune! {
+        struct Foo;
+        struct Bar;
+
+        impl Foo {
+            fn value(self) { 1 }
+        }
+
+        impl Bar {
+            fn value(self) { 10 }
+        }
+
+        pub fn main() {
+            let out = 0;
+
+            for value in [Foo, Bar, Bar, Foo, [1, 2, 3], Foo] {
+                out += if value is Vec { value.len() } else { value.value() };
+            }
+
+            out
+        }
+    };
+    assert_eq!(out, 26);
+}
+
+#[test]
+fn test_polymorphic_field_access() {
+    let m = make_module().expect("failed make module");
+
+    let out: i64 = rune_n! {
+        &m,
+        (First { value: 1 }, Second { value: 10 }),
+        i64 => pub fn main(first, second) {
+            let out = 0;
+
+            for value in [first, second, #{value: 100}, second, first] {
+                out += value.value;
+            }
+
+            out
+        }
+    };
+    assert_eq!(out, 122);
+}
+
+#[test]
+fn test_caches_cleared_on_swap() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+
+    let mut sources = rune::sources! {
+        entry => {
+            struct Foo;
+
+            impl Foo {
+                fn value(self) { 1 }
+            }
+
+            pub fn main() { Foo.value() }
+        }
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    assert!(unit.inline_caches() > 0);
+
+    let mut vm = Vm::new(runtime, Arc::new(unit));
+    assert_eq!(i64::from_value(vm.call(["main"], ())?)?, 1);
+
+    let mut sources = rune::sources! {
+        entry => {
+            struct Foo;
+
+            pub fn main() { Foo.value() }
+
+            impl Foo {
+                fn value(self) { 2 }
+            }
+        }
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    vm.swap_unit(Arc::new(unit))?;
+    assert_eq!(i64::from_value(vm.call(["main"], ())?)?, 2);
+    Ok(())
+}
+
+#[test]
+fn test_diff_ignores_cache_slots() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = rune::sources! {
+        entry => {
+            pub fn main() { 1 }
+            pub fn helper(v) { v.len() }
+        }
+    };
+
+    let old = rune::prepare(&mut sources).with_context(&context).build()?;
+
+    let mut sources = rune::sources! {
+        entry => {
+            pub fn main() { [].len() }
+            pub fn helper(v) { v.len() }
+        }
+    };
+
+    let new = rune::prepare(&mut sources).with_context(&context).build()?;
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.changed, vec![rune::Hash::type_hash(["main"])]);
+    assert_eq!(diff.unchanged, vec![rune::Hash::type_hash(["helper"])]);
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_int_op_const() {
+    let out: (i64, i64, i64, i64, i64) = rune! {
+        pub fn main() {
+            let a = 10;
+            (a + 2, a - -2, a * 3, a / 4, a % 4)
+        }
+    };
+    assert_eq!(out, (12, 12, 30, 2, 2));
+
+    let out: (bool, bool, bool, bool, bool, bool) = rune! {
+        pub fn main() {
+            let a = 10;
+            (a < 10, a > 9, a <= 10, a >= 11, a == 10, a != -10)
+        }
+    };
+    assert_eq!(out, (false, true, true, false, true, true));
+}
+
+#[test]
+fn test_int_op_operands() {
+    let out: i64 = rune! {
+        fn add(a, b) { a + b }
+
+        pub fn main() {
+            let a = 1;
+            let b = 2;
+            a + b + add(3, 4) * (a - b) - b * add(a, b)
+        }
+    };
+    assert_eq!(out, -10);
+
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = 0;
+
+            for i in 0..100 {
+                if i % 3 == 0 {
+                    n += i;
+                }
+            }
+
+            n
+        }
+    };
+    assert_eq!(out, 1683);
+}
+
+#[test]
+fn test_int_op_const_errors() {
+    assert_vm_error!(
+        "pub fn main() { let a = 9223372036854775807; a + 1 }",
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let a = -9223372036854775808; a - 1 }",
+        Underflow => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let a = 10; a / 0 }",
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let a = 10; a % 0 }",
+        DivideByZero => {}
+    );
+}
+
+#[test]
+fn test_int_op_fallback() {
+    let out: String = rune! {
+        pub fn main() {
+            let a = "a";
+            let b = "b";
+            a + b
+        }
+    };
+    assert_eq!(out, "ab");
+
+    let out: (f64, bool, bool) = rune! {
+        pub fn main() {
+            let a = 1.5;
+            (a + 1.0, a < 2.0, a == 1.5)
+        }
+    };
+    assert_eq!(out, (2.5, true, true));
+
+    assert_vm_error!(
+        "struct Foo; pub fn main() { let a = Foo; a == 1 }",
+        UnsupportedBinaryOperation { op: "==", .. } => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let a = 1.5; a + 1 }",
+        UnsupportedBinaryOperation { op: "+", .. } => {}
+    );
+}
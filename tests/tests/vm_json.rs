@@ -0,0 +1,51 @@
+use rune::Value;
+use rune_tests::*;
+
+#[test]
+fn test_round_trip() -> rune::Result<()> {
+    let output: Result<(Value, Value, Value, String, String), Value> = rune! {
+        pub fn main() {
+            let data = json::from_string("{\"a\": [1, 2.0, 18446744073709551615], \"b\": null}")?;
+            let a = data["a"];
+
+            Ok((
+                a[0],
+                a[1],
+                a[2],
+                json::to_string(a)?,
+                json::to_string_pretty([1])?,
+            ))
+        }
+    };
+    let (int, float, big, encoded, pretty) =
+        output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert!(matches!(int, Value::Integer(1)));
+    assert!(matches!(float, Value::Float(f) if f == 2.0));
+    assert!(matches!(big, Value::Float(..)));
+    // The exponent is formatted differently across serde_json versions.
+    assert!(encoded.starts_with("[1,2.0,1.8446744073709552e"));
+    assert_eq!(pretty, "[\n  1\n]");
+    Ok(())
+}
+
+#[test]
+fn test_float_numbers() -> rune::Result<()> {
+    let output: Result<(Value, bool, bool), Value> = rune! {
+        pub fn main() {
+            let data = json::from_string_with("[1, 2]", #{ numbers: "float" })?;
+
+            Ok((
+                data[0],
+                json::from_string_with("[]", #{ numbers: "nope" }).is_err(),
+                json::from_string_with("[]", #{ unknown: true }).is_err(),
+            ))
+        }
+    };
+    let (first, invalid, unknown) = output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert!(matches!(first, Value::Float(f) if f == 1.0));
+    assert!(invalid);
+    assert!(unknown);
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use rune::runtime::{VmErrorKind, VmLimits};
+use rune::Context;
+use rune_tests::*;
+
+#[test]
+fn test_max_call_depth() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = rune_vm_with! {
+        context =>
+        fn depth(n) { if n == 0 { 0 } else { depth(n - 1) + 1 } }
+        pub fn main(n) { depth(n) }
+    };
+
+    vm.set_limits(VmLimits::new().with_max_call_depth(32));
+
+    assert_eq!(vm.call(["main"], (16i64,))?.into_integer()?, 16);
+
+    let error = vm.call(["main"], (64i64,)).unwrap_err();
+
+    assert!(matches!(
+        error.as_unwound().0,
+        VmErrorKind::CallDepthExceeded { max: 32 }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_max_stack_size() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = rune_vm_with! {
+        context =>
+        fn depth(n, a, b, c) { if n == 0 { 0 } else { depth(n - 1, a, b, c) + 1 } }
+        pub fn main(n) { depth(n, 1, 2, 3) }
+    };
+
+    vm.set_limits(VmLimits::new().with_max_stack_size(64));
+
+    let error = vm.call(["main"], (1000i64,)).unwrap_err();
+
+    assert!(matches!(
+        error.as_unwound().0,
+        VmErrorKind::StackSizeExceeded { max: 64 }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_max_stack_size_without_calls() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = rune_vm_with! {
+        context =>
+        pub fn main() {
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20]
+        }
+    };
+
+    vm.set_limits(VmLimits::new().with_max_stack_size(16));
+
+    let error = vm.call(["main"], ()).unwrap_err();
+
+    assert!(matches!(
+        error.as_unwound().0,
+        VmErrorKind::StackSizeExceeded { max: 16 }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_max_instruction_allocation() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = rune_vm_with! {
+        context =>
+        pub fn main(n) {
+            let out = [];
+
+            for i in 0..n {
+                out.push([i, i, i, i]);
+            }
+
+            out.len()
+        }
+    };
+
+    vm.set_limits(VmLimits::new().with_max_instruction_allocation(4096));
+
+    assert_eq!(vm.call(["main"], (2i64,))?.into_integer()?, 2);
+    assert!(vm.instruction_allocation() > 0);
+
+    let error = vm.call(["main"], (10000i64,)).unwrap_err();
+
+    assert!(matches!(
+        error.as_unwound().0,
+        VmErrorKind::InstructionAllocationExceeded { max: 4096 }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_max_call_depth_through_native() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = rune_vm_with! {
+        context =>
+        fn f(n) { [n].iter().map(|x| f(x + 1)).collect::<Vec>() }
+        pub fn main() { f(0) }
+    };
+
+    vm.set_limits(VmLimits::new().with_max_call_depth(64));
+
+    let error = vm.call(["main"], ()).unwrap_err();
+
+    assert!(matches!(
+        error.as_unwound().0,
+        VmErrorKind::CallDepthExceeded { max: 64 }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_max_instruction_allocation_through_native() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = rune_vm_with! {
+        context =>
+        pub fn main(n) {
+            let out = String::new();
+
+            for i in 0..n {
+                out.push_str("0123456789abcdef");
+            }
+
+            out.len()
+        }
+    };
+
+    vm.set_limits(VmLimits::new().with_max_instruction_allocation(4096));
+
+    assert_eq!(vm.call(["main"], (4i64,))?.into_integer()?, 64);
+
+    let error = vm.call(["main"], (10000i64,)).unwrap_err();
+
+    assert!(matches!(
+        error.as_unwound().0,
+        VmErrorKind::InstructionAllocationExceeded { max: 4096 }
+    ));
+
+    Ok(())
+}
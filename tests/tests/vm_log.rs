@@ -0,0 +1,64 @@
+use rune::runtime::log::{Level, Logger};
+use rune::{Context, FromValue};
+use rune_tests::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Recorder {
+    records: Mutex<Vec<(Level, String)>>,
+}
+
+impl Logger for Recorder {
+    fn enabled(&self, level: Level) -> bool {
+        level >= Level::Info
+    }
+
+    fn log(&self, level: Level, message: &str) {
+        self.records
+            .lock()
+            .unwrap()
+            .push((level, message.to_owned()));
+    }
+}
+
+fn context(logger: Arc<Recorder>) -> rune::Result<Context> {
+    let mut context = rune_modules::default_context()?;
+    context.set_logger(logger);
+    Ok(context)
+}
+
+#[test]
+fn test_logger() -> rune::Result<()> {
+    let recorder = Arc::new(Recorder::default());
+
+    let mut vm = rune_vm_with! {
+        context(recorder.clone())? =>
+        use std::log::warn;
+
+        pub fn main() {
+            let n = 3;
+            std::log::trace!("trace {}", n);
+            std::log::info!("info {}", n + 1);
+            warn("careful");
+            std::log::error!("failed");
+            (std::log::enabled("debug"), std::log::enabled("error"))
+        }
+    };
+
+    let enabled = <(bool, bool)>::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(enabled, (false, true));
+
+    let records = recorder.records.lock().unwrap();
+
+    assert_eq!(
+        *records,
+        [
+            (Level::Trace, String::from("trace 3")),
+            (Level::Info, String::from("info 4")),
+            (Level::Warn, String::from("careful")),
+            (Level::Error, String::from("failed")),
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use rune::runtime::{Bytes, Capabilities, Value};
+use rune::{Context, FromValue};
+use rune_tests::*;
+
+fn context() -> rune::Result<Context> {
+    let mut context = rune::Context::with_default_modules()?;
+    context.install(rune_modules::net::module(true)?)?;
+    Ok(context)
+}
+
+#[tokio::test]
+async fn test_tcp_roundtrip() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context()? =>
+        use net::{TcpListener, TcpStream};
+
+        pub async fn main() {
+            let listener = TcpListener::bind("127.0.0.1:0").await?;
+            let client = TcpStream::connect(listener.local_addr()?).await?;
+            let (server, peer) = listener.accept().await?;
+
+            client.write_all(b"ping").await?;
+            let request = server.read_exact(4).await?;
+            server.write_all(b"pong").await?;
+            server.shutdown().await?;
+
+            let response = client.read(1024).await?;
+            let closed = client.read(1024).await?;
+
+            Ok((request, response, closed.len(), peer == client.local_addr()?))
+        }
+    };
+
+    let output = vm.async_call(["main"], ()).await?;
+    let output = <Result<(Bytes, Bytes, usize, bool), Value>>::from_value(output)?;
+    let (request, response, closed, same_peer) =
+        output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert_eq!(&request[..], b"ping");
+    assert_eq!(&response[..], b"pong");
+    assert_eq!(closed, 0);
+    assert!(same_peer);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_udp_roundtrip() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context()? =>
+        use net::UdpSocket;
+
+        pub async fn main() {
+            let a = UdpSocket::bind("127.0.0.1:0").await?;
+            let b = UdpSocket::bind("127.0.0.1:0").await?;
+
+            a.send_to(b"hello", b.local_addr()?).await?;
+            let (datagram, from) = b.recv_from(1024).await?;
+
+            b.connect(from).await?;
+            b.send(b"world").await?;
+            let reply = a.recv(1024).await?;
+
+            Ok((datagram, reply, from == a.local_addr()?))
+        }
+    };
+
+    let output = vm.async_call(["main"], ()).await?;
+    let output = <Result<(Bytes, Bytes, bool), Value>>::from_value(output)?;
+    let (datagram, reply, same_peer) = output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert_eq!(&datagram[..], b"hello");
+    assert_eq!(&reply[..], b"world");
+    assert!(same_peer);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missing_capability() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context()? =>
+        pub async fn main() {
+            net::TcpListener::bind("127.0.0.1:0").await
+        }
+    };
+
+    vm.set_capabilities(Capabilities::new());
+    assert!(vm.async_call(["main"], ()).await.is_err());
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use rune::runtime::output::Capture;
+use rune::Vm;
+use std::sync::Arc;
+
+#[test]
+fn test_capture_output() -> rune::Result<()> {
+    let stdout = Capture::new();
+    let stderr = Capture::new();
+
+    let mut context = rune_modules::default_context()?;
+    context.set_stdout(stdout.clone());
+    context.set_stderr(stderr.clone());
+
+    let mut sources = rune::sources! {
+        entry => {
+            pub fn main() {
+                print!("a");
+                println!("b {}", 1);
+                dbg(2, "c");
+                eprint("d");
+                eprintln!("e");
+            }
+        }
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    vm.call(["main"], ())?;
+
+    assert_eq!(stdout.drain_utf8()?, "ab 1\n2\n\"c\"\n");
+    assert_eq!(stderr.drain_utf8()?, "de\n");
+    assert!(stdout.drain().is_empty());
+    Ok(())
+}
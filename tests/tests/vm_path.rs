@@ -0,0 +1,110 @@
+use rune::FromValue;
+use rune_tests::*;
+
+#[test]
+fn test_unix_paths() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        use path::Path;
+
+        pub fn main() {
+            let path = Path::new("/usr/local//lib/archive.tar.gz");
+
+            (
+                path.to_string(),
+                path.parent().map(|p| p.to_string()),
+                path.file_name(),
+                path.file_stem(),
+                path.extension(),
+                path.with_extension("zip").to_string(),
+                path.is_absolute(),
+                path.components(),
+                Path::new("/").parent().is_none(),
+                Path::new(".bashrc").extension(),
+            )
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let output = <(
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        String,
+        bool,
+        Vec<String>,
+        bool,
+        Option<String>,
+    )>::from_value(output)?;
+
+    assert_eq!(output.0, "/usr/local/lib/archive.tar.gz");
+    assert_eq!(output.1.as_deref(), Some("/usr/local/lib"));
+    assert_eq!(output.2.as_deref(), Some("archive.tar.gz"));
+    assert_eq!(output.3.as_deref(), Some("archive.tar"));
+    assert_eq!(output.4.as_deref(), Some("gz"));
+    assert_eq!(output.5, "/usr/local/lib/archive.tar.zip");
+    assert!(output.6);
+    assert_eq!(output.7, ["usr", "local", "lib", "archive.tar.gz"]);
+    assert!(output.8);
+    assert_eq!(output.9, None);
+    Ok(())
+}
+
+#[test]
+fn test_join_and_normalize() -> rune::Result<()> {
+    let output: (String, String, String, String, bool) = rune! {
+        use path::Path;
+
+        pub fn main() {
+            (
+                Path::new("src/bin").join("../lib.rs").normalize().to_string(),
+                Path::new("a").join("/etc").to_string(),
+                Path::new("../a/./b/../../..").normalize().to_string(),
+                Path::new("/a/../..").normalize().to_string(),
+                Path::new("a/b") == Path::new("a\\b"),
+            )
+        }
+    };
+
+    assert_eq!(
+        output,
+        (
+            String::from("src/lib.rs"),
+            String::from("/etc"),
+            String::from("../.."),
+            String::from("/"),
+            true,
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_windows_paths() -> rune::Result<()> {
+    let output: (String, String, bool, String) = rune! {
+        use path::Path;
+
+        pub fn main() {
+            let path = Path::new("C:\\Users\\rune\\notes.txt");
+
+            (
+                path.with_extension("md").to_string(),
+                path.join("..\\other").normalize().to_string(),
+                path.is_absolute(),
+                path.join("D:\\data").to_string(),
+            )
+        }
+    };
+
+    assert_eq!(
+        output,
+        (
+            String::from("C:\\Users\\rune\\notes.md"),
+            String::from("C:\\Users\\rune\\other"),
+            true,
+            String::from("D:\\data"),
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use rune::runtime::{Ambient, Capabilities, CycleCollector, VmErrorKind, VmLimits, VmPool};
+use rune::{Context, FromValue};
+use rune_tests::build;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn test_vm_pool_reuse() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, "pub fn main(n) { let v = [n, n]; v[0] + v[1] }")?;
+    let pool = Arc::new(VmPool::new(Arc::new(context.runtime()), unit).with_max_idle(2));
+
+    let handles = (0..8i64)
+        .map(|n| {
+            let pool = pool.clone();
+
+            thread::spawn(move || {
+                (0..16)
+                    .map(|_| {
+                        let mut vm = pool.checkout();
+                        i64::from_value(vm.call(["main"], (n,)).unwrap()).unwrap()
+                    })
+                    .sum::<i64>()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let output = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(output, (0..8).map(|n| n * 32).collect::<Vec<_>>());
+    assert!(pool.idle() <= 2);
+
+    {
+        let mut vm = pool.checkout();
+        vm.stack_mut().push(rune::Value::from(1i64));
+    }
+
+    let vm = pool.checkout();
+    assert!(vm.stack().is_empty());
+    assert!(vm.call_frames().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_vm_pool_limits() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, "fn f(n) { f(n + 1) } pub fn main() { f(0) }")?;
+
+    let pool = VmPool::new(Arc::new(context.runtime()), unit)
+        .with_limits(VmLimits::new().with_max_call_depth(32));
+
+    for _ in 0..2 {
+        let mut vm = pool.checkout();
+        let error = vm.call(["main"], ()).unwrap_err();
+
+        assert!(matches!(
+            error.as_unwound().0,
+            VmErrorKind::CallDepthExceeded { max: 32 }
+        ));
+    }
+
+    assert_eq!(pool.idle(), 1);
+    let vm = pool.checkout().detach();
+    drop(vm);
+    assert_eq!(pool.idle(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_vm_pool_removes_thread_bound_state() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, "pub fn main() { let v = []; v.push(v); }")?;
+    let pool = VmPool::new(Arc::new(context.runtime()), unit);
+
+    {
+        let mut vm = pool.checkout();
+        let mut ambient = Ambient::new();
+        ambient.insert("user", "jane")?;
+        vm.set_ambient(ambient);
+        vm.set_capabilities(Capabilities::new());
+        vm.set_cycle_collector(Rc::new(CycleCollector::new()));
+        vm.call(["main"], ())?;
+        vm.stack_mut().push(rune::Value::from(1i64));
+    }
+
+    assert_eq!(pool.idle(), 1);
+
+    // NB: the virtual machine must be sendable once it is idle in the pool.
+    let pool = Arc::new(pool);
+    let other = pool.clone();
+
+    thread::spawn(move || {
+        let vm = other.checkout();
+        assert!(vm.stack().is_empty());
+        assert!(vm.ambient().is_none());
+        assert!(vm.capabilities().is_none());
+        assert!(vm.cycle_collector().is_none());
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(pool.idle(), 1);
+    Ok(())
+}
@@ -0,0 +1,52 @@
+#![cfg(unix)]
+
+use rune::runtime::{Bytes, Value};
+use rune::FromValue;
+use rune_tests::*;
+
+#[tokio::test]
+async fn test_output() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        use process::Command;
+
+        pub async fn main() {
+            let command = Command::new("sh");
+            command.args(["-c", "echo $GREETING; pwd; exit 3"]);
+            command.env("GREETING", "hello");
+            command.current_dir("/");
+            let output = command.output().await?;
+            Ok((output.stdout, output.status.success(), output.status.code()))
+        }
+    };
+
+    let output = vm.async_call(["main"], ()).await?;
+    let output = <Result<(Bytes, bool, Option<i32>), Value>>::from_value(output)?;
+    let (stdout, success, code) = output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert_eq!(stdout.into_vec(), b"hello\n/\n");
+    assert!(!success);
+    assert_eq!(code, Some(3));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spawn_piped() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        use process::{Command, Stdio};
+
+        pub async fn main() {
+            let command = Command::new("echo");
+            command.arg("piped");
+            command.stdout(Stdio::piped());
+            let child = command.spawn()?;
+            let output = child.wait_with_output().await?;
+            Ok(output.stdout)
+        }
+    };
+
+    let output = vm.async_call(["main"], ()).await?;
+    let output = <Result<Bytes, Value>>::from_value(output)?;
+    let stdout = output.map_err(|_| rune::Error::msg("script failed"))?;
+    assert_eq!(stdout.into_vec(), b"piped\n");
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use rune::runtime::Profiler;
+use rune::{FromValue, Hash};
+use rune_tests::*;
+use std::sync::Arc;
+
+#[test]
+fn test_profile_instructions() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        fn leaf(n) {
+            let out = 0;
+
+            for i in 0..n {
+                out += i;
+            }
+
+            out
+        }
+
+        fn middle() {
+            leaf(10)
+        }
+
+        pub fn main() {
+            middle() + leaf(100)
+        }
+    };
+
+    let profiler = Arc::new(Profiler::new());
+    vm.set_profiler(profiler.clone());
+
+    let output = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 45 + 4950);
+
+    let profile = profiler.profile();
+
+    let main = profile.function(Hash::type_hash(["main"])).expect("main");
+    let middle = profile.function(Hash::type_hash(["middle"])).expect("middle");
+    let leaf = profile.function(Hash::type_hash(["leaf"])).expect("leaf");
+
+    // Every instruction executed is attributed to main.
+    let total = profile.stacks.iter().map(|s| s.instructions).sum::<u64>();
+    assert_eq!(main.total_instructions, total);
+
+    assert!(leaf.self_instructions > middle.self_instructions);
+    assert_eq!(leaf.self_instructions, leaf.total_instructions);
+    assert!(middle.total_instructions > middle.self_instructions);
+
+    let mut collapsed = Vec::new();
+    profile.write_collapsed(&mut collapsed)?;
+    let collapsed = String::from_utf8(collapsed)?;
+
+    let stacks = collapsed
+        .lines()
+        .filter_map(|line| line.rsplit_once(' '))
+        .map(|(stack, _)| stack)
+        .collect::<Vec<_>>();
+
+    assert_eq!(stacks, vec!["main", "main;leaf", "main;middle", "main;middle;leaf"]);
+    Ok(())
+}
+
+#[test]
+fn test_profile_interval() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            let out = 0;
+
+            for i in 0..1000 {
+                out += i;
+            }
+
+            out
+        }
+    };
+
+    let profiler = Arc::new(Profiler::with_interval(100));
+    vm.set_profiler(profiler.clone());
+    vm.call(["main"], ())?;
+
+    let profile = profiler.profile();
+    let main = profile.function(Hash::type_hash(["main"])).expect("main");
+    assert!(main.self_instructions > 0);
+    assert_eq!(main.self_instructions % 100, 0);
+    Ok(())
+}
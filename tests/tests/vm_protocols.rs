@@ -0,0 +1,147 @@
+use rune::compile::ContextError;
+use rune::runtime::{Protocol, Value, VmError, VmErrorKind::*};
+use rune::{Any, Context, Module};
+use rune_tests::*;
+
+const VALIDATE: Protocol = Protocol::new("validate");
+const CHECK_LIMIT: Protocol = Protocol::new("check_limit");
+
+#[derive(Any)]
+struct Port(i64);
+
+fn validate(value: Value) -> Result<Value, VmError> {
+    value.call_protocol(VALIDATE, ())
+}
+
+fn check_limit(value: Value, limit: i64) -> Result<Value, VmError> {
+    value.call_protocol(CHECK_LIMIT, (limit,))
+}
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.protocol(VALIDATE, 0)?;
+    module.protocol(CHECK_LIMIT, 1)?;
+    module.ty::<Port>()?;
+    module.inst_fn(VALIDATE, |port: &Port| port.0 > 0 && port.0 < 65536)?;
+    module.inst_fn(CHECK_LIMIT, |port: &Port, limit: i64| port.0 <= limit)?;
+    module.function(["validate"], validate)?;
+    module.function(["check_limit"], check_limit)?;
+    Ok(module)
+}
+
+#[test]
+fn test_custom_protocol() {
+    let out: (bool, bool, bool, bool) = rune_n! {
+        make_module().expect("failed making module"),
+        (Port(8080),),
+        (bool, bool, bool, bool) =>
+        struct Name { value }
+
+        impl Name {
+            fn validate(self) {
+                self.value.len() > 0
+            }
+
+            fn check_limit(self, limit) {
+                self.value.len() <= limit
+            }
+        }
+
+        pub fn main(port) {
+            (
+                validate(port),
+                validate(Name { value: "" }),
+                check_limit(port, 1024),
+                check_limit(Name { value: "abc" }, 3),
+            )
+        }
+    };
+    assert_eq!(out, (true, false, false, true));
+}
+
+#[test]
+fn test_custom_protocol_errors() {
+    let mut context = rune_tests::modules::default_context().expect("failed to build context");
+    context
+        .install(make_module().expect("failed making module"))
+        .expect("failed to install module");
+
+    let error = rune_tests::run::<_, _, bool>(
+        &context,
+        r#"
+        struct Name { value }
+
+        impl Name {
+            fn check_limit(self) { true }
+        }
+
+        pub fn main() { check_limit(Name { value: "" }, 1) }
+        "#,
+        ["main"],
+        (),
+    )
+    .expect_err("expected error");
+
+    let (error, _) = error.expect_vm_error("expected vm error").into_unwound();
+
+    assert!(
+        matches!(error.kind(), BadArgumentCount { actual: 2, expected: 1 }),
+        "{:?}",
+        error
+    );
+
+    let error = rune_tests::run::<_, _, bool>(
+        &context,
+        "struct Name; pub fn main() { validate(Name) }",
+        ["main"],
+        (),
+    )
+    .expect_err("expected error");
+
+    let (error, _) = error.expect_vm_error("expected vm error").into_unwound();
+    assert!(matches!(error.kind(), MissingFunction { .. }), "{:?}", error);
+}
+
+#[test]
+fn test_custom_protocol_declarations() {
+    let mut module = Module::new();
+    module.protocol(VALIDATE, 0).unwrap();
+    module.protocol(VALIDATE, 0).unwrap();
+    assert!(matches!(
+        module.protocol(VALIDATE, 1),
+        Err(ContextError::ConflictingProtocol { name: "validate" })
+    ));
+
+    let mut context = Context::new();
+    context.install(make_module().unwrap()).unwrap();
+
+    let mut module = Module::new();
+    module.protocol(VALIDATE, 1).unwrap();
+    assert!(matches!(
+        context.install(&module),
+        Err(ContextError::ConflictingProtocol { name: "validate" })
+    ));
+
+    #[derive(Any)]
+    struct Other;
+
+    let mut module = Module::new();
+    module.ty::<Other>().unwrap();
+    module.inst_fn(VALIDATE, |_: &Other, _: i64| true).unwrap();
+
+    let mut context = Context::new();
+    context.install(&module).unwrap();
+
+    let mut declaration = Module::new();
+    declaration.protocol(VALIDATE, 0).unwrap();
+
+    assert!(matches!(
+        context.install(&declaration),
+        Err(ContextError::ProtocolArgumentMismatch {
+            name: "validate",
+            expected: 0,
+            actual: 1,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,105 @@
+use rune::runtime::host::Deterministic;
+use rune::{Context, FromValue};
+use rune_tests::*;
+
+fn context(seed: u64) -> rune::Result<Context> {
+    let mut context = rune_modules::default_context()?;
+    context.set_host(Deterministic::new(seed));
+    Ok(context)
+}
+
+#[test]
+fn test_seeded_is_deterministic() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        fn sample(seed) {
+            let rng = rand::Pcg64::new_seed(seed);
+            let deck = [1, 2, 3, 4, 5, 6, 7, 8];
+            rng.shuffle(deck);
+            (deck, rng.choose(deck), rng.float(), rng.int_range(0, 100).unwrap())
+        }
+
+        pub fn main() {
+            (sample(42), sample(42))
+        }
+    };
+
+    type Sample = (Vec<i64>, Option<i64>, f64, i64);
+
+    let output = vm.call(["main"], ())?;
+    let (a, b) = <(Sample, Sample)>::from_value(output)?;
+    assert_eq!(a, b);
+
+    let mut deck = a.0.clone();
+    deck.sort();
+    assert_eq!(deck, [1, 2, 3, 4, 5, 6, 7, 8]);
+    assert!(a.1.is_some());
+    assert!((0.0..1.0).contains(&a.2));
+    Ok(())
+}
+
+#[test]
+fn test_module_functions() -> rune::Result<()> {
+    let output: Result<(Vec<i64>, f64, Option<i64>, bool), rune::Value> = rune! {
+        pub fn main() {
+            let rolls = [];
+
+            for _ in 0..100 {
+                rolls.push(rand::int_in(1..=6)?);
+            }
+
+            Ok((rolls, rand::float(), rand::choose([]), rand::int_in(5..5).is_err()))
+        }
+    };
+    let (rolls, float, empty, error) = output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert!(rolls.iter().all(|n| (1..=6).contains(n)));
+    assert!((0.0..1.0).contains(&float));
+    assert_eq!(empty, None);
+    assert!(error);
+    Ok(())
+}
+
+#[test]
+fn test_module_functions_share_generator() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context(42)? =>
+        pub fn main() {
+            [rand::int()?, rand::int()?, rand::int_range(0, 1000)?]
+        }
+    };
+
+    let shared = Vec::<i64>::from_value(vm.call(["main"], ())?)?;
+
+    let mut vm = rune_vm_with! {
+        context(42)? =>
+        pub fn main() {
+            let rng = rand::WyRand::new();
+            [rng.int(), rng.int(), rng.int_range(0, 1000)?]
+        }
+    };
+
+    let owned = Vec::<i64>::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(shared, owned);
+    assert_ne!(shared[0], shared[1]);
+    Ok(())
+}
+
+#[test]
+fn test_int_range_bounds() -> rune::Result<()> {
+    let output: (bool, bool, bool, bool) = rune! {
+        pub fn main() {
+            let rng = rand::Pcg64::new_seed(7);
+            let full = rng.int_range(-9223372036854775807 - 1, 9223372036854775807);
+
+            (
+                full.is_ok(),
+                rng.int_range(10, 0).is_err(),
+                rand::WyRand::new().int_range(3, 3).is_err(),
+                rand::int_range(0, -9223372036854775807 - 1).is_err(),
+            )
+        }
+    };
+
+    assert_eq!(output, (true, true, true, true));
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use rune::{FromValue, Vm};
+use std::sync::Arc;
+
+fn build(mut sources: rune::Sources) -> rune::Result<Vm> {
+    let context = rune_modules::default_context()?;
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn test_regex() -> rune::Result<()> {
+    let mut vm = build(rune::sources! {
+        entry => {
+            use regex::Regex;
+
+            pub fn main() {
+                let re = Regex::new("(?P<year>\\d{4})-(?P<month>\\d{2})")?;
+                let captures = re.captures("released 2023-03").unwrap();
+
+                Ok((
+                    re.is_match("no date"),
+                    captures.name("year"),
+                    captures.get(2),
+                    captures.get(0),
+                    re.replace_all("2023-03 and 2024-01", "$month/$year"),
+                    Regex::new(",\\s*")?.split("a, b,c"),
+                    Regex::new("(").is_err(),
+                ))
+            }
+        }
+    })?;
+
+    let output = vm.call(["main"], ())?;
+    let output = <Result<
+        (
+            bool,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            String,
+            Vec<String>,
+            bool,
+        ),
+        rune::Value,
+    >>::from_value(output)?;
+
+    let (matched, year, month, whole, replaced, split, error) =
+        output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert!(!matched);
+    assert_eq!(year.as_deref(), Some("2023"));
+    assert_eq!(month.as_deref(), Some("03"));
+    assert_eq!(whole.as_deref(), Some("2023-03"));
+    assert_eq!(replaced, "03/2023 and 01/2024");
+    assert_eq!(split, ["a", "b", "c"]);
+    assert!(error);
+    Ok(())
+}
+
+#[test]
+fn test_regex_macro() -> rune::Result<()> {
+    let mut vm = build(rune::sources! {
+        entry => {
+            pub fn main() {
+                let count = 0;
+
+                for word in ["a1", "b", "c22"] {
+                    if regex::regex!("\\d+").is_match(word) {
+                        count += 1;
+                    }
+                }
+
+                count
+            }
+        }
+    })?;
+
+    let output = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 2);
+
+    let result = build(rune::sources! {
+        entry => {
+            pub fn main() {
+                regex::regex!("(")
+            }
+        }
+    });
+
+    assert!(result.is_err());
+    Ok(())
+}
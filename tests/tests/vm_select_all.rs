@@ -0,0 +1,60 @@
+use rune_tests::*;
+
+#[test]
+fn test_select_all_loop() {
+    let out: (i64, i64) = rune! {
+        use std::future;
+
+        pub async fn main() {
+            let futures = [];
+
+            for n in 0..5 {
+                futures.push(async { n * 10 });
+            }
+
+            let count = 0;
+            let sum = 0;
+
+            while let Some((index, value)) = future::select_all(futures).await {
+                assert_eq!(index * 10, value);
+                count += 1;
+                sum += value;
+            }
+
+            (count, sum)
+        }
+    };
+    assert_eq!(out, (5, 100));
+}
+
+#[test]
+fn test_select_all_first_ready() {
+    let out: (i64, i64, bool) = rune! {
+        use std::future;
+        use std::sync::mpsc;
+
+        pub async fn main() {
+            let (a, a_rx) = mpsc::channel(1);
+            let (b, b_rx) = mpsc::channel(1);
+            b.try_send(2)?;
+
+            let futures = (a_rx.recv(), b_rx.recv());
+            let (index, value) = future::select_all(futures).await?;
+
+            a.try_send(1)?;
+            let first = futures.0.await?;
+            (index, value? + first, future::select_all(futures).await.is_none())
+        }
+    };
+    assert_eq!(out, (1, 3, true));
+}
+
+#[test]
+fn test_select_all_empty() {
+    let out: Option<(i64, i64)> = rune! {
+        pub async fn main() {
+            std::future::select_all([]).await
+        }
+    };
+    assert_eq!(out, None);
+}
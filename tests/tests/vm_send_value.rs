@@ -0,0 +1,117 @@
+use rune::runtime::{ConstValue, SendValue, VmErrorKind};
+use rune::{FromValue, ToValue};
+use rune_tests::*;
+
+#[test]
+fn test_send_value_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SendValue>();
+}
+
+#[test]
+fn test_send_value_roundtrip() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        struct Point { x, y }
+        enum Shape { Circle(radius), Empty }
+
+        pub fn make() {
+            let shared = [1, 2];
+            (#{ a: shared, b: shared }, Point { x: 1, y: 2 }, Shape::Circle(3.0), Shape::Empty, Ok(Some('c')), 1..2)
+        }
+
+        pub fn check(value) {
+            let (object, point, circle, empty, result, range) = value;
+            assert_eq!(object.a, [1, 2]);
+            assert_eq!(object.b, [1, 2]);
+            assert_eq!(point.x + point.y, 3);
+
+            match circle {
+                Shape::Circle(r) => assert_eq!(r, 3.0),
+                _ => panic!("expected circle"),
+            }
+
+            match empty {
+                Shape::Empty => (),
+                _ => panic!("expected empty"),
+            }
+
+            assert_eq!(result, Ok(Some('c')));
+            assert_eq!(range.start, Some(1));
+            assert_eq!(range.end, Some(2));
+        }
+    };
+
+    let value = SendValue::from_value(vm.call(["make"], ())?)?;
+    let value = std::thread::spawn(move || value).join().unwrap();
+    vm.call(["check"], (value,))?;
+    Ok(())
+}
+
+#[test]
+fn test_send_value_from_const_value() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn check(value) {
+            assert_eq!(value, [1, "two", Some(3)]);
+        }
+    };
+
+    let value = ConstValue::Vec(vec![
+        ConstValue::Integer(1),
+        ConstValue::String(String::from("two")),
+        ConstValue::Option(Some(Box::new(ConstValue::Integer(3)))),
+    ]);
+
+    let value = SendValue::from(value);
+    assert!(matches!(&value, SendValue::Vec(values) if values.len() == 3));
+    vm.call(["check"], (value.to_value()?,))?;
+    Ok(())
+}
+
+#[test]
+fn test_send_value_unsupported() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn function() {
+            [|| 42]
+        }
+
+        pub fn cycle() {
+            let v = [];
+            v.push(v);
+            v
+        }
+    };
+
+    let error = SendValue::from_value(vm.call(["function"], ())?).unwrap_err();
+    assert!(matches!(error.kind(), VmErrorKind::SendNotSupported { .. }));
+
+    let value = vm.call(["cycle"], ())?;
+    assert!(SendValue::from_value(value).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_send_execution_on_another_thread() -> rune::Result<()> {
+    let vm = rune_vm! {
+        pub async fn main(value) {
+            let (a, b) = value;
+            #{ sum: a + b, values: [a, b] }
+        }
+    };
+
+    let args = SendValue::Tuple(vec![SendValue::Integer(1), SendValue::Integer(2)].into());
+    let execution = vm.send_execute(["main"], (args,))?;
+    let future = execution.async_complete_into::<SendValue>();
+
+    let output = std::thread::spawn(move || futures_executor::block_on(future))
+        .join()
+        .unwrap()?;
+
+    let object = match output {
+        SendValue::Object(object) => object,
+        output => panic!("unexpected output: {:?}", output),
+    };
+
+    assert!(matches!(object.get("sum"), Some(SendValue::Integer(3))));
+    assert!(matches!(object.get("values"), Some(SendValue::Vec(values)) if values.len() == 2));
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use rune::runtime::{
+    FuelState, Function, Generator, GeneratorState, RuntimeContext, VmErrorKind, VmSnapshot,
+};
+use rune::{FromValue, Unit, Value, Vm};
+use std::sync::Arc;
+
+fn unit(mut sources: rune::Sources) -> rune::Result<(Arc<RuntimeContext>, Arc<Unit>)> {
+    let context = rune_modules::default_context()?;
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    Ok((Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+fn roundtrip(snapshot: &VmSnapshot) -> rune::Result<VmSnapshot> {
+    let bytes = bincode::serialize(snapshot)?;
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+#[test]
+fn test_snapshot_generator() -> rune::Result<()> {
+    let (runtime, unit) = unit(rune::sources! {
+        entry => {
+            struct Point { x, y }
+
+            fn produce() {
+                let points = [];
+                let label = Some("points");
+
+                let n = 0;
+
+                while n < 4 {
+                    points.push(Point { x: n, y: n * 2 });
+                    yield n;
+                    n += 1;
+                }
+
+                let sum = 0;
+
+                while let Some(p) = points.pop() {
+                    sum += p.x + p.y;
+                }
+
+                (label, sum)
+            }
+
+            pub fn main() {
+                produce
+            }
+        }
+    })?;
+
+    let mut vm = Vm::new(runtime.clone(), unit.clone());
+    let function = Function::from_value(vm.call(["main"], ())?)?;
+    let mut generator = function.call::<_, Generator<Vm>>(())?;
+
+    assert_eq!(generator.next()?.map(i64::from_value).transpose()?, Some(0));
+    assert_eq!(generator.next()?.map(i64::from_value).transpose()?, Some(1));
+
+    let snapshot = roundtrip(&generator.snapshot()?)?;
+    drop(generator);
+
+    let mut generator = snapshot
+        .restore(runtime, unit)?
+        .into_generator()?;
+
+    assert_eq!(generator.next()?.map(i64::from_value).transpose()?, Some(2));
+    assert_eq!(generator.next()?.map(i64::from_value).transpose()?, Some(3));
+
+    match generator.resume(Value::Unit)? {
+        GeneratorState::Complete(value) => {
+            let (label, sum) = <(Option<String>, i64)>::from_value(value)?;
+            assert_eq!(label.as_deref(), Some("points"));
+            assert_eq!(sum, 18);
+        }
+        GeneratorState::Yielded(..) => panic!("unexpected yield"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_exhausted() -> rune::Result<()> {
+    let (runtime, unit) = unit(rune::sources! {
+        entry => {
+            fn add(a, b) { a + b }
+
+            pub fn main() {
+                let n = 0;
+
+                while n < 100 {
+                    n = add(n, 1);
+                }
+
+                n
+            }
+        }
+    })?;
+
+    let mut vm = Vm::new(runtime.clone(), unit.clone());
+    let mut execution = vm.execute(["main"], ())?.into_owned();
+    let mut snapshots = 0;
+
+    let value = loop {
+        match execution.resume_with_fuel(25)? {
+            FuelState::Complete(value) => break value,
+            FuelState::Exhausted => {
+                let snapshot = roundtrip(&execution.snapshot()?)?;
+                execution = snapshot.restore(runtime.clone(), unit.clone())?;
+                snapshots += 1;
+            }
+            FuelState::Yielded(..) => panic!("unexpected yield"),
+        }
+    };
+
+    assert!(snapshots > 0);
+    assert_eq!(i64::from_value(value)?, 100);
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_errors() -> rune::Result<()> {
+    let (runtime, unit) = unit(rune::sources! {
+        entry => {
+            pub fn main() {
+                let f = |n| n + 1;
+                yield f(1);
+            }
+        }
+    })?;
+
+    let mut vm = Vm::new(runtime, unit);
+    let mut execution = vm.execute(["main"], ())?;
+    execution.resume()?;
+
+    let error = execution.snapshot().unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::SendNotSupported { .. }
+    ));
+
+    let (runtime, unit) = self::unit(rune::sources! {
+        entry => {
+            pub fn main() {
+                yield 1;
+                yield 2;
+            }
+        }
+    })?;
+
+    let (_, other) = self::unit(rune::sources! {
+        entry => {
+            pub fn main() {
+                yield 1;
+            }
+        }
+    })?;
+
+    let mut vm = Vm::new(runtime.clone(), unit);
+    let mut execution = vm.execute(["main"], ())?;
+    execution.resume()?;
+
+    let error = match execution.snapshot()?.restore(runtime, other) {
+        Ok(..) => panic!("expected restore to fail"),
+        Err(error) => error,
+    };
+
+    assert!(matches!(error.into_kind(), VmErrorKind::SnapshotMismatch));
+    Ok(())
+}
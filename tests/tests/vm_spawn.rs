@@ -0,0 +1,174 @@
+use rune::runtime::{Ambient, Executor, SpawnFuture, VmErrorKind, VmLimits};
+use rune::{Context, FromValue};
+use rune_tests::*;
+
+/// An executor which drives every spawned future on a thread of its own.
+struct Threads;
+
+impl Executor for Threads {
+    fn spawn(&self, future: SpawnFuture) {
+        std::thread::spawn(move || futures_executor::block_on(future));
+    }
+}
+
+/// An executor which drops everything spawned on it.
+struct Discard;
+
+impl Executor for Discard {
+    fn spawn(&self, _: SpawnFuture) {}
+
+    fn spawn_blocking(&self, _: rune::runtime::SpawnTask) {}
+}
+
+fn context(executor: Option<Box<dyn FnOnce(&mut Context)>>) -> rune::Result<Context> {
+    let mut context = rune_modules::default_context()?;
+
+    if let Some(executor) = executor {
+        executor(&mut context);
+    }
+
+    Ok(context)
+}
+
+fn threads() -> Option<Box<dyn FnOnce(&mut Context)>> {
+    Some(Box::new(|context| context.set_executor(Threads)))
+}
+
+#[test]
+fn test_spawn_threads() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context(threads())? =>
+        fn work(n) {
+            let out = 0;
+
+            for i in 0..n {
+                out += i;
+            }
+
+            out
+        }
+
+        pub async fn main() {
+            let tasks = [];
+
+            for n in 0..4 {
+                tasks.push(std::thread::spawn(|| work(n * 10)));
+            }
+
+            std::future::join(tasks).await
+        }
+    };
+
+    let output = futures_executor::block_on(vm.async_call(["main"], ()))?;
+    let output = Vec::<i64>::from_value(output)?;
+    assert_eq!(output, vec![0, 45, 190, 435]);
+    Ok(())
+}
+
+#[test]
+fn test_spawn_futures() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context(threads())? =>
+        async fn fetch(name) {
+            #{ name, length: name.len() }
+        }
+
+        pub async fn main() {
+            let names = ["a", "bb", "ccc"];
+            let tasks = [];
+
+            for name in names {
+                tasks.push(std::future::spawn(|| fetch(name)));
+            }
+
+            let out = 0;
+
+            for result in std::future::join(tasks).await {
+                out += result.length;
+            }
+
+            out
+        }
+    };
+
+    let output = futures_executor::block_on(vm.async_call(["main"], ()))?;
+    assert_eq!(i64::from_value(output)?, 6);
+    Ok(())
+}
+
+#[test]
+fn test_spawn_errors() -> rune::Result<()> {
+    let build = |context: Context| {
+        rune_vm_with! {
+            context =>
+            fn generator() {
+                yield 1;
+            }
+
+            pub async fn plain() {
+                std::future::spawn(|| 42).await
+            }
+
+            pub async fn not_send() {
+                let g = generator();
+                std::thread::spawn(|| g.next()).await
+            }
+
+            pub async fn panics() {
+                std::thread::spawn(|| panic!("boom")).await
+            }
+        }
+    };
+
+    let mut vm = build(context(None)?);
+    let error = futures_executor::block_on(vm.async_call(["plain"], ())).unwrap_err();
+    assert!(matches!(error.as_unwound().0, VmErrorKind::MissingExecutor));
+
+    let mut vm = build(context(threads())?);
+    let error = futures_executor::block_on(vm.async_call(["not_send"], ())).unwrap_err();
+    assert!(matches!(error.as_unwound().0, VmErrorKind::SendNotSupported { .. }));
+
+    let error = futures_executor::block_on(vm.async_call(["panics"], ())).unwrap_err();
+    assert!(error.as_panic().is_some());
+
+    let mut vm = build(context(Some(Box::new(|c| c.set_executor(Discard))))?);
+    let error = futures_executor::block_on(vm.async_call(["plain"], ())).unwrap_err();
+    assert!(matches!(error.as_unwound().0, VmErrorKind::TaskCancelled));
+    Ok(())
+}
+
+#[test]
+fn test_spawn_inherits_ambient_and_limits() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context(threads())? =>
+        fn recurse(n) {
+            recurse(n + 1)
+        }
+
+        pub async fn user() {
+            let a = std::thread::spawn(|| std::env::ambient("user"));
+            let b = std::future::spawn(|| std::env::ambient("user"));
+            (a.await, b.await)
+        }
+
+        pub async fn recursion() {
+            std::thread::spawn(|| recurse(0)).await
+        }
+    };
+
+    let mut ambient = Ambient::new();
+    ambient.insert("user", "jane")?;
+    vm.set_ambient(ambient);
+    vm.set_limits(VmLimits::new().with_max_call_depth(32));
+
+    let output = futures_executor::block_on(vm.async_call(["user"], ()))?;
+    let output = <(Option<String>, Option<String>)>::from_value(output)?;
+    assert_eq!(output, (Some("jane".into()), Some("jane".into())));
+
+    let error = futures_executor::block_on(vm.async_call(["recursion"], ())).unwrap_err();
+    assert!(matches!(
+        error.as_unwound().0,
+        VmErrorKind::CallDepthExceeded { max: 32 }
+    ));
+    Ok(())
+}
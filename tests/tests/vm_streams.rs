@@ -63,3 +63,30 @@ fn test_resume() {
     };
     assert_eq!(out, 6);
 }
+
+#[test]
+fn test_native_stream() {
+    use futures_util::stream;
+    use rune::Module;
+
+    let mut module = Module::new();
+    module
+        .stream_function(["count"], |limit: i64| stream::iter(1..=limit))
+        .unwrap();
+
+    let out = rune_n! {
+        module,
+        (),
+        i64 => pub async fn main() {
+            let stream = count(4);
+            let result = 0;
+
+            while let Some(value) = stream.next().await {
+                result += value;
+            }
+
+            result
+        }
+    };
+    assert_eq!(out, 10);
+}
@@ -0,0 +1,91 @@
+use rune::runtime::{Executor, SpawnFuture, VmErrorKind};
+use rune::{Context, FromValue};
+use rune_tests::*;
+
+/// An executor which relies on the default timers.
+struct Threads;
+
+impl Executor for Threads {
+    fn spawn(&self, future: SpawnFuture) {
+        std::thread::spawn(move || futures_executor::block_on(future));
+    }
+}
+
+fn context(executor: bool) -> rune::Result<Context> {
+    let mut context = rune_modules::default_context()?;
+
+    if executor {
+        context.set_executor(Threads);
+    }
+    Ok(context)
+}
+
+#[test]
+fn test_sleep() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context(true)? =>
+        use std::time::{sleep, Duration};
+
+        pub async fn main() {
+            let duration = Duration::from_millis(20);
+            sleep(duration).await;
+            duration.as_millis()
+        }
+    };
+
+    let start = std::time::Instant::now();
+    let output = futures_executor::block_on(vm.async_call(["main"], ()))?;
+    assert_eq!(u64::from_value(output)?, 20);
+    assert!(start.elapsed() >= std::time::Duration::from_millis(20));
+    Ok(())
+}
+
+#[test]
+fn test_timeout() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context(true)? =>
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        pub async fn main() {
+            let duration = Duration::from_millis(10);
+
+            let fast = async { 42 }.timeout(duration).await;
+
+            let (sender, receiver) = mpsc::channel(1);
+            let slow = receiver.recv();
+            let first = slow.timeout(duration).await;
+
+            sender.try_send(7)?;
+            let second = slow.timeout(duration).await;
+
+            (fast, first.is_err(), second)
+        }
+    };
+
+    let output = futures_executor::block_on(vm.async_call(["main"], ()))?;
+    let (fast, elapsed, second) =
+        <(Result<i64, rune::Value>, bool, Result<Option<i64>, rune::Value>)>::from_value(output)?;
+
+    assert_eq!(fast.ok(), Some(42));
+    assert!(elapsed);
+    assert_eq!(second.ok(), Some(Some(7)));
+    Ok(())
+}
+
+#[test]
+fn test_sleep_missing_executor() -> rune::Result<()> {
+    let mut vm = rune_vm_with! {
+        context(false)? =>
+        pub async fn main() {
+            std::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    };
+
+    let error = futures_executor::block_on(vm.async_call(["main"], ())).unwrap_err();
+    assert!(matches!(
+        error.as_unwound().0,
+        VmErrorKind::MissingExecutor
+    ));
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use rune::runtime::{VmError, VmTracer};
+use rune::{FromValue, Hash, Value, Vm};
+use rune_tests::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<String>>,
+}
+
+impl Recorder {
+    fn push(&self, event: String) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+impl VmTracer for Recorder {
+    fn on_call(&self, _: &Vm, hash: Hash, args: &[Value]) {
+        let args = args
+            .iter()
+            .map(|v| format!("{:?}", v))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        self.push(format!("call {} ({})", hash, args));
+    }
+
+    fn on_return(&self, _: &Vm, value: &Value) {
+        self.push(format!("return {:?}", value));
+    }
+
+    fn on_yield(&self, _: &Vm, value: &Value) {
+        self.push(format!("yield {:?}", value));
+    }
+
+    fn on_await(&self, _: &Vm) {
+        self.push(String::from("await"));
+    }
+
+    fn on_unwind(&self, _: &Vm, _: &VmError) {
+        self.push(String::from("unwind"));
+    }
+}
+
+fn traced(mut vm: Vm) -> (Vm, Arc<Recorder>) {
+    let recorder = Arc::new(Recorder::default());
+    vm.set_tracer(recorder.clone());
+    (vm, recorder)
+}
+
+fn events(recorder: &Recorder) -> Vec<String> {
+    recorder.events.lock().unwrap().clone()
+}
+
+#[test]
+fn test_trace_calls_and_returns() -> rune::Result<()> {
+    let (mut vm, recorder) = traced(rune_vm! {
+        fn double(n) { n * 2 }
+        pub fn main() { double(21) }
+    });
+
+    let output = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 42);
+
+    assert_eq!(
+        events(&recorder),
+        vec![
+            format!("call {} ()", Hash::type_hash(["main"])),
+            format!("call {} (21)", Hash::type_hash(["double"])),
+            String::from("return 42"),
+            String::from("return 42"),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_trace_generator() -> rune::Result<()> {
+    let (mut vm, recorder) = traced(rune_vm! {
+        fn gen() {
+            yield 1;
+            yield 2;
+        }
+
+        pub fn main() {
+            let sum = 0;
+
+            for n in gen() {
+                sum += n;
+            }
+
+            sum
+        }
+    });
+
+    let output = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 3);
+
+    let events = events(&recorder);
+    let yields = events.iter().filter(|e| e.starts_with("yield")).count();
+    assert_eq!(yields, 2);
+
+    let calls = events.iter().filter(|e| e.starts_with("call")).count();
+    let returns = events.iter().filter(|e| e.starts_with("return")).count();
+    assert_eq!(calls, 2);
+    assert_eq!(returns, 2);
+    Ok(())
+}
+
+#[test]
+fn test_trace_async() -> rune::Result<()> {
+    let (mut vm, recorder) = traced(rune_vm! {
+        async fn value() { 42 }
+        pub async fn main() { value().await }
+    });
+
+    let output = futures_executor::block_on(vm.async_call(["main"], ()))?;
+    assert_eq!(i64::from_value(output)?, 42);
+    assert!(events(&recorder).iter().any(|e| e == "await"));
+    Ok(())
+}
+
+#[test]
+fn test_trace_unwind() -> rune::Result<()> {
+    let (mut vm, recorder) = traced(rune_vm! {
+        fn fail() { panic("boom") }
+        pub fn main() { fail() }
+    });
+
+    assert!(vm.call(["main"], ()).is_err());
+    assert_eq!(events(&recorder).last().map(String::as_str), Some("unwind"));
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use rune::FromValue;
+use rune_tests::*;
+
+#[test]
+fn test_graphemes() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            let text = "nai\u{308}ve 👩‍🔬";
+            let reversed = String::new();
+
+            for g in unicode::graphemes(text).rev() {
+                reversed += g;
+            }
+
+            (
+                unicode::graphemes(text).count(),
+                unicode::graphemes(text).take(3).collect::<Vec>(),
+                unicode::grapheme_indices(text).collect::<Vec>(),
+                reversed,
+            )
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let (count, truncated, indices, reversed) =
+        <(usize, Vec<String>, Vec<(usize, String)>, String)>::from_value(output)?;
+
+    assert_eq!(count, 7);
+    assert_eq!(truncated, ["n", "a", "i\u{308}"]);
+    assert_eq!(indices[3], (5, String::from("v")));
+    assert_eq!(reversed, "👩‍🔬 evi\u{308}an");
+    Ok(())
+}
+
+#[test]
+fn test_words() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            let text = "Hello, wörld! It's 3.5";
+
+            (
+                unicode::words(text).collect::<Vec>(),
+                unicode::word_bounds("a, b").collect::<Vec>(),
+            )
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let (words, bounds) = <(Vec<String>, Vec<String>)>::from_value(output)?;
+
+    assert_eq!(words, ["Hello", "wörld", "It's", "3.5"]);
+    assert_eq!(bounds, ["a", ",", " ", "b"]);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use rune::FromValue;
+use rune_tests::*;
+
+#[test]
+fn test_parse_and_format() -> rune::Result<()> {
+    let mut vm = rune_vm! {
+        use uuid::Uuid;
+
+        pub fn main() {
+            let id = Uuid::parse("67E55044-10B1-426F-9247-BB680E5FE0C8")?;
+            let simple = Uuid::parse("67e5504410b1426f9247bb680e5fe0c8")?;
+
+            Ok((
+                id == simple,
+                id.to_string(),
+                id.simple(),
+                id.urn(),
+                format!("{}", id),
+                id.version(),
+                Uuid::nil().is_nil(),
+                Uuid::from_bytes(id.as_bytes())? == id,
+                Uuid::parse("not a uuid").is_err(),
+            ))
+        }
+    };
+
+    let output = vm.call(["main"], ())?;
+    let output = <Result<
+        (bool, String, String, String, String, usize, bool, bool, bool),
+        rune::Value,
+    >>::from_value(output)?;
+    let (eq, hyphenated, simple, urn, display, version, nil, roundtrip, error) =
+        output.map_err(|_| rune::Error::msg("script failed"))?;
+
+    assert!(eq);
+    assert_eq!(hyphenated, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(simple, "67e5504410b1426f9247bb680e5fe0c8");
+    assert_eq!(urn, "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(display, hyphenated);
+    assert_eq!(version, 4);
+    assert!(nil);
+    assert!(roundtrip);
+    assert!(error);
+    Ok(())
+}
+
+#[test]
+fn test_new_v4_and_hashing() -> rune::Result<()> {
+    let output: (bool, usize, usize, bool) = rune! {
+        use std::collections::HashSet;
+        use uuid::Uuid;
+
+        pub fn main() {
+            let a = Uuid::new_v4();
+            let b = Uuid::new_v4();
+
+            let set = HashSet::new();
+            set.insert(a);
+            set.insert(Uuid::parse(a.to_string()).unwrap());
+            set.insert(b);
+
+            (a != b, a.version(), set.len(), set.contains(a))
+        }
+    };
+    assert_eq!(output, (true, 4, 2, true));
+    Ok(())
+}